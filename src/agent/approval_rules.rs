@@ -0,0 +1,857 @@
+//! Structured tool approval rules.
+//!
+//! A rule names the tool calls it covers, by tool name, path glob, command
+//! regex, MCP server and argument values, and says whether they are allowed,
+//! always put to the user, or denied. Every condition a rule sets must hold.
+//! An allow rule must cover every path and every command segment of a call;
+//! ask and deny rules apply as soon as one of them matches, so
+//! `git status && git push --force` is not allowed by a `^git status` rule.
+//! The paths of a shell call are its working directory plus every command
+//! argument that can leave it, so `allow path=~/projects/**` does not allow
+//! `rm -rf ~` run from a project.
+//!
+//! Rules are checked before the prefix lists, YOLO mode, read auto-approval
+//! and session approvals. The matching rule with the highest priority
+//! decides; at equal priority deny beats ask, and ask beats allow. Access
+//! outside the workspace roots is still approved separately by the workspace
+//! gate.
+//!
+//! The settings editor uses a one-line text form:
+//!
+//! ```text
+//! allow tool=ShellExec command="^git (status|diff|log)\b"
+//! deny priority=10 tool=ShellExec command="^git push .*--force"
+//! allow tool=WriteFile path=~/projects/**
+//! ask server=github arg.repo=acme/*
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::tool_approval_policy::{web_fetch_identifier, ToolApprovalDecision, ToolApprovalPolicy};
+use crate::agent::tool_audit::note_approval;
+use crate::agent::workspace::OUTSIDE_WORKSPACE_PREFIX;
+use crate::models::ApprovalSource;
+use crate::services::{AppSettingsService, ServiceResult};
+
+/// Path globs do not let `*` cross a directory separator; `**` does.
+const PATH_MATCH: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// What a matching rule does to a tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEffect {
+    Allow,
+    /// Always ask, even in YOLO mode or after a session approval.
+    Ask,
+    Deny,
+}
+
+impl RuleEffect {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Ask => "ask",
+            Self::Deny => "deny",
+        }
+    }
+
+    /// Parse the form written by `as_str`, ignoring case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "allow" => Some(Self::Allow),
+            "ask" => Some(Self::Ask),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn decision(self) -> ToolApprovalDecision {
+        match self {
+            Self::Allow => ToolApprovalDecision::Allow,
+            Self::Ask => ToolApprovalDecision::AskUser,
+            Self::Deny => ToolApprovalDecision::Deny,
+        }
+    }
+
+    /// Tie-break between rules of equal priority.
+    const fn strictness(self) -> u8 {
+        match self {
+            Self::Allow => 0,
+            Self::Ask => 1,
+            Self::Deny => 2,
+        }
+    }
+}
+
+/// One structured approval rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    pub effect: RuleEffect,
+    /// Rules with a higher priority are decided first.
+    #[serde(default)]
+    pub priority: i32,
+    /// Exact tool name, such as `ShellExec` or an MCP tool's name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Prefix of the call's approval identifiers, as in the prefix lists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier_prefix: Option<String>,
+    /// Glob over the absolute paths the call touches; `~/` is the home
+    /// directory. For `ShellExec` these include the command's escaping path
+    /// arguments, and `$` expansions, which no glob matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_glob: Option<String>,
+    /// Regex over the segments of a shell command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_regex: Option<String>,
+    /// Name of the MCP server the call goes to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_server: Option<String>,
+    /// Globs over argument values by argument name. Values that are not
+    /// strings are matched in their JSON form.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub arguments: BTreeMap<String, String>,
+}
+
+impl ApprovalRule {
+    /// A rule with no conditions yet.
+    #[must_use]
+    pub const fn new(effect: RuleEffect) -> Self {
+        Self {
+            effect,
+            priority: 0,
+            tool: None,
+            identifier_prefix: None,
+            path_glob: None,
+            command_regex: None,
+            mcp_server: None,
+            arguments: BTreeMap::new(),
+        }
+    }
+
+    /// The rule equivalent to a prefix list entry.
+    #[must_use]
+    pub fn from_prefix(effect: RuleEffect, prefix: impl Into<String>) -> Self {
+        Self {
+            identifier_prefix: Some(prefix.into()),
+            ..Self::new(effect)
+        }
+    }
+
+    /// Whether this rule covers `call`.
+    #[must_use]
+    pub fn matches(&self, call: &ToolCall<'_>) -> bool {
+        let every = self.effect == RuleEffect::Allow;
+        self.tool.as_deref().is_none_or(|tool| tool == call.tool)
+            && self
+                .mcp_server
+                .as_deref()
+                .is_none_or(|server| call.mcp_server == Some(server))
+            && self.identifier_prefix.as_deref().is_none_or(|prefix| {
+                covers(every, &call.identifiers, |identifier| {
+                    identifier.starts_with(prefix)
+                })
+            })
+            && self.path_glob.as_deref().is_none_or(|glob| {
+                path_pattern(glob).is_ok_and(|pattern| {
+                    covers(every, &call.paths, |path| {
+                        pattern.matches_path_with(path, PATH_MATCH)
+                    })
+                })
+            })
+            && self
+                .command_regex
+                .as_deref()
+                .is_none_or(|regex| command_matches(regex, call.command, every))
+            && self
+                .arguments
+                .iter()
+                .all(|(name, glob)| argument_matches(call.arguments, name, glob))
+    }
+
+    /// Parse the one-line text form written by `Display`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the problem when `text` is not a valid rule.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut words = split_rule_words(text)?.into_iter();
+        let effect_word = words
+            .next()
+            .ok_or_else(|| "The rule is empty".to_string())?;
+        let effect = RuleEffect::parse(&effect_word).ok_or_else(|| {
+            format!("Unknown effect '{effect_word}': start with allow, ask or deny")
+        })?;
+
+        let mut rule = Self::new(effect);
+        for word in words {
+            let (key, value) = word
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{word}'"))?;
+            if value.is_empty() {
+                return Err(format!("'{key}' has no value"));
+            }
+            let value = value.to_string();
+            match key {
+                "priority" => {
+                    rule.priority = value
+                        .parse()
+                        .map_err(|_| format!("Invalid priority '{value}'"))?;
+                }
+                "tool" => rule.tool = Some(value),
+                "server" => rule.mcp_server = Some(value),
+                "prefix" => rule.identifier_prefix = Some(value),
+                "path" => rule.path_glob = Some(value),
+                "command" => rule.command_regex = Some(value),
+                _ => {
+                    let name = key
+                        .strip_prefix("arg.")
+                        .filter(|name| !name.is_empty())
+                        .ok_or_else(|| format!("Unknown condition '{key}'"))?;
+                    rule.arguments.insert(name.to_string(), value);
+                }
+            }
+        }
+
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    /// Check that the rule has a condition and that its patterns compile.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first problem found.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.conditions().is_empty() {
+            return Err("A rule needs at least one condition, such as tool=ShellExec".to_string());
+        }
+        if let Some(glob) = &self.path_glob {
+            path_pattern(glob).map_err(|error| format!("Invalid path glob '{glob}': {error}"))?;
+        }
+        if let Some(regex) = &self.command_regex {
+            Regex::new(regex).map_err(|error| format!("Invalid command regex: {error}"))?;
+        }
+        for (name, glob) in &self.arguments {
+            Pattern::new(glob)
+                .map_err(|error| format!("Invalid glob for argument '{name}': {error}"))?;
+        }
+        Ok(())
+    }
+
+    /// The rule's conditions as `(key, value)` pairs of the text form.
+    fn conditions(&self) -> Vec<(String, &str)> {
+        [
+            ("tool", &self.tool),
+            ("server", &self.mcp_server),
+            ("prefix", &self.identifier_prefix),
+            ("path", &self.path_glob),
+            ("command", &self.command_regex),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_deref().map(|value| (key.to_string(), value)))
+        .chain(
+            self.arguments
+                .iter()
+                .map(|(name, glob)| (format!("arg.{name}"), glob.as_str())),
+        )
+        .collect()
+    }
+}
+
+impl fmt::Display for ApprovalRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.effect.as_str())?;
+        if self.priority != 0 {
+            write!(f, " priority={}", self.priority)?;
+        }
+        for (key, value) in self.conditions() {
+            write!(f, " {key}={}", quote_rule_value(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// The rule that decides `call`, if any matches.
+#[must_use]
+pub fn deciding_rule<'r>(
+    rules: &'r [ApprovalRule],
+    call: &ToolCall<'_>,
+) -> Option<&'r ApprovalRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(call))
+        .max_by_key(|rule| (rule.priority, rule.effect.strictness()))
+}
+
+/// A tool call as the approval rules see it.
+#[derive(Debug, Clone)]
+pub struct ToolCall<'a> {
+    tool: &'a str,
+    arguments: Option<&'a Value>,
+    identifiers: Vec<String>,
+    paths: Vec<PathBuf>,
+    command: Option<&'a str>,
+    mcp_server: Option<&'a str>,
+}
+
+impl<'a> ToolCall<'a> {
+    /// A call to `tool`, whose approval identifier is the tool name.
+    #[must_use]
+    pub fn new(tool: &'a str) -> Self {
+        Self {
+            tool,
+            arguments: None,
+            identifiers: vec![tool.to_string()],
+            paths: Vec::new(),
+            command: None,
+            mcp_server: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_arguments(mut self, arguments: &'a Value) -> Self {
+        self.arguments = Some(arguments);
+        self
+    }
+
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_paths(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.paths.extend(paths);
+        self
+    }
+
+    /// The call runs the shell `command`; its approval identifiers become
+    /// the command's shell identifiers, as for the prefix lists.
+    #[must_use]
+    pub fn with_command(mut self, command: &'a str) -> Self {
+        let identifiers = ToolApprovalPolicy::extract_shell_identifiers(command);
+        self.identifiers = if identifiers.is_empty() {
+            vec![command.trim().to_string()]
+        } else {
+            identifiers
+        };
+        self.command = Some(command);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_mcp_server(mut self, server: &'a str) -> Self {
+        self.mcp_server = Some(server);
+        self
+    }
+
+    /// Replace the approval identifier matched by prefix conditions.
+    #[must_use]
+    pub fn with_identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.identifiers = vec![identifier.into()];
+        self
+    }
+}
+
+/// A sample call typed into the settings rule tester.
+///
+/// The form is `Tool [command | url | path | {json arguments}]`: the rest of
+/// the line is the command for `ShellExec`, the URL for `WebFetch`, a path
+/// for other tools, or the arguments when it is a JSON object. A tool named
+/// `server/tool` is an MCP tool, identified as in per-tool approval mode.
+#[derive(Debug, Clone)]
+pub struct SampleCall {
+    tool: String,
+    server: Option<String>,
+    identifier: String,
+    arguments: Value,
+    command: Option<String>,
+    path: Option<PathBuf>,
+}
+
+impl SampleCall {
+    /// Parse a sample call.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the tool name is missing or the JSON
+    /// arguments do not parse.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (name, rest) = text
+            .split_once(char::is_whitespace)
+            .map_or((text, ""), |(name, rest)| (name, rest.trim()));
+        if name.is_empty() {
+            return Err("Type a tool name, then a command, path or JSON arguments".to_string());
+        }
+        let (server, tool) = match name.split_once('/') {
+            Some((server, tool)) if !server.is_empty() && !tool.is_empty() => {
+                (Some(server.to_string()), tool)
+            }
+            _ => (None, name),
+        };
+
+        let mut sample = Self {
+            tool: tool.to_string(),
+            server,
+            identifier: name.to_string(),
+            arguments: Value::Object(serde_json::Map::new()),
+            command: None,
+            path: None,
+        };
+        if rest.starts_with('{') {
+            sample.arguments = serde_json::from_str(rest)
+                .map_err(|error| format!("Invalid JSON arguments: {error}"))?;
+            let text_argument = |key: &str| {
+                sample
+                    .arguments
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            };
+            sample.command = text_argument("command");
+            sample.path = text_argument("path")
+                .or_else(|| text_argument("file_path"))
+                .map(|path| expand_home(&path).into());
+        } else if !rest.is_empty() {
+            match tool {
+                "ShellExec" => {
+                    sample.arguments = serde_json::json!({ "command": rest });
+                    sample.command = Some(rest.to_string());
+                }
+                "WebFetch" => {
+                    sample.arguments = serde_json::json!({ "url": rest });
+                    if let Some(host) = url::Url::parse(rest)
+                        .ok()
+                        .and_then(|url| url.host_str().map(str::to_string))
+                    {
+                        sample.identifier = web_fetch_identifier(&host);
+                    }
+                }
+                _ => {
+                    sample.arguments = serde_json::json!({ "path": rest });
+                    sample.path = Some(expand_home(rest).into());
+                }
+            }
+        }
+        Ok(sample)
+    }
+
+    /// The sample as the rules see it.
+    #[must_use]
+    pub fn call(&self) -> ToolCall<'_> {
+        let mut call = ToolCall::new(&self.tool)
+            .with_arguments(&self.arguments)
+            .with_identifier(self.identifier.clone())
+            .with_paths(self.path.clone());
+        if let Some(command) = &self.command {
+            call = call.with_command(command);
+        }
+        if let Some(server) = &self.server {
+            call = call.with_mcp_server(server);
+        }
+        call
+    }
+}
+
+impl ToolApprovalPolicy {
+    /// Decide `call` by the structured rules alone.
+    ///
+    /// `None` means no rule matches, and the prefix lists and toggles decide.
+    #[must_use]
+    pub fn rule_decision(&self, call: &ToolCall<'_>) -> Option<ToolApprovalDecision> {
+        deciding_rule(&self.rules, call).map(|rule| rule.effect.decision())
+    }
+
+    /// Decide `call` by its deciding rule, or else by `fallback`, the tool's
+    /// own evaluation against the lists and toggles.
+    ///
+    /// What decided is noted for the audit log. Allowing while YOLO mode is
+    /// on is put down to YOLO mode, since it allows whatever the lists do
+    /// not deny.
+    pub fn decide(
+        &self,
+        call: &ToolCall<'_>,
+        fallback: impl FnOnce(&Self) -> ToolApprovalDecision,
+    ) -> ToolApprovalDecision {
+        if let Some(rule) = deciding_rule(&self.rules, call) {
+            let decision = rule.effect.decision();
+            note_approval(decision, ApprovalSource::Rule, Some(rule.to_string()));
+            return decision;
+        }
+
+        let decision = fallback(self);
+        let source = if decision == ToolApprovalDecision::Allow && self.yolo_mode {
+            ApprovalSource::Yolo
+        } else {
+            ApprovalSource::Policy
+        };
+        note_approval(decision, source, None);
+        decision
+    }
+
+    /// Turn the allowlist and denylist entries into equivalent rules.
+    ///
+    /// Outside-workspace entries stay in the lists, which is where the
+    /// workspace gate looks for them. Returns how many entries moved.
+    pub fn migrate_prefix_entries(&mut self) -> usize {
+        let mut moved = 0;
+        for (list, effect) in [
+            (&mut self.persistent_denylist, RuleEffect::Deny),
+            (&mut self.persistent_allowlist, RuleEffect::Allow),
+        ] {
+            let (outside, entries): (Vec<_>, Vec<_>) = std::mem::take(list)
+                .into_iter()
+                .partition(|entry| entry.starts_with(OUTSIDE_WORKSPACE_PREFIX));
+            *list = outside;
+            for entry in entries {
+                let rule = ApprovalRule::from_prefix(effect, entry);
+                if !self.rules.contains(&rule) {
+                    self.rules.push(rule);
+                }
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    /// Add `rule` and save to settings; duplicates are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting the updated policy fails.
+    pub async fn add_rule(
+        &mut self,
+        rule: ApprovalRule,
+        app_settings: &dyn AppSettingsService,
+    ) -> ServiceResult<()> {
+        if self.rules.contains(&rule) {
+            return Ok(());
+        }
+
+        self.rules.push(rule);
+        self.save_to_settings(app_settings).await
+    }
+
+    /// Remove `rule` and save to settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting the updated policy fails.
+    pub async fn remove_rule(
+        &mut self,
+        rule: &ApprovalRule,
+        app_settings: &dyn AppSettingsService,
+    ) -> ServiceResult<()> {
+        let original_len = self.rules.len();
+        self.rules.retain(|existing| existing != rule);
+        if self.rules.len() == original_len {
+            return Ok(());
+        }
+
+        self.save_to_settings(app_settings).await
+    }
+}
+
+/// Whether `matches` holds for every value (allow rules) or for any value
+/// (ask and deny rules). A call with no values is never covered.
+fn covers<T>(every: bool, values: &[T], matches: impl Fn(&T) -> bool) -> bool {
+    if every {
+        !values.is_empty() && values.iter().all(matches)
+    } else {
+        values.iter().any(matches)
+    }
+}
+
+fn command_matches(regex: &str, command: Option<&str>, every: bool) -> bool {
+    let (Some(command), Ok(regex)) = (command, Regex::new(regex)) else {
+        return false;
+    };
+    let segments = ToolApprovalPolicy::split_compound_command(command);
+    if every {
+        covers(true, &segments, |segment| regex.is_match(segment))
+    } else {
+        regex.is_match(command) || segments.iter().any(|segment| regex.is_match(segment))
+    }
+}
+
+fn argument_matches(arguments: Option<&Value>, name: &str, glob: &str) -> bool {
+    let Some(value) = arguments.and_then(|arguments| arguments.get(name)) else {
+        return false;
+    };
+    let text = value
+        .as_str()
+        .map_or_else(|| value.to_string(), str::to_string);
+    Pattern::new(glob).is_ok_and(|pattern| pattern.matches(&text))
+}
+
+fn path_pattern(glob: &str) -> Result<Pattern, glob::PatternError> {
+    Pattern::new(&expand_home(glob))
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).display().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// `value` as written in the text form, quoted when it is empty or holds
+/// whitespace or quotes.
+pub(crate) fn quote_rule_value(value: &str) -> String {
+    if value.is_empty() || value.contains(|ch: char| ch.is_whitespace() || ch == '"') {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("\"{escaped}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+/// Split the text form into words, honouring double quotes. Inside quotes
+/// `\"` and `\\` are escapes; other backslashes are kept for regexes.
+pub(crate) fn split_rule_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        if quoted {
+            match ch {
+                '"' => quoted = false,
+                '\\' => match chars.next() {
+                    Some(next @ ('"' | '\\')) => current.push(next),
+                    Some(next) => {
+                        current.push('\\');
+                        current.push(next);
+                    }
+                    None => current.push('\\'),
+                },
+                _ => current.push(ch),
+            }
+        } else if ch == '"' {
+            quoted = true;
+            in_word = true;
+        } else if ch.is_whitespace() {
+            if in_word {
+                words.push(std::mem::take(&mut current));
+                in_word = false;
+            }
+        } else {
+            current.push(ch);
+            in_word = true;
+        }
+    }
+
+    if quoted {
+        return Err("Unterminated quote".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(text: &str) -> ApprovalRule {
+        ApprovalRule::parse(text).expect("rule should parse")
+    }
+
+    fn policy_with(rules: &[&str]) -> ToolApprovalPolicy {
+        ToolApprovalPolicy {
+            rules: rules.iter().map(|text| rule(text)).collect(),
+            ..ToolApprovalPolicy::default()
+        }
+    }
+
+    #[test]
+    fn text_form_round_trips() {
+        let text = r#"deny priority=10 tool=ShellExec command="^git push .*--force" arg.note="say \"hi\"""#;
+        let parsed = rule(text);
+
+        assert_eq!(parsed.effect, RuleEffect::Deny);
+        assert_eq!(parsed.priority, 10);
+        assert_eq!(parsed.command_regex.as_deref(), Some("^git push .*--force"));
+        assert_eq!(parsed.arguments["note"], "say \"hi\"");
+        assert_eq!(parsed.to_string(), text);
+        assert_eq!(rule(&parsed.to_string()), parsed);
+        assert_eq!(
+            rule(r"allow command=^git\s+status").to_string(),
+            r"allow command=^git\s+status"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_rules() {
+        for (text, expected) in [
+            ("", "empty"),
+            ("permit tool=ShellExec", "Unknown effect"),
+            ("allow", "at least one condition"),
+            ("allow tool", "key=value"),
+            ("allow colour=red", "Unknown condition"),
+            ("deny command=(", "Invalid command regex"),
+            ("deny path=[", "Invalid path glob"),
+            ("deny tool=\"ShellExec", "Unterminated quote"),
+        ] {
+            let error = ApprovalRule::parse(text).expect_err(text);
+            assert!(error.contains(expected), "{text}: {error}");
+        }
+    }
+
+    #[test]
+    fn command_rules_allow_git_status_but_never_force_push() {
+        let policy = policy_with(&[
+            r"allow tool=ShellExec command=^git\b",
+            r#"deny tool=ShellExec command="^git push .*--force""#,
+        ]);
+        let decide =
+            |command| policy.rule_decision(&ToolCall::new("ShellExec").with_command(command));
+
+        assert_eq!(decide("git status"), Some(ToolApprovalDecision::Allow));
+        assert_eq!(
+            decide("git push origin main --force"),
+            Some(ToolApprovalDecision::Deny)
+        );
+        assert_eq!(
+            decide("git status && git push --force"),
+            Some(ToolApprovalDecision::Deny)
+        );
+        assert_eq!(decide("git status && rm -rf build"), None);
+    }
+
+    #[test]
+    fn path_globs_must_cover_every_path_to_allow() {
+        let policy = policy_with(&["allow tool=WriteFile path=/srv/projects/**"]);
+        let decide = |paths: &[&str]| {
+            policy.rule_decision(
+                &ToolCall::new("WriteFile").with_paths(paths.iter().map(PathBuf::from)),
+            )
+        };
+
+        assert_eq!(
+            decide(&["/srv/projects/app/src/main.rs"]),
+            Some(ToolApprovalDecision::Allow)
+        );
+        assert_eq!(decide(&["/srv/projects/a.rs", "/etc/hosts"]), None);
+        assert_eq!(decide(&["/srv/projects-old/a.rs"]), None);
+        assert_eq!(decide(&[]), None);
+    }
+
+    #[test]
+    fn home_is_expanded_in_path_globs() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let call = ToolCall::new("ReadFile").with_path(home.join("notes/todo.md"));
+
+        assert!(rule("allow path=~/notes/*.md").matches(&call));
+    }
+
+    #[test]
+    fn priority_wins_then_deny_beats_ask_beats_allow() {
+        let call = ToolCall::new("ShellExec").with_command("cargo test");
+
+        let tied = policy_with(&["allow tool=ShellExec", "ask prefix=cargo"]);
+        assert_eq!(
+            tied.rule_decision(&call),
+            Some(ToolApprovalDecision::AskUser)
+        );
+
+        let tied = policy_with(&["ask tool=ShellExec", "deny prefix=cargo"]);
+        assert_eq!(tied.rule_decision(&call), Some(ToolApprovalDecision::Deny));
+
+        let ranked = policy_with(&["deny tool=ShellExec", "allow priority=5 prefix=cargo"]);
+        assert_eq!(
+            ranked.rule_decision(&call),
+            Some(ToolApprovalDecision::Allow)
+        );
+    }
+
+    #[test]
+    fn mcp_server_and_argument_conditions_must_all_hold() {
+        let allow = rule("allow server=github arg.repo=acme/* arg.draft=true");
+        let arguments = serde_json::json!({ "repo": "acme/widgets", "draft": true });
+        let other = serde_json::json!({ "repo": "evil/widgets", "draft": true });
+
+        assert!(allow.matches(
+            &ToolCall::new("create_pr")
+                .with_arguments(&arguments)
+                .with_mcp_server("github")
+        ));
+        assert!(!allow.matches(
+            &ToolCall::new("create_pr")
+                .with_arguments(&other)
+                .with_mcp_server("github")
+        ));
+        assert!(!allow.matches(&ToolCall::new("create_pr").with_arguments(&arguments)));
+    }
+
+    #[test]
+    fn migrated_prefix_entries_decide_like_the_lists() {
+        let outside = format!("{OUTSIDE_WORKSPACE_PREFIX}/srv/data/");
+        let mut policy = ToolApprovalPolicy {
+            persistent_allowlist: vec!["git".to_string(), outside.clone()],
+            persistent_denylist: vec!["git push".to_string()],
+            ..ToolApprovalPolicy::default()
+        };
+        let commands = ["git status", "git push origin", "git log | rm x", "ls"];
+        let before: Vec<_> = commands
+            .iter()
+            .map(|command| policy.evaluate_compound_command(command))
+            .collect();
+
+        assert_eq!(policy.migrate_prefix_entries(), 2);
+        assert_eq!(policy.persistent_allowlist, vec![outside]);
+        assert!(policy.persistent_denylist.is_empty());
+        let after: Vec<_> = commands
+            .iter()
+            .map(|command| {
+                policy
+                    .rule_decision(&ToolCall::new("ShellExec").with_command(command))
+                    .unwrap_or_else(|| policy.evaluate_compound_command(command))
+            })
+            .collect();
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn sample_calls_fill_in_the_matching_fields() {
+        let shell = SampleCall::parse("ShellExec git push --force").expect("shell sample");
+        assert!(rule("deny command=--force").matches(&shell.call()));
+        assert!(rule("deny prefix=git").matches(&shell.call()));
+
+        let fetch = SampleCall::parse("WebFetch https://docs.rs/serde").expect("fetch sample");
+        assert!(rule("allow prefix=web_fetch:docs.rs/").matches(&fetch.call()));
+        assert!(rule("allow arg.url=https://docs.rs/*").matches(&fetch.call()));
+
+        let mcp =
+            SampleCall::parse(r#"github/create_pr {"repo": "acme/app"}"#).expect("mcp sample");
+        assert!(rule("allow server=github tool=create_pr arg.repo=acme/*").matches(&mcp.call()));
+
+        let write = SampleCall::parse("WriteFile /srv/app/a.rs").expect("path sample");
+        assert!(rule("allow tool=WriteFile path=/srv/app/*.rs").matches(&write.call()));
+
+        assert!(SampleCall::parse("  ").is_err());
+        assert!(SampleCall::parse("Search {not json").is_err());
+    }
+}
@@ -0,0 +1,543 @@
+//! File checkpoints taken before the built-in tools write to disk.
+//!
+//! `WriteFile` and `EditFile` snapshot a file the first time they touch it in
+//! an assistant turn: its bytes when it exists, or a note that it did not.
+//! Each turn gets its own folder under the app data directory, so the whole
+//! turn, or one file of it, can be put back the way it was before the turn
+//! ran. Turns are tied to the transcript by the `seq` their reply is stored
+//! at. Stale turns are pruned by age and by the total size of the store.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+use crate::services::{ServiceError, ServiceResult};
+
+/// Turns older than this are pruned.
+pub const CHECKPOINT_MAX_AGE_DAYS: i64 = 14;
+
+/// Once the store grows past this many bytes, the oldest turns are pruned.
+pub const CHECKPOINT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+fn io_error(action: &str, path: &Path, error: &std::io::Error) -> ServiceError {
+    ServiceError::Io(format!("Failed to {action} '{}': {error}", path.display()))
+}
+
+/// Prior state of one file; `blob` is `None` when the file did not exist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileSnapshot {
+    path: PathBuf,
+    blob: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TurnManifest {
+    turn_id: Uuid,
+    message_seq: usize,
+    created_at: DateTime<Utc>,
+    files: Vec<FileSnapshot>,
+}
+
+/// Files one turn changed, as listed for the chat view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnCheckpoint {
+    pub turn_id: Uuid,
+    /// `seq` of the assistant message the turn produced.
+    pub message_seq: usize,
+    pub created_at: DateTime<Utc>,
+    pub files: Vec<PathBuf>,
+}
+
+/// On-disk store of per-turn file snapshots.
+///
+/// Layout: `<root>/<conversation_id>/<turn_id>/manifest.json` plus one
+/// `<uuid>.snapshot` file per captured file. Blob names are never reused, so
+/// reverting one file cannot free a name another snapshot still points at.
+#[derive(Debug)]
+pub struct CheckpointStore {
+    root: PathBuf,
+    max_age: chrono::Duration,
+    max_bytes: u64,
+    /// Serializes manifest updates from concurrent tool calls.
+    lock: AsyncMutex<()>,
+}
+
+impl CheckpointStore {
+    /// Store rooted at `root` with the default pruning limits.
+    #[must_use]
+    pub fn new(root: PathBuf) -> Self {
+        Self::with_limits(
+            root,
+            chrono::Duration::days(CHECKPOINT_MAX_AGE_DAYS),
+            CHECKPOINT_MAX_BYTES,
+        )
+    }
+
+    /// Store rooted at `root` that prunes turns past `max_age` or `max_bytes`.
+    #[must_use]
+    pub fn with_limits(root: PathBuf, max_age: chrono::Duration, max_bytes: u64) -> Self {
+        Self {
+            root,
+            max_age,
+            max_bytes,
+            lock: AsyncMutex::new(()),
+        }
+    }
+
+    fn turn_dir(&self, conversation_id: Uuid, turn_id: Uuid) -> PathBuf {
+        self.root
+            .join(conversation_id.to_string())
+            .join(turn_id.to_string())
+    }
+
+    /// Record the current state of `path` for a turn, unless already recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file exists but cannot be read, or the
+    /// snapshot cannot be written.
+    pub async fn snapshot(
+        &self,
+        conversation_id: Uuid,
+        turn_id: Uuid,
+        message_seq: usize,
+        path: &Path,
+    ) -> ServiceResult<()> {
+        let _guard = self.lock.lock().await;
+        let dir = self.turn_dir(conversation_id, turn_id);
+        let mut manifest = read_manifest(&dir).await?.unwrap_or_else(|| TurnManifest {
+            turn_id,
+            message_seq,
+            created_at: Utc::now(),
+            files: Vec::new(),
+        });
+        if manifest.files.iter().any(|file| file.path == path) {
+            return Ok(());
+        }
+
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|error| io_error("create checkpoint directory", &dir, &error))?;
+        let blob = match tokio::fs::read(path).await {
+            Ok(bytes) => {
+                let name = format!("{}.snapshot", Uuid::new_v4());
+                let blob_path = dir.join(&name);
+                tokio::fs::write(&blob_path, bytes)
+                    .await
+                    .map_err(|error| io_error("write checkpoint", &blob_path, &error))?;
+                Some(name)
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+            Err(error) => return Err(io_error("read", path, &error)),
+        };
+        manifest.files.push(FileSnapshot {
+            path: path.to_path_buf(),
+            blob,
+        });
+        write_manifest(&dir, &manifest).await
+    }
+
+    /// Turns with checkpoints in a conversation, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the conversation's folder cannot be listed.
+    pub async fn list_turns(&self, conversation_id: Uuid) -> ServiceResult<Vec<TurnCheckpoint>> {
+        let dir = self.root.join(conversation_id.to_string());
+        let mut turns: Vec<TurnCheckpoint> = list_manifests(&dir)
+            .await?
+            .into_iter()
+            .map(|(_, manifest)| TurnCheckpoint {
+                turn_id: manifest.turn_id,
+                message_seq: manifest.message_seq,
+                created_at: manifest.created_at,
+                files: manifest.files.into_iter().map(|file| file.path).collect(),
+            })
+            .collect();
+        turns.sort_by_key(|turn| turn.created_at);
+        Ok(turns)
+    }
+
+    /// Put every file of a turn back and drop the turn's checkpoint.
+    ///
+    /// Returns the restored paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` for an unknown turn, or an error when a file cannot
+    /// be restored; files restored before the failure stay restored.
+    pub async fn revert_turn(
+        &self,
+        conversation_id: Uuid,
+        turn_id: Uuid,
+    ) -> ServiceResult<Vec<PathBuf>> {
+        let _guard = self.lock.lock().await;
+        let dir = self.turn_dir(conversation_id, turn_id);
+        let manifest = require_manifest(&dir).await?;
+        for file in &manifest.files {
+            restore(&dir, file).await?;
+        }
+        remove_dir(&dir).await?;
+        Ok(manifest.files.into_iter().map(|file| file.path).collect())
+    }
+
+    /// Put one file of a turn back and drop it from the turn's checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` when the turn has no snapshot of `path`, or an
+    /// error when the file cannot be restored.
+    pub async fn revert_file(
+        &self,
+        conversation_id: Uuid,
+        turn_id: Uuid,
+        path: &Path,
+    ) -> ServiceResult<()> {
+        let _guard = self.lock.lock().await;
+        let dir = self.turn_dir(conversation_id, turn_id);
+        let mut manifest = require_manifest(&dir).await?;
+        let Some(index) = manifest.files.iter().position(|file| file.path == path) else {
+            return Err(ServiceError::NotFound(format!(
+                "no checkpoint of '{}' in this turn",
+                path.display()
+            )));
+        };
+        let file = manifest.files.remove(index);
+        restore(&dir, &file).await?;
+        if let Some(blob) = &file.blob {
+            let _ = tokio::fs::remove_file(dir.join(blob)).await;
+        }
+
+        if manifest.files.is_empty() {
+            remove_dir(&dir).await
+        } else {
+            write_manifest(&dir, &manifest).await
+        }
+    }
+
+    /// Drop turns older than the age limit, then the oldest turns until the
+    /// store fits the size limit. Returns how many turns were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the store cannot be listed or a turn cannot be
+    /// removed.
+    pub async fn prune(&self) -> ServiceResult<usize> {
+        let _guard = self.lock.lock().await;
+        let mut turns: Vec<(DateTime<Utc>, u64, PathBuf)> = Vec::new();
+        for conversation_dir in list_dirs(&self.root).await? {
+            for (turn_dir, manifest) in list_manifests(&conversation_dir).await? {
+                let size = dir_size(&turn_dir).await;
+                turns.push((manifest.created_at, size, turn_dir));
+            }
+        }
+        // Newest first, so the running total keeps the most recent turns.
+        turns.sort_by_key(|(created_at, _, _)| std::cmp::Reverse(*created_at));
+
+        let cutoff = Utc::now() - self.max_age;
+        let mut kept_bytes = 0_u64;
+        let mut removed = 0;
+        for (created_at, size, turn_dir) in turns {
+            if created_at >= cutoff && kept_bytes + size <= self.max_bytes {
+                kept_bytes += size;
+                continue;
+            }
+            remove_dir(&turn_dir).await?;
+            if let Some(conversation_dir) = turn_dir.parent() {
+                // Only succeeds once the conversation has no turns left.
+                let _ = tokio::fs::remove_dir(conversation_dir).await;
+            }
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// The checkpoint of the turn currently being streamed.
+#[derive(Debug, Clone)]
+pub struct TurnCheckpoints {
+    store: Arc<CheckpointStore>,
+    conversation_id: Uuid,
+    turn_id: Uuid,
+    message_seq: usize,
+}
+
+impl TurnCheckpoints {
+    /// Start a new turn whose reply will be stored at `message_seq`.
+    #[must_use]
+    pub fn new(store: Arc<CheckpointStore>, conversation_id: Uuid, message_seq: usize) -> Self {
+        Self {
+            store,
+            conversation_id,
+            turn_id: Uuid::new_v4(),
+            message_seq,
+        }
+    }
+
+    /// Record `path` before a tool writes to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the snapshot cannot be taken.
+    pub async fn snapshot(&self, path: &Path) -> ServiceResult<()> {
+        self.store
+            .snapshot(self.conversation_id, self.turn_id, self.message_seq, path)
+            .await
+    }
+}
+
+async fn read_manifest(dir: &Path) -> ServiceResult<Option<TurnManifest>> {
+    let path = dir.join(MANIFEST_FILE);
+    match tokio::fs::read_to_string(&path).await {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|error| ServiceError::Serialization(error.to_string())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(io_error("read", &path, &error)),
+    }
+}
+
+async fn require_manifest(dir: &Path) -> ServiceResult<TurnManifest> {
+    read_manifest(dir)
+        .await?
+        .ok_or_else(|| ServiceError::NotFound("checkpoint no longer exists".to_string()))
+}
+
+async fn write_manifest(dir: &Path, manifest: &TurnManifest) -> ServiceResult<()> {
+    let path = dir.join(MANIFEST_FILE);
+    let serialized = serde_json::to_string_pretty(manifest)
+        .map_err(|error| ServiceError::Serialization(error.to_string()))?;
+    tokio::fs::write(&path, serialized)
+        .await
+        .map_err(|error| io_error("write", &path, &error))
+}
+
+async fn restore(dir: &Path, file: &FileSnapshot) -> ServiceResult<()> {
+    match &file.blob {
+        Some(blob) => {
+            let blob_path = dir.join(blob);
+            let bytes = tokio::fs::read(&blob_path)
+                .await
+                .map_err(|error| io_error("read checkpoint", &blob_path, &error))?;
+            if let Some(parent) = file.path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|error| io_error("create directory", parent, &error))?;
+            }
+            tokio::fs::write(&file.path, bytes)
+                .await
+                .map_err(|error| io_error("restore", &file.path, &error))
+        }
+        None => match tokio::fs::remove_file(&file.path).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(io_error("remove", &file.path, &error)),
+        },
+    }
+}
+
+async fn remove_dir(dir: &Path) -> ServiceResult<()> {
+    match tokio::fs::remove_dir_all(dir).await {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(io_error("remove checkpoint", dir, &error)),
+    }
+}
+
+/// Subdirectories of `dir`; a missing `dir` has none.
+async fn list_dirs(dir: &Path) -> ServiceResult<Vec<PathBuf>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(io_error("list", dir, &error)),
+    };
+    let mut dirs = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|error| io_error("list", dir, &error))?
+    {
+        if entry.file_type().await.is_ok_and(|kind| kind.is_dir()) {
+            dirs.push(entry.path());
+        }
+    }
+    Ok(dirs)
+}
+
+/// Turn folders under a conversation folder with readable manifests.
+async fn list_manifests(dir: &Path) -> ServiceResult<Vec<(PathBuf, TurnManifest)>> {
+    let mut manifests = Vec::new();
+    for turn_dir in list_dirs(dir).await? {
+        match read_manifest(&turn_dir).await {
+            Ok(Some(manifest)) => manifests.push((turn_dir, manifest)),
+            Ok(None) => {}
+            Err(error) => {
+                tracing::warn!(
+                    "Skipping unreadable checkpoint {}: {error}",
+                    turn_dir.display()
+                );
+            }
+        }
+    }
+    Ok(manifests)
+}
+
+/// Total size of the files directly inside a turn folder.
+async fn dir_size(dir: &Path) -> u64 {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return 0;
+    };
+    let mut total = 0;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if let Ok(metadata) = entry.metadata().await {
+            total += metadata.len();
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn turn(store: &Arc<CheckpointStore>, conversation_id: Uuid) -> TurnCheckpoints {
+        TurnCheckpoints::new(store.clone(), conversation_id, 1)
+    }
+
+    #[tokio::test]
+    async fn revert_turn_restores_edited_and_removes_created_files() {
+        let dir = tempdir().unwrap();
+        let store = Arc::new(CheckpointStore::new(dir.path().join("checkpoints")));
+        let conversation_id = Uuid::new_v4();
+        let edited = dir.path().join("edited.txt");
+        let created = dir.path().join("nested/created.txt");
+        std::fs::write(&edited, "before").unwrap();
+
+        let checkpoints = turn(&store, conversation_id);
+        checkpoints.snapshot(&edited).await.unwrap();
+        std::fs::write(&edited, "after").unwrap();
+        checkpoints.snapshot(&created).await.unwrap();
+        std::fs::create_dir_all(created.parent().unwrap()).unwrap();
+        std::fs::write(&created, "new").unwrap();
+        // A second write in the same turn keeps the pre-turn snapshot.
+        checkpoints.snapshot(&edited).await.unwrap();
+        std::fs::write(&edited, "after again").unwrap();
+
+        let turns = store.list_turns(conversation_id).await.unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].message_seq, 1);
+        assert_eq!(turns[0].files, vec![edited.clone(), created.clone()]);
+
+        let restored = store
+            .revert_turn(conversation_id, turns[0].turn_id)
+            .await
+            .unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "before");
+        assert!(!created.exists());
+        assert!(store.list_turns(conversation_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn revert_file_leaves_the_rest_of_the_turn() {
+        let dir = tempdir().unwrap();
+        let store = Arc::new(CheckpointStore::new(dir.path().join("checkpoints")));
+        let conversation_id = Uuid::new_v4();
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("second.txt");
+        std::fs::write(&first, "one").unwrap();
+        std::fs::write(&second, "two").unwrap();
+
+        let checkpoints = turn(&store, conversation_id);
+        checkpoints.snapshot(&first).await.unwrap();
+        checkpoints.snapshot(&second).await.unwrap();
+        std::fs::write(&first, "changed").unwrap();
+        std::fs::write(&second, "changed").unwrap();
+
+        let turn_id = store.list_turns(conversation_id).await.unwrap()[0].turn_id;
+        store
+            .revert_file(conversation_id, turn_id, &first)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "one");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "changed");
+        let turns = store.list_turns(conversation_id).await.unwrap();
+        assert_eq!(turns[0].files, vec![second]);
+        assert!(matches!(
+            store.revert_file(conversation_id, turn_id, &first).await,
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn snapshots_after_a_file_revert_keep_their_own_contents() {
+        let dir = tempdir().unwrap();
+        let store = Arc::new(CheckpointStore::new(dir.path().join("checkpoints")));
+        let conversation_id = Uuid::new_v4();
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("second.txt");
+        let third = dir.path().join("third.txt");
+        std::fs::write(&first, "one").unwrap();
+        std::fs::write(&second, "two").unwrap();
+        std::fs::write(&third, "three").unwrap();
+
+        let checkpoints = turn(&store, conversation_id);
+        checkpoints.snapshot(&first).await.unwrap();
+        checkpoints.snapshot(&second).await.unwrap();
+        let turn_id = store.list_turns(conversation_id).await.unwrap()[0].turn_id;
+        store
+            .revert_file(conversation_id, turn_id, &first)
+            .await
+            .unwrap();
+
+        checkpoints.snapshot(&third).await.unwrap();
+        checkpoints.snapshot(&first).await.unwrap();
+        for path in [&first, &second, &third] {
+            std::fs::write(path, "changed").unwrap();
+        }
+
+        store.revert_turn(conversation_id, turn_id).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "one");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "two");
+        assert_eq!(std::fs::read_to_string(&third).unwrap(), "three");
+    }
+
+    #[tokio::test]
+    async fn prune_drops_old_turns_and_keeps_newest_within_size_limit() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("checkpoints");
+        let file = dir.path().join("file.txt");
+        std::fs::write(&file, "x".repeat(64)).unwrap();
+
+        let unlimited = Arc::new(CheckpointStore::new(root.clone()));
+        let older = Uuid::new_v4();
+        let newer = Uuid::new_v4();
+        turn(&unlimited, older).snapshot(&file).await.unwrap();
+        turn(&unlimited, newer).snapshot(&file).await.unwrap();
+        assert_eq!(unlimited.prune().await.unwrap(), 0);
+
+        // Room for a single turn: the older one goes.
+        let single_turn_bytes =
+            dir_size(&list_dirs(&root.join(newer.to_string())).await.unwrap()[0]).await;
+        let small = CheckpointStore::with_limits(
+            root.clone(),
+            chrono::Duration::days(CHECKPOINT_MAX_AGE_DAYS),
+            single_turn_bytes,
+        );
+        assert_eq!(small.prune().await.unwrap(), 1);
+        assert!(small.list_turns(older).await.unwrap().is_empty());
+        assert_eq!(small.list_turns(newer).await.unwrap().len(), 1);
+
+        let expired = CheckpointStore::with_limits(root, chrono::Duration::zero(), u64::MAX);
+        assert_eq!(expired.prune().await.unwrap(), 1);
+        assert!(expired.list_turns(newer).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,491 @@
+//! Sub-agents started by the `Delegate` tool.
+//!
+//! A sub-agent is a separate agent run with a fresh context window: it sees
+//! only the task it was given, works with the tools it was allowed, and hands
+//! back the text of its last response as its report. It shares the parent's
+//! `McpToolContext`, so its tool calls go through the same policy, approval
+//! gate and turn checkpoint, and count against the turn's tool budget.
+//! Progress is sent to the chat view as `SubAgentUpdated`, and the tokens it
+//! spends are added to the usage ledger under the profile it ran on.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::llm::{AgentClientExt, LlmClient, McpToolContext, Message, StreamEvent};
+use crate::models::{ModelProfile, TokenUsage};
+use crate::presentation::view_command::{SubAgentState, SubAgentSummary, ViewCommand};
+use crate::services::chat_impl::usage::record_turn_usage;
+use crate::services::usage::UsageService;
+use crate::services::{ServiceError, ServiceResult};
+
+/// Model responses a sub-agent gets when the call does not say.
+pub const DEFAULT_MAX_TURNS: u32 = 10;
+
+/// Most model responses a sub-agent may be given.
+pub const MAX_TURNS_LIMIT: u32 = 50;
+
+const SUB_AGENT_PROMPT: &str = "You are a sub-agent working on a single task delegated by another agent. \
+Use your tools to complete the task, then reply with a concise final report. \
+The report is the only thing the other agent sees, so include the facts, file paths and conclusions it needs, \
+and say plainly what you could not find or finish.";
+
+/// Profiles and tools a conversation's `Delegate` calls can draw on.
+#[derive(Clone)]
+pub struct DelegationContext {
+    /// Profile of the conversation; sub-agents use it unless told otherwise.
+    pub profile: ModelProfile,
+    /// Every configured profile, for sub-agents that name one.
+    pub profiles: Vec<ModelProfile>,
+    /// MCP tools available to the conversation.
+    pub mcp_tools: Vec<crate::llm::tools::Tool>,
+    /// Ledger sub-agent usage is recorded in; `None` records nothing.
+    pub usage_service: Option<Arc<dyn UsageService>>,
+}
+
+impl DelegationContext {
+    /// The profile called `name` (case-insensitively) or with that id, or
+    /// the conversation's profile when `name` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound`, listing the profile names, when none matches.
+    pub fn resolve_profile(&self, name: Option<&str>) -> ServiceResult<&ModelProfile> {
+        let Some(name) = name else {
+            return Ok(&self.profile);
+        };
+        self.profiles
+            .iter()
+            .find(|profile| {
+                profile.name.eq_ignore_ascii_case(name) || profile.id.to_string() == name
+            })
+            .ok_or_else(|| {
+                let names: Vec<_> = self.profiles.iter().map(|p| p.name.as_str()).collect();
+                ServiceError::NotFound(format!(
+                    "No profile named '{name}'; available profiles: {}",
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+/// A task handed to a sub-agent.
+#[derive(Debug, Clone)]
+pub struct SubAgentTask {
+    /// Short label shown in the chat view.
+    pub description: String,
+    pub prompt: String,
+    /// Tools the sub-agent may use; all of the conversation's when `None`.
+    pub allowed_tools: Option<Vec<String>>,
+    pub max_turns: u32,
+}
+
+/// What a sub-agent hands back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubAgentReport {
+    pub report: String,
+    pub turns: u32,
+    pub tool_calls: u32,
+    /// Stopped at `max_turns`; the report may be incomplete.
+    pub hit_turn_limit: bool,
+}
+
+/// Run `task` on a sub-agent using `profile` and return its report.
+///
+/// # Errors
+///
+/// Returns an error when the sub-agent cannot be created or its stream
+/// fails.
+pub async fn run_sub_agent(
+    parent: &McpToolContext,
+    delegation: &DelegationContext,
+    profile: &ModelProfile,
+    task: SubAgentTask,
+) -> ServiceResult<SubAgentReport> {
+    let client = LlmClient::from_profile(profile).map_err(|error| {
+        ServiceError::Configuration(format!(
+            "Failed to create a client for profile '{}': {error}",
+            profile.name
+        ))
+    })?;
+    let agent = client
+        .create_sub_agent(
+            delegation.mcp_tools.clone(),
+            SUB_AGENT_PROMPT,
+            task.allowed_tools.as_deref(),
+        )
+        .await
+        .map_err(|error| {
+            ServiceError::Internal(format!("Failed to create the sub-agent: {error}"))
+        })?;
+    let context = McpToolContext {
+        delegation: None,
+        ..parent.clone()
+    };
+
+    let mut progress = Progress::new(&task, profile);
+    let mut publisher = Publisher::new(parent, progress.summary.clone());
+    publisher.publish(&progress.summary);
+
+    let turn_limit = CancellationToken::new();
+    let messages = [Message::user(task.prompt)];
+    let outcome = {
+        let run = client.run_agent_stream(&agent, &messages, context, |event| {
+            match progress.observe(event) {
+                Observed::Changed => publisher.publish(&progress.summary),
+                Observed::TurnLimit => turn_limit.cancel(),
+                Observed::Unchanged => {}
+            }
+        });
+        tokio::select! {
+            result = run => Some(result),
+            () = turn_limit.cancelled() => None,
+        }
+    };
+
+    record_turn_usage(
+        delegation.usage_service.as_ref(),
+        parent.conversation_id,
+        profile,
+        progress.usage,
+    )
+    .await;
+
+    let hit_turn_limit = outcome.is_none();
+    let failure = match outcome {
+        Some(Err(error)) => Some(error.to_string()),
+        Some(Ok(())) if !progress.completed => progress.error.take(),
+        _ => None,
+    };
+    if let Some(error) = failure {
+        progress.summary.state = SubAgentState::Failed;
+        publisher.finish(&progress.summary);
+        return Err(ServiceError::Internal(format!(
+            "Sub-agent failed after {} turn(s): {error}",
+            progress.summary.turns
+        )));
+    }
+
+    progress.summary.state = SubAgentState::Finished;
+    publisher.finish(&progress.summary);
+    Ok(SubAgentReport {
+        report: progress.report.trim().to_string(),
+        turns: progress.summary.turns,
+        tool_calls: progress.summary.tool_calls,
+        hit_turn_limit,
+    })
+}
+
+/// Sends a sub-agent's progress to the chat view, and marks it stopped if
+/// the run is dropped before it finishes.
+struct Publisher {
+    view_tx: mpsc::Sender<ViewCommand>,
+    conversation_id: Uuid,
+    stopped: SubAgentSummary,
+    finished: bool,
+}
+
+impl Publisher {
+    fn new(parent: &McpToolContext, summary: SubAgentSummary) -> Self {
+        Self {
+            view_tx: parent.view_tx.clone(),
+            conversation_id: parent.conversation_id,
+            stopped: SubAgentSummary {
+                state: SubAgentState::Stopped,
+                ..summary
+            },
+            finished: false,
+        }
+    }
+
+    fn publish(&self, summary: &SubAgentSummary) {
+        let _ = self.view_tx.try_send(ViewCommand::SubAgentUpdated {
+            conversation_id: self.conversation_id,
+            agent: summary.clone(),
+        });
+    }
+
+    fn finish(&mut self, summary: &SubAgentSummary) {
+        self.finished = true;
+        self.publish(summary);
+    }
+}
+
+impl Drop for Publisher {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.publish(&self.stopped);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Observed {
+    Unchanged,
+    /// The summary shown in the chat view changed.
+    Changed,
+    /// The next model response would exceed `max_turns`.
+    TurnLimit,
+}
+
+/// Running tally of a sub-agent's stream.
+///
+/// The stream has no explicit response boundaries, so a turn is counted
+/// whenever model output follows tool results.
+#[derive(Debug)]
+struct Progress {
+    summary: SubAgentSummary,
+    /// Text of the current model response.
+    report: String,
+    /// Tool results arrived since the current response started.
+    tools_ran: bool,
+    completed: bool,
+    error: Option<String>,
+    /// Tokens the provider reported for the run.
+    usage: Option<TokenUsage>,
+}
+
+impl Progress {
+    fn new(task: &SubAgentTask, profile: &ModelProfile) -> Self {
+        Self {
+            summary: SubAgentSummary {
+                id: Uuid::new_v4(),
+                description: task.description.clone(),
+                profile_name: profile.name.clone(),
+                state: SubAgentState::Running,
+                turns: 1,
+                max_turns: task.max_turns,
+                tool_calls: 0,
+                last_tool: None,
+            },
+            report: String::new(),
+            tools_ran: false,
+            completed: false,
+            error: None,
+            usage: None,
+        }
+    }
+
+    fn observe(&mut self, event: StreamEvent) -> Observed {
+        match event {
+            StreamEvent::TextDelta(text) => {
+                let step = self.model_output();
+                if step != Observed::TurnLimit {
+                    self.report.push_str(&text);
+                }
+                step
+            }
+            StreamEvent::ThinkingDelta(_) => self.model_output(),
+            StreamEvent::ToolCallStarted { tool_name, .. } => {
+                if self.model_output() == Observed::TurnLimit {
+                    return Observed::TurnLimit;
+                }
+                self.summary.tool_calls += 1;
+                self.summary.last_tool = Some(tool_name);
+                Observed::Changed
+            }
+            StreamEvent::ToolCallCompleted { .. } => {
+                self.tools_ran = true;
+                Observed::Unchanged
+            }
+            StreamEvent::Usage(usage) => {
+                *self.usage.get_or_insert_with(TokenUsage::default) += usage;
+                Observed::Unchanged
+            }
+            StreamEvent::Complete { .. } => {
+                self.completed = true;
+                Observed::Unchanged
+            }
+            StreamEvent::Error(message) => {
+                self.error = Some(message);
+                Observed::Unchanged
+            }
+            _ => Observed::Unchanged,
+        }
+    }
+
+    /// Model output arrived; after tool results it opens the next turn.
+    fn model_output(&mut self) -> Observed {
+        if !self.tools_ran {
+            return Observed::Unchanged;
+        }
+        if self.summary.turns >= self.summary.max_turns {
+            return Observed::TurnLimit;
+        }
+        self.tools_ran = false;
+        self.summary.turns += 1;
+        self.report.clear();
+        Observed::Changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(max_turns: u32) -> Progress {
+        let task = SubAgentTask {
+            description: "Survey callers".to_string(),
+            prompt: "Find every caller of parse_params".to_string(),
+            allowed_tools: None,
+            max_turns,
+        };
+        Progress::new(&task, &ModelProfile::default())
+    }
+
+    fn tool_round(progress: &mut Progress, tool_name: &str) -> Observed {
+        let started = progress.observe(StreamEvent::ToolCallStarted {
+            tool_name: tool_name.to_string(),
+            call_id: "call".to_string(),
+        });
+        progress.observe(StreamEvent::ToolCallCompleted {
+            tool_name: tool_name.to_string(),
+            call_id: "call".to_string(),
+            success: true,
+            result: None,
+            error: None,
+        });
+        started
+    }
+
+    #[test]
+    fn report_is_the_text_of_the_last_response() {
+        let mut progress = progress(5);
+
+        progress.observe(StreamEvent::TextDelta("Let me search.".to_string()));
+        assert_eq!(tool_round(&mut progress, "Search"), Observed::Changed);
+        assert_eq!(
+            progress.observe(StreamEvent::TextDelta("Found 3 ".to_string())),
+            Observed::Changed
+        );
+        progress.observe(StreamEvent::TextDelta("callers.".to_string()));
+
+        assert_eq!(progress.report, "Found 3 callers.");
+        assert_eq!(progress.summary.turns, 2);
+        assert_eq!(progress.summary.tool_calls, 1);
+        assert_eq!(progress.summary.last_tool.as_deref(), Some("Search"));
+    }
+
+    #[test]
+    fn parallel_tool_calls_stay_in_one_turn() {
+        let mut progress = progress(5);
+
+        progress.observe(StreamEvent::ToolCallStarted {
+            tool_name: "ReadFile".to_string(),
+            call_id: "a".to_string(),
+        });
+        tool_round(&mut progress, "Glob");
+
+        assert_eq!(progress.summary.turns, 1);
+        assert_eq!(progress.summary.tool_calls, 2);
+    }
+
+    #[test]
+    fn turn_limit_stops_before_the_next_response() {
+        let mut progress = progress(2);
+
+        tool_round(&mut progress, "Search");
+        progress.observe(StreamEvent::TextDelta("Partial findings".to_string()));
+        tool_round(&mut progress, "ReadFile");
+
+        assert_eq!(
+            progress.observe(StreamEvent::TextDelta("more".to_string())),
+            Observed::TurnLimit
+        );
+        assert_eq!(progress.summary.turns, 2);
+        assert_eq!(progress.report, "Partial findings");
+    }
+
+    #[tokio::test]
+    async fn usage_is_recorded_under_the_sub_agent_profile() {
+        struct Ledger(std::sync::Mutex<Vec<crate::models::UsageRecord>>);
+
+        #[async_trait::async_trait]
+        impl UsageService for Ledger {
+            async fn record(&self, record: crate::models::UsageRecord) -> ServiceResult<()> {
+                self.0.lock().unwrap().push(record);
+                Ok(())
+            }
+            async fn conversation_totals(
+                &self,
+                _conversation_id: Uuid,
+            ) -> ServiceResult<crate::models::UsageTotals> {
+                Ok(crate::models::UsageTotals::default())
+            }
+            async fn profile_totals(&self) -> ServiceResult<Vec<crate::models::ProfileUsage>> {
+                Ok(Vec::new())
+            }
+            async fn daily_totals(
+                &self,
+                _since: chrono::NaiveDate,
+            ) -> ServiceResult<Vec<crate::models::DailyUsage>> {
+                Ok(Vec::new())
+            }
+        }
+
+        let mut progress = progress(5);
+        let request = TokenUsage {
+            input_tokens: 400,
+            output_tokens: 80,
+            ..TokenUsage::default()
+        };
+        progress.observe(StreamEvent::Usage(request));
+        progress.observe(StreamEvent::Usage(request));
+        assert_eq!(
+            progress.usage.map(|usage| usage.input_tokens),
+            Some(800),
+            "every reported request counts"
+        );
+
+        let ledger = Arc::new(Ledger(std::sync::Mutex::new(Vec::new())));
+        let sub_agent_profile = ModelProfile {
+            name: "Deep Research".to_string(),
+            ..ModelProfile::default()
+        };
+        let conversation_id = Uuid::new_v4();
+        record_turn_usage(
+            Some(&(ledger.clone() as Arc<dyn UsageService>)),
+            conversation_id,
+            &sub_agent_profile,
+            progress.usage,
+        )
+        .await;
+
+        let records = ledger.0.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].profile_id, sub_agent_profile.id);
+        assert_eq!(records[0].conversation_id, Some(conversation_id));
+        assert_eq!(records[0].usage.output_tokens, 160);
+    }
+
+    #[test]
+    fn resolve_profile_matches_names_case_insensitively() {
+        let conversation_profile = ModelProfile::default();
+        let other = ModelProfile {
+            name: "Fast Research".to_string(),
+            ..ModelProfile::default()
+        };
+        let delegation = DelegationContext {
+            profile: conversation_profile.clone(),
+            profiles: vec![conversation_profile.clone(), other.clone()],
+            mcp_tools: Vec::new(),
+            usage_service: None,
+        };
+
+        assert_eq!(
+            delegation.resolve_profile(None).unwrap().id,
+            conversation_profile.id
+        );
+        assert_eq!(
+            delegation
+                .resolve_profile(Some("fast research"))
+                .unwrap()
+                .id,
+            other.id
+        );
+        let error = delegation.resolve_profile(Some("missing")).unwrap_err();
+        assert!(error.to_string().contains("Fast Research"));
+    }
+}
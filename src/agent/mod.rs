@@ -4,7 +4,16 @@
 //! with our application-specific configuration and MCP toolsets.
 //!
 //! # Architecture
+//! - `approval_rules.rs`: Structured allow/ask/deny rules for tool approval
+//! - `checkpoints.rs`: Per-turn file snapshots behind undo of agent edits
+//! - `delegation.rs`: Sub-agents started by the `Delegate` tool
+//! - `policy_overrides.rs`: Profile and conversation layers over the approval policy
+//! - `processes.rs`: Background shell processes started by `ShellExec`
 //! - `runtime.rs`: Global tokio runtime that persists for application lifetime
+//! - `shell_sessions.rs`: Persistent per-conversation shells for `ShellExec`
+//! - `tool_audit.rs`: Audit log recording of every tool call
+//! - `tool_budget.rs`: Per-turn limits on tool calls and their runtime
+//! - `workspace.rs`: Workspace roots confining the built-in file and shell tools
 //! - `mod.rs` (this file): `PersonalAgent` wrapper and global singleton
 //!
 //! # Global Runtime Pattern
@@ -26,10 +35,20 @@
 //! }
 //! ```
 
+pub mod approval_rules;
+pub mod checkpoints;
+pub mod delegation;
+pub mod policy_overrides;
+pub mod processes;
 pub mod runtime;
+pub mod shell_sessions;
 pub mod tool_approval_policy;
+pub mod tool_audit;
+pub mod tool_budget;
 pub mod tools;
+pub mod workspace;
 
+pub use approval_rules::{ApprovalRule, RuleEffect, ToolCall};
 pub use runtime::{agent_runtime, run_in_agent_runtime, spawn_in_agent_runtime};
 pub use tool_approval_policy::{McpApprovalMode, ToolApprovalDecision, ToolApprovalPolicy};
 
@@ -0,0 +1,497 @@
+//! Profile and conversation layers over the tool approval policy.
+//!
+//! The global `ToolApprovalPolicy` is the bottom layer. A profile may override
+//! parts of it for every conversation on that profile, and a conversation may
+//! override the result again. A layer replaces only the fields it sets; a list
+//! it sets replaces the list below it instead of adding to it. Structured
+//! rules, web fetch domains and session approvals are not layered.
+//!
+//! The settings editor uses a one-line text form per layer:
+//!
+//! ```text
+//! yolo=on
+//! reads=off mcp=per_server allow="git status,cargo test" deny=rm
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::approval_rules::{quote_rule_value, split_rule_words};
+use super::tool_approval_policy::{McpApprovalMode, ToolApprovalPolicy};
+use crate::services::{AppSettingsService, ServiceError, ServiceResult};
+
+/// Settings key for persisted profile and conversation overrides.
+pub const POLICY_OVERRIDES_SETTINGS_KEY: &str = "tool_approval.overrides";
+
+/// Which layer of the policy stack an override belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideLayer {
+    Profile,
+    Conversation,
+}
+
+impl OverrideLayer {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Profile => "Profile",
+            Self::Conversation => "Conversation",
+        }
+    }
+}
+
+/// The policy fields one layer overrides; `None` inherits from below.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yolo_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_approve_reads: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mcp_approval_mode: Option<McpApprovalMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_allowlist: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_denylist: Option<Vec<String>>,
+}
+
+const fn switch_word(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+const fn mcp_mode_word(mode: McpApprovalMode) -> &'static str {
+    match mode {
+        McpApprovalMode::PerTool => "per_tool",
+        McpApprovalMode::PerServer => "per_server",
+    }
+}
+
+fn parse_switch(key: &str, value: &str) -> Result<bool, String> {
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(format!("'{key}' must be on or off, got '{value}'")),
+    }
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl PolicyOverride {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.yolo_mode.is_none()
+            && self.auto_approve_reads.is_none()
+            && self.mcp_approval_mode.is_none()
+            && self.persistent_allowlist.is_none()
+            && self.persistent_denylist.is_none()
+    }
+
+    /// Replace the fields this layer sets in `policy`.
+    pub fn apply_to(&self, policy: &mut ToolApprovalPolicy) {
+        if let Some(yolo_mode) = self.yolo_mode {
+            policy.yolo_mode = yolo_mode;
+        }
+        if let Some(auto_approve_reads) = self.auto_approve_reads {
+            policy.auto_approve_reads = auto_approve_reads;
+        }
+        if let Some(mode) = self.mcp_approval_mode {
+            policy.mcp_approval_mode = mode;
+        }
+        if let Some(allowlist) = &self.persistent_allowlist {
+            policy.persistent_allowlist.clone_from(allowlist);
+        }
+        if let Some(denylist) = &self.persistent_denylist {
+            policy.persistent_denylist.clone_from(denylist);
+        }
+    }
+
+    /// Short descriptions of the overridden fields, for status surfaces.
+    #[must_use]
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels = Vec::new();
+        if let Some(yolo_mode) = self.yolo_mode {
+            labels.push(format!("YOLO {}", switch_word(yolo_mode)));
+        }
+        if let Some(reads) = self.auto_approve_reads {
+            labels.push(format!("read auto-approval {}", switch_word(reads)));
+        }
+        if let Some(mode) = self.mcp_approval_mode {
+            labels.push(format!("MCP {}", mcp_mode_word(mode).replace('_', " ")));
+        }
+        if let Some(allowlist) = &self.persistent_allowlist {
+            labels.push(format!("allowlist of {}", allowlist.len()));
+        }
+        if let Some(denylist) = &self.persistent_denylist {
+            labels.push(format!("denylist of {}", denylist.len()));
+        }
+        labels
+    }
+
+    /// Parse the one-line text form written by `Display`. Blank text is an
+    /// empty override, and `allow=""` overrides the allowlist with no entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first problem found.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        for word in split_rule_words(text)? {
+            let (key, value) = word
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{word}'"))?;
+            match key {
+                "yolo" => parsed.yolo_mode = Some(parse_switch(key, value)?),
+                "reads" => parsed.auto_approve_reads = Some(parse_switch(key, value)?),
+                "mcp" => {
+                    parsed.mcp_approval_mode = Some(match value {
+                        "per_tool" => McpApprovalMode::PerTool,
+                        "per_server" => McpApprovalMode::PerServer,
+                        _ => {
+                            return Err(format!(
+                                "'mcp' must be per_tool or per_server, got '{value}'"
+                            ))
+                        }
+                    });
+                }
+                "allow" => parsed.persistent_allowlist = Some(parse_list(value)),
+                "deny" => parsed.persistent_denylist = Some(parse_list(value)),
+                _ => {
+                    return Err(format!(
+                        "Unknown setting '{key}': use yolo, reads, mcp, allow or deny"
+                    ))
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for PolicyOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = Vec::new();
+        if let Some(yolo_mode) = self.yolo_mode {
+            words.push(format!("yolo={}", switch_word(yolo_mode)));
+        }
+        if let Some(reads) = self.auto_approve_reads {
+            words.push(format!("reads={}", switch_word(reads)));
+        }
+        if let Some(mode) = self.mcp_approval_mode {
+            words.push(format!("mcp={}", mcp_mode_word(mode)));
+        }
+        if let Some(allowlist) = &self.persistent_allowlist {
+            words.push(format!("allow={}", quote_rule_value(&allowlist.join(","))));
+        }
+        if let Some(denylist) = &self.persistent_denylist {
+            words.push(format!("deny={}", quote_rule_value(&denylist.join(","))));
+        }
+        f.write_str(&words.join(" "))
+    }
+}
+
+/// Every profile and conversation override, persisted as one app setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyOverrides {
+    profiles: HashMap<Uuid, PolicyOverride>,
+    conversations: HashMap<Uuid, PolicyOverride>,
+}
+
+impl PolicyOverrides {
+    /// Load overrides from app settings, defaulting to none on malformed data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when reading from the settings service fails.
+    pub async fn load_from_settings(app_settings: &dyn AppSettingsService) -> ServiceResult<Self> {
+        let stored = app_settings
+            .get_setting(POLICY_OVERRIDES_SETTINGS_KEY)
+            .await?;
+        Ok(stored
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default())
+    }
+
+    /// Persist overrides to app settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when serialization or the settings write fails.
+    pub async fn save_to_settings(
+        &self,
+        app_settings: &dyn AppSettingsService,
+    ) -> ServiceResult<()> {
+        let serialized = serde_json::to_string(self)
+            .map_err(|error| ServiceError::Serialization(error.to_string()))?;
+        app_settings
+            .set_setting(POLICY_OVERRIDES_SETTINGS_KEY, serialized)
+            .await
+    }
+
+    /// Drop the persisted override of a deleted conversation, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when reading or writing the settings fails.
+    pub async fn remove_conversation(
+        app_settings: &dyn AppSettingsService,
+        conversation_id: Uuid,
+    ) -> ServiceResult<()> {
+        let mut overrides = Self::load_from_settings(app_settings).await?;
+        if overrides.conversations.remove(&conversation_id).is_none() {
+            return Ok(());
+        }
+        overrides.save_to_settings(app_settings).await
+    }
+
+    const fn layer(&self, layer: OverrideLayer) -> &HashMap<Uuid, PolicyOverride> {
+        match layer {
+            OverrideLayer::Profile => &self.profiles,
+            OverrideLayer::Conversation => &self.conversations,
+        }
+    }
+
+    /// The override `layer` holds for `id`, if any.
+    #[must_use]
+    pub fn get(&self, layer: OverrideLayer, id: Uuid) -> Option<&PolicyOverride> {
+        self.layer(layer).get(&id)
+    }
+
+    /// Store `policy_override` for `id`; an empty override removes the entry.
+    pub fn set(&mut self, layer: OverrideLayer, id: Uuid, policy_override: PolicyOverride) {
+        let entries = match layer {
+            OverrideLayer::Profile => &mut self.profiles,
+            OverrideLayer::Conversation => &mut self.conversations,
+        };
+        if policy_override.is_empty() {
+            entries.remove(&id);
+        } else {
+            entries.insert(id, policy_override);
+        }
+    }
+
+    /// The policy a conversation on `profile_id` runs under: `global` with
+    /// the profile's and then the conversation's overrides applied.
+    #[must_use]
+    pub fn effective(
+        &self,
+        global: &ToolApprovalPolicy,
+        profile_id: Uuid,
+        conversation_id: Uuid,
+    ) -> ToolApprovalPolicy {
+        let mut policy = global.clone();
+        for (layer, id) in [
+            (OverrideLayer::Profile, profile_id),
+            (OverrideLayer::Conversation, conversation_id),
+        ] {
+            if let Some(policy_override) = self.get(layer, id) {
+                policy_override.apply_to(&mut policy);
+            }
+        }
+        policy
+    }
+
+    /// Whether YOLO mode is on for a conversation when the global setting is
+    /// `global`; for views that track only that toggle.
+    #[must_use]
+    pub fn yolo_mode(
+        &self,
+        global: bool,
+        profile_id: Option<Uuid>,
+        conversation_id: Option<Uuid>,
+    ) -> bool {
+        [
+            (OverrideLayer::Conversation, conversation_id),
+            (OverrideLayer::Profile, profile_id),
+        ]
+        .into_iter()
+        .find_map(|(layer, id)| self.get(layer, id?)?.yolo_mode)
+        .unwrap_or(global)
+    }
+
+    /// Add `identifiers` to the allowlist of the innermost layer that replaces
+    /// the global allowlist for a conversation. Returns `false` when no layer
+    /// does, so the global allowlist is the one in force.
+    pub fn allow_in_overriding_layer(
+        &mut self,
+        profile_id: Option<Uuid>,
+        conversation_id: Uuid,
+        identifiers: &[String],
+    ) -> bool {
+        let overriding =
+            |policy_override: &&mut PolicyOverride| policy_override.persistent_allowlist.is_some();
+        let Some(allowlist) = self
+            .conversations
+            .get_mut(&conversation_id)
+            .filter(overriding)
+            .or_else(|| {
+                profile_id
+                    .and_then(|id| self.profiles.get_mut(&id))
+                    .filter(overriding)
+            })
+            .and_then(|policy_override| policy_override.persistent_allowlist.as_mut())
+        else {
+            return false;
+        };
+        for identifier in identifiers {
+            if !allowlist.contains(identifier) {
+                allowlist.push(identifier.clone());
+            }
+        }
+        true
+    }
+
+    /// Labels of the overrides in force for a conversation, bottom layer
+    /// first, e.g. `Conversation: YOLO on`.
+    #[must_use]
+    pub fn active_labels(
+        &self,
+        profile_id: Option<Uuid>,
+        conversation_id: Option<Uuid>,
+    ) -> Vec<String> {
+        [
+            (OverrideLayer::Profile, profile_id),
+            (OverrideLayer::Conversation, conversation_id),
+        ]
+        .into_iter()
+        .filter_map(|(layer, id)| Some((layer, self.get(layer, id?)?)))
+        .flat_map(|(layer, policy_override)| {
+            policy_override
+                .labels()
+                .into_iter()
+                .map(move |label| format!("{}: {label}", layer.label()))
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_form_round_trips() {
+        for text in [
+            "yolo=on",
+            "reads=off mcp=per_server",
+            "allow=\"git status,cargo test\" deny=rm",
+            "allow=\"\"",
+        ] {
+            let parsed = PolicyOverride::parse(text).expect(text);
+            assert_eq!(parsed.to_string(), text);
+        }
+        assert!(PolicyOverride::parse("  ").unwrap().is_empty());
+        assert!(PolicyOverride::parse("yolo=yes")
+            .unwrap_err()
+            .contains("on or off"));
+        assert!(PolicyOverride::parse("rules=none")
+            .unwrap_err()
+            .contains("Unknown setting"));
+    }
+
+    #[test]
+    fn conversation_layer_wins_over_profile_layer() {
+        let profile_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        let global = ToolApprovalPolicy {
+            auto_approve_reads: true,
+            persistent_allowlist: vec!["git".to_string()],
+            ..ToolApprovalPolicy::default()
+        };
+        let mut overrides = PolicyOverrides::default();
+        overrides.set(
+            OverrideLayer::Profile,
+            profile_id,
+            PolicyOverride::parse("yolo=on allow=\"\"").unwrap(),
+        );
+        overrides.set(
+            OverrideLayer::Conversation,
+            conversation_id,
+            PolicyOverride::parse("yolo=off").unwrap(),
+        );
+
+        let effective = overrides.effective(&global, profile_id, conversation_id);
+        assert!(!effective.yolo_mode);
+        assert!(effective.auto_approve_reads);
+        assert!(effective.persistent_allowlist.is_empty());
+
+        let elsewhere = overrides.effective(&global, profile_id, Uuid::new_v4());
+        assert!(elsewhere.yolo_mode);
+        assert!(!overrides.yolo_mode(true, Some(profile_id), Some(conversation_id)));
+        assert!(overrides.yolo_mode(false, Some(profile_id), None));
+        assert!(overrides.yolo_mode(true, None, None));
+        assert_eq!(
+            overrides.effective(&global, Uuid::new_v4(), Uuid::new_v4()),
+            global
+        );
+
+        assert_eq!(
+            overrides.active_labels(Some(profile_id), Some(conversation_id)),
+            vec![
+                "Profile: YOLO on".to_string(),
+                "Profile: allowlist of 0".to_string(),
+                "Conversation: YOLO off".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn approvals_land_in_the_innermost_overriding_allowlist() {
+        let profile_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        let mut overrides = PolicyOverrides::default();
+        overrides.set(
+            OverrideLayer::Profile,
+            profile_id,
+            PolicyOverride::parse("allow=git").unwrap(),
+        );
+        overrides.set(
+            OverrideLayer::Conversation,
+            conversation_id,
+            PolicyOverride::parse("yolo=off").unwrap(),
+        );
+
+        let identifiers = ["cargo test".to_string(), "git".to_string()];
+        assert!(overrides.allow_in_overriding_layer(
+            Some(profile_id),
+            conversation_id,
+            &identifiers
+        ));
+        assert_eq!(
+            overrides
+                .get(OverrideLayer::Profile, profile_id)
+                .and_then(|o| o.persistent_allowlist.clone()),
+            Some(vec!["git".to_string(), "cargo test".to_string()])
+        );
+        assert!(!overrides.allow_in_overriding_layer(None, conversation_id, &identifiers));
+    }
+
+    #[test]
+    fn empty_overrides_are_removed() {
+        let id = Uuid::new_v4();
+        let mut overrides = PolicyOverrides::default();
+        overrides.set(
+            OverrideLayer::Conversation,
+            id,
+            PolicyOverride::parse("yolo=on").unwrap(),
+        );
+        overrides.set(OverrideLayer::Conversation, id, PolicyOverride::default());
+
+        assert_eq!(overrides, PolicyOverrides::default());
+    }
+}
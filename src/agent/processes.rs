@@ -0,0 +1,709 @@
+//! Background shell processes started by `ShellExec`.
+//!
+//! A process started with `background: true` keeps running after the tool
+//! call returns. Its output is buffered here until the agent reads it, and it
+//! can be written to and killed by the process tools of the same
+//! conversation. Every change is published to the chat view as
+//! `BackgroundProcessesUpdated`.
+//!
+//! The registry is process-wide so that stopping a conversation's stream and
+//! quitting the app can both reach it.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex as StdMutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::sync::{mpsc, Mutex as AsyncMutex, Notify};
+use uuid::Uuid;
+
+use crate::presentation::view_command::{
+    BackgroundProcessState, BackgroundProcessSummary, ViewCommand,
+};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Running processes a conversation may have at once.
+pub const MAX_RUNNING_PER_CONVERSATION: usize = 8;
+
+/// Unread bytes kept per stream; older output is dropped past this.
+pub const MAX_UNREAD_BYTES: usize = 256 * 1024;
+
+/// Finished processes kept per conversation so their last output can be read.
+const MAX_FINISHED_PER_CONVERSATION: usize = 8;
+
+/// How often the watcher checks whether a process has exited.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Output gathered since the last read, and where the process stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// Bytes dropped because they were not read before the buffer filled.
+    pub dropped_bytes: usize,
+    pub state: BackgroundProcessState,
+}
+
+#[derive(Debug, Default)]
+struct StreamBuffer {
+    unread: VecDeque<u8>,
+    dropped: usize,
+}
+
+impl StreamBuffer {
+    fn push(&mut self, bytes: &[u8]) {
+        self.unread.extend(bytes);
+        let excess = self.unread.len().saturating_sub(MAX_UNREAD_BYTES);
+        if excess > 0 {
+            self.unread.drain(..excess);
+            self.dropped += excess;
+        }
+    }
+
+    fn take(&mut self) -> (String, usize) {
+        let bytes: Vec<u8> = self.unread.drain(..).collect();
+        let dropped = std::mem::take(&mut self.dropped);
+        (String::from_utf8_lossy(&bytes).into_owned(), dropped)
+    }
+}
+
+#[derive(Debug)]
+struct ProcessIo {
+    stdout: StreamBuffer,
+    stderr: StreamBuffer,
+    state: BackgroundProcessState,
+}
+
+#[derive(Debug)]
+struct BackgroundProcess {
+    id: String,
+    conversation_id: Uuid,
+    command: String,
+    /// `None` once the process has been reaped.
+    child: StdMutex<Option<tokio::process::Child>>,
+    stdin: AsyncMutex<Option<tokio::process::ChildStdin>>,
+    io: StdMutex<ProcessIo>,
+    /// Signalled when output arrives or the process ends.
+    changed: Notify,
+    view_tx: mpsc::Sender<ViewCommand>,
+}
+
+impl BackgroundProcess {
+    fn io(&self) -> MutexGuard<'_, ProcessIo> {
+        self.io.lock().expect("process io poisoned")
+    }
+
+    fn state(&self) -> BackgroundProcessState {
+        self.io().state
+    }
+
+    /// Running with no unread output.
+    fn is_idle(&self) -> bool {
+        let io = self.io();
+        io.stdout.unread.is_empty()
+            && io.stderr.unread.is_empty()
+            && io.state == BackgroundProcessState::Running
+    }
+
+    fn take_output(&self) -> ProcessOutput {
+        let mut io = self.io();
+        let (stdout, stdout_dropped) = io.stdout.take();
+        let (stderr, stderr_dropped) = io.stderr.take();
+        ProcessOutput {
+            stdout,
+            stderr,
+            dropped_bytes: stdout_dropped + stderr_dropped,
+            state: io.state,
+        }
+    }
+
+    fn summary(&self) -> BackgroundProcessSummary {
+        BackgroundProcessSummary {
+            id: self.id.clone(),
+            command: self.command.clone(),
+            state: self.state(),
+        }
+    }
+}
+
+/// Registry of the background processes of every conversation.
+#[derive(Debug, Default)]
+pub struct BackgroundProcesses {
+    processes: StdMutex<Vec<Arc<BackgroundProcess>>>,
+    next_id: AtomicUsize,
+}
+
+static GLOBAL_PROCESSES: LazyLock<Arc<BackgroundProcesses>> =
+    LazyLock::new(|| Arc::new(BackgroundProcesses::default()));
+
+impl BackgroundProcesses {
+    /// Return the process-wide registry.
+    #[must_use]
+    pub fn global() -> Arc<Self> {
+        GLOBAL_PROCESSES.clone()
+    }
+
+    /// Start `command` in the background for a conversation.
+    ///
+    /// `command` is the prepared shell invocation; its standard streams are
+    /// replaced with pipes. `command_line` is what the chat view shows.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Validation` when the conversation already runs
+    /// `MAX_RUNNING_PER_CONVERSATION` processes, or `Io` when the process
+    /// cannot be started.
+    pub fn spawn(
+        self: &Arc<Self>,
+        conversation_id: Uuid,
+        command_line: &str,
+        directory: &Path,
+        mut command: tokio::process::Command,
+        view_tx: mpsc::Sender<ViewCommand>,
+    ) -> ServiceResult<BackgroundProcessSummary> {
+        if self.running_count(conversation_id) >= MAX_RUNNING_PER_CONVERSATION {
+            return Err(ServiceError::Validation(format!(
+                "At most {MAX_RUNNING_PER_CONVERSATION} background processes can run per conversation; kill one first"
+            )));
+        }
+
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        // Its own group lets a kill reach whatever the shell started too.
+        #[cfg(unix)]
+        command.process_group(0);
+        let mut child = command.spawn().map_err(|error| {
+            ServiceError::Io(format!(
+                "Failed to start '{command_line}' in '{}': {error}",
+                directory.display()
+            ))
+        })?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let stdin = child.stdin.take();
+
+        let id = format!("proc-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let process = Arc::new(BackgroundProcess {
+            id,
+            conversation_id,
+            command: command_line.to_string(),
+            child: StdMutex::new(Some(child)),
+            stdin: AsyncMutex::new(stdin),
+            io: StdMutex::new(ProcessIo {
+                stdout: StreamBuffer::default(),
+                stderr: StreamBuffer::default(),
+                state: BackgroundProcessState::Running,
+            }),
+            changed: Notify::new(),
+            view_tx,
+        });
+
+        let readers = [
+            stdout.map(|pipe| spawn_reader(process.clone(), pipe, false)),
+            stderr.map(|pipe| spawn_reader(process.clone(), pipe, true)),
+        ];
+        tokio::spawn(watch_exit(self.clone(), process.clone(), readers));
+
+        insert_and_prune(&mut self.entries(), process.clone());
+        self.publish(conversation_id);
+        Ok(process.summary())
+    }
+
+    /// Processes of a conversation, oldest first.
+    #[must_use]
+    pub fn list(&self, conversation_id: Uuid) -> Vec<BackgroundProcessSummary> {
+        self.entries()
+            .iter()
+            .filter(|process| process.conversation_id == conversation_id)
+            .map(|process| process.summary())
+            .collect()
+    }
+
+    /// Take the output a process produced since the last read.
+    ///
+    /// When there is none yet and the process is still running, waits up to
+    /// `wait` for output or exit first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` when the conversation has no process `id`.
+    pub async fn read_output(
+        &self,
+        conversation_id: Uuid,
+        id: &str,
+        wait: Duration,
+    ) -> ServiceResult<ProcessOutput> {
+        let process = self.find(conversation_id, id)?;
+        let deadline = tokio::time::Instant::now() + wait;
+        while process.is_idle() {
+            if tokio::time::timeout_at(deadline, process.changed.notified())
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+        Ok(process.take_output())
+    }
+
+    /// Write `input` to a running process's stdin, closing it after when
+    /// `close` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` for an unknown process, `Validation` when it has
+    /// exited or its stdin is closed, or `Io` when the write fails.
+    pub async fn write_input(
+        &self,
+        conversation_id: Uuid,
+        id: &str,
+        input: &str,
+        close: bool,
+    ) -> ServiceResult<()> {
+        let process = self.find(conversation_id, id)?;
+        if process.state() != BackgroundProcessState::Running {
+            return Err(ServiceError::Validation(format!(
+                "Process {id} is no longer running"
+            )));
+        }
+
+        let mut stdin = process.stdin.lock().await;
+        let pipe = stdin.as_mut().ok_or_else(|| {
+            ServiceError::Validation(format!("Stdin of process {id} is already closed"))
+        })?;
+        let write = async {
+            pipe.write_all(input.as_bytes()).await?;
+            pipe.flush().await
+        };
+        write.await.map_err(|error| {
+            ServiceError::Io(format!("Failed to write to process {id}: {error}"))
+        })?;
+        if close {
+            *stdin = None;
+        }
+        drop(stdin);
+        Ok(())
+    }
+
+    /// Kill a process of a conversation.
+    ///
+    /// Returns `false` when it had already exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` when the conversation has no process `id`.
+    pub fn kill(&self, conversation_id: Uuid, id: &str) -> ServiceResult<bool> {
+        let process = self.find(conversation_id, id)?;
+        let killed = kill_process(&process);
+        if killed {
+            self.publish(conversation_id);
+        }
+        Ok(killed)
+    }
+
+    /// Kill every running process of a conversation; returns how many.
+    pub fn kill_conversation(&self, conversation_id: Uuid) -> usize {
+        let processes: Vec<_> = self
+            .entries()
+            .iter()
+            .filter(|process| process.conversation_id == conversation_id)
+            .cloned()
+            .collect();
+        let killed = processes
+            .iter()
+            .filter(|process| kill_process(process))
+            .count();
+        if killed > 0 {
+            self.publish(conversation_id);
+        }
+        killed
+    }
+
+    /// Kill every running process; used when the app quits.
+    pub fn kill_all(&self) {
+        let processes = self.entries().clone();
+        for process in &processes {
+            kill_process(process);
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Vec<Arc<BackgroundProcess>>> {
+        self.processes.lock().expect("process registry poisoned")
+    }
+
+    fn find(&self, conversation_id: Uuid, id: &str) -> ServiceResult<Arc<BackgroundProcess>> {
+        self.entries()
+            .iter()
+            .find(|process| process.conversation_id == conversation_id && process.id == id)
+            .cloned()
+            .ok_or_else(|| ServiceError::NotFound(format!("No background process '{id}'")))
+    }
+
+    fn running_count(&self, conversation_id: Uuid) -> usize {
+        self.entries()
+            .iter()
+            .filter(|process| {
+                process.conversation_id == conversation_id
+                    && process.state() == BackgroundProcessState::Running
+            })
+            .count()
+    }
+
+    /// Send the conversation's process list to the chat view.
+    fn publish(&self, conversation_id: Uuid) {
+        let view_tx = self
+            .entries()
+            .iter()
+            .rev()
+            .find(|process| process.conversation_id == conversation_id)
+            .map(|process| process.view_tx.clone());
+        if let Some(view_tx) = view_tx {
+            let _ = view_tx.try_send(ViewCommand::BackgroundProcessesUpdated {
+                conversation_id,
+                processes: self.list(conversation_id),
+            });
+        }
+    }
+}
+
+/// Kill the process and everything it started, and mark it killed.
+///
+/// On unix the whole process group gets SIGKILL; on Windows the process
+/// tree is ended with `taskkill`. Returns `false` when it was no longer
+/// running.
+fn kill_process(process: &BackgroundProcess) -> bool {
+    let mut io = process.io();
+    if io.state != BackgroundProcessState::Running {
+        return false;
+    }
+    if let Some(child) = process
+        .child
+        .lock()
+        .expect("process child poisoned")
+        .as_mut()
+    {
+        if let Some(pid) = child.id() {
+            kill_descendants(pid);
+        }
+        if let Err(error) = child.start_kill() {
+            tracing::warn!(
+                "failed to kill background process {}: {}",
+                process.id,
+                error
+            );
+        }
+    }
+    io.state = BackgroundProcessState::Killed;
+    drop(io);
+    process.changed.notify_one();
+    true
+}
+
+/// SIGKILL the process group `spawn` created for the process `pid`.
+#[cfg(unix)]
+#[allow(
+    unsafe_code,
+    reason = "killpg has no safe std wrapper; it only sends a signal to a \
+              group this registry created."
+)]
+fn kill_descendants(pid: u32) {
+    let Ok(group) = libc::pid_t::try_from(pid) else {
+        return;
+    };
+    // SAFETY: `killpg` takes no pointers; a group that is already gone only
+    // makes it return ESRCH.
+    unsafe {
+        libc::killpg(group, libc::SIGKILL);
+    }
+}
+
+/// End the process tree rooted at `pid`; must run before the root is killed
+/// so `taskkill` can still find its children.
+#[cfg(windows)]
+fn kill_descendants(pid: u32) {
+    let status = std::process::Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    if let Err(error) = status {
+        tracing::warn!("failed to run taskkill for process {pid}: {error}");
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+const fn kill_descendants(_pid: u32) {}
+
+/// Add `process`, then drop the oldest finished processes of its
+/// conversation past the limit.
+fn insert_and_prune(processes: &mut Vec<Arc<BackgroundProcess>>, process: Arc<BackgroundProcess>) {
+    let conversation_id = process.conversation_id;
+    processes.push(process);
+    let finished = processes
+        .iter()
+        .filter(|process| {
+            process.conversation_id == conversation_id
+                && process.state() != BackgroundProcessState::Running
+        })
+        .count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_PER_CONVERSATION);
+    processes.retain(|process| {
+        let prune = excess > 0
+            && process.conversation_id == conversation_id
+            && process.state() != BackgroundProcessState::Running;
+        if prune {
+            excess -= 1;
+        }
+        !prune
+    });
+}
+
+fn spawn_reader<R>(
+    process: Arc<BackgroundProcess>,
+    mut pipe: R,
+    is_stderr: bool,
+) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buffer = [0_u8; 8192];
+        loop {
+            match pipe.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(read) => {
+                    let mut io = process.io();
+                    let stream = if is_stderr {
+                        &mut io.stderr
+                    } else {
+                        &mut io.stdout
+                    };
+                    stream.push(&buffer[..read]);
+                    drop(io);
+                    process.changed.notify_one();
+                }
+                Err(error) => {
+                    tracing::debug!("failed to read output of process {}: {}", process.id, error);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Reap the process once it exits, then publish its final state.
+async fn watch_exit(
+    registry: Arc<BackgroundProcesses>,
+    process: Arc<BackgroundProcess>,
+    readers: [Option<tokio::task::JoinHandle<()>>; 2],
+) {
+    let status = loop {
+        let polled = process
+            .child
+            .lock()
+            .expect("process child poisoned")
+            .as_mut()
+            .map(tokio::process::Child::try_wait);
+        match polled {
+            Some(Ok(Some(status))) => break Some(status),
+            Some(Ok(None)) => tokio::time::sleep(EXIT_POLL_INTERVAL).await,
+            Some(Err(error)) => {
+                tracing::warn!("failed to wait for process {}: {}", process.id, error);
+                break None;
+            }
+            None => break None,
+        }
+    };
+    process.child.lock().expect("process child poisoned").take();
+
+    // Let the readers drain what the process wrote before it exited.
+    for reader in readers.into_iter().flatten() {
+        let _ = tokio::time::timeout(Duration::from_secs(1), reader).await;
+    }
+    {
+        let mut io = process.io();
+        if io.state == BackgroundProcessState::Running {
+            io.state = BackgroundProcessState::Exited {
+                code: status.and_then(|status| status.code()),
+            };
+        }
+    }
+    *process.stdin.lock().await = None;
+    process.changed.notify_one();
+    registry.publish(process.conversation_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new("bash");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    fn spawn(
+        registry: &Arc<BackgroundProcesses>,
+        conversation_id: Uuid,
+        script: &str,
+    ) -> (BackgroundProcessSummary, mpsc::Receiver<ViewCommand>) {
+        let (view_tx, view_rx) = mpsc::channel(16);
+        let summary = registry
+            .spawn(
+                conversation_id,
+                script,
+                &std::env::temp_dir(),
+                shell(script),
+                view_tx,
+            )
+            .expect("process should start");
+        (summary, view_rx)
+    }
+
+    async fn read_until_finished(
+        registry: &BackgroundProcesses,
+        conversation_id: Uuid,
+        id: &str,
+    ) -> (String, BackgroundProcessState) {
+        let mut stdout = String::new();
+        for _ in 0..50 {
+            let output = registry
+                .read_output(conversation_id, id, Duration::from_millis(200))
+                .await
+                .expect("process should exist");
+            stdout.push_str(&output.stdout);
+            if output.state != BackgroundProcessState::Running {
+                return (stdout, output.state);
+            }
+        }
+        panic!("process {id} did not finish");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn output_is_read_incrementally_until_exit() {
+        let registry = Arc::new(BackgroundProcesses::default());
+        let conversation_id = Uuid::new_v4();
+        let (summary, mut view_rx) =
+            spawn(&registry, conversation_id, "echo one; sleep 0.3; echo two");
+
+        let first = registry
+            .read_output(conversation_id, &summary.id, Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(first.stdout, "one\n");
+        assert_eq!(first.state, BackgroundProcessState::Running);
+
+        let (rest, state) = read_until_finished(&registry, conversation_id, &summary.id).await;
+        assert_eq!(rest, "two\n");
+        assert_eq!(state, BackgroundProcessState::Exited { code: Some(0) });
+
+        let Some(ViewCommand::BackgroundProcessesUpdated { processes, .. }) = view_rx.recv().await
+        else {
+            panic!("expected a process list update");
+        };
+        assert_eq!(processes[0].command, "echo one; sleep 0.3; echo two");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdin_is_forwarded_and_closed() {
+        let registry = Arc::new(BackgroundProcesses::default());
+        let conversation_id = Uuid::new_v4();
+        let (summary, _view_rx) = spawn(&registry, conversation_id, "cat");
+
+        registry
+            .write_input(conversation_id, &summary.id, "hello\n", true)
+            .await
+            .expect("write should succeed");
+
+        let (stdout, state) = read_until_finished(&registry, conversation_id, &summary.id).await;
+        assert_eq!(stdout, "hello\n");
+        assert_eq!(state, BackgroundProcessState::Exited { code: Some(0) });
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cancelling_a_conversation_kills_only_its_processes() {
+        let registry = Arc::new(BackgroundProcesses::default());
+        let conversation_id = Uuid::new_v4();
+        let other_conversation = Uuid::new_v4();
+        let (summary, _view_rx) = spawn(&registry, conversation_id, "sleep 30");
+        let (other, _other_rx) = spawn(&registry, other_conversation, "sleep 30");
+
+        assert_eq!(registry.kill_conversation(conversation_id), 1);
+        assert_eq!(
+            registry.list(conversation_id)[0].state,
+            BackgroundProcessState::Killed
+        );
+        assert_eq!(
+            registry.list(other_conversation)[0].state,
+            BackgroundProcessState::Running
+        );
+        assert!(
+            registry
+                .read_output(other_conversation, &summary.id, Duration::ZERO)
+                .await
+                .is_err(),
+            "processes are scoped to their conversation"
+        );
+
+        assert!(registry.kill(other_conversation, &other.id).unwrap());
+        assert!(!registry.kill(other_conversation, &other.id).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn killing_a_process_kills_what_it_started() {
+        fn is_alive(pid: &str) -> bool {
+            std::process::Command::new("ps")
+                .args(["-o", "stat=", "-p", pid])
+                .output()
+                .is_ok_and(|output| {
+                    let stat = String::from_utf8_lossy(&output.stdout);
+                    let stat = stat.trim();
+                    !stat.is_empty() && !stat.starts_with('Z')
+                })
+        }
+
+        let registry = Arc::new(BackgroundProcesses::default());
+        let conversation_id = Uuid::new_v4();
+        let (summary, _view_rx) = spawn(&registry, conversation_id, "sleep 60 & echo $!; wait");
+        let output = registry
+            .read_output(conversation_id, &summary.id, Duration::from_secs(2))
+            .await
+            .unwrap();
+        let grandchild = output.stdout.trim().to_string();
+        assert!(is_alive(&grandchild), "sleep should be running");
+
+        assert!(registry.kill(conversation_id, &summary.id).unwrap());
+
+        for _ in 0..50 {
+            if !is_alive(&grandchild) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(40)).await;
+        }
+        panic!("sleep {grandchild} outlived the killed process");
+    }
+
+    #[test]
+    fn stream_buffer_drops_oldest_bytes_past_the_limit() {
+        let mut buffer = StreamBuffer::default();
+        buffer.push(&vec![b'a'; MAX_UNREAD_BYTES]);
+        buffer.push(b"tail");
+
+        let (text, dropped) = buffer.take();
+        assert_eq!(dropped, 4);
+        assert_eq!(text.len(), MAX_UNREAD_BYTES);
+        assert!(text.ends_with("tail"));
+        assert_eq!(buffer.take(), (String::new(), 0));
+    }
+}
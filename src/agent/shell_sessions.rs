@@ -0,0 +1,410 @@
+//! Persistent shell sessions used by `ShellExec`.
+//!
+//! A `ShellExec` call with `session: true` runs in a long-lived bash owned by
+//! its conversation, so `cd`, `export` and sourced scripts carry over to the
+//! next call. Each command is written to the shell's stdin followed by marker
+//! lines on stdout and stderr; the stdout marker carries the exit status and
+//! the working directory, and everything before a marker is that command's
+//! output.
+//!
+//! A session ends when a command times out, the shell exits, the
+//! conversation's stream is cancelled or the app quits. The next session
+//! command then starts a fresh shell.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, LazyLock, Mutex as StdMutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout};
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+use crate::services::{ServiceError, ServiceResult};
+
+/// Result of one command run in a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionRun {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+    /// Directory the command started in.
+    pub directory: PathBuf,
+    pub timed_out: bool,
+    /// The shell is gone after this command; the next one starts a new shell.
+    pub session_ended: bool,
+}
+
+/// A running bash and the directory it was left in.
+#[derive(Debug)]
+struct ShellSession {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    stderr: BufReader<ChildStderr>,
+    directory: PathBuf,
+}
+
+/// A conversation's session. The shell is taken out while a command runs, so
+/// a run that is dropped part-way drops (and kills) the shell with it.
+#[derive(Debug, Default)]
+struct SessionSlot {
+    shell: AsyncMutex<Option<ShellSession>>,
+    directory: StdMutex<Option<PathBuf>>,
+}
+
+/// Persistent shells, one per conversation.
+#[derive(Debug, Default)]
+pub struct ShellSessions {
+    slots: StdMutex<HashMap<Uuid, Arc<SessionSlot>>>,
+}
+
+static GLOBAL_SESSIONS: LazyLock<Arc<ShellSessions>> =
+    LazyLock::new(|| Arc::new(ShellSessions::default()));
+
+impl ShellSessions {
+    /// Return the process-wide registry.
+    #[must_use]
+    pub fn global() -> Arc<Self> {
+        GLOBAL_SESSIONS.clone()
+    }
+
+    /// Working directory the conversation's shell was left in, if it has one.
+    #[must_use]
+    pub fn directory(&self, conversation_id: Uuid) -> Option<PathBuf> {
+        let slot = self.slots().get(&conversation_id).cloned()?;
+        let directory = slot.directory.lock().ok()?.clone();
+        directory
+    }
+
+    /// Run `command` in the conversation's shell, starting one in
+    /// `directory` if there is none.
+    ///
+    /// With `change_directory` the shell first changes to `directory`, and
+    /// stays there afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Io` when the shell cannot be started or written to.
+    pub async fn run(
+        &self,
+        conversation_id: Uuid,
+        command: &str,
+        directory: &Path,
+        change_directory: bool,
+        timeout: Duration,
+    ) -> ServiceResult<SessionRun> {
+        let slot = self.slots().entry(conversation_id).or_default().clone();
+        let mut shell = slot.shell.lock().await;
+        let (mut session, change_directory) = match shell.take() {
+            Some(session) => (session, change_directory.then_some(directory)),
+            None => (ShellSession::start(directory)?, None),
+        };
+
+        let run = session.execute(command, change_directory, timeout).await;
+        let directory_after = match &run {
+            Ok(run) if !run.session_ended => Some(session.directory.clone()),
+            _ => None,
+        };
+        if directory_after.is_some() {
+            *shell = Some(session);
+        }
+        drop(shell);
+        if let Ok(mut directory) = slot.directory.lock() {
+            *directory = directory_after;
+        }
+        run
+    }
+
+    /// End the conversation's shell; used when its stream is cancelled.
+    pub fn close(&self, conversation_id: Uuid) {
+        let slot = self.slots().remove(&conversation_id);
+        if let Some(slot) = slot {
+            close_slot(&slot);
+        }
+    }
+
+    /// End every shell; used when the app quits.
+    pub fn close_all(&self) {
+        let slots: Vec<_> = self.slots().drain().map(|(_, slot)| slot).collect();
+        for slot in &slots {
+            close_slot(slot);
+        }
+    }
+
+    fn slots(&self) -> MutexGuard<'_, HashMap<Uuid, Arc<SessionSlot>>> {
+        self.slots.lock().expect("shell session registry poisoned")
+    }
+}
+
+/// Kill an idle shell now. A shell that is busy is killed when its run
+/// finishes or is dropped, since the slot is no longer reachable.
+fn close_slot(slot: &SessionSlot) {
+    if let Ok(mut shell) = slot.shell.try_lock() {
+        if let Some(mut session) = shell.take() {
+            let _ = session.child.start_kill();
+        }
+    }
+}
+
+impl ShellSession {
+    fn start(directory: &Path) -> ServiceResult<Self> {
+        let mut child = tokio::process::Command::new("bash")
+            .current_dir(directory)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|error| {
+                ServiceError::Io(format!(
+                    "Failed to start a shell session in '{}': {error}",
+                    directory.display()
+                ))
+            })?;
+
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            return Err(ServiceError::Io(
+                "Failed to capture the shell session's streams".to_string(),
+            ));
+        };
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            stderr: BufReader::new(stderr),
+            directory: directory.to_path_buf(),
+        })
+    }
+
+    async fn execute(
+        &mut self,
+        command: &str,
+        change_directory: Option<&Path>,
+        timeout: Duration,
+    ) -> ServiceResult<SessionRun> {
+        let marker = format!("__PERSONAL_AGENT_DONE_{}__", Uuid::new_v4().simple());
+        let started_in = change_directory.map_or_else(|| self.directory.clone(), Path::to_path_buf);
+        let script = session_script(command, change_directory, &marker);
+        let write = async {
+            self.stdin.write_all(script.as_bytes()).await?;
+            self.stdin.flush().await
+        };
+        write.await.map_err(|error| {
+            ServiceError::Io(format!(
+                "The shell session stopped accepting commands ({error}); run the command again to start a new one"
+            ))
+        })?;
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let read = async {
+            tokio::join!(
+                read_until_marker(&mut self.stdout, &marker, &mut stdout),
+                read_until_marker(&mut self.stderr, &marker, &mut stderr)
+            )
+        };
+        let (run_status, timed_out) = match tokio::time::timeout(timeout, read).await {
+            Ok((Ok(Some(status)), Ok(Some(_)))) => (Some(status), false),
+            Ok(_) => (None, false),
+            Err(_elapsed) => (None, true),
+        };
+
+        let (exit_code, session_ended) = match run_status {
+            Some(status) => {
+                let (code, directory) = status.split_once(' ').unwrap_or((status.as_str(), ""));
+                if !directory.is_empty() {
+                    self.directory = PathBuf::from(directory);
+                }
+                (code.parse().ok(), false)
+            }
+            None if timed_out => {
+                let _ = self.child.start_kill();
+                (None, true)
+            }
+            None => (
+                self.child
+                    .wait()
+                    .await
+                    .ok()
+                    .and_then(|status| status.code()),
+                true,
+            ),
+        };
+
+        Ok(SessionRun {
+            stdout: String::from_utf8_lossy(&stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+            exit_code,
+            directory: started_in,
+            timed_out,
+            session_ended,
+        })
+    }
+}
+
+/// The text written to the shell for one command.
+///
+/// The command goes through a quoted heredoc and `eval`, so it runs in the
+/// session's shell exactly as written, and a syntax error fails the command
+/// instead of leaving the shell waiting for more input. Its stdin is
+/// `/dev/null` so it cannot read the lines that follow.
+fn session_script(command: &str, change_directory: Option<&Path>, marker: &str) -> String {
+    let change_directory = change_directory
+        .map(|directory| format!("cd -- {} && ", shell_quote(&directory.to_string_lossy())))
+        .unwrap_or_default();
+    format!(
+        "{change_directory}eval \"$(cat <<'{marker}_COMMAND'\n{command}\n{marker}_COMMAND\n)\" < /dev/null\n\
+         __personal_agent_status=$?\n\
+         printf '\\n%s %s %s\\n' '{marker}' \"$__personal_agent_status\" \"$PWD\"\n\
+         printf '\\n%s\\n' '{marker}' >&2\n"
+    )
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Append lines to `output` until the line starting with `marker`, and return
+/// the rest of that line; `None` when the stream ends first.
+///
+/// The marker is printed after a newline so it always starts a line; that
+/// newline is not part of the command's output and is removed again.
+async fn read_until_marker<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    marker: &str,
+    output: &mut Vec<u8>,
+) -> std::io::Result<Option<String>> {
+    loop {
+        let mut line = Vec::new();
+        if reader.read_until(b'\n', &mut line).await? == 0 {
+            return Ok(None);
+        }
+        if let Some(rest) = line.strip_prefix(marker.as_bytes()) {
+            if output.last() == Some(&b'\n') {
+                output.pop();
+            }
+            return Ok(Some(String::from_utf8_lossy(rest).trim().to_string()));
+        }
+        output.extend_from_slice(&line);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    async fn run(
+        sessions: &ShellSessions,
+        conversation_id: Uuid,
+        command: &str,
+        directory: &Path,
+    ) -> SessionRun {
+        sessions
+            .run(conversation_id, command, directory, false, TIMEOUT)
+            .await
+            .expect("command should run")
+    }
+
+    #[tokio::test]
+    async fn directory_and_environment_carry_over_between_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        let sessions = ShellSessions::default();
+        let conversation_id = Uuid::new_v4();
+
+        let first = run(
+            &sessions,
+            conversation_id,
+            "cd nested && export GREETING=hi",
+            dir.path(),
+        )
+        .await;
+        assert_eq!(first.exit_code, Some(0));
+        assert_eq!(first.directory, dir.path());
+
+        let second = run(
+            &sessions,
+            conversation_id,
+            "echo \"$GREETING\"; pwd",
+            dir.path(),
+        )
+        .await;
+        let nested = dir.path().join("nested").canonicalize().unwrap();
+        assert_eq!(second.stdout, format!("hi\n{}\n", nested.display()));
+        assert_eq!(sessions.directory(conversation_id), Some(nested));
+    }
+
+    #[tokio::test]
+    async fn output_and_exit_code_stay_with_their_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let sessions = ShellSessions::default();
+        let conversation_id = Uuid::new_v4();
+
+        let failed = run(
+            &sessions,
+            conversation_id,
+            "printf partial; echo oops >&2; false",
+            dir.path(),
+        )
+        .await;
+        assert_eq!(failed.stdout, "partial");
+        assert_eq!(failed.stderr, "oops\n");
+        assert_eq!(failed.exit_code, Some(1));
+
+        let syntax = run(&sessions, conversation_id, "echo 'unterminated", dir.path()).await;
+        assert_eq!(syntax.exit_code, Some(2));
+        assert!(!syntax.session_ended);
+
+        let next = run(&sessions, conversation_id, "echo next", dir.path()).await;
+        assert_eq!(next.stdout, "next\n");
+        assert_eq!(next.stderr, "");
+    }
+
+    #[tokio::test]
+    async fn exiting_the_shell_ends_the_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let sessions = ShellSessions::default();
+        let conversation_id = Uuid::new_v4();
+
+        run(&sessions, conversation_id, "export KEPT=1", dir.path()).await;
+        let exited = run(&sessions, conversation_id, "exit 3", dir.path()).await;
+        assert_eq!(exited.exit_code, Some(3));
+        assert!(exited.session_ended);
+        assert_eq!(sessions.directory(conversation_id), None);
+
+        let fresh = run(&sessions, conversation_id, "echo \"[$KEPT]\"", dir.path()).await;
+        assert_eq!(fresh.stdout, "[]\n");
+    }
+
+    #[tokio::test]
+    async fn timed_out_commands_end_the_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let sessions = ShellSessions::default();
+        let conversation_id = Uuid::new_v4();
+
+        let slow = sessions
+            .run(
+                conversation_id,
+                "echo started; sleep 5",
+                dir.path(),
+                false,
+                Duration::from_millis(300),
+            )
+            .await
+            .expect("command should run");
+        assert!(slow.timed_out);
+        assert!(slow.session_ended);
+        assert_eq!(slow.stdout, "started\n");
+        assert_eq!(slow.exit_code, None);
+
+        let next = run(&sessions, conversation_id, "echo again", dir.path()).await;
+        assert_eq!(next.stdout, "again\n");
+    }
+}
@@ -2,11 +2,15 @@
 //!
 //! This module provides deterministic policy evaluation for tool execution.
 //! Decisions are evaluated in strict order using prefix matching semantics.
+//! Structured rules (see `agent::approval_rules`) are checked before any of it.
 
 use std::collections::HashSet;
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::agent::approval_rules::ApprovalRule;
+use crate::agent::workspace::outside_workspace_identifier;
 use crate::services::{AppSettingsService, ServiceError, ServiceResult};
 
 /// Settings key for persisted approval policy configuration.
@@ -42,6 +46,12 @@ pub struct ToolApprovalPolicy {
     pub persistent_allowlist: Vec<String>,
     pub persistent_denylist: Vec<String>,
     pub session_allowlist: HashSet<String>,
+    /// Domains `WebFetch` may reach without asking; subdomains included.
+    pub web_fetch_allowed_domains: Vec<String>,
+    /// Domains `WebFetch` may never reach; subdomains included.
+    pub web_fetch_denied_domains: Vec<String>,
+    /// Structured rules, checked before everything else.
+    pub rules: Vec<ApprovalRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -53,6 +63,9 @@ struct PersistedToolApprovalPolicy {
     mcp_approval_mode: McpApprovalMode,
     persistent_allowlist: Vec<String>,
     persistent_denylist: Vec<String>,
+    web_fetch_allowed_domains: Vec<String>,
+    web_fetch_denied_domains: Vec<String>,
+    rules: Vec<ApprovalRule>,
 }
 
 impl Default for ToolApprovalPolicy {
@@ -65,6 +78,9 @@ impl Default for ToolApprovalPolicy {
             persistent_allowlist: Vec::new(),
             persistent_denylist: Vec::new(),
             session_allowlist: HashSet::new(),
+            web_fetch_allowed_domains: Vec::new(),
+            web_fetch_denied_domains: Vec::new(),
+            rules: Vec::new(),
         }
     }
 }
@@ -79,6 +95,9 @@ impl From<PersistedToolApprovalPolicy> for ToolApprovalPolicy {
             persistent_allowlist: value.persistent_allowlist,
             persistent_denylist: value.persistent_denylist,
             session_allowlist: HashSet::new(),
+            web_fetch_allowed_domains: value.web_fetch_allowed_domains,
+            web_fetch_denied_domains: value.web_fetch_denied_domains,
+            rules: value.rules,
         }
     }
 }
@@ -92,10 +111,22 @@ impl From<&ToolApprovalPolicy> for PersistedToolApprovalPolicy {
             mcp_approval_mode: value.mcp_approval_mode,
             persistent_allowlist: value.persistent_allowlist.clone(),
             persistent_denylist: value.persistent_denylist.clone(),
+            web_fetch_allowed_domains: value.web_fetch_allowed_domains.clone(),
+            web_fetch_denied_domains: value.web_fetch_denied_domains.clone(),
+            rules: value.rules.clone(),
         }
     }
 }
 
+/// Approval identifier for `WebFetch` requests to `host`.
+///
+/// The trailing `/` keeps a list entry for `example.com` from also matching
+/// `example.com.evil.net`.
+#[must_use]
+pub fn web_fetch_identifier(host: &str) -> String {
+    format!("web_fetch:{host}/")
+}
+
 impl ToolApprovalPolicy {
     /// Evaluate a tool identifier using issue-defined precedence.
     #[must_use]
@@ -127,6 +158,45 @@ impl ToolApprovalPolicy {
         ToolApprovalDecision::AskUser
     }
 
+    /// Evaluate access to `path` outside the workspace roots.
+    ///
+    /// Only explicit list entries apply: YOLO mode and read auto-approval cover
+    /// tools inside the workspace, not the rest of the machine.
+    #[must_use]
+    pub fn evaluate_outside_workspace(&self, path: &Path) -> ToolApprovalDecision {
+        let identifier = outside_workspace_identifier(path);
+        if Self::matches_prefix_in_slice(&self.persistent_denylist, &identifier) {
+            return ToolApprovalDecision::Deny;
+        }
+
+        if Self::matches_prefix_in_slice(&self.persistent_allowlist, &identifier)
+            || Self::matches_prefix_in_set(&self.session_allowlist, &identifier)
+        {
+            return ToolApprovalDecision::Allow;
+        }
+
+        ToolApprovalDecision::AskUser
+    }
+
+    /// Evaluate a `WebFetch` request to `host`.
+    ///
+    /// The domain lists are checked first, deny before allow, and match the
+    /// domain itself and any subdomain. Other hosts fall through to
+    /// [`Self::evaluate`] with [`web_fetch_identifier`].
+    #[must_use]
+    pub fn evaluate_web_fetch(&self, host: &str) -> ToolApprovalDecision {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        if Self::matches_domain(&self.web_fetch_denied_domains, &host) {
+            return ToolApprovalDecision::Deny;
+        }
+
+        if Self::matches_domain(&self.web_fetch_allowed_domains, &host) {
+            return ToolApprovalDecision::Allow;
+        }
+
+        self.evaluate(&web_fetch_identifier(&host))
+    }
+
     /// Load policy from app settings, defaulting safely on missing/malformed data.
     ///
     /// # Errors
@@ -487,6 +557,18 @@ impl ToolApprovalPolicy {
             .any(|entry| tool_identifier.starts_with(entry))
     }
 
+    #[must_use]
+    fn matches_domain(domains: &[String], host: &str) -> bool {
+        domains
+            .iter()
+            .map(|domain| domain.trim().trim_start_matches("*.").trim_end_matches('.'))
+            .filter(|domain| !domain.is_empty())
+            .any(|domain| {
+                host.strip_suffix(&domain.to_ascii_lowercase())
+                    .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+            })
+    }
+
     #[must_use]
     fn matches_prefix_in_set(entries: &HashSet<String>, tool_identifier: &str) -> bool {
         entries
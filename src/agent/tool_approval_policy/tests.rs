@@ -1,7 +1,8 @@
 use tempfile::TempDir;
 
 use super::{
-    McpApprovalMode, ToolApprovalDecision, ToolApprovalPolicy, TOOL_APPROVAL_POLICY_SETTINGS_KEY,
+    web_fetch_identifier, ApprovalRule, McpApprovalMode, ToolApprovalDecision, ToolApprovalPolicy,
+    TOOL_APPROVAL_POLICY_SETTINGS_KEY,
 };
 use crate::services::{AppSettingsService, AppSettingsServiceImpl, ServiceError, ServiceResult};
 
@@ -135,6 +136,11 @@ fn evaluate_returns_allow_for_auto_approved_read_tools() {
     };
 
     assert_eq!(policy.evaluate("ReadFile"), ToolApprovalDecision::Allow);
+    assert_eq!(
+        policy.evaluate("ListDirectory"),
+        ToolApprovalDecision::Allow
+    );
+    assert_eq!(policy.evaluate("Glob"), ToolApprovalDecision::Allow);
 }
 
 #[test]
@@ -375,6 +381,11 @@ async fn save_and_load_round_trip_persists_non_session_fields() {
         mcp_approval_mode: McpApprovalMode::PerServer,
         persistent_allowlist: vec!["git status".to_string()],
         persistent_denylist: vec!["git push".to_string()],
+        web_fetch_allowed_domains: vec!["docs.rs".to_string()],
+        web_fetch_denied_domains: vec!["example.net".to_string()],
+        rules: vec![
+            ApprovalRule::parse("deny tool=ShellExec command=--force").expect("rule should parse")
+        ],
         ..ToolApprovalPolicy::default()
     };
     policy.allow_for_session("temporary/session");
@@ -393,6 +404,15 @@ async fn save_and_load_round_trip_persists_non_session_fields() {
     assert_eq!(loaded.mcp_approval_mode, McpApprovalMode::PerServer);
     assert_eq!(loaded.persistent_allowlist, vec!["git status".to_string()]);
     assert_eq!(loaded.persistent_denylist, vec!["git push".to_string()]);
+    assert_eq!(
+        loaded.web_fetch_allowed_domains,
+        vec!["docs.rs".to_string()]
+    );
+    assert_eq!(
+        loaded.web_fetch_denied_domains,
+        vec!["example.net".to_string()]
+    );
+    assert_eq!(loaded.rules, policy.rules);
     assert!(loaded.session_allowlist.is_empty());
 }
 
@@ -674,3 +694,86 @@ async fn skills_auto_approve_persists_via_settings() {
 
     assert!(loaded.skills_auto_approve);
 }
+
+#[test]
+fn outside_workspace_access_ignores_yolo_and_read_auto_approval() {
+    let path = std::path::Path::new("/srv/shared/notes.txt");
+    let mut policy = ToolApprovalPolicy {
+        yolo_mode: true,
+        auto_approve_reads: true,
+        ..ToolApprovalPolicy::default()
+    };
+    assert_eq!(
+        policy.evaluate_outside_workspace(path),
+        ToolApprovalDecision::AskUser
+    );
+
+    policy.allow_for_session("outside_workspace:/srv/shared/");
+    assert_eq!(
+        policy.evaluate_outside_workspace(path),
+        ToolApprovalDecision::Allow
+    );
+    assert_eq!(
+        policy.evaluate_outside_workspace(std::path::Path::new("/srv/shared-other/a.txt")),
+        ToolApprovalDecision::AskUser
+    );
+
+    policy.persistent_denylist = vec!["outside_workspace:".to_string()];
+    assert_eq!(
+        policy.evaluate_outside_workspace(path),
+        ToolApprovalDecision::Deny
+    );
+}
+
+#[test]
+fn web_fetch_domain_lists_match_subdomains_and_deny_wins() {
+    let mut policy = ToolApprovalPolicy {
+        yolo_mode: true,
+        web_fetch_allowed_domains: vec!["docs.rs".to_string(), "example.com".to_string()],
+        web_fetch_denied_domains: vec!["*.internal.example.com".to_string()],
+        ..ToolApprovalPolicy::default()
+    };
+
+    assert_eq!(
+        policy.evaluate_web_fetch("Docs.RS."),
+        ToolApprovalDecision::Allow
+    );
+    assert_eq!(
+        policy.evaluate_web_fetch("api.internal.example.com"),
+        ToolApprovalDecision::Deny
+    );
+    assert_eq!(
+        policy.evaluate_web_fetch("notdocs.rs"),
+        ToolApprovalDecision::Allow,
+        "unlisted hosts fall through to YOLO mode"
+    );
+
+    policy.yolo_mode = false;
+    assert_eq!(
+        policy.evaluate_web_fetch("notdocs.rs"),
+        ToolApprovalDecision::AskUser
+    );
+
+    policy.allow_for_session(web_fetch_identifier("notdocs.rs"));
+    assert_eq!(
+        policy.evaluate_web_fetch("notdocs.rs"),
+        ToolApprovalDecision::Allow
+    );
+    assert_eq!(
+        policy.evaluate_web_fetch("notdocs.rs.evil.net"),
+        ToolApprovalDecision::AskUser
+    );
+}
+
+#[test]
+fn web_fetch_is_not_auto_approved_as_a_read() {
+    let policy = ToolApprovalPolicy {
+        auto_approve_reads: true,
+        ..ToolApprovalPolicy::default()
+    };
+
+    assert_eq!(
+        policy.evaluate_web_fetch("example.com"),
+        ToolApprovalDecision::AskUser
+    );
+}
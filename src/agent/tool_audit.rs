@@ -0,0 +1,214 @@
+//! Audit recording for tool calls.
+//!
+//! Every native and MCP tool executor is registered wrapped in an
+//! `AuditedExecutor`. It times the call and, when the run context carries a
+//! `ToolAuditService`, records it once it finishes. What decided the call's
+//! approval is reported from inside the tool: `ToolApprovalPolicy::decide`
+//! notes the rule or policy decision, the workspace gate and the approval gate
+//! override it when they refuse or the user answers, and `ShellExec` notes its
+//! exit code. The notes go to a slot that lives for the duration of the call,
+//! so concurrent calls never see each other's notes.
+
+use std::cell::RefCell;
+use std::time::Instant;
+
+use chrono::Utc;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolError, ToolReturn};
+
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::llm::client_agent::McpToolContext;
+use crate::models::{truncate_audit_text, ApprovalSource, AuditOutcome, ToolAuditEntry};
+
+tokio::task_local! {
+    static CALL_NOTES: RefCell<CallNotes>;
+}
+
+/// What the tool reported about the call while it ran.
+#[derive(Debug, Default)]
+struct CallNotes {
+    approval: Option<NotedApproval>,
+    exit_code: Option<i32>,
+}
+
+#[derive(Debug)]
+struct NotedApproval {
+    decision: ToolApprovalDecision,
+    source: ApprovalSource,
+    detail: Option<String>,
+}
+
+/// Note the approval decision for the current tool call. A later note
+/// replaces an earlier one. Does nothing outside an audited call.
+pub fn note_approval(
+    decision: ToolApprovalDecision,
+    source: ApprovalSource,
+    detail: Option<String>,
+) {
+    let _ = CALL_NOTES.try_with(|notes| {
+        notes.borrow_mut().approval = Some(NotedApproval {
+            decision,
+            source,
+            detail,
+        });
+    });
+}
+
+/// Note the user's answer to the current call's approval prompt.
+pub fn note_user_decision(approved: bool) {
+    let decision = if approved {
+        ToolApprovalDecision::Allow
+    } else {
+        ToolApprovalDecision::Deny
+    };
+    note_approval(decision, ApprovalSource::User, None);
+}
+
+/// Note the exit code of the command the current call ran.
+pub fn note_exit_code(exit_code: Option<i32>) {
+    let _ = CALL_NOTES.try_with(|notes| notes.borrow_mut().exit_code = exit_code);
+}
+
+impl CallNotes {
+    /// The noted approval source and rule, and whether approval was refused.
+    fn approval(self) -> (ApprovalSource, Option<String>, bool) {
+        self.approval
+            .map_or((ApprovalSource::None, None, false), |approval| {
+                let denied = approval.decision == ToolApprovalDecision::Deny;
+                (approval.source, approval.detail, denied)
+            })
+    }
+}
+
+/// The outcome and output text of a finished call.
+fn call_outcome(result: &Result<ToolReturn, ToolError>, denied: bool) -> (AuditOutcome, String) {
+    match result {
+        Ok(value) => (
+            AuditOutcome::Succeeded,
+            value.as_text().unwrap_or("[non-text result]").to_string(),
+        ),
+        Err(error) if denied => (AuditOutcome::Denied, error.to_string()),
+        Err(error) => (AuditOutcome::Failed, error.to_string()),
+    }
+}
+
+/// A tool executor whose calls are recorded in the audit log.
+pub struct AuditedExecutor<E> {
+    tool_name: String,
+    inner: E,
+}
+
+impl<E> AuditedExecutor<E> {
+    #[must_use]
+    pub fn new(tool_name: impl Into<String>, inner: E) -> Self {
+        Self {
+            tool_name: tool_name.into(),
+            inner,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> ToolExecutor<McpToolContext> for AuditedExecutor<E>
+where
+    E: ToolExecutor<McpToolContext> + Send + Sync,
+{
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let Some(audit_service) = ctx.deps().audit_service.clone() else {
+            return self.inner.execute(args, ctx).await;
+        };
+
+        let arguments = truncate_audit_text(&args.to_string());
+        let started_at = Utc::now();
+        let started = Instant::now();
+        let (result, notes) = CALL_NOTES
+            .scope(RefCell::new(CallNotes::default()), async {
+                let result = self.inner.execute(args, ctx).await;
+                (result, CALL_NOTES.with(RefCell::take))
+            })
+            .await;
+
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let exit_code = notes.exit_code;
+        let (approval_source, approval_detail, denied) = notes.approval();
+        let (outcome, output) = call_outcome(&result, denied);
+        let conversation_id = ctx.deps().conversation_id;
+        let entry = ToolAuditEntry {
+            conversation_id: (!conversation_id.is_nil()).then_some(conversation_id),
+            tool_name: self.tool_name.clone(),
+            arguments,
+            approval_source,
+            approval_detail,
+            outcome,
+            exit_code,
+            output: truncate_audit_text(&output),
+            started_at,
+            duration_ms,
+        };
+        if let Err(error) = audit_service.record(entry).await {
+            tracing::warn!(tool = %self.tool_name, "Failed to record tool audit entry: {error}");
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refused_calls_keep_the_source_that_refused_them() {
+        let notes = CallNotes {
+            approval: Some(NotedApproval {
+                decision: ToolApprovalDecision::Deny,
+                source: ApprovalSource::Rule,
+                detail: Some("deny tool=ShellExec".to_string()),
+            }),
+            exit_code: None,
+        };
+
+        let (source, detail, denied) = notes.approval();
+        let (outcome, output) = call_outcome(
+            &Err(ToolError::execution_failed(
+                "Tool execution denied by policy",
+            )),
+            denied,
+        );
+
+        assert_eq!(source, ApprovalSource::Rule);
+        assert_eq!(detail.as_deref(), Some("deny tool=ShellExec"));
+        assert_eq!(outcome, AuditOutcome::Denied);
+        assert!(output.contains("denied by policy"));
+        assert_eq!(
+            CallNotes::default().approval(),
+            (ApprovalSource::None, None, false)
+        );
+    }
+
+    #[tokio::test]
+    async fn notes_are_scoped_to_the_call() {
+        note_approval(ToolApprovalDecision::Allow, ApprovalSource::Yolo, None);
+
+        let notes = CALL_NOTES
+            .scope(RefCell::new(CallNotes::default()), async {
+                note_approval(ToolApprovalDecision::AskUser, ApprovalSource::Policy, None);
+                note_user_decision(true);
+                note_exit_code(Some(2));
+                CALL_NOTES.with(RefCell::take)
+            })
+            .await;
+
+        assert_eq!(notes.exit_code, Some(2));
+        let (source, _, denied) = notes.approval();
+        assert_eq!(source, ApprovalSource::User);
+        assert_eq!(
+            call_outcome(&Ok(ToolReturn::text("Exit Code: 2")), denied),
+            (AuditOutcome::Succeeded, "Exit Code: 2".to_string())
+        );
+    }
+}
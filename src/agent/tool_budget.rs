@@ -0,0 +1,428 @@
+//! Per-turn limits on tool use.
+//!
+//! Every native and MCP tool executor is registered wrapped in a
+//! `BudgetedExecutor`, which counts the call against the turn's `TurnBudget`
+//! before running it. When a limit is reached the call waits on the approval
+//! gate behind a "continue / stop" prompt in the chat view: continuing raises
+//! that limit by its configured amount again, stopping refuses the call and
+//! the chat service cancels the turn.
+//!
+//! The limits are read from the `tool_budget` app setting as JSON; any limit
+//! set to `null` is not enforced.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolError, ToolReturn};
+
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+use crate::services::{AppSettingsService, ServiceResult};
+
+pub const TOOL_BUDGET_SETTINGS_KEY: &str = "tool_budget";
+
+/// The tool whose runtime counts against `max_shell_secs`.
+const SHELL_TOOL_NAME: &str = "ShellExec";
+
+/// Configured limits for one turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolBudgetLimits {
+    /// Tool calls per turn, sub-agent calls included.
+    pub max_tool_calls: Option<u32>,
+    /// Calls in a row to the same tool with the same arguments.
+    pub max_identical_calls: Option<u32>,
+    /// Wall-clock seconds since the turn started.
+    pub max_turn_secs: Option<u64>,
+    /// Seconds spent running `ShellExec` commands.
+    pub max_shell_secs: Option<u64>,
+}
+
+impl Default for ToolBudgetLimits {
+    fn default() -> Self {
+        Self {
+            max_tool_calls: Some(100),
+            max_identical_calls: Some(5),
+            max_turn_secs: Some(30 * 60),
+            max_shell_secs: Some(10 * 60),
+        }
+    }
+}
+
+impl ToolBudgetLimits {
+    /// Load limits from app settings, defaulting on malformed data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when reading from the settings service fails.
+    pub async fn load_from_settings(app_settings: &dyn AppSettingsService) -> ServiceResult<Self> {
+        let stored = app_settings.get_setting(TOOL_BUDGET_SETTINGS_KEY).await?;
+        Ok(stored
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default())
+    }
+}
+
+/// The limit a call ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLimit {
+    ToolCalls,
+    IdenticalCalls,
+    TurnTime,
+    ShellTime,
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    calls: u32,
+    last_call: Option<(String, String)>,
+    streak: u32,
+    shell_runtime: Duration,
+    allowed_calls: Option<u32>,
+    allowed_streak: Option<u32>,
+    allowed_turn: Option<Duration>,
+    allowed_shell: Option<Duration>,
+}
+
+/// What one turn has used of its limits.
+#[derive(Debug)]
+pub struct TurnBudget {
+    limits: ToolBudgetLimits,
+    started: Instant,
+    state: Mutex<BudgetState>,
+}
+
+impl TurnBudget {
+    #[must_use]
+    pub fn new(limits: ToolBudgetLimits) -> Self {
+        Self {
+            limits,
+            started: Instant::now(),
+            state: Mutex::new(BudgetState {
+                calls: 0,
+                last_call: None,
+                streak: 0,
+                shell_runtime: Duration::ZERO,
+                allowed_calls: limits.max_tool_calls,
+                allowed_streak: limits.max_identical_calls,
+                allowed_turn: limits.max_turn_secs.map(Duration::from_secs),
+                allowed_shell: limits.max_shell_secs.map(Duration::from_secs),
+            }),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, BudgetState> {
+        self.state.lock().expect("turn budget poisoned")
+    }
+
+    /// Count a call to `tool_name` with `args`.
+    pub fn record_call(&self, tool_name: &str, args: &serde_json::Value) {
+        let call = (tool_name.to_string(), args.to_string());
+        let mut state = self.state();
+        state.calls = state.calls.saturating_add(1);
+        if state.last_call.as_ref() == Some(&call) {
+            state.streak = state.streak.saturating_add(1);
+        } else {
+            state.last_call = Some(call);
+            state.streak = 1;
+            state.allowed_streak = self.limits.max_identical_calls;
+        }
+    }
+
+    /// Add the runtime of a finished `ShellExec` call.
+    pub fn record_shell_runtime(&self, elapsed: Duration) {
+        let mut state = self.state();
+        state.shell_runtime = state.shell_runtime.saturating_add(elapsed);
+    }
+
+    /// The first limit the turn is past, checked before running a call to
+    /// `tool_name`. Shell time only stops further shell commands.
+    pub fn exceeded(&self, tool_name: &str) -> Option<BudgetLimit> {
+        let state = self.state();
+        let limit = if state
+            .allowed_calls
+            .is_some_and(|allowed| state.calls > allowed)
+        {
+            Some(BudgetLimit::ToolCalls)
+        } else if state
+            .allowed_streak
+            .is_some_and(|allowed| state.streak > allowed)
+        {
+            Some(BudgetLimit::IdenticalCalls)
+        } else if state
+            .allowed_turn
+            .is_some_and(|allowed| self.started.elapsed() >= allowed)
+        {
+            Some(BudgetLimit::TurnTime)
+        } else if tool_name == SHELL_TOOL_NAME
+            && state
+                .allowed_shell
+                .is_some_and(|allowed| state.shell_runtime >= allowed)
+        {
+            Some(BudgetLimit::ShellTime)
+        } else {
+            None
+        };
+        drop(state);
+        limit
+    }
+
+    /// Allow `limit`'s configured amount again on top of what the turn has
+    /// used. Concurrent calls that hit the same limit extend it only once.
+    pub fn extend(&self, limit: BudgetLimit) {
+        let limits = self.limits;
+        let elapsed = self.started.elapsed();
+        let mut state = self.state();
+        match limit {
+            BudgetLimit::ToolCalls => {
+                state.allowed_calls = limits
+                    .max_tool_calls
+                    .map(|max| state.calls.saturating_add(max));
+            }
+            BudgetLimit::IdenticalCalls => {
+                state.allowed_streak = limits
+                    .max_identical_calls
+                    .map(|max| state.streak.saturating_add(max));
+            }
+            BudgetLimit::TurnTime => {
+                state.allowed_turn = limits
+                    .max_turn_secs
+                    .map(|max| elapsed.saturating_add(Duration::from_secs(max)));
+            }
+            BudgetLimit::ShellTime => {
+                state.allowed_shell = limits
+                    .max_shell_secs
+                    .map(|max| state.shell_runtime.saturating_add(Duration::from_secs(max)));
+            }
+        }
+    }
+
+    /// What the turn has used of `limit`, for the prompt.
+    pub fn describe(&self, limit: BudgetLimit) -> String {
+        let state = self.state();
+        let description = match limit {
+            BudgetLimit::ToolCalls => format!("{} tool calls this turn", state.calls),
+            BudgetLimit::IdenticalCalls => {
+                let tool_name = state
+                    .last_call
+                    .as_ref()
+                    .map_or("", |(name, _)| name.as_str());
+                format!("{} identical {tool_name} calls in a row", state.streak)
+            }
+            BudgetLimit::TurnTime => {
+                format!(
+                    "Turn running for {}",
+                    format_duration(self.started.elapsed())
+                )
+            }
+            BudgetLimit::ShellTime => format!(
+                "{} of shell commands this turn",
+                format_duration(state.shell_runtime)
+            ),
+        };
+        drop(state);
+        description
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+/// Ask the user whether the turn may go past `limit`.
+async fn ask_to_continue(
+    tool_context: &McpToolContext,
+    tool_name: &str,
+    summary: String,
+) -> Result<(), ToolError> {
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let waiter = tool_context.approval_gate.wait_for_approvals(
+        request_id.clone(),
+        Vec::new(),
+        tool_context.conversation_id,
+    );
+    let context = ToolApprovalContext::new("Tool budget reached", ToolCategory::Budget, summary)
+        .with_detail("next call", tool_name);
+
+    if tool_context
+        .view_tx
+        .try_send(ViewCommand::ToolApprovalRequest {
+            conversation_id: tool_context.conversation_id,
+            request_id: request_id.clone(),
+            context,
+        })
+        .is_err()
+    {
+        let _ = tool_context.approval_gate.resolve(&request_id, false);
+        return Err(ToolError::execution_failed(
+            "Failed to send approval request to UI (channel full or closed)",
+        ));
+    }
+
+    if waiter.wait().await.unwrap_or(false) {
+        Ok(())
+    } else {
+        Err(ToolError::execution_failed(
+            "Turn stopped by user at the tool budget",
+        ))
+    }
+}
+
+/// A tool executor whose calls count against the turn's budget.
+pub struct BudgetedExecutor<E> {
+    tool_name: String,
+    inner: E,
+}
+
+impl<E> BudgetedExecutor<E> {
+    #[must_use]
+    pub fn new(tool_name: impl Into<String>, inner: E) -> Self {
+        Self {
+            tool_name: tool_name.into(),
+            inner,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> ToolExecutor<McpToolContext> for BudgetedExecutor<E>
+where
+    E: ToolExecutor<McpToolContext> + Send + Sync,
+{
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let Some(budget) = ctx.deps().budget.clone() else {
+            return self.inner.execute(args, ctx).await;
+        };
+
+        budget.record_call(&self.tool_name, &args);
+        while let Some(limit) = budget.exceeded(&self.tool_name) {
+            ask_to_continue(ctx.deps(), &self.tool_name, budget.describe(limit)).await?;
+            budget.extend(limit);
+        }
+
+        let started = Instant::now();
+        let result = self.inner.execute(args, ctx).await;
+        if self.tool_name == SHELL_TOOL_NAME {
+            budget.record_shell_runtime(started.elapsed());
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn limits() -> ToolBudgetLimits {
+        ToolBudgetLimits {
+            max_tool_calls: Some(3),
+            max_identical_calls: Some(2),
+            max_turn_secs: None,
+            max_shell_secs: Some(60),
+        }
+    }
+
+    #[test]
+    fn continuing_past_the_call_limit_allows_as_many_calls_again() {
+        let budget = TurnBudget::new(limits());
+        for index in 0..3 {
+            budget.record_call("ReadFile", &json!({ "path": index }));
+            assert_eq!(budget.exceeded("ReadFile"), None);
+        }
+
+        budget.record_call("ReadFile", &json!({ "path": 3 }));
+        assert_eq!(budget.exceeded("ReadFile"), Some(BudgetLimit::ToolCalls));
+        assert_eq!(
+            budget.describe(BudgetLimit::ToolCalls),
+            "4 tool calls this turn"
+        );
+
+        budget.extend(BudgetLimit::ToolCalls);
+        budget.extend(BudgetLimit::ToolCalls);
+        for index in 4..7 {
+            budget.record_call("ReadFile", &json!({ "path": index }));
+            assert_eq!(budget.exceeded("ReadFile"), None);
+        }
+        budget.record_call("ReadFile", &json!({ "path": 7 }));
+        assert_eq!(budget.exceeded("ReadFile"), Some(BudgetLimit::ToolCalls));
+    }
+
+    #[test]
+    fn identical_calls_are_limited_until_a_different_call_breaks_the_streak() {
+        let budget = TurnBudget::new(ToolBudgetLimits {
+            max_tool_calls: None,
+            ..limits()
+        });
+        let args = json!({ "command": "cargo test" });
+        budget.record_call("ShellExec", &args);
+        budget.record_call("ShellExec", &args);
+        assert_eq!(budget.exceeded("ShellExec"), None);
+
+        budget.record_call("ShellExec", &args);
+        assert_eq!(
+            budget.exceeded("ShellExec"),
+            Some(BudgetLimit::IdenticalCalls)
+        );
+        assert_eq!(
+            budget.describe(BudgetLimit::IdenticalCalls),
+            "3 identical ShellExec calls in a row"
+        );
+        budget.extend(BudgetLimit::IdenticalCalls);
+        assert_eq!(budget.exceeded("ShellExec"), None);
+
+        budget.record_call("ShellExec", &json!({ "command": "cargo build" }));
+        budget.record_call("ShellExec", &args);
+        budget.record_call("ShellExec", &args);
+        budget.record_call("ShellExec", &args);
+        assert_eq!(
+            budget.exceeded("ShellExec"),
+            Some(BudgetLimit::IdenticalCalls)
+        );
+    }
+
+    #[test]
+    fn shell_time_only_stops_further_shell_commands() {
+        let budget = TurnBudget::new(limits());
+        budget.record_call("ShellExec", &json!({ "command": "make" }));
+        budget.record_shell_runtime(Duration::from_secs(75));
+
+        assert_eq!(budget.exceeded("ReadFile"), None);
+        assert_eq!(budget.exceeded("ShellExec"), Some(BudgetLimit::ShellTime));
+        assert_eq!(
+            budget.describe(BudgetLimit::ShellTime),
+            "1m 15s of shell commands this turn"
+        );
+
+        budget.extend(BudgetLimit::ShellTime);
+        assert_eq!(budget.exceeded("ShellExec"), None);
+    }
+
+    #[test]
+    fn limits_parse_with_defaults_and_null_disables_a_limit() {
+        let parsed: ToolBudgetLimits =
+            serde_json::from_str(r#"{"max_tool_calls": 20, "max_turn_secs": null}"#).unwrap();
+
+        assert_eq!(parsed.max_tool_calls, Some(20));
+        assert_eq!(parsed.max_turn_secs, None);
+        assert_eq!(
+            parsed.max_identical_calls,
+            ToolBudgetLimits::default().max_identical_calls
+        );
+
+        let budget = TurnBudget::new(parsed);
+        assert_eq!(budget.exceeded("ReadFile"), None);
+    }
+}
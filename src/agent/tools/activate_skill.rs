@@ -1,3 +1,4 @@
+use crate::agent::approval_rules::ToolCall;
 use crate::agent::tool_approval_policy::ToolApprovalDecision;
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::ViewCommand;
@@ -36,7 +37,8 @@ impl ToolExecutor<McpToolContext> for ActivateSkillExecutor {
             )));
         }
 
-        check_approval(ctx.deps(), &skill).await?;
+        let call = ToolCall::new("activate_skill").with_arguments(&args);
+        check_approval(ctx.deps(), &call, &skill).await?;
 
         let body = ctx
             .deps()
@@ -57,11 +59,12 @@ impl ToolExecutor<McpToolContext> for ActivateSkillExecutor {
 
 async fn check_approval(
     tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
     skill: &crate::models::Skill,
 ) -> Result<(), ToolError> {
     let decision = {
         let policy = tool_context.policy.lock().await;
-        policy.evaluate("activate_skill")
+        policy.decide(call, |policy| policy.evaluate("activate_skill"))
     };
 
     match decision {
@@ -184,6 +187,12 @@ mod tests {
             policy,
             skills_service: skills_service.clone(),
             filter_emoji: false,
+            workspace_roots: crate::agent::workspace::WorkspaceRoots::default(),
+            checkpoints: None,
+            delegation: None,
+            todo_service: None,
+            audit_service: None,
+            budget: None,
         };
 
         (ctx, temp_dir, skills_service, view_rx)
@@ -0,0 +1,618 @@
+//! `ApplyPatch` tool implementation.
+//!
+//! This module provides a built-in `ApplyPatchExecutor` that applies a
+//! unified diff, or a list of exact find-and-replace edits, across one or
+//! more files in a single call. Every file is patched in memory first: if any
+//! hunk or edit fails, nothing is written and each failure is reported. The
+//! whole call is approved once, with the combined diff.
+
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tools::diff_preview::unified_diff;
+use crate::agent::tools::edit_file::{map_read_error, map_write_error, resolve_path, strip_emojis};
+use crate::agent::tools::patch::{apply_hunks, parse_patch, Hunk};
+use crate::agent::tools::workspace_gate::confine_decision;
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+use std::path::{Path, PathBuf};
+
+/// Executor for the `ApplyPatch` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct ApplyPatchExecutor;
+
+/// One requested change, labelled for failure reports.
+enum Change {
+    Hunk(Hunk),
+    Edit { old_text: String, new_text: String },
+}
+
+/// The changes requested for one file, before it is read.
+struct FileChanges {
+    path: String,
+    is_new: bool,
+    changes: Vec<(String, Change)>,
+}
+
+/// A file's content before and after the patch.
+struct PlannedWrite {
+    path: PathBuf,
+    /// `None` when the patch creates the file.
+    original: Option<String>,
+    updated: String,
+    change_count: usize,
+}
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for ApplyPatchExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let mut requested = parse_changes(&args)?;
+
+        // Filter emojis from the text being written, but not from context or
+        // old_text, which must match the file.
+        if ctx.deps().filter_emoji {
+            for file in &mut requested {
+                for (_, change) in &mut file.changes {
+                    match change {
+                        Change::Hunk(hunk) => hunk.map_added(strip_emojis),
+                        Change::Edit { new_text, .. } => *new_text = strip_emojis(new_text),
+                    }
+                }
+            }
+        }
+
+        let mut files: Vec<(PathBuf, FileChanges)> = Vec::new();
+        let mut outside: Vec<PathBuf> = Vec::new();
+        for file in requested {
+            let resolved = resolve_path(&ctx.deps().workspace_roots, &file.path)?;
+            outside.extend(resolved.outside_workspace().iter().cloned());
+            match files.iter_mut().find(|(path, _)| *path == resolved.path) {
+                Some((_, existing)) => existing.changes.extend(file.changes),
+                None => files.push((resolved.path, file)),
+            }
+        }
+
+        let planned = plan_writes(files).await?;
+        let call = ToolCall::new("ApplyPatch")
+            .with_arguments(&args)
+            .with_paths(planned.iter().map(|write| write.path.clone()));
+        check_approval(ctx.deps(), &call, &planned, &outside).await?;
+
+        // The user may have taken a while; refuse to write over newer edits.
+        for write in &planned {
+            if read_optional(&write.path).await? != write.original {
+                return Err(ToolError::execution_failed(format!(
+                    "'{}' changed while waiting for approval; no files were changed",
+                    write.path.display()
+                )));
+            }
+        }
+
+        if let Some(checkpoints) = &ctx.deps().checkpoints {
+            for write in &planned {
+                checkpoints.snapshot(&write.path).await.map_err(|error| {
+                    ToolError::execution_failed(format!(
+                        "Failed to checkpoint '{}' before writing: {error}",
+                        write.path.display()
+                    ))
+                })?;
+            }
+        }
+
+        write_all(&planned).await?;
+
+        let mut summary = format!("Applied patch to {} file(s):", planned.len());
+        for write in &planned {
+            let verb = if write.original.is_none() {
+                "created"
+            } else {
+                "edited"
+            };
+            summary.push_str(&format!(
+                "\n- {} ({verb}, {} change(s))",
+                write.path.display(),
+                write.change_count
+            ));
+        }
+        Ok(ToolReturn::text(summary))
+    }
+}
+
+/// Read the `patch` or `edits` argument into per-file changes.
+fn parse_changes(args: &serde_json::Value) -> Result<Vec<FileChanges>, ToolError> {
+    match (args.get("patch"), args.get("edits")) {
+        (Some(patch), None) => {
+            let text = patch.as_str().ok_or_else(|| {
+                ToolError::execution_failed("Invalid 'patch' argument: expected a string")
+            })?;
+            let patches = parse_patch(text)
+                .map_err(|error| ToolError::execution_failed(format!("Invalid patch: {error}")))?;
+            Ok(patches
+                .into_iter()
+                .map(|patch| FileChanges {
+                    path: patch.path,
+                    is_new: patch.is_new,
+                    changes: patch
+                        .hunks
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, hunk)| (format!("hunk {}", index + 1), Change::Hunk(hunk)))
+                        .collect(),
+                })
+                .collect())
+        }
+        (None, Some(edits)) => {
+            let edits = edits.as_array().ok_or_else(|| {
+                ToolError::execution_failed("Invalid 'edits' argument: expected an array")
+            })?;
+            if edits.is_empty() {
+                return Err(ToolError::execution_failed(
+                    "The 'edits' argument must not be empty",
+                ));
+            }
+            let mut files: Vec<FileChanges> = Vec::new();
+            for (index, edit) in edits.iter().enumerate() {
+                let field = |key: &str| {
+                    edit.get(key)
+                        .and_then(serde_json::Value::as_str)
+                        .map(ToOwned::to_owned)
+                        .ok_or_else(|| {
+                            ToolError::execution_failed(format!(
+                                "Edit {} is missing a string '{key}'",
+                                index + 1
+                            ))
+                        })
+                };
+                let path = field("path")?;
+                let old_text = field("old_text")?;
+                let new_text = field("new_text")?;
+                if old_text.is_empty() {
+                    return Err(ToolError::execution_failed(format!(
+                        "Edit {} has an empty 'old_text'",
+                        index + 1
+                    )));
+                }
+                let change = (
+                    format!("edit {}", index + 1),
+                    Change::Edit { old_text, new_text },
+                );
+                match files.iter_mut().find(|file| file.path == path) {
+                    Some(file) => file.changes.push(change),
+                    None => files.push(FileChanges {
+                        path,
+                        is_new: false,
+                        changes: vec![change],
+                    }),
+                }
+            }
+            Ok(files)
+        }
+        _ => Err(ToolError::execution_failed(
+            "Provide exactly one of 'patch' or 'edits'",
+        )),
+    }
+}
+
+/// Patch every file in memory, collecting all failures.
+async fn plan_writes(files: Vec<(PathBuf, FileChanges)>) -> Result<Vec<PlannedWrite>, ToolError> {
+    let mut planned = Vec::with_capacity(files.len());
+    let mut failures = Vec::new();
+
+    for (path, file) in files {
+        let original = read_optional(&path).await?;
+        match (&original, file.is_new) {
+            (Some(_), true) => {
+                failures.push(format!("{}: file already exists", path.display()));
+                continue;
+            }
+            (None, false) => {
+                failures.push(format!("{}: file not found", path.display()));
+                continue;
+            }
+            _ => {}
+        }
+
+        let change_count = file.changes.len();
+        match apply_changes(original.as_deref().unwrap_or_default(), file.changes) {
+            Ok(updated) => planned.push(PlannedWrite {
+                path,
+                original,
+                updated,
+                change_count,
+            }),
+            Err(reasons) => failures.extend(
+                reasons
+                    .into_iter()
+                    .map(|reason| format!("{}: {reason}", path.display())),
+            ),
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(planned)
+    } else {
+        Err(ToolError::execution_failed(format!(
+            "Patch not applied; no files were changed:\n- {}",
+            failures.join("\n- ")
+        )))
+    }
+}
+
+/// Apply one file's changes in order, reporting each one that fails.
+fn apply_changes(content: &str, changes: Vec<(String, Change)>) -> Result<String, Vec<String>> {
+    let mut updated = content.to_string();
+    let mut failures = Vec::new();
+    let mut hunks: Vec<(String, Hunk)> = Vec::new();
+
+    for (label, change) in changes {
+        match change {
+            Change::Hunk(hunk) => hunks.push((label, hunk)),
+            Change::Edit { old_text, new_text } => {
+                flush_hunks(&mut updated, &mut hunks, &mut failures);
+                match apply_edit(&updated, &old_text, &new_text) {
+                    Ok(patched) => updated = patched,
+                    Err(reason) => failures.push(format!("{label}: {reason}")),
+                }
+            }
+        }
+    }
+    flush_hunks(&mut updated, &mut hunks, &mut failures);
+
+    if failures.is_empty() {
+        Ok(updated)
+    } else {
+        Err(failures)
+    }
+}
+
+/// Apply pending hunks together, so their line numbers stay relative to the
+/// same text.
+fn flush_hunks(updated: &mut String, hunks: &mut Vec<(String, Hunk)>, failures: &mut Vec<String>) {
+    if hunks.is_empty() {
+        return;
+    }
+    let (labels, batch): (Vec<String>, Vec<Hunk>) = hunks.drain(..).unzip();
+    match apply_hunks(updated, &batch) {
+        Ok(patched) => *updated = patched,
+        Err(errors) => failures.extend(
+            errors
+                .into_iter()
+                .map(|(index, reason)| format!("{}: {reason}", labels[index])),
+        ),
+    }
+}
+
+/// Replace the single exact match of `old_text`, falling back to a
+/// whitespace-tolerant line match when there is none.
+fn apply_edit(content: &str, old_text: &str, new_text: &str) -> Result<String, String> {
+    let mut matches = content.match_indices(old_text);
+    match (matches.next(), matches.next()) {
+        (Some((start, _)), None) => {
+            let mut updated = String::with_capacity(content.len() + new_text.len());
+            updated.push_str(&content[..start]);
+            updated.push_str(new_text);
+            updated.push_str(&content[start + old_text.len()..]);
+            Ok(updated)
+        }
+        (Some(_), Some(_)) => {
+            Err("'old_text' matches more than once; include more surrounding text".to_string())
+        }
+        (None, _) => apply_hunks(content, &[Hunk::replacement(old_text, new_text)])
+            .map_err(|_| "'old_text' not found, even ignoring whitespace".to_string()),
+    }
+}
+
+async fn read_optional(path: &Path) -> Result<Option<String>, ToolError> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(map_read_error(path, &error)),
+    }
+}
+
+/// Write every planned file, restoring the ones already written if any
+/// write fails.
+async fn write_all(planned: &[PlannedWrite]) -> Result<(), ToolError> {
+    for (index, write) in planned.iter().enumerate() {
+        if let Err(error) = write_one(write).await {
+            for done in planned[..index].iter().rev() {
+                let _ = match &done.original {
+                    Some(original) => tokio::fs::write(&done.path, original).await,
+                    None => tokio::fs::remove_file(&done.path).await,
+                };
+            }
+            return Err(map_write_error(&write.path, &error));
+        }
+    }
+    Ok(())
+}
+
+async fn write_one(write: &PlannedWrite) -> std::io::Result<()> {
+    if write.original.is_none() {
+        if let Some(parent) = write.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+    }
+    tokio::fs::write(&write.path, &write.updated).await
+}
+
+/// Diff of every planned file, each under `---`/`+++` headers.
+fn combined_diff(planned: &[PlannedWrite]) -> String {
+    planned
+        .iter()
+        .map(|write| {
+            let path = write.path.display();
+            let old_header = if write.original.is_some() {
+                path.to_string()
+            } else {
+                "/dev/null".to_string()
+            };
+            format!(
+                "--- {old_header}\n+++ {path}\n{}",
+                unified_diff(
+                    write.original.as_deref().unwrap_or_default(),
+                    &write.updated
+                )
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Check tool approval policy and await user decision if required.
+///
+/// All files are put to the user as one request.
+async fn check_approval(
+    tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
+    planned: &[PlannedWrite],
+    outside_workspace: &[PathBuf],
+) -> Result<(), ToolError> {
+    let decision = {
+        let policy = tool_context.policy.lock().await;
+        policy.decide(call, |policy| policy.evaluate("ApplyPatch"))
+    };
+    let decision =
+        confine_decision(tool_context, "ApplyPatch", decision, outside_workspace).await?;
+
+    match decision {
+        ToolApprovalDecision::Allow => Ok(()),
+        ToolApprovalDecision::Deny => Err(ToolError::execution_failed(
+            "Tool execution denied by policy",
+        )),
+        ToolApprovalDecision::AskUser => {
+            let Some((first, rest)) = planned.split_first() else {
+                return Ok(());
+            };
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let waiter = tool_context.approval_gate.wait_for_approval(
+                request_id.clone(),
+                "ApplyPatch".to_string(),
+                tool_context.conversation_id,
+            );
+
+            let mut context = ToolApprovalContext::new(
+                "ApplyPatch",
+                ToolCategory::FileEdit,
+                first.path.display().to_string(),
+            );
+            for write in rest {
+                context = context.with_detail("file", write.path.display().to_string());
+            }
+            context = context.with_diff(combined_diff(planned));
+
+            if tool_context
+                .view_tx
+                .try_send(ViewCommand::ToolApprovalRequest {
+                    conversation_id: tool_context.conversation_id,
+                    request_id: request_id.clone(),
+                    context,
+                })
+                .is_err()
+            {
+                let _ = tool_context.approval_gate.resolve(&request_id, false);
+                return Err(ToolError::execution_failed(
+                    "Failed to send approval request to UI (channel full or closed)",
+                ));
+            }
+
+            let approved = waiter.wait().await.unwrap_or(false);
+            if approved {
+                Ok(())
+            } else {
+                Err(ToolError::execution_failed("Tool execution denied by user"))
+            }
+        }
+    }
+}
+
+/// Get the `ApplyPatch` tool definition.
+#[must_use]
+pub fn get_apply_patch_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "patch": {
+                "type": "string",
+                "description": "A unified diff with '--- path' / '+++ path' headers and '@@' hunks; may cover several files. Paths are absolute or relative to the workspace root. Use '--- /dev/null' to create a file."
+            },
+            "edits": {
+                "type": "array",
+                "description": "Exact find-and-replace edits, applied in order",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "The path to the file to edit; absolute, or relative to the workspace root"
+                        },
+                        "old_text": {
+                            "type": "string",
+                            "description": "The exact literal text to find; must match once"
+                        },
+                        "new_text": {
+                            "type": "string",
+                            "description": "The replacement text"
+                        }
+                    },
+                    "required": ["path", "old_text", "new_text"]
+                }
+            }
+        }
+    });
+
+    ToolDefinition::new(
+        "ApplyPatch",
+        "Apply several changes across one or more files in one call, given either 'patch' (a unified diff) or 'edits' (a list of exact find-and-replace edits). Hunks are located by their context lines, tolerating wrong line numbers and whitespace differences. Changes are all-or-nothing: if any hunk or edit fails, no file is changed and every failure is reported.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn yolo_context() -> RunContext<McpToolContext> {
+        RunContext::new(
+            McpToolContext {
+                policy: std::sync::Arc::new(tokio::sync::Mutex::new(ToolApprovalPolicy {
+                    yolo_mode: true,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            },
+            "test-model",
+        )
+    }
+
+    #[tokio::test]
+    async fn apply_patch_edits_and_creates_files() {
+        let dir = tempdir().expect("temp dir should be created");
+        let existing = dir.path().join("lib.rs");
+        let created = dir.path().join("docs/notes.md");
+        fs::write(&existing, "fn a() {}\n\nfn b() {}\n").expect("seed file should be written");
+
+        let patch = format!(
+            "--- {0}\n+++ {0}\n@@ -1,3 +1,3 @@\n-fn a() {{}}\n+fn a() {{ todo() }}\n \n fn b() {{}}\n--- /dev/null\n+++ {1}\n@@ -0,0 +1 @@\n+# Notes\n",
+            existing.display(),
+            created.display()
+        );
+        let result = ApplyPatchExecutor
+            .execute(serde_json::json!({ "patch": patch }), &yolo_context())
+            .await;
+
+        assert!(result.is_ok(), "patch should apply: {result:?}");
+        assert_eq!(
+            fs::read_to_string(&existing).unwrap(),
+            "fn a() { todo() }\n\nfn b() {}\n"
+        );
+        assert_eq!(fs::read_to_string(&created).unwrap(), "# Notes\n");
+    }
+
+    #[tokio::test]
+    async fn apply_patch_reports_failures_and_changes_nothing() {
+        let dir = tempdir().expect("temp dir should be created");
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("second.txt");
+        fs::write(&first, "one\ntwo\n").expect("seed file should be written");
+        fs::write(&second, "three\n").expect("seed file should be written");
+
+        let args = serde_json::json!({
+            "edits": [
+                { "path": first, "old_text": "one", "new_text": "1" },
+                { "path": second, "old_text": "missing", "new_text": "x" },
+                { "path": second, "old_text": "three", "new_text": "3" },
+            ]
+        });
+        let error = ApplyPatchExecutor
+            .execute(args, &yolo_context())
+            .await
+            .expect_err("a failing edit should fail the whole call");
+
+        let message = error.to_string();
+        assert!(message.contains("no files were changed"));
+        assert!(message.contains("edit 2: 'old_text' not found"));
+        assert!(!message.contains("edit 3"));
+        assert_eq!(fs::read_to_string(&first).unwrap(), "one\ntwo\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "three\n");
+    }
+
+    #[tokio::test]
+    async fn apply_patch_asks_once_for_all_files() {
+        let dir = tempdir().expect("temp dir should be created");
+        let first = dir.path().join("a.txt");
+        let second = dir.path().join("b.txt");
+        fs::write(&first, "a\n").expect("seed file should be written");
+        fs::write(&second, "b\n").expect("seed file should be written");
+
+        let (view_tx, mut view_rx) = tokio::sync::mpsc::channel(10);
+        let approval_gate = std::sync::Arc::new(crate::llm::client_agent::ApprovalGate::new());
+        let run_ctx = RunContext::new(
+            McpToolContext {
+                view_tx,
+                approval_gate: approval_gate.clone(),
+                ..Default::default()
+            },
+            "test-model",
+        );
+        let args = serde_json::json!({
+            "edits": [
+                { "path": first, "old_text": "a", "new_text": "A" },
+                { "path": second, "old_text": "b", "new_text": "B" },
+            ]
+        });
+        let handle = tokio::spawn(async move { ApplyPatchExecutor.execute(args, &run_ctx).await });
+
+        let Some(ViewCommand::ToolApprovalRequest {
+            request_id,
+            context,
+            ..
+        }) = view_rx.recv().await
+        else {
+            panic!("expected ToolApprovalRequest");
+        };
+        assert_eq!(context.tool_name, "ApplyPatch");
+        assert_eq!(context.primary_target, first.display().to_string());
+        let diff = context.diff.expect("approval should carry a diff");
+        assert!(diff.contains("-a\n+A"));
+        assert!(diff.contains("-b\n+B"));
+
+        let _ = approval_gate.resolve(&request_id, true);
+        assert!(handle.await.unwrap().is_ok());
+        assert!(
+            view_rx.try_recv().is_err(),
+            "only one approval is requested"
+        );
+        assert_eq!(fs::read_to_string(&first).unwrap(), "A\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "B\n");
+    }
+
+    #[tokio::test]
+    async fn apply_patch_requires_exactly_one_input() {
+        let error = ApplyPatchExecutor
+            .execute(serde_json::json!({}), &yolo_context())
+            .await
+            .expect_err("missing input should fail");
+
+        assert!(error
+            .to_string()
+            .contains("exactly one of 'patch' or 'edits'"));
+    }
+
+    #[test]
+    fn get_apply_patch_tool_definition_returns_valid_schema() {
+        let def = get_apply_patch_tool_definition();
+        assert_eq!(def.name, "ApplyPatch");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
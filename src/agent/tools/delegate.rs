@@ -0,0 +1,231 @@
+//! `Delegate` tool implementation.
+//!
+//! This module provides a built-in `DelegateExecutor` that hands a
+//! self-contained task to a sub-agent (see `agent::delegation`) and returns
+//! its report. The sub-agent's own tool calls are approval-gated as usual,
+//! so delegating is not.
+
+use crate::agent::delegation::{
+    self, SubAgentReport, SubAgentTask, DEFAULT_MAX_TURNS, MAX_TURNS_LIMIT,
+};
+use crate::llm::client_agent::McpToolContext;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+
+/// Executor for the `Delegate` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct DelegateExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for DelegateExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let task = parse_task(&args)?;
+        let Some(delegation) = ctx.deps().delegation.as_deref() else {
+            return Err(ToolError::execution_failed(
+                "Delegation is not available here; sub-agents cannot start sub-agents",
+            ));
+        };
+        let profile = delegation
+            .resolve_profile(args.get("profile").and_then(serde_json::Value::as_str))
+            .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+        let description = task.description.clone();
+        let report = delegation::run_sub_agent(ctx.deps(), delegation, profile, task)
+            .await
+            .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+        Ok(ToolReturn::text(format_report(
+            &description,
+            &profile.name,
+            &report,
+        )))
+    }
+}
+
+fn parse_task(args: &serde_json::Value) -> Result<SubAgentTask, ToolError> {
+    let required = |name: &str| {
+        args.get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                ToolError::execution_failed(format!("Missing required '{name}' argument"))
+            })
+    };
+    let description = required("description")?;
+    let prompt = required("prompt")?;
+
+    let allowed_tools = match args.get("tools") {
+        None => None,
+        Some(value) => Some(
+            value
+                .as_array()
+                .and_then(|tools| {
+                    tools
+                        .iter()
+                        .map(|tool| tool.as_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    ToolError::execution_failed("Invalid tools: must be an array of tool names")
+                })?,
+        ),
+    };
+
+    let max_turns = match args.get("max_turns") {
+        None => DEFAULT_MAX_TURNS,
+        Some(value) => value
+            .as_u64()
+            .and_then(|turns| u32::try_from(turns).ok())
+            .filter(|turns| (1..=MAX_TURNS_LIMIT).contains(turns))
+            .ok_or_else(|| {
+                ToolError::execution_failed(format!(
+                    "Invalid max_turns: {value} (must be 1..={MAX_TURNS_LIMIT})"
+                ))
+            })?,
+    };
+
+    Ok(SubAgentTask {
+        description,
+        prompt,
+        allowed_tools,
+        max_turns,
+    })
+}
+
+fn format_report(description: &str, profile_name: &str, report: &SubAgentReport) -> String {
+    let mut text = format!(
+        "Sub-agent report: {description}\nProfile: {profile_name}\nTurns: {}\nTool calls: {}\n",
+        report.turns, report.tool_calls
+    );
+    if report.hit_turn_limit {
+        text.push_str("Stopped at the turn limit; the report may be incomplete.\n");
+    }
+    text.push('\n');
+    if report.report.is_empty() {
+        text.push_str("(the sub-agent gave no report)");
+    } else {
+        text.push_str(&report.report);
+    }
+    text
+}
+
+/// Get the `Delegate` tool definition.
+#[must_use]
+pub fn get_delegate_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "description": {
+                "type": "string",
+                "description": "Short label for the task, shown to the user while it runs"
+            },
+            "prompt": {
+                "type": "string",
+                "description": "Complete instructions for the sub-agent. It sees nothing of this conversation, so include every detail it needs"
+            },
+            "profile": {
+                "type": "string",
+                "description": "Name of the model profile to run the sub-agent on (default: this conversation's profile)"
+            },
+            "tools": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "Names of the tools the sub-agent may use (default: all of this conversation's tools)"
+            },
+            "max_turns": {
+                "type": "integer",
+                "description": "Most model responses the sub-agent may take (default 10, max 50)",
+                "minimum": 1,
+                "maximum": MAX_TURNS_LIMIT
+            }
+        },
+        "required": ["description", "prompt"]
+    });
+
+    ToolDefinition::new(
+        "Delegate",
+        "Hand a self-contained task to a sub-agent with a fresh context and get back its final report. Use it for research or multi-step work whose intermediate output you do not need to see.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_task_applies_defaults_and_limits() {
+        let task = parse_task(&serde_json::json!({
+            "description": "Find callers",
+            "prompt": "List every caller of parse_task"
+        }))
+        .expect("valid task");
+        assert_eq!(task.max_turns, DEFAULT_MAX_TURNS);
+        assert_eq!(task.allowed_tools, None);
+
+        let task = parse_task(&serde_json::json!({
+            "description": "Find callers",
+            "prompt": "List every caller of parse_task",
+            "tools": ["Search", "ReadFile"],
+            "max_turns": 3
+        }))
+        .expect("valid task");
+        assert_eq!(task.max_turns, 3);
+        assert_eq!(
+            task.allowed_tools,
+            Some(vec!["Search".to_string(), "ReadFile".to_string()])
+        );
+
+        let error = parse_task(&serde_json::json!({
+            "description": "Find callers",
+            "prompt": "List every caller",
+            "max_turns": 0
+        }))
+        .expect_err("zero turns should fail");
+        assert!(error.to_string().contains("max_turns"));
+    }
+
+    #[tokio::test]
+    async fn delegate_fails_without_a_delegation_context() {
+        let error = DelegateExecutor
+            .execute(
+                serde_json::json!({ "description": "Nested", "prompt": "Do it" }),
+                &RunContext::new(McpToolContext::default(), "test-model"),
+            )
+            .await
+            .expect_err("sub-agents cannot delegate");
+
+        assert!(error.to_string().contains("not available"));
+    }
+
+    #[test]
+    fn format_report_notes_the_turn_limit() {
+        let report = SubAgentReport {
+            report: "Three callers found.".to_string(),
+            turns: 4,
+            tool_calls: 6,
+            hit_turn_limit: true,
+        };
+
+        let text = format_report("Find callers", "Fast", &report);
+
+        assert!(text.starts_with("Sub-agent report: Find callers\nProfile: Fast\nTurns: 4\n"));
+        assert!(text.contains("turn limit"));
+        assert!(text.ends_with("Three callers found."));
+    }
+
+    #[test]
+    fn get_delegate_tool_definition_returns_valid_schema() {
+        let def = get_delegate_tool_definition();
+        assert_eq!(def.name, "Delegate");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
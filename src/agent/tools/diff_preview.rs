@@ -0,0 +1,234 @@
+//! Unified diffs for the file tools' approval requests.
+//!
+//! The diff is computed line by line: the common prefix and suffix are
+//! trimmed, and the changed middle is aligned with a longest-common-subsequence
+//! table when it is small enough. Larger middles are shown as a plain
+//! remove-then-add block rather than spending seconds aligning them.
+
+/// Unchanged lines kept around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Changed middles with more cells than this skip LCS alignment.
+const MAX_ALIGNMENT_CELLS: usize = 1_000_000;
+
+/// Longest diff sent to the UI; the rest is summarised in a trailing line.
+pub(crate) const MAX_DIFF_LINES: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+impl Op<'_> {
+    const fn is_change(self) -> bool {
+        !matches!(self, Op::Equal(_))
+    }
+}
+
+/// Unified diff of `old` against `new`, without file headers.
+///
+/// Returns an empty string when the texts have the same lines.
+pub(crate) fn unified_diff(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+    let mut lines = render_hunks(&ops);
+
+    if lines.len() > MAX_DIFF_LINES {
+        let hidden = lines.len() - MAX_DIFF_LINES;
+        lines.truncate(MAX_DIFF_LINES);
+        lines.push(format!("... {hidden} more diff lines not shown"));
+    }
+    lines.join("\n")
+}
+
+fn diff_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<Op<'a>> = old[..prefix].iter().map(|line| Op::Equal(line)).collect();
+    if old_middle.len().saturating_mul(new_middle.len()) <= MAX_ALIGNMENT_CELLS {
+        ops.extend(align(old_middle, new_middle));
+    } else {
+        ops.extend(old_middle.iter().map(|line| Op::Delete(line)));
+        ops.extend(new_middle.iter().map(|line| Op::Insert(line)));
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|line| Op::Equal(line)));
+    ops
+}
+
+/// Align two line slices through their longest common subsequence.
+fn align<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let width = new.len() + 1;
+    // `table[i * width + j]` is the LCS length of `old[i..]` and `new[j..]`.
+    let mut table = vec![0_u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            ops.push(Op::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| Op::Delete(line)));
+    ops.extend(new[j..].iter().map(|line| Op::Insert(line)));
+    ops
+}
+
+/// Group ops into `@@` hunks with `CONTEXT_LINES` of context.
+fn render_hunks(ops: &[Op<'_>]) -> Vec<String> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if !op.is_change() {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES + 1).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    // Line numbers (0-based) each op starts at in the old and new text.
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut old_line, mut new_line) = (0_usize, 0_usize);
+    for op in ops {
+        positions.push((old_line, new_line));
+        match op {
+            Op::Equal(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Op::Delete(_) => old_line += 1,
+            Op::Insert(_) => new_line += 1,
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (start, end) in ranges {
+        let hunk = &ops[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Delete(_)))
+            .count();
+        let (old_start, new_start) = positions[start];
+        lines.push(format!(
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        lines.extend(hunk.iter().map(|op| match op {
+            Op::Equal(line) => format!(" {line}"),
+            Op::Delete(line) => format!("-{line}"),
+            Op::Insert(line) => format!("+{line}"),
+        }));
+    }
+    lines
+}
+
+/// `start,count` as written in a hunk header; empty ranges name the line before.
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{count}", start + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_in_the_middle_keeps_three_lines_of_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+
+        assert_eq!(
+            unified_diff(old, new),
+            "@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8"
+        );
+    }
+
+    #[test]
+    fn new_file_is_all_additions() {
+        assert_eq!(unified_diff("", "a\nb\n"), "@@ -0,0 +1,2 @@\n+a\n+b");
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let old = (1..=20)
+            .map(|n| format!("{n}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n";
+        let new: String = (1..=20)
+            .map(|n| match n {
+                2 => "two\n".to_string(),
+                19 => "nineteen\n".to_string(),
+                _ => n.to_string() + "\n",
+            })
+            .collect();
+
+        let diff = unified_diff(&old, &new);
+        assert_eq!(
+            diff.lines().filter(|line| line.starts_with("@@")).count(),
+            2
+        );
+        assert!(diff.contains("-19\n+nineteen"));
+    }
+
+    #[test]
+    fn identical_text_has_no_diff() {
+        assert!(unified_diff("same\n", "same").is_empty());
+    }
+
+    #[test]
+    fn long_diffs_are_truncated_with_a_summary() {
+        let new = (0..MAX_DIFF_LINES + 10)
+            .map(|n| format!("{n}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n";
+
+        let diff = unified_diff("", &new);
+        let lines: Vec<&str> = diff.lines().collect();
+        assert_eq!(lines.len(), MAX_DIFF_LINES + 1);
+        assert_eq!(lines[MAX_DIFF_LINES], "... 11 more diff lines not shown");
+    }
+}
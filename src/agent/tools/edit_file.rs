@@ -4,7 +4,11 @@
 //! literal find-and-replace edit to an existing file. Optional line scoping
 //! can be used to disambiguate duplicate matches.
 
+use crate::agent::approval_rules::ToolCall;
 use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tools::diff_preview::unified_diff;
+use crate::agent::tools::workspace_gate::confine_decision;
+use crate::agent::workspace::{ResolvedPath, WorkspaceRoots};
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
 use serdes_ai_agent::prelude::*;
@@ -43,10 +47,32 @@ impl ToolExecutor<McpToolContext> for EditFileExecutor {
         let start_line = parse_optional_line(&args, "start_line")?;
         let end_line = parse_optional_line(&args, "end_line")?;
 
-        let absolute_path = resolve_path(&path)?;
+        let resolved = resolve_path(&ctx.deps().workspace_roots, &path)?;
+        let absolute_path = resolved.path.clone();
         let approval_path = absolute_path.display().to_string();
 
-        check_approval(ctx.deps(), &approval_path, start_line, end_line).await?;
+        // Filter emojis from new_text (output) if enabled, but NOT from old_text
+        // (input must match file content exactly for find-and-replace to work)
+        let filtered_new_text = if ctx.deps().filter_emoji {
+            strip_emojis(&new_text)
+        } else {
+            new_text
+        };
+
+        let call = ToolCall::new("EditFile")
+            .with_arguments(&args)
+            .with_path(absolute_path.clone());
+        check_approval(
+            ctx.deps(),
+            &call,
+            &approval_path,
+            start_line,
+            end_line,
+            resolved.outside_workspace(),
+            &old_text,
+            &filtered_new_text,
+        )
+        .await?;
 
         let content = tokio::fs::read_to_string(&absolute_path)
             .await
@@ -71,20 +97,24 @@ impl ToolExecutor<McpToolContext> for EditFileExecutor {
         let absolute_match_start = scope.start_byte + first_match;
         let absolute_match_end = absolute_match_start + old_text.len();
 
-        // Filter emojis from new_text (output) if enabled, but NOT from old_text
-        // (input must match file content exactly for find-and-replace to work)
-        let filtered_new_text = if ctx.deps().filter_emoji {
-            strip_emojis(&new_text)
-        } else {
-            new_text
-        };
-
         let mut updated_content =
             String::with_capacity(content.len() - old_text.len() + filtered_new_text.len());
         updated_content.push_str(&content[..absolute_match_start]);
         updated_content.push_str(&filtered_new_text);
         updated_content.push_str(&content[absolute_match_end..]);
 
+        if let Some(checkpoints) = &ctx.deps().checkpoints {
+            checkpoints
+                .snapshot(&absolute_path)
+                .await
+                .map_err(|error| {
+                    ToolError::execution_failed(format!(
+                        "Failed to checkpoint '{}' before writing: {error}",
+                        absolute_path.display()
+                    ))
+                })?;
+        }
+
         tokio::fs::write(&absolute_path, &updated_content)
             .await
             .map_err(|error| map_write_error(&absolute_path, &error))?;
@@ -98,7 +128,7 @@ impl ToolExecutor<McpToolContext> for EditFileExecutor {
 }
 
 /// Strip emojis from a string, replacing them with empty string.
-fn strip_emojis(text: &str) -> String {
+pub(super) fn strip_emojis(text: &str) -> String {
     text.chars().filter(|c| !is_emoji(*c)).collect()
 }
 
@@ -133,15 +163,18 @@ const fn is_emoji(c: char) -> bool {
     )
 }
 
-/// Validate that the path is absolute and return it as a `PathBuf`.
-fn resolve_path(path: &str) -> Result<PathBuf, ToolError> {
-    let file_path = Path::new(path);
-    if !file_path.is_absolute() {
-        return Err(ToolError::execution_failed(
-            "The 'path' argument must be an absolute path",
-        ));
-    }
-    Ok(file_path.to_path_buf())
+/// Resolve the path through the workspace roots.
+///
+/// Relative paths are only accepted when a workspace root is configured.
+pub(super) fn resolve_path(
+    workspace: &WorkspaceRoots,
+    path: &str,
+) -> Result<ResolvedPath, ToolError> {
+    workspace.resolve(Path::new(path)).ok_or_else(|| {
+        ToolError::execution_failed(
+            "The 'path' argument must be an absolute path when no workspace root is set",
+        )
+    })
 }
 
 fn required_string(args: &serde_json::Value, key: &str) -> Result<String, ToolError> {
@@ -246,7 +279,7 @@ fn resolve_search_scope(
     })
 }
 
-fn map_read_error(path: &Path, error: &std::io::Error) -> ToolError {
+pub(super) fn map_read_error(path: &Path, error: &std::io::Error) -> ToolError {
     let message = match error.kind() {
         std::io::ErrorKind::NotFound => format!("File not found: {}", path.display()),
         std::io::ErrorKind::PermissionDenied => {
@@ -257,7 +290,7 @@ fn map_read_error(path: &Path, error: &std::io::Error) -> ToolError {
     ToolError::execution_failed(message)
 }
 
-fn map_write_error(path: &Path, error: &std::io::Error) -> ToolError {
+pub(super) fn map_write_error(path: &Path, error: &std::io::Error) -> ToolError {
     let message = match error.kind() {
         std::io::ErrorKind::PermissionDenied => {
             format!("Permission denied writing file: {}", path.display())
@@ -269,16 +302,22 @@ fn map_write_error(path: &Path, error: &std::io::Error) -> ToolError {
 }
 
 /// Check tool approval policy and await user decision if required.
+#[allow(clippy::too_many_arguments)]
 async fn check_approval(
     tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
     path: &str,
     start_line: Option<usize>,
     end_line: Option<usize>,
+    outside_workspace: &[PathBuf],
+    old_text: &str,
+    new_text: &str,
 ) -> Result<(), ToolError> {
     let decision = {
         let policy = tool_context.policy.lock().await;
-        policy.evaluate("EditFile")
+        policy.decide(call, |policy| policy.evaluate("EditFile"))
     };
+    let decision = confine_decision(tool_context, "EditFile", decision, outside_workspace).await?;
 
     match decision {
         ToolApprovalDecision::Allow => Ok(()),
@@ -294,7 +333,8 @@ async fn check_approval(
             );
 
             // Build rich context for approval UI
-            let mut context = ToolApprovalContext::new("EditFile", ToolCategory::FileEdit, path);
+            let mut context = ToolApprovalContext::new("EditFile", ToolCategory::FileEdit, path)
+                .with_diff(unified_diff(old_text, new_text));
             if let Some(start) = start_line {
                 context = context.with_detail(
                     "line_range",
@@ -335,7 +375,7 @@ pub fn get_edit_file_tool_definition() -> ToolDefinition {
         "properties": {
             "path": {
                 "type": "string",
-                "description": "The absolute path to the file to edit"
+                "description": "The path to the file to edit; absolute, or relative to the workspace root"
             },
             "old_text": {
                 "type": "string",
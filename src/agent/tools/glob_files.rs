@@ -0,0 +1,273 @@
+//! `Glob` tool implementation.
+//!
+//! This module provides a built-in `GlobExecutor` that finds files by path
+//! pattern under a directory. The walk honours `.gitignore` like
+//! `ListDirectory`, and patterns are matched the way `Search` matches its
+//! `include` filter: against the path relative to the base directory, or
+//! against the file name when the pattern has no `/`.
+
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tools::workspace_gate::{confine_decision, resolve_read_path};
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+use glob::Pattern;
+use ignore::WalkBuilder;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+use std::path::{Path, PathBuf};
+
+/// Maximum number of paths returned by the tool.
+const MAX_RESULTS: usize = 500;
+
+/// Executor for the `Glob` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct GlobExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for GlobExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let pattern = args
+            .get("pattern")
+            .and_then(serde_json::Value::as_str)
+            .filter(|pattern| !pattern.is_empty())
+            .ok_or_else(|| ToolError::execution_failed("Missing required 'pattern' argument"))?
+            .to_string();
+        let compiled = Pattern::new(&pattern).map_err(|e| {
+            ToolError::execution_failed(format!("Invalid glob pattern '{pattern}': {e}"))
+        })?;
+        let path = match args.get("path").and_then(serde_json::Value::as_str) {
+            Some(path) if !path.is_empty() => path,
+            _ => ".",
+        };
+
+        let resolved = resolve_read_path(&ctx.deps().workspace_roots, path)?;
+        let base = resolved.path.clone();
+        let call = ToolCall::new("Glob")
+            .with_arguments(&args)
+            .with_path(base.clone());
+        check_approval(
+            ctx.deps(),
+            &call,
+            &pattern,
+            &base.display().to_string(),
+            resolved.outside_workspace(),
+        )
+        .await?;
+
+        if !tokio::fs::metadata(&base)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            return Err(ToolError::execution_failed(format!(
+                "Directory not found: {}",
+                base.display()
+            )));
+        }
+
+        let matches_name_only = !pattern.contains('/');
+        let (paths, truncated) =
+            tokio::task::spawn_blocking(move || find_matches(&base, &compiled, matches_name_only))
+                .await
+                .map_err(|e| ToolError::execution_failed(format!("Glob failed: {e}")))?;
+
+        Ok(ToolReturn::text(format_results(
+            &pattern, &paths, truncated,
+        )))
+    }
+}
+
+/// Files under `base` matching `pattern`, as sorted relative paths.
+fn find_matches(base: &Path, pattern: &Pattern, matches_name_only: bool) -> (Vec<String>, bool) {
+    let walker = WalkBuilder::new(base)
+        .standard_filters(true)
+        .sort_by_file_name(std::cmp::Ord::cmp)
+        .build();
+
+    let mut paths = Vec::new();
+    for entry in walker.flatten() {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(base)
+            .unwrap_or_else(|_| entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        let name = entry.file_name().to_string_lossy();
+        if pattern.matches(&relative) || (matches_name_only && pattern.matches(&name)) {
+            if paths.len() == MAX_RESULTS {
+                return (paths, true);
+            }
+            paths.push(relative);
+        }
+    }
+    (paths, false)
+}
+
+fn format_results(pattern: &str, paths: &[String], truncated: bool) -> String {
+    if paths.is_empty() {
+        return format!("No files found matching '{pattern}'");
+    }
+    let mut output = format!("Found {} file(s) matching '{pattern}':\n", paths.len());
+    output.push_str(&paths.join("\n"));
+    if truncated {
+        output.push_str(&format!(
+            "\n... results truncated after {MAX_RESULTS} files; use a more specific pattern"
+        ));
+    }
+    output
+}
+
+/// Check tool approval policy and await user decision if required.
+async fn check_approval(
+    tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
+    pattern: &str,
+    path: &str,
+    outside_workspace: &[PathBuf],
+) -> Result<(), ToolError> {
+    let decision = {
+        let policy = tool_context.policy.lock().await;
+        policy.decide(call, |policy| policy.evaluate("Glob"))
+    };
+    let decision = confine_decision(tool_context, "Glob", decision, outside_workspace).await?;
+
+    match decision {
+        ToolApprovalDecision::Allow => Ok(()),
+        ToolApprovalDecision::Deny => Err(ToolError::execution_failed(
+            "Tool execution denied by policy",
+        )),
+        ToolApprovalDecision::AskUser => {
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let waiter = tool_context.approval_gate.wait_for_approval(
+                request_id.clone(),
+                "Glob".to_string(),
+                tool_context.conversation_id,
+            );
+
+            let context = ToolApprovalContext::new("Glob", ToolCategory::Search, path)
+                .with_detail("pattern", pattern);
+
+            if tool_context
+                .view_tx
+                .try_send(ViewCommand::ToolApprovalRequest {
+                    conversation_id: tool_context.conversation_id,
+                    request_id: request_id.clone(),
+                    context,
+                })
+                .is_err()
+            {
+                let _ = tool_context.approval_gate.resolve(&request_id, false);
+                return Err(ToolError::execution_failed(
+                    "Failed to send approval request to UI (channel full or closed)",
+                ));
+            }
+
+            let approved = waiter.wait().await.unwrap_or(false);
+            if approved {
+                Ok(())
+            } else {
+                Err(ToolError::execution_failed("Tool execution denied by user"))
+            }
+        }
+    }
+}
+
+/// Get the `Glob` tool definition.
+#[must_use]
+pub fn get_glob_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "pattern": {
+                "type": "string",
+                "description": "Glob pattern, for example '*.toml' (matches file names anywhere) or 'src/**/*.rs' (matches paths relative to 'path')"
+            },
+            "path": {
+                "type": "string",
+                "description": "Directory to search under; absolute, or relative to the workspace root. Defaults to the workspace root."
+            }
+        },
+        "required": ["pattern"]
+    });
+
+    ToolDefinition::new(
+        "Glob",
+        "Find files whose paths match a glob pattern, skipping files ignored by .gitignore. Returns paths relative to the search directory. Use this instead of running find.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn seed_tree() -> tempfile::TempDir {
+        let dir = tempdir().expect("temp dir should be created");
+        fs::create_dir_all(dir.path().join("src/agent")).unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        fs::write(dir.path().join("src/agent/mod.rs"), "").unwrap();
+        fs::write(dir.path().join("target/build.rs"), "").unwrap();
+        dir
+    }
+
+    fn glob(dir: &Path, pattern: &str) -> (Vec<String>, bool) {
+        let pattern = Pattern::new(pattern).unwrap();
+        find_matches(dir, &pattern, !pattern.as_str().contains('/'))
+    }
+
+    #[test]
+    fn name_patterns_match_at_any_depth_and_skip_ignored() {
+        let dir = seed_tree();
+
+        let (paths, truncated) = glob(dir.path(), "*.rs");
+
+        assert_eq!(paths, vec!["src/agent/mod.rs", "src/main.rs"]);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn path_patterns_match_relative_paths() {
+        let dir = seed_tree();
+
+        assert_eq!(glob(dir.path(), "src/*/*.rs").0, vec!["src/agent/mod.rs"]);
+        assert_eq!(glob(dir.path(), "Cargo.*").0, vec!["Cargo.toml"]);
+    }
+
+    #[tokio::test]
+    async fn glob_rejects_invalid_patterns() {
+        let error = GlobExecutor
+            .execute(
+                serde_json::json!({ "pattern": "src/[" }),
+                &RunContext::new(McpToolContext::default(), "test-model"),
+            )
+            .await
+            .expect_err("invalid pattern should fail");
+
+        assert!(error.to_string().contains("Invalid glob pattern"));
+    }
+
+    #[test]
+    fn get_glob_tool_definition_returns_valid_schema() {
+        let def = get_glob_tool_definition();
+        assert_eq!(def.name, "Glob");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
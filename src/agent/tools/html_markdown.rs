@@ -0,0 +1,455 @@
+//! HTML to Markdown conversion for `WebFetch`.
+//!
+//! This is a forgiving single-pass converter aimed at readability, not
+//! fidelity: it keeps headings, paragraphs, lists, links, emphasis, code and
+//! preformatted blocks, drops scripts, styles and other non-content
+//! elements, and never fails on malformed markup.
+
+use std::fmt::Write as _;
+use url::Url;
+
+/// Elements whose content is never shown.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "canvas", "head",
+];
+
+/// Elements rendered as paragraphs, separated by blank lines.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "section", "article", "main", "header", "footer", "nav", "aside", "table", "form",
+    "figure", "dl", "details", "summary", "address",
+];
+
+/// Elements that start on their own line.
+const LINE_ELEMENTS: &[&str] = &["tr", "dt", "dd", "figcaption", "caption"];
+
+/// Convert `html` to Markdown; `base` resolves relative links.
+pub(crate) fn html_to_markdown(html: &str, base: Option<&Url>) -> String {
+    let mut converter = Converter {
+        base,
+        ..Converter::default()
+    };
+    converter.run(html);
+    converter.finish()
+}
+
+#[derive(Default)]
+struct Converter<'a> {
+    base: Option<&'a Url>,
+    out: String,
+    title: Option<String>,
+    in_title: bool,
+    pending_space: bool,
+    pre_depth: usize,
+    /// `None` for `<ul>`, `Some(next number)` for `<ol>`.
+    lists: Vec<Option<usize>>,
+    /// Targets of the open `<a>` elements.
+    links: Vec<Option<String>>,
+    /// Whether the current table row already has a cell.
+    row_has_cell: bool,
+}
+
+impl Converter<'_> {
+    fn run(&mut self, html: &str) {
+        // ASCII lowercasing keeps byte offsets, so searches in `lower` index `html`.
+        let lower = html.to_ascii_lowercase();
+        let mut pos = 0;
+        while pos < html.len() {
+            let Some(offset) = html[pos..].find('<') else {
+                self.text(&html[pos..]);
+                break;
+            };
+            self.text(&html[pos..pos + offset]);
+            pos += offset;
+
+            if lower[pos..].starts_with("<!--") {
+                pos = lower[pos..]
+                    .find("-->")
+                    .map_or(html.len(), |end| pos + end + 3);
+                continue;
+            }
+            let rest = &html[pos + 1..];
+            let closing = rest.starts_with('/');
+            let name_start = pos + 1 + usize::from(closing);
+            let name_len = html[name_start..]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(html.len() - name_start);
+            if name_len == 0 && !rest.starts_with('!') && !rest.starts_with('?') {
+                // A stray '<' in text.
+                self.text("<");
+                pos += 1;
+                continue;
+            }
+            let Some(tag_end) = find_tag_end(html, name_start + name_len) else {
+                break;
+            };
+            let name = &lower[name_start..name_start + name_len];
+            let attrs = &html[name_start + name_len..tag_end];
+            pos = tag_end + 1;
+
+            if name.is_empty() {
+                // <!DOCTYPE ...> or <?xml ...?>
+                continue;
+            }
+            if closing {
+                self.close(name);
+            } else if SKIPPED_ELEMENTS.contains(&name) && !attrs.trim_end().ends_with('/') {
+                let end_tag = format!("</{name}");
+                pos = lower[pos..].find(&end_tag).map_or(html.len(), |end| {
+                    let close = pos + end;
+                    find_tag_end(html, close + end_tag.len()).map_or(html.len(), |gt| gt + 1)
+                });
+                if name == "head" {
+                    self.title = extract_title(&html[..pos], &lower[..pos]);
+                }
+            } else {
+                self.open(name, attrs);
+            }
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.blank_line();
+                let level = usize::from(name.as_bytes()[1] - b'0');
+                self.out.push_str(&"#".repeat(level));
+                self.out.push(' ');
+            }
+            "title" => self.in_title = true,
+            "br" => {
+                self.trim_trailing_spaces();
+                self.out.push('\n');
+                self.pending_space = false;
+            }
+            "hr" => {
+                self.blank_line();
+                self.out.push_str("---");
+                self.blank_line();
+            }
+            "ul" | "ol" => {
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+                self.lists.push((name == "ol").then_some(1));
+            }
+            "li" => {
+                self.newline();
+                let depth = self.lists.len().saturating_sub(1);
+                self.out.push_str(&"  ".repeat(depth));
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let _ = write!(self.out, "{number}. ");
+                        *number += 1;
+                    }
+                    _ => self.out.push_str("- "),
+                }
+            }
+            "blockquote" => {
+                self.blank_line();
+                self.out.push_str("> ");
+            }
+            "pre" => {
+                self.blank_line();
+                self.out.push_str("```\n");
+                self.pre_depth += 1;
+            }
+            "code" | "kbd" | "samp" if self.pre_depth == 0 => self.inline("`"),
+            "strong" | "b" => self.inline("**"),
+            "em" | "i" => self.inline("*"),
+            "a" => {
+                let href = attribute(attrs, "href")
+                    .filter(|href| !href.starts_with('#') && !href.starts_with("javascript:"))
+                    .map(|href| self.resolve(&href));
+                if href.is_some() {
+                    self.inline("[");
+                }
+                self.links.push(href);
+            }
+            "img" => {
+                let alt = attribute(attrs, "alt").unwrap_or_default();
+                if let Some(src) = attribute(attrs, "src").filter(|_| !alt.trim().is_empty()) {
+                    let src = self.resolve(&src);
+                    self.inline(&format!("![{}]({src})", alt.trim()));
+                }
+            }
+            "td" | "th" => {
+                if self.row_has_cell {
+                    self.inline(" | ");
+                }
+                self.row_has_cell = true;
+            }
+            _ if LINE_ELEMENTS.contains(&name) => {
+                self.newline();
+                self.row_has_cell = false;
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => self.blank_line(),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote" => self.blank_line(),
+            "title" => self.in_title = false,
+            "ul" | "ol" => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            "pre" if self.pre_depth > 0 => {
+                self.pre_depth -= 1;
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.blank_line();
+            }
+            "code" | "kbd" | "samp" if self.pre_depth == 0 => self.out.push('`'),
+            "strong" | "b" => self.out.push_str("**"),
+            "em" | "i" => self.out.push('*'),
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    let _ = write!(self.out, "]({href})");
+                }
+            }
+            _ if LINE_ELEMENTS.contains(&name) => self.newline(),
+            _ if BLOCK_ELEMENTS.contains(&name) => self.blank_line(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        if raw.is_empty() {
+            return;
+        }
+        let decoded = decode_entities(raw);
+        if self.in_title {
+            self.title
+                .get_or_insert_with(String::new)
+                .push_str(decoded.trim());
+            return;
+        }
+        if self.pre_depth > 0 {
+            self.out.push_str(&decoded);
+            return;
+        }
+        for c in decoded.chars() {
+            if c.is_whitespace() {
+                self.pending_space = true;
+            } else {
+                self.flush_space();
+                self.out.push(c);
+            }
+        }
+    }
+
+    /// Push an opening marker, keeping the space before it.
+    fn inline(&mut self, marker: &str) {
+        self.flush_space();
+        self.out.push_str(marker);
+    }
+
+    fn flush_space(&mut self) {
+        if self.pending_space && !self.out.is_empty() && !self.out.ends_with(['\n', ' ']) {
+            self.out.push(' ');
+        }
+        self.pending_space = false;
+    }
+
+    fn trim_trailing_spaces(&mut self) {
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+    }
+
+    fn newline(&mut self) {
+        self.trim_trailing_spaces();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        self.pending_space = false;
+    }
+
+    fn blank_line(&mut self) {
+        self.newline();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn resolve(&self, href: &str) -> String {
+        self.base
+            .and_then(|base| base.join(href).ok())
+            .map_or_else(|| href.to_string(), String::from)
+    }
+
+    fn finish(self) -> String {
+        let mut lines: Vec<&str> = Vec::new();
+        for line in self.out.lines().map(str::trim_end) {
+            // Collapse runs of blank lines left by empty elements.
+            if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+        let body = lines.join("\n").trim().to_string();
+        match self.title.filter(|title| !title.is_empty()) {
+            Some(title) if !body.starts_with("# ") => format!("# {title}\n\n{body}"),
+            _ => body,
+        }
+    }
+}
+
+/// Index of the `>` closing a tag, skipping quoted attribute values.
+fn find_tag_end(html: &str, from: usize) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (offset, c) in html[from..].char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(from + offset),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn extract_title(html: &str, lower: &str) -> Option<String> {
+    let start = lower.find("<title")?;
+    let open_end = find_tag_end(html, start)? + 1;
+    let end = lower[open_end..].find("</title")? + open_end;
+    let title = decode_entities(&html[open_end..end]);
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// Value of attribute `name` in a tag's attribute text.
+fn attribute(attrs: &str, name: &str) -> Option<String> {
+    let lower = attrs.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        let preceded_by_space = lower[..start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_whitespace);
+        let rest = attrs[search..].trim_start();
+        if !preceded_by_space || !rest.starts_with('=') {
+            continue;
+        }
+        let value = rest[1..].trim_start();
+        let raw = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value
+                .split(|c: char| c.is_whitespace() || c == '>')
+                .next()
+                .unwrap_or_default(),
+        };
+        return Some(decode_entities(raw));
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| decode_entity(&rest[1..=end]).map(|c| (c, end + 2)));
+        if let Some((c, len)) = decoded {
+            out.push(c);
+            rest = &rest[len..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201C}',
+        "rdquo" => '\u{201D}',
+        "copy" => '\u{00A9}',
+        "reg" => '\u{00AE}',
+        "trade" => '\u{2122}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_headings_paragraphs_and_inline_markup() {
+        let html = "<html><head><title>Release &amp; Notes</title><style>p{}</style></head>\
+            <body><h2>What's new</h2><p>Faster <b>builds</b>, see\n  <a href=\"/docs/build\">the docs</a>.</p>\
+            <script>alert('x')</script><p>Use <code>cargo run</code></p></body></html>";
+        let base = Url::parse("https://example.com/blog/post").unwrap();
+
+        assert_eq!(
+            html_to_markdown(html, Some(&base)),
+            "# Release & Notes\n\n## What's new\n\nFaster **builds**, see [the docs](https://example.com/docs/build).\n\nUse `cargo run`"
+        );
+    }
+
+    #[test]
+    fn converts_lists_and_preformatted_blocks() {
+        let html = "<ul><li>one</li><li>two<ol><li>a</li><li>b</li></ol></li></ul>\
+            <pre><code>fn main() {\n    println!(\"&lt;hi&gt;\");\n}</code></pre>";
+
+        assert_eq!(
+            html_to_markdown(html, None),
+            "- one\n- two\n  1. a\n  2. b\n\n```\nfn main() {\n    println!(\"<hi>\");\n}\n```"
+        );
+    }
+
+    #[test]
+    fn tolerates_malformed_markup() {
+        let html = "a < b && <p class='x>y'>c &unknown; &#x41;<!-- hidden --><div";
+
+        assert_eq!(html_to_markdown(html, None), "a < b &&\n\nc &unknown; A");
+    }
+
+    #[test]
+    fn attribute_values_are_read_with_any_quoting() {
+        assert_eq!(
+            attribute(" data-href=\"no\" href='/a b'", "href").as_deref(),
+            Some("/a b")
+        );
+        assert_eq!(
+            attribute(" HREF=/plain >", "href").as_deref(),
+            Some("/plain")
+        );
+        assert_eq!(attribute(" title=\"x\"", "href"), None);
+    }
+}
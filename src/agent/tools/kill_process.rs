@@ -0,0 +1,88 @@
+//! `KillProcess` tool implementation.
+//!
+//! This module provides a built-in `KillProcessExecutor` that stops a
+//! background `ShellExec` process of the current conversation. Stopping a
+//! process only undoes what an approved command started, so it is not
+//! approval-gated.
+
+use crate::agent::processes::BackgroundProcesses;
+use crate::llm::client_agent::McpToolContext;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+
+/// Executor for the `KillProcess` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct KillProcessExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for KillProcessExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let process_id = args
+            .get("process_id")
+            .and_then(serde_json::Value::as_str)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| ToolError::execution_failed("Missing required 'process_id' argument"))?;
+
+        let killed = BackgroundProcesses::global()
+            .kill(ctx.deps().conversation_id, process_id)
+            .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+        Ok(ToolReturn::text(if killed {
+            format!("Killed {process_id}")
+        } else {
+            format!("{process_id} had already exited")
+        }))
+    }
+}
+
+/// Get the `KillProcess` tool definition.
+#[must_use]
+pub fn get_kill_process_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "process_id": {
+                "type": "string",
+                "description": "Id returned by ShellExec when the command was started in the background"
+            }
+        },
+        "required": ["process_id"]
+    });
+
+    ToolDefinition::new(
+        "KillProcess",
+        "Stop a background process started by ShellExec. Its unread output can still be read afterwards.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn kill_process_requires_a_process_id() {
+        let error = KillProcessExecutor
+            .execute(
+                serde_json::json!({}),
+                &RunContext::new(McpToolContext::default(), "test-model"),
+            )
+            .await
+            .expect_err("missing id should fail");
+
+        assert!(error.to_string().contains("process_id"));
+    }
+
+    #[test]
+    fn get_kill_process_tool_definition_returns_valid_schema() {
+        let def = get_kill_process_tool_definition();
+        assert_eq!(def.name, "KillProcess");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
@@ -0,0 +1,337 @@
+//! `ListDirectory` tool implementation.
+//!
+//! This module provides a built-in `ListDirectoryExecutor` that prints a
+//! directory tree with file sizes. The walk honours `.gitignore` and hidden
+//! files the same way the `Search` fallback does.
+
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tools::workspace_gate::{confine_decision, resolve_read_path};
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+use ignore::WalkBuilder;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+use std::path::{Path, PathBuf};
+
+/// Depth listed when the call does not ask for one.
+const DEFAULT_DEPTH: usize = 1;
+/// Deepest tree the tool will list.
+const MAX_DEPTH: usize = 5;
+/// Maximum number of entries listed before truncation.
+const MAX_ENTRIES: usize = 500;
+
+/// Executor for the `ListDirectory` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct ListDirectoryExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for ListDirectoryExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let path = match args.get("path").and_then(serde_json::Value::as_str) {
+            Some(path) if !path.is_empty() => path,
+            _ => ".",
+        };
+        let depth = parse_depth(&args)?;
+        let include_hidden = args
+            .get("include_hidden")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let resolved = resolve_read_path(&ctx.deps().workspace_roots, path)?;
+        let root = resolved.path.clone();
+        let call = ToolCall::new("ListDirectory")
+            .with_arguments(&args)
+            .with_path(root.clone());
+        check_approval(
+            ctx.deps(),
+            &call,
+            &root.display().to_string(),
+            resolved.outside_workspace(),
+        )
+        .await?;
+
+        match tokio::fs::metadata(&root).await {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => {
+                return Err(ToolError::execution_failed(format!(
+                    "Path is not a directory: {}",
+                    root.display()
+                )));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(ToolError::execution_failed(format!(
+                    "Directory not found: {}",
+                    root.display()
+                )));
+            }
+            Err(e) => {
+                return Err(ToolError::execution_failed(format!(
+                    "Failed to access directory '{}': {e}",
+                    root.display()
+                )));
+            }
+        }
+
+        tokio::task::spawn_blocking(move || list_tree(&root, depth, include_hidden))
+            .await
+            .map(ToolReturn::text)
+            .map_err(|e| ToolError::execution_failed(format!("Directory listing failed: {e}")))
+    }
+}
+
+fn parse_depth(args: &serde_json::Value) -> Result<usize, ToolError> {
+    let Some(value) = args.get("depth") else {
+        return Ok(DEFAULT_DEPTH);
+    };
+    value
+        .as_u64()
+        .and_then(|depth| usize::try_from(depth).ok())
+        .filter(|depth| (1..=MAX_DEPTH).contains(depth))
+        .ok_or_else(|| {
+            ToolError::execution_failed(format!(
+                "Invalid 'depth' argument: expected an integer from 1 to {MAX_DEPTH}"
+            ))
+        })
+}
+
+/// Render the tree under `root`, directories suffixed with `/` and files
+/// followed by their size.
+fn list_tree(root: &Path, depth: usize, include_hidden: bool) -> String {
+    let walker = WalkBuilder::new(root)
+        .standard_filters(true)
+        .hidden(!include_hidden)
+        .max_depth(Some(depth))
+        .sort_by_file_name(std::cmp::Ord::cmp)
+        .build();
+
+    let mut lines = vec![format!("{}/", root.display())];
+    let mut truncated = false;
+    for entry in walker.flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
+        if lines.len() > MAX_ENTRIES {
+            truncated = true;
+            break;
+        }
+        let indent = "  ".repeat(entry.depth());
+        let name = entry.file_name().to_string_lossy();
+        let is_dir = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir());
+        if is_dir {
+            lines.push(format!("{indent}{name}/"));
+        } else {
+            let size = entry.metadata().map_or(0, |metadata| metadata.len());
+            lines.push(format!("{indent}{name} ({})", format_size(size)));
+        }
+    }
+
+    if lines.len() == 1 {
+        lines.push("  (empty)".to_string());
+    }
+    if truncated {
+        lines.push(format!(
+            "... listing truncated after {MAX_ENTRIES} entries; list a subdirectory or lower 'depth'"
+        ));
+    }
+    lines.join("\n")
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_wrap
+)]
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let exp = (bytes.ilog2() / 10).min(UNITS.len() as u32 - 1);
+    let value = bytes as f64 / (1024_f64.powi(exp as i32));
+    format!("{:.1} {}", value, UNITS[exp as usize])
+}
+
+/// Check tool approval policy and await user decision if required.
+async fn check_approval(
+    tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
+    path: &str,
+    outside_workspace: &[PathBuf],
+) -> Result<(), ToolError> {
+    let decision = {
+        let policy = tool_context.policy.lock().await;
+        policy.decide(call, |policy| policy.evaluate("ListDirectory"))
+    };
+    let decision =
+        confine_decision(tool_context, "ListDirectory", decision, outside_workspace).await?;
+
+    match decision {
+        ToolApprovalDecision::Allow => Ok(()),
+        ToolApprovalDecision::Deny => Err(ToolError::execution_failed(
+            "Tool execution denied by policy",
+        )),
+        ToolApprovalDecision::AskUser => {
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let waiter = tool_context.approval_gate.wait_for_approval(
+                request_id.clone(),
+                "ListDirectory".to_string(),
+                tool_context.conversation_id,
+            );
+
+            let context = ToolApprovalContext::new("ListDirectory", ToolCategory::FileRead, path);
+
+            if tool_context
+                .view_tx
+                .try_send(ViewCommand::ToolApprovalRequest {
+                    conversation_id: tool_context.conversation_id,
+                    request_id: request_id.clone(),
+                    context,
+                })
+                .is_err()
+            {
+                let _ = tool_context.approval_gate.resolve(&request_id, false);
+                return Err(ToolError::execution_failed(
+                    "Failed to send approval request to UI (channel full or closed)",
+                ));
+            }
+
+            let approved = waiter.wait().await.unwrap_or(false);
+            if approved {
+                Ok(())
+            } else {
+                Err(ToolError::execution_failed("Tool execution denied by user"))
+            }
+        }
+    }
+}
+
+/// Get the `ListDirectory` tool definition.
+#[must_use]
+pub fn get_list_directory_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "path": {
+                "type": "string",
+                "description": "Directory to list; absolute, or relative to the workspace root. Defaults to the workspace root."
+            },
+            "depth": {
+                "type": "integer",
+                "description": "How many levels to descend (default 1)",
+                "minimum": 1,
+                "maximum": MAX_DEPTH
+            },
+            "include_hidden": {
+                "type": "boolean",
+                "description": "Include dotfiles and dot-directories (default false)"
+            }
+        }
+    });
+
+    ToolDefinition::new(
+        "ListDirectory",
+        "List a directory as a tree with file sizes, skipping files ignored by .gitignore. Use this instead of running ls or find.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn read_approved_context() -> RunContext<McpToolContext> {
+        RunContext::new(
+            McpToolContext {
+                policy: std::sync::Arc::new(tokio::sync::Mutex::new(ToolApprovalPolicy {
+                    auto_approve_reads: true,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            },
+            "test-model",
+        )
+    }
+
+    fn output_text(result: ToolReturn) -> String {
+        let serdes_ai::core::messages::ToolReturnContent::Text { content } = result.content else {
+            panic!("expected text content");
+        };
+        content
+    }
+
+    #[tokio::test]
+    async fn list_directory_shows_tree_with_sizes_and_skips_ignored() {
+        let dir = tempdir().expect("temp dir should be created");
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("target/out.bin"), "x").unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.path().join("src/nested/deep.rs"), "").unwrap();
+
+        let args = serde_json::json!({ "path": dir.path(), "depth": 2 });
+        let output = output_text(
+            ListDirectoryExecutor
+                .execute(args, &read_approved_context())
+                .await
+                .expect("listing should succeed"),
+        );
+
+        assert!(output.contains("\n  src/\n    main.rs (13 B)\n    nested/"));
+        assert!(!output.contains("deep.rs"), "depth 2 stops above nested/");
+        assert!(!output.contains("target"));
+        assert!(!output.contains(".gitignore"));
+    }
+
+    #[tokio::test]
+    async fn list_directory_rejects_files_and_bad_depth() {
+        let dir = tempdir().expect("temp dir should be created");
+        let file = dir.path().join("file.txt");
+        fs::write(&file, "data").unwrap();
+
+        let error = ListDirectoryExecutor
+            .execute(
+                serde_json::json!({ "path": file }),
+                &read_approved_context(),
+            )
+            .await
+            .expect_err("a file is not a directory");
+        assert!(error.to_string().contains("not a directory"));
+
+        let error = ListDirectoryExecutor
+            .execute(
+                serde_json::json!({ "path": dir.path(), "depth": 9 }),
+                &read_approved_context(),
+            )
+            .await
+            .expect_err("depth above the maximum should fail");
+        assert!(error.to_string().contains("Invalid 'depth'"));
+    }
+
+    #[test]
+    fn format_size_uses_binary_units() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn get_list_directory_tool_definition_returns_valid_schema() {
+        let def = get_list_directory_tool_definition();
+        assert_eq!(def.name, "ListDirectory");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
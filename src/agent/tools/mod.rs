@@ -12,10 +12,31 @@
 //!   creation
 //! - `EditFile`: Apply an exact literal find-and-replace edit in an existing
 //!   file, with optional line range scoping for disambiguation
+//! - `ApplyPatch`: Apply a multi-file unified diff or edit list atomically,
+//!   locating hunks by fuzzy context matching
 //! - `ShellExec`: Execute shell commands with timeout and approval-policy
-//!   checks
+//!   checks, or start them in the background
+//! - `ReadProcessOutput`, `WriteProcessInput`, `KillProcess`: Follow, feed and
+//!   stop background `ShellExec` processes
 //! - `Search`: Search file contents recursively by regex with ripgrep-first
 //!   execution and built-in fallback
+//! - `ListDirectory`: List a directory tree with file sizes, honouring
+//!   `.gitignore`
+//! - `Glob`: Find files by path pattern, honouring `.gitignore`
+//! - `WebFetch`: Fetch an http(s) URL and return it as text, converting HTML
+//!   to markdown, gated by the policy's per-domain allow and deny lists
+//! - `ReadMcpResource`: Read a resource exposed by a connected MCP server, or
+//!   list the available ones, approved like a call of that server's tools
+//! - `TodoWrite`: Keep the conversation's task list, shown as a checklist
+//!   and carried across history compression
+//! - `Delegate`: Hand a task to a sub-agent with a fresh context, a chosen
+//!   profile and a tool allowlist, and return its report
+//!
+//! All of the file tools except `Search` resolve paths through the conversation's
+//! workspace roots; `workspace_gate` asks separately for anything they would
+//! touch outside them. The file-writing tools also snapshot each file into
+//! the turn's checkpoint before changing it, and attach a `diff_preview` of
+//! the change to their approval requests.
 //!
 //! # Adding New Native Tools
 //!
@@ -27,15 +48,39 @@
 //! 4. Register the tool in `client_agent.rs` in the `register_native_tools` function
 
 pub mod activate_skill;
+pub mod apply_patch;
+pub mod delegate;
+pub(crate) mod diff_preview;
 pub mod edit_file;
+pub mod glob_files;
+pub(crate) mod html_markdown;
+pub mod kill_process;
+pub mod list_directory;
+pub(crate) mod patch;
 pub mod read_file;
+pub mod read_mcp_resource;
+pub mod read_process_output;
 pub mod search;
 pub mod shell_exec;
+pub mod todo_write;
+pub mod web_fetch;
+pub(crate) mod workspace_gate;
 pub mod write_file;
+pub mod write_process_input;
 
 pub use activate_skill::{get_activate_skill_tool_definition, ActivateSkillExecutor};
+pub use apply_patch::{get_apply_patch_tool_definition, ApplyPatchExecutor};
+pub use delegate::{get_delegate_tool_definition, DelegateExecutor};
 pub use edit_file::{get_edit_file_tool_definition, EditFileExecutor};
+pub use glob_files::{get_glob_tool_definition, GlobExecutor};
+pub use kill_process::{get_kill_process_tool_definition, KillProcessExecutor};
+pub use list_directory::{get_list_directory_tool_definition, ListDirectoryExecutor};
 pub use read_file::{get_read_file_tool_definition, ReadFileExecutor};
+pub use read_mcp_resource::{get_read_mcp_resource_tool_definition, ReadMcpResourceExecutor};
+pub use read_process_output::{get_read_process_output_tool_definition, ReadProcessOutputExecutor};
 pub use search::{get_search_tool_definition, SearchExecutor};
 pub use shell_exec::{get_shell_exec_tool_definition, ShellExecExecutor};
+pub use todo_write::{get_todo_write_tool_definition, TodoWriteExecutor};
+pub use web_fetch::{get_web_fetch_tool_definition, WebFetchExecutor};
 pub use write_file::{get_write_file_tool_definition, WriteFileExecutor};
+pub use write_process_input::{get_write_process_input_tool_definition, WriteProcessInputExecutor};
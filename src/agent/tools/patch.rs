@@ -0,0 +1,420 @@
+//! Unified diff parsing and fuzzy hunk application for `ApplyPatch`.
+//!
+//! Hunks are located by their context and removed lines rather than by the
+//! line numbers in their headers, which models often get wrong; hunk line
+//! counts are not trusted either. Each hunk is looked for exactly, then
+//! ignoring trailing whitespace, then ignoring surrounding whitespace, and the
+//! match closest to the header's line number wins. Context lines keep the
+//! file's own text, so a whitespace-fuzzy match never rewrites them.
+
+const DEV_NULL: &str = "/dev/null";
+
+/// One file section of a unified diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FilePatch {
+    /// Target path, with git's `a/` and `b/` prefixes removed.
+    pub(crate) path: String,
+    /// Whether the old side is `/dev/null`.
+    pub(crate) is_new: bool,
+    pub(crate) hunks: Vec<Hunk>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// A run of changes and the context that locates it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Hunk {
+    /// 1-based old-side start line, when the header has one.
+    old_start: Option<usize>,
+    lines: Vec<HunkLine>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Fuzz {
+    Exact,
+    TrailingWhitespace,
+    Whitespace,
+}
+
+impl Fuzz {
+    fn matches(self, file_line: &str, hunk_line: &str) -> bool {
+        match self {
+            Self::Exact => file_line == hunk_line,
+            Self::TrailingWhitespace => file_line.trim_end() == hunk_line.trim_end(),
+            Self::Whitespace => file_line.trim() == hunk_line.trim(),
+        }
+    }
+}
+
+impl Hunk {
+    /// Hunk replacing the lines of `old` with those of `new`, located by
+    /// content alone.
+    pub(crate) fn replacement(old: &str, new: &str) -> Self {
+        let lines = old
+            .lines()
+            .map(|line| HunkLine::Remove(line.to_string()))
+            .chain(new.lines().map(|line| HunkLine::Add(line.to_string())))
+            .collect();
+        Self {
+            old_start: None,
+            lines,
+        }
+    }
+
+    /// Rewrite the added lines, leaving context and removals as given.
+    pub(crate) fn map_added(&mut self, f: impl Fn(&str) -> String) {
+        for line in &mut self.lines {
+            if let HunkLine::Add(text) = line {
+                *text = f(text);
+            }
+        }
+    }
+
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    fn new_len(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| !matches!(line, HunkLine::Remove(_)))
+            .count()
+    }
+}
+
+/// Split a unified diff into per-file patches.
+///
+/// Git metadata (`diff --git`, `index`, mode lines) and prose between files
+/// are ignored.
+///
+/// # Errors
+///
+/// Returns a message when the text has no file headers, a hunk comes before
+/// any header, a file has no hunks, or a file is deleted or renamed.
+pub(crate) fn parse_patch(text: &str) -> Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut patches: Vec<FilePatch> = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        if let Some((old_path, new_path)) = file_header(&lines, index) {
+            patches.push(file_patch(old_path, new_path)?);
+            index += 2;
+        } else if lines[index].starts_with("@@") {
+            let file = patches
+                .last_mut()
+                .ok_or("Found a '@@' hunk before any '--- '/'+++ ' file header")?;
+            let (hunk, next) = parse_hunk(&lines, index);
+            file.hunks.push(hunk);
+            index = next;
+        } else {
+            index += 1;
+        }
+    }
+
+    if patches.is_empty() {
+        return Err("No '--- path' / '+++ path' file headers found in the patch".to_string());
+    }
+    if let Some(empty) = patches.iter().find(|patch| patch.hunks.is_empty()) {
+        return Err(format!("No hunks given for '{}'", empty.path));
+    }
+    Ok(patches)
+}
+
+/// Old and new paths when `lines[index]` starts a `---`/`+++` header pair.
+fn file_header<'a>(lines: &[&'a str], index: usize) -> Option<(&'a str, &'a str)> {
+    let old = lines.get(index)?.strip_prefix("--- ")?;
+    let new = lines.get(index + 1)?.strip_prefix("+++ ")?;
+    Some((header_path(old), header_path(new)))
+}
+
+/// Path from a header line, without a trailing timestamp.
+fn header_path(raw: &str) -> &str {
+    raw.split('\t').next().unwrap_or(raw).trim_end()
+}
+
+fn file_patch(old_path: &str, new_path: &str) -> Result<FilePatch, String> {
+    if new_path == DEV_NULL {
+        return Err(format!("Deleting files is not supported ('{old_path}')"));
+    }
+    let is_new = old_path == DEV_NULL;
+    let git_style = (is_new || old_path.starts_with("a/")) && new_path.starts_with("b/");
+    let path = if git_style { &new_path[2..] } else { new_path };
+    if !is_new {
+        let old = if git_style { &old_path[2..] } else { old_path };
+        if old != path {
+            return Err(format!(
+                "Renaming files is not supported ('{old}' -> '{path}')"
+            ));
+        }
+    }
+    Ok(FilePatch {
+        path: path.to_string(),
+        is_new,
+        hunks: Vec::new(),
+    })
+}
+
+/// Parse the hunk whose `@@` header is at `start`; returns it and the index
+/// of the first line after it.
+fn parse_hunk(lines: &[&str], start: usize) -> (Hunk, usize) {
+    let old_start = lines[start]
+        .strip_prefix("@@ -")
+        .and_then(|rest| rest.split([',', ' ']).next())
+        .and_then(|number| number.parse().ok());
+
+    let mut hunk_lines = Vec::new();
+    // Blank lines stand for empty context lines, except at the end of a hunk.
+    let mut trailing_blank = 0;
+    let mut index = start + 1;
+    while index < lines.len() {
+        let line = lines[index];
+        if line.starts_with("@@")
+            || line.starts_with("diff ")
+            || file_header(lines, index).is_some()
+        {
+            break;
+        }
+        let parsed = match line.chars().next() {
+            None => Some(HunkLine::Context(String::new())),
+            Some(' ') => Some(HunkLine::Context(line[1..].to_string())),
+            Some('-') => Some(HunkLine::Remove(line[1..].to_string())),
+            Some('+') => Some(HunkLine::Add(line[1..].to_string())),
+            // "\ No newline at end of file"
+            Some('\\') => None,
+            Some(_) => break,
+        };
+        if line.is_empty() {
+            trailing_blank += 1;
+        } else if parsed.is_some() {
+            trailing_blank = 0;
+        }
+        hunk_lines.extend(parsed);
+        index += 1;
+    }
+    hunk_lines.truncate(hunk_lines.len() - trailing_blank);
+
+    (
+        Hunk {
+            old_start,
+            lines: hunk_lines,
+        },
+        index,
+    )
+}
+
+/// Apply `hunks` to `content` in order.
+///
+/// Line endings and the trailing newline follow the original content.
+///
+/// # Errors
+///
+/// Returns the 0-based index and reason of every hunk that could not be
+/// placed; the other hunks are still tried so all failures are reported.
+pub(crate) fn apply_hunks(content: &str, hunks: &[Hunk]) -> Result<String, Vec<(usize, String)>> {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let trailing_newline = content.is_empty() || content.ends_with('\n');
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut failures = Vec::new();
+    // Lines before `cursor` belong to hunks already applied.
+    let mut cursor = 0;
+    // How far applied hunks have shifted the old line numbers.
+    let mut drift: isize = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old = hunk.old_lines();
+        let expected = hunk.old_start.map(|start| {
+            let start = if old.is_empty() {
+                start
+            } else {
+                start.saturating_sub(1)
+            };
+            start.saturating_add_signed(drift)
+        });
+        let position = match locate(&lines, cursor, &old, expected) {
+            Ok(position) => position,
+            Err(reason) => {
+                failures.push((index, reason));
+                continue;
+            }
+        };
+
+        let mut file_lines = lines[position..position + old.len()].iter();
+        let mut replacement = Vec::with_capacity(hunk.new_len());
+        for line in &hunk.lines {
+            match line {
+                HunkLine::Context(_) => replacement.extend(file_lines.next().cloned()),
+                HunkLine::Remove(_) => {
+                    file_lines.next();
+                }
+                HunkLine::Add(text) => replacement.push(text.clone()),
+            }
+        }
+        let added = replacement.len();
+        lines.splice(position..position + old.len(), replacement);
+        cursor = position + added;
+        drift += isize::try_from(added).unwrap_or(isize::MAX)
+            - isize::try_from(old.len()).unwrap_or(isize::MAX);
+    }
+
+    if !failures.is_empty() {
+        return Err(failures);
+    }
+    let mut updated = lines.join(newline);
+    if trailing_newline && !lines.is_empty() {
+        updated.push_str(newline);
+    }
+    Ok(updated)
+}
+
+/// Index in `lines` (at or after `from`) where `old` matches.
+fn locate(
+    lines: &[String],
+    from: usize,
+    old: &[&str],
+    expected: Option<usize>,
+) -> Result<usize, String> {
+    if old.is_empty() {
+        return Ok(expected.unwrap_or(lines.len()).clamp(from, lines.len()));
+    }
+    if lines.len() < old.len() + from {
+        return Err("context not found (the file is too short)".to_string());
+    }
+
+    for fuzz in [Fuzz::Exact, Fuzz::TrailingWhitespace, Fuzz::Whitespace] {
+        let candidates: Vec<usize> = (from..=lines.len() - old.len())
+            .filter(|&start| {
+                old.iter()
+                    .zip(&lines[start..])
+                    .all(|(hunk_line, file_line)| fuzz.matches(file_line, hunk_line))
+            })
+            .collect();
+        match (candidates.as_slice(), expected) {
+            ([], _) => {}
+            ([only], _) => return Ok(*only),
+            (_, Some(expected)) => {
+                return Ok(candidates
+                    .into_iter()
+                    .min_by_key(|start| start.abs_diff(expected))
+                    .unwrap_or(from));
+            }
+            (_, None) => {
+                return Err(format!(
+                    "context matches {} places; include more surrounding lines",
+                    candidates.len()
+                ));
+            }
+        }
+    }
+    Err("context not found".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_hunks(patch: &str) -> Vec<Hunk> {
+        let mut patches = parse_patch(patch).unwrap();
+        assert_eq!(patches.len(), 1);
+        patches.remove(0).hunks
+    }
+
+    #[test]
+    fn parses_git_style_multi_file_patch() {
+        let patch = "diff --git a/src/a.rs b/src/a.rs\nindex 1..2 100644\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1,2 +1,2 @@\n-one\n+uno\n two\n--- /dev/null\n+++ b/notes.md\n@@ -0,0 +1 @@\n+hello\n";
+
+        let patches = parse_patch(patch).unwrap();
+
+        assert_eq!(patches.len(), 2);
+        assert_eq!(patches[0].path, "src/a.rs");
+        assert!(!patches[0].is_new);
+        assert_eq!(patches[1].path, "notes.md");
+        assert!(patches[1].is_new);
+        assert_eq!(apply_hunks("", &patches[1].hunks).unwrap(), "hello\n");
+    }
+
+    #[test]
+    fn rejects_deletes_and_headerless_hunks() {
+        assert!(parse_patch("--- a/x\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n")
+            .unwrap_err()
+            .contains("Deleting"));
+        assert!(parse_patch("@@ -1 +1 @@\n-a\n+b\n")
+            .unwrap_err()
+            .contains("before any"));
+    }
+
+    #[test]
+    fn wrong_line_numbers_and_whitespace_are_tolerated() {
+        let content = "fn main() {\n    let x = 1;\n    println!(\"{x}\");\n}\n";
+        // Header is off by 40 lines and the context lost its indentation.
+        let hunks = single_hunks(
+            "--- main.rs\n+++ main.rs\n@@ -41,3 +41,3 @@\n fn main() {\n-let x = 1;\n+    let x = 2;\n     println!(\"{x}\");\n",
+        );
+
+        assert_eq!(
+            apply_hunks(content, &hunks).unwrap(),
+            "fn main() {\n    let x = 2;\n    println!(\"{x}\");\n}\n"
+        );
+    }
+
+    #[test]
+    fn later_hunks_follow_earlier_line_shifts() {
+        let content = (1..=12)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n";
+        let hunks = single_hunks(
+            "--- f\n+++ f\n@@ -1,2 +1,4 @@\n line 1\n+added a\n+added b\n line 2\n@@ -11,2 +13,1 @@\n line 11\n-line 12\n",
+        );
+
+        let updated = apply_hunks(&content, &hunks).unwrap();
+
+        assert!(updated.starts_with("line 1\nadded a\nadded b\nline 2\n"));
+        assert!(updated.ends_with("line 10\nline 11\n"));
+    }
+
+    #[test]
+    fn every_failing_hunk_is_reported() {
+        let hunks = single_hunks(
+            "--- f\n+++ f\n@@ -1 +1 @@\n-missing\n+x\n@@ -2 +2 @@\n-b\n+B\n@@ -3 +3 @@\n-gone\n+y\n",
+        );
+
+        let failures = apply_hunks("a\nb\nc\n", &hunks).unwrap_err();
+
+        let indexes: Vec<usize> = failures.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indexes, vec![0, 2]);
+        assert!(failures[0].1.contains("context not found"));
+    }
+
+    #[test]
+    fn replacement_without_line_numbers_must_be_unambiguous() {
+        let hunk = Hunk::replacement("dup", "new");
+
+        let failures = apply_hunks("dup\nmid\ndup\n", &[hunk]).unwrap_err();
+
+        assert!(failures[0].1.contains("matches 2 places"));
+    }
+
+    #[test]
+    fn crlf_line_endings_are_preserved() {
+        let hunk = Hunk::replacement("b", "B");
+
+        assert_eq!(apply_hunks("a\r\nb\r\n", &[hunk]).unwrap(), "a\r\nB\r\n");
+    }
+}
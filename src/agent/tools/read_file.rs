@@ -3,13 +3,15 @@
 //! This module provides a built-in `ReadFileExecutor` that reads file contents
 //! with support for line ranges, truncation, and binary file detection.
 
+use crate::agent::approval_rules::ToolCall;
 use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tools::workspace_gate::{confine_decision, resolve_read_path};
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
 use serdes_ai_agent::prelude::*;
 use serdes_ai_agent::ToolExecutor;
 use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
-use std::path::Path;
+use std::path::PathBuf;
 
 /// Maximum number of lines to read before truncation
 const MAX_LINES: usize = 2000;
@@ -48,21 +50,21 @@ impl ToolExecutor<McpToolContext> for ReadFileExecutor {
             .and_then(serde_json::Value::as_u64)
             .and_then(|v| usize::try_from(v).ok());
 
-        // Resolve the path
-        let file_path = Path::new(path);
-        let absolute_path = if file_path.is_absolute() {
-            file_path.to_path_buf()
-        } else {
-            // Relative path - resolve against current working directory
-            std::env::current_dir()
-                .map_err(|e| {
-                    ToolError::execution_failed(format!("Failed to get current directory: {e}"))
-                })?
-                .join(file_path)
-        };
+        // Resolve the path through the workspace roots
+        let resolved = resolve_read_path(&ctx.deps().workspace_roots, path)?;
+        let absolute_path = resolved.path.clone();
 
         let approval_path = absolute_path.display().to_string();
-        check_approval(ctx.deps(), &approval_path).await?;
+        let call = ToolCall::new("ReadFile")
+            .with_arguments(&args)
+            .with_path(absolute_path.clone());
+        check_approval(
+            ctx.deps(),
+            &call,
+            &approval_path,
+            resolved.outside_workspace(),
+        )
+        .await?;
 
         // Check if file exists and is accessible
         match tokio::fs::metadata(&absolute_path).await {
@@ -129,11 +131,17 @@ impl ToolExecutor<McpToolContext> for ReadFileExecutor {
 }
 
 /// Check tool approval policy and await user decision if required.
-async fn check_approval(tool_context: &McpToolContext, path: &str) -> Result<(), ToolError> {
+async fn check_approval(
+    tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
+    path: &str,
+    outside_workspace: &[PathBuf],
+) -> Result<(), ToolError> {
     let decision = {
         let policy = tool_context.policy.lock().await;
-        policy.evaluate("ReadFile")
+        policy.decide(call, |policy| policy.evaluate("ReadFile"))
     };
+    let decision = confine_decision(tool_context, "ReadFile", decision, outside_workspace).await?;
 
     match decision {
         ToolApprovalDecision::Allow => Ok(()),
@@ -331,7 +339,7 @@ pub fn get_read_file_tool_definition() -> ToolDefinition {
         "properties": {
             "path": {
                 "type": "string",
-                "description": "The path to the file to read (absolute or relative to current directory)"
+                "description": "The path to the file to read (absolute, or relative to the workspace root or current directory)"
             },
             "start_line": {
                 "type": "integer",
@@ -657,4 +665,56 @@ line2
         let result = handle.await.expect("task should complete");
         assert!(result.is_ok(), "approval should allow read execution");
     }
+
+    #[tokio::test]
+    async fn read_file_outside_workspace_asks_even_with_read_auto_approval() {
+        let project = tempfile::tempdir().unwrap();
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"secret\n").unwrap();
+
+        let executor = ReadFileExecutor;
+        let args = serde_json::json!({"path": file.path().to_str().unwrap()});
+
+        let (view_tx, mut view_rx) = tokio::sync::mpsc::channel(10);
+        let approval_gate = std::sync::Arc::new(crate::llm::client_agent::ApprovalGate::new());
+        let policy = std::sync::Arc::new(tokio::sync::Mutex::new(ToolApprovalPolicy {
+            auto_approve_reads: true,
+            ..Default::default()
+        }));
+        let run_ctx = RunContext::new(
+            McpToolContext {
+                view_tx,
+                approval_gate: approval_gate.clone(),
+                policy,
+                workspace_roots: crate::agent::workspace::WorkspaceRoots::new([project
+                    .path()
+                    .to_path_buf()]),
+                ..Default::default()
+            },
+            "test-model",
+        );
+
+        let handle = tokio::spawn(async move { executor.execute(args, &run_ctx).await });
+
+        let request_id = match view_rx.recv().await.expect("approval request") {
+            ViewCommand::ToolApprovalRequest {
+                request_id,
+                context,
+                ..
+            } => {
+                assert_eq!(context.tool_name, "ReadFile");
+                assert_eq!(context.category, ToolCategory::OutsideWorkspace);
+                request_id
+            }
+            other => panic!("expected ToolApprovalRequest, got {other:?}"),
+        };
+
+        let _ = approval_gate.resolve(&request_id, false);
+
+        let result = handle.await.expect("task should complete");
+        assert!(
+            result.is_err(),
+            "denied outside access should fail the read"
+        );
+    }
 }
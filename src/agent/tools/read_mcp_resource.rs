@@ -0,0 +1,231 @@
+//! `ReadMcpResource` tool implementation.
+//!
+//! This module provides a built-in `ReadMcpResourceExecutor` that reads a
+//! resource exposed by a running MCP server, or lists the available ones when
+//! no uri is given. Reads are approved like a call of the server's
+//! `read_resource` tool, so the MCP approval mode, per-server rules and read
+//! auto-approval apply to them.
+
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::llm::client_agent::McpToolContext;
+use crate::llm::mcp_tool_executor::handle_mcp_approval;
+use crate::mcp::{McpResource, McpService};
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+
+/// Name reads are approved under, as if the server had a tool of that name.
+const READ_RESOURCE_TOOL: &str = "read_resource";
+
+/// Most characters of resource text returned to the model.
+const MAX_RESOURCE_CHARS: usize = 100_000;
+
+/// Executor for the `ReadMcpResource` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct ReadMcpResourceExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for ReadMcpResourceExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let text_arg = |name: &str| {
+            args.get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let resources = McpService::global().lock().await.get_resources();
+
+        let Some(uri) = text_arg("uri") else {
+            return Ok(ToolReturn::text(format_resource_list(
+                &resources,
+                text_arg("server"),
+            )));
+        };
+        let server = resolve_server(&resources, text_arg("server"), uri)
+            .map_err(ToolError::execution_failed)?;
+
+        let (tool_identifier, decision) = {
+            let policy = ctx.deps().policy.lock().await;
+            let tool_identifier = policy.mcp_tool_identifier(&server, READ_RESOURCE_TOOL);
+            let call = ToolCall::new(READ_RESOURCE_TOOL)
+                .with_arguments(&args)
+                .with_mcp_server(&server)
+                .with_identifier(tool_identifier.clone());
+            let decision = policy.decide(&call, |policy| policy.evaluate(&tool_identifier));
+            drop(policy);
+            (tool_identifier, decision)
+        };
+        match decision {
+            ToolApprovalDecision::Allow => {}
+            ToolApprovalDecision::Deny => {
+                return Err(ToolError::execution_failed(
+                    "Resource read denied by policy",
+                ));
+            }
+            ToolApprovalDecision::AskUser => {
+                handle_mcp_approval(ctx, READ_RESOURCE_TOOL, &server, &tool_identifier, &args)
+                    .await?;
+            }
+        }
+
+        let text = McpService::global()
+            .lock()
+            .await
+            .read_resource(&server, uri)
+            .await
+            .map_err(ToolError::execution_failed)?;
+
+        Ok(ToolReturn::text(truncate_resource_text(text)))
+    }
+}
+
+/// The server to read `uri` from: the one named, or the only one listing it.
+fn resolve_server(
+    resources: &[McpResource],
+    server: Option<&str>,
+    uri: &str,
+) -> Result<String, String> {
+    if let Some(server) = server {
+        return Ok(server.to_string());
+    }
+
+    let mut listing = resources
+        .iter()
+        .filter(|resource| resource.uri == uri)
+        .map(|resource| resource.server.as_str())
+        .collect::<Vec<_>>();
+    listing.dedup();
+    match listing.as_slice() {
+        [server] => Ok((*server).to_string()),
+        [] => Err(format!(
+            "No MCP server lists {uri}; pass 'server' to read it anyway"
+        )),
+        servers => Err(format!(
+            "{uri} is listed by several servers ({}); pass 'server'",
+            servers.join(", ")
+        )),
+    }
+}
+
+fn format_resource_list(resources: &[McpResource], server: Option<&str>) -> String {
+    let lines = resources
+        .iter()
+        .filter(|resource| server.is_none_or(|server| resource.server == server))
+        .map(|resource| {
+            let mut line = format!(
+                "- [{}] {} ({})",
+                resource.server, resource.uri, resource.name
+            );
+            if let Some(description) = &resource.description {
+                line.push_str(": ");
+                line.push_str(description);
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        "No MCP resources are available".to_string()
+    } else {
+        format!("Available MCP resources:\n{}", lines.join("\n"))
+    }
+}
+
+fn truncate_resource_text(text: String) -> String {
+    match text.char_indices().nth(MAX_RESOURCE_CHARS) {
+        Some((cut, _)) => format!(
+            "{}\n[truncated after {MAX_RESOURCE_CHARS} characters]",
+            &text[..cut]
+        ),
+        None => text,
+    }
+}
+
+/// Get the `ReadMcpResource` tool definition.
+#[must_use]
+pub fn get_read_mcp_resource_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "uri": {
+                "type": "string",
+                "description": "Uri of the resource to read. Omit it to list the available resources"
+            },
+            "server": {
+                "type": "string",
+                "description": "Name of the MCP server; only needed when the uri is not listed by exactly one server"
+            }
+        }
+    });
+
+    ToolDefinition::new(
+        "ReadMcpResource",
+        "Read a resource (a file, database row, document, ...) exposed by a connected MCP server, or list the available resources when no uri is given.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(server: &str, uri: &str) -> McpResource {
+        McpResource {
+            server: server.to_string(),
+            uri: uri.to_string(),
+            name: uri.to_string(),
+            description: None,
+            mime_type: None,
+        }
+    }
+
+    #[test]
+    fn the_server_is_resolved_from_the_listing() {
+        let resources = vec![
+            resource("files", "file:///notes.md"),
+            resource("files", "file:///todo.md"),
+            resource("backup", "file:///todo.md"),
+        ];
+
+        assert_eq!(
+            resolve_server(&resources, None, "file:///notes.md"),
+            Ok("files".to_string())
+        );
+        assert_eq!(
+            resolve_server(&resources, Some("backup"), "file:///other.md"),
+            Ok("backup".to_string())
+        );
+        assert!(resolve_server(&resources, None, "file:///todo.md")
+            .is_err_and(|error| error.contains("several servers")));
+        assert!(resolve_server(&resources, None, "db://rows/1").is_err());
+    }
+
+    #[test]
+    fn the_listing_can_be_narrowed_to_one_server() {
+        let resources = vec![
+            resource("files", "file:///notes.md"),
+            resource("db", "db://rows/1"),
+        ];
+
+        let listing = format_resource_list(&resources, Some("db"));
+        assert!(listing.contains("db://rows/1"));
+        assert!(!listing.contains("notes.md"));
+        assert_eq!(
+            format_resource_list(&[], None),
+            "No MCP resources are available"
+        );
+    }
+
+    #[test]
+    fn get_read_mcp_resource_tool_definition_returns_valid_schema() {
+        let def = get_read_mcp_resource_tool_definition();
+        assert_eq!(def.name, "ReadMcpResource");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
@@ -0,0 +1,157 @@
+//! `ReadProcessOutput` tool implementation.
+//!
+//! This module provides a built-in `ReadProcessOutputExecutor` that returns
+//! the output a background `ShellExec` process produced since it was last
+//! read, optionally waiting briefly for more. Reading is not approval-gated:
+//! the command itself was approved when it was started.
+
+use crate::agent::processes::{BackgroundProcesses, ProcessOutput};
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::BackgroundProcessState;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+use std::time::Duration;
+
+/// Longest the tool waits for new output.
+const MAX_WAIT_SECS: u64 = 30;
+
+/// Executor for the `ReadProcessOutput` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct ReadProcessOutputExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for ReadProcessOutputExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let process_id = args
+            .get("process_id")
+            .and_then(serde_json::Value::as_str)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| ToolError::execution_failed("Missing required 'process_id' argument"))?;
+        let wait_secs = match args.get("wait_secs") {
+            None => 0,
+            Some(value) => value
+                .as_u64()
+                .filter(|secs| *secs <= MAX_WAIT_SECS)
+                .ok_or_else(|| {
+                    ToolError::execution_failed(format!(
+                        "Invalid wait_secs: {value} (must be 0..={MAX_WAIT_SECS})"
+                    ))
+                })?,
+        };
+
+        let output = BackgroundProcesses::global()
+            .read_output(
+                ctx.deps().conversation_id,
+                process_id,
+                Duration::from_secs(wait_secs),
+            )
+            .await
+            .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+        Ok(ToolReturn::text(format_output(process_id, &output)))
+    }
+}
+
+fn format_output(process_id: &str, output: &ProcessOutput) -> String {
+    let state = match output.state {
+        BackgroundProcessState::Running => "running".to_string(),
+        BackgroundProcessState::Exited { code: Some(code) } => format!("exited with code {code}"),
+        BackgroundProcessState::Exited { code: None } => "exited".to_string(),
+        BackgroundProcessState::Killed => "killed".to_string(),
+    };
+    let stream = |text: &str| {
+        if text.trim().is_empty() {
+            "(no new output)".to_string()
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut formatted = format!(
+        "Process: {process_id}\nState: {state}\nStdout: {}\nStderr: {}",
+        stream(&output.stdout),
+        stream(&output.stderr)
+    );
+    if output.dropped_bytes > 0 {
+        formatted.push_str(&format!(
+            "\n... {} earlier bytes were dropped because they were not read in time",
+            output.dropped_bytes
+        ));
+    }
+    formatted
+}
+
+/// Get the `ReadProcessOutput` tool definition.
+#[must_use]
+pub fn get_read_process_output_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "process_id": {
+                "type": "string",
+                "description": "Id returned by ShellExec when the command was started in the background"
+            },
+            "wait_secs": {
+                "type": "integer",
+                "description": "Seconds to wait for new output when there is none yet (default 0, max 30)",
+                "minimum": 0,
+                "maximum": MAX_WAIT_SECS
+            }
+        },
+        "required": ["process_id"]
+    });
+
+    ToolDefinition::new(
+        "ReadProcessOutput",
+        "Read the stdout and stderr a background process produced since the last read, and whether it is still running.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_output_reports_state_and_dropped_bytes() {
+        let output = ProcessOutput {
+            stdout: "ready\n".to_string(),
+            stderr: String::new(),
+            dropped_bytes: 12,
+            state: BackgroundProcessState::Exited { code: Some(1) },
+        };
+
+        let text = format_output("proc-3", &output);
+
+        assert!(text.starts_with("Process: proc-3\nState: exited with code 1\n"));
+        assert!(text.contains("Stdout: ready\n"));
+        assert!(text.contains("Stderr: (no new output)"));
+        assert!(text.contains("12 earlier bytes were dropped"));
+    }
+
+    #[tokio::test]
+    async fn read_process_output_rejects_unknown_processes() {
+        let error = ReadProcessOutputExecutor
+            .execute(
+                serde_json::json!({ "process_id": "proc-0" }),
+                &RunContext::new(McpToolContext::default(), "test-model"),
+            )
+            .await
+            .expect_err("unknown process should fail");
+
+        assert!(error.to_string().contains("proc-0"));
+    }
+
+    #[test]
+    fn get_read_process_output_tool_definition_returns_valid_schema() {
+        let def = get_read_process_output_tool_definition();
+        assert_eq!(def.name, "ReadProcessOutput");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
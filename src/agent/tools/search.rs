@@ -4,6 +4,7 @@
 //! using regex across a directory tree. It prefers ripgrep when available and
 //! falls back to a built-in recursive search implementation.
 
+use crate::agent::approval_rules::ToolCall;
 use crate::agent::tool_approval_policy::ToolApprovalDecision;
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
@@ -64,8 +65,13 @@ impl ToolExecutor<McpToolContext> for SearchExecutor {
     ) -> Result<ToolReturn, ToolError> {
         let search_args = parse_search_args(&args).map_err(ToolError::execution_failed)?;
 
+        let call = ToolCall::new("Search").with_arguments(&args).with_path(
+            std::path::absolute(&search_args.path)
+                .unwrap_or_else(|_| search_args.path.clone().into()),
+        );
         check_approval(
             ctx.deps(),
+            &call,
             &search_args.pattern,
             &search_args.path,
             search_args.include.as_deref(),
@@ -81,13 +87,14 @@ impl ToolExecutor<McpToolContext> for SearchExecutor {
 /// Check tool approval policy and await user decision if required.
 async fn check_approval(
     tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
     pattern: &str,
     path: &str,
     include: Option<&str>,
 ) -> Result<(), ToolError> {
     let decision = {
         let policy = tool_context.policy.lock().await;
-        policy.evaluate("Search")
+        policy.decide(call, |policy| policy.evaluate("Search"))
     };
 
     match decision {
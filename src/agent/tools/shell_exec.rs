@@ -1,14 +1,25 @@
 //! `ShellExec` tool implementation.
 //!
 //! This module provides a built-in `ShellExecExecutor` that runs shell commands
-//! with timeout handling and structured output formatting.
-
-use std::path::{Path, PathBuf};
+//! with timeout handling and structured output formatting. With
+//! `background: true` the command is handed to the conversation's
+//! `BackgroundProcesses` instead, and the call returns its process id. With
+//! `session: true` it runs in the conversation's persistent shell from
+//! `ShellSessions`, keeping the working directory and environment of earlier
+//! session commands.
+
+use std::path::{Component, Path, PathBuf};
 use std::process::Stdio;
 use std::time::Duration;
 
 use tokio::io::AsyncReadExt;
 
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::processes::BackgroundProcesses;
+use crate::agent::shell_sessions::ShellSessions;
+use crate::agent::tool_audit::note_exit_code;
+use crate::agent::tools::workspace_gate::confine_decision;
+use crate::agent::workspace::{canonicalize_existing_prefix, WorkspaceRoots};
 use crate::agent::{ToolApprovalDecision, ToolApprovalPolicy};
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
@@ -27,6 +38,8 @@ struct ShellExecParams {
     command: String,
     working_dir: Option<String>,
     timeout_secs: u32,
+    background: bool,
+    session: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +53,13 @@ struct ShellExecResult {
 }
 
 impl ShellExecResult {
+    /// The result as the agent sees it; the exit code is also noted for the
+    /// audit log.
+    fn into_tool_return(self) -> ToolReturn {
+        note_exit_code(self.exit_code);
+        ToolReturn::text(self.format_for_agent())
+    }
+
     fn format_for_agent(&self) -> String {
         let stdout = if self.stdout.trim().is_empty() {
             "(empty)".to_string()
@@ -84,12 +104,39 @@ impl ToolExecutor<McpToolContext> for ShellExecExecutor {
         args: serde_json::Value,
         ctx: &RunContext<McpToolContext>,
     ) -> Result<ToolReturn, ToolError> {
-        let params = parse_params(&args)?;
-
-        check_approval(ctx.deps(), &params.command, params.working_dir.as_deref()).await?;
+        let mut params = parse_params(&args)?;
+        let session_directory = params
+            .session
+            .then(|| ShellSessions::global().directory(ctx.deps().conversation_id))
+            .flatten();
+        let change_directory = params.working_dir.is_some();
+        let outside_workspace = confine_to_workspace(
+            &ctx.deps().workspace_roots,
+            &mut params,
+            session_directory.as_deref(),
+        );
+
+        let call = ToolCall::new("ShellExec")
+            .with_arguments(&args)
+            .with_command(&params.command)
+            .with_paths(rule_paths(&params));
+        check_approval(
+            ctx.deps(),
+            &call,
+            &params.command,
+            params.working_dir.as_deref(),
+            &outside_workspace,
+        )
+        .await?;
 
+        if params.background {
+            return start_background_command(ctx.deps(), &params).await;
+        }
+        if params.session {
+            return run_in_session(ctx.deps(), &params, change_directory).await;
+        }
         let result = execute_shell_command(&params).await?;
-        Ok(ToolReturn::text(result.format_for_agent()))
+        Ok(result.into_tool_return())
     }
 }
 
@@ -134,26 +181,152 @@ fn parse_params(args: &serde_json::Value) -> Result<ShellExecParams, ToolError>
         }
     };
 
+    let background = parse_flag(args, "background")?;
+    let session = parse_flag(args, "session")?;
+    if background && session {
+        return Err(ToolError::execution_failed(
+            "background and session cannot be combined; start background processes outside the session",
+        ));
+    }
+
     Ok(ShellExecParams {
         command,
         working_dir,
         timeout_secs: timeout_secs_u32,
+        background,
+        session,
     })
 }
 
+fn parse_flag(args: &serde_json::Value, name: &str) -> Result<bool, ToolError> {
+    match args.get(name) {
+        None => Ok(false),
+        Some(value) => value.as_bool().ok_or_else(|| {
+            ToolError::execution_failed(format!("Invalid {name}: {value} (must be a boolean)"))
+        }),
+    }
+}
+
+/// Pin the working directory inside the workspace and collect the paths the
+/// command would reach outside it.
+///
+/// Relative paths resolve from `session_directory` when a session shell is
+/// already running there, and from the workspace root otherwise. Without a
+/// workspace root the parameters are left alone and nothing is reported,
+/// matching the unconfined behaviour.
+fn confine_to_workspace(
+    workspace: &WorkspaceRoots,
+    params: &mut ShellExecParams,
+    session_directory: Option<&Path>,
+) -> Vec<PathBuf> {
+    let Some(root) = workspace.primary() else {
+        return Vec::new();
+    };
+    let directory = workspace.resolve_from(
+        session_directory.unwrap_or(root),
+        Path::new(params.working_dir.as_deref().unwrap_or(".")),
+    );
+    params.working_dir = Some(directory.path.display().to_string());
+
+    let mut outside = directory.outside_workspace().to_vec();
+    for argument in command_path_arguments(&params.command) {
+        let path = if expands_at_run_time(&argument.to_string_lossy()) {
+            argument
+        } else {
+            let resolved = workspace.resolve_from(&directory.path, &argument);
+            if resolved.inside_workspace {
+                continue;
+            }
+            resolved.path
+        };
+        if !outside.contains(&path) {
+            outside.push(path);
+        }
+    }
+    outside
+}
+
+/// The paths approval rules see for the call: the working directory and
+/// every command argument able to leave it, resolved against that
+/// directory. Expansions stay as written, so no path glob covers them.
+fn rule_paths(params: &ShellExecParams) -> Vec<PathBuf> {
+    let working_dir = params.working_dir.as_deref().map(PathBuf::from);
+    let base = working_dir.clone().or_else(|| std::env::current_dir().ok());
+    let mut paths = working_dir.into_iter().collect::<Vec<_>>();
+    for argument in command_path_arguments(&params.command) {
+        let path = match &base {
+            Some(base) if !expands_at_run_time(&argument.to_string_lossy()) => {
+                canonicalize_existing_prefix(&base.join(argument))
+            }
+            _ => argument,
+        };
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Command arguments that look like paths able to leave the working
+/// directory: absolute, home-relative, containing `..`, or built by a `$`
+/// expansion or backtick substitution that only the shell can resolve.
+///
+/// This is a heuristic, not a shell parser: the command is split on shell
+/// operators before it is split into words, so `cd ..&&ls` still shows the
+/// `..`. A bare `cd` counts as the home directory it changes to.
+fn command_path_arguments(command: &str) -> Vec<PathBuf> {
+    command
+        .split([';', '&', '|', '<', '>', '(', ')', '\n'])
+        .flat_map(|segment| {
+            let words = segment.split_whitespace().collect::<Vec<_>>();
+            let bare_cd = (words == ["cd"]).then(dirs::home_dir).flatten();
+            words.into_iter().filter_map(escaping_path).chain(bare_cd)
+        })
+        .collect()
+}
+
+fn escaping_path(word: &str) -> Option<PathBuf> {
+    let word = word.trim_matches(|c: char| matches!(c, '"' | '\''));
+    let word = word.split_once('=').map_or(word, |(_, value)| value);
+    if expands_at_run_time(word) {
+        return Some(PathBuf::from(word));
+    }
+    if word == "~" {
+        return dirs::home_dir();
+    }
+    if let Some(rest) = word.strip_prefix("~/") {
+        return dirs::home_dir().map(|home| home.join(rest));
+    }
+    let path = Path::new(word);
+    let escapes = path.is_absolute()
+        || path
+            .components()
+            .any(|component| component == Component::ParentDir);
+    escapes.then(|| path.to_path_buf())
+}
+
+/// Whether `word` holds a `$` expansion or backtick substitution, whose
+/// path is unknown until the shell runs it and so counts as outside.
+fn expands_at_run_time(word: &str) -> bool {
+    word.contains(['$', '`'])
+}
+
 /// Check tool approval policy and await user decision if required.
 async fn check_approval(
     tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
     command: &str,
     working_dir: Option<&str>,
+    outside_workspace: &[PathBuf],
 ) -> Result<(), ToolError> {
     let (decision, mut identifiers) = {
         let policy = tool_context.policy.lock().await;
         (
-            policy.evaluate_compound_command(command),
+            policy.decide(call, |policy| policy.evaluate_compound_command(command)),
             ToolApprovalPolicy::extract_shell_identifiers(command),
         )
     };
+    let decision = confine_decision(tool_context, "ShellExec", decision, outside_workspace).await?;
 
     if identifiers.is_empty() {
         identifiers.push("ShellExec".to_string());
@@ -223,16 +396,87 @@ async fn execute_shell_command(params: &ShellExecParams) -> Result<ShellExecResu
     })
 }
 
-fn spawn_shell_child(
+/// Run the command in the conversation's persistent shell.
+///
+/// The shell only changes directory when `working_dir` was given; otherwise
+/// it stays wherever earlier session commands left it.
+async fn run_in_session(
+    tool_context: &McpToolContext,
     params: &ShellExecParams,
-    resolved_directory: &Path,
-) -> Result<tokio::process::Child, ToolError> {
+    change_directory: bool,
+) -> Result<ToolReturn, ToolError> {
+    let directory = resolve_working_directory(params.working_dir.as_deref()).await?;
+    let run = ShellSessions::global()
+        .run(
+            tool_context.conversation_id,
+            &params.command,
+            &directory,
+            change_directory,
+            Duration::from_secs(u64::from(params.timeout_secs)),
+        )
+        .await
+        .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+    let mut stderr = run.stderr;
+    if run.session_ended {
+        if !stderr.is_empty() && !stderr.ends_with('\n') {
+            stderr.push('\n');
+        }
+        stderr.push_str(
+            "Shell session ended; the next session command starts a new shell with the default directory and environment",
+        );
+    }
+    let result = ShellExecResult {
+        command: params.command.clone(),
+        directory: run.directory.display().to_string(),
+        stdout: run.stdout,
+        stderr,
+        exit_code: run.exit_code,
+        timed_out: run.timed_out,
+    };
+    Ok(result.into_tool_return())
+}
+
+/// Start the command under the conversation's background processes.
+async fn start_background_command(
+    tool_context: &McpToolContext,
+    params: &ShellExecParams,
+) -> Result<ToolReturn, ToolError> {
+    let resolved_directory = resolve_working_directory(params.working_dir.as_deref()).await?;
+    let process = BackgroundProcesses::global()
+        .spawn(
+            tool_context.conversation_id,
+            &params.command,
+            &resolved_directory,
+            shell_command(&params.command, &resolved_directory),
+            tool_context.view_tx.clone(),
+        )
+        .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+    Ok(ToolReturn::text(format!(
+        "Command: {}\nDirectory: {}\nStarted background process {}\nUse ReadProcessOutput to see its output, WriteProcessInput to send it input, and KillProcess to stop it.",
+        params.command,
+        resolved_directory.display(),
+        process.id
+    )))
+}
+
+fn shell_command(command_text: &str, resolved_directory: &Path) -> tokio::process::Command {
     let (shell, shell_flag) = shell_program_and_flag();
     let mut command = tokio::process::Command::new(shell);
     command
         .arg(shell_flag)
-        .arg(&params.command)
-        .current_dir(resolved_directory)
+        .arg(command_text)
+        .current_dir(resolved_directory);
+    command
+}
+
+fn spawn_shell_child(
+    params: &ShellExecParams,
+    resolved_directory: &Path,
+) -> Result<tokio::process::Child, ToolError> {
+    let mut command = shell_command(&params.command, resolved_directory);
+    command
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
@@ -398,13 +642,21 @@ pub fn get_shell_exec_tool_definition() -> ToolDefinition {
             },
             "working_dir": {
                 "type": "string",
-                "description": "Optional working directory for command execution"
+                "description": "Optional working directory for command execution; defaults to the workspace root"
             },
             "timeout_secs": {
                 "type": "integer",
-                "description": "Optional timeout in seconds (default 300, max 900)",
+                "description": "Optional timeout in seconds (default 300, max 900); ignored for background commands",
                 "minimum": 1,
                 "maximum": MAX_TIMEOUT_SECS
+            },
+            "background": {
+                "type": "boolean",
+                "description": "Run the command in the background, for dev servers and long builds, and return a process id instead of waiting (default false)"
+            },
+            "session": {
+                "type": "boolean",
+                "description": "Run in this conversation's persistent shell, so cd, exported variables and activated virtualenvs carry over to later session commands (default false)"
             }
         },
         "required": ["command"]
@@ -412,170 +664,10 @@ pub fn get_shell_exec_tool_definition() -> ToolDefinition {
 
     ToolDefinition::new(
         "ShellExec",
-        "Execute a shell command and return structured stdout/stderr/exit-code output.",
+        "Execute a shell command and return structured stdout/stderr/exit-code output. Long-running commands can be started in the background and followed with ReadProcessOutput.",
     )
     .with_parameters(input_schema)
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::agent::tool_approval_policy::ToolApprovalPolicy;
-
-    fn make_context_with_policy(policy: ToolApprovalPolicy) -> McpToolContext {
-        let (view_tx, _view_rx) = tokio::sync::mpsc::channel(8);
-        McpToolContext {
-            conversation_id: uuid::Uuid::nil(),
-            view_tx,
-            approval_gate: std::sync::Arc::new(crate::llm::client_agent::ApprovalGate::new()),
-            policy: std::sync::Arc::new(tokio::sync::Mutex::new(policy)),
-            ..Default::default()
-        }
-    }
-
-    #[test]
-    fn parse_params_defaults_timeout() {
-        let params =
-            parse_params(&serde_json::json!({"command": "echo hi"})).expect("params should parse");
-        assert_eq!(params.timeout_secs, DEFAULT_TIMEOUT_SECS);
-    }
-
-    #[test]
-    fn parse_params_rejects_out_of_range_timeout() {
-        let error = parse_params(&serde_json::json!({
-            "command": "echo hi",
-            "timeout_secs": 901
-        }))
-        .expect_err("timeout greater than max should fail");
-
-        assert!(error.to_string().contains("Invalid timeout_secs"));
-    }
-
-    #[test]
-    fn parse_params_rejects_malformed_timeout_values() {
-        for value in [
-            serde_json::json!("5"),
-            serde_json::json!(-1),
-            serde_json::json!(1.5),
-            serde_json::json!(true),
-            serde_json::json!({"secs": 5}),
-        ] {
-            let error = parse_params(&serde_json::json!({
-                "command": "echo hi",
-                "timeout_secs": value,
-            }))
-            .expect_err("malformed timeout should fail");
-
-            assert!(error.to_string().contains("Invalid timeout_secs"));
-        }
-    }
-
-    #[test]
-    fn parse_params_rejects_null_timeout() {
-        let error = parse_params(&serde_json::json!({
-            "command": "echo hi",
-            "timeout_secs": null,
-        }))
-        .expect_err("null timeout should fail");
-
-        assert!(error.to_string().contains("Invalid timeout_secs"));
-    }
-
-    #[test]
-    fn result_formatter_matches_issue_shape() {
-        let result = ShellExecResult {
-            command: "echo hi".to_string(),
-            directory: "/tmp".to_string(),
-            stdout: "hi\n".to_string(),
-            stderr: String::new(),
-            exit_code: Some(0),
-            timed_out: false,
-        };
-
-        let formatted = result.format_for_agent();
-        assert!(formatted.contains("Command: echo hi"));
-        assert!(formatted.contains("Directory: /tmp"));
-        assert!(formatted.contains("Stdout: hi"));
-        assert!(formatted.contains("Stderr: (empty)"));
-        assert!(formatted.contains("Exit Code: 0"));
-    }
-
-    #[tokio::test]
-    async fn shell_exec_allows_command_when_policy_allows_identifier() {
-        let policy = ToolApprovalPolicy {
-            persistent_allowlist: vec!["echo".to_string()],
-            ..ToolApprovalPolicy::default()
-        };
-        let context = make_context_with_policy(policy);
-
-        let executor = ShellExecExecutor;
-        let args = serde_json::json!({"command": "echo shell-exec-test"});
-        let run_ctx = RunContext::new(context, "test-model");
-
-        let output = executor
-            .execute(args, &run_ctx)
-            .await
-            .expect("execution should succeed");
-
-        let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
-        else {
-            panic!("expected text content");
-        };
-        assert!(text.contains("Command: echo shell-exec-test"));
-        assert!(text.contains("Exit Code: 0"));
-    }
-
-    #[tokio::test]
-    async fn shell_exec_denies_command_when_policy_denies_compound_segment() {
-        let policy = ToolApprovalPolicy {
-            persistent_allowlist: vec!["ls".to_string()],
-            persistent_denylist: vec!["rm".to_string()],
-            ..ToolApprovalPolicy::default()
-        };
-        let context = make_context_with_policy(policy);
-
-        let executor = ShellExecExecutor;
-        let args = serde_json::json!({"command": "ls && rm -rf /tmp/never"});
-        let run_ctx = RunContext::new(context, "test-model");
-
-        let error = executor
-            .execute(args, &run_ctx)
-            .await
-            .expect_err("execution should be denied");
-
-        assert!(error.to_string().contains("denied by policy"));
-    }
-
-    #[tokio::test]
-    async fn shell_exec_timeout_reports_timed_out_notice() {
-        let policy = ToolApprovalPolicy {
-            yolo_mode: true,
-            ..ToolApprovalPolicy::default()
-        };
-        let context = make_context_with_policy(policy);
-
-        let executor = ShellExecExecutor;
-        let args = serde_json::json!({"command": "sleep 10", "timeout_secs": 1});
-        let run_ctx = RunContext::new(context, "test-model");
-
-        let output = executor
-            .execute(args, &run_ctx)
-            .await
-            .expect("execution should return timeout result");
-
-        let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
-        else {
-            panic!("expected text content");
-        };
-
-        assert!(text.contains("Command timed out before completion"));
-    }
-
-    #[test]
-    fn shell_exec_tool_definition_is_valid() {
-        let def = get_shell_exec_tool_definition();
-        assert_eq!(def.name, "ShellExec");
-        assert!(!def.description.is_empty());
-        assert!(def.parameters().is_object());
-    }
-}
+mod tests;
@@ -0,0 +1,424 @@
+use super::*;
+use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+
+fn make_context_with_policy(policy: ToolApprovalPolicy) -> McpToolContext {
+    let (view_tx, _view_rx) = tokio::sync::mpsc::channel(8);
+    McpToolContext {
+        conversation_id: uuid::Uuid::nil(),
+        view_tx,
+        approval_gate: std::sync::Arc::new(crate::llm::client_agent::ApprovalGate::new()),
+        policy: std::sync::Arc::new(tokio::sync::Mutex::new(policy)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parse_params_defaults_timeout() {
+    let params =
+        parse_params(&serde_json::json!({"command": "echo hi"})).expect("params should parse");
+    assert_eq!(params.timeout_secs, DEFAULT_TIMEOUT_SECS);
+}
+
+#[test]
+fn parse_params_rejects_out_of_range_timeout() {
+    let error = parse_params(&serde_json::json!({
+        "command": "echo hi",
+        "timeout_secs": 901
+    }))
+    .expect_err("timeout greater than max should fail");
+
+    assert!(error.to_string().contains("Invalid timeout_secs"));
+}
+
+#[test]
+fn parse_params_rejects_malformed_timeout_values() {
+    for value in [
+        serde_json::json!("5"),
+        serde_json::json!(-1),
+        serde_json::json!(1.5),
+        serde_json::json!(true),
+        serde_json::json!({"secs": 5}),
+    ] {
+        let error = parse_params(&serde_json::json!({
+            "command": "echo hi",
+            "timeout_secs": value,
+        }))
+        .expect_err("malformed timeout should fail");
+
+        assert!(error.to_string().contains("Invalid timeout_secs"));
+    }
+}
+
+#[test]
+fn parse_params_rejects_null_timeout() {
+    let error = parse_params(&serde_json::json!({
+        "command": "echo hi",
+        "timeout_secs": null,
+    }))
+    .expect_err("null timeout should fail");
+
+    assert!(error.to_string().contains("Invalid timeout_secs"));
+}
+
+#[test]
+fn result_formatter_matches_issue_shape() {
+    let result = ShellExecResult {
+        command: "echo hi".to_string(),
+        directory: "/tmp".to_string(),
+        stdout: "hi\n".to_string(),
+        stderr: String::new(),
+        exit_code: Some(0),
+        timed_out: false,
+    };
+
+    let formatted = result.format_for_agent();
+    assert!(formatted.contains("Command: echo hi"));
+    assert!(formatted.contains("Directory: /tmp"));
+    assert!(formatted.contains("Stdout: hi"));
+    assert!(formatted.contains("Stderr: (empty)"));
+    assert!(formatted.contains("Exit Code: 0"));
+}
+
+#[tokio::test]
+async fn shell_exec_allows_command_when_policy_allows_identifier() {
+    let policy = ToolApprovalPolicy {
+        persistent_allowlist: vec!["echo".to_string()],
+        ..ToolApprovalPolicy::default()
+    };
+    let context = make_context_with_policy(policy);
+
+    let executor = ShellExecExecutor;
+    let args = serde_json::json!({"command": "echo shell-exec-test"});
+    let run_ctx = RunContext::new(context, "test-model");
+
+    let output = executor
+        .execute(args, &run_ctx)
+        .await
+        .expect("execution should succeed");
+
+    let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
+    else {
+        panic!("expected text content");
+    };
+    assert!(text.contains("Command: echo shell-exec-test"));
+    assert!(text.contains("Exit Code: 0"));
+}
+
+#[tokio::test]
+async fn shell_exec_denies_command_when_policy_denies_compound_segment() {
+    let policy = ToolApprovalPolicy {
+        persistent_allowlist: vec!["ls".to_string()],
+        persistent_denylist: vec!["rm".to_string()],
+        ..ToolApprovalPolicy::default()
+    };
+    let context = make_context_with_policy(policy);
+
+    let executor = ShellExecExecutor;
+    let args = serde_json::json!({"command": "ls && rm -rf /tmp/never"});
+    let run_ctx = RunContext::new(context, "test-model");
+
+    let error = executor
+        .execute(args, &run_ctx)
+        .await
+        .expect_err("execution should be denied");
+
+    assert!(error.to_string().contains("denied by policy"));
+}
+
+#[tokio::test]
+async fn shell_exec_timeout_reports_timed_out_notice() {
+    let policy = ToolApprovalPolicy {
+        yolo_mode: true,
+        ..ToolApprovalPolicy::default()
+    };
+    let context = make_context_with_policy(policy);
+
+    let executor = ShellExecExecutor;
+    let args = serde_json::json!({"command": "sleep 10", "timeout_secs": 1});
+    let run_ctx = RunContext::new(context, "test-model");
+
+    let output = executor
+        .execute(args, &run_ctx)
+        .await
+        .expect("execution should return timeout result");
+
+    let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
+    else {
+        panic!("expected text content");
+    };
+
+    assert!(text.contains("Command timed out before completion"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn shell_exec_background_returns_a_process_id() {
+    let policy = ToolApprovalPolicy {
+        yolo_mode: true,
+        ..ToolApprovalPolicy::default()
+    };
+    let conversation_id = uuid::Uuid::new_v4();
+    let context = McpToolContext {
+        conversation_id,
+        ..make_context_with_policy(policy)
+    };
+    let run_ctx = RunContext::new(context, "test-model");
+
+    let output = ShellExecExecutor
+        .execute(
+            serde_json::json!({"command": "sleep 30", "background": true}),
+            &run_ctx,
+        )
+        .await
+        .expect("background command should start");
+    let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
+    else {
+        panic!("expected text content");
+    };
+
+    let processes = BackgroundProcesses::global().list(conversation_id);
+    assert_eq!(processes.len(), 1);
+    assert!(text.contains(&format!("Started background process {}", processes[0].id)));
+    assert_eq!(
+        BackgroundProcesses::global().kill_conversation(conversation_id),
+        1
+    );
+}
+
+#[test]
+fn parse_params_rejects_background_sessions() {
+    let error = parse_params(&serde_json::json!({
+        "command": "npm run dev",
+        "background": true,
+        "session": true
+    }))
+    .expect_err("background sessions should be rejected");
+    assert!(error.to_string().contains("cannot be combined"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn shell_exec_session_keeps_the_working_directory() {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    std::fs::create_dir(dir.path().join("nested")).expect("nested dir should be created");
+    let policy = ToolApprovalPolicy {
+        yolo_mode: true,
+        ..ToolApprovalPolicy::default()
+    };
+    let conversation_id = uuid::Uuid::new_v4();
+    let run_ctx = RunContext::new(
+        McpToolContext {
+            conversation_id,
+            ..make_context_with_policy(policy)
+        },
+        "test-model",
+    );
+    let working_dir = dir.path().display().to_string();
+
+    ShellExecExecutor
+        .execute(
+            serde_json::json!({"command": "cd nested", "working_dir": working_dir, "session": true}),
+            &run_ctx,
+        )
+        .await
+        .expect("cd should run");
+    let output = ShellExecExecutor
+        .execute(
+            serde_json::json!({"command": "pwd", "session": true}),
+            &run_ctx,
+        )
+        .await
+        .expect("pwd should run");
+    ShellSessions::global().close(conversation_id);
+
+    let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
+    else {
+        panic!("expected text content");
+    };
+    assert!(text.contains(&format!("Stdout: {}/nested", dir.path().display())));
+    assert!(text.contains("Exit Code: 0"));
+}
+
+#[test]
+fn command_path_arguments_picks_out_escaping_paths() {
+    let paths = command_path_arguments("cat ../secret.txt src/lib.rs --out=/tmp/x 'a/../b'");
+    assert_eq!(
+        paths,
+        [
+            PathBuf::from("../secret.txt"),
+            PathBuf::from("/tmp/x"),
+            PathBuf::from("a/../b"),
+        ]
+    );
+}
+
+#[test]
+fn command_path_arguments_splits_on_shell_operators() {
+    assert_eq!(
+        command_path_arguments("cd ..&&rm -rf x"),
+        [PathBuf::from("..")]
+    );
+    assert_eq!(
+        command_path_arguments("ls||cat ../a;echo hi|tee ../b"),
+        [PathBuf::from("../a"), PathBuf::from("../b")]
+    );
+    assert_eq!(
+        command_path_arguments("cargo build && cargo test"),
+        [] as [PathBuf; 0]
+    );
+}
+
+#[test]
+fn command_path_arguments_counts_expansions_as_escaping() {
+    assert_eq!(
+        command_path_arguments("cat $HOME/.ssh/id_rsa"),
+        [PathBuf::from("$HOME/.ssh/id_rsa")]
+    );
+    assert_eq!(
+        command_path_arguments("rm -rf \"${TARGET}\""),
+        [PathBuf::from("${TARGET}")]
+    );
+    assert!(!command_path_arguments("cat $(cat list.txt)").is_empty());
+    assert_eq!(
+        command_path_arguments("cat `cat list.txt`"),
+        [PathBuf::from("`cat"), PathBuf::from("list.txt`")]
+    );
+    assert_eq!(
+        command_path_arguments("cd $HOME; rm -rf x"),
+        [PathBuf::from("$HOME")]
+    );
+}
+
+#[test]
+fn command_path_arguments_treats_bare_cd_as_home() {
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+    assert_eq!(command_path_arguments("cd; rm -rf x"), [home]);
+}
+
+#[test]
+fn path_rules_must_cover_the_paths_a_command_reaches() {
+    use crate::agent::approval_rules::{ApprovalRule, RuleEffect};
+
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let project = root.join("project");
+    std::fs::create_dir(&project).unwrap();
+    let rule = ApprovalRule {
+        path_glob: Some(format!("{}/**", root.display())),
+        ..ApprovalRule::new(RuleEffect::Allow)
+    };
+    let allows = |command: &str| {
+        let params = ShellExecParams {
+            command: command.to_string(),
+            working_dir: Some(project.display().to_string()),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            background: false,
+            session: false,
+        };
+        rule.matches(
+            &ToolCall::new("ShellExec")
+                .with_command(command)
+                .with_paths(rule_paths(&params)),
+        )
+    };
+
+    assert!(allows("ls src"));
+    assert!(allows("cat ../notes.txt"));
+    assert!(!allows("rm -rf ../../.."));
+    assert!(!allows("rm -rf /"));
+    assert!(!allows("rm -rf $HOME"));
+}
+
+#[tokio::test]
+async fn shell_exec_defaults_to_workspace_root_and_confines_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let policy = ToolApprovalPolicy {
+        persistent_allowlist: vec!["pwd".to_string(), "ls".to_string()],
+        persistent_denylist: vec!["outside_workspace:".to_string()],
+        ..ToolApprovalPolicy::default()
+    };
+    let context = McpToolContext {
+        workspace_roots: WorkspaceRoots::new([project.clone()]),
+        ..make_context_with_policy(policy)
+    };
+    let run_ctx = RunContext::new(context, "test-model");
+
+    let output = ShellExecExecutor
+        .execute(serde_json::json!({"command": "pwd"}), &run_ctx)
+        .await
+        .expect("command inside the workspace should run");
+    let serdes_ai::core::messages::ToolReturnContent::Text { content: text } = output.content
+    else {
+        panic!("expected text content");
+    };
+    let root = std::fs::canonicalize(&project).unwrap();
+    assert!(text.contains(&format!("Directory: {}", root.display())));
+
+    let error = ShellExecExecutor
+        .execute(serde_json::json!({"command": "ls .."}), &run_ctx)
+        .await
+        .expect_err("reaching above the root should be denied");
+    assert!(error.to_string().contains("outside the workspace"));
+}
+
+#[tokio::test]
+async fn shell_exec_outside_the_workspace_still_shows_the_command() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let (view_tx, mut view_rx) = tokio::sync::mpsc::channel(8);
+    let approval_gate = std::sync::Arc::new(crate::llm::client_agent::ApprovalGate::new());
+    let run_ctx = RunContext::new(
+        McpToolContext {
+            view_tx,
+            approval_gate: approval_gate.clone(),
+            workspace_roots: WorkspaceRoots::new([project.clone()]),
+            ..make_context_with_policy(ToolApprovalPolicy::default())
+        },
+        "test-model",
+    );
+    let command = "rm -rf ../scratch";
+    let args = serde_json::json!({ "command": command });
+    let handle = tokio::spawn(async move { ShellExecExecutor.execute(args, &run_ctx).await });
+
+    let Some(ViewCommand::ToolApprovalRequest {
+        request_id,
+        context,
+        ..
+    }) = view_rx.recv().await
+    else {
+        panic!("expected the outside-workspace request");
+    };
+    assert_eq!(context.category, ToolCategory::OutsideWorkspace);
+    let _ = approval_gate.resolve(&request_id, true);
+
+    let Some(ViewCommand::ToolApprovalRequest {
+        request_id,
+        context,
+        ..
+    }) = view_rx.recv().await
+    else {
+        panic!("expected the command's own request");
+    };
+    assert_eq!(context.category, ToolCategory::Shell);
+    assert_eq!(context.primary_target, command);
+    let _ = approval_gate.resolve(&request_id, false);
+
+    let error = handle
+        .await
+        .unwrap()
+        .expect_err("denied command should not run");
+    assert!(error.to_string().contains("denied by user"));
+}
+
+#[test]
+fn shell_exec_tool_definition_is_valid() {
+    let def = get_shell_exec_tool_definition();
+    assert_eq!(def.name, "ShellExec");
+    assert!(!def.description.is_empty());
+    assert!(def.parameters().is_object());
+}
@@ -0,0 +1,222 @@
+//! `TodoWrite` tool implementation.
+//!
+//! This module provides a built-in `TodoWriteExecutor` that replaces the
+//! conversation's task list. The list is stored through the conversation's
+//! `TodoService`, shown as a checklist in the chat view, and repeated in
+//! every later system prompt, so it survives history compression. Writing
+//! the list touches nothing outside the app, so it is not approval-gated.
+
+use crate::llm::client_agent::McpToolContext;
+use crate::models::{format_todo_list, TodoItem, TodoStatus};
+use crate::presentation::view_command::ViewCommand;
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+
+/// Most items a task list may hold.
+const MAX_TODO_ITEMS: usize = 50;
+
+/// Executor for the `TodoWrite` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct TodoWriteExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for TodoWriteExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let todos = parse_todos(&args)?;
+        let deps = ctx.deps();
+        let Some(todo_service) = deps.todo_service.as_ref() else {
+            return Err(ToolError::execution_failed(
+                "The task list is not available in this conversation",
+            ));
+        };
+
+        todo_service
+            .replace(deps.conversation_id, todos.clone())
+            .await
+            .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+        let _ = deps.view_tx.try_send(ViewCommand::TodoListUpdated {
+            conversation_id: deps.conversation_id,
+            todos: todos.clone(),
+        });
+
+        Ok(ToolReturn::text(if todos.is_empty() {
+            "Task list cleared".to_string()
+        } else {
+            let done = todos
+                .iter()
+                .filter(|item| item.status == TodoStatus::Completed)
+                .count();
+            format!(
+                "Task list updated ({done}/{} done):\n{}",
+                todos.len(),
+                format_todo_list(&todos)
+            )
+        }))
+    }
+}
+
+fn parse_todos(args: &serde_json::Value) -> Result<Vec<TodoItem>, ToolError> {
+    let items = args
+        .get("todos")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ToolError::execution_failed("Missing required 'todos' array"))?;
+    if items.len() > MAX_TODO_ITEMS {
+        return Err(ToolError::execution_failed(format!(
+            "Too many todos: {} (at most {MAX_TODO_ITEMS})",
+            items.len()
+        )));
+    }
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let content = item
+                .get("content")
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|content| !content.is_empty())
+                .ok_or_else(|| {
+                    ToolError::execution_failed(format!("Todo {index} has no 'content'"))
+                })?;
+            let status = item
+                .get("status")
+                .and_then(serde_json::Value::as_str)
+                .and_then(TodoStatus::parse)
+                .ok_or_else(|| {
+                    ToolError::execution_failed(format!(
+                        "Todo {index} needs a 'status' of pending, in_progress or completed"
+                    ))
+                })?;
+            Ok(TodoItem::new(content, status))
+        })
+        .collect()
+}
+
+/// Get the `TodoWrite` tool definition.
+#[must_use]
+pub fn get_todo_write_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "todos": {
+                "type": "array",
+                "description": "The complete task list, in order; it replaces the previous list. Pass an empty array to clear it",
+                "maxItems": MAX_TODO_ITEMS,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "content": {
+                            "type": "string",
+                            "description": "What the step is, in a few words"
+                        },
+                        "status": {
+                            "type": "string",
+                            "enum": ["pending", "in_progress", "completed"]
+                        }
+                    },
+                    "required": ["content", "status"]
+                }
+            }
+        },
+        "required": ["todos"]
+    });
+
+    ToolDefinition::new(
+        "TodoWrite",
+        "Keep a task list for multi-step work. Write the plan before starting, mark one step in_progress while working on it and completed as soon as it is done. The user sees the list, and it is kept when older messages are compressed.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{ServiceResult, TodoService};
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct MemoryTodos(Mutex<Vec<TodoItem>>);
+
+    #[async_trait::async_trait]
+    impl TodoService for MemoryTodos {
+        async fn list(&self, _conversation_id: Uuid) -> ServiceResult<Vec<TodoItem>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        async fn replace(&self, _conversation_id: Uuid, items: Vec<TodoItem>) -> ServiceResult<()> {
+            *self.0.lock().unwrap() = items;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn todo_write_stores_the_list_and_updates_the_view() {
+        let todos = Arc::new(MemoryTodos::default());
+        let (view_tx, mut view_rx) = tokio::sync::mpsc::channel(4);
+        let context = McpToolContext {
+            view_tx,
+            todo_service: Some(todos.clone()),
+            ..McpToolContext::default()
+        };
+
+        let result = TodoWriteExecutor
+            .execute(
+                serde_json::json!({ "todos": [
+                    { "content": "Read the parser", "status": "completed" },
+                    { "content": "Fix the bug", "status": "in_progress" }
+                ]}),
+                &RunContext::new(context, "test-model"),
+            )
+            .await
+            .expect("valid list should be written");
+
+        let text = result.as_text().expect("should have text content");
+        assert!(text.starts_with("Task list updated (1/2 done)"));
+        assert_eq!(todos.0.lock().unwrap().len(), 2);
+        let Some(ViewCommand::TodoListUpdated { todos: shown, .. }) = view_rx.recv().await else {
+            panic!("expected a TodoListUpdated command");
+        };
+        assert_eq!(
+            shown[1],
+            TodoItem::new("Fix the bug", TodoStatus::InProgress)
+        );
+    }
+
+    #[test]
+    fn parse_todos_rejects_unknown_statuses() {
+        let error = parse_todos(&serde_json::json!({ "todos": [
+            { "content": "Ship it", "status": "done" }
+        ]}))
+        .expect_err("unknown status should fail");
+
+        assert!(error.to_string().contains("in_progress"));
+    }
+
+    #[tokio::test]
+    async fn todo_write_fails_without_a_todo_service() {
+        let error = TodoWriteExecutor
+            .execute(
+                serde_json::json!({ "todos": [] }),
+                &RunContext::new(McpToolContext::default(), "test-model"),
+            )
+            .await
+            .expect_err("no store should fail");
+
+        assert!(error.to_string().contains("not available"));
+    }
+
+    #[test]
+    fn get_todo_write_tool_definition_returns_valid_schema() {
+        let def = get_todo_write_tool_definition();
+        assert_eq!(def.name, "TodoWrite");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
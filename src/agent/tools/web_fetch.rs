@@ -0,0 +1,502 @@
+//! `WebFetch` tool implementation.
+//!
+//! This module provides a built-in `WebFetchExecutor` that downloads an
+//! http(s) URL and returns it as text, converting HTML pages to Markdown.
+//! Each host the request reaches, including redirect targets, is checked
+//! against the policy's per-domain lists before any request is sent to it.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+use url::Url;
+
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::tool_approval_policy::{web_fetch_identifier, ToolApprovalDecision};
+use crate::agent::tools::html_markdown::html_to_markdown;
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+
+/// Time allowed for the whole request, body included.
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Redirects followed before giving up.
+const MAX_REDIRECTS: usize = 5;
+/// Bytes of response body read before the rest is dropped.
+const MAX_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+/// Characters of converted text returned to the model.
+const MAX_OUTPUT_CHARS: usize = 100_000;
+
+/// Client shared by every fetch. Redirects are not followed by reqwest so
+/// each hop can be checked against the domain policy first.
+static HTTP_CLIENT: LazyLock<Result<reqwest::Client, String>> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| e.to_string())
+});
+
+/// Executor for the `WebFetch` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct WebFetchExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for WebFetchExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let raw_url = args
+            .get("url")
+            .and_then(serde_json::Value::as_str)
+            .filter(|url| !url.trim().is_empty())
+            .ok_or_else(|| ToolError::execution_failed("Missing required 'url' argument"))?;
+        let url = parse_url(raw_url.trim())?;
+
+        let client = HTTP_CLIENT.as_ref().map_err(|e| {
+            ToolError::execution_failed(format!("Failed to build HTTP client: {e}"))
+        })?;
+
+        let (url, response) = fetch_following_redirects(ctx.deps(), client, url).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ToolError::execution_failed(format!(
+                "Request to {url} failed with HTTP {status}"
+            )));
+        }
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_ascii_lowercase()
+            });
+        let (body, body_truncated) = read_capped_body(response, &url).await?;
+
+        let text = match content_type.as_deref() {
+            Some("text/html" | "application/xhtml+xml") => {
+                html_to_markdown(&String::from_utf8_lossy(&body), Some(&url))
+            }
+            Some(content_type) if is_text_content_type(content_type) => {
+                String::from_utf8_lossy(&body).into_owned()
+            }
+            None if !body.contains(&0) => String::from_utf8_lossy(&body).into_owned(),
+            other => {
+                return Err(ToolError::execution_failed(format!(
+                    "Unsupported content type '{}' at {url}; WebFetch only returns text",
+                    other.unwrap_or("unknown")
+                )));
+            }
+        };
+
+        Ok(ToolReturn::text(format_output(
+            &url,
+            content_type.as_deref(),
+            &text,
+            body_truncated,
+        )))
+    }
+}
+
+fn parse_url(raw: &str) -> Result<Url, ToolError> {
+    let url = Url::parse(raw)
+        .map_err(|e| ToolError::execution_failed(format!("Invalid URL '{raw}': {e}")))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ToolError::execution_failed(format!(
+            "Unsupported URL scheme '{}': only http and https are allowed",
+            url.scheme()
+        )));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(ToolError::execution_failed(format!(
+            "URL has no host: {raw}"
+        )));
+    }
+    Ok(url)
+}
+
+/// Send the request, approving each new host before contacting it.
+async fn fetch_following_redirects(
+    tool_context: &McpToolContext,
+    client: &reqwest::Client,
+    mut url: Url,
+) -> Result<(Url, reqwest::Response), ToolError> {
+    let mut approved_host: Option<String> = None;
+    for _ in 0..=MAX_REDIRECTS {
+        let host = url.host_str().unwrap_or_default().to_string();
+        if approved_host.as_deref() != Some(host.as_str()) {
+            check_approval(tool_context, &host, url.as_str()).await?;
+            approved_host = Some(host);
+        }
+
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| request_error(&url, &e))?;
+        if !response.status().is_redirection() {
+            return Ok((url, response));
+        }
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| {
+                ToolError::execution_failed(format!(
+                    "Redirect from {url} has no usable Location header"
+                ))
+            })?;
+        let next = url.join(location).map_err(|e| {
+            ToolError::execution_failed(format!("Invalid redirect target '{location}': {e}"))
+        })?;
+        url = parse_url(next.as_str())?;
+    }
+
+    Err(ToolError::execution_failed(format!(
+        "Too many redirects (more than {MAX_REDIRECTS}) fetching {url}"
+    )))
+}
+
+fn request_error(url: &Url, error: &reqwest::Error) -> ToolError {
+    if error.is_timeout() {
+        ToolError::execution_failed(format!(
+            "Request to {url} timed out after {REQUEST_TIMEOUT_SECS}s"
+        ))
+    } else {
+        ToolError::execution_failed(format!("Request to {url} failed: {error}"))
+    }
+}
+
+/// Read at most `MAX_RESPONSE_BYTES` of the body; the flag reports whether
+/// more was available.
+async fn read_capped_body(
+    mut response: reqwest::Response,
+    url: &Url,
+) -> Result<(Vec<u8>, bool), ToolError> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| request_error(url, &e))? {
+        let remaining = MAX_RESPONSE_BYTES - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
+
+fn is_text_content_type(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || content_type.ends_with("json")
+        || content_type.ends_with("xml")
+        || matches!(
+            content_type,
+            "application/javascript"
+                | "application/x-yaml"
+                | "application/yaml"
+                | "application/toml"
+        )
+}
+
+fn format_output(
+    url: &Url,
+    content_type: Option<&str>,
+    text: &str,
+    body_truncated: bool,
+) -> String {
+    let mut output = format!(
+        "URL: {url}\nContent-Type: {}\n\n",
+        content_type.unwrap_or("unknown")
+    );
+    let mut truncated = body_truncated;
+    if let Some((end, _)) = text.char_indices().nth(MAX_OUTPUT_CHARS) {
+        output.push_str(&text[..end]);
+        truncated = true;
+    } else {
+        output.push_str(text);
+    }
+    if truncated {
+        output.push_str("\n\n... content truncated; the page is larger than WebFetch returns");
+    }
+    output
+}
+
+/// Check tool approval policy and await user decision if required.
+async fn check_approval(
+    tool_context: &McpToolContext,
+    host: &str,
+    url: &str,
+) -> Result<(), ToolError> {
+    let identifier = web_fetch_identifier(&host.to_ascii_lowercase());
+    let arguments = serde_json::json!({ "url": url });
+    let call = ToolCall::new("WebFetch")
+        .with_arguments(&arguments)
+        .with_identifier(identifier.clone());
+    let decision = {
+        let policy = tool_context.policy.lock().await;
+        policy.decide(&call, |policy| policy.evaluate_web_fetch(host))
+    };
+
+    match decision {
+        ToolApprovalDecision::Allow => Ok(()),
+        ToolApprovalDecision::Deny => Err(ToolError::execution_failed(format!(
+            "Tool execution denied by policy: {host} is on the WebFetch deny list or denied by a rule"
+        ))),
+        ToolApprovalDecision::AskUser => {
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let waiter = tool_context.approval_gate.wait_for_approval(
+                request_id.clone(),
+                identifier,
+                tool_context.conversation_id,
+            );
+
+            let context = ToolApprovalContext::new("WebFetch", ToolCategory::Web, url)
+                .with_detail("domain", host);
+
+            if tool_context
+                .view_tx
+                .try_send(ViewCommand::ToolApprovalRequest {
+                    conversation_id: tool_context.conversation_id,
+                    request_id: request_id.clone(),
+                    context,
+                })
+                .is_err()
+            {
+                let _ = tool_context.approval_gate.resolve(&request_id, false);
+                return Err(ToolError::execution_failed(
+                    "Failed to send approval request to UI (channel full or closed)",
+                ));
+            }
+
+            let approved = waiter.wait().await.unwrap_or(false);
+            if approved {
+                Ok(())
+            } else {
+                Err(ToolError::execution_failed("Tool execution denied by user"))
+            }
+        }
+    }
+}
+
+/// Get the `WebFetch` tool definition.
+#[must_use]
+pub fn get_web_fetch_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "url": {
+                "type": "string",
+                "description": "Absolute http or https URL to fetch"
+            }
+        },
+        "required": ["url"]
+    });
+
+    ToolDefinition::new(
+        "WebFetch",
+        "Fetch a web page or other text resource over http(s). HTML is converted to Markdown; JSON, XML and plain text are returned as-is. Large responses are truncated.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+    use crate::llm::client_agent::ApprovalGate;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn context_with(policy: ToolApprovalPolicy) -> RunContext<McpToolContext> {
+        RunContext::new(
+            McpToolContext {
+                policy: Arc::new(tokio::sync::Mutex::new(policy)),
+                ..Default::default()
+            },
+            "test-model",
+        )
+    }
+
+    fn yolo_context() -> RunContext<McpToolContext> {
+        context_with(ToolApprovalPolicy {
+            yolo_mode: true,
+            ..Default::default()
+        })
+    }
+
+    fn output_text(result: ToolReturn) -> String {
+        let serdes_ai::core::messages::ToolReturnContent::Text { content } = result.content else {
+            panic!("expected text content");
+        };
+        content
+    }
+
+    #[tokio::test]
+    async fn web_fetch_converts_html_after_following_redirects() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/old"))
+            .respond_with(ResponseTemplate::new(301).insert_header("Location", "/guide"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/guide"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html><head><title>Guide</title></head><body>\
+                 <p>Read <a href=\"/api\">the API</a>.</p></body></html>",
+                "text/html; charset=utf-8",
+            ))
+            .mount(&server)
+            .await;
+
+        let output = output_text(
+            WebFetchExecutor
+                .execute(
+                    serde_json::json!({ "url": format!("{}/old", server.uri()) }),
+                    &yolo_context(),
+                )
+                .await
+                .expect("fetch should succeed"),
+        );
+
+        assert!(output.starts_with(&format!(
+            "URL: {}/guide\nContent-Type: text/html",
+            server.uri()
+        )));
+        assert!(output.contains(&format!("# Guide\n\nRead [the API]({}/api).", server.uri())));
+    }
+
+    #[tokio::test]
+    async fn web_fetch_truncates_large_bodies() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("x".repeat(MAX_RESPONSE_BYTES + 10), "text/plain"),
+            )
+            .mount(&server)
+            .await;
+
+        let output = output_text(
+            WebFetchExecutor
+                .execute(serde_json::json!({ "url": server.uri() }), &yolo_context())
+                .await
+                .expect("fetch should succeed"),
+        );
+
+        assert!(output.ends_with("... content truncated; the page is larger than WebFetch returns"));
+        assert!(output.len() < MAX_RESPONSE_BYTES);
+    }
+
+    #[tokio::test]
+    async fn web_fetch_honours_denied_domains_without_sending_a_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let context = context_with(ToolApprovalPolicy {
+            yolo_mode: true,
+            web_fetch_denied_domains: vec!["127.0.0.1".to_string()],
+            ..Default::default()
+        });
+
+        let error = WebFetchExecutor
+            .execute(serde_json::json!({ "url": server.uri() }), &context)
+            .await
+            .expect_err("denied domain should fail");
+
+        assert!(error.to_string().contains("deny list"));
+    }
+
+    #[tokio::test]
+    async fn web_fetch_asks_for_unlisted_domains_with_web_category() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw("{\"ok\":true}", "application/json"),
+            )
+            .mount(&server)
+            .await;
+        let (view_tx, mut view_rx) = tokio::sync::mpsc::channel(4);
+        let approval_gate = Arc::new(ApprovalGate::new());
+        let context = RunContext::new(
+            McpToolContext {
+                view_tx,
+                approval_gate: approval_gate.clone(),
+                ..Default::default()
+            },
+            "test-model",
+        );
+        let url = format!("{}/status", server.uri());
+
+        let task = {
+            let url = url.clone();
+            tokio::spawn(async move {
+                WebFetchExecutor
+                    .execute(serde_json::json!({ "url": url }), &context)
+                    .await
+            })
+        };
+        let Some(ViewCommand::ToolApprovalRequest {
+            request_id,
+            context,
+            ..
+        }) = view_rx.recv().await
+        else {
+            panic!("expected an approval request");
+        };
+        assert_eq!(context.category, ToolCategory::Web);
+        assert_eq!(context.primary_target, url);
+        let _ = approval_gate.resolve(&request_id, true);
+
+        let output = output_text(task.await.unwrap().expect("approved fetch should succeed"));
+        assert!(output.ends_with("{\"ok\":true}"));
+    }
+
+    #[tokio::test]
+    async fn web_fetch_rejects_other_schemes_and_binary_content() {
+        let error = WebFetchExecutor
+            .execute(
+                serde_json::json!({ "url": "file:///etc/passwd" }),
+                &yolo_context(),
+            )
+            .await
+            .expect_err("file URLs should fail");
+        assert!(error.to_string().contains("Unsupported URL scheme"));
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(vec![0_u8, 1, 2], "image/png"))
+            .mount(&server)
+            .await;
+        let error = WebFetchExecutor
+            .execute(serde_json::json!({ "url": server.uri() }), &yolo_context())
+            .await
+            .expect_err("binary content should fail");
+        assert!(error
+            .to_string()
+            .contains("Unsupported content type 'image/png'"));
+    }
+
+    #[test]
+    fn get_web_fetch_tool_definition_returns_valid_schema() {
+        let def = get_web_fetch_tool_definition();
+        assert_eq!(def.name, "WebFetch");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
@@ -0,0 +1,168 @@
+//! Outside-workspace approval shared by the file and shell tools.
+//!
+//! A tool first evaluates its own policy decision, then passes it here with
+//! the paths it would touch outside the workspace roots. Denial on either side
+//! wins. Outside access that is not already allowlisted is put to the user as
+//! its own request, listing only the paths. Approving it does not approve the
+//! call itself: the tool's own decision still applies afterwards, so a command
+//! or diff that needs approval is still shown in its own prompt.
+
+use std::path::{Path, PathBuf};
+
+use serdes_ai_tools::ToolError;
+
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tool_audit::note_approval;
+use crate::agent::workspace::{outside_workspace_identifier, ResolvedPath, WorkspaceRoots};
+use crate::llm::client_agent::McpToolContext;
+use crate::models::ApprovalSource;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+
+/// Combine `tool_decision` with approval for the `outside` paths.
+///
+/// Returns the decision the tool should continue with, which is the tool's
+/// own decision once outside access is allowed.
+///
+/// # Errors
+///
+/// Returns an error when the user denies outside access or the approval
+/// request cannot reach the UI.
+pub(crate) async fn confine_decision(
+    tool_context: &McpToolContext,
+    tool_name: &str,
+    tool_decision: ToolApprovalDecision,
+    outside: &[PathBuf],
+) -> Result<ToolApprovalDecision, ToolError> {
+    if tool_decision == ToolApprovalDecision::Deny || outside.is_empty() {
+        return Ok(tool_decision);
+    }
+
+    let outside_decision = {
+        let policy = tool_context.policy.lock().await;
+        outside
+            .iter()
+            .map(|path| policy.evaluate_outside_workspace(path))
+            .fold(ToolApprovalDecision::Allow, |combined, decision| {
+                match (combined, decision) {
+                    (ToolApprovalDecision::Deny, _) | (_, ToolApprovalDecision::Deny) => {
+                        ToolApprovalDecision::Deny
+                    }
+                    (ToolApprovalDecision::AskUser, _) | (_, ToolApprovalDecision::AskUser) => {
+                        ToolApprovalDecision::AskUser
+                    }
+                    _ => ToolApprovalDecision::Allow,
+                }
+            })
+    };
+
+    match outside_decision {
+        ToolApprovalDecision::Deny => {
+            note_approval(ToolApprovalDecision::Deny, ApprovalSource::Policy, None);
+            Err(ToolError::execution_failed(
+                "Access outside the workspace denied by policy",
+            ))
+        }
+        ToolApprovalDecision::Allow => Ok(tool_decision),
+        ToolApprovalDecision::AskUser => {
+            request_outside_approval(tool_context, tool_name, outside).await?;
+            Ok(tool_decision)
+        }
+    }
+}
+
+/// Resolve a path for a read-only tool.
+///
+/// Without a workspace root, relative paths resolve against the current
+/// working directory.
+///
+/// # Errors
+///
+/// Returns an error when the current directory cannot be read.
+pub(crate) fn resolve_read_path(
+    workspace: &WorkspaceRoots,
+    path: &str,
+) -> Result<ResolvedPath, ToolError> {
+    let path = Path::new(path);
+    if let Some(resolved) = workspace.resolve(path) {
+        return Ok(resolved);
+    }
+    let cwd = std::env::current_dir().map_err(|e| {
+        ToolError::execution_failed(format!("Failed to get current directory: {e}"))
+    })?;
+    Ok(ResolvedPath {
+        path: cwd.join(path),
+        inside_workspace: true,
+    })
+}
+
+/// Directory an approval of `path` extends to when remembered.
+fn approval_scope(path: &Path) -> &Path {
+    if path.is_dir() {
+        path
+    } else {
+        // An unexpanded word such as `$HOME` has an empty parent, which
+        // would approve every path; approve the word itself instead.
+        path.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(path)
+    }
+}
+
+async fn request_outside_approval(
+    tool_context: &McpToolContext,
+    tool_name: &str,
+    outside: &[PathBuf],
+) -> Result<(), ToolError> {
+    let Some((first, rest)) = outside.split_first() else {
+        return Ok(());
+    };
+    let mut identifiers: Vec<String> = Vec::new();
+    for path in outside {
+        let identifier = outside_workspace_identifier(approval_scope(path));
+        if !identifiers.contains(&identifier) {
+            identifiers.push(identifier);
+        }
+    }
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let waiter = tool_context.approval_gate.wait_for_approvals(
+        request_id.clone(),
+        identifiers,
+        tool_context.conversation_id,
+    );
+
+    let mut context = ToolApprovalContext::new(
+        tool_name,
+        ToolCategory::OutsideWorkspace,
+        first.display().to_string(),
+    );
+    for path in rest {
+        context = context.with_detail("path", path.display().to_string());
+    }
+    if let Some(root) = tool_context.workspace_roots.primary() {
+        context = context.with_detail("workspace", root.display().to_string());
+    }
+
+    if tool_context
+        .view_tx
+        .try_send(ViewCommand::ToolApprovalRequest {
+            conversation_id: tool_context.conversation_id,
+            request_id: request_id.clone(),
+            context,
+        })
+        .is_err()
+    {
+        let _ = tool_context.approval_gate.resolve(&request_id, false);
+        return Err(ToolError::execution_failed(
+            "Failed to send approval request to UI (channel full or closed)",
+        ));
+    }
+
+    if waiter.wait().await.unwrap_or(false) {
+        Ok(())
+    } else {
+        Err(ToolError::execution_failed(
+            "Access outside the workspace denied by user",
+        ))
+    }
+}
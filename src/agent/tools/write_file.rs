@@ -3,13 +3,17 @@
 //! This module provides a built-in `WriteFileExecutor` that creates or fully
 //! overwrites files with support for creating parent directories.
 
+use crate::agent::approval_rules::ToolCall;
 use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::agent::tools::diff_preview::unified_diff;
+use crate::agent::tools::workspace_gate::confine_decision;
+use crate::agent::workspace::{ResolvedPath, WorkspaceRoots};
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
 use serdes_ai_agent::prelude::*;
 use serdes_ai_agent::ToolExecutor;
 use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Executor for the `WriteFile` built-in tool.
 ///
@@ -37,13 +41,10 @@ impl ToolExecutor<McpToolContext> for WriteFileExecutor {
             .ok_or_else(|| ToolError::execution_failed("Missing required 'content' argument"))?
             .to_string();
 
-        let absolute_path = resolve_path(&path)?;
+        let resolved = resolve_path(&ctx.deps().workspace_roots, &path)?;
+        let absolute_path = resolved.path.clone();
         let approval_path = absolute_path.display().to_string();
 
-        check_approval(ctx.deps(), &approval_path).await?;
-
-        ensure_parent_dirs(&absolute_path).await?;
-
         // Filter emojis from output content if enabled
         let filtered_content = if ctx.deps().filter_emoji {
             strip_emojis(&content)
@@ -51,6 +52,32 @@ impl ToolExecutor<McpToolContext> for WriteFileExecutor {
             content
         };
 
+        let call = ToolCall::new("WriteFile")
+            .with_arguments(&args)
+            .with_path(absolute_path.clone());
+        check_approval(
+            ctx.deps(),
+            &call,
+            &approval_path,
+            resolved.outside_workspace(),
+            &filtered_content,
+        )
+        .await?;
+
+        if let Some(checkpoints) = &ctx.deps().checkpoints {
+            checkpoints
+                .snapshot(&absolute_path)
+                .await
+                .map_err(|error| {
+                    ToolError::execution_failed(format!(
+                        "Failed to checkpoint '{}' before writing: {error}",
+                        absolute_path.display()
+                    ))
+                })?;
+        }
+
+        ensure_parent_dirs(&absolute_path).await?;
+
         write_content(&absolute_path, &filtered_content).await?;
 
         let line_count = filtered_content.lines().count();
@@ -99,11 +126,18 @@ const fn is_emoji(c: char) -> bool {
 }
 
 /// Check tool approval policy and await user decision if required.
-async fn check_approval(tool_context: &McpToolContext, path: &str) -> Result<(), ToolError> {
+async fn check_approval(
+    tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
+    path: &str,
+    outside_workspace: &[PathBuf],
+    content: &str,
+) -> Result<(), ToolError> {
     let decision = {
         let policy = tool_context.policy.lock().await;
-        policy.evaluate("WriteFile")
+        policy.decide(call, |policy| policy.evaluate("WriteFile"))
     };
+    let decision = confine_decision(tool_context, "WriteFile", decision, outside_workspace).await?;
 
     match decision {
         ToolApprovalDecision::Allow => Ok(()),
@@ -119,7 +153,8 @@ async fn check_approval(tool_context: &McpToolContext, path: &str) -> Result<(),
             );
 
             // Build rich context for approval UI
-            let context = ToolApprovalContext::new("WriteFile", ToolCategory::FileWrite, path);
+            let context = ToolApprovalContext::new("WriteFile", ToolCategory::FileWrite, path)
+                .with_diff(write_diff(Path::new(path), content).await);
 
             if tool_context
                 .view_tx
@@ -147,15 +182,26 @@ async fn check_approval(tool_context: &McpToolContext, path: &str) -> Result<(),
     }
 }
 
-/// Validate that the path is absolute and return it as a `PathBuf`.
-fn resolve_path(path: &str) -> Result<std::path::PathBuf, ToolError> {
-    let file_path = Path::new(path);
-    if !file_path.is_absolute() {
-        return Err(ToolError::execution_failed(
-            "The 'path' argument must be an absolute path",
-        ));
+/// Diff of `content` against the file's current text.
+///
+/// A missing file diffs as empty; unreadable or non-UTF-8 files get no diff.
+async fn write_diff(path: &Path, content: &str) -> String {
+    match tokio::fs::read_to_string(path).await {
+        Ok(current) => unified_diff(&current, content),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => unified_diff("", content),
+        Err(_) => String::new(),
     }
-    Ok(file_path.to_path_buf())
+}
+
+/// Resolve the path through the workspace roots.
+///
+/// Relative paths are only accepted when a workspace root is configured.
+fn resolve_path(workspace: &WorkspaceRoots, path: &str) -> Result<ResolvedPath, ToolError> {
+    workspace.resolve(Path::new(path)).ok_or_else(|| {
+        ToolError::execution_failed(
+            "The 'path' argument must be an absolute path when no workspace root is set",
+        )
+    })
 }
 
 /// Create parent directories if they don't exist.
@@ -212,7 +258,7 @@ pub fn get_write_file_tool_definition() -> ToolDefinition {
         "properties": {
             "path": {
                 "type": "string",
-                "description": "The absolute file path to write to"
+                "description": "The file path to write to; absolute, or relative to the workspace root"
             },
             "content": {
                 "type": "string",
@@ -445,6 +491,131 @@ mod tests {
         assert!(err.to_string().contains("absolute path"));
     }
 
+    fn workspace_context(root: &Path, policy: ToolApprovalPolicy) -> RunContext<McpToolContext> {
+        let context = McpToolContext {
+            policy: std::sync::Arc::new(tokio::sync::Mutex::new(policy)),
+            workspace_roots: WorkspaceRoots::new([root.to_path_buf()]),
+            ..Default::default()
+        };
+        RunContext::new(context, "test-model")
+    }
+
+    #[tokio::test]
+    async fn write_file_resolves_relative_path_inside_workspace() {
+        let dir = tempdir().unwrap();
+        let run_ctx = workspace_context(
+            dir.path(),
+            ToolApprovalPolicy {
+                yolo_mode: true,
+                ..Default::default()
+            },
+        );
+
+        let args = serde_json::json!({"path": "nested/out.txt", "content": "data"});
+        WriteFileExecutor.execute(args, &run_ctx).await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("nested/out.txt")).unwrap(),
+            "data"
+        );
+    }
+
+    #[tokio::test]
+    async fn write_file_outside_workspace_is_not_covered_by_yolo() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        let run_ctx = workspace_context(
+            &project,
+            ToolApprovalPolicy {
+                yolo_mode: true,
+                persistent_denylist: vec!["outside_workspace:".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let args = serde_json::json!({"path": "../escaped.txt", "content": "data"});
+        let err = WriteFileExecutor.execute(args, &run_ctx).await.unwrap_err();
+
+        assert!(err.to_string().contains("outside the workspace"));
+        assert!(!dir.path().join("escaped.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn write_file_checkpoints_prior_content_for_revert() {
+        use crate::agent::checkpoints::{CheckpointStore, TurnCheckpoints};
+
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("notes.txt");
+        std::fs::write(&file_path, "original").unwrap();
+        let store = std::sync::Arc::new(CheckpointStore::new(dir.path().join("checkpoints")));
+        let conversation_id = uuid::Uuid::new_v4();
+        let context = McpToolContext {
+            policy: std::sync::Arc::new(tokio::sync::Mutex::new(ToolApprovalPolicy {
+                yolo_mode: true,
+                ..Default::default()
+            })),
+            checkpoints: Some(TurnCheckpoints::new(store.clone(), conversation_id, 1)),
+            ..Default::default()
+        };
+        let run_ctx = RunContext::new(context, "test-model");
+
+        let args = serde_json::json!({"path": file_path.to_str().unwrap(), "content": "rewritten"});
+        WriteFileExecutor.execute(args, &run_ctx).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "rewritten");
+
+        let turns = store.list_turns(conversation_id).await.unwrap();
+        assert_eq!(turns[0].files, vec![file_path.clone()]);
+        store
+            .revert_turn(conversation_id, turns[0].turn_id)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "original");
+    }
+
+    #[tokio::test]
+    async fn write_file_approval_request_carries_diff_against_current_content() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("config.toml");
+        std::fs::write(&file_path, "name = \"old\"\nport = 80\n").unwrap();
+
+        let (view_tx, mut view_rx) = tokio::sync::mpsc::channel(10);
+        let approval_gate = std::sync::Arc::new(crate::llm::client_agent::ApprovalGate::new());
+        let run_ctx = RunContext::new(
+            McpToolContext {
+                view_tx,
+                approval_gate: approval_gate.clone(),
+                ..Default::default()
+            },
+            "test-model",
+        );
+        let args = serde_json::json!({
+            "path": file_path.to_str().unwrap(),
+            "content": "name = \"new\"\nport = 80\n",
+        });
+        let handle = tokio::spawn(async move { WriteFileExecutor.execute(args, &run_ctx).await });
+
+        let Some(ViewCommand::ToolApprovalRequest {
+            request_id,
+            context,
+            ..
+        }) = view_rx.recv().await
+        else {
+            panic!("expected ToolApprovalRequest");
+        };
+        assert_eq!(
+            context.diff.as_deref(),
+            Some("@@ -1,2 +1,2 @@\n-name = \"old\"\n+name = \"new\"\n port = 80")
+        );
+
+        let _ = approval_gate.resolve(&request_id, false);
+        assert!(handle.await.unwrap().is_err());
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "name = \"old\"\nport = 80\n"
+        );
+    }
+
     #[test]
     fn get_write_file_tool_definition_returns_valid_schema() {
         let def = get_write_file_tool_definition();
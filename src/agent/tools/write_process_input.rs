@@ -0,0 +1,175 @@
+//! `WriteProcessInput` tool implementation.
+//!
+//! This module provides a built-in `WriteProcessInputExecutor` that writes
+//! text to the stdin of a background `ShellExec` process. Input can drive the
+//! process as much as a new command can, so it is gated by the approval
+//! policy under the `Shell` category.
+
+use crate::agent::approval_rules::ToolCall;
+use crate::agent::processes::BackgroundProcesses;
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+use crate::llm::client_agent::McpToolContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
+use serdes_ai_agent::prelude::*;
+use serdes_ai_agent::ToolExecutor;
+use serdes_ai_tools::{ToolDefinition, ToolError, ToolReturn};
+
+/// Executor for the `WriteProcessInput` built-in tool.
+#[derive(Debug, Clone, Copy)]
+pub struct WriteProcessInputExecutor;
+
+#[async_trait::async_trait]
+impl ToolExecutor<McpToolContext> for WriteProcessInputExecutor {
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        ctx: &RunContext<McpToolContext>,
+    ) -> Result<ToolReturn, ToolError> {
+        let process_id = args
+            .get("process_id")
+            .and_then(serde_json::Value::as_str)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| ToolError::execution_failed("Missing required 'process_id' argument"))?;
+        let input = args
+            .get("input")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| ToolError::execution_failed("Missing required 'input' argument"))?;
+        let close = args
+            .get("close")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let call = ToolCall::new("WriteProcessInput").with_arguments(&args);
+        check_approval(ctx.deps(), &call, process_id, input).await?;
+
+        BackgroundProcesses::global()
+            .write_input(ctx.deps().conversation_id, process_id, input, close)
+            .await
+            .map_err(|error| ToolError::execution_failed(error.to_string()))?;
+
+        let closed = if close { " and closed its stdin" } else { "" };
+        Ok(ToolReturn::text(format!(
+            "Wrote {} bytes to {process_id}{closed}",
+            input.len()
+        )))
+    }
+}
+
+/// Check tool approval policy and await user decision if required.
+async fn check_approval(
+    tool_context: &McpToolContext,
+    call: &ToolCall<'_>,
+    process_id: &str,
+    input: &str,
+) -> Result<(), ToolError> {
+    let decision = {
+        let policy = tool_context.policy.lock().await;
+        policy.decide(call, |policy| policy.evaluate("WriteProcessInput"))
+    };
+
+    match decision {
+        ToolApprovalDecision::Allow => Ok(()),
+        ToolApprovalDecision::Deny => Err(ToolError::execution_failed(
+            "Tool execution denied by policy",
+        )),
+        ToolApprovalDecision::AskUser => {
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let waiter = tool_context.approval_gate.wait_for_approval(
+                request_id.clone(),
+                "WriteProcessInput".to_string(),
+                tool_context.conversation_id,
+            );
+
+            let context = ToolApprovalContext::new("WriteProcessInput", ToolCategory::Shell, input)
+                .with_detail("process", process_id);
+
+            if tool_context
+                .view_tx
+                .try_send(ViewCommand::ToolApprovalRequest {
+                    conversation_id: tool_context.conversation_id,
+                    request_id: request_id.clone(),
+                    context,
+                })
+                .is_err()
+            {
+                let _ = tool_context.approval_gate.resolve(&request_id, false);
+                return Err(ToolError::execution_failed(
+                    "Failed to send approval request to UI (channel full or closed)",
+                ));
+            }
+
+            let approved = waiter.wait().await.unwrap_or(false);
+            if approved {
+                Ok(())
+            } else {
+                Err(ToolError::execution_failed("Tool execution denied by user"))
+            }
+        }
+    }
+}
+
+/// Get the `WriteProcessInput` tool definition.
+#[must_use]
+pub fn get_write_process_input_tool_definition() -> ToolDefinition {
+    let input_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "process_id": {
+                "type": "string",
+                "description": "Id returned by ShellExec when the command was started in the background"
+            },
+            "input": {
+                "type": "string",
+                "description": "Text to write to the process's stdin; include a trailing newline to submit a line"
+            },
+            "close": {
+                "type": "boolean",
+                "description": "Close stdin after writing, signalling end of input (default false)"
+            }
+        },
+        "required": ["process_id", "input"]
+    });
+
+    ToolDefinition::new(
+        "WriteProcessInput",
+        "Write text to the stdin of a running background process.",
+    )
+    .with_parameters(input_schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn write_process_input_honours_policy_deny() {
+        let policy = ToolApprovalPolicy {
+            persistent_denylist: vec!["WriteProcessInput".to_string()],
+            ..Default::default()
+        };
+        let context = McpToolContext {
+            policy: Arc::new(tokio::sync::Mutex::new(policy)),
+            ..Default::default()
+        };
+
+        let error = WriteProcessInputExecutor
+            .execute(
+                serde_json::json!({ "process_id": "proc-1", "input": "y\n" }),
+                &RunContext::new(context, "test-model"),
+            )
+            .await
+            .expect_err("denied input should fail");
+
+        assert!(error.to_string().contains("denied by policy"));
+    }
+
+    #[test]
+    fn get_write_process_input_tool_definition_returns_valid_schema() {
+        let def = get_write_process_input_tool_definition();
+        assert_eq!(def.name, "WriteProcessInput");
+        assert!(!def.description.is_empty());
+        assert!(def.parameters().is_object());
+    }
+}
@@ -0,0 +1,358 @@
+//! Workspace roots that confine the built-in file and shell tools.
+//!
+//! A profile may name one or more root directories. Tool paths are resolved
+//! against the first root, `..` is collapsed before anything touches the disk,
+//! and the deepest existing ancestor is canonicalized so a symlink inside a
+//! root cannot lead a tool somewhere else. Paths that land outside every root
+//! need the separate outside-workspace approval, which lets a team auto-approve
+//! tools inside a project without also approving them for the whole machine.
+//!
+//! With no roots configured the tools keep their unconfined behaviour.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::{AppSettingsService, ServiceError, ServiceResult};
+
+/// Settings key for persisted workspace roots.
+pub const WORKSPACE_SETTINGS_KEY: &str = "workspace.roots";
+
+/// Approval identifier prefix for access outside the workspace roots.
+pub const OUTSIDE_WORKSPACE_PREFIX: &str = "outside_workspace:";
+
+/// Approval identifier for touching `path` outside the workspace.
+///
+/// The trailing separator makes an allowlisted directory match everything
+/// beneath it without also matching siblings that share its name as a prefix.
+#[must_use]
+pub fn outside_workspace_identifier(path: &Path) -> String {
+    let display = path.display().to_string();
+    if display.ends_with(MAIN_SEPARATOR) {
+        format!("{OUTSIDE_WORKSPACE_PREFIX}{display}")
+    } else {
+        format!("{OUTSIDE_WORKSPACE_PREFIX}{display}{MAIN_SEPARATOR}")
+    }
+}
+
+/// A tool path after workspace resolution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPath {
+    /// Absolute path the tool should operate on.
+    pub path: PathBuf,
+    /// Whether `path` lies under one of the roots (always true when unconfined).
+    pub inside_workspace: bool,
+}
+
+impl ResolvedPath {
+    /// The path as a one-element slice when it needs outside-workspace approval.
+    #[must_use]
+    pub const fn outside_workspace(&self) -> &[PathBuf] {
+        if self.inside_workspace {
+            &[]
+        } else {
+            std::slice::from_ref(&self.path)
+        }
+    }
+}
+
+/// Root directories the tools of one conversation are confined to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceRoots {
+    roots: Vec<PathBuf>,
+}
+
+impl WorkspaceRoots {
+    /// Build from configured roots; relative entries are ignored.
+    pub fn new(roots: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut canonical: Vec<PathBuf> = Vec::new();
+        for root in roots.into_iter().filter(|root| root.is_absolute()) {
+            let root = canonicalize_existing_prefix(&root);
+            if !canonical.contains(&root) {
+                canonical.push(root);
+            }
+        }
+        Self { roots: canonical }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Root that relative paths and the default shell directory resolve against.
+    #[must_use]
+    pub fn primary(&self) -> Option<&Path> {
+        self.roots.first().map(PathBuf::as_path)
+    }
+
+    /// Resolve a path supplied by a tool call.
+    ///
+    /// Relative paths join the primary root. Returns `None` for a relative
+    /// path when no root is configured, leaving the caller's old fallback in
+    /// place. Without roots, absolute paths are returned untouched.
+    #[must_use]
+    pub fn resolve(&self, path: &Path) -> Option<ResolvedPath> {
+        if self.roots.is_empty() {
+            return path.is_absolute().then(|| ResolvedPath {
+                path: path.to_path_buf(),
+                inside_workspace: true,
+            });
+        }
+        let base = self.primary()?.to_path_buf();
+        Some(self.resolve_from(&base, path))
+    }
+
+    /// Resolve `path` relative to `base`, e.g. a shell command's directory.
+    #[must_use]
+    pub fn resolve_from(&self, base: &Path, path: &Path) -> ResolvedPath {
+        let joined = base.join(path);
+        if self.roots.is_empty() {
+            return ResolvedPath {
+                path: joined,
+                inside_workspace: true,
+            };
+        }
+        let path = canonicalize_existing_prefix(&joined);
+        let inside_workspace = self.roots.iter().any(|root| path.starts_with(root));
+        ResolvedPath {
+            path,
+            inside_workspace,
+        }
+    }
+}
+
+/// Collapse `.` and `..` without consulting the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Canonicalize the longest existing prefix of `path` and re-append the rest.
+///
+/// Symlinks in the existing part are followed, so the result names where a
+/// write would really land even when the file itself does not exist yet.
+pub(crate) fn canonicalize_existing_prefix(path: &Path) -> PathBuf {
+    let normalized = normalize_lexically(path);
+    let mut existing = normalized.as_path();
+    let mut missing: Vec<OsString> = Vec::new();
+    loop {
+        if let Ok(canonical) = std::fs::canonicalize(existing) {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |resolved, part| resolved.join(part));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return normalized,
+        }
+    }
+}
+
+/// Workspace roots per profile, persisted as one app setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WorkspaceSettings(HashMap<Uuid, Vec<PathBuf>>);
+
+impl WorkspaceSettings {
+    /// Load roots from app settings, defaulting to none on malformed data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when reading from the settings service fails.
+    pub async fn load_from_settings(app_settings: &dyn AppSettingsService) -> ServiceResult<Self> {
+        let stored = app_settings.get_setting(WORKSPACE_SETTINGS_KEY).await?;
+        Ok(stored
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default())
+    }
+
+    /// Persist roots to app settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when serialization or the settings write fails.
+    pub async fn save_to_settings(
+        &self,
+        app_settings: &dyn AppSettingsService,
+    ) -> ServiceResult<()> {
+        let serialized = serde_json::to_string(self)
+            .map_err(|error| ServiceError::Serialization(error.to_string()))?;
+        app_settings
+            .set_setting(WORKSPACE_SETTINGS_KEY, serialized)
+            .await
+    }
+
+    /// Configured roots for `profile_id`, in the order they were added.
+    #[must_use]
+    pub fn profile_roots(&self, profile_id: Uuid) -> &[PathBuf] {
+        self.0.get(&profile_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Roots the tools of a conversation on `profile_id` are confined to.
+    #[must_use]
+    pub fn roots_for(&self, profile_id: Uuid) -> WorkspaceRoots {
+        WorkspaceRoots::new(self.profile_roots(profile_id).iter().cloned())
+    }
+
+    /// Add an absolute root; returns `false` for relative or duplicate paths.
+    pub fn add_root(&mut self, profile_id: Uuid, root: PathBuf) -> bool {
+        if !root.is_absolute() {
+            return false;
+        }
+        let roots = self.0.entry(profile_id).or_default();
+        if roots.contains(&root) {
+            return false;
+        }
+        roots.push(root);
+        true
+    }
+
+    /// Remove a root; returns `false` when it was not configured.
+    pub fn remove_root(&mut self, profile_id: Uuid, root: &Path) -> bool {
+        let Some(roots) = self.0.get_mut(&profile_id) else {
+            return false;
+        };
+        let original_len = roots.len();
+        roots.retain(|entry| entry != root);
+        let removed = roots.len() != original_len;
+        if roots.is_empty() {
+            self.0.remove(&profile_id);
+        }
+        removed
+    }
+
+    /// Every profile's roots as display strings, for settings surfaces.
+    #[must_use]
+    pub fn display_roots(&self) -> Vec<(Uuid, Vec<String>)> {
+        self.0
+            .iter()
+            .map(|(profile_id, roots)| {
+                (
+                    *profile_id,
+                    roots
+                        .iter()
+                        .map(|root| root.display().to_string())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn roots_for(dir: &Path) -> WorkspaceRoots {
+        WorkspaceRoots::new([dir.to_path_buf()])
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_primary_root() {
+        let dir = tempdir().unwrap();
+        let roots = roots_for(dir.path());
+
+        let resolved = roots.resolve(Path::new("src/main.rs")).unwrap();
+        assert!(resolved.inside_workspace);
+        assert_eq!(
+            resolved.path,
+            std::fs::canonicalize(dir.path())
+                .unwrap()
+                .join("src/main.rs")
+        );
+    }
+
+    #[test]
+    fn parent_traversal_out_of_root_is_outside_workspace() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        let roots = roots_for(&project);
+
+        let escaped = roots.resolve(Path::new("../secrets.txt")).unwrap();
+        assert!(!escaped.inside_workspace);
+        assert_eq!(escaped.outside_workspace(), [escaped.path.clone()]);
+
+        let contained = roots.resolve(Path::new("a/../b.txt")).unwrap();
+        assert!(contained.inside_workspace);
+        assert!(contained.outside_workspace().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_pointing_out_of_root_is_outside_workspace() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("project");
+        let elsewhere = dir.path().join("elsewhere");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::create_dir(&elsewhere).unwrap();
+        std::os::unix::fs::symlink(&elsewhere, project.join("link")).unwrap();
+        let roots = roots_for(&project);
+
+        let resolved = roots.resolve(Path::new("link/new-file.txt")).unwrap();
+        assert!(!resolved.inside_workspace);
+        assert!(resolved
+            .path
+            .starts_with(std::fs::canonicalize(&elsewhere).unwrap()));
+    }
+
+    #[test]
+    fn no_roots_leaves_paths_unconfined() {
+        let roots = WorkspaceRoots::default();
+        assert!(roots.resolve(Path::new("relative.txt")).is_none());
+
+        let absolute = std::env::temp_dir().join("..").join("anything");
+        let resolved = roots.resolve(&absolute).unwrap();
+        assert!(resolved.inside_workspace);
+        assert_eq!(resolved.path, absolute);
+    }
+
+    #[test]
+    fn outside_identifier_scopes_to_directory() {
+        let identifier = outside_workspace_identifier(Path::new("/srv/data"));
+        assert_eq!(
+            identifier,
+            format!("{OUTSIDE_WORKSPACE_PREFIX}/srv/data{MAIN_SEPARATOR}")
+        );
+    }
+
+    #[test]
+    fn settings_track_roots_per_profile() {
+        let profile_id = Uuid::new_v4();
+        let mut settings = WorkspaceSettings::default();
+
+        assert!(!settings.add_root(profile_id, PathBuf::from("relative")));
+        assert!(settings.add_root(profile_id, PathBuf::from("/work/app")));
+        assert!(!settings.add_root(profile_id, PathBuf::from("/work/app")));
+        assert_eq!(
+            settings.profile_roots(profile_id),
+            [PathBuf::from("/work/app")]
+        );
+        assert!(settings.roots_for(Uuid::new_v4()).is_empty());
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: WorkspaceSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, settings);
+
+        assert!(settings.remove_root(profile_id, Path::new("/work/app")));
+        assert!(settings.display_roots().is_empty());
+    }
+}
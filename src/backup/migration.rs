@@ -0,0 +1,56 @@
+//! Pre-migration backups.
+//!
+//! Before a database is moved to a newer schema, a copy of it is written
+//! through `BackupService` so the upgrade can be undone from the backup list.
+//! Fresh databases (schema version 0) hold nothing worth saving and are
+//! migrated directly.
+
+use tracing::info;
+
+use crate::backup::BackupResult;
+use crate::db::DbHandle;
+use crate::services::{BackupService, ServiceError, ServiceResult};
+
+/// Back up `db` if it has pending migrations, then apply them.
+///
+/// `db` is expected to come from `spawn_db_thread_unmigrated`. Returns the
+/// schema version after migrating.
+///
+/// # Errors
+///
+/// Returns an error if the database is newer than this build, if the backup
+/// does not succeed (migrations are then not attempted), or if a migration
+/// fails.
+pub async fn migrate_with_backup(
+    db: &DbHandle,
+    backup_service: &dyn BackupService,
+) -> ServiceResult<u32> {
+    let (current, pending) = db.pending_migrations().await?;
+    let Some(&target) = pending.last() else {
+        return Ok(current);
+    };
+
+    if current > 0 {
+        match backup_service
+            .create_pre_migration_backup(current, target)
+            .await?
+        {
+            BackupResult::Success { path, .. } => {
+                info!(
+                    "Backed up schema v{} database to {} before migrating to v{}",
+                    current,
+                    path.display(),
+                    target
+                );
+            }
+            BackupResult::Skipped { reason } | BackupResult::Failed { error: reason } => {
+                return Err(ServiceError::Storage(format!(
+                    "pre-migration backup did not complete ({reason}); \
+                     refusing to migrate schema v{current} to v{target}"
+                )));
+            }
+        }
+    }
+
+    db.migrate().await
+}
@@ -5,11 +5,14 @@
 //! - Rolling retention policy
 //! - Manual backup/restore support
 //! - Startup recovery for corrupted databases
+//! - Automatic backups before schema migrations
 
+pub mod migration;
 pub mod scheduler;
 pub mod settings;
 pub mod types;
 
+pub use migration::migrate_with_backup;
 pub use scheduler::{reset_scheduler_flag_for_tests, spawn_backup_scheduler, BackupScheduler};
 pub use settings::DatabaseBackupSettings;
 pub use types::{BackupInfo, BackupMetadata, BackupResult, RestoreResult};
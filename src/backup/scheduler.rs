@@ -408,6 +408,16 @@ mod tests {
         async fn should_backup(&self) -> crate::services::ServiceResult<bool> {
             Ok(true)
         }
+
+        async fn create_pre_migration_backup(
+            &self,
+            _from_version: u32,
+            _to_version: u32,
+        ) -> crate::services::ServiceResult<crate::backup::BackupResult> {
+            Ok(crate::backup::BackupResult::Skipped {
+                reason: "pre-migration backups not supported by mock".to_string(),
+            })
+        }
     }
 
     #[test]
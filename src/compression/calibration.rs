@@ -0,0 +1,125 @@
+//! Per-profile correction of token estimates from provider-reported usage.
+//!
+//! Local tokenizers only approximate what a provider bills: Claude and Gemini
+//! use their own vocabularies, and every request also carries a system prompt
+//! and tool schemas the estimate does not see. After each single-request turn
+//! the ratio of reported prompt tokens to the local estimate is folded into a
+//! running factor for the profile, which then scales the compression pipeline's
+//! usage ratio.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Lower bound for a correction factor; anything below is a bad sample.
+pub const MIN_CORRECTION: f64 = 0.5;
+/// Upper bound for a correction factor; anything above is a bad sample.
+pub const MAX_CORRECTION: f64 = 3.0;
+/// Weight of a new sample in the running factor.
+const SAMPLE_WEIGHT: f64 = 0.3;
+
+/// Learned correction for one profile.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TokenCalibration {
+    /// Reported prompt tokens divided by the local estimate.
+    pub factor: f64,
+    /// Number of turns folded into `factor`.
+    pub samples: u32,
+}
+
+/// Correction factors keyed by profile id, persisted as one app setting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TokenCalibrations(HashMap<Uuid, TokenCalibration>);
+
+impl TokenCalibrations {
+    /// Factor to apply for `profile_id`; `1.0` until a sample is recorded.
+    #[must_use]
+    pub fn factor_for(&self, profile_id: Uuid) -> f64 {
+        self.0
+            .get(&profile_id)
+            .map_or(1.0, |calibration| calibration.factor)
+    }
+
+    #[must_use]
+    pub fn get(&self, profile_id: Uuid) -> Option<&TokenCalibration> {
+        self.0.get(&profile_id)
+    }
+
+    /// Fold one turn into the profile's factor.
+    ///
+    /// Returns `false` and leaves the factor alone when either count is zero.
+    pub fn record(
+        &mut self,
+        profile_id: Uuid,
+        estimated_tokens: usize,
+        reported_tokens: u32,
+    ) -> bool {
+        if estimated_tokens == 0 || reported_tokens == 0 {
+            return false;
+        }
+        let estimated = estimated_tokens
+            .to_string()
+            .parse::<f64>()
+            .unwrap_or(f64::MAX);
+        let ratio = (f64::from(reported_tokens) / estimated).clamp(MIN_CORRECTION, MAX_CORRECTION);
+
+        self.0
+            .entry(profile_id)
+            .and_modify(|calibration| {
+                calibration.factor =
+                    (ratio - calibration.factor).mul_add(SAMPLE_WEIGHT, calibration.factor);
+                calibration.samples = calibration.samples.saturating_add(1);
+            })
+            .or_insert(TokenCalibration {
+                factor: ratio,
+                samples: 1,
+            });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sample_sets_factor_and_later_samples_smooth_it() {
+        let profile_id = Uuid::new_v4();
+        let mut calibrations = TokenCalibrations::default();
+        assert!((calibrations.factor_for(profile_id) - 1.0).abs() < f64::EPSILON);
+
+        assert!(calibrations.record(profile_id, 1_000, 1_200));
+        assert!((calibrations.factor_for(profile_id) - 1.2).abs() < 1e-9);
+
+        assert!(calibrations.record(profile_id, 1_000, 1_000));
+        assert!((calibrations.factor_for(profile_id) - 1.14).abs() < 1e-9);
+        assert_eq!(calibrations.get(profile_id).map(|c| c.samples), Some(2));
+    }
+
+    #[test]
+    fn outliers_are_clamped_and_empty_samples_ignored() {
+        let profile_id = Uuid::new_v4();
+        let mut calibrations = TokenCalibrations::default();
+
+        assert!(!calibrations.record(profile_id, 0, 500));
+        assert!(!calibrations.record(profile_id, 500, 0));
+        assert!(calibrations.get(profile_id).is_none());
+
+        assert!(calibrations.record(profile_id, 10, 1_000));
+        assert!((calibrations.factor_for(profile_id) - MAX_CORRECTION).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn serializes_as_a_map_of_profile_ids() {
+        let profile_id = Uuid::new_v4();
+        let mut calibrations = TokenCalibrations::default();
+        calibrations.record(profile_id, 100, 150);
+
+        let json = serde_json::to_value(&calibrations).unwrap();
+        assert_eq!(json[profile_id.to_string()]["samples"], 1);
+        let restored: TokenCalibrations = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, calibrations);
+    }
+}
@@ -1,5 +1,6 @@
 //! Context compression pipeline for long-running conversations.
 
+pub mod calibration;
 pub mod phases;
 pub mod pipeline;
 pub mod thinking_stripper;
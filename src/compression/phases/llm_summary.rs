@@ -0,0 +1,372 @@
+//! Model-generated summary of the middle of a long conversation.
+//!
+//! This is the preferred form of the sandwich phase: the same middle window the
+//! heuristic [`SandwichSummarizer`](super::sandwich_summary::SandwichSummarizer)
+//! would collapse is instead sent to a (usually small, cheap) summarizer model.
+//! The result is kept in `ContextState.summary` together with the range it
+//! covers, so on later turns only the messages that newly slid into the middle
+//! window are sent, along with the previous summary to extend.
+
+use std::fmt::Write as _;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use super::sandwich_summary::{summary_window, SummaryResult};
+use crate::config::CompressionConfig;
+use crate::llm::{LlmClient, Message as LlmMessage, Role};
+use crate::models::ModelProfile;
+
+const SUMMARY_CONTINUATION_DIRECTIVE: &str =
+    "Earlier turns of this conversation were condensed into the summary above. \
+     Continue the conversation using it as established context.";
+
+const SUMMARIZER_INSTRUCTIONS: &str = "You condense the middle of a long conversation between \
+a user and an AI assistant so the assistant can continue without the original messages. \
+Keep decisions, requirements, names, paths, identifiers, numbers and open questions. \
+Summarize tool calls by what they did and what they found; drop raw output. \
+If a previous summary is given, extend it with the new messages rather than restating it, \
+and drop details the new messages make obsolete. Reply with the summary only, as terse \
+markdown bullet points.";
+
+/// Maximum characters of a single tool result included in a summary request.
+///
+/// Tool output is the bulk of most long contexts and rarely matters verbatim.
+const MAX_TOOL_RESULT_CHARS: usize = 1_500;
+
+/// Sends a summary request to a model.
+///
+/// Implementations return the model's answer verbatim. Tests substitute a
+/// scripted stand-in so the incremental and fallback paths run without a
+/// provider.
+#[async_trait]
+pub trait ContextSummarizer: Send + Sync {
+    /// Ask `profile`'s model to answer `request`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the model cannot be reached.
+    async fn summarize(&self, profile: &ModelProfile, request: &[LlmMessage]) -> Result<String>;
+}
+
+/// Real summarizer backed by [`LlmClient`].
+pub struct LlmContextSummarizer;
+
+#[async_trait]
+impl ContextSummarizer for LlmContextSummarizer {
+    async fn summarize(&self, profile: &ModelProfile, request: &[LlmMessage]) -> Result<String> {
+        let client = LlmClient::from_profile(profile)
+            .map_err(|error| anyhow!("failed to create summarizer client: {error}"))?;
+        let response = client
+            .request(request)
+            .await
+            .map_err(|error| anyhow!("summary request failed: {error}"))?;
+        Ok(response.content)
+    }
+}
+
+/// Inputs for one run of the model-generated summary phase.
+pub struct LlmSummaryPhase<'a> {
+    pub summarizer: &'a dyn ContextSummarizer,
+    /// Profile of the summarizer model, which may differ from the chat model.
+    pub profile: &'a ModelProfile,
+    /// Summary persisted by a previous turn, with the message range it covers.
+    pub prior: Option<(&'a str, (usize, usize))>,
+}
+
+impl LlmSummaryPhase<'_> {
+    /// Replace the middle of `messages` with a model-written summary.
+    ///
+    /// A prior summary is reused as-is when it still covers the current middle
+    /// window, and extended with only the new messages when the window has
+    /// grown past it. Otherwise the whole window is summarized from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when no summary window can be formed, or when the
+    /// summarizer fails or answers with nothing; callers fall back to the
+    /// heuristic summary.
+    pub async fn summarize(
+        &self,
+        messages: &[LlmMessage],
+        config: &CompressionConfig,
+    ) -> Result<SummaryResult> {
+        let (start, end) = summary_window(messages.len(), config)?;
+
+        let (range, summary) = match self.prior {
+            Some((summary, (prior_start, prior_end)))
+                if prior_start <= start && prior_end == end =>
+            {
+                ((prior_start, end), summary.to_string())
+            }
+            Some((summary, (prior_start, prior_end)))
+                if prior_start <= start && prior_end < end =>
+            {
+                let request = build_summary_request(Some(summary), &messages[prior_end..end]);
+                ((prior_start, end), self.request(&request).await?)
+            }
+            _ => {
+                let request = build_summary_request(None, &messages[start..end]);
+                ((start, end), self.request(&request).await?)
+            }
+        };
+
+        let mut summarized = Vec::with_capacity(messages.len() - (range.1 - range.0) + 1);
+        summarized.extend_from_slice(&messages[..range.0]);
+        summarized.push(summary_message(&summary));
+        summarized.extend_from_slice(&messages[range.1..]);
+
+        Ok(SummaryResult {
+            messages: summarized,
+            summary_range: Some(range),
+            preserved_facts: Vec::new(),
+            summary: Some(summary),
+        })
+    }
+
+    async fn request(&self, request: &[LlmMessage]) -> Result<String> {
+        let response = self.summarizer.summarize(self.profile, request).await?;
+        let summary = response.trim();
+        if summary.is_empty() {
+            return Err(anyhow!("summarizer returned an empty summary"));
+        }
+        Ok(summary.to_string())
+    }
+}
+
+fn summary_message(summary: &str) -> LlmMessage {
+    LlmMessage {
+        role: Role::System,
+        content: format!("<summary>\n{summary}\n</summary>\n{SUMMARY_CONTINUATION_DIRECTIVE}"),
+        thinking_content: None,
+        tool_uses: Vec::new(),
+        tool_results: Vec::new(),
+    }
+}
+
+/// Build the request asking for a summary of `messages`, extending
+/// `previous_summary` when given.
+#[must_use]
+pub fn build_summary_request(
+    previous_summary: Option<&str>,
+    messages: &[LlmMessage],
+) -> Vec<LlmMessage> {
+    let mut prompt = String::new();
+    if let Some(previous) = previous_summary {
+        prompt.push_str("<previous_summary>\n");
+        prompt.push_str(previous);
+        prompt.push_str("\n</previous_summary>\n");
+    }
+    prompt.push_str("<messages>\n");
+    for message in messages {
+        render_transcript_entry(&mut prompt, message);
+    }
+    prompt.push_str("</messages>");
+
+    vec![
+        LlmMessage::system(SUMMARIZER_INSTRUCTIONS),
+        LlmMessage::user(prompt),
+    ]
+}
+
+fn render_transcript_entry(output: &mut String, message: &LlmMessage) {
+    let role = match message.role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::System => "system",
+    };
+    let content = message.content.trim();
+    if !content.is_empty() {
+        let _ = writeln!(output, "[{role}] {content}");
+    }
+    for tool in &message.tool_uses {
+        let _ = writeln!(output, "[tool call] {} {}", tool.name, tool.input);
+    }
+    for result in &message.tool_results {
+        let label = if result.is_error {
+            "tool error"
+        } else {
+            "tool result"
+        };
+        let text = result.content.trim();
+        let mut clipped: String = text.chars().take(MAX_TOOL_RESULT_CHARS).collect();
+        if clipped.len() < text.len() {
+            clipped.push_str(" [...]");
+        }
+        let _ = writeln!(output, "[{label}] {clipped}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Local stand-in for a summarizer model: answers from a script and
+    /// records every request it receives.
+    #[derive(Default)]
+    struct ScriptedSummarizer {
+        responses: Mutex<VecDeque<Result<String>>>,
+        requests: Mutex<Vec<Vec<LlmMessage>>>,
+    }
+
+    impl ScriptedSummarizer {
+        fn answering(responses: Vec<Result<String>>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into()),
+                requests: Mutex::default(),
+            }
+        }
+
+        fn prompts(&self) -> Vec<String> {
+            self.requests
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|request| request[1].content.clone())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl ContextSummarizer for ScriptedSummarizer {
+        async fn summarize(
+            &self,
+            _profile: &ModelProfile,
+            request: &[LlmMessage],
+        ) -> Result<String> {
+            self.requests.lock().unwrap().push(request.to_vec());
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(anyhow!("script exhausted")))
+        }
+    }
+
+    fn config() -> CompressionConfig {
+        CompressionConfig {
+            preserve_top_fraction: 0.1,
+            preserve_bottom_fraction: 0.2,
+            min_middle_messages: 2,
+            ..CompressionConfig::default()
+        }
+    }
+
+    fn transcript(len: usize) -> Vec<LlmMessage> {
+        (0..len)
+            .map(|index| {
+                if index % 2 == 0 {
+                    LlmMessage::user(format!("question {index}"))
+                } else {
+                    LlmMessage::assistant(format!("answer {index}"))
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn first_summary_covers_whole_middle_window() {
+        let summarizer = ScriptedSummarizer::answering(vec![Ok("- asked about 1-7".to_string())]);
+        let profile = ModelProfile::default();
+        let phase = LlmSummaryPhase {
+            summarizer: &summarizer,
+            profile: &profile,
+            prior: None,
+        };
+
+        let result = phase.summarize(&transcript(10), &config()).await.unwrap();
+
+        assert_eq!(result.summary_range, Some((1, 8)));
+        assert_eq!(result.summary.as_deref(), Some("- asked about 1-7"));
+        assert_eq!(result.messages.len(), 4);
+        assert!(result.messages[1].content.contains("- asked about 1-7"));
+        let prompts = summarizer.prompts();
+        assert!(prompts[0].contains("[assistant] answer 1"));
+        assert!(prompts[0].contains("[assistant] answer 7"));
+        assert!(!prompts[0].contains("question 8"));
+        assert!(!prompts[0].contains("<previous_summary>"));
+    }
+
+    #[tokio::test]
+    async fn grown_window_sends_only_new_messages_with_prior_summary() {
+        let summarizer = ScriptedSummarizer::answering(vec![Ok("- extended".to_string())]);
+        let profile = ModelProfile::default();
+        let phase = LlmSummaryPhase {
+            summarizer: &summarizer,
+            profile: &profile,
+            prior: Some(("- asked about 1-7", (1, 8))),
+        };
+
+        let result = phase.summarize(&transcript(14), &config()).await.unwrap();
+
+        assert_eq!(result.summary_range, Some((1, 12)));
+        assert_eq!(result.summary.as_deref(), Some("- extended"));
+        assert_eq!(result.messages.len(), 4);
+        let prompt = &summarizer.prompts()[0];
+        assert!(prompt.contains("<previous_summary>\n- asked about 1-7\n</previous_summary>"));
+        assert!(prompt.contains("question 8"));
+        assert!(prompt.contains("answer 11"));
+        assert!(!prompt.contains("answer 7"));
+        assert!(!prompt.contains("question 12"));
+    }
+
+    #[tokio::test]
+    async fn unchanged_window_reuses_prior_summary_without_request() {
+        let summarizer = ScriptedSummarizer::default();
+        let profile = ModelProfile::default();
+        let phase = LlmSummaryPhase {
+            summarizer: &summarizer,
+            profile: &profile,
+            prior: Some(("- cached", (1, 8))),
+        };
+
+        let result = phase.summarize(&transcript(10), &config()).await.unwrap();
+
+        assert_eq!(result.summary.as_deref(), Some("- cached"));
+        assert!(summarizer.prompts().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stale_prior_and_failures_are_reported() {
+        let summarizer = ScriptedSummarizer::answering(vec![
+            Err(anyhow!("provider down")),
+            Ok("   ".to_string()),
+        ]);
+        let profile = ModelProfile::default();
+        let phase = LlmSummaryPhase {
+            summarizer: &summarizer,
+            profile: &profile,
+            prior: Some(("- from a longer history", (1, 20))),
+        };
+
+        assert!(phase.summarize(&transcript(10), &config()).await.is_err());
+        assert!(phase.summarize(&transcript(10), &config()).await.is_err());
+        let prompts = summarizer.prompts();
+        assert_eq!(prompts.len(), 2);
+        assert!(!prompts[0].contains("<previous_summary>"));
+    }
+
+    #[test]
+    fn request_clips_long_tool_results() {
+        let message = LlmMessage::assistant("")
+            .with_tool_uses(vec![crate::llm::tools::ToolUse::new(
+                "t1",
+                "read_file",
+                serde_json::json!({"path": "big.log"}),
+            )])
+            .with_tool_results(vec![crate::llm::tools::ToolResult::success(
+                "t1",
+                "x".repeat(MAX_TOOL_RESULT_CHARS + 50),
+            )]);
+
+        let request = build_summary_request(None, &[message]);
+
+        assert_eq!(request[0].role, Role::System);
+        assert!(request[1].content.contains("[tool call] read_file"));
+        assert!(request[1].content.contains(" [...]"));
+        assert!(request[1].content.len() < MAX_TOOL_RESULT_CHARS + 200);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod llm_summary;
 pub mod observation_masking;
 pub mod sandwich_summary;
 pub mod truncation;
@@ -11,6 +11,8 @@ pub struct SummaryResult {
     pub messages: Vec<LlmMessage>,
     pub summary_range: Option<(usize, usize)>,
     pub preserved_facts: Vec<String>,
+    /// Model-written summary text; `None` for the heuristic summary.
+    pub summary: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -33,30 +35,7 @@ impl SandwichSummarizer {
         messages: &[LlmMessage],
         config: &CompressionConfig,
     ) -> Result<SummaryResult> {
-        if messages.len() < config.min_middle_messages + 2 {
-            return Err(anyhow!("not enough messages to summarize"));
-        }
-
-        let top_count = scaled_fraction_count(messages.len(), config.preserve_top_fraction).max(1);
-        let bottom_count =
-            scaled_fraction_count(messages.len(), config.preserve_bottom_fraction).max(1);
-        let mut middle_start = top_count.min(messages.len());
-        let mut middle_end = messages.len().saturating_sub(bottom_count);
-
-        if middle_end <= middle_start {
-            return Err(anyhow!("no middle range available for summarization"));
-        }
-
-        while middle_end.saturating_sub(middle_start) < config.min_middle_messages {
-            if middle_start > 0 {
-                middle_start -= 1;
-            } else if middle_end < messages.len() {
-                middle_end += 1;
-            } else {
-                return Err(anyhow!("unable to form summary window"));
-            }
-        }
-
+        let (middle_start, middle_end) = summary_window(messages.len(), config)?;
         let middle_messages = &messages[middle_start..middle_end];
         let preserved_facts = collect_preserved_facts(middle_messages);
         let summary_text = render_structured_summary(middle_messages, &preserved_facts);
@@ -77,10 +56,44 @@ impl SandwichSummarizer {
             messages: summarized,
             summary_range: Some((middle_start, middle_end)),
             preserved_facts,
+            summary: None,
         })
     }
 }
 
+/// Pick the `[start, end)` range of messages to collapse into a summary.
+///
+/// # Errors
+///
+/// Returns an error when there are too few messages to summarize or when a valid
+/// middle window cannot be formed from the configured preservation rules.
+pub(crate) fn summary_window(len: usize, config: &CompressionConfig) -> Result<(usize, usize)> {
+    if len < config.min_middle_messages + 2 {
+        return Err(anyhow!("not enough messages to summarize"));
+    }
+
+    let top_count = scaled_fraction_count(len, config.preserve_top_fraction).max(1);
+    let bottom_count = scaled_fraction_count(len, config.preserve_bottom_fraction).max(1);
+    let mut middle_start = top_count.min(len);
+    let mut middle_end = len.saturating_sub(bottom_count);
+
+    if middle_end <= middle_start {
+        return Err(anyhow!("no middle range available for summarization"));
+    }
+
+    while middle_end.saturating_sub(middle_start) < config.min_middle_messages {
+        if middle_start > 0 {
+            middle_start -= 1;
+        } else if middle_end < len {
+            middle_end += 1;
+        } else {
+            return Err(anyhow!("unable to form summary window"));
+        }
+    }
+
+    Ok((middle_start, middle_end))
+}
+
 #[must_use]
 fn scaled_fraction_count(total: usize, fraction: f64) -> usize {
     let fraction = fraction.clamp(0.0, 1.0);
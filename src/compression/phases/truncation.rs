@@ -22,6 +22,11 @@ impl TopDownTruncator {
         }
     }
 
+    #[must_use]
+    pub const fn with_estimator(estimator: TokenEstimator) -> Self {
+        Self { estimator }
+    }
+
     #[must_use]
     pub fn truncate(
         &self,
@@ -37,7 +42,7 @@ impl TopDownTruncator {
         let mut dropped = 0;
 
         while messages.len() > config.min_keep_messages
-            && self.estimator.estimate_llm_context_tokens(messages) > target_tokens
+            && self.estimator.calibrated_context_tokens(messages) > target_tokens
         {
             let remove_index = usize::from(preserve_system);
             if remove_index >= messages.len() {
@@ -1,5 +1,6 @@
+use crate::compression::phases::llm_summary::LlmSummaryPhase;
 use crate::compression::phases::observation_masking::ObservationMasker;
-use crate::compression::phases::sandwich_summary::SandwichSummarizer;
+use crate::compression::phases::sandwich_summary::{SandwichSummarizer, SummaryResult};
 use crate::compression::phases::truncation::TopDownTruncator;
 use crate::compression::token_estimation::TokenEstimator;
 use crate::config::CompressionConfig;
@@ -13,6 +14,9 @@ pub struct CompressionResult {
     pub masked_tool_seqs: Option<Vec<usize>>,
     pub summary_range: Option<(usize, usize)>,
     pub preserved_facts: Option<Vec<String>>,
+    /// Model-written summary covering `summary_range`, if one was produced.
+    pub summary: Option<String>,
+    /// Uncorrected tokenizer estimate of `messages`.
     pub estimated_tokens: usize,
 }
 
@@ -29,8 +33,58 @@ impl CompressionPipeline {
         }
     }
 
+    /// Use `estimator` for usage ratios and truncation, e.g. one built for
+    /// the chat model and calibrated against its reported usage.
+    #[must_use]
+    pub const fn with_estimator(estimator: TokenEstimator) -> Self {
+        Self { estimator }
+    }
+
     #[must_use]
     pub fn compress(
+        &self,
+        messages: Vec<LlmMessage>,
+        context_window: usize,
+        config: &CompressionConfig,
+    ) -> CompressionResult {
+        let mut result = self.mask_observations(messages, context_window, config);
+        if self.needs_summary(&result, context_window, config) {
+            if let Ok(summary) = SandwichSummarizer::new().summarize(&result.messages, config) {
+                apply_summary(&mut result, summary);
+            }
+        }
+        self.truncate(result, context_window, config)
+    }
+
+    /// Like [`compress`](Self::compress), but asks a model for the middle
+    /// summary, falling back to the heuristic summary when that fails.
+    pub async fn compress_with_llm_summary(
+        &self,
+        messages: Vec<LlmMessage>,
+        context_window: usize,
+        config: &CompressionConfig,
+        llm_summary: &LlmSummaryPhase<'_>,
+    ) -> CompressionResult {
+        let mut result = self.mask_observations(messages, context_window, config);
+        if self.needs_summary(&result, context_window, config) {
+            let summary = match llm_summary.summarize(&result.messages, config).await {
+                Ok(summary) => Ok(summary),
+                Err(error) => {
+                    tracing::warn!(
+                        error = %error,
+                        "Model summary failed; using heuristic summary"
+                    );
+                    SandwichSummarizer::new().summarize(&result.messages, config)
+                }
+            };
+            if let Ok(summary) = summary {
+                apply_summary(&mut result, summary);
+            }
+        }
+        self.truncate(result, context_window, config)
+    }
+
+    fn mask_observations(
         &self,
         mut messages: Vec<LlmMessage>,
         context_window: usize,
@@ -38,56 +92,72 @@ impl CompressionPipeline {
     ) -> CompressionResult {
         let mut phase = CompressionPhase::None;
         let mut masked_tool_seqs = None;
-        let mut summary_range = None;
-        let mut preserved_facts = None;
 
-        let mut usage_ratio = self.estimator.usage_ratio(&messages, context_window);
-        if usage_ratio >= config.observation_mask_threshold {
+        if self.estimator.usage_ratio(&messages, context_window)
+            >= config.observation_mask_threshold
+        {
             let masking = ObservationMasker::new(config).mask_observations(&mut messages);
             if !masking.masked_message_indices.is_empty() {
                 phase = CompressionPhase::ObservationMasked;
                 masked_tool_seqs = Some(masking.masked_message_indices);
             }
-            usage_ratio = self.estimator.usage_ratio(&messages, context_window);
         }
 
-        if usage_ratio >= config.sandwich_summary_threshold {
-            if let Ok(summary) = SandwichSummarizer::new().summarize(&messages, config) {
-                messages = summary.messages;
-                phase = CompressionPhase::Summarized;
-                summary_range = summary.summary_range;
-                if !summary.preserved_facts.is_empty() {
-                    preserved_facts = Some(summary.preserved_facts);
-                }
-            }
-            usage_ratio = self.estimator.usage_ratio(&messages, context_window);
+        CompressionResult {
+            messages,
+            phase,
+            masked_tool_seqs,
+            summary_range: None,
+            preserved_facts: None,
+            summary: None,
+            estimated_tokens: 0,
         }
+    }
+
+    fn needs_summary(
+        &self,
+        result: &CompressionResult,
+        context_window: usize,
+        config: &CompressionConfig,
+    ) -> bool {
+        self.estimator.usage_ratio(&result.messages, context_window)
+            >= config.sandwich_summary_threshold
+    }
 
+    fn truncate(
+        &self,
+        mut result: CompressionResult,
+        context_window: usize,
+        config: &CompressionConfig,
+    ) -> CompressionResult {
+        let usage_ratio = self.estimator.usage_ratio(&result.messages, context_window);
         if usage_ratio >= config.truncation_threshold {
-            let mut truncated_messages = messages;
-            let truncation = TopDownTruncator::new().truncate(
-                &mut truncated_messages,
+            let truncation = TopDownTruncator::with_estimator(self.estimator.clone()).truncate(
+                &mut result.messages,
                 usage_ratio,
                 context_window,
                 config,
             );
             if truncation.dropped_messages > 0 {
-                phase = CompressionPhase::Truncated;
+                result.phase = CompressionPhase::Truncated;
             }
-            messages = truncated_messages;
         }
 
-        CompressionResult {
-            estimated_tokens: self.estimator.estimate_llm_context_tokens(&messages),
-            messages,
-            phase,
-            masked_tool_seqs,
-            summary_range,
-            preserved_facts,
-        }
+        result.estimated_tokens = self.estimator.estimate_llm_context_tokens(&result.messages);
+        result
     }
 }
 
+fn apply_summary(result: &mut CompressionResult, summary: SummaryResult) {
+    result.messages = summary.messages;
+    result.phase = CompressionPhase::Summarized;
+    result.summary_range = summary.summary_range;
+    if !summary.preserved_facts.is_empty() {
+        result.preserved_facts = Some(summary.preserved_facts);
+    }
+    result.summary = summary.summary;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +186,81 @@ mod tests {
         assert_eq!(result.phase, CompressionPhase::ObservationMasked);
         assert_eq!(result.masked_tool_seqs, Some(vec![0]));
     }
+
+    /// Summarizer stand-in that answers with `Some` text or fails on `None`.
+    struct FixedSummarizer(Option<&'static str>);
+
+    #[async_trait::async_trait]
+    impl crate::compression::phases::llm_summary::ContextSummarizer for FixedSummarizer {
+        async fn summarize(
+            &self,
+            _profile: &crate::models::ModelProfile,
+            _request: &[LlmMessage],
+        ) -> anyhow::Result<String> {
+            self.0
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("offline"))
+        }
+    }
+
+    fn summary_config() -> CompressionConfig {
+        CompressionConfig {
+            observation_mask_threshold: 2.0,
+            sandwich_summary_threshold: 0.0,
+            truncation_threshold: 3.0,
+            min_middle_messages: 2,
+            ..CompressionConfig::default()
+        }
+    }
+
+    fn long_history() -> Vec<LlmMessage> {
+        (0..8)
+            .map(|index| LlmMessage::user(format!("message {index}")))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn llm_summary_replaces_middle_and_is_reported() {
+        let summarizer = FixedSummarizer(Some("- the gist"));
+        let profile = crate::models::ModelProfile::default();
+        let phase = LlmSummaryPhase {
+            summarizer: &summarizer,
+            profile: &profile,
+            prior: None,
+        };
+
+        let result = CompressionPipeline::new()
+            .compress_with_llm_summary(long_history(), 1_000, &summary_config(), &phase)
+            .await;
+
+        assert_eq!(result.phase, CompressionPhase::Summarized);
+        assert_eq!(result.summary.as_deref(), Some("- the gist"));
+        assert!(result
+            .messages
+            .iter()
+            .any(|message| message.content.contains("- the gist")));
+    }
+
+    #[tokio::test]
+    async fn llm_summary_failure_falls_back_to_heuristic() {
+        let summarizer = FixedSummarizer(None);
+        let profile = crate::models::ModelProfile::default();
+        let phase = LlmSummaryPhase {
+            summarizer: &summarizer,
+            profile: &profile,
+            prior: None,
+        };
+
+        let result = CompressionPipeline::new()
+            .compress_with_llm_summary(long_history(), 1_000, &summary_config(), &phase)
+            .await;
+
+        assert_eq!(result.phase, CompressionPhase::Summarized);
+        assert_eq!(result.summary, None);
+        assert!(result.summary_range.is_some());
+        assert!(result
+            .messages
+            .iter()
+            .any(|message| message.content.contains("<facts>")));
+    }
 }
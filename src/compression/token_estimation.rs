@@ -1,17 +1,86 @@
+use std::sync::OnceLock;
+
 use crate::llm::Message as LlmMessage;
 use crate::models::Message;
-use tiktoken_rs::{cl100k_base, CoreBPE};
+use tiktoken_rs::{cl100k_base, o200k_base, CoreBPE};
 
 const MESSAGE_OVERHEAD_TOKENS: usize = 4;
 
+/// Average characters per token assumed for models without a known tokenizer.
+const HEURISTIC_CHARS_PER_TOKEN: usize = 4;
+
+/// Tokenizer used to estimate a model's token counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizerFamily {
+    /// `o200k_base`: GPT-4o, GPT-4.1, GPT-5 and the o-series.
+    O200k,
+    /// `cl100k_base`: GPT-4, GPT-3.5, and a close approximation for Claude.
+    Cl100k,
+    /// Character-count heuristic for models with no public tokenizer.
+    Heuristic,
+}
+
+impl TokenizerFamily {
+    /// Pick the tokenizer family for a model id such as `gpt-4o-mini` or
+    /// `anthropic/claude-sonnet-4`. Router prefixes before the last `/` are
+    /// ignored.
+    #[must_use]
+    pub fn for_model(model_id: &str) -> Self {
+        let model = model_id.rsplit('/').next().unwrap_or(model_id);
+        let model = model.to_ascii_lowercase();
+        let starts_with_any = |prefixes: &[&str]| prefixes.iter().any(|p| model.starts_with(p));
+
+        if starts_with_any(&[
+            "gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-5", "gpt-oss", "chatgpt-", "o1", "o3", "o4",
+            "codex",
+        ]) {
+            Self::O200k
+        } else if starts_with_any(&["gpt-4", "gpt-3.5", "text-embedding", "claude"]) {
+            Self::Cl100k
+        } else {
+            Self::Heuristic
+        }
+    }
+
+    /// Shared tokenizer for this family; `None` for the heuristic.
+    ///
+    /// Building a BPE table takes noticeable time, so each is built once.
+    fn tokenizer(self) -> Option<&'static CoreBPE> {
+        static O200K: OnceLock<CoreBPE> = OnceLock::new();
+        static CL100K: OnceLock<CoreBPE> = OnceLock::new();
+        match self {
+            Self::O200k => Some(
+                O200K.get_or_init(|| o200k_base().expect("o200k_base tokenizer should initialize")),
+            ),
+            Self::Cl100k => {
+                Some(CL100K.get_or_init(|| {
+                    cl100k_base().expect("cl100k_base tokenizer should initialize")
+                }))
+            }
+            Self::Heuristic => None,
+        }
+    }
+}
+
+/// Estimates prompt sizes for one model.
+///
+/// Raw estimates come from the model family's tokenizer. `usage_ratio` and
+/// [`calibrated_context_tokens`](Self::calibrated_context_tokens) additionally
+/// apply a correction factor learned from provider-reported usage, which
+/// absorbs tokenizer mismatch and per-request overhead such as tool schemas.
 #[derive(Clone)]
 pub struct TokenEstimator {
-    tokenizer: CoreBPE,
+    family: TokenizerFamily,
+    tokenizer: Option<&'static CoreBPE>,
+    correction: f64,
 }
 
 impl std::fmt::Debug for TokenEstimator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("TokenEstimator").finish()
+        f.debug_struct("TokenEstimator")
+            .field("family", &self.family)
+            .field("correction", &self.correction)
+            .finish()
     }
 }
 
@@ -23,8 +92,39 @@ impl TokenEstimator {
     /// Panics if the tokenizer cannot be initialized.
     #[must_use]
     pub fn new() -> Self {
-        let tokenizer = cl100k_base().expect("cl100k_base tokenizer should initialize");
-        Self { tokenizer }
+        Self::for_family(TokenizerFamily::Cl100k)
+    }
+
+    /// Create an estimator using the tokenizer of `model_id`'s family.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tokenizer cannot be initialized.
+    #[must_use]
+    pub fn for_model(model_id: &str) -> Self {
+        Self::for_family(TokenizerFamily::for_model(model_id))
+    }
+
+    fn for_family(family: TokenizerFamily) -> Self {
+        Self {
+            family,
+            tokenizer: family.tokenizer(),
+            correction: 1.0,
+        }
+    }
+
+    /// Scale calibrated estimates by `correction` (observed / estimated tokens).
+    #[must_use]
+    pub const fn with_correction(mut self, correction: f64) -> Self {
+        if correction.is_finite() && correction > 0.0 {
+            self.correction = correction;
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn family(&self) -> TokenizerFamily {
+        self.family
     }
 
     #[must_use]
@@ -73,19 +173,31 @@ impl TokenEstimator {
             .sum()
     }
 
+    /// Raw estimate scaled by the calibrated correction factor.
+    #[must_use]
+    pub fn calibrated_context_tokens(&self, messages: &[LlmMessage]) -> usize {
+        let estimated = usize_to_f64(self.estimate_llm_context_tokens(messages));
+        (estimated * self.correction)
+            .round()
+            .to_string()
+            .parse::<usize>()
+            .unwrap_or(usize::MAX)
+    }
+
     #[must_use]
     pub fn usage_ratio(&self, messages: &[LlmMessage], context_window: usize) -> f64 {
         if context_window == 0 {
             return 1.0;
         }
 
-        let estimated_tokens = self.estimate_llm_context_tokens(messages);
-        let estimated_tokens = estimated_tokens
-            .to_string()
-            .parse::<f64>()
-            .unwrap_or(f64::MAX);
-        let context_window = context_window.to_string().parse::<f64>().unwrap_or(1.0);
-        estimated_tokens / context_window
+        let estimated_tokens = usize_to_f64(self.estimate_llm_context_tokens(messages));
+        estimated_tokens * self.correction / usize_to_f64(context_window).max(1.0)
+    }
+
+    /// Raw token estimate for free text such as a system prompt.
+    #[must_use]
+    pub fn estimate_text_tokens(&self, text: &str) -> usize {
+        self.count_text_tokens(text)
     }
 
     #[must_use]
@@ -94,10 +206,17 @@ impl TokenEstimator {
             return 0;
         }
 
-        self.tokenizer.encode_ordinary(text).len()
+        self.tokenizer.map_or_else(
+            || text.chars().count().div_ceil(HEURISTIC_CHARS_PER_TOKEN),
+            |tokenizer| tokenizer.encode_ordinary(text).len(),
+        )
     }
 }
 
+fn usize_to_f64(value: usize) -> f64 {
+    value.to_string().parse::<f64>().unwrap_or(f64::MAX)
+}
+
 impl Default for TokenEstimator {
     fn default() -> Self {
         Self::new()
@@ -151,4 +270,42 @@ mod tests {
 
         assert!(estimator.estimate_llm_message_tokens(&message) > MESSAGE_OVERHEAD_TOKENS);
     }
+
+    #[test]
+    fn tokenizer_family_follows_model_id() {
+        assert_eq!(
+            TokenizerFamily::for_model("gpt-4o-mini"),
+            TokenizerFamily::O200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("openai/o3-mini"),
+            TokenizerFamily::O200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("gpt-4-turbo"),
+            TokenizerFamily::Cl100k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("anthropic/claude-sonnet-4"),
+            TokenizerFamily::Cl100k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("gemini-2.5-pro"),
+            TokenizerFamily::Heuristic
+        );
+    }
+
+    #[test]
+    fn correction_scales_usage_ratio_but_not_raw_estimate() {
+        let messages = vec![LlmMessage::user("a".repeat(400))];
+        let estimator = TokenEstimator::for_model("gemini-2.5-pro");
+        let raw = estimator.estimate_llm_context_tokens(&messages);
+        assert_eq!(raw, 100 + MESSAGE_OVERHEAD_TOKENS);
+
+        let calibrated = estimator.clone().with_correction(1.5);
+        assert_eq!(calibrated.estimate_llm_context_tokens(&messages), raw);
+        assert_eq!(calibrated.calibrated_context_tokens(&messages), 156);
+        let ratio = calibrated.usage_ratio(&messages, 1_000);
+        assert!((ratio - 0.156).abs() < 1e-9);
+    }
 }
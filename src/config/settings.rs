@@ -51,6 +51,10 @@ pub struct CompressionConfig {
     pub min_middle_messages: usize,
     pub truncation_target: f64,
     pub min_keep_messages: usize,
+    /// Profile used to write model-generated summaries of the middle of long
+    /// conversations. `None` keeps the heuristic sandwich summary.
+    #[serde(default)]
+    pub summarizer_profile_id: Option<Uuid>,
 }
 
 impl Default for Config {
@@ -95,6 +99,7 @@ impl Default for CompressionConfig {
             min_middle_messages: 4,
             truncation_target: 0.60,
             min_keep_messages: 4,
+            summarizer_profile_id: None,
         }
     }
 }
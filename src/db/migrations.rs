@@ -0,0 +1,211 @@
+//! Ordered, forward-only schema migrations.
+//!
+//! Every schema change is a `Migration` appended to `MIGRATIONS` with the next
+//! version number. `PRAGMA user_version` records the last migration applied;
+//! `run_pending` applies the missing ones in order, each in its own
+//! transaction together with its `user_version` bump, so a failed step leaves
+//! the database at the previous version rather than half-migrated.
+//!
+//! Migrations are never edited or removed once released. A database whose
+//! `user_version` is above `LATEST_VERSION` was written by a newer build and
+//! is refused rather than opened with a schema this build does not understand.
+
+use rusqlite::{Connection, Transaction};
+
+use super::schema;
+
+/// One forward-only schema step.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Schema version after this migration has been applied.
+    pub version: u32,
+    /// Short human-readable summary, used in logs.
+    pub description: &'static str,
+    up: fn(&Transaction<'_>) -> Result<(), rusqlite::Error>,
+}
+
+/// Every migration, in the order it must be applied.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "base conversation, message, and search schema",
+        up: schema::create_base_schema,
+    },
+    Migration {
+        version: 2,
+        description: "conversation branches",
+        up: schema::add_conversation_branches,
+    },
+    Migration {
+        version: 3,
+        description: "alternate message versions",
+        up: schema::add_message_versions,
+    },
+    Migration {
+        version: 4,
+        description: "conversation pinning, archive, folders, and tags",
+        up: schema::add_conversation_organization,
+    },
+    Migration {
+        version: 5,
+        description: "token usage ledger",
+        up: schema::add_usage_ledger,
+    },
+    Migration {
+        version: 6,
+        description: "imported conversation keys",
+        up: schema::add_conversation_imports,
+    },
+    Migration {
+        version: 7,
+        description: "agent task lists",
+        up: schema::add_conversation_todos,
+    },
+    Migration {
+        version: 8,
+        description: "tool audit log",
+        up: schema::add_tool_audit,
+    },
+];
+
+/// Schema version this build creates and understands.
+pub const LATEST_VERSION: u32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+/// Read `PRAGMA user_version`.
+///
+/// # Errors
+///
+/// Returns a `rusqlite::Error` if the PRAGMA query fails.
+pub fn schema_version(conn: &Connection) -> Result<u32, rusqlite::Error> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Refuse databases written by a newer build.
+///
+/// # Errors
+///
+/// Returns `rusqlite::Error::SqliteFailure` (`SQLITE_MISMATCH`) naming both
+/// versions when `version` is above `LATEST_VERSION`.
+pub fn ensure_supported(version: u32) -> Result<(), rusqlite::Error> {
+    if version > LATEST_VERSION {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_MISMATCH),
+            Some(format!(
+                "database schema version {version} is newer than this build supports \
+                 ({LATEST_VERSION}); update the app to open it"
+            )),
+        ));
+    }
+    Ok(())
+}
+
+/// Migrations not yet applied to `conn`, in order.
+///
+/// # Errors
+///
+/// Returns a `rusqlite::Error` if the version query fails or the database is
+/// newer than this build.
+pub fn pending(conn: &Connection) -> Result<Vec<&'static Migration>, rusqlite::Error> {
+    pending_in(conn, MIGRATIONS)
+}
+
+/// Apply every pending migration and return the resulting schema version.
+///
+/// # Errors
+///
+/// Returns a `rusqlite::Error` if the database is newer than this build or a
+/// migration fails; migrations committed before the failure are kept.
+pub fn run_pending(conn: &Connection) -> Result<u32, rusqlite::Error> {
+    run_in(conn, MIGRATIONS)
+}
+
+fn pending_in<'a>(
+    conn: &Connection,
+    migrations: &'a [Migration],
+) -> Result<Vec<&'a Migration>, rusqlite::Error> {
+    let current = schema_version(conn)?;
+    ensure_supported(current)?;
+    Ok(migrations
+        .iter()
+        .filter(|migration| migration.version > current)
+        .collect())
+}
+
+fn run_in(conn: &Connection, migrations: &[Migration]) -> Result<u32, rusqlite::Error> {
+    let mut version = schema_version(conn)?;
+    for migration in pending_in(conn, migrations)? {
+        tracing::info!(
+            from = version,
+            to = migration.version,
+            "Applying schema migration: {}",
+            migration.description
+        );
+        let tx = conn.unchecked_transaction()?;
+        (migration.up)(&tx)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        version = migration.version;
+    }
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_notes(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+        tx.execute_batch("CREATE TABLE notes (body TEXT)")
+    }
+
+    fn fail_after_write(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+        tx.execute_batch("INSERT INTO notes VALUES ('partial')")?;
+        tx.execute_batch("SELECT * FROM missing_table")
+    }
+
+    #[test]
+    fn registry_is_contiguous_from_one() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(
+                u32::try_from(index + 1).ok(),
+                Some(migration.version),
+                "{migration:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_and_keeps_earlier_steps() {
+        let conn = Connection::open_in_memory().unwrap();
+        let migrations = [
+            Migration {
+                version: 1,
+                description: "notes",
+                up: create_notes,
+            },
+            Migration {
+                version: 2,
+                description: "broken",
+                up: fail_after_write,
+            },
+        ];
+
+        assert!(run_in(&conn, &migrations).is_err());
+
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 0, "the failed step's writes must be rolled back");
+    }
+
+    #[test]
+    fn newer_database_is_refused() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", LATEST_VERSION + 1)
+            .unwrap();
+
+        let err = run_pending(&conn).unwrap_err();
+        assert!(err.to_string().contains("newer than this build"), "{err}");
+        assert!(pending(&conn).is_err());
+    }
+}
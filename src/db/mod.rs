@@ -1,9 +1,11 @@
 //! Database layer for SQLite-backed conversation storage.
 //!
 //! Provides a single-threaded DB worker with a closure-based dispatch handle
-//! (`DbHandle`) and schema initialization logic.
+//! (`DbHandle`), schema initialization logic, and the versioned migration
+//! registry.
 
+pub mod migrations;
 pub mod schema;
 pub mod worker;
 
-pub use worker::{spawn_db_thread, DbHandle};
+pub use worker::{spawn_db_thread, spawn_db_thread_unmigrated, DbHandle};
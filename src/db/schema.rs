@@ -1,11 +1,13 @@
 //! `SQLite` schema DDL and initialization logic.
 //!
 //! `initialize_schema` is called once by the DB worker thread immediately after
-//! opening the connection. It applies all PRAGMAs and, when `PRAGMA user_version`
-//! is 0, runs the full DDL to create tables, indexes, the FTS5 virtual table, and
-//! all triggers.
+//! opening the connection. It applies all PRAGMAs and then runs any pending
+//! migrations. The DDL for each schema version lives here; the ordered
+//! registry and runner live in `migrations`.
 
-use rusqlite::Connection;
+use rusqlite::{Connection, Transaction};
+
+use super::migrations;
 
 // ---------------------------------------------------------------------------
 // PRAGMAs applied on every connection open
@@ -17,7 +19,7 @@ const PRAGMA_BUSY_TIMEOUT: &str = "PRAGMA busy_timeout = 5000";
 const PRAGMA_SYNCHRONOUS: &str = "PRAGMA synchronous = NORMAL";
 
 // ---------------------------------------------------------------------------
-// Full DDL — applied only when user_version == 0
+// Version 1 — base schema
 // ---------------------------------------------------------------------------
 
 const CREATE_CONVERSATIONS: &str = "
@@ -94,51 +96,284 @@ CREATE TRIGGER IF NOT EXISTS conversations_ad AFTER DELETE ON conversations BEGI
     DELETE FROM search_index WHERE conversation_id = OLD.id;
 END";
 
-const SET_USER_VERSION_1: &str = "PRAGMA user_version = 1";
+// ---------------------------------------------------------------------------
+// Version 2 — conversation branches
+// ---------------------------------------------------------------------------
+
+const ADD_CONVERSATIONS_PARENT_ID: &str =
+    "ALTER TABLE conversations ADD COLUMN parent_id TEXT REFERENCES conversations(id) ON DELETE SET NULL";
+
+const ADD_CONVERSATIONS_PARENT_SEQ: &str =
+    "ALTER TABLE conversations ADD COLUMN parent_seq INTEGER";
+
+const CREATE_IDX_CONVERSATIONS_PARENT: &str =
+    "CREATE INDEX IF NOT EXISTS idx_conversations_parent ON conversations(parent_id)";
+
+// ---------------------------------------------------------------------------
+// Version 3 — alternate message versions
+// ---------------------------------------------------------------------------
+
+/// Archived conversation tails. A row belongs to version `version` of the tail
+/// that starts at `anchor_seq`; rows whose anchor lies inside another archived
+/// tail are owned by it (`owner_anchor_seq`/`owner_version`) until restored.
+const CREATE_MESSAGE_VERSIONS: &str = "
+CREATE TABLE IF NOT EXISTS message_versions (
+    id                  INTEGER PRIMARY KEY,
+    conversation_id     TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    anchor_seq          INTEGER NOT NULL,
+    version             INTEGER NOT NULL,
+    owner_anchor_seq    INTEGER,
+    owner_version       INTEGER,
+    seq                 INTEGER NOT NULL,
+    role                TEXT NOT NULL CHECK(role IN ('user', 'assistant', 'system')),
+    content             TEXT NOT NULL,
+    thinking_content    TEXT,
+    model_id            TEXT,
+    tool_calls          TEXT,
+    tool_results        TEXT,
+    created_at          TEXT NOT NULL
+)";
+
+const CREATE_IDX_MESSAGE_VERSIONS_ANCHOR: &str =
+    "CREATE INDEX IF NOT EXISTS idx_message_versions_anchor \
+     ON message_versions(conversation_id, anchor_seq, version)";
+
+// Archived versions are indexed under the negated row id so they can never
+// collide with a live `messages.id` in `search_index.message_rowid`.
+const CREATE_TRIGGER_MESSAGE_VERSIONS_AI: &str = "
+CREATE TRIGGER IF NOT EXISTS message_versions_ai AFTER INSERT ON message_versions BEGIN
+    INSERT INTO search_index(title, content, conversation_id, message_rowid)
+    SELECT c.title, NEW.content, NEW.conversation_id, -NEW.id
+    FROM conversations c WHERE c.id = NEW.conversation_id;
+END";
+
+const CREATE_TRIGGER_MESSAGE_VERSIONS_AD: &str = "
+CREATE TRIGGER IF NOT EXISTS message_versions_ad AFTER DELETE ON message_versions BEGIN
+    DELETE FROM search_index WHERE message_rowid = -OLD.id;
+END";
+
+// ---------------------------------------------------------------------------
+// Version 4 — pinning, archive, folders, and tags
+// ---------------------------------------------------------------------------
+
+const ADD_CONVERSATIONS_PINNED: &str =
+    "ALTER TABLE conversations ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0";
+
+const ADD_CONVERSATIONS_ARCHIVED: &str =
+    "ALTER TABLE conversations ADD COLUMN archived INTEGER NOT NULL DEFAULT 0";
+
+const ADD_CONVERSATIONS_FOLDER: &str = "ALTER TABLE conversations ADD COLUMN folder TEXT";
+
+const CREATE_IDX_CONVERSATIONS_FOLDER: &str =
+    "CREATE INDEX IF NOT EXISTS idx_conversations_folder ON conversations(folder)";
+
+const CREATE_CONVERSATION_TAGS: &str = "
+CREATE TABLE IF NOT EXISTS conversation_tags (
+    conversation_id     TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    tag                 TEXT NOT NULL,
+    PRIMARY KEY (conversation_id, tag)
+)";
+
+const CREATE_IDX_CONVERSATION_TAGS_TAG: &str =
+    "CREATE INDEX IF NOT EXISTS idx_conversation_tags_tag ON conversation_tags(tag)";
+
+// ---------------------------------------------------------------------------
+// Version 5 — token usage ledger
+// ---------------------------------------------------------------------------
+
+// `conversation_id` is nulled rather than cascaded on delete so spend history
+// survives deleting the conversation it came from.
+const CREATE_USAGE: &str = "
+CREATE TABLE IF NOT EXISTS usage (
+    id                  INTEGER PRIMARY KEY,
+    conversation_id     TEXT REFERENCES conversations(id) ON DELETE SET NULL,
+    profile_id          TEXT NOT NULL,
+    provider_id         TEXT NOT NULL,
+    model_id            TEXT NOT NULL,
+    input_tokens        INTEGER NOT NULL DEFAULT 0,
+    output_tokens       INTEGER NOT NULL DEFAULT 0,
+    cache_read_tokens   INTEGER NOT NULL DEFAULT 0,
+    cache_write_tokens  INTEGER NOT NULL DEFAULT 0,
+    cost_usd            REAL,
+    created_at          TEXT NOT NULL
+)";
+
+const CREATE_IDX_USAGE_CONVERSATION: &str =
+    "CREATE INDEX IF NOT EXISTS idx_usage_conversation ON usage(conversation_id)";
+
+const CREATE_IDX_USAGE_CREATED: &str =
+    "CREATE INDEX IF NOT EXISTS idx_usage_created ON usage(created_at)";
+
+// ---------------------------------------------------------------------------
+// Version 6 — imported conversation keys
+// ---------------------------------------------------------------------------
+
+/// One row per conversation created by an import, keyed by the source app and
+/// that app's conversation id so re-importing the same export is a no-op.
+/// Deleting the conversation forgets the key, allowing it to be imported again.
+const CREATE_CONVERSATION_IMPORTS: &str = "
+CREATE TABLE IF NOT EXISTS conversation_imports (
+    source          TEXT NOT NULL,
+    external_id     TEXT NOT NULL,
+    conversation_id TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    imported_at     TEXT NOT NULL,
+    PRIMARY KEY (source, external_id)
+)";
+
+const CREATE_IDX_CONVERSATION_IMPORTS_CONVERSATION: &str =
+    "CREATE INDEX IF NOT EXISTS idx_conversation_imports_conversation \
+     ON conversation_imports(conversation_id)";
+
+// ---------------------------------------------------------------------------
+// Version 7 — agent task lists
+// ---------------------------------------------------------------------------
+
+/// The task list the agent keeps with `TodoWrite`, one row per item in list
+/// order. The whole list is rewritten on every update.
+const CREATE_CONVERSATION_TODOS: &str = "
+CREATE TABLE IF NOT EXISTS conversation_todos (
+    conversation_id TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    position        INTEGER NOT NULL,
+    content         TEXT NOT NULL,
+    status          TEXT NOT NULL,
+    PRIMARY KEY (conversation_id, position)
+)";
+
+// ---------------------------------------------------------------------------
+// Version 8 — tool audit log
+// ---------------------------------------------------------------------------
+
+// One row per tool invocation, written when the call finishes. Like `usage`,
+// `conversation_id` is nulled on delete so the record outlives the
+// conversation.
+const CREATE_TOOL_AUDIT: &str = "
+CREATE TABLE IF NOT EXISTS tool_audit (
+    id                INTEGER PRIMARY KEY,
+    conversation_id   TEXT REFERENCES conversations(id) ON DELETE SET NULL,
+    tool_name         TEXT NOT NULL,
+    arguments         TEXT NOT NULL,
+    approval_source   TEXT NOT NULL,
+    approval_detail   TEXT,
+    outcome           TEXT NOT NULL,
+    exit_code         INTEGER,
+    output            TEXT NOT NULL,
+    started_at        TEXT NOT NULL,
+    duration_ms       INTEGER NOT NULL
+)";
+
+const CREATE_IDX_TOOL_AUDIT_STARTED: &str =
+    "CREATE INDEX IF NOT EXISTS idx_tool_audit_started ON tool_audit(started_at)";
+
+// ---------------------------------------------------------------------------
+// Migration steps (registered in `migrations::MIGRATIONS`)
+// ---------------------------------------------------------------------------
+
+/// Version 1: tables, the FTS5 virtual table, sync triggers, and indexes.
+pub(super) fn create_base_schema(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    // Tables
+    tx.execute_batch(CREATE_CONVERSATIONS)?;
+    tx.execute_batch(CREATE_MESSAGES)?;
+    // FTS5 virtual table
+    tx.execute_batch(CREATE_SEARCH_INDEX)?;
+    // Triggers
+    tx.execute_batch(CREATE_TRIGGER_MESSAGES_AI)?;
+    tx.execute_batch(CREATE_TRIGGER_MESSAGES_AU)?;
+    tx.execute_batch(CREATE_TRIGGER_MESSAGES_AD)?;
+    tx.execute_batch(CREATE_TRIGGER_CONVERSATIONS_TITLE_AU)?;
+    tx.execute_batch(CREATE_TRIGGER_CONVERSATIONS_AD)?;
+    // Indexes
+    tx.execute_batch(CREATE_IDX_CONVERSATIONS_UPDATED)?;
+    tx.execute_batch(CREATE_IDX_CONVERSATIONS_PROFILE)?;
+    tx.execute_batch(CREATE_IDX_MESSAGES_ORDERING)?;
+    tx.execute_batch(CREATE_IDX_MESSAGES_CONVERSATION_TS)?;
+    Ok(())
+}
+
+/// Version 2: conversation branch columns.
+pub(super) fn add_conversation_branches(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(ADD_CONVERSATIONS_PARENT_ID)?;
+    tx.execute_batch(ADD_CONVERSATIONS_PARENT_SEQ)?;
+    tx.execute_batch(CREATE_IDX_CONVERSATIONS_PARENT)?;
+    Ok(())
+}
+
+/// Version 3: the `message_versions` table and its FTS triggers.
+pub(super) fn add_message_versions(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(CREATE_MESSAGE_VERSIONS)?;
+    tx.execute_batch(CREATE_IDX_MESSAGE_VERSIONS_ANCHOR)?;
+    tx.execute_batch(CREATE_TRIGGER_MESSAGE_VERSIONS_AI)?;
+    tx.execute_batch(CREATE_TRIGGER_MESSAGE_VERSIONS_AD)?;
+    Ok(())
+}
+
+/// Version 4: pinned/archived flags, folders, and the `conversation_tags` table.
+pub(super) fn add_conversation_organization(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(ADD_CONVERSATIONS_PINNED)?;
+    tx.execute_batch(ADD_CONVERSATIONS_ARCHIVED)?;
+    tx.execute_batch(ADD_CONVERSATIONS_FOLDER)?;
+    tx.execute_batch(CREATE_IDX_CONVERSATIONS_FOLDER)?;
+    tx.execute_batch(CREATE_CONVERSATION_TAGS)?;
+    tx.execute_batch(CREATE_IDX_CONVERSATION_TAGS_TAG)?;
+    Ok(())
+}
+
+/// Version 5: the `usage` ledger table.
+pub(super) fn add_usage_ledger(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(CREATE_USAGE)?;
+    tx.execute_batch(CREATE_IDX_USAGE_CONVERSATION)?;
+    tx.execute_batch(CREATE_IDX_USAGE_CREATED)?;
+    Ok(())
+}
+
+/// Version 6: the `conversation_imports` table.
+pub(super) fn add_conversation_imports(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(CREATE_CONVERSATION_IMPORTS)?;
+    tx.execute_batch(CREATE_IDX_CONVERSATION_IMPORTS_CONVERSATION)?;
+    Ok(())
+}
+
+/// Version 7: the `conversation_todos` table.
+pub(super) fn add_conversation_todos(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(CREATE_CONVERSATION_TODOS)?;
+    Ok(())
+}
+
+/// Version 8: the `tool_audit` table.
+pub(super) fn add_tool_audit(tx: &Transaction<'_>) -> Result<(), rusqlite::Error> {
+    tx.execute_batch(CREATE_TOOL_AUDIT)?;
+    tx.execute_batch(CREATE_IDX_TOOL_AUDIT_STARTED)?;
+    Ok(())
+}
 
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
 
-/// Apply all PRAGMAs and, if the database is uninitialized (`user_version = 0`),
-/// run the full DDL to create all tables, indexes, the FTS5 virtual table, and
-/// all synchronization triggers.
-///
-/// Idempotent: subsequent calls with `user_version = 1` are no-ops.
+/// Apply the connection-level PRAGMAs. Safe to call on every open.
 ///
 /// # Errors
 ///
-/// Returns a `rusqlite::Error` if any PRAGMA, DDL statement, or version query fails.
-pub fn initialize_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
-    // Always apply connection-level PRAGMAs.
+/// Returns a `rusqlite::Error` if any PRAGMA fails.
+pub fn apply_pragmas(conn: &Connection) -> Result<(), rusqlite::Error> {
     conn.execute_batch(PRAGMA_WAL)?;
     conn.execute_batch(PRAGMA_FOREIGN_KEYS)?;
     conn.execute_batch(PRAGMA_BUSY_TIMEOUT)?;
     conn.execute_batch(PRAGMA_SYNCHRONOUS)?;
+    Ok(())
+}
 
-    let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
-
-    if version == 0 {
-        // Tables
-        conn.execute_batch(CREATE_CONVERSATIONS)?;
-        conn.execute_batch(CREATE_MESSAGES)?;
-        // FTS5 virtual table
-        conn.execute_batch(CREATE_SEARCH_INDEX)?;
-        // Triggers
-        conn.execute_batch(CREATE_TRIGGER_MESSAGES_AI)?;
-        conn.execute_batch(CREATE_TRIGGER_MESSAGES_AU)?;
-        conn.execute_batch(CREATE_TRIGGER_MESSAGES_AD)?;
-        conn.execute_batch(CREATE_TRIGGER_CONVERSATIONS_TITLE_AU)?;
-        conn.execute_batch(CREATE_TRIGGER_CONVERSATIONS_AD)?;
-        // Indexes
-        conn.execute_batch(CREATE_IDX_CONVERSATIONS_UPDATED)?;
-        conn.execute_batch(CREATE_IDX_CONVERSATIONS_PROFILE)?;
-        conn.execute_batch(CREATE_IDX_MESSAGES_ORDERING)?;
-        conn.execute_batch(CREATE_IDX_MESSAGES_CONVERSATION_TS)?;
-        // Mark schema as initialized
-        conn.execute_batch(SET_USER_VERSION_1)?;
-    }
-    // user_version == 1: schema already current, nothing to do.
-
+/// Apply all PRAGMAs and bring the schema up to `migrations::LATEST_VERSION`.
+///
+/// A fresh database (`user_version = 0`) runs every registered migration;
+/// older databases run only the ones they are missing. Idempotent: subsequent
+/// calls at the current version are no-ops.
+///
+/// # Errors
+///
+/// Returns a `rusqlite::Error` if any PRAGMA or migration fails, or if the
+/// database was written by a newer build (see `migrations::ensure_supported`).
+pub fn initialize_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
+    apply_pragmas(conn)?;
+    migrations::run_pending(conn)?;
     Ok(())
 }
@@ -11,7 +11,8 @@ use std::sync::Arc;
 
 use crate::services::ServiceError;
 
-use super::schema::initialize_schema;
+use super::migrations;
+use super::schema::{apply_pragmas, initialize_schema};
 
 // ---------------------------------------------------------------------------
 // Job type
@@ -140,6 +141,35 @@ impl DbHandle {
             .map_err(ServiceError::Storage)
     }
 
+    /// Current schema version and the versions of any migrations not yet
+    /// applied, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceError::Storage` if the DB thread has shut down, the
+    /// version query fails, or the database is newer than this build.
+    pub async fn pending_migrations(&self) -> Result<(u32, Vec<u32>), ServiceError> {
+        self.execute(|conn| {
+            let current = migrations::schema_version(conn)?;
+            let pending = migrations::pending(conn)?
+                .into_iter()
+                .map(|migration| migration.version)
+                .collect();
+            Ok((current, pending))
+        })
+        .await
+    }
+
+    /// Apply all pending migrations and return the resulting schema version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceError::Storage` if the DB thread has shut down or a
+    /// migration fails.
+    pub async fn migrate(&self) -> Result<u32, ServiceError> {
+        self.execute(migrations::run_pending).await
+    }
+
     /// Get the database file path.
     #[must_use]
     pub fn path(&self) -> &Path {
@@ -152,7 +182,7 @@ impl DbHandle {
 // ---------------------------------------------------------------------------
 
 /// Spawn the DB worker thread, open the `SQLite` connection, and initialize the
-/// schema.
+/// schema, applying any pending migrations.
 ///
 /// Returns a `DbHandle` on success, or a `ServiceError` if the thread fails to
 /// start, the database fails to open, or schema initialization fails.
@@ -175,6 +205,32 @@ impl DbHandle {
 /// Returns `ServiceError::Storage` if the OS thread cannot be spawned, if
 /// opening the database file fails, or if schema initialization fails.
 pub fn spawn_db_thread(db_path: &Path) -> Result<DbHandle, ServiceError> {
+    spawn_with_init(db_path, initialize_schema)
+}
+
+/// Spawn the DB worker thread without running pending migrations.
+///
+/// Applies the connection PRAGMAs and refuses databases newer than this
+/// build, but leaves the schema at its current version so the caller can take
+/// a backup first (see `crate::backup::migrate_with_backup`). Call
+/// `DbHandle::migrate` before using any service on the handle.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Storage` if the OS thread cannot be spawned, if
+/// opening the database file fails, or if the database is newer than this
+/// build.
+pub fn spawn_db_thread_unmigrated(db_path: &Path) -> Result<DbHandle, ServiceError> {
+    spawn_with_init(db_path, |conn| {
+        apply_pragmas(conn)?;
+        migrations::ensure_supported(migrations::schema_version(conn)?)
+    })
+}
+
+fn spawn_with_init(
+    db_path: &Path,
+    init: fn(&rusqlite::Connection) -> Result<(), rusqlite::Error>,
+) -> Result<DbHandle, ServiceError> {
     let (tx, rx) = std::sync::mpsc::channel::<DbJob>();
     let (init_tx, init_rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
     let path = db_path.to_owned();
@@ -190,7 +246,7 @@ pub fn spawn_db_thread(db_path: &Path) -> Result<DbHandle, ServiceError> {
                 }
             };
             if let Some(ref c) = conn {
-                match initialize_schema(c) {
+                match init(c) {
                     Ok(()) => {
                         let _ = init_tx.send(Ok(()));
                     }
@@ -96,6 +96,13 @@ pub enum UserEvent {
     /// User changed the export directory path in settings.
     SetExportDirectory { path: String },
 
+    /// User asked to export several conversations into one archive.
+    /// `ids: None` exports every conversation, archived ones included.
+    ExportConversations {
+        ids: Option<Vec<Uuid>>,
+        archive: crate::models::ConversationArchiveFormat,
+    },
+
     /// User started renaming conversation
     StartRenameConversation { id: Uuid },
 
@@ -105,6 +112,62 @@ pub enum UserEvent {
     /// User cancelled rename
     CancelRenameConversation,
 
+    /// User chose to branch the conversation at a visible message index.
+    BranchConversation {
+        conversation_id: Uuid,
+        message_index: usize,
+    },
+
+    /// User asked for a new response at a visible message index.
+    ///
+    /// `message_index` is the assistant message to replace, or one past the
+    /// last visible message to retry a prompt that never got an answer.
+    RegenerateResponse {
+        conversation_id: Uuid,
+        message_index: usize,
+    },
+
+    /// User edited the prompt at a visible message index and resent it.
+    EditAndResend {
+        conversation_id: Uuid,
+        message_index: usize,
+        content: String,
+    },
+
+    /// User picked another version of the message at a visible index.
+    SwitchMessageVersion {
+        conversation_id: Uuid,
+        message_index: usize,
+        version: u32,
+    },
+
+    /// User asked to restore the files an assistant turn changed.
+    ///
+    /// With `path`, only that file is restored; otherwise the whole turn.
+    RevertTurnFiles {
+        conversation_id: Uuid,
+        turn_id: Uuid,
+        path: Option<String>,
+    },
+
+    /// User stopped a background process from the chat view.
+    KillBackgroundProcess {
+        conversation_id: Uuid,
+        process_id: String,
+    },
+
+    /// User opened the composer's MCP picker and needs the resources and
+    /// prompts of the running servers.
+    RefreshMcpCatalog,
+
+    /// User picked an MCP resource to attach to the message being composed.
+    AttachMcpResource { server: String, uri: String },
+
+    /// User asked to insert a server prompt rendered with arguments.
+    InsertMcpPrompt {
+        invocation: crate::mcp::McpPromptInvocation,
+    },
+
     // ===== Profile Actions =====
     /// User selected a profile as default
     SelectProfile { id: Uuid },
@@ -174,8 +237,24 @@ pub enum UserEvent {
     /// User toggled the sidebar visibility in popout mode.
     ToggleSidebar,
 
-    /// User typed a search query in the sidebar search box.
-    SearchConversations { query: String },
+    /// User typed a search query in the sidebar search box. `filter` is the
+    /// list's current organization filter.
+    SearchConversations {
+        query: String,
+        filter: crate::models::ConversationFilter,
+    },
+
+    /// User pinned or unpinned a conversation.
+    SetConversationPinned { id: Uuid, pinned: bool },
+
+    /// User archived or restored a conversation.
+    SetConversationArchived { id: Uuid, archived: bool },
+
+    /// User moved a conversation into a folder (`None` = unfiled).
+    SetConversationFolder { id: Uuid, folder: Option<String> },
+
+    /// User replaced a conversation's tags.
+    SetConversationTags { id: Uuid, tags: Vec<String> },
 
     /// User requested a fresh profile snapshot for chat/settings dropdowns.
     RefreshProfiles,
@@ -296,6 +375,42 @@ pub enum UserEvent {
     /// User removed a denylist prefix for persistent tool approvals.
     RemoveToolApprovalDenylistPrefix { prefix: String },
 
+    /// User added a structured tool approval rule.
+    AddToolApprovalRule { rule: crate::agent::ApprovalRule },
+
+    /// User removed a structured tool approval rule.
+    RemoveToolApprovalRule { rule: crate::agent::ApprovalRule },
+
+    /// User converted the allowlist and denylist prefixes into rules.
+    ConvertToolApprovalPrefixesToRules,
+
+    /// User added a workspace root confining a profile's file and shell tools.
+    AddWorkspaceRoot { profile_id: Uuid, path: String },
+
+    /// User removed a workspace root from a profile.
+    RemoveWorkspaceRoot { profile_id: Uuid, path: String },
+
+    /// User replaced a profile's or conversation's approval policy override;
+    /// an empty override clears it.
+    SetApprovalPolicyOverride {
+        layer: crate::agent::policy_overrides::OverrideLayer,
+        id: Uuid,
+        policy_override: crate::agent::policy_overrides::PolicyOverride,
+    },
+
+    /// User changed the settings audit log filter.
+    FilterToolAudit {
+        search: String,
+        outcome: Option<crate::models::AuditOutcome>,
+    },
+
+    /// User asked to export the audit records matching the filter.
+    ExportToolAudit {
+        search: String,
+        outcome: Option<crate::models::AuditOutcome>,
+        format: crate::models::AuditExportFormat,
+    },
+
     // ===== Database Backup Actions =====
     /// User requested a manual backup now
     TriggerBackupNow,
@@ -321,6 +436,9 @@ pub enum UserEvent {
     /// User requested to restore a database from a backup file (recovery flow)
     RestoreDatabaseBackup { backup_path: std::path::PathBuf },
 
+    /// User chose a ChatGPT, Claude, or Personal Agent export file to import
+    ImportConversations { path: String },
+
     /// User toggled "Launch at login" (macOS only).
     ///
     /// @plan PLAN-20260409-ISSUE177
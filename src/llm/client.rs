@@ -55,6 +55,9 @@ pub enum StreamEvent {
         /// All tool results returned during the turn.
         tool_results: Vec<crate::llm::tools::ToolResult>,
     },
+    /// Token usage summed over every model request of the completed turn.
+    /// Only sent when the provider reported usage.
+    Usage(crate::models::TokenUsage),
     /// Stream completed
     Complete {
         input_tokens: Option<u32>,
@@ -1,7 +1,10 @@
 //! Agent-based LLM client with MCP tool integration for `PersonalAgent`.
 use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+use crate::agent::tool_audit::AuditedExecutor;
+use crate::agent::tool_budget::BudgetedExecutor;
 use crate::llm::error::debug_error_message;
 use crate::llm::{LlmError, Message, Role, StreamEvent};
+use crate::models::TokenUsage;
 use crate::presentation::view_command::ViewCommand;
 use futures::StreamExt;
 use serdes_ai::core::messages::{
@@ -41,7 +44,8 @@ pub struct ApprovalWaiter {
 }
 
 impl ApprovalWaiter {
-    /// Await the approval decision for this pending request.
+    /// Await the approval decision for this pending request. The answer is
+    /// noted for the audit log of the tool call waiting on it.
     ///
     /// # Errors
     ///
@@ -55,7 +59,9 @@ impl ApprovalWaiter {
             .receiver
             .take()
             .expect("ApprovalWaiter receiver should be present");
-        receiver.await
+        let decision = receiver.await;
+        crate::agent::tool_audit::note_user_decision(decision == Ok(true));
+        decision
     }
 }
 
@@ -290,6 +296,21 @@ pub struct McpToolContext {
     /// When true, strip emojis from tool outputs (e.g., file content written by `WriteFile`).
     /// This does NOT affect tool inputs (e.g., `old_text` in `EditFile` must match exactly).
     pub filter_emoji: bool,
+    /// Roots the built-in file and shell tools are confined to; empty means unconfined.
+    pub workspace_roots: crate::agent::workspace::WorkspaceRoots,
+    /// Checkpoint of the current turn; `None` writes without snapshots.
+    pub checkpoints: Option<crate::agent::checkpoints::TurnCheckpoints>,
+    /// What the `Delegate` tool may hand to a sub-agent; `None` inside
+    /// sub-agents and wherever delegation is unavailable.
+    pub delegation: Option<Arc<crate::agent::delegation::DelegationContext>>,
+    /// Store for the conversation's `TodoWrite` task list; `None` where the
+    /// list is unavailable.
+    pub todo_service: Option<Arc<dyn crate::services::TodoService>>,
+    /// Audit log every tool call is recorded in; `None` records nothing.
+    pub audit_service: Option<Arc<dyn crate::services::ToolAuditService>>,
+    /// Limits of the current turn, shared with its sub-agents; `None` runs
+    /// tools without limits.
+    pub budget: Option<Arc<crate::agent::tool_budget::TurnBudget>>,
 }
 
 impl Default for McpToolContext {
@@ -314,6 +335,37 @@ impl Default for McpToolContext {
             policy: Arc::new(AsyncMutex::new(ToolApprovalPolicy::default())),
             skills_service,
             filter_emoji: false,
+            workspace_roots: crate::agent::workspace::WorkspaceRoots::default(),
+            checkpoints: None,
+            delegation: None,
+            todo_service: None,
+            audit_service: None,
+            budget: None,
+        }
+    }
+}
+
+/// Which tools an agent is built with.
+#[derive(Debug, Clone, Copy)]
+enum ToolSelection<'a> {
+    /// Every native and MCP tool; used for conversation agents.
+    All,
+    /// Tools for a sub-agent: the named ones, or all when `None`, but never
+    /// `Delegate`, so sub-agents cannot delegate further, nor `TodoWrite`,
+    /// whose list belongs to the conversation.
+    SubAgent(Option<&'a [String]>),
+}
+
+/// Tools only a conversation's own agent is given.
+const CONVERSATION_ONLY_TOOLS: &[&str] = &["Delegate", "TodoWrite"];
+
+impl ToolSelection<'_> {
+    fn allows(self, tool_name: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::SubAgent(_) if CONVERSATION_ONLY_TOOLS.contains(&tool_name) => false,
+            Self::SubAgent(None) => true,
+            Self::SubAgent(Some(names)) => names.iter().any(|name| name == tool_name),
         }
     }
 }
@@ -322,36 +374,88 @@ impl Default for McpToolContext {
 ///
 /// Native tools are registered before MCP tools so they appear first
 /// in the tool list. These tools bypass the MCP layer for direct
-/// local operations.
+/// local operations. Every executor is wrapped for the turn's budget and
+/// the audit log.
 fn register_native_tools(
     mut builder: AgentBuilder<McpToolContext>,
+    selection: ToolSelection<'_>,
 ) -> AgentBuilder<McpToolContext> {
-    // Register ReadFile tool
-    let read_file_def = crate::agent::tools::get_read_file_tool_definition();
-    builder = builder.tool_with_executor(read_file_def, crate::agent::tools::ReadFileExecutor);
-
-    // Register Search tool
-    let search_def = crate::agent::tools::get_search_tool_definition();
-    builder = builder.tool_with_executor(search_def, crate::agent::tools::SearchExecutor);
-
-    // Register WriteFile tool
-    let write_file_def = crate::agent::tools::get_write_file_tool_definition();
-    builder = builder.tool_with_executor(write_file_def, crate::agent::tools::WriteFileExecutor);
-
-    // Register activate_skill tool
-    let activate_skill_def = crate::agent::tools::get_activate_skill_tool_definition();
-    builder = builder.tool_with_executor(
-        activate_skill_def,
-        crate::agent::tools::ActivateSkillExecutor,
+    use crate::agent::tools;
+
+    macro_rules! register {
+        ($definition:expr, $executor:expr) => {{
+            let definition = $definition;
+            if selection.allows(&definition.name) {
+                let executor = AuditedExecutor::new(
+                    &definition.name,
+                    BudgetedExecutor::new(&definition.name, $executor),
+                );
+                builder = builder.tool_with_executor(definition, executor);
+            }
+        }};
+    }
+
+    register!(
+        tools::get_read_file_tool_definition(),
+        tools::ReadFileExecutor
+    );
+    register!(tools::get_search_tool_definition(), tools::SearchExecutor);
+    register!(
+        tools::get_list_directory_tool_definition(),
+        tools::ListDirectoryExecutor
+    );
+    register!(tools::get_glob_tool_definition(), tools::GlobExecutor);
+    register!(
+        tools::get_write_file_tool_definition(),
+        tools::WriteFileExecutor
+    );
+    register!(
+        tools::get_activate_skill_tool_definition(),
+        tools::ActivateSkillExecutor
+    );
+    register!(
+        tools::get_edit_file_tool_definition(),
+        tools::EditFileExecutor
+    );
+    register!(
+        tools::get_apply_patch_tool_definition(),
+        tools::ApplyPatchExecutor
+    );
+    register!(
+        tools::get_shell_exec_tool_definition(),
+        tools::ShellExecExecutor
     );
 
-    // Register EditFile tool
-    let edit_file_def = crate::agent::tools::get_edit_file_tool_definition();
-    builder = builder.tool_with_executor(edit_file_def, crate::agent::tools::EditFileExecutor);
+    // The tools that follow background ShellExec processes
+    register!(
+        tools::get_read_process_output_tool_definition(),
+        tools::ReadProcessOutputExecutor
+    );
+    register!(
+        tools::get_write_process_input_tool_definition(),
+        tools::WriteProcessInputExecutor
+    );
+    register!(
+        tools::get_kill_process_tool_definition(),
+        tools::KillProcessExecutor
+    );
 
-    // Register ShellExec tool
-    let shell_exec_def = crate::agent::tools::get_shell_exec_tool_definition();
-    builder = builder.tool_with_executor(shell_exec_def, crate::agent::tools::ShellExecExecutor);
+    register!(
+        tools::get_web_fetch_tool_definition(),
+        tools::WebFetchExecutor
+    );
+    register!(
+        tools::get_read_mcp_resource_tool_definition(),
+        tools::ReadMcpResourceExecutor
+    );
+    register!(
+        tools::get_todo_write_tool_definition(),
+        tools::TodoWriteExecutor
+    );
+    register!(
+        tools::get_delegate_tool_definition(),
+        tools::DelegateExecutor
+    );
 
     builder
 }
@@ -374,6 +478,16 @@ pub trait AgentClientExt {
         mcp_tools: Vec<crate::llm::tools::Tool>,
         system_prompt: &str,
     ) -> impl std::future::Future<Output = StdResult<Agent<McpToolContext>, LlmError>> + Send;
+
+    /// Create a sub-agent limited to `allowed_tools` (all when `None`).
+    ///
+    /// Sub-agents never get the `Delegate` or `TodoWrite` tools.
+    fn create_sub_agent(
+        &self,
+        mcp_tools: Vec<crate::llm::tools::Tool>,
+        system_prompt: &str,
+        allowed_tools: Option<&[String]>,
+    ) -> impl std::future::Future<Output = StdResult<Agent<McpToolContext>, LlmError>> + Send;
 }
 
 impl AgentClientExt for crate::llm::LlmClient {
@@ -401,15 +515,75 @@ impl AgentClientExt for crate::llm::LlmClient {
             self.profile.model_id,
             self.profile.base_url
         );
-        self.set_api_key_env();
+        self.build_agent(mcp_tools, system_prompt, ToolSelection::All)
+    }
 
-        let model = self.build_agent_model()?;
-        let builder = self.build_agent_builder(model, system_prompt);
-        // Register native tools first (they appear first in tool list)
-        let builder = register_native_tools(builder);
-        let builder = Self::register_mcp_tools(builder, mcp_tools);
+    async fn create_sub_agent(
+        &self,
+        mcp_tools: Vec<crate::llm::tools::Tool>,
+        system_prompt: &str,
+        allowed_tools: Option<&[String]>,
+    ) -> StdResult<Agent<McpToolContext>, LlmError> {
+        tracing::info!(
+            "create_sub_agent: model={}, tools={:?}",
+            self.profile.model_id,
+            allowed_tools
+        );
+        self.build_agent(
+            mcp_tools,
+            system_prompt,
+            ToolSelection::SubAgent(allowed_tools),
+        )
+    }
+}
 
-        Ok(builder.build())
+/// Read a serialized response usage object. Providers and library versions
+/// disagree on field names (`input_tokens` vs `request_tokens`, and so on),
+/// so each count accepts every known spelling; missing counts are zero.
+///
+/// They also disagree on what the prompt count covers. Anthropic's
+/// `input_tokens` leaves cache reads and writes out and reports them as
+/// `cache_read_input_tokens` / `cache_creation_input_tokens`; every other
+/// shape includes them: OpenAI-compatible `prompt_tokens` (cache reads in
+/// `prompt_tokens_details.cached_tokens`) and the library's `request_tokens`. Those totals have the
+/// cached tokens subtracted so `input_tokens` is uncached and no token is
+/// billed twice.
+pub(crate) fn usage_from_json(usage: &serde_json::Value) -> TokenUsage {
+    let read = |path: &str| {
+        usage
+            .pointer(path)
+            .and_then(serde_json::Value::as_u64)
+            .map(|n| u32::try_from(n).unwrap_or(u32::MAX))
+    };
+    let count = |paths: &[&str]| paths.iter().find_map(|path| read(path)).unwrap_or(0);
+
+    let anthropic_shape = read("/cache_read_input_tokens").is_some()
+        || read("/cache_creation_input_tokens").is_some();
+    let cache_read_tokens = count(&[
+        "/cache_read_tokens",
+        "/cache_read_input_tokens",
+        "/prompt_tokens_details/cached_tokens",
+        "/input_tokens_details/cached_tokens",
+    ]);
+    let cache_write_tokens = count(&[
+        "/cache_write_tokens",
+        "/cache_creation_tokens",
+        "/cache_creation_input_tokens",
+    ]);
+    let prompt_tokens = count(&["/input_tokens", "/request_tokens", "/prompt_tokens"]);
+    let input_tokens = if anthropic_shape {
+        prompt_tokens
+    } else {
+        prompt_tokens
+            .saturating_sub(cache_read_tokens)
+            .saturating_sub(cache_write_tokens)
+    };
+
+    TokenUsage {
+        input_tokens,
+        output_tokens: count(&["/output_tokens", "/response_tokens", "/completion_tokens"]),
+        cache_read_tokens,
+        cache_write_tokens,
     }
 }
 
@@ -472,6 +646,21 @@ impl crate::llm::LlmClient {
         (tool_calls, tool_results)
     }
 
+    /// Sum the usage reported on every model response produced this turn.
+    fn collect_usage(messages: &[ModelRequest], history_request_count: usize) -> TokenUsage {
+        let mut usage = TokenUsage::default();
+        for request in messages.iter().skip(history_request_count) {
+            for part in &request.parts {
+                if let ModelRequestPart::ModelResponse(response) = part {
+                    if let Ok(value) = serde_json::to_value(response) {
+                        usage += usage_from_json(&value["usage"]);
+                    }
+                }
+            }
+        }
+        usage
+    }
+
     fn emit_tool_executed_transcript<F>(on_event: &mut F, call_id: String, success: bool)
     where
         F: FnMut(StreamEvent) + Send,
@@ -539,9 +728,14 @@ impl crate::llm::LlmClient {
                     tool_calls,
                     tool_results,
                 });
+                let usage = Self::collect_usage(&messages, history_request_count);
+                let reported = !usage.is_empty();
+                if reported {
+                    on_event(StreamEvent::Usage(usage));
+                }
                 on_event(StreamEvent::Complete {
-                    input_tokens: None,
-                    output_tokens: None,
+                    input_tokens: reported.then_some(usage.input_tokens),
+                    output_tokens: reported.then_some(usage.output_tokens),
                 });
             }
             AgentStreamEvent::Error { message } => {
@@ -633,15 +827,42 @@ impl crate::llm::LlmClient {
         builder
     }
 
+    fn build_agent(
+        &self,
+        mcp_tools: Vec<crate::llm::tools::Tool>,
+        system_prompt: &str,
+        selection: ToolSelection<'_>,
+    ) -> StdResult<Agent<McpToolContext>, LlmError> {
+        self.set_api_key_env();
+
+        let model = self.build_agent_model()?;
+        let builder = self.build_agent_builder(model, system_prompt);
+        // Register native tools first (they appear first in tool list)
+        let builder = register_native_tools(builder, selection);
+        let builder = Self::register_mcp_tools(builder, mcp_tools, selection);
+
+        Ok(builder.build())
+    }
+
     fn register_mcp_tools(
         mut builder: AgentBuilder<McpToolContext>,
         mcp_tools: Vec<crate::llm::tools::Tool>,
+        selection: ToolSelection<'_>,
     ) -> AgentBuilder<McpToolContext> {
-        for tool in mcp_tools {
+        for tool in mcp_tools
+            .into_iter()
+            .filter(|tool| selection.allows(&tool.name))
+        {
             let tool_name = tool.name.clone();
             let tool_def = ToolDefinition::new(&tool_name, &tool.description)
                 .with_parameters(tool.input_schema.clone());
-            let executor = crate::llm::mcp_tool_executor::McpToolExecutor::new(&tool_name);
+            let executor = AuditedExecutor::new(
+                &tool_name,
+                BudgetedExecutor::new(
+                    &tool_name,
+                    crate::llm::mcp_tool_executor::McpToolExecutor::new(&tool_name),
+                ),
+            );
             builder = builder.tool_with_executor(tool_def, executor);
         }
         builder
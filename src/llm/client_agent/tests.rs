@@ -483,3 +483,81 @@ async fn resolving_one_conversation_does_not_wake_another() {
     let result = tokio::time::timeout(Duration::from_millis(50), waiter_b.wait()).await;
     assert!(result.is_err(), "waiter B should still be pending");
 }
+
+#[test]
+fn usage_from_json_accepts_alternate_field_names() {
+    let usage = usage_from_json(&serde_json::json!({
+        "request_tokens": 1184,
+        "response_tokens": 30,
+        "cache_read_tokens": 1000,
+        "cache_creation_tokens": 64,
+    }));
+    assert_eq!(
+        usage,
+        TokenUsage {
+            input_tokens: 120,
+            output_tokens: 30,
+            cache_read_tokens: 1000,
+            cache_write_tokens: 64,
+        }
+    );
+    assert!(usage_from_json(&serde_json::Value::Null).is_empty());
+}
+
+fn sonnet_pricing() -> crate::registry::Cost {
+    crate::registry::Cost {
+        input: 3.0,
+        output: 15.0,
+        cache_read: Some(0.3),
+    }
+}
+
+#[test]
+fn anthropic_usage_is_priced_without_double_counting_cache() {
+    let usage = usage_from_json(&serde_json::json!({
+        "input_tokens": 200,
+        "output_tokens": 50,
+        "cache_read_input_tokens": 1000,
+        "cache_creation_input_tokens": 100,
+    }));
+    assert_eq!(usage.input_tokens, 200);
+    assert_eq!(usage.prompt_tokens(), 1300);
+
+    // 300 input and cache-write tokens at $3, 1000 cache reads at $0.30,
+    // 50 output tokens at $15 (per million).
+    let cost = usage.cost_usd(&sonnet_pricing());
+    let expected = 1950.0 / 1_000_000.0;
+    assert!((cost - expected).abs() < 1e-12, "{cost}");
+}
+
+#[test]
+fn openai_usage_is_priced_without_double_counting_cache() {
+    let usage = usage_from_json(&serde_json::json!({
+        "prompt_tokens": 1200,
+        "completion_tokens": 50,
+        "total_tokens": 1250,
+        "prompt_tokens_details": { "cached_tokens": 1000 },
+    }));
+    assert_eq!(usage.input_tokens, 200);
+    assert_eq!(usage.cache_read_tokens, 1000);
+    assert_eq!(usage.prompt_tokens(), 1200);
+
+    // 200 uncached input tokens at $3, 1000 cache reads at $0.30, 50 output
+    // tokens at $15 (per million).
+    let cost = usage.cost_usd(&sonnet_pricing());
+    let expected = 1650.0 / 1_000_000.0;
+    assert!((cost - expected).abs() < 1e-12, "{cost}");
+}
+
+#[test]
+fn sub_agent_tool_selection_follows_allowlist_and_never_delegates() {
+    let allowed = vec!["ReadFile".to_string(), "Delegate".to_string()];
+
+    assert!(ToolSelection::All.allows("Delegate"));
+    assert!(ToolSelection::SubAgent(None).allows("ShellExec"));
+    assert!(!ToolSelection::SubAgent(None).allows("Delegate"));
+    assert!(!ToolSelection::SubAgent(None).allows("TodoWrite"));
+    assert!(ToolSelection::SubAgent(Some(&allowed)).allows("ReadFile"));
+    assert!(!ToolSelection::SubAgent(Some(&allowed)).allows("WriteFile"));
+    assert!(!ToolSelection::SubAgent(Some(&allowed)).allows("Delegate"));
+}
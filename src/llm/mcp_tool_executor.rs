@@ -3,6 +3,7 @@
 //! Provides the bridge between Agent tools and MCP (Model Context Protocol) tools,
 //! including approval context construction for rich UI display.
 
+use crate::agent::approval_rules::ToolCall;
 use crate::agent::tool_approval_policy::ToolApprovalDecision;
 use crate::llm::client_agent::McpToolContext;
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
@@ -49,7 +50,11 @@ impl ToolExecutor<McpToolContext> for McpToolExecutor {
         let (tool_identifier, decision) = {
             let policy = ctx.deps().policy.lock().await;
             let tool_identifier = policy.mcp_tool_identifier(&provider.mcp_name, &self.tool_name);
-            let decision = policy.evaluate(&tool_identifier);
+            let call = ToolCall::new(&self.tool_name)
+                .with_arguments(&args)
+                .with_mcp_server(&provider.mcp_name)
+                .with_identifier(tool_identifier.clone());
+            let decision = policy.decide(&call, |policy| policy.evaluate(&tool_identifier));
             drop(policy);
             (tool_identifier, decision)
         };
@@ -90,7 +95,7 @@ impl ToolExecutor<McpToolContext> for McpToolExecutor {
 }
 
 /// Handle approval request for MCP tool execution.
-async fn handle_mcp_approval(
+pub(crate) async fn handle_mcp_approval(
     ctx: &RunContext<McpToolContext>,
     tool_name: &str,
     mcp_name: &str,
@@ -25,21 +25,25 @@ use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
 // Use the library crate
-use personal_agent::db::spawn_db_thread;
+use personal_agent::agent::checkpoints::CheckpointStore;
+use personal_agent::agent::processes::BackgroundProcesses;
+use personal_agent::agent::shell_sessions::ShellSessions;
 use personal_agent::events::types::UserEvent;
 use personal_agent::events::EventBus;
 use personal_agent::llm::client_agent::ApprovalGate;
 use personal_agent::presentation::{
-    ApiKeyManagerPresenter, ChatPresenter, ErrorPresenter, HistoryPresenter, McpAddPresenter,
-    McpConfigurePresenter, ModelSelectorPresenter, ProfileEditorPresenter, SettingsPresenter,
-    ViewCommand,
+    ApiKeyManagerPresenter, AuditPresenter, ChatPresenter, ErrorPresenter, HistoryPresenter,
+    ImportPresenter, McpAddPresenter, McpConfigurePresenter, ModelSelectorPresenter,
+    ProfileEditorPresenter, SettingsPresenter, UsagePresenter, ViewCommand,
 };
 use personal_agent::services::{
     AppSettingsService, AppSettingsServiceImpl, BackupService, BackupServiceImpl, ChatService,
-    ChatServiceImpl, ConversationService, McpRegistryService, McpRegistryServiceImpl, McpService,
-    McpServiceImpl, ModelsRegistryService, ModelsRegistryServiceImpl, ProfileService,
-    ProfileServiceImpl, SecretsService, SecretsServiceImpl, SkillsService, SkillsServiceImpl,
-    SqliteConversationService,
+    ChatServiceImpl, ConversationImportService, ConversationImportServiceImpl, ConversationService,
+    McpRegistryService, McpRegistryServiceImpl, McpService, McpServiceImpl, ModelsRegistryService,
+    ModelsRegistryServiceImpl, ProfileService, ProfileServiceImpl, SecretsService,
+    SecretsServiceImpl, SkillsService, SkillsServiceImpl, SqliteConversationService,
+    SqliteTodoService, SqliteToolAuditService, SqliteUsageService, TodoService, ToolAuditService,
+    UsageService,
 };
 use personal_agent::ui_gpui::app_store::{
     BeginSelectionMode, BeginSelectionResult, StartupInputs, StartupMode,
@@ -291,7 +295,13 @@ fn handle_select_conversation_intent(app_state: &AppState, conversation_id: uuid
         .app_store
         .begin_selection(conversation_id, BeginSelectionMode::PublishImmediately)
     {
-        BeginSelectionResult::NoOpSameSelection => {}
+        // Re-selecting the open conversation from a search hit only needs the
+        // chat view to see the snapshot again to perform the message jump.
+        BeginSelectionResult::NoOpSameSelection => {
+            if selection_intent_channel().has_message_jump(conversation_id) {
+                app_state.app_store.republish();
+            }
+        }
         BeginSelectionResult::BeganSelection { generation } => {
             emit_select_or_record_failure(app_state, conversation_id, generation);
         }
@@ -422,6 +432,12 @@ fn start_tray_and_apply_popup_flags(tray: &mut SystemTray, cx: &mut App) {
 #[allow(clippy::cognitive_complexity)]
 fn run_gpui_app(cx: &mut App) {
     cx.set_quit_mode(QuitMode::Explicit);
+    cx.on_app_quit(|_| {
+        BackgroundProcesses::global().kill_all();
+        ShellSessions::global().close_all();
+        async {}
+    })
+    .detach();
 
     #[cfg(target_os = "macos")]
     let Some(mtm) = MainThreadMarker::new() else {
@@ -610,6 +626,11 @@ struct Services {
     mcp_registry: Arc<dyn McpRegistryService>,
     chat: Arc<dyn ChatService>,
     backup: Arc<dyn personal_agent::services::BackupService>,
+    usage: Arc<dyn UsageService>,
+    import: Arc<dyn ConversationImportService>,
+    checkpoints: Arc<CheckpointStore>,
+    todos: Arc<dyn TodoService>,
+    audit: Arc<dyn ToolAuditService>,
 }
 
 async fn create_services(
@@ -624,17 +645,23 @@ async fn create_services(
         AppSettingsServiceImpl::new(runtime_paths.app_settings_path.clone())
             .expect("Failed to create AppSettingsService"),
     );
-    let db_path = runtime_paths.base_dir.join("personalagent.db");
-    let db_path_for_backup = db_path.clone();
-    let db = tokio::task::spawn_blocking(move || spawn_db_thread(&db_path))
+    let db_path_for_backup = runtime_paths.base_dir.join("personalagent.db");
+    let db = startup::open_database(runtime_paths, app_settings.clone())
         .await
-        .expect("spawn_blocking join failed")
-        .expect("Failed to spawn DB thread");
+        .expect("Failed to open database");
 
     // Clone the DbHandle for the backup service (DbHandle is cheap to clone)
     let db_for_backup = db.clone();
 
+    let usage: Arc<dyn UsageService> = Arc::new(SqliteUsageService::new(db.clone()));
+    let todos: Arc<dyn TodoService> = Arc::new(SqliteTodoService::new(db.clone()));
+    let audit: Arc<dyn ToolAuditService> = Arc::new(SqliteToolAuditService::new(db.clone()));
+    let db_for_import = db.clone();
     let conversation: Arc<dyn ConversationService> = Arc::new(SqliteConversationService::new(db));
+    let import: Arc<dyn ConversationImportService> = Arc::new(ConversationImportServiceImpl::new(
+        db_for_import,
+        conversation.clone(),
+    ));
 
     // Create backup service
     let backup: Arc<dyn personal_agent::services::BackupService> =
@@ -668,6 +695,9 @@ async fn create_services(
         Arc::new(McpRegistryServiceImpl::new().expect("Failed to create McpRegistryService"));
 
     let approval_gate = Arc::new(ApprovalGate::new());
+    let checkpoints = Arc::new(CheckpointStore::new(
+        runtime_paths.base_dir.join("checkpoints"),
+    ));
 
     let chat: Arc<dyn ChatService> = Arc::new(
         ChatServiceImpl::new_with_settings(
@@ -678,7 +708,11 @@ async fn create_services(
             view_tx,
             approval_gate,
         )
-        .await,
+        .await
+        .with_usage_service(usage.clone())
+        .with_checkpoint_store(checkpoints.clone())
+        .with_todo_service(todos.clone())
+        .with_audit_service(audit.clone()),
     );
 
     Services {
@@ -692,6 +726,11 @@ async fn create_services(
         mcp_registry,
         chat,
         backup,
+        usage,
+        import,
+        checkpoints,
+        todos,
+        audit,
     }
 }
 
@@ -811,12 +850,15 @@ async fn start_all_presenters(
         services.profile.clone(),
         services.app_settings.clone(),
         view_tx.clone(),
-    );
+    )
+    .with_checkpoint_store(services.checkpoints.clone())
+    .with_todo_service(services.todos.clone());
     let mut history = HistoryPresenter::new(
         Arc::clone(event_bus),
         services.conversation.clone(),
         view_tx.clone(),
-    );
+    )
+    .with_app_settings_service(services.app_settings.clone());
     let mut settings = SettingsPresenter::new_with_event_bus(
         services.profile.clone(),
         services.app_settings.clone(),
@@ -851,6 +893,27 @@ async fn start_all_presenters(
         api_key_manager_view_tx,
     );
 
+    let mut usage = UsagePresenter::new(
+        Arc::clone(event_bus),
+        services.usage.clone(),
+        services.profile.clone(),
+        view_tx.clone(),
+    );
+
+    let mut audit = AuditPresenter::new(
+        Arc::clone(event_bus),
+        services.audit.clone(),
+        services.app_settings.clone(),
+        view_tx.clone(),
+    );
+
+    let mut import = ImportPresenter::new(
+        Arc::clone(event_bus),
+        services.import.clone(),
+        services.profile.clone(),
+        view_tx.clone(),
+    );
+
     let mut error = ErrorPresenter::new_with_event_bus(event_bus, view_tx);
 
     start_presenter!("ChatPresenter", chat);
@@ -861,6 +924,9 @@ async fn start_all_presenters(
     start_presenter!("McpAddPresenter", mcp_add);
     start_presenter!("McpConfigurePresenter", mcp_configure);
     start_presenter!("ApiKeyManagerPresenter", api_key_manager);
+    start_presenter!("UsagePresenter", usage);
+    start_presenter!("AuditPresenter", audit);
+    start_presenter!("ImportPresenter", import);
     start_presenter!("ErrorPresenter", error);
-    info!("All 9 presenters started");
+    info!("All 12 presenters started");
 }
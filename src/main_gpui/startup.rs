@@ -10,11 +10,12 @@ use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use flate2::read::GzDecoder;
-use personal_agent::backup::BackupInfo;
-use personal_agent::db::spawn_db_thread;
+use personal_agent::backup::{migrate_with_backup, BackupInfo};
+use personal_agent::db::{spawn_db_thread_unmigrated, DbHandle};
+use personal_agent::models::ConversationFilter;
 use personal_agent::services::{
-    AppSettingsService, AppSettingsServiceImpl, ConversationService, ProfileService,
-    ProfileServiceImpl, SqliteConversationService,
+    AppSettingsService, AppSettingsServiceImpl, BackupServiceImpl, ConversationService,
+    ProfileService, ProfileServiceImpl, SqliteConversationService,
 };
 use personal_agent::ui_gpui::app_store::{
     StartupInputs, StartupMode, StartupSelectedConversation, StartupTranscriptResult,
@@ -114,12 +115,30 @@ fn build_startup_app_settings(
 async fn build_startup_conversation_service(
     runtime_paths: &RuntimePaths,
 ) -> Result<SqliteConversationService, String> {
+    let app_settings: Arc<dyn AppSettingsService> =
+        Arc::new(build_startup_app_settings(runtime_paths)?);
+    let db = open_database(runtime_paths, app_settings).await?;
+    Ok(SqliteConversationService::new(db))
+}
+
+/// Open the conversation database, taking a backup through `BackupService`
+/// before applying any pending schema migrations.
+pub async fn open_database(
+    runtime_paths: &RuntimePaths,
+    app_settings: Arc<dyn AppSettingsService>,
+) -> Result<DbHandle, String> {
     let db_path = runtime_paths.base_dir.join("personalagent.db");
-    let db = tokio::task::spawn_blocking(move || spawn_db_thread(&db_path))
+    let spawn_path = db_path.clone();
+    let db = tokio::task::spawn_blocking(move || spawn_db_thread_unmigrated(&spawn_path))
         .await
-        .map_err(|e| format!("Failed to join DB spawn task for startup bootstrap: {e}"))?
-        .map_err(|e| format!("Failed to spawn DB thread for startup bootstrap: {e}"))?;
-    Ok(SqliteConversationService::new(db))
+        .map_err(|e| format!("Failed to join DB spawn task: {e}"))?
+        .map_err(|e| format!("Failed to spawn DB thread: {e}"))?;
+
+    let backup_service = BackupServiceImpl::new(db.clone(), app_settings, db_path);
+    migrate_with_backup(&db, &backup_service)
+        .await
+        .map_err(|e| format!("Failed to migrate database schema: {e}"))?;
+    Ok(db)
 }
 
 async fn build_startup_profile_service(
@@ -278,8 +297,10 @@ async fn build_conversation_data(
     ),
     String,
 > {
+    // Archived conversations are listed (the views hide them by default) but
+    // never restored as the startup selection.
     let conversations = conversation_service
-        .list_metadata(None, None)
+        .list_metadata(&ConversationFilter::all(), None, None)
         .await
         .map_err(|e| format!("Failed to list conversations for startup bootstrap: {e}"))?;
 
@@ -296,11 +317,17 @@ async fn build_conversation_data(
                 updated_at: metadata.updated_at,
                 message_count: metadata.message_count,
                 preview: metadata.last_message_preview.clone(),
+                parent_id: metadata.parent_id,
+                organization: metadata.organization.clone(),
             },
         )
         .collect::<Vec<_>>();
 
-    let selected = match conversations.first().map(|m| m.id) {
+    let selected = match conversations
+        .iter()
+        .find(|m| !m.organization.archived)
+        .map(|m| m.id)
+    {
         Some(conversation_id) => {
             let transcript_result =
                 load_startup_transcript(conversation_service, conversation_id).await;
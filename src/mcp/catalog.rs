@@ -0,0 +1,429 @@
+//! MCP resources and prompts.
+//!
+//! Servers that advertise the `resources` or `prompts` capability in their
+//! `initialize` response have them listed when they start. Responses are
+//! read in their wire form (the MCP JSON shapes), so this module only depends
+//! on the client serializing what the server sent.
+//!
+//! The composer inserts a read resource as a `<resource>` block and a server
+//! prompt as its rendered text. Prompts with arguments are filled in through
+//! the `/prompt server/name key=value` command.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent::approval_rules::{quote_rule_value, split_rule_words};
+
+/// Composer command that inserts a server prompt.
+pub const PROMPT_COMMAND: &str = "/prompt";
+
+/// What a server supports beyond tools, from its `initialize` response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct McpCapabilities {
+    pub resources: bool,
+    pub prompts: bool,
+}
+
+impl McpCapabilities {
+    /// Read the capabilities from an `initialize` result.
+    #[must_use]
+    pub fn from_initialize(result: &Value) -> Self {
+        let capabilities = result.get("capabilities");
+        let advertises = |name: &str| {
+            capabilities
+                .and_then(|capabilities| capabilities.get(name))
+                .is_some_and(|capability| !capability.is_null())
+        };
+        Self {
+            resources: advertises("resources"),
+            prompts: advertises("prompts"),
+        }
+    }
+}
+
+/// A resource a server exposes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpResource {
+    /// Name of the server it belongs to.
+    pub server: String,
+    pub uri: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+/// An argument of a server prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPromptArgument {
+    pub name: String,
+    pub description: Option<String>,
+    pub required: bool,
+}
+
+/// A prompt template a server exposes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPrompt {
+    /// Name of the server it belongs to.
+    pub server: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireResource {
+    uri: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    mime_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WirePromptArgument {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    required: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct WirePrompt {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    arguments: Vec<WirePromptArgument>,
+}
+
+/// The entries of a list response, which is either the array itself or an
+/// object holding it under `key`. Entries that do not parse are skipped.
+fn list_entries<T: for<'de> Deserialize<'de>>(listed: Value, key: &str) -> Vec<T> {
+    let entries = match listed {
+        Value::Array(entries) => entries,
+        Value::Object(mut object) => match object.remove(key) {
+            Some(Value::Array(entries)) => entries,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .filter_map(|entry| serde_json::from_value(entry).ok())
+        .collect()
+}
+
+/// Resources from a `resources/list` response of `server`.
+#[must_use]
+pub fn parse_resources(server: &str, listed: Value) -> Vec<McpResource> {
+    list_entries::<WireResource>(listed, "resources")
+        .into_iter()
+        .map(|resource| McpResource {
+            server: server.to_string(),
+            name: resource
+                .name
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| resource.uri.clone()),
+            uri: resource.uri,
+            description: resource.description,
+            mime_type: resource.mime_type,
+        })
+        .collect()
+}
+
+/// Prompts from a `prompts/list` response of `server`.
+#[must_use]
+pub fn parse_prompts(server: &str, listed: Value) -> Vec<McpPrompt> {
+    list_entries::<WirePrompt>(listed, "prompts")
+        .into_iter()
+        .map(|prompt| McpPrompt {
+            server: server.to_string(),
+            name: prompt.name,
+            description: prompt.description,
+            arguments: prompt
+                .arguments
+                .into_iter()
+                .map(|argument| McpPromptArgument {
+                    name: argument.name,
+                    description: argument.description,
+                    required: argument.required.unwrap_or(false),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Text of one resource contents entry; binary contents are described
+/// rather than decoded.
+fn contents_text(contents: &Value) -> Option<String> {
+    if let Some(text) = contents.get("text").and_then(Value::as_str) {
+        return Some(text.to_string());
+    }
+    let blob = contents.get("blob").and_then(Value::as_str)?;
+    let mime_type = contents
+        .get("mimeType")
+        .and_then(Value::as_str)
+        .unwrap_or("application/octet-stream");
+    Some(format!(
+        "[binary {mime_type} content, {} base64 characters]",
+        blob.len()
+    ))
+}
+
+/// Text of a `resources/read` response.
+#[must_use]
+pub fn resource_text(read: &Value) -> String {
+    let contents = read.get("contents").unwrap_or(read);
+    contents
+        .as_array()
+        .map(|entries| entries.iter().filter_map(contents_text).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .join("\n\n")
+}
+
+/// Text of one prompt message content: text, or an embedded resource.
+fn message_content_text(content: &Value) -> Option<String> {
+    match content.get("type").and_then(Value::as_str) {
+        Some("resource") => content.get("resource").and_then(contents_text),
+        _ => content
+            .get("text")
+            .and_then(Value::as_str)
+            .map(ToString::to_string),
+    }
+}
+
+/// Text of a `prompts/get` response, messages separated by blank lines.
+#[must_use]
+pub fn prompt_text(rendered: &Value) -> String {
+    rendered
+        .get("messages")
+        .and_then(Value::as_array)
+        .map(|messages| {
+            messages
+                .iter()
+                .filter_map(|message| message.get("content"))
+                .flat_map(|content| match content {
+                    Value::Array(parts) => parts.iter().filter_map(message_content_text).collect(),
+                    content => message_content_text(content)
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+        .join("\n\n")
+}
+
+/// A resource read from `server` as it is attached to a message.
+#[must_use]
+pub fn resource_attachment(server: &str, uri: &str, text: &str) -> String {
+    format!(
+        "<resource server=\"{server}\" uri=\"{uri}\">\n{}\n</resource>\n",
+        text.trim_end()
+    )
+}
+
+/// A server prompt with the arguments to render it with.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPromptInvocation {
+    pub server: String,
+    pub name: String,
+    pub arguments: BTreeMap<String, String>,
+}
+
+impl McpPromptInvocation {
+    /// The command for `prompt` with every argument left empty, for the user
+    /// to fill in.
+    #[must_use]
+    pub fn template(prompt: &McpPrompt) -> String {
+        Self {
+            server: prompt.server.clone(),
+            name: prompt.name.clone(),
+            arguments: prompt
+                .arguments
+                .iter()
+                .map(|argument| (argument.name.clone(), String::new()))
+                .collect(),
+        }
+        .to_string()
+    }
+
+    /// Parse composer text as a `/prompt` command; `None` when it is not one.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Result<Self, String>> {
+        let rest = text.trim().strip_prefix(PROMPT_COMMAND)?;
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        Some(Self::parse_words(rest))
+    }
+
+    fn parse_words(text: &str) -> Result<Self, String> {
+        let mut words = split_rule_words(text)?.into_iter();
+        let target = words
+            .next()
+            .ok_or_else(|| format!("Usage: {PROMPT_COMMAND} server/prompt key=value ..."))?;
+        let (server, name) = target
+            .rsplit_once('/')
+            .filter(|(server, name)| !server.is_empty() && !name.is_empty())
+            .ok_or_else(|| format!("Expected server/prompt, got '{target}'"))?;
+
+        let mut arguments = BTreeMap::new();
+        for word in words {
+            let (key, value) = word
+                .split_once('=')
+                .filter(|(key, _)| !key.is_empty())
+                .ok_or_else(|| format!("Expected key=value, got '{word}'"))?;
+            arguments.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(Self {
+            server: server.to_string(),
+            name: name.to_string(),
+            arguments,
+        })
+    }
+
+    /// Arguments `prompt` requires that are missing or empty.
+    #[must_use]
+    pub fn missing_arguments(&self, prompt: &McpPrompt) -> Vec<String> {
+        prompt
+            .arguments
+            .iter()
+            .filter(|argument| argument.required)
+            .filter(|argument| {
+                self.arguments
+                    .get(&argument.name)
+                    .is_none_or(|value| value.trim().is_empty())
+            })
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
+}
+
+impl fmt::Display for McpPromptInvocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let target = format!("{}/{}", self.server, self.name);
+        write!(f, "{PROMPT_COMMAND} {}", quote_rule_value(&target))?;
+        for (key, value) in &self.arguments {
+            write!(f, " {key}={}", quote_rule_value(value))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn capabilities_come_from_the_initialize_result() {
+        let capabilities = McpCapabilities::from_initialize(&json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": { "tools": {}, "resources": { "subscribe": false } },
+            "serverInfo": { "name": "files", "version": "1.0" }
+        }));
+
+        assert!(capabilities.resources);
+        assert!(!capabilities.prompts);
+        assert_eq!(
+            McpCapabilities::from_initialize(&json!({})),
+            McpCapabilities::default()
+        );
+    }
+
+    #[test]
+    fn listings_parse_from_either_wire_shape() {
+        let resources = parse_resources(
+            "files",
+            json!({ "resources": [
+                { "uri": "file:///notes.md", "name": "notes", "mimeType": "text/markdown" },
+                { "uri": "db://rows/1" },
+                { "name": "no uri" }
+            ]}),
+        );
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].mime_type.as_deref(), Some("text/markdown"));
+        assert_eq!(resources[1].name, "db://rows/1");
+
+        let prompts = parse_prompts(
+            "git",
+            json!([{ "name": "review", "arguments": [
+                { "name": "branch", "required": true },
+                { "name": "focus" }
+            ]}]),
+        );
+        assert_eq!(prompts[0].server, "git");
+        assert!(prompts[0].arguments[0].required);
+        assert!(!prompts[0].arguments[1].required);
+    }
+
+    #[test]
+    fn read_and_rendered_content_become_text() {
+        let read = json!({ "contents": [
+            { "uri": "file:///a.txt", "text": "first" },
+            { "uri": "file:///a.png", "mimeType": "image/png", "blob": "aGVsbG8=" }
+        ]});
+        assert_eq!(
+            resource_text(&read),
+            "first\n\n[binary image/png content, 8 base64 characters]"
+        );
+
+        let rendered = json!({ "messages": [
+            { "role": "user", "content": { "type": "text", "text": "Review main" } },
+            { "role": "user", "content": { "type": "resource",
+                "resource": { "uri": "file:///diff", "text": "+ added" } } }
+        ]});
+        assert_eq!(prompt_text(&rendered), "Review main\n\n+ added");
+    }
+
+    #[test]
+    fn prompt_commands_round_trip_and_report_missing_arguments() {
+        let prompt = McpPrompt {
+            server: "my git".to_string(),
+            name: "review".to_string(),
+            description: None,
+            arguments: vec![
+                McpPromptArgument {
+                    name: "branch".to_string(),
+                    description: None,
+                    required: true,
+                },
+                McpPromptArgument {
+                    name: "focus".to_string(),
+                    description: None,
+                    required: false,
+                },
+            ],
+        };
+        let template = McpPromptInvocation::template(&prompt);
+        assert_eq!(template, "/prompt \"my git/review\" branch=\"\" focus=\"\"");
+
+        let invocation = McpPromptInvocation::parse(&template)
+            .expect("prompt command")
+            .expect("valid command");
+        assert_eq!(invocation.missing_arguments(&prompt), vec!["branch"]);
+
+        let invocation = McpPromptInvocation::parse(
+            "/prompt \"my git/review\" branch=main focus=\"error paths\"",
+        )
+        .expect("prompt command")
+        .expect("valid command");
+        assert_eq!(invocation.server, "my git");
+        assert_eq!(invocation.arguments["focus"], "error paths");
+        assert!(invocation.missing_arguments(&prompt).is_empty());
+
+        assert_eq!(McpPromptInvocation::parse("/prompts are neat"), None);
+        assert_eq!(McpPromptInvocation::parse("hello"), None);
+        assert!(McpPromptInvocation::parse("/prompt review").is_some_and(|r| r.is_err()));
+        assert!(McpPromptInvocation::parse("/prompt git/review branch").is_some_and(|r| r.is_err()));
+    }
+}
@@ -1,4 +1,5 @@
 //! MCP (Model Context Protocol) support module
+pub mod catalog;
 pub mod manager;
 pub mod oauth;
 pub mod registry;
@@ -9,6 +10,9 @@ pub mod status;
 pub mod toolset;
 pub mod types;
 
+pub use catalog::{
+    McpCapabilities, McpPrompt, McpPromptArgument, McpPromptInvocation, McpResource,
+};
 pub use manager::{McpError, McpManager, McpResult};
 pub use oauth::{
     generate_smithery_oauth_url, start_oauth_callback_server, OAuthCallbackResult, OAuthConfig,
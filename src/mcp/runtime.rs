@@ -8,6 +8,9 @@ use tokio::time::{timeout, Duration};
 use uuid::Uuid;
 
 use crate::config::Config;
+use crate::mcp::catalog::{
+    parse_prompts, parse_resources, McpCapabilities, McpPrompt, McpPromptInvocation, McpResource,
+};
 use crate::mcp::{
     McpConfig, McpManager, McpStatus, McpStatusManager, McpTransport, SecretsManager,
 };
@@ -17,6 +20,9 @@ pub struct McpConnection {
     pub config: McpConfig,
     pub client: Arc<Mutex<McpClient>>,
     pub tools: Vec<McpTool>,
+    pub capabilities: McpCapabilities,
+    pub resources: Vec<McpResource>,
+    pub prompts: Vec<McpPrompt>,
 }
 
 /// Provider metadata for an MCP tool.
@@ -83,7 +89,8 @@ impl McpRuntime {
 
         let env = self.prepare_env(config)?;
         let client = self.create_client(config, env).await?;
-        let tools = self.initialize_client(config, &client).await?;
+        let (tools, capabilities) = self.initialize_client(config, &client).await?;
+        let (resources, prompts) = Self::list_catalog(config, &client, capabilities).await;
 
         // Register as active
         self.manager.register_active(config);
@@ -95,6 +102,9 @@ impl McpRuntime {
                 config: config.clone(),
                 client: Arc::new(Mutex::new(client)),
                 tools,
+                capabilities,
+                resources,
+                prompts,
             },
         );
 
@@ -217,9 +227,10 @@ impl McpRuntime {
         &self,
         config: &McpConfig,
         client: &McpClient,
-    ) -> Result<Vec<McpTool>, String> {
-        // Initialize the client
-        timeout(MCP_INIT_TIMEOUT, client.initialize())
+    ) -> Result<(Vec<McpTool>, McpCapabilities), String> {
+        // Initialize the client; its result says whether resources and
+        // prompts are worth listing
+        let initialized = timeout(MCP_INIT_TIMEOUT, client.initialize())
             .await
             .map_err(|_| {
                 let err = "Failed to initialize MCP: timeout".to_string();
@@ -233,6 +244,9 @@ impl McpRuntime {
                     .set_status(config.id, McpStatus::Error(err.clone()));
                 err
             })?;
+        let capabilities = McpCapabilities::from_initialize(
+            &serde_json::to_value(initialized).unwrap_or_default(),
+        );
 
         // List tools from the MCP server
         let mcp_tools = timeout(MCP_INIT_TIMEOUT, client.list_tools())
@@ -261,7 +275,46 @@ impl McpRuntime {
             })
             .collect();
 
-        Ok(tools)
+        Ok((tools, capabilities))
+    }
+
+    /// List the resources and prompts a server advertises. A failed listing
+    /// leaves that list empty rather than failing the server, whose tools
+    /// still work.
+    async fn list_catalog(
+        config: &McpConfig,
+        client: &McpClient,
+        capabilities: McpCapabilities,
+    ) -> (Vec<McpResource>, Vec<McpPrompt>) {
+        let mut resources = Vec::new();
+        if capabilities.resources {
+            match timeout(MCP_INIT_TIMEOUT, client.list_resources()).await {
+                Ok(Ok(listed)) => {
+                    resources = parse_resources(
+                        &config.name,
+                        serde_json::to_value(listed).unwrap_or_default(),
+                    );
+                }
+                Ok(Err(e)) => tracing::warn!("Failed to list resources of {}: {e}", config.name),
+                Err(_) => tracing::warn!("Listing resources of {} timed out", config.name),
+            }
+        }
+
+        let mut prompts = Vec::new();
+        if capabilities.prompts {
+            match timeout(MCP_INIT_TIMEOUT, client.list_prompts()).await {
+                Ok(Ok(listed)) => {
+                    prompts = parse_prompts(
+                        &config.name,
+                        serde_json::to_value(listed).unwrap_or_default(),
+                    );
+                }
+                Ok(Err(e)) => tracing::warn!("Failed to list prompts of {}: {e}", config.name),
+                Err(_) => tracing::warn!("Listing prompts of {} timed out", config.name),
+            }
+        }
+
+        (resources, prompts)
     }
 
     /// Stop an MCP server
@@ -296,6 +349,117 @@ impl McpRuntime {
             .collect()
     }
 
+    /// Get all resources listed by active MCPs
+    #[must_use]
+    pub fn get_all_resources(&self) -> Vec<McpResource> {
+        self.connections
+            .values()
+            .flat_map(|c| c.resources.iter().cloned())
+            .collect()
+    }
+
+    /// Get all prompts listed by active MCPs
+    #[must_use]
+    pub fn get_all_prompts(&self) -> Vec<McpPrompt> {
+        self.connections
+            .values()
+            .flat_map(|c| c.prompts.iter().cloned())
+            .collect()
+    }
+
+    /// Find the running connection named `server`
+    fn connection_by_name(&self, server: &str) -> Result<(Uuid, &McpConnection), String> {
+        self.connections
+            .iter()
+            .find(|(_, conn)| conn.config.name == server)
+            .map(|(id, conn)| (*id, conn))
+            .ok_or_else(|| format!("MCP server not running: {server}"))
+    }
+
+    /// Mark `mcp_id` as used and hand out its client
+    fn touch_client(&mut self, mcp_id: Uuid) -> Result<Arc<Mutex<McpClient>>, String> {
+        self.manager.touch(&mcp_id);
+        self.connections
+            .get(&mcp_id)
+            .map(|conn| Arc::clone(&conn.client))
+            .ok_or_else(|| format!("MCP connection not found: {mcp_id}"))
+    }
+
+    /// Read a resource from the MCP named `server`, returning the
+    /// `resources/read` result as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is not running, does not support
+    /// resources, or the read fails or times out.
+    pub async fn read_resource(
+        &mut self,
+        server: &str,
+        uri: &str,
+    ) -> Result<serde_json::Value, String> {
+        let (mcp_id, conn) = self.connection_by_name(server)?;
+        if !conn.capabilities.resources {
+            return Err(format!("MCP server {server} does not provide resources"));
+        }
+        let client = self.touch_client(mcp_id)?;
+
+        let result = timeout(MCP_TOOL_TIMEOUT, client.lock().await.read_resource(uri))
+            .await
+            .map_err(|_| "MCP resource read timed out".to_string())?
+            .map_err(|e| format!("MCP resource read failed: {e}"))?;
+
+        Ok(serde_json::to_value(result).unwrap_or_default())
+    }
+
+    /// Render a prompt of the MCP named in `invocation`, returning the
+    /// `prompts/get` result as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server or prompt is unknown, a required
+    /// argument is missing, or the request fails or times out.
+    pub async fn get_prompt(
+        &mut self,
+        invocation: &McpPromptInvocation,
+    ) -> Result<serde_json::Value, String> {
+        let (mcp_id, conn) = self.connection_by_name(&invocation.server)?;
+        let prompt = conn
+            .prompts
+            .iter()
+            .find(|prompt| prompt.name == invocation.name)
+            .ok_or_else(|| {
+                format!(
+                    "MCP server {} has no prompt named {}",
+                    invocation.server, invocation.name
+                )
+            })?;
+        let missing = invocation.missing_arguments(prompt);
+        if !missing.is_empty() {
+            return Err(format!(
+                "Prompt {} needs: {}",
+                invocation.name,
+                missing.join(", ")
+            ));
+        }
+        let client = self.touch_client(mcp_id)?;
+
+        let arguments: HashMap<String, String> = invocation
+            .arguments
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let result = timeout(
+            MCP_TOOL_TIMEOUT,
+            client.lock().await.get_prompt(&invocation.name, arguments),
+        )
+        .await
+        .map_err(|_| "MCP prompt request timed out".to_string())?
+        .map_err(|e| format!("MCP prompt request failed: {e}"))?;
+
+        Ok(serde_json::to_value(result).unwrap_or_default())
+    }
+
     /// Find which MCP provides a tool
     #[must_use]
     pub fn find_tool_provider(&self, tool_name: &str) -> Option<Uuid> {
@@ -437,6 +601,9 @@ mod tests {
                     input_schema: serde_json::json!({"type":"object"}),
                     mcp_id,
                 }],
+                capabilities: McpCapabilities::default(),
+                resources: Vec::new(),
+                prompts: Vec::new(),
             },
         );
 
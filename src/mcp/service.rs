@@ -7,6 +7,7 @@ use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::config::Config;
+use crate::mcp::catalog::{self, McpPrompt, McpPromptInvocation, McpResource};
 use crate::mcp::{McpRuntime, SecretsManager};
 
 static MCP_SERVICE: OnceLock<Arc<Mutex<McpService>>> = OnceLock::new();
@@ -131,6 +132,42 @@ impl McpService {
         result
     }
 
+    /// Get resources listed by active MCPs
+    #[must_use]
+    pub fn get_resources(&self) -> Vec<McpResource> {
+        self.runtime.get_all_resources()
+    }
+
+    /// Get prompts listed by active MCPs
+    #[must_use]
+    pub fn get_prompts(&self) -> Vec<McpPrompt> {
+        self.runtime.get_all_prompts()
+    }
+
+    /// Read a resource from the named MCP as text
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server cannot provide the resource.
+    pub async fn read_resource(&mut self, server: &str, uri: &str) -> Result<String, String> {
+        let read = self.runtime.read_resource(server, uri).await?;
+        Ok(catalog::resource_text(&read))
+    }
+
+    /// Render a server prompt as text
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prompt is unknown, its required arguments are
+    /// missing, or the server request fails.
+    pub async fn render_prompt(
+        &mut self,
+        invocation: &McpPromptInvocation,
+    ) -> Result<String, String> {
+        let rendered = self.runtime.get_prompt(invocation).await?;
+        Ok(catalog::prompt_text(&rendered))
+    }
+
     /// Find MCP provider metadata for a tool by name.
     #[must_use]
     pub fn find_tool_provider_metadata(
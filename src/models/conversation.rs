@@ -24,6 +24,142 @@ pub struct ConversationMetadata {
     pub updated_at: DateTime<Utc>,
     pub message_count: usize,
     pub last_message_preview: Option<String>,
+    /// Conversation this one was branched from, if any.
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    #[serde(default)]
+    pub organization: ConversationOrganization,
+}
+
+/// User-assigned organization of a conversation: pinning, archive state,
+/// folder, and tags.
+///
+/// Tags are stored normalized (see `normalize_tag`) and sorted; `folder` is a
+/// single free-form name, with `None` meaning unfiled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationOrganization {
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Normalize a user-entered tag: trimmed, lowercased, leading `#` dropped,
+/// inner whitespace collapsed to `-`. Returns `None` if nothing is left.
+#[must_use]
+pub fn normalize_tag(raw: &str) -> Option<String> {
+    let tag = raw
+        .trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Normalize a user-entered folder name: trimmed, with blank meaning unfiled.
+#[must_use]
+pub fn normalize_folder(raw: &str) -> Option<String> {
+    let folder = raw.trim();
+    (!folder.is_empty()).then(|| folder.to_string())
+}
+
+/// Which archived conversations a listing includes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFilter {
+    /// Hide archived conversations (the default).
+    #[default]
+    Exclude,
+    /// Show archived and active conversations alike.
+    Include,
+    /// Show only archived conversations.
+    Only,
+}
+
+/// Organization filter applied by `ConversationService::list_metadata` and
+/// `ConversationService::search`. The default hides archived conversations
+/// and applies no other restriction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationFilter {
+    /// Only pinned conversations.
+    #[serde(default)]
+    pub pinned_only: bool,
+    #[serde(default)]
+    pub archived: ArchiveFilter,
+    /// Only conversations in this folder.
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Only conversations carrying every one of these tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl ConversationFilter {
+    /// Every conversation, archived or not.
+    #[must_use]
+    pub fn all() -> Self {
+        Self {
+            archived: ArchiveFilter::Include,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a conversation with `organization` passes this filter.
+    ///
+    /// Mirrors the SQL the `SQLite` service builds, normalizing the folder
+    /// and tags the same way, for views that filter an already-loaded list.
+    #[must_use]
+    pub fn admits(&self, organization: &ConversationOrganization) -> bool {
+        let archive_ok = match self.archived {
+            ArchiveFilter::Exclude => !organization.archived,
+            ArchiveFilter::Include => true,
+            ArchiveFilter::Only => organization.archived,
+        };
+        archive_ok
+            && (!self.pinned_only || organization.pinned)
+            && self
+                .folder
+                .as_deref()
+                .and_then(normalize_folder)
+                .is_none_or(|folder| organization.folder.as_ref() == Some(&folder))
+            && self
+                .tags
+                .iter()
+                .filter_map(|tag| normalize_tag(tag))
+                .all(|tag| organization.tags.contains(&tag))
+    }
+}
+
+/// One member of a conversation branch tree.
+///
+/// Branches are created by forking an existing conversation at a message
+/// `seq`; `parent_seq` records the last message copied from the parent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConversationBranch {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub parent_seq: Option<usize>,
+    pub created_at: DateTime<Utc>,
+    pub message_count: usize,
+}
+
+/// Alternate versions available at one message position.
+///
+/// Regenerating a response or editing a prompt archives the conversation
+/// from `seq` onward as a version and starts a new one in its place.
+/// `selected` is the version currently shown, or `None` when the position
+/// is empty (e.g. a regeneration that failed before producing a reply).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageVersionInfo {
+    pub seq: usize,
+    pub selected: Option<u32>,
+    pub count: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
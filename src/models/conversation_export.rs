@@ -7,6 +7,7 @@ pub enum ConversationExportFormat {
     Txt,
     #[default]
     Md,
+    Html,
 }
 
 impl ConversationExportFormat {
@@ -16,6 +17,7 @@ impl ConversationExportFormat {
             Self::Json => "json",
             Self::Txt => "txt",
             Self::Md => "md",
+            Self::Html => "html",
         }
     }
 
@@ -30,6 +32,7 @@ impl ConversationExportFormat {
             Self::Json => "JSON",
             Self::Txt => "TXT",
             Self::Md => "MD",
+            Self::Html => "HTML",
         }
     }
 
@@ -38,7 +41,8 @@ impl ConversationExportFormat {
         match self {
             Self::Md => Self::Txt,
             Self::Txt => Self::Json,
-            Self::Json => Self::Md,
+            Self::Json => Self::Html,
+            Self::Html => Self::Md,
         }
     }
 
@@ -50,15 +54,53 @@ impl ConversationExportFormat {
             Some(Self::Txt)
         } else if value.eq_ignore_ascii_case("md") || value.eq_ignore_ascii_case("markdown") {
             Some(Self::Md)
+        } else if value.eq_ignore_ascii_case("html") || value.eq_ignore_ascii_case("htm") {
+            Some(Self::Html)
         } else {
             None
         }
     }
 }
 
+/// Container for exporting several conversations at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ConversationArchiveFormat {
+    /// Zip with a manifest and one file per conversation in the export format.
+    #[default]
+    Zip,
+    /// JSON Lines: the manifest, then one conversation JSON object per line.
+    Jsonl,
+}
+
+impl ConversationArchiveFormat {
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Jsonl => "jsonl",
+        }
+    }
+
+    #[must_use]
+    pub const fn display_label(self) -> &'static str {
+        match self {
+            Self::Zip => "ZIP",
+            Self::Jsonl => "JSONL",
+        }
+    }
+
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Zip => Self::Jsonl,
+            Self::Jsonl => Self::Zip,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ConversationExportFormat;
+    use super::{ConversationArchiveFormat, ConversationExportFormat};
 
     #[test]
     fn default_is_markdown() {
@@ -69,7 +111,7 @@ mod tests {
     }
 
     #[test]
-    fn cycle_order_is_md_txt_json() {
+    fn cycle_order_is_md_txt_json_html() {
         assert_eq!(
             ConversationExportFormat::Md.next(),
             ConversationExportFormat::Txt
@@ -80,6 +122,10 @@ mod tests {
         );
         assert_eq!(
             ConversationExportFormat::Json.next(),
+            ConversationExportFormat::Html
+        );
+        assert_eq!(
+            ConversationExportFormat::Html.next(),
             ConversationExportFormat::Md
         );
     }
@@ -102,6 +148,23 @@ mod tests {
             ConversationExportFormat::from_setting_value("JSON"),
             Some(ConversationExportFormat::Json)
         );
+        assert_eq!(
+            ConversationExportFormat::from_setting_value("htm"),
+            Some(ConversationExportFormat::Html)
+        );
         assert_eq!(ConversationExportFormat::from_setting_value("pdf"), None);
     }
+
+    #[test]
+    fn archive_format_toggles_between_zip_and_jsonl() {
+        assert_eq!(
+            ConversationArchiveFormat::default(),
+            ConversationArchiveFormat::Zip
+        );
+        assert_eq!(
+            ConversationArchiveFormat::Zip.next(),
+            ConversationArchiveFormat::Jsonl
+        );
+        assert_eq!(ConversationArchiveFormat::Jsonl.extension(), "jsonl");
+    }
 }
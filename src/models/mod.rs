@@ -7,11 +7,26 @@ mod skill;
 
 pub mod profile;
 mod search;
+mod search_query;
+mod todo;
+mod tool_audit;
+mod usage;
 
 pub use context_state::{CompressionPhase, ContextState};
-pub use conversation::{Conversation, ConversationMetadata, Message, MessageRole};
+pub use conversation::{
+    normalize_folder, normalize_tag, ArchiveFilter, Conversation, ConversationBranch,
+    ConversationFilter, ConversationMetadata, ConversationOrganization, Message, MessageRole,
+    MessageVersionInfo,
+};
 pub use skill::{Skill, SkillMetadata, SkillSource};
 
-pub use conversation_export::ConversationExportFormat;
+pub use conversation_export::{ConversationArchiveFormat, ConversationExportFormat};
 pub use profile::{AuthConfig, ModelParameters, ModelProfile};
 pub use search::{SearchMatchType, SearchResult};
+pub use search_query::{SearchQuery, SearchTerm};
+pub use todo::{format_todo_list, TodoItem, TodoStatus};
+pub use tool_audit::{
+    truncate_audit_text, ApprovalSource, AuditExportFormat, AuditOutcome, ToolAuditEntry,
+    ToolAuditFilter, MAX_AUDIT_TEXT_CHARS,
+};
+pub use usage::{DailyUsage, ProfileUsage, TokenUsage, UsageRecord, UsageTotals};
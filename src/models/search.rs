@@ -1,5 +1,7 @@
 //! Search result types for full-text conversation search
 
+use std::ops::Range;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -14,6 +16,16 @@ pub struct SearchResult {
     pub score: f64,
     pub updated_at: DateTime<Utc>,
     pub message_count: usize,
+    /// Byte ranges of `title` matched by the query.
+    #[serde(default)]
+    pub title_highlights: Vec<Range<usize>>,
+    /// Byte ranges of `match_context` matched by the query.
+    #[serde(default)]
+    pub context_highlights: Vec<Range<usize>>,
+    /// Visible-message index of the best-matching message, or `None` when the
+    /// hit is in an inactive message version.
+    #[serde(default)]
+    pub message_index: Option<usize>,
 }
 
 /// How the search query matched a conversation.
@@ -0,0 +1,354 @@
+//! Structured conversation search queries.
+//!
+//! A query is free text plus optional `key:value` operators:
+//!
+//! | Operator            | Meaning                                              |
+//! |---------------------|------------------------------------------------------|
+//! | `profile:<id/name>` | conversations created with that profile              |
+//! | `model:<text>`      | messages whose model id contains the text            |
+//! | `role:<role>`       | messages from `user`, `assistant`, or `system`       |
+//! | `before:YYYY-MM-DD` | messages written before that day                     |
+//! | `after:YYYY-MM-DD`  | messages written on or after that day                |
+//! | `tag:<tag>`         | conversations carrying the tag (repeatable)          |
+//!
+//! Free text is matched with FTS5: `"quoted phrases"` match exactly, `word*`
+//! matches a prefix, and the last bare word is always prefix-matched so
+//! results follow the user as they type. Operator values may be quoted
+//! (`profile:"Work GPT"`). A malformed operator (unknown key, bad date or
+//! role) is searched as plain text instead of being dropped.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use super::{normalize_tag, MessageRole};
+
+/// One free-text component of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchTerm {
+    Word(String),
+    Prefix(String),
+    Phrase(String),
+}
+
+/// A parsed search query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub terms: Vec<SearchTerm>,
+    /// Profile id, or a profile name for the presenter to resolve.
+    pub profile: Option<String>,
+    pub model: Option<String>,
+    pub role: Option<MessageRole>,
+    pub before: Option<NaiveDate>,
+    pub after: Option<NaiveDate>,
+    /// Normalized tags (see `normalize_tag`).
+    pub tags: Vec<String>,
+}
+
+/// Characters with structural meaning in FTS5 syntax; stripped from words.
+const FTS_STRUCTURAL: [char; 9] = ['*', '(', ')', '{', '}', '^', '~', ':', '"'];
+
+enum Token {
+    Bare(String),
+    Quoted(String),
+    Field(String, String),
+}
+
+/// Split on whitespace, keeping `"quoted runs"` and `key:"quoted values"`
+/// together. An unterminated quote runs to the end of the input.
+fn tokenize(raw: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = raw.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            tokens.push(Token::Quoted(phrase));
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            if c == '"' && word.ends_with(':') {
+                let value: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                word.pop();
+                tokens.push(Token::Field(word, value));
+                word = String::new();
+                break;
+            }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            tokens.push(Token::Bare(word));
+        }
+    }
+
+    tokens
+}
+
+fn parse_role(value: &str) -> Option<MessageRole> {
+    match value.to_ascii_lowercase().as_str() {
+        "user" => Some(MessageRole::User),
+        "assistant" => Some(MessageRole::Assistant),
+        "system" => Some(MessageRole::System),
+        _ => None,
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+const fn role_name(role: &MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+        MessageRole::System => "system",
+    }
+}
+
+/// Quote a string as an FTS5 string literal.
+fn fts_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Quote an operator value if it would not survive re-tokenizing.
+fn display_value(value: &str) -> String {
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        format!("\"{value}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+impl SearchQuery {
+    /// Parse a raw query string. Never fails; see the module docs for how
+    /// malformed input degrades.
+    #[must_use]
+    pub fn parse(raw: &str) -> Self {
+        let mut query = Self::default();
+        for token in tokenize(raw) {
+            match token {
+                Token::Quoted(phrase) => query.push_phrase(&phrase),
+                Token::Field(key, value) => {
+                    if !query.apply_operator(&key, &value) {
+                        query.push_words(&format!("{key} {value}"));
+                    }
+                }
+                Token::Bare(word) => {
+                    let applied = word
+                        .split_once(':')
+                        .is_some_and(|(key, value)| query.apply_operator(key, value));
+                    if !applied {
+                        query.push_words(&word);
+                    }
+                }
+            }
+        }
+        query
+    }
+
+    /// Apply `key:value`; returns false when it is not a valid operator.
+    fn apply_operator(&mut self, key: &str, value: &str) -> bool {
+        let value = value.trim();
+        if value.is_empty() {
+            return false;
+        }
+        match key.to_ascii_lowercase().as_str() {
+            "profile" => self.profile = Some(value.to_string()),
+            "model" => self.model = Some(value.to_string()),
+            "role" => match parse_role(value) {
+                Some(role) => self.role = Some(role),
+                None => return false,
+            },
+            "before" => match parse_date(value) {
+                Some(date) => self.before = Some(date),
+                None => return false,
+            },
+            "after" => match parse_date(value) {
+                Some(date) => self.after = Some(date),
+                None => return false,
+            },
+            "tag" => match normalize_tag(value) {
+                Some(tag) if !self.tags.contains(&tag) => self.tags.push(tag),
+                Some(_) => {}
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    fn push_phrase(&mut self, phrase: &str) {
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !phrase.is_empty() {
+            self.terms.push(SearchTerm::Phrase(phrase));
+        }
+    }
+
+    fn push_words(&mut self, text: &str) {
+        let prefix = text.ends_with('*');
+        let cleaned: String = text
+            .chars()
+            .map(|c| if FTS_STRUCTURAL.contains(&c) { ' ' } else { c })
+            .collect();
+        let words: Vec<&str> = cleaned.split_whitespace().collect();
+        let last = words.len().saturating_sub(1);
+        for (i, word) in words.into_iter().enumerate() {
+            self.terms.push(if prefix && i == last {
+                SearchTerm::Prefix(word.to_string())
+            } else {
+                SearchTerm::Word(word.to_string())
+            });
+        }
+    }
+
+    /// True when the query has neither free text nor operators.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The FTS5 `MATCH` expression for the free-text part, or `None` when the
+    /// query is operators only.
+    #[must_use]
+    pub fn fts_expression(&self) -> Option<String> {
+        let last = self.terms.len().checked_sub(1)?;
+        let parts: Vec<String> = self
+            .terms
+            .iter()
+            .enumerate()
+            .map(|(i, term)| match term {
+                SearchTerm::Word(word) if i == last => format!("{}*", fts_quote(word)),
+                SearchTerm::Word(word) | SearchTerm::Phrase(word) => fts_quote(word),
+                SearchTerm::Prefix(word) => format!("{}*", fts_quote(word)),
+            })
+            .collect();
+        Some(parts.join(" "))
+    }
+}
+
+/// Canonical query text; parsing it yields an equal `SearchQuery`.
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = self
+            .terms
+            .iter()
+            .map(|term| match term {
+                SearchTerm::Word(word) => word.clone(),
+                SearchTerm::Prefix(word) => format!("{word}*"),
+                SearchTerm::Phrase(phrase) => format!("\"{phrase}\""),
+            })
+            .collect();
+        if let Some(profile) = &self.profile {
+            parts.push(format!("profile:{}", display_value(profile)));
+        }
+        if let Some(model) = &self.model {
+            parts.push(format!("model:{}", display_value(model)));
+        }
+        if let Some(role) = &self.role {
+            parts.push(format!("role:{}", role_name(role)));
+        }
+        if let Some(before) = self.before {
+            parts.push(format!("before:{before}"));
+        }
+        if let Some(after) = self.after {
+            parts.push(format!("after:{after}"));
+        }
+        parts.extend(self.tags.iter().map(|tag| format!("tag:{tag}")));
+        f.write_str(&parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_words_prefix_match_the_last_word() {
+        let query = SearchQuery::parse("rust borrow");
+        assert_eq!(
+            query.terms,
+            [
+                SearchTerm::Word("rust".to_string()),
+                SearchTerm::Word("borrow".to_string())
+            ]
+        );
+        assert_eq!(
+            query.fts_expression().as_deref(),
+            Some("\"rust\" \"borrow\"*")
+        );
+    }
+
+    #[test]
+    fn phrases_and_explicit_prefixes() {
+        let query = SearchQuery::parse("\"borrow checker\" life* \"unterminated phrase");
+        assert_eq!(
+            query.fts_expression().as_deref(),
+            Some("\"borrow checker\" \"life\"* \"unterminated phrase\"")
+        );
+    }
+
+    #[test]
+    fn operators_are_extracted() {
+        let query = SearchQuery::parse(
+            "lifetimes profile:\"Work GPT\" model:sonnet role:Assistant \
+             before:2024-06-01 after:2024-01-01 tag:#Rust tag:rust",
+        );
+        assert_eq!(query.terms, [SearchTerm::Word("lifetimes".to_string())]);
+        assert_eq!(query.profile.as_deref(), Some("Work GPT"));
+        assert_eq!(query.model.as_deref(), Some("sonnet"));
+        assert_eq!(query.role, Some(MessageRole::Assistant));
+        assert_eq!(query.before, NaiveDate::from_ymd_opt(2024, 6, 1));
+        assert_eq!(query.after, NaiveDate::from_ymd_opt(2024, 1, 1));
+        assert_eq!(query.tags, ["rust"]);
+    }
+
+    #[test]
+    fn malformed_operators_fall_back_to_text() {
+        let query = SearchQuery::parse("before:someday role:robot foo:bar tag:");
+        assert_eq!(query.before, None);
+        assert_eq!(query.role, None);
+        let words: Vec<_> = query
+            .terms
+            .iter()
+            .map(|term| match term {
+                SearchTerm::Word(word) | SearchTerm::Prefix(word) | SearchTerm::Phrase(word) => {
+                    word.as_str()
+                }
+            })
+            .collect();
+        assert_eq!(
+            words,
+            ["before", "someday", "role", "robot", "foo", "bar", "tag"]
+        );
+    }
+
+    #[test]
+    fn structural_characters_never_reach_fts() {
+        let query = SearchQuery::parse("foo(bar) ^baz~ {qux}");
+        assert_eq!(
+            query.fts_expression().as_deref(),
+            Some("\"foo\" \"bar\" \"baz\" \"qux\"*")
+        );
+        assert!(SearchQuery::parse("  ()*  ").is_empty());
+        assert_eq!(SearchQuery::parse("tag:rust").fts_expression(), None);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let query = SearchQuery::parse(
+            "\"exact words\" pre* last profile:\"Work GPT\" role:user after:2024-01-01 tag:q3",
+        );
+        assert_eq!(SearchQuery::parse(&query.to_string()), query);
+    }
+}
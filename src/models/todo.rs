@@ -0,0 +1,102 @@
+//! The task list an agent keeps for a conversation with `TodoWrite`.
+//!
+//! The list lives outside the message history, so compressing the history
+//! never loses it; each turn's system prompt carries the current list.
+
+use serde::{Deserialize, Serialize};
+
+/// Progress of one task list item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+impl TodoStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+        }
+    }
+
+    /// Parse the form written by `as_str`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "in_progress" => Some(Self::InProgress),
+            "completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
+}
+
+/// One item of a conversation's task list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub content: String,
+    pub status: TodoStatus,
+}
+
+impl TodoItem {
+    #[must_use]
+    pub fn new(content: impl Into<String>, status: TodoStatus) -> Self {
+        Self {
+            content: content.into(),
+            status,
+        }
+    }
+}
+
+/// The list as plain-text checklist lines, as the model sees it.
+#[must_use]
+pub fn format_todo_list(items: &[TodoItem]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            let mark = match item.status {
+                TodoStatus::Pending => "[ ]",
+                TodoStatus::InProgress => "[~]",
+                TodoStatus::Completed => "[x]",
+            };
+            format!("- {mark} {}", item.content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_round_trips_through_its_text_form() {
+        for status in [
+            TodoStatus::Pending,
+            TodoStatus::InProgress,
+            TodoStatus::Completed,
+        ] {
+            assert_eq!(TodoStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(TodoStatus::parse("done"), None);
+    }
+
+    #[test]
+    fn format_todo_list_marks_each_status() {
+        let items = [
+            TodoItem::new("Read the parser", TodoStatus::Completed),
+            TodoItem::new("Fix the bug", TodoStatus::InProgress),
+            TodoItem::new("Add a test", TodoStatus::Pending),
+        ];
+
+        assert_eq!(
+            format_todo_list(&items),
+            "- [x] Read the parser\n- [~] Fix the bug\n- [ ] Add a test"
+        );
+    }
+}
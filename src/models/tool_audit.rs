@@ -0,0 +1,179 @@
+//! Audit records of the tool calls agents make.
+//!
+//! Every native and MCP tool call is recorded when it finishes, whether it
+//! ran, failed or was refused, together with what decided its approval.
+//! Arguments and output are truncated before they are stored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest argument or output text kept in an audit record, in characters.
+pub const MAX_AUDIT_TEXT_CHARS: usize = 2_000;
+
+/// What decided whether a tool call could run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalSource {
+    /// A structured approval rule; the record's `approval_detail` holds it.
+    Rule,
+    /// YOLO mode.
+    Yolo,
+    /// The prefix lists, domain lists and auto-approval toggles.
+    Policy,
+    /// The user, answering an approval prompt.
+    User,
+    /// No approval was reached: the tool is not gated, or it failed before
+    /// its approval check.
+    None,
+}
+
+impl ApprovalSource {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Rule => "rule",
+            Self::Yolo => "yolo",
+            Self::Policy => "policy",
+            Self::User => "user",
+            Self::None => "none",
+        }
+    }
+
+    /// Parse the form written by `as_str`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "rule" => Some(Self::Rule),
+            "yolo" => Some(Self::Yolo),
+            "policy" => Some(Self::Policy),
+            "user" => Some(Self::User),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+/// How a recorded tool call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Succeeded,
+    Failed,
+    /// Approval was refused, so the tool did not run.
+    Denied,
+}
+
+impl AuditOutcome {
+    pub const ALL: [Self; 3] = [Self::Succeeded, Self::Failed, Self::Denied];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Denied => "denied",
+        }
+    }
+
+    /// Parse the form written by `as_str`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "succeeded" => Some(Self::Succeeded),
+            "failed" => Some(Self::Failed),
+            "denied" => Some(Self::Denied),
+            _ => None,
+        }
+    }
+}
+
+/// One audited tool call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolAuditEntry {
+    /// `None` for calls made outside a conversation, and once the
+    /// conversation has been deleted.
+    pub conversation_id: Option<Uuid>,
+    pub tool_name: String,
+    /// The call's JSON arguments.
+    pub arguments: String,
+    pub approval_source: ApprovalSource,
+    /// The deciding rule's text when `approval_source` is `Rule`.
+    pub approval_detail: Option<String>,
+    pub outcome: AuditOutcome,
+    /// Exit code of a `ShellExec` command, when it reported one.
+    pub exit_code: Option<i32>,
+    /// The tool's result text, or its error message.
+    pub output: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+}
+
+/// Which audit records to list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolAuditFilter {
+    /// Case-insensitive text matched against the tool name, arguments,
+    /// output, deciding rule and conversation id.
+    pub search: Option<String>,
+    pub outcome: Option<AuditOutcome>,
+    /// Most records to return, newest first; `None` returns all of them.
+    pub limit: Option<u32>,
+}
+
+/// File format of an audit log export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditExportFormat {
+    Csv,
+    Json,
+}
+
+impl AuditExportFormat {
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Cut `text` to `MAX_AUDIT_TEXT_CHARS` characters, marking the cut.
+#[must_use]
+pub fn truncate_audit_text(text: &str) -> String {
+    match text.char_indices().nth(MAX_AUDIT_TEXT_CHARS) {
+        Some((cut, _)) => format!("{}… [truncated]", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_and_outcomes_round_trip_through_their_text_form() {
+        for source in [
+            ApprovalSource::Rule,
+            ApprovalSource::Yolo,
+            ApprovalSource::Policy,
+            ApprovalSource::User,
+            ApprovalSource::None,
+        ] {
+            assert_eq!(ApprovalSource::parse(source.as_str()), Some(source));
+        }
+        for outcome in AuditOutcome::ALL {
+            assert_eq!(AuditOutcome::parse(outcome.as_str()), Some(outcome));
+        }
+        assert_eq!(AuditOutcome::parse("ok"), None);
+    }
+
+    #[test]
+    fn truncate_audit_text_cuts_on_a_char_boundary() {
+        assert_eq!(truncate_audit_text("short"), "short");
+
+        let long = "é".repeat(MAX_AUDIT_TEXT_CHARS + 5);
+        let cut = truncate_audit_text(&long);
+        assert!(cut.starts_with(&"é".repeat(MAX_AUDIT_TEXT_CHARS)));
+        assert!(cut.ends_with("… [truncated]"));
+    }
+}
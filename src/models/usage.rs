@@ -0,0 +1,182 @@
+//! Token usage and cost accounting for assistant turns.
+//!
+//! Every completed turn records the tokens its provider reported, priced
+//! with the models.dev rates (USD per million tokens) when the model is in
+//! the registry. Totals are summed in the database, so ledger rows are never
+//! rewritten after the fact.
+
+use std::ops::AddAssign;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::registry::Cost;
+
+/// Tokens reported for one assistant turn, summed over every model request
+/// the turn made (tool calls add requests).
+///
+/// `input_tokens` counts uncached prompt tokens; cache reads and writes are
+/// counted separately so they can be priced at their own rates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    #[serde(default)]
+    pub cache_read_tokens: u32,
+    #[serde(default)]
+    pub cache_write_tokens: u32,
+}
+
+impl TokenUsage {
+    /// True when the provider reported no tokens at all.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.input_tokens == 0
+            && self.output_tokens == 0
+            && self.cache_read_tokens == 0
+            && self.cache_write_tokens == 0
+    }
+
+    /// Size of the prompt the provider processed, cached or not.
+    #[must_use]
+    pub const fn prompt_tokens(&self) -> u32 {
+        self.input_tokens
+            .saturating_add(self.cache_read_tokens)
+            .saturating_add(self.cache_write_tokens)
+    }
+
+    /// Price this usage with models.dev rates. Cache reads fall back to the
+    /// input rate when the registry has no cache price; cache writes are
+    /// always billed at the input rate.
+    #[must_use]
+    pub fn cost_usd(&self, pricing: &Cost) -> f64 {
+        let input = f64::from(self.input_tokens) + f64::from(self.cache_write_tokens);
+        let cache_read_rate = pricing.cache_read.unwrap_or(pricing.input);
+        let per_million = f64::from(self.output_tokens).mul_add(
+            pricing.output,
+            f64::from(self.cache_read_tokens).mul_add(cache_read_rate, input * pricing.input),
+        );
+        per_million / 1_000_000.0
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.cache_read_tokens = self
+            .cache_read_tokens
+            .saturating_add(other.cache_read_tokens);
+        self.cache_write_tokens = self
+            .cache_write_tokens
+            .saturating_add(other.cache_write_tokens);
+    }
+}
+
+/// One ledger row: the usage of a single assistant turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// `None` once the conversation has been deleted; spend is kept.
+    pub conversation_id: Option<Uuid>,
+    pub profile_id: Uuid,
+    pub provider_id: String,
+    pub model_id: String,
+    pub usage: TokenUsage,
+    /// `None` when the model has no registry pricing.
+    pub cost_usd: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Usage summed over a set of turns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    /// Sum of the priced turns only.
+    pub cost_usd: f64,
+    pub turns: u64,
+    /// Turns whose model had no pricing; `cost_usd` understates spend when
+    /// this is non-zero.
+    pub unpriced_turns: u64,
+}
+
+impl UsageTotals {
+    /// Every token counted, cached or not.
+    #[must_use]
+    pub const fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+}
+
+/// Totals for one calendar day (UTC).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub totals: UsageTotals,
+}
+
+/// Totals for one model profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileUsage {
+    pub profile_id: Uuid,
+    pub totals: UsageTotals,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pricing(cache_read: Option<f64>) -> Cost {
+        Cost {
+            input: 3.0,
+            output: 15.0,
+            cache_read,
+        }
+    }
+
+    #[test]
+    fn cost_uses_per_million_rates() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_read_tokens: 2_000_000,
+            cache_write_tokens: 0,
+        };
+        let cost = usage.cost_usd(&pricing(Some(0.3)));
+        assert!((cost - (3.0 + 1.5 + 0.6)).abs() < 1e-9, "{cost}");
+    }
+
+    #[test]
+    fn cache_reads_fall_back_to_input_rate() {
+        let usage = TokenUsage {
+            cache_read_tokens: 1_000_000,
+            cache_write_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        let cost = usage.cost_usd(&pricing(None));
+        assert!((cost - 6.0).abs() < 1e-9, "{cost}");
+    }
+
+    #[test]
+    fn usage_accumulates_without_overflow() {
+        let mut usage = TokenUsage {
+            input_tokens: u32::MAX,
+            output_tokens: 1,
+            ..TokenUsage::default()
+        };
+        usage += TokenUsage {
+            input_tokens: 5,
+            output_tokens: 2,
+            cache_read_tokens: 7,
+            cache_write_tokens: 0,
+        };
+        assert_eq!(usage.input_tokens, u32::MAX);
+        assert_eq!(usage.output_tokens, 3);
+        assert_eq!(usage.cache_read_tokens, 7);
+        assert!(!usage.is_empty());
+        assert!(TokenUsage::default().is_empty());
+    }
+}
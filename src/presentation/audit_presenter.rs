@@ -0,0 +1,390 @@
+//! `AuditPresenter` - tool audit log for the settings view
+//!
+//! `AuditPresenter` lists the audit records matching the settings audit
+//! filter, refreshes them when a turn completes, and exports every record
+//! matching the filter as CSV or JSON into the export directory.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc;
+
+use super::conversation_export::{
+    resolve_export_directory, resolve_unique_export_path, write_export_file_retrying_collisions,
+    EXPORT_DIR_SETTING_KEY,
+};
+use super::view_command::ErrorSeverity;
+use super::{Presenter, PresenterError, ViewCommand};
+use crate::events::bus::EventBus;
+use crate::events::{
+    types::{ChatEvent, UserEvent},
+    AppEvent,
+};
+use crate::models::{AuditExportFormat, AuditOutcome, ToolAuditEntry, ToolAuditFilter};
+use crate::services::{AppSettingsService, ToolAuditService};
+
+/// Most records shown in the settings audit panel; exports are not limited.
+const VIEW_LIMIT: u32 = 200;
+
+const EXPORT_TITLE: &str = "Export Audit Log";
+
+const CSV_HEADER: &str = "started_at,conversation_id,tool_name,arguments,approval_source,approval_detail,outcome,exit_code,duration_ms,output";
+
+fn audit_filter(
+    search: &str,
+    outcome: Option<AuditOutcome>,
+    limit: Option<u32>,
+) -> ToolAuditFilter {
+    let search = search.trim();
+    ToolAuditFilter {
+        search: (!search.is_empty()).then(|| search.to_string()),
+        outcome,
+        limit,
+    }
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break.
+///
+/// Tool arguments and output are agent-controlled, so a field a spreadsheet
+/// would run as a formula gets a leading `'`. Plain numbers such as a `-1`
+/// exit code are left alone.
+fn csv_field(value: &str) -> String {
+    let value =
+        if value.starts_with(['=', '+', '-', '@', '\t', '\r']) && value.parse::<f64>().is_err() {
+            format!("'{value}")
+        } else {
+            value.to_string()
+        };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Render audit records as CSV with a header row.
+#[must_use]
+pub fn render_audit_csv(entries: &[ToolAuditEntry]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+    for entry in entries {
+        let fields = [
+            entry.started_at.to_rfc3339(),
+            entry
+                .conversation_id
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            entry.tool_name.clone(),
+            entry.arguments.clone(),
+            entry.approval_source.as_str().to_string(),
+            entry.approval_detail.clone().unwrap_or_default(),
+            entry.outcome.as_str().to_string(),
+            entry
+                .exit_code
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            entry.duration_ms.to_string(),
+            entry.output.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Render audit records as a pretty-printed JSON array.
+///
+/// # Errors
+///
+/// Returns the serialization error if a record cannot be encoded.
+pub fn render_audit_json(entries: &[ToolAuditEntry]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(entries)
+}
+
+/// `AuditPresenter` - tool audit log viewing and export
+pub struct AuditPresenter {
+    /// Reference to event bus for subscribing to events
+    event_bus: Arc<EventBus>,
+
+    /// Tool audit log
+    audit_service: Arc<dyn ToolAuditService>,
+
+    /// Export directory setting
+    app_settings_service: Arc<dyn AppSettingsService>,
+
+    /// View command sender (mpsc for reliable delivery)
+    view_tx: mpsc::Sender<ViewCommand>,
+
+    /// Filter last applied in the settings view, reused for refreshes
+    filter: Arc<Mutex<ToolAuditFilter>>,
+
+    /// Running flag for event loop
+    running: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl AuditPresenter {
+    /// Create a new `AuditPresenter`
+    pub fn new(
+        event_bus: Arc<EventBus>,
+        audit_service: Arc<dyn ToolAuditService>,
+        app_settings_service: Arc<dyn AppSettingsService>,
+        view_tx: mpsc::Sender<ViewCommand>,
+    ) -> Self {
+        Self {
+            event_bus,
+            audit_service,
+            app_settings_service,
+            view_tx,
+            filter: Arc::new(Mutex::new(audit_filter("", None, Some(VIEW_LIMIT)))),
+            running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+
+    /// Start the presenter event loop and emit the most recent records.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PresenterError` if presenter startup becomes fallible in the future.
+    pub async fn start(&mut self) -> Result<(), PresenterError> {
+        if self.running.load(std::sync::atomic::Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        self.running
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let mut rx = self.event_bus.subscribe();
+        let running = self.running.clone();
+        let audit_service = self.audit_service.clone();
+        let app_settings_service = self.app_settings_service.clone();
+        let view_tx = self.view_tx.clone();
+        let filter = self.filter.clone();
+
+        Self::emit_entries(&audit_service, &view_tx, &filter).await;
+
+        tokio::spawn(async move {
+            while running.load(std::sync::atomic::Ordering::Relaxed) {
+                match rx.recv().await {
+                    Ok(event) => {
+                        Self::handle_event(
+                            &audit_service,
+                            &app_settings_service,
+                            &view_tx,
+                            &filter,
+                            event,
+                        )
+                        .await;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("AuditPresenter lagged: {} events missed", n);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                        tracing::info!("AuditPresenter event stream closed");
+                        break;
+                    }
+                }
+            }
+            tracing::info!("AuditPresenter event loop ended");
+        });
+
+        Ok(())
+    }
+
+    /// Stop the presenter event loop
+    ///
+    /// # Errors
+    ///
+    /// Returns `PresenterError` if presenter shutdown becomes fallible in the future.
+    pub async fn stop(&mut self) -> Result<(), PresenterError> {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Check if presenter is running
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    async fn handle_event(
+        audit_service: &Arc<dyn ToolAuditService>,
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        filter: &Arc<Mutex<ToolAuditFilter>>,
+        event: AppEvent,
+    ) {
+        match event {
+            AppEvent::User(UserEvent::FilterToolAudit { search, outcome }) => {
+                *filter.lock().expect("audit filter mutex poisoned") =
+                    audit_filter(&search, outcome, Some(VIEW_LIMIT));
+                Self::emit_entries(audit_service, view_tx, filter).await;
+            }
+            AppEvent::User(UserEvent::ExportToolAudit {
+                search,
+                outcome,
+                format,
+            }) => {
+                let filter = audit_filter(&search, outcome, None);
+                Self::export(audit_service, app_settings_service, view_tx, filter, format).await;
+            }
+            // A turn's tool calls are all recorded before `StreamCompleted`.
+            AppEvent::Chat(ChatEvent::StreamCompleted { .. }) => {
+                Self::emit_entries(audit_service, view_tx, filter).await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn emit_entries(
+        audit_service: &Arc<dyn ToolAuditService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        filter: &Arc<Mutex<ToolAuditFilter>>,
+    ) {
+        let filter = filter.lock().expect("audit filter mutex poisoned").clone();
+        match audit_service.list(filter).await {
+            Ok(entries) => {
+                let _ = view_tx.send(ViewCommand::ToolAuditLoaded { entries }).await;
+            }
+            Err(e) => tracing::warn!("Failed to load tool audit log: {e}"),
+        }
+    }
+
+    async fn export(
+        audit_service: &Arc<dyn ToolAuditService>,
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        filter: ToolAuditFilter,
+        format: AuditExportFormat,
+    ) {
+        let body = match audit_service.list(filter).await {
+            Ok(entries) if entries.is_empty() => {
+                let _ = view_tx
+                    .send(ViewCommand::ShowNotification {
+                        message: "No audit records match the filter".to_string(),
+                    })
+                    .await;
+                return;
+            }
+            Ok(entries) => match format {
+                AuditExportFormat::Csv => Ok(render_audit_csv(&entries)),
+                AuditExportFormat::Json => {
+                    render_audit_json(&entries).map_err(|e| format!("failed to serialize: {e}"))
+                }
+            },
+            Err(e) => Err(format!("failed to load audit log: {e}")),
+        };
+
+        let configured_export_dir = app_settings_service
+            .get_setting(EXPORT_DIR_SETTING_KEY)
+            .await
+            .ok()
+            .flatten();
+        let export_dir = resolve_export_directory(configured_export_dir.as_deref());
+        let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+        let filename = format!("{timestamp}-tool-audit.{}", format.extension());
+
+        let written = body.and_then(|body| {
+            let path = resolve_unique_export_path(&export_dir, &filename);
+            write_export_file_retrying_collisions(path, &body)
+                .map_err(|e| format!("failed to write export file {filename}: {e}"))
+        });
+        let command = match written {
+            Ok(path) => ViewCommand::ShowNotification {
+                message: format!("Audit log exported to {}", path.display()),
+            },
+            Err(message) => ViewCommand::ShowError {
+                title: EXPORT_TITLE.to_string(),
+                message,
+                severity: ErrorSeverity::Error,
+            },
+        };
+        let _ = view_tx.send(command).await;
+    }
+}
+
+impl Presenter for AuditPresenter {
+    fn start(&mut self) -> Result<(), PresenterError> {
+        // Note: This is a sync wrapper - in real usage, call async start() directly
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), PresenterError> {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApprovalSource;
+    use chrono::{TimeZone, Utc};
+
+    fn entry() -> ToolAuditEntry {
+        ToolAuditEntry {
+            conversation_id: None,
+            tool_name: "ShellExec".to_string(),
+            arguments: r#"{"command":"echo \"hi\", there"}"#.to_string(),
+            approval_source: ApprovalSource::Rule,
+            approval_detail: Some("allow tool=ShellExec".to_string()),
+            outcome: AuditOutcome::Succeeded,
+            exit_code: Some(0),
+            output: "hi\nthere".to_string(),
+            started_at: Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap(),
+            duration_ms: 42,
+        }
+    }
+
+    #[test]
+    fn csv_export_quotes_fields_with_separators() {
+        let csv = render_audit_csv(&[entry()]);
+        let mut lines = csv.lines();
+
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert_eq!(
+            csv.split_once('\n').map(|(_, rows)| rows),
+            Some(
+                "2026-03-01T09:30:00+00:00,,ShellExec,\"{\"\"command\"\":\"\"echo \\\"\"hi\\\"\", there\"\"}\",rule,allow tool=ShellExec,succeeded,0,42,\"hi\nthere\"\n"
+            )
+        );
+    }
+
+    #[test]
+    fn csv_export_defuses_formulas() {
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("+1+1"), "'+1+1");
+        assert_eq!(csv_field("-2+3,x"), "\"'-2+3,x\"");
+        assert_eq!(csv_field("-1"), "-1");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+
+    #[test]
+    fn json_export_round_trips() {
+        let json = render_audit_json(&[entry()]).expect("serializes");
+        let parsed: Vec<ToolAuditEntry> = serde_json::from_str(&json).expect("parses");
+
+        assert_eq!(parsed, vec![entry()]);
+        assert!(json.contains("\"approval_source\": \"rule\""));
+    }
+
+    #[test]
+    fn blank_searches_are_dropped_from_the_filter() {
+        assert_eq!(audit_filter("  ", None, Some(5)).search, None);
+        assert_eq!(
+            audit_filter(" git ", Some(AuditOutcome::Denied), None),
+            ToolAuditFilter {
+                search: Some("git".to_string()),
+                outcome: Some(AuditOutcome::Denied),
+                limit: None,
+            }
+        );
+    }
+}
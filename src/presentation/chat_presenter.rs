@@ -5,16 +5,19 @@ use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
 
 use super::view_command::{
-    ConversationMessagePayload, ConversationSearchResult, ConversationSummary, ErrorSeverity,
-    MessageRole,
+    ConversationMessagePayload, ConversationSummary, ErrorSeverity, MessageRole,
 };
 use super::{Presenter, PresenterError, ViewCommand};
+use crate::agent::checkpoints::CheckpointStore;
 use crate::events::bus::EventBus;
-use crate::events::{types::ConversationEvent, AppEvent};
+use crate::events::{
+    types::{ChatEvent, ConversationEvent},
+    AppEvent,
+};
 
-use crate::models::ConversationExportFormat;
+use crate::models::{ConversationExportFormat, ConversationFilter, Message};
 use crate::services::{
-    AppSettingsService, ChatService, ConversationService, ProfileService, ServiceError,
+    AppSettingsService, ChatService, ConversationService, ProfileService, ServiceError, TodoService,
 };
 
 type PendingDraftConversation = Arc<std::sync::Mutex<Option<Uuid>>>;
@@ -30,6 +33,8 @@ pub(super) struct ChatPresenterState<'a> {
     pub(super) app_settings_service: &'a Arc<dyn AppSettingsService>,
     pub(super) current_export_format: &'a Arc<std::sync::Mutex<ConversationExportFormat>>,
     pub(super) pending_draft_conversation_id: &'a PendingDraftConversation,
+    pub(super) checkpoint_store: Option<&'a Arc<CheckpointStore>>,
+    pub(super) todo_service: Option<&'a Arc<dyn TodoService>>,
 }
 
 pub struct ChatPresenter {
@@ -41,6 +46,8 @@ pub struct ChatPresenter {
     view_tx: mpsc::Sender<ViewCommand>,
     running: Arc<std::sync::atomic::AtomicBool>,
     pending_draft_conversation_id: PendingDraftConversation,
+    checkpoint_store: Option<Arc<CheckpointStore>>,
+    todo_service: Option<Arc<dyn TodoService>>,
 
     current_export_format: Arc<std::sync::Mutex<crate::models::ConversationExportFormat>>,
 }
@@ -66,8 +73,25 @@ impl ChatPresenter {
                 ConversationExportFormat::default(),
             )),
             pending_draft_conversation_id: Arc::new(std::sync::Mutex::new(None)),
+            checkpoint_store: None,
+            todo_service: None,
         }
     }
+
+    /// List and revert the file checkpoints kept in `store`.
+    #[must_use]
+    pub fn with_checkpoint_store(mut self, store: Arc<CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
+    /// Load each selected conversation's `TodoWrite` task list from `todo_service`.
+    #[must_use]
+    pub fn with_todo_service(mut self, todo_service: Arc<dyn TodoService>) -> Self {
+        self.todo_service = Some(todo_service);
+        self
+    }
+
     /// Start the presenter event loop.
     ///
     /// # Errors
@@ -123,7 +147,28 @@ impl ChatPresenter {
 
             AppEvent::Chat(chat_evt) => {
                 tracing::info!("ChatPresenter handling ChatEvent: {:?}", chat_evt);
+                let completed = match &chat_evt {
+                    ChatEvent::StreamCompleted {
+                        conversation_id, ..
+                    } => Some(*conversation_id),
+                    _ => None,
+                };
                 Self::handle_chat_event(view_tx, chat_evt).await;
+                if let Some(conversation_id) = completed {
+                    Self::emit_message_versions(
+                        deps.conversation_service,
+                        view_tx,
+                        conversation_id,
+                    )
+                    .await;
+                    Self::emit_turn_checkpoints(
+                        state.checkpoint_store,
+                        deps.conversation_service,
+                        view_tx,
+                        conversation_id,
+                    )
+                    .await;
+                }
             }
             AppEvent::Conversation(conv_evt) => {
                 Self::handle_conversation_event(view_tx, conv_evt).await;
@@ -152,48 +197,6 @@ impl ChatPresenter {
         }
     }
 
-    pub(super) async fn handle_search_conversations(
-        conversation_service: &Arc<dyn ConversationService>,
-        view_tx: &mpsc::Sender<ViewCommand>,
-        query: String,
-    ) {
-        if query.trim().is_empty() {
-            let _ = view_tx
-                .send(ViewCommand::ConversationSearchResults { results: vec![] })
-                .await;
-            return;
-        }
-        match conversation_service.search(&query, Some(50), None).await {
-            Ok(results) => {
-                let view_results: Vec<ConversationSearchResult> = results
-                    .into_iter()
-                    .map(|r| ConversationSearchResult {
-                        id: r.conversation_id,
-                        title: r.title,
-                        is_title_match: matches!(
-                            r.match_type,
-                            crate::models::SearchMatchType::Title
-                        ),
-                        match_context: r.match_context,
-                        message_count: r.message_count,
-                        updated_at: r.updated_at,
-                    })
-                    .collect();
-                let _ = view_tx
-                    .send(ViewCommand::ConversationSearchResults {
-                        results: view_results,
-                    })
-                    .await;
-            }
-            Err(e) => {
-                tracing::warn!("Conversation search failed: {e}");
-                let _ = view_tx
-                    .send(ViewCommand::ConversationSearchResults { results: vec![] })
-                    .await;
-            }
-        }
-    }
-
     async fn emit_initial_conversation_list(
         conversation_service: &Arc<dyn ConversationService>,
         view_tx: &mpsc::Sender<ViewCommand>,
@@ -214,7 +217,10 @@ impl ChatPresenter {
         conversation_service: &Arc<dyn ConversationService>,
         view_tx: &mpsc::Sender<ViewCommand>,
     ) {
-        match conversation_service.list_metadata(Some(1), Some(0)).await {
+        match conversation_service
+            .list_metadata(&ConversationFilter::default(), Some(1), Some(0))
+            .await
+        {
             Ok(conversations) => {
                 if let Some(conversation) = conversations.first() {
                     Self::activate_startup_conversation(
@@ -283,6 +289,8 @@ impl ChatPresenter {
         let app_settings_service = self.app_settings_service.clone();
         let current_export_format = self.current_export_format.clone();
         let pending_draft_conversation_id = self.pending_draft_conversation_id.clone();
+        let checkpoint_store = self.checkpoint_store.clone();
+        let todo_service = self.todo_service.clone();
 
         let mut view_tx = self.view_tx.clone();
 
@@ -299,6 +307,8 @@ impl ChatPresenter {
                             app_settings_service: &app_settings_service,
                             current_export_format: &current_export_format,
                             pending_draft_conversation_id: &pending_draft_conversation_id,
+                            checkpoint_store: checkpoint_store.as_ref(),
+                            todo_service: todo_service.as_ref(),
                         };
                         Self::handle_event(&deps, &state, &mut view_tx, event).await;
                     }
@@ -319,7 +329,10 @@ impl ChatPresenter {
         conversation_service: &Arc<dyn ConversationService>,
         view_tx: &mut mpsc::Sender<ViewCommand>,
     ) -> Result<(), ServiceError> {
-        let conversations = conversation_service.list_metadata(None, None).await?;
+        // Archived conversations are included; the views hide them by default.
+        let conversations = conversation_service
+            .list_metadata(&ConversationFilter::all(), None, None)
+            .await?;
         let summaries = conversations
             .into_iter()
             .map(|metadata| ConversationSummary {
@@ -331,6 +344,8 @@ impl ChatPresenter {
                 updated_at: metadata.updated_at,
                 message_count: metadata.message_count,
                 preview: metadata.last_message_preview,
+                parent_id: metadata.parent_id,
+                organization: metadata.organization,
             })
             .collect();
 
@@ -351,7 +366,24 @@ impl ChatPresenter {
     ) -> Result<usize, ServiceError> {
         let messages = conversation_service.get_messages(conversation_id).await?;
         let replay_count = messages.len();
-        let loaded_messages = messages
+        let loaded_messages = Self::conversation_message_payloads(messages);
+
+        let _ = view_tx
+            .send(ViewCommand::ConversationMessagesLoaded {
+                conversation_id,
+                selection_generation,
+                messages: loaded_messages,
+            })
+            .await;
+
+        Ok(replay_count)
+    }
+
+    /// Convert stored messages into view payloads, dropping system messages.
+    pub(super) fn conversation_message_payloads(
+        messages: Vec<Message>,
+    ) -> Vec<ConversationMessagePayload> {
+        messages
             .into_iter()
             .filter_map(|message| {
                 let role = match message.role {
@@ -368,17 +400,7 @@ impl ChatPresenter {
                     model_id: message.model_id,
                 })
             })
-            .collect::<Vec<_>>();
-
-        let _ = view_tx
-            .send(ViewCommand::ConversationMessagesLoaded {
-                conversation_id,
-                selection_generation,
-                messages: loaded_messages,
-            })
-            .await;
-
-        Ok(replay_count)
+            .collect()
     }
 
     /// Handle `SendMessage` user event
@@ -691,6 +713,8 @@ impl ChatPresenter {
                             count,
                             "ChatPresenter: replaying selected conversation messages"
                         );
+                        Self::emit_conversation_branches(conversation_service, view_tx, id).await;
+                        Self::emit_message_versions(conversation_service, view_tx, id).await;
                     }
                     Err(e) => {
                         let error_msg =
@@ -787,7 +811,10 @@ impl ChatPresenter {
             let pending_draft_id =
                 Self::pending_draft_conversation_id(pending_draft_conversation_id);
             if pending_draft_id == Some(id) {
-                let Ok(metadata) = conversation_service.list_metadata(None, None).await else {
+                let Ok(metadata) = conversation_service
+                    .list_metadata(&ConversationFilter::all(), None, None)
+                    .await
+                else {
                     return Err(Box::new(ServiceError::Internal(
                         "Failed to list conversations for pending draft validation".to_string(),
                     )));
@@ -0,0 +1,185 @@
+//! Conversation branching for `ChatPresenter`.
+//!
+//! The chat view addresses messages by their visible index (system messages
+//! are never shown), while the conversation service forks by stored `seq`.
+//! Helpers here translate between the two and keep the view's branch strip
+//! in sync with the selected conversation's branch tree.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::view_command::{ConversationBranchSummary, ErrorSeverity};
+use super::{ChatPresenter, ViewCommand};
+use crate::models::{ConversationBranch, Message, MessageRole};
+use crate::services::ConversationService;
+
+/// Map a visible (non-system) message index to its stored `seq`.
+pub(super) fn seq_for_visible_index(messages: &[Message], index: usize) -> Option<usize> {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| message.role != MessageRole::System)
+        .nth(index)
+        .map(|(seq, _)| seq)
+}
+
+/// Map a stored `seq` back to the visible index it occupies.
+pub(super) fn visible_index_for_seq(messages: &[Message], seq: usize) -> Option<usize> {
+    messages
+        .iter()
+        .take(seq + 1)
+        .filter(|message| message.role != MessageRole::System)
+        .count()
+        .checked_sub(1)
+}
+
+fn branch_summary(
+    branch: ConversationBranch,
+    parent_messages: Option<&[Message]>,
+) -> ConversationBranchSummary {
+    let forked_at_index = match (branch.parent_seq, parent_messages) {
+        (Some(seq), Some(messages)) => visible_index_for_seq(messages, seq),
+        (Some(seq), None) => Some(seq),
+        (None, _) => None,
+    };
+    ConversationBranchSummary {
+        id: branch.id,
+        title: branch
+            .title
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| "Untitled Conversation".to_string()),
+        parent_id: branch.parent_id,
+        forked_at_index,
+        message_count: branch.message_count,
+    }
+}
+
+impl ChatPresenter {
+    /// Fork `conversation_id` at the visible message `message_index` and
+    /// switch the view to the new branch.
+    pub(super) async fn handle_branch_conversation(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        message_index: usize,
+    ) {
+        let result = async {
+            let messages = conversation_service.get_messages(conversation_id).await?;
+            let seq = seq_for_visible_index(&messages, message_index).ok_or_else(|| {
+                crate::services::ServiceError::NotFound(format!(
+                    "no message at index {message_index}"
+                ))
+            })?;
+            conversation_service.fork_at(conversation_id, seq).await
+        }
+        .await;
+
+        match result {
+            Ok(branch) => {
+                tracing::info!(
+                    source_id = %conversation_id,
+                    branch_id = %branch.id,
+                    message_index,
+                    "ChatPresenter: branched conversation"
+                );
+                let _ = Self::emit_conversation_list(conversation_service, view_tx).await;
+                let _ = view_tx
+                    .send(ViewCommand::ConversationBranched {
+                        source_id: conversation_id,
+                        branch_id: branch.id,
+                    })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to branch conversation {}: {}", conversation_id, e);
+                let _ = view_tx
+                    .send(ViewCommand::ShowError {
+                        title: "Branch Conversation".to_string(),
+                        message: format!("Failed to branch conversation: {e}"),
+                        severity: ErrorSeverity::Error,
+                    })
+                    .await;
+            }
+        }
+    }
+
+    /// Send the branch tree containing `conversation_id` to the view.
+    ///
+    /// Fork points are reported as visible indices in each branch's parent,
+    /// so the view can mark them without knowing about stored `seq` values.
+    pub(super) async fn emit_conversation_branches(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+    ) {
+        let branches = match conversation_service.list_branches(conversation_id).await {
+            Ok(branches) if !branches.is_empty() => branches,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::debug!("Failed to list branches for {}: {}", conversation_id, e);
+                return;
+            }
+        };
+
+        let mut parent_messages: HashMap<Uuid, Vec<Message>> = HashMap::new();
+        for parent_id in branches.iter().filter_map(|branch| branch.parent_id) {
+            if parent_messages.contains_key(&parent_id) {
+                continue;
+            }
+            if let Ok(messages) = conversation_service.get_messages(parent_id).await {
+                parent_messages.insert(parent_id, messages);
+            }
+        }
+
+        let branches = branches
+            .into_iter()
+            .map(|branch| {
+                let messages = branch
+                    .parent_id
+                    .and_then(|parent_id| parent_messages.get(&parent_id))
+                    .map(Vec::as_slice);
+                branch_summary(branch, messages)
+            })
+            .collect();
+
+        let _ = view_tx
+            .send(ViewCommand::ConversationBranchesLoaded {
+                conversation_id,
+                branches,
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> Vec<Message> {
+        vec![
+            Message::system("be terse".to_string()),
+            Message::user("hi".to_string()),
+            Message::assistant("hello".to_string()),
+            Message::user("again".to_string()),
+        ]
+    }
+
+    #[test]
+    fn visible_index_skips_system_messages() {
+        let messages = transcript();
+        assert_eq!(seq_for_visible_index(&messages, 0), Some(1));
+        assert_eq!(seq_for_visible_index(&messages, 2), Some(3));
+        assert_eq!(seq_for_visible_index(&messages, 3), None);
+    }
+
+    #[test]
+    fn seq_maps_back_to_visible_index() {
+        let messages = transcript();
+        assert_eq!(visible_index_for_seq(&messages, 1), Some(0));
+        assert_eq!(visible_index_for_seq(&messages, 3), Some(2));
+        assert_eq!(visible_index_for_seq(&messages, 0), None);
+    }
+}
@@ -63,6 +63,33 @@ impl ChatService for RecordingChatService {
     ) -> Result<(), crate::services::ServiceError> {
         Ok(())
     }
+
+    async fn regenerate(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = crate::services::ChatStreamEvent> + Send + Unpin>,
+        crate::services::ServiceError,
+    > {
+        Err(crate::services::ServiceError::Internal(
+            "regenerate not supported by mock".to_string(),
+        ))
+    }
+
+    async fn edit_and_resend(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _content: String,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = crate::services::ChatStreamEvent> + Send + Unpin>,
+        crate::services::ServiceError,
+    > {
+        Err(crate::services::ServiceError::Internal(
+            "edit and resend not supported by mock".to_string(),
+        ))
+    }
 }
 
 /// Test that `StopStreaming` event forwards the `conversation_id` to `cancel()`.
@@ -100,6 +127,8 @@ async fn handle_stop_streaming_forwards_conversation_id() {
         app_settings_service: &app_settings_service,
         current_export_format: &current_export_format,
         pending_draft_conversation_id: &pending_draft_conversation_id,
+        checkpoint_store: None,
+        todo_service: None,
     };
     ChatPresenter::handle_user_event(&deps, &state, &mut view_tx.clone(), event).await;
 
@@ -148,6 +177,8 @@ async fn handle_stop_streaming_does_not_cancel_other_conversations() {
         app_settings_service: &app_settings_service,
         current_export_format: &current_export_format,
         pending_draft_conversation_id: &pending_draft_conversation_id,
+        checkpoint_store: None,
+        todo_service: None,
     };
     ChatPresenter::handle_user_event(&deps, &state, &mut view_tx.clone(), event).await;
 
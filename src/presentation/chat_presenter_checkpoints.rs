@@ -0,0 +1,140 @@
+//! File checkpoint listing and revert for `ChatPresenter`.
+//!
+//! Checkpoints are stored per turn with the `seq` of the reply the turn
+//! produced; they are reported to the view by visible message index, like
+//! message versions. A turn whose reply was never stored (the stream failed
+//! after a tool wrote) is anchored at the last visible message instead.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::chat_presenter_branch::visible_index_for_seq;
+use super::view_command::{ErrorSeverity, TurnCheckpointSummary};
+use super::{ChatPresenter, ViewCommand};
+use crate::agent::checkpoints::CheckpointStore;
+use crate::models::Message;
+use crate::services::{ConversationService, ServiceResult};
+
+/// Visible index a turn's checkpoint is shown under.
+fn anchor_index(messages: &[Message], message_seq: usize) -> Option<usize> {
+    let seq = message_seq.min(messages.len().checked_sub(1)?);
+    visible_index_for_seq(messages, seq)
+}
+
+impl ChatPresenter {
+    pub(super) async fn emit_turn_checkpoints(
+        checkpoint_store: Option<&Arc<CheckpointStore>>,
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+    ) {
+        let Some(store) = checkpoint_store else {
+            return;
+        };
+        let turns = match store.list_turns(conversation_id).await {
+            Ok(turns) => turns,
+            Err(e) => {
+                tracing::debug!(
+                    "Failed to list file checkpoints for {}: {}",
+                    conversation_id,
+                    e
+                );
+                return;
+            }
+        };
+        let Ok(messages) = conversation_service.get_messages(conversation_id).await else {
+            return;
+        };
+
+        let checkpoints = turns
+            .into_iter()
+            .filter_map(|turn| {
+                Some(TurnCheckpointSummary {
+                    message_index: anchor_index(&messages, turn.message_seq)?,
+                    turn_id: turn.turn_id,
+                    files: turn
+                        .files
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect(),
+                })
+            })
+            .collect();
+        let _ = view_tx
+            .send(ViewCommand::TurnCheckpointsLoaded {
+                conversation_id,
+                checkpoints,
+            })
+            .await;
+    }
+
+    /// Restore the files of a turn, or just `path`, from their checkpoint.
+    pub(super) async fn handle_revert_turn_files(
+        checkpoint_store: Option<&Arc<CheckpointStore>>,
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        turn_id: Uuid,
+        path: Option<&str>,
+    ) {
+        let Some(store) = checkpoint_store else {
+            return;
+        };
+        let result: ServiceResult<usize> = match path {
+            Some(path) => store
+                .revert_file(conversation_id, turn_id, Path::new(path))
+                .await
+                .map(|()| 1),
+            None => store
+                .revert_turn(conversation_id, turn_id)
+                .await
+                .map(|restored| restored.len()),
+        };
+
+        match result {
+            Ok(count) => {
+                let noun = if count == 1 { "file" } else { "files" };
+                let _ = view_tx
+                    .send(ViewCommand::ShowNotification {
+                        message: format!("Restored {count} {noun}"),
+                    })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to revert turn {} files: {}", turn_id, e);
+                let _ = view_tx
+                    .send(ViewCommand::ShowError {
+                        title: "Revert Failed".to_string(),
+                        message: format!("Failed to restore files: {e}"),
+                        severity: ErrorSeverity::Error,
+                    })
+                    .await;
+            }
+        }
+
+        Self::emit_turn_checkpoints(Some(store), conversation_service, view_tx, conversation_id)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsaved_reply_anchors_at_last_visible_message() {
+        let messages = vec![
+            Message::system("system".to_string()),
+            Message::user("write it".to_string()),
+            Message::assistant("done".to_string()),
+            Message::user("again".to_string()),
+        ];
+
+        assert_eq!(anchor_index(&messages, 2), Some(1));
+        assert_eq!(anchor_index(&messages, 4), Some(2));
+        assert_eq!(anchor_index(&[], 0), None);
+    }
+}
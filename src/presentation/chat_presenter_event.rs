@@ -2,9 +2,10 @@ use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use super::chat_presenter::{ChatPresenter, ChatPresenterDeps, ChatPresenterState};
+use super::chat_presenter_organize::OrganizationChange;
 use super::ViewCommand;
 use crate::events::types::{ToolApprovalResponseAction, UserEvent};
-use crate::models::ConversationExportFormat;
+use crate::models::{ConversationArchiveFormat, ConversationExportFormat};
 
 impl ChatPresenter {
     /// Handle user events
@@ -47,6 +48,102 @@ impl ChatPresenter {
             } => {
                 Self::handle_select_conversation_for_event(deps, view_tx, id, selection_generation)
                     .await;
+                Self::emit_turn_checkpoints(
+                    state.checkpoint_store,
+                    deps.conversation_service,
+                    view_tx,
+                    id,
+                )
+                .await;
+                Self::emit_todo_list(state.todo_service, view_tx, id).await;
+            }
+            UserEvent::BranchConversation {
+                conversation_id,
+                message_index,
+            } => {
+                Self::handle_branch_conversation(
+                    deps.conversation_service,
+                    view_tx,
+                    conversation_id,
+                    message_index,
+                )
+                .await;
+            }
+            UserEvent::RegenerateResponse {
+                conversation_id,
+                message_index,
+            } => {
+                Self::handle_regenerate_response(
+                    deps.conversation_service,
+                    deps.chat_service,
+                    view_tx,
+                    conversation_id,
+                    message_index,
+                )
+                .await;
+            }
+            UserEvent::EditAndResend {
+                conversation_id,
+                message_index,
+                content,
+            } => {
+                Self::handle_edit_and_resend(
+                    deps.conversation_service,
+                    deps.chat_service,
+                    view_tx,
+                    conversation_id,
+                    message_index,
+                    content,
+                )
+                .await;
+            }
+            UserEvent::SwitchMessageVersion {
+                conversation_id,
+                message_index,
+                version,
+            } => {
+                Self::handle_switch_message_version(
+                    deps.conversation_service,
+                    view_tx,
+                    conversation_id,
+                    message_index,
+                    version,
+                )
+                .await;
+            }
+            UserEvent::RevertTurnFiles {
+                conversation_id,
+                turn_id,
+                path,
+            } => {
+                Self::handle_revert_turn_files(
+                    state.checkpoint_store,
+                    deps.conversation_service,
+                    view_tx,
+                    conversation_id,
+                    turn_id,
+                    path.as_deref(),
+                )
+                .await;
+            }
+            UserEvent::KillBackgroundProcess {
+                conversation_id,
+                process_id,
+            } => {
+                if let Err(error) = crate::agent::processes::BackgroundProcesses::global()
+                    .kill(conversation_id, &process_id)
+                {
+                    tracing::warn!("Failed to kill background process {process_id}: {error}");
+                }
+            }
+            UserEvent::RefreshMcpCatalog => {
+                Self::emit_mcp_catalog(view_tx).await;
+            }
+            UserEvent::AttachMcpResource { server, uri } => {
+                Self::attach_mcp_resource(view_tx, server, uri);
+            }
+            UserEvent::InsertMcpPrompt { invocation } => {
+                Self::insert_mcp_prompt(view_tx, invocation);
             }
             UserEvent::RefreshHistory | UserEvent::RefreshConversations => {
                 let _ = Self::emit_conversation_list(deps.conversation_service, view_tx).await;
@@ -57,6 +154,10 @@ impl ChatPresenter {
             UserEvent::SaveConversation => {
                 Self::handle_save_conversation_for_event(deps, state, view_tx).await;
             }
+            UserEvent::ExportConversations { ids, archive } => {
+                Self::handle_export_conversations_for_event(deps, state, view_tx, ids, archive)
+                    .await;
+            }
             UserEvent::SaveErrorLog { format } => {
                 Self::handle_save_error_log(state.app_settings_service, view_tx, format).await;
             }
@@ -76,8 +177,46 @@ impl ChatPresenter {
             UserEvent::ToggleWindowMode => {
                 let _ = view_tx.send(ViewCommand::ToggleWindowMode).await;
             }
-            UserEvent::SearchConversations { query } => {
-                Self::handle_search_conversations(deps.conversation_service, view_tx, query).await;
+            UserEvent::SearchConversations { query, filter } => {
+                let query = Self::resolve_search_profile(deps.profile_service, query).await;
+                Self::handle_search_conversations(
+                    deps.conversation_service,
+                    view_tx,
+                    query,
+                    filter,
+                )
+                .await;
+            }
+            UserEvent::SetConversationPinned { id, pinned } => {
+                Self::handle_organize_for_event(
+                    deps,
+                    view_tx,
+                    id,
+                    OrganizationChange::Pinned(pinned),
+                )
+                .await;
+            }
+            UserEvent::SetConversationArchived { id, archived } => {
+                Self::handle_organize_for_event(
+                    deps,
+                    view_tx,
+                    id,
+                    OrganizationChange::Archived(archived),
+                )
+                .await;
+            }
+            UserEvent::SetConversationFolder { id, folder } => {
+                Self::handle_organize_for_event(
+                    deps,
+                    view_tx,
+                    id,
+                    OrganizationChange::Folder(folder),
+                )
+                .await;
+            }
+            UserEvent::SetConversationTags { id, tags } => {
+                Self::handle_organize_for_event(deps, view_tx, id, OrganizationChange::Tags(tags))
+                    .await;
             }
             _ => {}
         }
@@ -125,6 +264,15 @@ impl ChatPresenter {
         Self::handle_rename_conversation(deps.conversation_service, view_tx, id, title).await;
     }
 
+    async fn handle_organize_for_event(
+        deps: &ChatPresenterDeps<'_>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        id: Uuid,
+        change: OrganizationChange,
+    ) {
+        Self::handle_organize_conversation(deps.conversation_service, view_tx, id, change).await;
+    }
+
     async fn handle_select_conversation_for_event(
         deps: &ChatPresenterDeps<'_>,
         view_tx: &mut mpsc::Sender<ViewCommand>,
@@ -168,6 +316,24 @@ impl ChatPresenter {
         .await;
     }
 
+    async fn handle_export_conversations_for_event(
+        deps: &ChatPresenterDeps<'_>,
+        state: &ChatPresenterState<'_>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        ids: Option<Vec<Uuid>>,
+        archive: ConversationArchiveFormat,
+    ) {
+        Self::handle_export_conversations(
+            deps.conversation_service,
+            state.app_settings_service,
+            state.current_export_format,
+            view_tx,
+            ids,
+            archive,
+        )
+        .await;
+    }
+
     async fn handle_tool_approval_response_for_event(
         deps: &ChatPresenterDeps<'_>,
         view_tx: &mpsc::Sender<ViewCommand>,
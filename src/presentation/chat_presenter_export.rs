@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use tokio::sync::mpsc;
+use uuid::Uuid;
 
+use super::conversation_archive::{build_archive_filename, render_archive};
 use super::conversation_export::{
     build_export_filename, render_export_content, resolve_export_directory,
     resolve_unique_export_path, validate_export_directory, write_export_file_retrying_collisions,
@@ -9,7 +11,7 @@ use super::conversation_export::{
 };
 use super::view_command::ErrorSeverity;
 use super::{ChatPresenter, ViewCommand};
-use crate::models::ConversationExportFormat;
+use crate::models::{ConversationArchiveFormat, ConversationExportFormat, ConversationFilter};
 use crate::services::{AppSettingsService, ConversationService};
 use crate::ui_gpui::error_log::{render_error_log_json, render_error_log_text, ErrorLogStore};
 
@@ -176,6 +178,86 @@ impl ChatPresenter {
             .await;
     }
 
+    /// Load the requested conversations, or all of them, and write them into
+    /// one archive in the export directory.
+    async fn write_conversation_archive(
+        conversation_service: &Arc<dyn ConversationService>,
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        format: ConversationExportFormat,
+        ids: Option<Vec<Uuid>>,
+        archive: ConversationArchiveFormat,
+    ) -> Result<(std::path::PathBuf, usize), String> {
+        let ids = match ids {
+            Some(ids) => ids,
+            None => conversation_service
+                .list_metadata(&ConversationFilter::all(), None, None)
+                .await
+                .map_err(|error| format!("failed to list conversations: {error}"))?
+                .into_iter()
+                .map(|metadata| metadata.id)
+                .collect(),
+        };
+        if ids.is_empty() {
+            return Err("no conversations to export".to_string());
+        }
+
+        let mut conversations = Vec::with_capacity(ids.len());
+        for id in ids {
+            conversations.push(
+                conversation_service
+                    .load(id)
+                    .await
+                    .map_err(|error| format!("failed to load conversation {id}: {error}"))?,
+            );
+        }
+
+        let exported_at = chrono::Utc::now();
+        let body = render_archive(&conversations, archive, format, exported_at)?;
+        let configured_export_dir = app_settings_service
+            .get_setting(EXPORT_DIR_SETTING_KEY)
+            .await
+            .ok()
+            .flatten();
+        let export_dir = resolve_export_directory(configured_export_dir.as_deref());
+        let filename = build_archive_filename(conversations.len(), archive, exported_at);
+        let initial_path = resolve_unique_export_path(&export_dir, &filename);
+        let path = write_export_file_retrying_collisions(initial_path, &body)
+            .map_err(|error| format!("failed to write archive {filename}: {error}"))?;
+        Ok((path, conversations.len()))
+    }
+
+    pub(crate) async fn handle_export_conversations(
+        conversation_service: &Arc<dyn ConversationService>,
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        current_export_format: &Arc<std::sync::Mutex<ConversationExportFormat>>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        ids: Option<Vec<Uuid>>,
+        archive: ConversationArchiveFormat,
+    ) {
+        let format = *current_export_format
+            .lock()
+            .expect("export format mutex poisoned");
+        let command = match Self::write_conversation_archive(
+            conversation_service,
+            app_settings_service,
+            format,
+            ids,
+            archive,
+        )
+        .await
+        {
+            Ok((path, count)) => ViewCommand::ConversationArchiveExported {
+                path: path.display().to_string(),
+                count,
+            },
+            Err(error) => {
+                tracing::warn!("Conversation archive export failed: {error}");
+                ViewCommand::ConversationArchiveFailed { error }
+            }
+        };
+        let _ = view_tx.send(command).await;
+    }
+
     pub(crate) async fn handle_save_error_log(
         app_settings_service: &Arc<dyn AppSettingsService>,
         view_tx: &mut mpsc::Sender<ViewCommand>,
@@ -205,9 +287,9 @@ impl ChatPresenter {
                     return;
                 }
             },
-            ConversationExportFormat::Txt | ConversationExportFormat::Md => {
-                render_error_log_text(&entries)
-            }
+            ConversationExportFormat::Txt
+            | ConversationExportFormat::Md
+            | ConversationExportFormat::Html => render_error_log_text(&entries),
         };
 
         let configured_export_dir = app_settings_service
@@ -219,7 +301,9 @@ impl ChatPresenter {
         let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
         let extension = match format {
             ConversationExportFormat::Json => "json",
-            ConversationExportFormat::Txt | ConversationExportFormat::Md => "txt",
+            ConversationExportFormat::Txt
+            | ConversationExportFormat::Md
+            | ConversationExportFormat::Html => "txt",
         };
         let filename = format!("{timestamp}-error-log.{extension}");
         let initial_path = resolve_unique_export_path(&export_dir, &filename);
@@ -0,0 +1,70 @@
+//! MCP resources and prompts for the chat composer.
+//!
+//! The picker asks for the catalog each time it opens, since servers come
+//! and go. Reading a resource or rendering a prompt can take as long as a
+//! tool call, so both run off the event loop and answer with the text to
+//! insert.
+
+use tokio::sync::mpsc;
+
+use super::{ChatPresenter, ViewCommand};
+use crate::mcp::catalog::resource_attachment;
+use crate::mcp::{McpPromptInvocation, McpService};
+
+impl ChatPresenter {
+    pub(super) async fn emit_mcp_catalog(view_tx: &mut mpsc::Sender<ViewCommand>) {
+        let (resources, prompts) = {
+            let service = McpService::global();
+            let service = service.lock().await;
+            (service.get_resources(), service.get_prompts())
+        };
+        let _ = view_tx
+            .send(ViewCommand::McpCatalogLoaded { resources, prompts })
+            .await;
+    }
+
+    pub(super) fn attach_mcp_resource(
+        view_tx: &mpsc::Sender<ViewCommand>,
+        server: String,
+        uri: String,
+    ) {
+        let view_tx = view_tx.clone();
+        tokio::spawn(async move {
+            let read = McpService::global()
+                .lock()
+                .await
+                .read_resource(&server, &uri)
+                .await;
+            let command = match read {
+                Ok(text) => ViewCommand::McpComposerTextReady {
+                    text: resource_attachment(&server, &uri, &text),
+                },
+                Err(error) => ViewCommand::McpComposerTextFailed {
+                    error: format!("Could not read {uri}: {error}"),
+                },
+            };
+            let _ = view_tx.send(command).await;
+        });
+    }
+
+    pub(super) fn insert_mcp_prompt(
+        view_tx: &mpsc::Sender<ViewCommand>,
+        invocation: McpPromptInvocation,
+    ) {
+        let view_tx = view_tx.clone();
+        tokio::spawn(async move {
+            let rendered = McpService::global()
+                .lock()
+                .await
+                .render_prompt(&invocation)
+                .await;
+            let command = match rendered {
+                Ok(text) => ViewCommand::McpComposerTextReady { text },
+                Err(error) => ViewCommand::McpComposerTextFailed {
+                    error: format!("Could not insert {}: {error}", invocation.name),
+                },
+            };
+            let _ = view_tx.send(command).await;
+        });
+    }
+}
@@ -0,0 +1,158 @@
+//! Conversation pinning, archive, folders, and tags for `ChatPresenter`.
+//!
+//! Every change is persisted through `ConversationService` and followed by a
+//! full `ConversationListRefreshed`, since pinning or archiving can move a
+//! conversation between the groups the views render.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::view_command::ErrorSeverity;
+use super::{ChatPresenter, ViewCommand};
+use crate::services::{ConversationService, ServiceResult};
+
+/// One organization change requested by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum OrganizationChange {
+    Pinned(bool),
+    Archived(bool),
+    Folder(Option<String>),
+    Tags(Vec<String>),
+}
+
+impl OrganizationChange {
+    async fn apply(
+        self,
+        conversation_service: &Arc<dyn ConversationService>,
+        id: Uuid,
+    ) -> ServiceResult<()> {
+        match self {
+            Self::Pinned(pinned) => conversation_service.set_pinned(id, pinned).await,
+            Self::Archived(archived) => conversation_service.set_archived(id, archived).await,
+            Self::Folder(folder) => conversation_service.set_folder(id, folder).await,
+            Self::Tags(tags) => conversation_service.set_tags(id, tags).await,
+        }
+    }
+}
+
+impl ChatPresenter {
+    /// Persist an organization change and refresh the conversation list.
+    pub(super) async fn handle_organize_conversation(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        id: Uuid,
+        change: OrganizationChange,
+    ) {
+        if let Err(e) = change.apply(conversation_service, id).await {
+            tracing::warn!("Failed to organize conversation {}: {}", id, e);
+            let _ = view_tx
+                .send(ViewCommand::ShowError {
+                    title: "Conversations".to_string(),
+                    message: format!("Failed to update conversation: {e}"),
+                    severity: ErrorSeverity::Error,
+                })
+                .await;
+            return;
+        }
+
+        let _ = Self::emit_conversation_list(conversation_service, view_tx).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::spawn_db_thread;
+    use crate::services::SqliteConversationService;
+
+    async fn sqlite_service(dir: &tempfile::TempDir) -> Arc<dyn ConversationService> {
+        let db_path = dir.path().join("test.db");
+        let db = tokio::task::spawn_blocking(move || spawn_db_thread(&db_path).unwrap())
+            .await
+            .unwrap();
+        Arc::new(SqliteConversationService::new(db))
+    }
+
+    async fn refreshed_list(
+        view_rx: &mut mpsc::Receiver<ViewCommand>,
+    ) -> Vec<super::super::view_command::ConversationSummary> {
+        match view_rx.recv().await {
+            Some(ViewCommand::ConversationListRefreshed { conversations }) => conversations,
+            other => panic!("expected ConversationListRefreshed, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn changes_are_persisted_and_listed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = sqlite_service(&dir).await;
+        let id = service
+            .create(Some("Plans".to_string()), Uuid::new_v4())
+            .await
+            .unwrap()
+            .id;
+        let (mut view_tx, mut view_rx) = mpsc::channel(16);
+
+        for change in [
+            OrganizationChange::Pinned(true),
+            OrganizationChange::Folder(Some("Travel".to_string())),
+            OrganizationChange::Tags(vec!["#Summer".to_string()]),
+            OrganizationChange::Archived(true),
+        ] {
+            ChatPresenter::handle_organize_conversation(&service, &mut view_tx, id, change).await;
+            refreshed_list(&mut view_rx).await;
+        }
+
+        let organization = service
+            .list_metadata(&crate::models::ConversationFilter::all(), None, None)
+            .await
+            .unwrap()[0]
+            .organization
+            .clone();
+        assert!(organization.pinned && organization.archived);
+        assert_eq!(organization.folder.as_deref(), Some("Travel"));
+        assert_eq!(organization.tags, ["summer"]);
+    }
+
+    #[tokio::test]
+    async fn archived_conversations_stay_in_the_view_list() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = sqlite_service(&dir).await;
+        let id = service.create(None, Uuid::new_v4()).await.unwrap().id;
+        let (mut view_tx, mut view_rx) = mpsc::channel(16);
+
+        ChatPresenter::handle_organize_conversation(
+            &service,
+            &mut view_tx,
+            id,
+            OrganizationChange::Archived(true),
+        )
+        .await;
+
+        let listed = refreshed_list(&mut view_rx).await;
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].organization.archived);
+    }
+
+    #[tokio::test]
+    async fn unknown_conversation_reports_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = sqlite_service(&dir).await;
+        let (mut view_tx, mut view_rx) = mpsc::channel(16);
+
+        ChatPresenter::handle_organize_conversation(
+            &service,
+            &mut view_tx,
+            Uuid::new_v4(),
+            OrganizationChange::Pinned(true),
+        )
+        .await;
+
+        assert!(matches!(
+            view_rx.recv().await,
+            Some(ViewCommand::ShowError { .. })
+        ));
+    }
+}
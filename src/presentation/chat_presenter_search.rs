@@ -0,0 +1,128 @@
+//! Sidebar conversation search for `ChatPresenter`.
+//!
+//! Queries are passed to `ConversationService::search` as typed, except that
+//! a `profile:` operator naming a profile is rewritten to that profile's id,
+//! since only ids are stored with conversations.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::view_command::ConversationSearchResult;
+use super::{ChatPresenter, ViewCommand};
+use crate::models::{ConversationFilter, ModelProfile, SearchMatchType, SearchQuery};
+use crate::services::{ConversationService, ProfileService};
+
+/// Rewrite `profile:<name>` in `query` to the id of the profile with that
+/// name (case-insensitive). Returns `None` when nothing needs rewriting.
+fn rewrite_profile_name(query: &str, profiles: &[ModelProfile]) -> Option<String> {
+    let mut parsed = SearchQuery::parse(query);
+    let name = parsed.profile.as_deref()?;
+    if Uuid::parse_str(name).is_ok() {
+        return None;
+    }
+    let profile = profiles
+        .iter()
+        .find(|profile| profile.name.eq_ignore_ascii_case(name))?;
+    parsed.profile = Some(profile.id.to_string());
+    Some(parsed.to_string())
+}
+
+impl ChatPresenter {
+    /// Resolve a profile name in a search query; unknown names are left as
+    /// typed and simply match nothing.
+    pub(super) async fn resolve_search_profile(
+        profile_service: &Arc<dyn ProfileService>,
+        query: String,
+    ) -> String {
+        if SearchQuery::parse(&query).profile.is_none() {
+            return query;
+        }
+        match profile_service.list().await {
+            Ok(profiles) => rewrite_profile_name(&query, &profiles).unwrap_or(query),
+            Err(e) => {
+                tracing::warn!("Could not list profiles for search: {e}");
+                query
+            }
+        }
+    }
+
+    pub(super) async fn handle_search_conversations(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        query: String,
+        filter: ConversationFilter,
+    ) {
+        if query.trim().is_empty() {
+            let _ = view_tx
+                .send(ViewCommand::ConversationSearchResults { results: vec![] })
+                .await;
+            return;
+        }
+        match conversation_service
+            .search(&query, &filter, Some(50), None)
+            .await
+        {
+            Ok(results) => {
+                let view_results: Vec<ConversationSearchResult> = results
+                    .into_iter()
+                    .map(|r| ConversationSearchResult {
+                        id: r.conversation_id,
+                        title: r.title,
+                        is_title_match: matches!(r.match_type, SearchMatchType::Title),
+                        match_context: r.match_context,
+                        message_count: r.message_count,
+                        updated_at: r.updated_at,
+                        title_highlights: r.title_highlights,
+                        context_highlights: r.context_highlights,
+                        message_index: r.message_index,
+                    })
+                    .collect();
+                let _ = view_tx
+                    .send(ViewCommand::ConversationSearchResults {
+                        results: view_results,
+                    })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!("Conversation search failed: {e}");
+                let _ = view_tx
+                    .send(ViewCommand::ConversationSearchResults { results: vec![] })
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> ModelProfile {
+        ModelProfile {
+            name: name.to_string(),
+            ..ModelProfile::default()
+        }
+    }
+
+    #[test]
+    fn profile_names_are_rewritten_to_ids() {
+        let profiles = [profile("Default"), profile("Work GPT")];
+        let rewritten = rewrite_profile_name("lifetimes profile:\"work gpt\"", &profiles).unwrap();
+        assert_eq!(
+            SearchQuery::parse(&rewritten).profile,
+            Some(profiles[1].id.to_string())
+        );
+        assert!(rewritten.starts_with("lifetimes "));
+    }
+
+    #[test]
+    fn ids_and_unknown_names_are_left_alone() {
+        let profiles = [profile("Default")];
+        let by_id = format!("profile:{}", profiles[0].id);
+        assert_eq!(rewrite_profile_name(&by_id, &profiles), None);
+        assert_eq!(rewrite_profile_name("profile:Missing", &profiles), None);
+        assert_eq!(rewrite_profile_name("no operators", &profiles), None);
+    }
+}
@@ -0,0 +1,402 @@
+// ============================================================================
+// Populated and failing search mocks for the conversation search tests.
+// ============================================================================
+
+use super::*;
+
+/// Mock that returns populated search results to exercise mapping code.
+pub(super) struct SearchableConversationService;
+
+#[async_trait::async_trait]
+impl ConversationService for SearchableConversationService {
+    async fn create(
+        &self,
+        _title: Option<String>,
+        _model_profile_id: Uuid,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        unimplemented!()
+    }
+    async fn load(
+        &self,
+        _id: Uuid,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        unimplemented!()
+    }
+    async fn list_metadata(
+        &self,
+        _filter: &crate::models::ConversationFilter,
+        _limit: Option<usize>,
+        _offset: Option<usize>,
+    ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
+        Ok(vec![])
+    }
+    async fn add_message(
+        &self,
+        _conversation_id: Uuid,
+        message: crate::models::Message,
+    ) -> Result<crate::models::Message, crate::services::ServiceError> {
+        Ok(message)
+    }
+    async fn search(
+        &self,
+        _query: &str,
+        _filter: &crate::models::ConversationFilter,
+        _limit: Option<usize>,
+        _offset: Option<usize>,
+    ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
+        Ok(vec![
+            crate::models::SearchResult {
+                conversation_id: Uuid::new_v4(),
+                title: "Rust async patterns".to_string(),
+                match_type: crate::models::SearchMatchType::Title,
+                match_context: "...tokio async patterns...".to_string(),
+                score: 1.5,
+                updated_at: chrono::Utc::now(),
+                message_count: 12,
+                title_highlights: vec![5..10],
+                context_highlights: vec![3..8],
+                message_index: Some(4),
+            },
+            crate::models::SearchResult {
+                conversation_id: Uuid::new_v4(),
+                title: "EventBus refactoring".to_string(),
+                match_type: crate::models::SearchMatchType::Content,
+                match_context: "switching from tokio broadcast to flume".to_string(),
+                score: 0.8,
+                updated_at: chrono::Utc::now(),
+                message_count: 8,
+                title_highlights: Vec::new(),
+                context_highlights: Vec::new(),
+                message_index: None,
+            },
+        ])
+    }
+    async fn message_count(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<usize, crate::services::ServiceError> {
+        Ok(0)
+    }
+    async fn update_context_state(
+        &self,
+        _id: Uuid,
+        _state: &crate::models::ContextState,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn get_context_state(
+        &self,
+        _id: Uuid,
+    ) -> Result<Option<crate::models::ContextState>, crate::services::ServiceError> {
+        Ok(None)
+    }
+    async fn rename(
+        &self,
+        _id: Uuid,
+        _new_title: String,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn delete(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn set_active(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn get_active(&self) -> Result<Option<Uuid>, crate::services::ServiceError> {
+        Ok(None)
+    }
+    async fn get_messages(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::Message>, crate::services::ServiceError> {
+        Ok(vec![])
+    }
+    async fn get_message(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<Option<crate::models::Message>, crate::services::ServiceError> {
+        Ok(None)
+    }
+    async fn update(
+        &self,
+        _id: Uuid,
+        _title: Option<String>,
+        _model_profile_id: Option<Uuid>,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        unimplemented!()
+    }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::ConversationBranch>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::MessageVersionInfo>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Mock that fails search to exercise the error path.
+pub(super) struct FailingSearchService;
+
+#[async_trait::async_trait]
+impl ConversationService for FailingSearchService {
+    async fn create(
+        &self,
+        _title: Option<String>,
+        _model_profile_id: Uuid,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        unimplemented!()
+    }
+    async fn load(
+        &self,
+        _id: Uuid,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        unimplemented!()
+    }
+    async fn list_metadata(
+        &self,
+        _filter: &crate::models::ConversationFilter,
+        _limit: Option<usize>,
+        _offset: Option<usize>,
+    ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
+        Ok(vec![])
+    }
+    async fn add_message(
+        &self,
+        _conversation_id: Uuid,
+        message: crate::models::Message,
+    ) -> Result<crate::models::Message, crate::services::ServiceError> {
+        Ok(message)
+    }
+    async fn search(
+        &self,
+        _query: &str,
+        _filter: &crate::models::ConversationFilter,
+        _limit: Option<usize>,
+        _offset: Option<usize>,
+    ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
+        Err(crate::services::ServiceError::Storage(
+            "Search unavailable".to_string(),
+        ))
+    }
+    async fn message_count(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<usize, crate::services::ServiceError> {
+        Ok(0)
+    }
+    async fn update_context_state(
+        &self,
+        _id: Uuid,
+        _state: &crate::models::ContextState,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn get_context_state(
+        &self,
+        _id: Uuid,
+    ) -> Result<Option<crate::models::ContextState>, crate::services::ServiceError> {
+        Ok(None)
+    }
+    async fn rename(
+        &self,
+        _id: Uuid,
+        _new_title: String,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn delete(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn set_active(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+    async fn get_active(&self) -> Result<Option<Uuid>, crate::services::ServiceError> {
+        Ok(None)
+    }
+    async fn get_messages(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::Message>, crate::services::ServiceError> {
+        Ok(vec![])
+    }
+    async fn get_message(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<Option<crate::models::Message>, crate::services::ServiceError> {
+        Ok(None)
+    }
+    async fn update(
+        &self,
+        _id: Uuid,
+        _title: Option<String>,
+        _model_profile_id: Option<Uuid>,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        unimplemented!()
+    }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::ConversationBranch>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::MessageVersionInfo>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+}
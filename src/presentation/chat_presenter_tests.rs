@@ -1,5 +1,6 @@
 use super::*;
 use crate::events::types::ChatEvent;
+use search_mocks::{FailingSearchService, SearchableConversationService};
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 
@@ -34,6 +35,7 @@ impl ConversationService for MockConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &crate::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
@@ -51,6 +53,7 @@ impl ConversationService for MockConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &crate::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
@@ -106,6 +109,13 @@ impl ConversationService for MockConversationService {
         Ok(vec![])
     }
 
+    async fn get_message(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<Option<crate::models::Message>, crate::services::ServiceError> {
+        Ok(None)
+    }
     async fn update(
         &self,
         _id: Uuid,
@@ -116,6 +126,91 @@ impl ConversationService for MockConversationService {
             "Not implemented".to_string(),
         ))
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::ConversationBranch>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::MessageVersionInfo>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Mock `ChatService` for testing
@@ -154,6 +249,33 @@ impl ChatService for MockChatService {
     ) -> Result<(), crate::services::ServiceError> {
         Ok(())
     }
+
+    async fn regenerate(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = crate::services::ChatStreamEvent> + Send + Unpin>,
+        crate::services::ServiceError,
+    > {
+        Err(crate::services::ServiceError::Internal(
+            "regenerate not supported by mock".to_string(),
+        ))
+    }
+
+    async fn edit_and_resend(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _content: String,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = crate::services::ChatStreamEvent> + Send + Unpin>,
+        crate::services::ServiceError,
+    > {
+        Err(crate::services::ServiceError::Internal(
+            "edit and resend not supported by mock".to_string(),
+        ))
+    }
 }
 
 struct MockProfileService;
@@ -438,6 +560,7 @@ impl ConversationService for PopulatedMetadataService {
     }
     async fn list_metadata(
         &self,
+        _filter: &crate::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
@@ -450,6 +573,8 @@ impl ConversationService for PopulatedMetadataService {
                 profile_id: Some(Uuid::new_v4()),
                 message_count: 10,
                 last_message_preview: Some("How to use tokio select".to_string()),
+                parent_id: None,
+                organization: crate::models::ConversationOrganization::default(),
             },
             crate::models::ConversationMetadata {
                 id: Uuid::new_v4(),
@@ -459,6 +584,8 @@ impl ConversationService for PopulatedMetadataService {
                 profile_id: None,
                 message_count: 0,
                 last_message_preview: None,
+                parent_id: None,
+                organization: crate::models::ConversationOrganization::default(),
             },
         ])
     }
@@ -472,6 +599,7 @@ impl ConversationService for PopulatedMetadataService {
     async fn search(
         &self,
         _q: &str,
+        _filter: &crate::models::ConversationFilter,
         _l: Option<usize>,
         _o: Option<usize>,
     ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
@@ -515,6 +643,13 @@ impl ConversationService for PopulatedMetadataService {
     ) -> Result<Vec<crate::models::Message>, crate::services::ServiceError> {
         Ok(vec![])
     }
+    async fn get_message(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<Option<crate::models::Message>, crate::services::ServiceError> {
+        Ok(None)
+    }
     async fn update(
         &self,
         _id: Uuid,
@@ -523,6 +658,91 @@ impl ConversationService for PopulatedMetadataService {
     ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
         unimplemented!()
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::ConversationBranch>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), crate::services::ServiceError> {
+        Err(crate::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<Vec<crate::models::MessageVersionInfo>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Test `emit_conversation_list` maps metadata (including preview) to summaries
@@ -560,8 +780,13 @@ async fn test_search_conversations_empty_query() {
     let (view_tx, mut view_rx) = mpsc::channel::<ViewCommand>(100);
     let conversation_service = Arc::new(MockConversationService) as Arc<dyn ConversationService>;
 
-    ChatPresenter::handle_search_conversations(&conversation_service, &view_tx, String::new())
-        .await;
+    ChatPresenter::handle_search_conversations(
+        &conversation_service,
+        &view_tx,
+        String::new(),
+        crate::models::ConversationFilter::default(),
+    )
+    .await;
 
     let cmd = view_rx.try_recv().expect("Should emit search results");
     match cmd {
@@ -578,8 +803,13 @@ async fn test_search_conversations_whitespace_query() {
     let (view_tx, mut view_rx) = mpsc::channel::<ViewCommand>(100);
     let conversation_service = Arc::new(MockConversationService) as Arc<dyn ConversationService>;
 
-    ChatPresenter::handle_search_conversations(&conversation_service, &view_tx, "   ".to_string())
-        .await;
+    ChatPresenter::handle_search_conversations(
+        &conversation_service,
+        &view_tx,
+        "   ".to_string(),
+        crate::models::ConversationFilter::default(),
+    )
+    .await;
 
     let cmd = view_rx.try_recv().expect("Should emit search results");
     match cmd {
@@ -600,6 +830,7 @@ async fn test_search_conversations_non_empty_query() {
         &conversation_service,
         &view_tx,
         "tokio".to_string(),
+        crate::models::ConversationFilter::default(),
     )
     .await;
 
@@ -613,216 +844,19 @@ async fn test_search_conversations_non_empty_query() {
     }
 }
 
-/// Mock that returns populated search results to exercise mapping code.
-struct SearchableConversationService;
-
-#[async_trait::async_trait]
-impl ConversationService for SearchableConversationService {
-    async fn create(
-        &self,
-        _title: Option<String>,
-        _model_profile_id: Uuid,
-    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
-        unimplemented!()
-    }
-    async fn load(
-        &self,
-        _id: Uuid,
-    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
-        unimplemented!()
-    }
-    async fn list_metadata(
-        &self,
-        _limit: Option<usize>,
-        _offset: Option<usize>,
-    ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
-        Ok(vec![])
-    }
-    async fn add_message(
-        &self,
-        _conversation_id: Uuid,
-        message: crate::models::Message,
-    ) -> Result<crate::models::Message, crate::services::ServiceError> {
-        Ok(message)
-    }
-    async fn search(
-        &self,
-        _query: &str,
-        _limit: Option<usize>,
-        _offset: Option<usize>,
-    ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
-        Ok(vec![
-            crate::models::SearchResult {
-                conversation_id: Uuid::new_v4(),
-                title: "Rust async patterns".to_string(),
-                match_type: crate::models::SearchMatchType::Title,
-                match_context: "...tokio async patterns...".to_string(),
-                score: 1.5,
-                updated_at: chrono::Utc::now(),
-                message_count: 12,
-            },
-            crate::models::SearchResult {
-                conversation_id: Uuid::new_v4(),
-                title: "EventBus refactoring".to_string(),
-                match_type: crate::models::SearchMatchType::Content,
-                match_context: "switching from tokio broadcast to flume".to_string(),
-                score: 0.8,
-                updated_at: chrono::Utc::now(),
-                message_count: 8,
-            },
-        ])
-    }
-    async fn message_count(
-        &self,
-        _conversation_id: Uuid,
-    ) -> Result<usize, crate::services::ServiceError> {
-        Ok(0)
-    }
-    async fn update_context_state(
-        &self,
-        _id: Uuid,
-        _state: &crate::models::ContextState,
-    ) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn get_context_state(
-        &self,
-        _id: Uuid,
-    ) -> Result<Option<crate::models::ContextState>, crate::services::ServiceError> {
-        Ok(None)
-    }
-    async fn rename(
-        &self,
-        _id: Uuid,
-        _new_title: String,
-    ) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn delete(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn set_active(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn get_active(&self) -> Result<Option<Uuid>, crate::services::ServiceError> {
-        Ok(None)
-    }
-    async fn get_messages(
-        &self,
-        _conversation_id: Uuid,
-    ) -> Result<Vec<crate::models::Message>, crate::services::ServiceError> {
-        Ok(vec![])
-    }
-    async fn update(
-        &self,
-        _id: Uuid,
-        _title: Option<String>,
-        _model_profile_id: Option<Uuid>,
-    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
-        unimplemented!()
-    }
-}
-
-/// Mock that fails search to exercise the error path.
-struct FailingSearchService;
-
-#[async_trait::async_trait]
-impl ConversationService for FailingSearchService {
-    async fn create(
-        &self,
-        _title: Option<String>,
-        _model_profile_id: Uuid,
-    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
-        unimplemented!()
-    }
-    async fn load(
-        &self,
-        _id: Uuid,
-    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
-        unimplemented!()
-    }
-    async fn list_metadata(
-        &self,
-        _limit: Option<usize>,
-        _offset: Option<usize>,
-    ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
-        Ok(vec![])
-    }
-    async fn add_message(
-        &self,
-        _conversation_id: Uuid,
-        message: crate::models::Message,
-    ) -> Result<crate::models::Message, crate::services::ServiceError> {
-        Ok(message)
-    }
-    async fn search(
-        &self,
-        _query: &str,
-        _limit: Option<usize>,
-        _offset: Option<usize>,
-    ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
-        Err(crate::services::ServiceError::Storage(
-            "Search unavailable".to_string(),
-        ))
-    }
-    async fn message_count(
-        &self,
-        _conversation_id: Uuid,
-    ) -> Result<usize, crate::services::ServiceError> {
-        Ok(0)
-    }
-    async fn update_context_state(
-        &self,
-        _id: Uuid,
-        _state: &crate::models::ContextState,
-    ) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn get_context_state(
-        &self,
-        _id: Uuid,
-    ) -> Result<Option<crate::models::ContextState>, crate::services::ServiceError> {
-        Ok(None)
-    }
-    async fn rename(
-        &self,
-        _id: Uuid,
-        _new_title: String,
-    ) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn delete(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn set_active(&self, _id: Uuid) -> Result<(), crate::services::ServiceError> {
-        Ok(())
-    }
-    async fn get_active(&self) -> Result<Option<Uuid>, crate::services::ServiceError> {
-        Ok(None)
-    }
-    async fn get_messages(
-        &self,
-        _conversation_id: Uuid,
-    ) -> Result<Vec<crate::models::Message>, crate::services::ServiceError> {
-        Ok(vec![])
-    }
-    async fn update(
-        &self,
-        _id: Uuid,
-        _title: Option<String>,
-        _model_profile_id: Option<Uuid>,
-    ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
-        unimplemented!()
-    }
-}
-
 /// Test search with actual results exercises the mapping code
 #[tokio::test]
 async fn test_search_conversations_with_results() {
     let (view_tx, mut view_rx) = mpsc::channel::<ViewCommand>(100);
     let service = Arc::new(SearchableConversationService) as Arc<dyn ConversationService>;
 
-    ChatPresenter::handle_search_conversations(&service, &view_tx, "tokio".to_string()).await;
+    ChatPresenter::handle_search_conversations(
+        &service,
+        &view_tx,
+        "tokio".to_string(),
+        crate::models::ConversationFilter::default(),
+    )
+    .await;
 
     let cmd = view_rx.try_recv().expect("Should emit search results");
     match cmd {
@@ -831,6 +865,9 @@ async fn test_search_conversations_with_results() {
             assert_eq!(results[0].title, "Rust async patterns");
             assert!(results[0].is_title_match);
             assert_eq!(results[0].message_count, 12);
+            assert_eq!(results[0].title_highlights, [5..10]);
+            assert_eq!(results[0].context_highlights, [3..8]);
+            assert_eq!(results[0].message_index, Some(4));
             assert_eq!(results[1].title, "EventBus refactoring");
             assert!(!results[1].is_title_match);
             assert_eq!(
@@ -848,7 +885,13 @@ async fn test_search_conversations_service_error() {
     let (view_tx, mut view_rx) = mpsc::channel::<ViewCommand>(100);
     let service = Arc::new(FailingSearchService) as Arc<dyn ConversationService>;
 
-    ChatPresenter::handle_search_conversations(&service, &view_tx, "query".to_string()).await;
+    ChatPresenter::handle_search_conversations(
+        &service,
+        &view_tx,
+        "query".to_string(),
+        crate::models::ConversationFilter::default(),
+    )
+    .await;
 
     let cmd = view_rx
         .try_recv()
@@ -880,6 +923,9 @@ fn test_conversation_search_result_fields() {
         match_context: "context".to_string(),
         message_count: 5,
         updated_at: now,
+        title_highlights: Vec::new(),
+        context_highlights: Vec::new(),
+        message_index: Some(2),
     };
     assert_eq!(result.id, id);
     assert!(result.is_title_match);
@@ -890,3 +936,6 @@ fn test_conversation_search_result_fields() {
 
 #[path = "chat_presenter_cancel_tests.rs"]
 mod cancel_tests;
+
+#[path = "chat_presenter_search_mocks.rs"]
+mod search_mocks;
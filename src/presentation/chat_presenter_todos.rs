@@ -0,0 +1,37 @@
+//! `TodoWrite` task list loading for `ChatPresenter`.
+//!
+//! The tool pushes its own updates while a turn runs; selecting a
+//! conversation loads the stored list so the checklist is shown again.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::{ChatPresenter, ViewCommand};
+use crate::services::TodoService;
+
+impl ChatPresenter {
+    pub(super) async fn emit_todo_list(
+        todo_service: Option<&Arc<dyn TodoService>>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+    ) {
+        let Some(todo_service) = todo_service else {
+            return;
+        };
+        match todo_service.list(conversation_id).await {
+            Ok(todos) => {
+                let _ = view_tx
+                    .send(ViewCommand::TodoListUpdated {
+                        conversation_id,
+                        todos,
+                    })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!(%conversation_id, "Failed to load the task list: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Regenerate, edit-and-resend, and message version switching for
+//! `ChatPresenter`.
+//!
+//! Each flow rewrites the tail of the selected conversation, so the view's
+//! transcript is replaced wholesale (`ConversationTranscriptReplaced`) rather
+//! than appended to. Version switcher state is reported by visible message
+//! index, like branch fork points.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::chat_presenter_branch::{seq_for_visible_index, visible_index_for_seq};
+use super::view_command::{ErrorSeverity, MessageRole, MessageVersionSummary};
+use super::{ChatPresenter, ViewCommand};
+use crate::models::{Message, MessageVersionInfo};
+use crate::services::{ChatService, ConversationService, ServiceError, ServiceResult};
+
+/// Resolve a visible index to a `seq`, allowing one past the last message.
+fn seq_or_end(messages: &[Message], message_index: usize) -> ServiceResult<usize> {
+    let visible = messages
+        .iter()
+        .filter(|message| message.role != crate::models::MessageRole::System)
+        .count();
+    if message_index == visible {
+        return Ok(messages.len());
+    }
+    seq_for_visible_index(messages, message_index)
+        .ok_or_else(|| ServiceError::NotFound(format!("no message at index {message_index}")))
+}
+
+fn version_summary(messages: &[Message], info: MessageVersionInfo) -> MessageVersionSummary {
+    let message_index = if info.seq < messages.len() {
+        visible_index_for_seq(messages, info.seq).unwrap_or(0)
+    } else {
+        messages
+            .iter()
+            .filter(|message| message.role != crate::models::MessageRole::System)
+            .count()
+    };
+    MessageVersionSummary {
+        message_index,
+        selected: info.selected,
+        count: info.count,
+    }
+}
+
+impl ChatPresenter {
+    /// Archive the response at `message_index` and stream a new one.
+    pub(super) async fn handle_regenerate_response(
+        conversation_service: &Arc<dyn ConversationService>,
+        chat_service: &Arc<dyn ChatService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        message_index: usize,
+    ) {
+        let result = async {
+            let messages = conversation_service.get_messages(conversation_id).await?;
+            let seq = seq_or_end(&messages, message_index)?;
+            let prefix = messages.into_iter().take(seq).collect();
+            Self::begin_rewritten_turn(view_tx, conversation_id, prefix, None).await;
+            chat_service.regenerate(conversation_id, seq).await
+        }
+        .await;
+
+        Self::finish_rewritten_turn(conversation_service, view_tx, conversation_id, result).await;
+    }
+
+    /// Replace the prompt at `message_index` with `content` and resend it.
+    pub(super) async fn handle_edit_and_resend(
+        conversation_service: &Arc<dyn ConversationService>,
+        chat_service: &Arc<dyn ChatService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        message_index: usize,
+        content: String,
+    ) {
+        let trimmed = content.trim().to_string();
+        if trimmed.is_empty() {
+            return;
+        }
+
+        let result = async {
+            let messages = conversation_service.get_messages(conversation_id).await?;
+            let seq = seq_for_visible_index(&messages, message_index).ok_or_else(|| {
+                ServiceError::NotFound(format!("no message at index {message_index}"))
+            })?;
+            let prefix = messages.into_iter().take(seq).collect();
+            Self::begin_rewritten_turn(view_tx, conversation_id, prefix, Some(trimmed.clone()))
+                .await;
+            chat_service
+                .edit_and_resend(conversation_id, seq, trimmed)
+                .await
+        }
+        .await;
+
+        Self::finish_rewritten_turn(conversation_service, view_tx, conversation_id, result).await;
+    }
+
+    /// Make another version of the message at `message_index` the live one.
+    pub(super) async fn handle_switch_message_version(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        message_index: usize,
+        version: u32,
+    ) {
+        let result = async {
+            let messages = conversation_service.get_messages(conversation_id).await?;
+            let seq = seq_or_end(&messages, message_index)?;
+            conversation_service
+                .switch_message_version(conversation_id, seq, version)
+                .await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to switch message version in {}: {}",
+                conversation_id,
+                e
+            );
+            let _ = view_tx
+                .send(ViewCommand::ShowError {
+                    title: "Message Versions".to_string(),
+                    message: format!("Failed to switch message version: {e}"),
+                    severity: ErrorSeverity::Error,
+                })
+                .await;
+            return;
+        }
+
+        Self::emit_transcript_replaced(conversation_service, view_tx, conversation_id).await;
+        Self::emit_message_versions(conversation_service, view_tx, conversation_id).await;
+    }
+
+    /// Send the version switcher state for `conversation_id` to the view.
+    pub(super) async fn emit_message_versions(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+    ) {
+        let versions = match conversation_service
+            .list_message_versions(conversation_id)
+            .await
+        {
+            // An empty list is still sent so a stale switcher is cleared.
+            Ok(versions) => versions,
+            Err(e) => {
+                tracing::debug!(
+                    "Failed to list message versions for {}: {}",
+                    conversation_id,
+                    e
+                );
+                return;
+            }
+        };
+        let Ok(messages) = conversation_service.get_messages(conversation_id).await else {
+            return;
+        };
+
+        let versions = versions
+            .into_iter()
+            .map(|info| version_summary(&messages, info))
+            .collect();
+        let _ = view_tx
+            .send(ViewCommand::MessageVersionsLoaded {
+                conversation_id,
+                versions,
+            })
+            .await;
+    }
+
+    /// Show the surviving prefix (plus an edited prompt) and the thinking
+    /// indicator before the service starts the new turn.
+    async fn begin_rewritten_turn(
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        prefix: Vec<Message>,
+        edited_prompt: Option<String>,
+    ) {
+        let _ = view_tx
+            .send(ViewCommand::ConversationTranscriptReplaced {
+                conversation_id,
+                messages: Self::conversation_message_payloads(prefix),
+            })
+            .await;
+        if let Some(content) = edited_prompt {
+            let _ = view_tx
+                .send(ViewCommand::MessageAppended {
+                    conversation_id,
+                    role: MessageRole::User,
+                    content,
+                    model_id: None,
+                })
+                .await;
+        }
+        let _ = view_tx
+            .send(ViewCommand::ShowThinking {
+                conversation_id,
+                model_id: "Assistant".to_string(),
+            })
+            .await;
+    }
+
+    async fn finish_rewritten_turn<T>(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+        result: ServiceResult<T>,
+    ) {
+        match result {
+            Ok(_stream) => {
+                // Stream events arrive via ChatEvent, as for send_message.
+                Self::emit_message_versions(conversation_service, view_tx, conversation_id).await;
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                tracing::error!("Failed to rewrite conversation turn: {}", error_msg);
+                let _ = view_tx
+                    .send(ViewCommand::StreamError {
+                        conversation_id,
+                        error: error_msg,
+                        recoverable: false,
+                    })
+                    .await;
+                let _ = view_tx
+                    .send(ViewCommand::HideThinking { conversation_id })
+                    .await;
+                Self::emit_transcript_replaced(conversation_service, view_tx, conversation_id)
+                    .await;
+            }
+        }
+    }
+
+    async fn emit_transcript_replaced(
+        conversation_service: &Arc<dyn ConversationService>,
+        view_tx: &mut mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+    ) {
+        match conversation_service.get_messages(conversation_id).await {
+            Ok(messages) => {
+                let _ = view_tx
+                    .send(ViewCommand::ConversationTranscriptReplaced {
+                        conversation_id,
+                        messages: Self::conversation_message_payloads(messages),
+                    })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to reload messages for {}: {}", conversation_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> Vec<Message> {
+        vec![
+            Message::system("be terse".to_string()),
+            Message::user("hi".to_string()),
+            Message::assistant("hello".to_string()),
+        ]
+    }
+
+    #[test]
+    fn seq_or_end_accepts_one_past_last_visible_message() {
+        let messages = transcript();
+        assert_eq!(seq_or_end(&messages, 1).unwrap(), 2);
+        assert_eq!(seq_or_end(&messages, 2).unwrap(), 3);
+        assert!(matches!(
+            seq_or_end(&messages, 3),
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn version_summary_maps_empty_tail_past_the_end() {
+        let messages = transcript();
+        let live = version_summary(
+            &messages,
+            MessageVersionInfo {
+                seq: 2,
+                selected: Some(1),
+                count: 2,
+            },
+        );
+        assert_eq!(live.message_index, 1);
+        let empty = version_summary(
+            &messages,
+            MessageVersionInfo {
+                seq: 3,
+                selected: None,
+                count: 1,
+            },
+        );
+        assert_eq!(empty.message_index, 2);
+    }
+}
@@ -0,0 +1,325 @@
+//! Bulk conversation export into a single archive with a manifest.
+//!
+//! A zip archive holds `manifest.json` plus `conversations/<file>` rendered in
+//! the current export format. A JSON Lines archive starts with the manifest
+//! line followed by one full conversation JSON object per line. Every
+//! manifest entry carries a SHA-256 of its file or line so a recipient can
+//! check nothing was altered in transit.
+
+use std::collections::HashSet;
+use std::io::{Cursor, Write as _};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use zip::result::ZipResult;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::conversation_export::{build_export_filename, render_export_content};
+use crate::models::{Conversation, ConversationArchiveFormat, ConversationExportFormat};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const CONVERSATIONS_DIR: &str = "conversations";
+
+/// Describes the archive contents.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveManifest {
+    pub exported_at: DateTime<Utc>,
+    pub app_version: String,
+    pub archive_format: ConversationArchiveFormat,
+    /// Format of the per-conversation files; JSON for JSON Lines archives.
+    pub conversation_format: ConversationExportFormat,
+    pub conversation_count: usize,
+    pub message_count: usize,
+    pub conversations: Vec<ManifestEntry>,
+}
+
+/// One exported conversation.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub profile_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub message_count: usize,
+    /// Path inside a zip archive, or `None` for JSON Lines.
+    pub file: Option<String>,
+    /// Hex SHA-256 of the file, or of the conversation's JSON line.
+    pub sha256: String,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn manifest_entry(conversation: &Conversation, file: Option<String>, body: &[u8]) -> ManifestEntry {
+    ManifestEntry {
+        id: conversation.id,
+        title: conversation.title.clone(),
+        profile_id: conversation.profile_id,
+        created_at: conversation.created_at,
+        updated_at: conversation.updated_at,
+        message_count: conversation.messages.len(),
+        file,
+        sha256: sha256_hex(body),
+    }
+}
+
+fn new_manifest(
+    archive_format: ConversationArchiveFormat,
+    conversation_format: ConversationExportFormat,
+    exported_at: DateTime<Utc>,
+) -> ArchiveManifest {
+    ArchiveManifest {
+        exported_at,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        archive_format,
+        conversation_format,
+        conversation_count: 0,
+        message_count: 0,
+        conversations: Vec::new(),
+    }
+}
+
+fn push_entry(manifest: &mut ArchiveManifest, entry: ManifestEntry) {
+    manifest.conversation_count += 1;
+    manifest.message_count += entry.message_count;
+    manifest.conversations.push(entry);
+}
+
+/// Pick a file name not yet used in the archive, suffixing `-1`, `-2`, ...
+fn unique_entry_name(used: &mut HashSet<String>, filename: &str) -> String {
+    let (stem, extension) = filename.rsplit_once('.').unwrap_or((filename, ""));
+    let mut candidate = filename.to_string();
+    let mut index = 1;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{stem}-{index}.{extension}");
+        index += 1;
+    }
+    candidate
+}
+
+fn add_zip_file(
+    writer: &mut ZipWriter<Cursor<Vec<u8>>>,
+    path: &str,
+    body: &[u8],
+    options: SimpleFileOptions,
+) -> ZipResult<()> {
+    writer.start_file(path, options)?;
+    writer.write_all(body)?;
+    Ok(())
+}
+
+fn render_zip(
+    conversations: &[Conversation],
+    format: ConversationExportFormat,
+    exported_at: DateTime<Utc>,
+) -> Result<Vec<u8>, String> {
+    let mut manifest = new_manifest(ConversationArchiveFormat::Zip, format, exported_at);
+    // Zip timestamps start in 1980; anything earlier falls back to that.
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(zip::DateTime::try_from(exported_at.naive_utc()).unwrap_or_default());
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut used_names = HashSet::new();
+
+    for conversation in conversations {
+        let body = render_export_content(conversation, format)?;
+        let name = unique_entry_name(
+            &mut used_names,
+            &build_export_filename(conversation, format),
+        );
+        let path = format!("{CONVERSATIONS_DIR}/{name}");
+        add_zip_file(&mut writer, &path, body.as_bytes(), options)
+            .map_err(|error| format!("failed to add {path} to archive: {error}"))?;
+        push_entry(
+            &mut manifest,
+            manifest_entry(conversation, Some(path), body.as_bytes()),
+        );
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| format!("failed to serialize archive manifest: {error}"))?;
+    add_zip_file(&mut writer, MANIFEST_FILE, &manifest_json, options)
+        .and_then(|()| writer.finish())
+        .map(Cursor::into_inner)
+        .map_err(|error| format!("failed to write archive: {error}"))
+}
+
+fn render_jsonl(
+    conversations: &[Conversation],
+    exported_at: DateTime<Utc>,
+) -> Result<Vec<u8>, String> {
+    let mut manifest = new_manifest(
+        ConversationArchiveFormat::Jsonl,
+        ConversationExportFormat::Json,
+        exported_at,
+    );
+    let mut lines = Vec::with_capacity(conversations.len());
+    for conversation in conversations {
+        let line = serde_json::to_string(conversation)
+            .map_err(|error| format!("failed to serialize conversation JSON: {error}"))?;
+        push_entry(
+            &mut manifest,
+            manifest_entry(conversation, None, line.as_bytes()),
+        );
+        lines.push(line);
+    }
+
+    let mut output = serde_json::to_string(&manifest)
+        .map_err(|error| format!("failed to serialize archive manifest: {error}"))?;
+    output.push('\n');
+    for line in lines {
+        output.push_str(&line);
+        output.push('\n');
+    }
+    Ok(output.into_bytes())
+}
+
+/// Render `conversations` into an archive of the given kind.
+///
+/// # Errors
+///
+/// Returns an error when a conversation or the manifest cannot be serialized
+/// or the zip writer fails.
+pub fn render_archive(
+    conversations: &[Conversation],
+    archive: ConversationArchiveFormat,
+    format: ConversationExportFormat,
+    exported_at: DateTime<Utc>,
+) -> Result<Vec<u8>, String> {
+    match archive {
+        ConversationArchiveFormat::Zip => render_zip(conversations, format, exported_at),
+        ConversationArchiveFormat::Jsonl => render_jsonl(conversations, exported_at),
+    }
+}
+
+/// File name for an archive, e.g. `20260109-110807-conversations-12.zip`.
+#[must_use]
+pub fn build_archive_filename(
+    count: usize,
+    archive: ConversationArchiveFormat,
+    exported_at: DateTime<Utc>,
+) -> String {
+    format!(
+        "{}-conversations-{count}.{}",
+        exported_at.format("%Y%m%d-%H%M%S"),
+        archive.extension()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read as _;
+
+    use chrono::TimeZone;
+    use serde_json::Value;
+    use zip::ZipArchive;
+
+    use super::*;
+    use crate::models::Message;
+
+    fn conversation(title: &str) -> Conversation {
+        let mut conversation = Conversation::new(Uuid::new_v4());
+        conversation.title = Some(title.to_string());
+        conversation.updated_at = Utc.with_ymd_and_hms(2026, 1, 9, 11, 8, 7).unwrap();
+        conversation.messages = vec![
+            Message::user("Ship it?".to_string()),
+            Message::assistant("Yes.".to_string()),
+        ];
+        conversation
+    }
+
+    fn read_entries(bytes: Vec<u8>) -> Vec<(String, Vec<u8>)> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        (0..archive.len())
+            .map(|index| {
+                let mut file = archive.by_index(index).unwrap();
+                let mut data = Vec::new();
+                file.read_to_end(&mut data).unwrap();
+                (file.name().to_string(), data)
+            })
+            .collect()
+    }
+
+    fn exported_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 1, 8, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn zip_archive_has_manifest_and_one_file_per_conversation() {
+        let conversations = vec![conversation("Retro"), conversation("Retro")];
+        let bytes = render_archive(
+            &conversations,
+            ConversationArchiveFormat::Zip,
+            ConversationExportFormat::Md,
+            exported_at(),
+        )
+        .unwrap();
+
+        let entries = read_entries(bytes);
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "conversations/20260109-110807-retro.md",
+                "conversations/20260109-110807-retro-1.md",
+                "manifest.json",
+            ]
+        );
+
+        let manifest: Value = serde_json::from_slice(&entries[2].1).unwrap();
+        assert_eq!(manifest["conversation_count"], 2);
+        assert_eq!(manifest["message_count"], 4);
+        assert_eq!(manifest["conversation_format"], "Md");
+        assert_eq!(
+            manifest["conversations"][1]["file"],
+            "conversations/20260109-110807-retro-1.md"
+        );
+        assert_eq!(
+            manifest["conversations"][0]["sha256"],
+            sha256_hex(&entries[0].1)
+        );
+    }
+
+    #[test]
+    fn jsonl_archive_starts_with_manifest_line() {
+        let conversations = vec![conversation("One"), conversation("Two")];
+        let bytes = render_archive(
+            &conversations,
+            ConversationArchiveFormat::Jsonl,
+            ConversationExportFormat::Html,
+            exported_at(),
+        )
+        .unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+
+        let manifest: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(manifest["archive_format"], "Jsonl");
+        assert_eq!(manifest["conversation_format"], "Json");
+        assert_eq!(
+            manifest["conversations"][1]["sha256"],
+            sha256_hex(lines[2].as_bytes())
+        );
+
+        let second: Conversation = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(second.title.as_deref(), Some("Two"));
+        assert_eq!(second.messages.len(), 2);
+    }
+
+    #[test]
+    fn archive_filename_includes_count_and_extension() {
+        assert_eq!(
+            build_archive_filename(12, ConversationArchiveFormat::Jsonl, exported_at()),
+            "20260201-080000-conversations-12.jsonl"
+        );
+    }
+}
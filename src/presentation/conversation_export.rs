@@ -88,6 +88,9 @@ pub fn render_export_content(
             .map_err(|error| format!("failed to serialize conversation JSON: {error}")),
         ConversationExportFormat::Txt => Ok(render_txt(conversation)),
         ConversationExportFormat::Md => Ok(render_markdown(conversation)),
+        ConversationExportFormat::Html => {
+            Ok(super::conversation_export_html::render_html(conversation))
+        }
     }
 }
 
@@ -263,18 +266,21 @@ pub fn validate_export_directory(path: &str) -> Result<(), String> {
         )
 }
 
-pub fn write_export_file(path: &Path, content: &str) -> std::io::Result<()> {
+pub fn write_export_file(
+    path: &Path,
+    content: &(impl AsRef<[u8]> + ?Sized),
+) -> std::io::Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
     let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
-    file.write_all(content.as_bytes())
+    file.write_all(content.as_ref())
 }
 
 pub fn write_export_file_retrying_collisions(
     initial_path: PathBuf,
-    content: &str,
+    content: &(impl AsRef<[u8]> + ?Sized),
 ) -> std::io::Result<PathBuf> {
     let parent = initial_path
         .parent()
@@ -417,6 +423,21 @@ mod tests {
         assert!(content.contains("\"assistant\""));
     }
 
+    #[test]
+    fn html_render_is_a_standalone_document() {
+        let content =
+            render_export_content(&fixture_conversation(), ConversationExportFormat::Html)
+                .expect("html render should succeed");
+
+        assert!(content.contains("<title>Sprint Planning / Q1</title>"));
+        assert!(content.contains("<style>"));
+        assert!(content.contains("Weight impact against implementation risk."));
+        assert!(
+            build_export_filename(&fixture_conversation(), ConversationExportFormat::Html)
+                .ends_with(".html")
+        );
+    }
+
     #[test]
     fn resolve_export_directory_prefers_non_empty_setting() {
         let configured = resolve_export_directory(Some("/tmp/exports"));
@@ -0,0 +1,271 @@
+//! Self-contained HTML rendering for conversation exports.
+//!
+//! The output is a single file with inline styles and no external assets so
+//! it can be opened offline. Message bodies are rendered from markdown; raw
+//! HTML inside a message is escaped rather than passed through, links keep
+//! only http(s) and mailto targets, and images are shown as their alt text
+//! unless their data is inlined. Thinking and tool activity are collapsed
+//! into `<details>` blocks.
+
+use std::fmt::Write as _;
+
+use pulldown_cmark::{html::push_html, Event, Options, Parser, Tag, TagEnd};
+
+use crate::llm::tools::{ToolResult, ToolUse};
+use crate::models::{Conversation, Message, MessageRole};
+
+const STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:860px;\
+margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}\
+header{border-bottom:1px solid #d0d7de;margin-bottom:1.5rem}\
+header dl{display:grid;grid-template-columns:max-content 1fr;gap:.2rem 1rem;font-size:.85rem;color:#59636e}\
+header dd{margin:0}\
+article{border:1px solid #d0d7de;border-radius:8px;padding:.75rem 1rem;margin:1rem 0}\
+article.user{background:#f6f8fa}\
+article h2{font-size:.8rem;text-transform:uppercase;letter-spacing:.04em;color:#59636e;margin:0 0 .5rem}\
+pre{background:#f6f8fa;border-radius:6px;padding:.6rem;overflow-x:auto;white-space:pre-wrap}\
+code{font-family:ui-monospace,SFMono-Regular,Menlo,monospace;font-size:.85em}\
+details{margin:.5rem 0;font-size:.9rem}\
+summary{cursor:pointer;color:#59636e}\
+.error{color:#d1242f}\
+table{border-collapse:collapse}td,th{border:1px solid #d0d7de;padding:.2rem .5rem}";
+
+/// Escape text for use in HTML element content and attribute values.
+#[must_use]
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Link schemes kept in the export; other links are shown as their text.
+const LINK_SCHEMES: &[&str] = &["http://", "https://", "mailto:"];
+
+/// Inlined image types kept in the export; other images are shown as their
+/// alt text, so the file never loads anything remote.
+const IMAGE_DATA_PREFIXES: &[&str] = &[
+    "data:image/png;",
+    "data:image/jpeg;",
+    "data:image/gif;",
+    "data:image/webp;",
+];
+
+/// Whether `destination` starts with one of `prefixes`, ignoring case and the
+/// whitespace and control characters browsers skip when reading a URL.
+fn destination_has_prefix(destination: &str, prefixes: &[&str]) -> bool {
+    let normalized: String = destination
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && !ch.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    prefixes.iter().any(|prefix| normalized.starts_with(prefix))
+}
+
+/// Render markdown to HTML, turning any embedded raw HTML into plain text and
+/// unwrapping links and images the export must not carry.
+fn markdown_to_html(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    // Whether each open link or image is kept, so its end tag matches.
+    let mut kept_links = Vec::new();
+    let mut kept_images = Vec::new();
+    let events = Parser::new_ext(markdown, options).filter_map(move |event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Some(Event::Text(raw)),
+        Event::Start(Tag::Link { ref dest_url, .. }) => {
+            let kept = destination_has_prefix(dest_url, LINK_SCHEMES);
+            kept_links.push(kept);
+            kept.then_some(event)
+        }
+        Event::End(TagEnd::Link) => kept_links.pop().unwrap_or(false).then_some(event),
+        Event::Start(Tag::Image { ref dest_url, .. }) => {
+            let kept = destination_has_prefix(dest_url, IMAGE_DATA_PREFIXES);
+            kept_images.push(kept);
+            kept.then_some(event)
+        }
+        Event::End(TagEnd::Image) => kept_images.pop().unwrap_or(false).then_some(event),
+        other => Some(other),
+    });
+    let mut html = String::new();
+    push_html(&mut html, events);
+    html
+}
+
+fn parse_json_list<T: serde::de::DeserializeOwned>(raw: Option<&str>) -> Vec<T> {
+    raw.and_then(|raw| serde_json::from_str(raw).ok())
+        .unwrap_or_default()
+}
+
+fn render_tool_activity(output: &mut String, message: &Message) {
+    let calls: Vec<ToolUse> = parse_json_list(message.tool_calls.as_deref());
+    let results: Vec<ToolResult> = parse_json_list(message.tool_results.as_deref());
+
+    for call in &calls {
+        let input = serde_json::to_string_pretty(&call.input).unwrap_or_default();
+        let _ = write!(
+            output,
+            "<details class=\"tool\"><summary>Tool call: {}</summary><pre><code>{}</code></pre>",
+            escape_html(&call.name),
+            escape_html(&input)
+        );
+        for result in results.iter().filter(|r| r.tool_use_id == call.id) {
+            let _ = write!(
+                output,
+                "<div{}>Result{}:</div><pre><code>{}</code></pre>",
+                if result.is_error {
+                    " class=\"error\""
+                } else {
+                    ""
+                },
+                if result.is_error { " (error)" } else { "" },
+                escape_html(&result.content)
+            );
+        }
+        output.push_str("</details>\n");
+    }
+}
+
+fn render_message(output: &mut String, message: &Message) {
+    let (class, label) = match message.role {
+        MessageRole::User => ("user", "User"),
+        MessageRole::Assistant => ("assistant", "Assistant"),
+        MessageRole::System => return,
+    };
+    let _ = writeln!(
+        output,
+        "<article class=\"{class}\"><h2>{label} · <time datetime=\"{}\">{}</time></h2>",
+        message.timestamp.to_rfc3339(),
+        message.timestamp.format("%Y-%m-%d %H:%M:%S")
+    );
+    if let Some(thinking) = message
+        .thinking_content
+        .as_deref()
+        .map(str::trim)
+        .filter(|thinking| !thinking.is_empty())
+    {
+        let _ = writeln!(
+            output,
+            "<details class=\"thinking\"><summary>Thinking</summary><pre>{}</pre></details>",
+            escape_html(thinking)
+        );
+    }
+    render_tool_activity(output, message);
+    output.push_str(&markdown_to_html(message.content.trim_end()));
+    output.push_str("</article>\n");
+}
+
+/// Render a conversation as a standalone HTML document.
+#[must_use]
+pub fn render_html(conversation: &Conversation) -> String {
+    let title = escape_html(
+        conversation
+            .title
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or("Untitled Conversation"),
+    );
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>"
+    );
+    let _ = writeln!(
+        output,
+        "<header><h1>{title}</h1><dl><dt>Conversation ID</dt><dd><code>{}</code></dd>\
+         <dt>Created</dt><dd>{}</dd><dt>Updated</dt><dd>{}</dd></dl></header>",
+        conversation.id,
+        conversation.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        conversation.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    for message in &conversation.messages {
+        render_message(&mut output, message);
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn conversation_with(messages: Vec<Message>) -> Conversation {
+        let mut conversation = Conversation::new(Uuid::new_v4());
+        conversation.title = Some("Q3 <review>".to_string());
+        conversation.messages = messages;
+        conversation
+    }
+
+    #[test]
+    fn renders_markdown_and_escapes_raw_html() {
+        let html = render_html(&conversation_with(vec![
+            Message::user("Is <script>alert(1)</script> safe?".to_string()),
+            Message::assistant("**No.** Use `escape`.".to_string()),
+        ]));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Q3 &lt;review&gt;</title>"));
+        assert!(html.contains("<strong>No.</strong>"));
+        assert!(html.contains("<code>escape</code>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn keeps_only_web_and_mail_links() {
+        let html = markdown_to_html(
+            "[docs](https://example.com/a) [mail](MAILTO:a@example.com) \
+             [x](javascript:alert(1)) [y](Jav&#x61;Script:alert(2)) [z](data:text/html,hi)",
+        );
+
+        assert!(html.contains("<a href=\"https://example.com/a\">docs</a>"));
+        assert!(html.contains("<a href=\"MAILTO:a@example.com\">mail</a>"));
+        assert_eq!(html.matches("<a ").count(), 2);
+        assert!(!html.to_ascii_lowercase().contains("javascript"));
+        assert!(!html.contains("data:text/html"));
+        assert!(html.contains(" x y z"));
+    }
+
+    #[test]
+    fn shows_remote_images_as_alt_text_and_keeps_inlined_ones() {
+        let html = markdown_to_html(
+            "![chart](https://example.com/c.png) ![dot](data:image/png;base64,iVBORw0KGgo=)",
+        );
+
+        assert!(!html.contains("example.com"));
+        assert!(html.contains("chart"));
+        assert_eq!(html.matches("<img").count(), 1);
+        assert!(html.contains("src=\"data:image/png;base64,iVBORw0KGgo=\""));
+    }
+
+    #[test]
+    fn renders_thinking_and_tool_calls_and_skips_system() {
+        let mut reply =
+            Message::assistant_with_thinking("Done.".to_string(), "Check first".to_string());
+        reply.tool_calls =
+            Some(r#"[{"id":"t1","name":"read_file","input":{"path":"a.rs"}}]"#.to_string());
+        reply.tool_results =
+            Some(r#"[{"tool_use_id":"t1","content":"fn main() {}","is_error":true}]"#.to_string());
+        let html = render_html(&conversation_with(vec![
+            Message::system("hidden policy".to_string()),
+            reply,
+        ]));
+
+        assert!(html.contains("<summary>Thinking</summary><pre>Check first</pre>"));
+        assert!(html.contains("Tool call: read_file"));
+        assert!(html.contains("&quot;path&quot;: &quot;a.rs&quot;"));
+        assert!(html.contains("Result (error):"));
+        assert!(html.contains("fn main() {}"));
+        assert!(!html.contains("hidden policy"));
+    }
+}
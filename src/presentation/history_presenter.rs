@@ -11,12 +11,13 @@ use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use super::{Presenter, PresenterError, ViewCommand};
+use crate::agent::policy_overrides::PolicyOverrides;
 use crate::events::bus::EventBus;
 use crate::events::{
     types::{ConversationEvent, UserEvent},
     AppEvent,
 };
-use crate::services::ConversationService;
+use crate::services::{AppSettingsService, ConversationService};
 
 /// `HistoryPresenter` - handles conversation history UI events
 ///
@@ -29,6 +30,9 @@ pub struct HistoryPresenter {
     /// Reference to conversation service
     conversation_service: Arc<dyn ConversationService>,
 
+    /// Settings holding per-conversation approval policy overrides
+    app_settings_service: Option<Arc<dyn AppSettingsService>>,
+
     /// View command sender (mpsc for reliable delivery)
     view_tx: mpsc::Sender<ViewCommand>,
 
@@ -49,11 +53,23 @@ impl HistoryPresenter {
         Self {
             event_bus,
             conversation_service,
+            app_settings_service: None,
             view_tx,
             running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
         }
     }
 
+    /// Remove a deleted conversation's approval policy override from
+    /// `app_settings_service`.
+    #[must_use]
+    pub fn with_app_settings_service(
+        mut self,
+        app_settings_service: Arc<dyn AppSettingsService>,
+    ) -> Self {
+        self.app_settings_service = Some(app_settings_service);
+        self
+    }
+
     /// Start the presenter event loop
     ///
     /// # Errors
@@ -74,13 +90,20 @@ impl HistoryPresenter {
         let mut rx = self.event_bus.subscribe();
         let running = self.running.clone();
         let conversation_service = self.conversation_service.clone();
+        let app_settings_service = self.app_settings_service.clone();
         let mut view_tx = self.view_tx.clone();
 
         tokio::spawn(async move {
             while running.load(std::sync::atomic::Ordering::Relaxed) {
                 match rx.recv().await {
                     Ok(event) => {
-                        Self::handle_event(&conversation_service, &mut view_tx, event).await;
+                        Self::handle_event(
+                            &conversation_service,
+                            app_settings_service.as_ref(),
+                            &mut view_tx,
+                            event,
+                        )
+                        .await;
                     }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                         tracing::warn!("HistoryPresenter lagged: {} events missed", n);
@@ -125,12 +148,19 @@ impl HistoryPresenter {
     /// @requirement REQ-025.1
     async fn handle_event(
         conversation_service: &Arc<dyn ConversationService>,
+        app_settings_service: Option<&Arc<dyn AppSettingsService>>,
         view_tx: &mut mpsc::Sender<ViewCommand>,
         event: AppEvent,
     ) {
         match event {
             AppEvent::User(user_evt) => {
-                Self::handle_user_event(conversation_service, view_tx, user_evt).await;
+                Self::handle_user_event(
+                    conversation_service,
+                    app_settings_service,
+                    view_tx,
+                    user_evt,
+                )
+                .await;
             }
             AppEvent::Conversation(conv_evt) => {
                 Self::handle_conversation_event(view_tx, conv_evt).await;
@@ -145,6 +175,7 @@ impl HistoryPresenter {
     /// @requirement REQ-025.1
     async fn handle_user_event(
         conversation_service: &Arc<dyn ConversationService>,
+        app_settings_service: Option<&Arc<dyn AppSettingsService>>,
         view_tx: &mut mpsc::Sender<ViewCommand>,
         event: UserEvent,
     ) {
@@ -152,6 +183,13 @@ impl HistoryPresenter {
             tracing::info!(%id, "HistoryPresenter: deleting conversation");
             match conversation_service.delete(id).await {
                 Ok(()) => {
+                    if let Some(app_settings) = app_settings_service {
+                        if let Err(e) =
+                            PolicyOverrides::remove_conversation(app_settings.as_ref(), id).await
+                        {
+                            tracing::warn!(%id, error = %e, "Failed to remove policy override");
+                        }
+                    }
                     let _ = view_tx.send(ViewCommand::ConversationDeleted { id }).await;
                 }
                 Err(e) => {
@@ -0,0 +1,179 @@
+//! `ImportPresenter` - importing conversations from other chat apps
+//!
+//! `ImportPresenter` handles `UserEvent::ImportConversations` from the
+//! settings view, imports the file under the default profile, reports the
+//! outcome, and asks for a conversation list refresh when anything was added.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::{Presenter, PresenterError, ViewCommand};
+use crate::events::bus::EventBus;
+use crate::events::{types::UserEvent, AppEvent};
+use crate::services::{ConversationImportService, ProfileService};
+
+/// `ImportPresenter` - conversation import
+pub struct ImportPresenter {
+    /// Reference to event bus for subscribing to events
+    event_bus: Arc<EventBus>,
+
+    /// Importer
+    import_service: Arc<dyn ConversationImportService>,
+
+    /// Default profile for imported conversations
+    profile_service: Arc<dyn ProfileService>,
+
+    /// View command sender (mpsc for reliable delivery)
+    view_tx: mpsc::Sender<ViewCommand>,
+
+    /// Running flag for event loop
+    running: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl ImportPresenter {
+    /// Create a new `ImportPresenter`
+    pub fn new(
+        event_bus: Arc<EventBus>,
+        import_service: Arc<dyn ConversationImportService>,
+        profile_service: Arc<dyn ProfileService>,
+        view_tx: mpsc::Sender<ViewCommand>,
+    ) -> Self {
+        Self {
+            event_bus,
+            import_service,
+            profile_service,
+            view_tx,
+            running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+
+    /// Start the presenter event loop
+    ///
+    /// # Errors
+    ///
+    /// Returns `PresenterError` if presenter startup becomes fallible in the future.
+    pub async fn start(&mut self) -> Result<(), PresenterError> {
+        if self.running.load(std::sync::atomic::Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        self.running
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let mut rx = self.event_bus.subscribe();
+        let running = self.running.clone();
+        let event_bus = self.event_bus.clone();
+        let import_service = self.import_service.clone();
+        let profile_service = self.profile_service.clone();
+        let view_tx = self.view_tx.clone();
+
+        tokio::spawn(async move {
+            while running.load(std::sync::atomic::Ordering::Relaxed) {
+                match rx.recv().await {
+                    Ok(AppEvent::User(UserEvent::ImportConversations { path })) => {
+                        Self::on_import(
+                            &event_bus,
+                            &import_service,
+                            &profile_service,
+                            &view_tx,
+                            PathBuf::from(path),
+                        )
+                        .await;
+                    }
+                    Ok(_) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("ImportPresenter lagged: {} events missed", n);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                        tracing::info!("ImportPresenter event stream closed");
+                        break;
+                    }
+                }
+            }
+            tracing::info!("ImportPresenter event loop ended");
+        });
+
+        Ok(())
+    }
+
+    /// Stop the presenter event loop
+    ///
+    /// # Errors
+    ///
+    /// Returns `PresenterError` if presenter shutdown becomes fallible in the future.
+    pub async fn stop(&mut self) -> Result<(), PresenterError> {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Check if presenter is running
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Imported conversations go under the default profile, or the first
+    /// profile if none is marked default.
+    async fn import_profile_id(profile_service: &Arc<dyn ProfileService>) -> Uuid {
+        if let Ok(Some(profile)) = profile_service.get_default().await {
+            return profile.id;
+        }
+        profile_service
+            .list()
+            .await
+            .ok()
+            .and_then(|profiles| profiles.first().map(|profile| profile.id))
+            .unwrap_or_else(Uuid::nil)
+    }
+
+    async fn on_import(
+        event_bus: &Arc<EventBus>,
+        import_service: &Arc<dyn ConversationImportService>,
+        profile_service: &Arc<dyn ProfileService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        path: PathBuf,
+    ) {
+        let profile_id = Self::import_profile_id(profile_service).await;
+        tracing::info!(path = %path.display(), "Importing conversations");
+
+        match import_service.import_file(&path, profile_id).await {
+            Ok(summary) => {
+                if summary.imported > 0 {
+                    let _ = event_bus.publish(AppEvent::User(UserEvent::RefreshConversations));
+                }
+                let _ = view_tx
+                    .send(ViewCommand::ConversationImportFinished { summary })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "Conversation import failed: {e}");
+                let _ = view_tx
+                    .send(ViewCommand::ConversationImportFailed {
+                        error: e.to_string(),
+                    })
+                    .await;
+            }
+        }
+    }
+}
+
+impl Presenter for ImportPresenter {
+    fn start(&mut self) -> Result<(), PresenterError> {
+        // Note: This is a sync wrapper - in real usage, call async start() directly
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), PresenterError> {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
@@ -38,15 +38,26 @@
 
 // Presenter modules
 pub mod api_key_manager_presenter;
+pub mod audit_presenter;
 pub mod chat_presenter;
+mod chat_presenter_branch;
+mod chat_presenter_checkpoints;
 mod chat_presenter_emoji;
 mod chat_presenter_event;
 
 mod chat_presenter_export;
 mod chat_presenter_handlers;
+mod chat_presenter_mcp;
+mod chat_presenter_organize;
+mod chat_presenter_search;
+mod chat_presenter_todos;
+mod chat_presenter_versions;
+mod conversation_archive;
 mod conversation_export;
+mod conversation_export_html;
 pub mod error_presenter;
 pub mod history_presenter;
+pub mod import_presenter;
 pub mod mcp_add_presenter;
 pub mod mcp_configure_presenter;
 pub mod model_selector_presenter;
@@ -55,7 +66,10 @@ pub mod settings_presenter;
 mod settings_presenter_backup;
 mod settings_presenter_launch_at_login;
 mod settings_presenter_mcp;
+mod settings_presenter_policy_overrides;
 mod settings_presenter_tool_approval;
+mod settings_presenter_workspace;
+pub mod usage_presenter;
 pub mod view_command;
 
 /// Presenter error type
@@ -99,14 +113,17 @@ pub trait Presenter: Send + Sync {
 }
 
 pub use api_key_manager_presenter::ApiKeyManagerPresenter;
+pub use audit_presenter::AuditPresenter;
 pub use chat_presenter::ChatPresenter;
 pub use conversation_export::render_export_content;
 pub use error_presenter::ErrorPresenter;
 pub use history_presenter::HistoryPresenter;
+pub use import_presenter::ImportPresenter;
 pub use mcp_add_presenter::McpAddPresenter;
 pub use mcp_configure_presenter::McpConfigurePresenter;
 pub use model_selector_presenter::ModelSelectorPresenter;
 pub use profile_editor_presenter::ProfileEditorPresenter;
 pub use settings_presenter::SettingsPresenter;
+pub use usage_presenter::UsagePresenter;
 /// Re-exports
 pub use view_command::ViewCommand;
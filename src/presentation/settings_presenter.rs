@@ -163,6 +163,8 @@ impl SettingsPresenter {
         Self::emit_theme_snapshot(&self.app_settings_service, &self.view_tx, None).await;
         Self::emit_font_settings_snapshot(&self.app_settings_service, &self.view_tx).await;
         Self::emit_tool_approval_policy_snapshot(&self.app_settings_service, &self.view_tx).await;
+        Self::emit_workspace_roots_snapshot(&self.app_settings_service, &self.view_tx).await;
+        Self::emit_policy_overrides_snapshot(&self.app_settings_service, &self.view_tx).await;
         Self::emit_skills_snapshot(&self.skills_service, &self.view_tx).await;
         Self::emit_launch_at_login_snapshot(
             &self.app_settings_service,
@@ -377,11 +379,15 @@ impl SettingsPresenter {
                 Self::emit_theme_snapshot(app_settings_service, view_tx, None).await;
                 Self::emit_font_settings_snapshot(app_settings_service, view_tx).await;
                 Self::emit_tool_approval_policy_snapshot(app_settings_service, view_tx).await;
+                Self::emit_workspace_roots_snapshot(app_settings_service, view_tx).await;
+                Self::emit_policy_overrides_snapshot(app_settings_service, view_tx).await;
                 Self::emit_skills_snapshot(skills_service, view_tx).await;
                 true
             }
             UserEvent::RefreshToolApprovalPolicy => {
                 Self::emit_tool_approval_policy_snapshot(app_settings_service, view_tx).await;
+                Self::emit_workspace_roots_snapshot(app_settings_service, view_tx).await;
+                Self::emit_policy_overrides_snapshot(app_settings_service, view_tx).await;
                 true
             }
             UserEvent::RefreshSkills => {
@@ -392,78 +398,6 @@ impl SettingsPresenter {
         }
     }
 
-    async fn handle_tool_approval_user_event(
-        app_settings_service: &Arc<dyn AppSettingsService>,
-        view_tx: &broadcast::Sender<ViewCommand>,
-        event: &UserEvent,
-    ) -> bool {
-        match event {
-            UserEvent::SetToolApprovalYoloMode { enabled } => {
-                Self::on_set_tool_approval_yolo_mode(app_settings_service, view_tx, *enabled).await;
-                true
-            }
-            UserEvent::SetToolApprovalAutoApproveReads { enabled } => {
-                Self::on_set_tool_approval_auto_approve_reads(
-                    app_settings_service,
-                    view_tx,
-                    *enabled,
-                )
-                .await;
-                true
-            }
-            UserEvent::SetToolApprovalSkillsAutoApprove { enabled } => {
-                Self::on_set_tool_approval_skills_auto_approve(
-                    app_settings_service,
-                    view_tx,
-                    *enabled,
-                )
-                .await;
-                true
-            }
-            UserEvent::SetToolApprovalMcpApprovalMode { mode } => {
-                Self::on_set_tool_approval_mcp_mode(app_settings_service, view_tx, *mode).await;
-                true
-            }
-            UserEvent::AddToolApprovalAllowlistPrefix { prefix } => {
-                Self::on_add_tool_approval_allowlist_prefix(
-                    app_settings_service,
-                    view_tx,
-                    prefix.clone(),
-                )
-                .await;
-                true
-            }
-            UserEvent::RemoveToolApprovalAllowlistPrefix { prefix } => {
-                Self::on_remove_tool_approval_allowlist_prefix(
-                    app_settings_service,
-                    view_tx,
-                    prefix.clone(),
-                )
-                .await;
-                true
-            }
-            UserEvent::AddToolApprovalDenylistPrefix { prefix } => {
-                Self::on_add_tool_approval_denylist_prefix(
-                    app_settings_service,
-                    view_tx,
-                    prefix.clone(),
-                )
-                .await;
-                true
-            }
-            UserEvent::RemoveToolApprovalDenylistPrefix { prefix } => {
-                Self::on_remove_tool_approval_denylist_prefix(
-                    app_settings_service,
-                    view_tx,
-                    prefix.clone(),
-                )
-                .await;
-                true
-            }
-            _ => false,
-        }
-    }
-
     async fn handle_skills_user_event(
         skills_service: &Arc<dyn SkillsService>,
         view_tx: &broadcast::Sender<ViewCommand>,
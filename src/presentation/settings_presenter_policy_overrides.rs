@@ -0,0 +1,66 @@
+//! Approval policy override handlers for `SettingsPresenter`.
+
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::settings_presenter::SettingsPresenter;
+use super::view_command::{ErrorSeverity, ViewCommand};
+use crate::agent::policy_overrides::{OverrideLayer, PolicyOverride, PolicyOverrides};
+use crate::services::app_settings::AppSettingsService;
+
+fn send_override_error(view_tx: &broadcast::Sender<ViewCommand>, message: &str) {
+    let _ = view_tx.send(ViewCommand::ShowError {
+        title: "Approval Overrides".to_string(),
+        message: message.to_string(),
+        severity: ErrorSeverity::Warning,
+    });
+}
+
+impl SettingsPresenter {
+    pub(super) async fn emit_policy_overrides_snapshot(
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &broadcast::Sender<ViewCommand>,
+    ) {
+        match PolicyOverrides::load_from_settings(app_settings_service.as_ref()).await {
+            Ok(overrides) => {
+                let _ = view_tx.send(ViewCommand::ApprovalPolicyOverridesUpdated { overrides });
+            }
+            Err(error) => {
+                tracing::warn!("Failed to load approval policy overrides snapshot: {error}");
+                send_override_error(view_tx, "Failed to load approval policy overrides");
+            }
+        }
+    }
+
+    pub(super) async fn on_set_policy_override(
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &broadcast::Sender<ViewCommand>,
+        layer: OverrideLayer,
+        id: Uuid,
+        policy_override: PolicyOverride,
+    ) {
+        let mut overrides =
+            match PolicyOverrides::load_from_settings(app_settings_service.as_ref()).await {
+                Ok(overrides) => overrides,
+                Err(error) => {
+                    tracing::warn!("Failed to load approval policy overrides for update: {error}");
+                    send_override_error(view_tx, "Failed to update approval policy overrides");
+                    return;
+                }
+            };
+
+        overrides.set(layer, id, policy_override);
+        if let Err(error) = overrides
+            .save_to_settings(app_settings_service.as_ref())
+            .await
+        {
+            tracing::warn!("Failed to persist approval policy overrides: {error}");
+            send_override_error(view_tx, "Failed to persist approval policy overrides");
+            return;
+        }
+
+        Self::emit_policy_overrides_snapshot(app_settings_service, view_tx).await;
+    }
+}
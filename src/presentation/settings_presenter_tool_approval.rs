@@ -7,9 +7,116 @@ use tokio::sync::broadcast;
 use super::settings_presenter::SettingsPresenter;
 use super::view_command::ViewCommand;
 use crate::agent::tool_approval_policy::{McpApprovalMode, ToolApprovalPolicy};
+use crate::events::types::UserEvent;
 use crate::services::app_settings::AppSettingsService;
 
 impl SettingsPresenter {
+    pub(super) async fn handle_tool_approval_user_event(
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &broadcast::Sender<ViewCommand>,
+        event: &UserEvent,
+    ) -> bool {
+        match event {
+            UserEvent::SetToolApprovalYoloMode { enabled } => {
+                Self::on_set_tool_approval_yolo_mode(app_settings_service, view_tx, *enabled).await;
+                true
+            }
+            UserEvent::SetToolApprovalAutoApproveReads { enabled } => {
+                Self::on_set_tool_approval_auto_approve_reads(
+                    app_settings_service,
+                    view_tx,
+                    *enabled,
+                )
+                .await;
+                true
+            }
+            UserEvent::SetToolApprovalSkillsAutoApprove { enabled } => {
+                Self::on_set_tool_approval_skills_auto_approve(
+                    app_settings_service,
+                    view_tx,
+                    *enabled,
+                )
+                .await;
+                true
+            }
+            UserEvent::SetToolApprovalMcpApprovalMode { mode } => {
+                Self::on_set_tool_approval_mcp_mode(app_settings_service, view_tx, *mode).await;
+                true
+            }
+            UserEvent::AddToolApprovalAllowlistPrefix { prefix } => {
+                Self::on_add_tool_approval_allowlist_prefix(
+                    app_settings_service,
+                    view_tx,
+                    prefix.clone(),
+                )
+                .await;
+                true
+            }
+            UserEvent::RemoveToolApprovalAllowlistPrefix { prefix } => {
+                Self::on_remove_tool_approval_allowlist_prefix(
+                    app_settings_service,
+                    view_tx,
+                    prefix.clone(),
+                )
+                .await;
+                true
+            }
+            UserEvent::AddToolApprovalDenylistPrefix { prefix } => {
+                Self::on_add_tool_approval_denylist_prefix(
+                    app_settings_service,
+                    view_tx,
+                    prefix.clone(),
+                )
+                .await;
+                true
+            }
+            UserEvent::RemoveToolApprovalDenylistPrefix { prefix } => {
+                Self::on_remove_tool_approval_denylist_prefix(
+                    app_settings_service,
+                    view_tx,
+                    prefix.clone(),
+                )
+                .await;
+                true
+            }
+            UserEvent::AddToolApprovalRule { .. }
+            | UserEvent::RemoveToolApprovalRule { .. }
+            | UserEvent::ConvertToolApprovalPrefixesToRules => {
+                Self::on_tool_approval_rule_event(app_settings_service, view_tx, event).await;
+                true
+            }
+            UserEvent::AddWorkspaceRoot { profile_id, path }
+            | UserEvent::RemoveWorkspaceRoot { profile_id, path } => {
+                let add = matches!(event, UserEvent::AddWorkspaceRoot { .. });
+                Self::on_update_workspace_root(
+                    app_settings_service,
+                    view_tx,
+                    *profile_id,
+                    path,
+                    add,
+                )
+                .await;
+                true
+            }
+            UserEvent::SetApprovalPolicyOverride {
+                layer,
+                id,
+                policy_override,
+            } => {
+                Self::on_set_policy_override(
+                    app_settings_service,
+                    view_tx,
+                    *layer,
+                    *id,
+                    policy_override.clone(),
+                )
+                .await;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub(super) async fn emit_tool_approval_policy_snapshot(
         app_settings_service: &Arc<dyn AppSettingsService>,
         view_tx: &broadcast::Sender<ViewCommand>,
@@ -23,6 +130,7 @@ impl SettingsPresenter {
                     mcp_approval_mode: policy.mcp_approval_mode,
                     persistent_allowlist: policy.persistent_allowlist,
                     persistent_denylist: policy.persistent_denylist,
+                    rules: policy.rules,
                 });
                 let _ = view_tx.send(ViewCommand::YoloModeChanged {
                     active: policy.yolo_mode,
@@ -332,4 +440,57 @@ impl SettingsPresenter {
 
         Self::emit_tool_approval_policy_snapshot(app_settings_service, view_tx).await;
     }
+
+    /// Add or remove a structured rule, or convert the prefix lists to rules.
+    pub(super) async fn on_tool_approval_rule_event(
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &broadcast::Sender<ViewCommand>,
+        event: &UserEvent,
+    ) {
+        let mut policy =
+            match ToolApprovalPolicy::load_from_settings(app_settings_service.as_ref()).await {
+                Ok(policy) => policy,
+                Err(error) => {
+                    tracing::warn!("Failed to load tool approval policy for rule update: {error}");
+                    let _ = view_tx.send(ViewCommand::ShowError {
+                        title: "Tool Approval Settings".to_string(),
+                        message: "Failed to update approval rules".to_string(),
+                        severity: super::view_command::ErrorSeverity::Warning,
+                    });
+                    return;
+                }
+            };
+
+        let result = match event {
+            UserEvent::AddToolApprovalRule { rule } => {
+                policy
+                    .add_rule(rule.clone(), app_settings_service.as_ref())
+                    .await
+            }
+            UserEvent::RemoveToolApprovalRule { rule } => {
+                policy
+                    .remove_rule(rule, app_settings_service.as_ref())
+                    .await
+            }
+            UserEvent::ConvertToolApprovalPrefixesToRules => {
+                if policy.migrate_prefix_entries() == 0 {
+                    return;
+                }
+                policy.save_to_settings(app_settings_service.as_ref()).await
+            }
+            _ => return,
+        };
+
+        if let Err(error) = result {
+            tracing::warn!("Failed to persist approval rules: {error}");
+            let _ = view_tx.send(ViewCommand::ShowError {
+                title: "Tool Approval Settings".to_string(),
+                message: "Failed to persist approval rules".to_string(),
+                severity: super::view_command::ErrorSeverity::Warning,
+            });
+            return;
+        }
+
+        Self::emit_tool_approval_policy_snapshot(app_settings_service, view_tx).await;
+    }
 }
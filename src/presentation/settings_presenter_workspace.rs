@@ -0,0 +1,82 @@
+//! Workspace root handlers for `SettingsPresenter`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::settings_presenter::SettingsPresenter;
+use super::view_command::{ErrorSeverity, ViewCommand};
+use crate::agent::workspace::WorkspaceSettings;
+use crate::services::app_settings::AppSettingsService;
+
+fn send_workspace_error(view_tx: &broadcast::Sender<ViewCommand>, message: &str) {
+    let _ = view_tx.send(ViewCommand::ShowError {
+        title: "Workspace Roots".to_string(),
+        message: message.to_string(),
+        severity: ErrorSeverity::Warning,
+    });
+}
+
+impl SettingsPresenter {
+    pub(super) async fn emit_workspace_roots_snapshot(
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &broadcast::Sender<ViewCommand>,
+    ) {
+        match WorkspaceSettings::load_from_settings(app_settings_service.as_ref()).await {
+            Ok(settings) => {
+                let _ = view_tx.send(ViewCommand::WorkspaceRootsUpdated {
+                    roots: settings.display_roots(),
+                });
+            }
+            Err(error) => {
+                tracing::warn!("Failed to load workspace roots snapshot: {error}");
+                send_workspace_error(view_tx, "Failed to load workspace roots");
+            }
+        }
+    }
+
+    pub(super) async fn on_update_workspace_root(
+        app_settings_service: &Arc<dyn AppSettingsService>,
+        view_tx: &broadcast::Sender<ViewCommand>,
+        profile_id: Uuid,
+        path: &str,
+        add: bool,
+    ) {
+        let mut settings =
+            match WorkspaceSettings::load_from_settings(app_settings_service.as_ref()).await {
+                Ok(settings) => settings,
+                Err(error) => {
+                    tracing::warn!("Failed to load workspace roots for update: {error}");
+                    send_workspace_error(view_tx, "Failed to update workspace roots");
+                    return;
+                }
+            };
+
+        let path = PathBuf::from(path.trim());
+        if add && !path.is_absolute() {
+            send_workspace_error(view_tx, "Workspace roots must be absolute paths");
+            return;
+        }
+        let changed = if add {
+            settings.add_root(profile_id, path)
+        } else {
+            settings.remove_root(profile_id, &path)
+        };
+        if !changed {
+            return;
+        }
+
+        if let Err(error) = settings
+            .save_to_settings(app_settings_service.as_ref())
+            .await
+        {
+            tracing::warn!("Failed to persist workspace roots: {error}");
+            send_workspace_error(view_tx, "Failed to persist workspace roots");
+            return;
+        }
+
+        Self::emit_workspace_roots_snapshot(app_settings_service, view_tx).await;
+    }
+}
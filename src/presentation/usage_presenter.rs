@@ -0,0 +1,265 @@
+//! `UsagePresenter` - token usage and spend for the chat and settings views
+//!
+//! `UsagePresenter` reads the usage ledger and emits conversation totals when
+//! a conversation is selected or a turn completes, and the per-profile and
+//! per-day summary for the settings usage panel.
+
+use std::sync::Arc;
+
+use chrono::{Days, Utc};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::view_command::ProfileUsageSummary;
+use super::{Presenter, PresenterError, ViewCommand};
+use crate::events::bus::EventBus;
+use crate::events::{
+    types::{ChatEvent, UserEvent},
+    AppEvent,
+};
+use crate::models::{ModelProfile, ProfileUsage};
+use crate::services::{ProfileService, UsageService};
+
+/// Days shown in the settings usage panel, including today.
+const SUMMARY_DAYS: u64 = 30;
+
+/// Name shown for usage recorded under a profile that no longer exists.
+const DELETED_PROFILE_NAME: &str = "Deleted profile";
+
+/// Attach profile names to per-profile totals.
+fn profile_summaries(
+    usage: Vec<ProfileUsage>,
+    profiles: &[ModelProfile],
+) -> Vec<ProfileUsageSummary> {
+    usage
+        .into_iter()
+        .map(|entry| ProfileUsageSummary {
+            profile_id: entry.profile_id,
+            profile_name: profiles
+                .iter()
+                .find(|profile| profile.id == entry.profile_id)
+                .map_or_else(|| DELETED_PROFILE_NAME.to_string(), |p| p.name.clone()),
+            totals: entry.totals,
+        })
+        .collect()
+}
+
+/// `UsagePresenter` - usage ledger reporting
+pub struct UsagePresenter {
+    /// Reference to event bus for subscribing to events
+    event_bus: Arc<EventBus>,
+
+    /// Usage ledger
+    usage_service: Arc<dyn UsageService>,
+
+    /// Profile names for the settings summary
+    profile_service: Arc<dyn ProfileService>,
+
+    /// View command sender (mpsc for reliable delivery)
+    view_tx: mpsc::Sender<ViewCommand>,
+
+    /// Running flag for event loop
+    running: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl UsagePresenter {
+    /// Create a new `UsagePresenter`
+    pub fn new(
+        event_bus: Arc<EventBus>,
+        usage_service: Arc<dyn UsageService>,
+        profile_service: Arc<dyn ProfileService>,
+        view_tx: mpsc::Sender<ViewCommand>,
+    ) -> Self {
+        Self {
+            event_bus,
+            usage_service,
+            profile_service,
+            view_tx,
+            running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+
+    /// Start the presenter event loop and emit the initial usage summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PresenterError` if presenter startup becomes fallible in the future.
+    pub async fn start(&mut self) -> Result<(), PresenterError> {
+        if self.running.load(std::sync::atomic::Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        self.running
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let mut rx = self.event_bus.subscribe();
+        let running = self.running.clone();
+        let usage_service = self.usage_service.clone();
+        let profile_service = self.profile_service.clone();
+        let view_tx = self.view_tx.clone();
+
+        Self::emit_summary(&usage_service, &profile_service, &view_tx).await;
+
+        tokio::spawn(async move {
+            while running.load(std::sync::atomic::Ordering::Relaxed) {
+                match rx.recv().await {
+                    Ok(event) => {
+                        Self::handle_event(&usage_service, &profile_service, &view_tx, event).await;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("UsagePresenter lagged: {} events missed", n);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                        tracing::info!("UsagePresenter event stream closed");
+                        break;
+                    }
+                }
+            }
+            tracing::info!("UsagePresenter event loop ended");
+        });
+
+        Ok(())
+    }
+
+    /// Stop the presenter event loop
+    ///
+    /// # Errors
+    ///
+    /// Returns `PresenterError` if presenter shutdown becomes fallible in the future.
+    pub async fn stop(&mut self) -> Result<(), PresenterError> {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Check if presenter is running
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    async fn handle_event(
+        usage_service: &Arc<dyn UsageService>,
+        profile_service: &Arc<dyn ProfileService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        event: AppEvent,
+    ) {
+        match event {
+            AppEvent::User(UserEvent::SelectConversation { id, .. }) => {
+                Self::emit_conversation_usage(usage_service, view_tx, id).await;
+            }
+            // The turn is recorded before `StreamCompleted` is published.
+            AppEvent::Chat(ChatEvent::StreamCompleted {
+                conversation_id, ..
+            }) => {
+                Self::emit_conversation_usage(usage_service, view_tx, conversation_id).await;
+                Self::emit_summary(usage_service, profile_service, view_tx).await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn emit_conversation_usage(
+        usage_service: &Arc<dyn UsageService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+        conversation_id: Uuid,
+    ) {
+        match usage_service.conversation_totals(conversation_id).await {
+            Ok(totals) => {
+                let _ = view_tx
+                    .send(ViewCommand::ConversationUsageUpdated {
+                        conversation_id,
+                        totals,
+                    })
+                    .await;
+            }
+            Err(e) => {
+                tracing::warn!(%conversation_id, "Failed to load conversation usage: {e}");
+            }
+        }
+    }
+
+    async fn emit_summary(
+        usage_service: &Arc<dyn UsageService>,
+        profile_service: &Arc<dyn ProfileService>,
+        view_tx: &mpsc::Sender<ViewCommand>,
+    ) {
+        let since = Utc::now()
+            .date_naive()
+            .checked_sub_days(Days::new(SUMMARY_DAYS - 1))
+            .unwrap_or_default();
+        let (by_profile, by_day) = match (
+            usage_service.profile_totals().await,
+            usage_service.daily_totals(since).await,
+        ) {
+            (Ok(by_profile), Ok(by_day)) => (by_profile, by_day),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::warn!("Failed to load usage summary: {e}");
+                return;
+            }
+        };
+        let profiles = profile_service.list().await.unwrap_or_default();
+
+        let _ = view_tx
+            .send(ViewCommand::UsageSummaryLoaded {
+                by_profile: profile_summaries(by_profile, &profiles),
+                by_day,
+            })
+            .await;
+    }
+}
+
+impl Presenter for UsagePresenter {
+    fn start(&mut self) -> Result<(), PresenterError> {
+        // Note: This is a sync wrapper - in real usage, call async start() directly
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), PresenterError> {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UsageTotals;
+
+    #[test]
+    fn summaries_name_profiles_and_mark_deleted_ones() {
+        let kept = ModelProfile {
+            name: "Work".to_string(),
+            ..ModelProfile::default()
+        };
+        let gone = Uuid::new_v4();
+        let totals = UsageTotals {
+            turns: 3,
+            ..UsageTotals::default()
+        };
+
+        let summaries = profile_summaries(
+            vec![
+                ProfileUsage {
+                    profile_id: kept.id,
+                    totals,
+                },
+                ProfileUsage {
+                    profile_id: gone,
+                    totals: UsageTotals::default(),
+                },
+            ],
+            &[kept.clone()],
+        );
+
+        assert_eq!(summaries[0].profile_name, "Work");
+        assert_eq!(summaries[0].totals.turns, 3);
+        assert_eq!(summaries[1].profile_id, gone);
+        assert_eq!(summaries[1].profile_name, DELETED_PROFILE_NAME);
+    }
+}
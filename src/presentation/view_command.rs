@@ -10,8 +10,10 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::agent::McpApprovalMode;
-use crate::models::ConversationExportFormat;
+use crate::agent::policy_overrides::PolicyOverrides;
+use crate::agent::{ApprovalRule, McpApprovalMode};
+use crate::mcp::{McpPrompt, McpResource};
+use crate::models::{ConversationExportFormat, DailyUsage, TodoItem, ToolAuditEntry, UsageTotals};
 
 /// Application window mode — popup (tray-anchored) or popout (free-floating).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -163,6 +165,72 @@ pub enum ViewCommand {
     /// Conversation title was updated
     ConversationTitleUpdated { id: Uuid, title: String },
 
+    /// A branch was forked from `source_id`; the view should switch to it.
+    ConversationBranched { source_id: Uuid, branch_id: Uuid },
+
+    /// Branch tree containing the selected conversation, root first.
+    ConversationBranchesLoaded {
+        conversation_id: Uuid,
+        branches: Vec<ConversationBranchSummary>,
+    },
+
+    /// The selected conversation's transcript was rewritten in place
+    /// (regenerate, edit-and-resend, or a version switch).
+    ConversationTranscriptReplaced {
+        conversation_id: Uuid,
+        messages: Vec<ConversationMessagePayload>,
+    },
+
+    /// Messages in the selected conversation that have alternate versions.
+    MessageVersionsLoaded {
+        conversation_id: Uuid,
+        versions: Vec<MessageVersionSummary>,
+    },
+
+    /// Turns in the selected conversation whose file changes can be reverted.
+    TurnCheckpointsLoaded {
+        conversation_id: Uuid,
+        checkpoints: Vec<TurnCheckpointSummary>,
+    },
+
+    /// Background shell processes of a conversation, oldest first.
+    BackgroundProcessesUpdated {
+        conversation_id: Uuid,
+        processes: Vec<BackgroundProcessSummary>,
+    },
+
+    /// Progress of a sub-agent started by the `Delegate` tool.
+    SubAgentUpdated {
+        conversation_id: Uuid,
+        agent: SubAgentSummary,
+    },
+
+    /// The conversation's `TodoWrite` task list, in order.
+    TodoListUpdated {
+        conversation_id: Uuid,
+        todos: Vec<TodoItem>,
+    },
+
+    /// Resources and prompts of the running MCP servers, for the composer's
+    /// picker.
+    McpCatalogLoaded {
+        resources: Vec<McpResource>,
+        prompts: Vec<McpPrompt>,
+    },
+
+    /// Text of an attached resource or rendered prompt, to insert into the
+    /// composer.
+    McpComposerTextReady { text: String },
+
+    /// Reading a resource or rendering a prompt for the composer failed.
+    McpComposerTextFailed { error: String },
+
+    /// Token usage and spend recorded for a conversation.
+    ConversationUsageUpdated {
+        conversation_id: Uuid,
+        totals: UsageTotals,
+    },
+
     // ===== Settings Commands =====
     /// Show settings view
     ShowSettings {
@@ -199,6 +267,12 @@ pub enum ViewCommand {
     /// Error log was successfully exported to disk.
     ErrorLogExportCompleted { path: String },
 
+    /// Several conversations were written to one archive file.
+    ConversationArchiveExported { path: String, count: usize },
+
+    /// Archive export failed.
+    ConversationArchiveFailed { error: String },
+
     /// Profile was created
     ProfileCreated { id: Uuid, name: String },
 
@@ -354,11 +428,27 @@ pub enum ViewCommand {
         mcp_approval_mode: McpApprovalMode,
         persistent_allowlist: Vec<String>,
         persistent_denylist: Vec<String>,
+        rules: Vec<ApprovalRule>,
     },
 
     /// Request presenters/views to refresh tool approval settings from persistence.
     RefreshToolApprovalSettings,
 
+    /// Persisted workspace roots per profile, as display paths.
+    WorkspaceRootsUpdated { roots: Vec<(Uuid, Vec<String>)> },
+
+    /// Persisted profile and conversation approval policy overrides.
+    ApprovalPolicyOverridesUpdated { overrides: PolicyOverrides },
+
+    /// Usage totals per profile and per recent day for the settings usage panel.
+    UsageSummaryLoaded {
+        by_profile: Vec<ProfileUsageSummary>,
+        by_day: Vec<DailyUsage>,
+    },
+
+    /// Tool audit records matching the settings audit filter, newest first.
+    ToolAuditLoaded { entries: Vec<ToolAuditEntry> },
+
     // ===== Database Backup Commands =====
     /// Backup settings and list loaded for settings view
     BackupSettingsLoaded {
@@ -386,6 +476,15 @@ pub enum ViewCommand {
     /// Clear the active conversation (e.g., after restore if conversation no longer exists)
     ClearActiveConversation,
 
+    // ===== Import Commands =====
+    /// Conversation import finished
+    ConversationImportFinished {
+        summary: crate::services::ImportSummary,
+    },
+
+    /// Conversation import could not read the file
+    ConversationImportFailed { error: String },
+
     // ===== Error Commands =====
     /// Show error to user
     ShowError {
@@ -426,6 +525,13 @@ pub enum ToolCategory {
     Shell,
     /// MCP tool execution
     Mcp,
+    /// File or shell access outside the workspace roots
+    OutsideWorkspace,
+    /// Web request (`WebFetch`)
+    Web,
+    /// A turn reached one of its tool budget limits; answered with continue
+    /// or stop rather than an approval
+    Budget,
 }
 
 /// Structured context for tool approval requests.
@@ -444,6 +550,9 @@ pub struct ToolApprovalContext {
     pub details: Vec<(String, String)>,
     /// For MCP tools, the server name
     pub server_name: Option<String>,
+    /// Unified diff of the change, for file edits and writes
+    #[serde(default)]
+    pub diff: Option<String>,
 }
 
 impl ToolApprovalContext {
@@ -460,6 +569,7 @@ impl ToolApprovalContext {
             primary_target: primary_target.into(),
             details: Vec::new(),
             server_name: None,
+            diff: None,
         }
     }
 
@@ -470,6 +580,16 @@ impl ToolApprovalContext {
         self
     }
 
+    /// Attach a unified diff of the change; an empty diff is ignored.
+    #[must_use]
+    pub fn with_diff(mut self, diff: impl Into<String>) -> Self {
+        let diff = diff.into();
+        if !diff.is_empty() {
+            self.diff = Some(diff);
+        }
+        self
+    }
+
     /// Set the server name (for MCP tools).
     #[must_use]
     pub fn with_server_name(mut self, server_name: impl Into<String>) -> Self {
@@ -508,6 +628,87 @@ pub struct ConversationSummary {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub message_count: usize,
     pub preview: Option<String>,
+    /// Conversation this one was branched from, if any.
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Pinned/archived state, folder, and tags.
+    #[serde(default)]
+    pub organization: crate::models::ConversationOrganization,
+}
+
+/// One conversation in a branch tree, for the chat view branch strip.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationBranchSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub parent_id: Option<Uuid>,
+    /// Visible message index in the parent where this branch was forked.
+    pub forked_at_index: Option<usize>,
+    pub message_count: usize,
+}
+
+/// Version switcher state for one message in the chat view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageVersionSummary {
+    /// Visible message index the versions are anchored at.
+    pub message_index: usize,
+    /// Version currently shown, or `None` if the live tail is empty.
+    pub selected: Option<u32>,
+    pub count: u32,
+}
+
+/// Files one assistant turn changed, anchored at the message it produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnCheckpointSummary {
+    /// Visible index of the turn's reply (or its prompt if no reply was stored).
+    pub message_index: usize,
+    pub turn_id: Uuid,
+    pub files: Vec<String>,
+}
+
+/// Where a background shell process stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackgroundProcessState {
+    Running,
+    /// Exited on its own; `code` is `None` when it was ended by a signal.
+    Exited {
+        code: Option<i32>,
+    },
+    /// Killed by a tool call, the user, or a cancelled stream.
+    Killed,
+}
+
+/// A background shell process started by `ShellExec`, as listed in the chat view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackgroundProcessSummary {
+    pub id: String,
+    pub command: String,
+    pub state: BackgroundProcessState,
+}
+
+/// Where a sub-agent stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubAgentState {
+    Running,
+    /// Returned its report, possibly cut short by its turn limit.
+    Finished,
+    Failed,
+    /// Dropped before finishing, e.g. because the stream was cancelled.
+    Stopped,
+}
+
+/// A sub-agent started by the `Delegate` tool, as shown in the chat view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubAgentSummary {
+    pub id: Uuid,
+    pub description: String,
+    pub profile_name: String,
+    pub state: SubAgentState,
+    /// Model responses so far, counting the one in progress.
+    pub turns: u32,
+    pub max_turns: u32,
+    pub tool_calls: u32,
+    pub last_tool: Option<String>,
 }
 
 /// A single search result for the sidebar conversation search.
@@ -519,6 +720,24 @@ pub struct ConversationSearchResult {
     pub match_context: String,
     pub message_count: usize,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Byte ranges of `title` matched by the query.
+    #[serde(default)]
+    pub title_highlights: Vec<std::ops::Range<usize>>,
+    /// Byte ranges of `match_context` matched by the query.
+    #[serde(default)]
+    pub context_highlights: Vec<std::ops::Range<usize>>,
+    /// Visible message to jump to when the result is opened.
+    #[serde(default)]
+    pub message_index: Option<usize>,
+}
+
+/// Usage totals for one profile in the settings usage panel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileUsageSummary {
+    pub profile_id: Uuid,
+    /// Profile name, or a placeholder once the profile has been deleted.
+    pub profile_name: String,
+    pub totals: UsageTotals,
 }
 
 /// Summary of a stored API key for the key manager UI.
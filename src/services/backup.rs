@@ -20,6 +20,17 @@ pub trait BackupService: Send + Sync {
     /// Returns `BackupResult::Skipped` if no changes have occurred since last backup.
     async fn create_backup(&self) -> ServiceResult<BackupResult>;
 
+    /// Create a backup before schema migrations from `from_version` to
+    /// `to_version` run
+    ///
+    /// Unlike `create_backup`, this ignores the enabled flag and change
+    /// detection: it is the only way back if a migration goes wrong.
+    async fn create_pre_migration_backup(
+        &self,
+        from_version: u32,
+        to_version: u32,
+    ) -> ServiceResult<BackupResult>;
+
     /// List all available backups
     ///
     /// Returns metadata about each backup file including path, timestamp, and size.
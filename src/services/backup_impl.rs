@@ -449,6 +449,29 @@ impl BackupService for BackupServiceImpl {
         })
     }
 
+    async fn create_pre_migration_backup(
+        &self,
+        from_version: u32,
+        to_version: u32,
+    ) -> ServiceResult<BackupResult> {
+        let start = std::time::Instant::now();
+        let (backup_path, _timestamp) = self.prepare_backup_target().await?;
+        self.perform_sqlite_backup(backup_path.clone()).await?;
+
+        let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+        tracing::info!(
+            from_version,
+            to_version,
+            "Created pre-migration backup at {}",
+            backup_path.display()
+        );
+
+        Ok(BackupResult::Success {
+            path: backup_path,
+            duration_ms,
+        })
+    }
+
     async fn list_backups(&self) -> ServiceResult<Vec<BackupInfo>> {
         let backup_dir = self.backup_dir().await?;
         self.list_backups_internal(&backup_dir).await
@@ -32,6 +32,28 @@ pub trait ChatService: Send + Sync {
         content: String,
     ) -> ServiceResult<Box<dyn futures::Stream<Item = ChatStreamEvent> + Send + Unpin>>;
 
+    /// Generate a new assistant response at message `seq`.
+    ///
+    /// Messages from `seq` onward are kept as an alternate version. When
+    /// `seq` is one past the last message (a prompt that never got a reply),
+    /// the response is simply retried.
+    async fn regenerate(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<Box<dyn futures::Stream<Item = ChatStreamEvent> + Send + Unpin>>;
+
+    /// Replace the user prompt at message `seq` with `content` and resend it.
+    ///
+    /// The original prompt and everything after it are kept as an alternate
+    /// version.
+    async fn edit_and_resend(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+        content: String,
+    ) -> ServiceResult<Box<dyn futures::Stream<Item = ChatStreamEvent> + Send + Unpin>>;
+
     /// Cancel a conversation's active stream. @plan PLAN-20260416-ISSUE173.P03 @requirement REQ-173-002.1
     fn cancel(&self, conversation_id: Uuid);
     /// Any stream active? @plan PLAN-20260416-ISSUE173.P03 @requirement REQ-173-001.1
@@ -1,8 +1,12 @@
 use super::{ChatService, ChatStreamEvent, ServiceError, ServiceResult};
+use crate::agent::checkpoints::{CheckpointStore, TurnCheckpoints};
+use crate::agent::delegation::DelegationContext;
 use crate::agent::tool_approval_policy::ToolApprovalPolicy;
-use crate::compression::pipeline::{CompressionPipeline, CompressionResult};
+use crate::agent::tool_budget::ToolBudgetLimits;
+use crate::agent::workspace::{WorkspaceRoots, WorkspaceSettings};
+use crate::compression::phases::llm_summary::{ContextSummarizer, LlmContextSummarizer};
+use crate::compression::pipeline::CompressionResult;
 use crate::compression::thinking_stripper::strip_thinking_from_previous_turns;
-use crate::config::CompressionConfig;
 use crate::events::types::{ChatEvent, ToolApprovalResponseAction};
 use crate::events::{emit, AppEvent};
 use crate::llm::client_agent::ApprovalGate;
@@ -15,22 +19,26 @@ use crate::services::conversation_title::{
     ConversationTitleGenerator, DisabledConversationTitleGenerator, LlmConversationTitleGenerator,
 };
 use crate::services::template::{expand_system_prompt, TemplateContext};
-use crate::services::{ConversationService, SkillsService};
+use crate::services::{
+    ConversationService, SkillsService, TodoService, ToolAuditService, UsageService,
+};
 use crate::ui_gpui::error_log::ErrorLogStreamLifecycle;
-use futures::{stream, Stream};
 use std::collections::HashMap;
-use std::pin::Pin;
 use std::sync::{Arc, Mutex as StdMutex};
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+mod approval;
+mod compression;
 mod prompt;
 mod streaming;
 mod titling;
+mod turn;
+pub(crate) mod usage;
 
-use prompt::{build_system_prompt, filter_emoji_setting};
+use prompt::{append_todo_list, build_system_prompt, filter_emoji_setting};
 use streaming::{
     build_stream_error_diagnostics, clear_streaming_state, emit_stream_error, run_stream_task,
     StreamDiagnosticContext, StreamTranscript, STREAM_ERROR_MESSAGE,
@@ -41,8 +49,6 @@ use titling::{generate_and_apply_title, TitleGenerationRequest};
 #[cfg(test)]
 use streaming::persist_context_state;
 
-const COMPRESSION_SETTINGS_KEY: &str = "compression";
-
 /// Lifecycle state of an active stream.
 /// @plan PLAN-20260416-ISSUE173.P14-CR3
 pub(super) enum StreamLifecycle {
@@ -68,6 +74,8 @@ pub(super) struct ActiveStream {
     pub(super) cancel: CancellationToken,
     /// Current lifecycle state of the stream.
     pub(super) state: StreamLifecycle,
+    /// Effective approval policy of the running turn. None while in Starting state.
+    pub(super) policy: Option<Arc<AsyncMutex<ToolApprovalPolicy>>>,
 }
 
 pub struct ChatServiceImpl {
@@ -87,6 +95,16 @@ pub struct ChatServiceImpl {
     policy: Arc<AsyncMutex<ToolApprovalPolicy>>,
     /// Names untitled conversations from their first prompt.
     title_generator: Arc<dyn ConversationTitleGenerator>,
+    /// Ledger for per-turn token usage; `None` disables recording.
+    usage_service: Option<Arc<dyn UsageService>>,
+    /// Writes model summaries when the compression settings name a summarizer.
+    context_summarizer: Arc<dyn ContextSummarizer>,
+    /// Snapshots files before the built-in tools write them; `None` disables undo.
+    checkpoint_store: Option<Arc<CheckpointStore>>,
+    /// Stores `TodoWrite` task lists; `None` leaves the tool unavailable.
+    todo_service: Option<Arc<dyn TodoService>>,
+    /// Audit log for tool calls; `None` disables recording.
+    audit_service: Option<Arc<dyn ToolAuditService>>,
 }
 
 impl ChatServiceImpl {
@@ -114,6 +132,11 @@ impl ChatServiceImpl {
             approval_gate,
             policy,
             title_generator: Arc::new(LlmConversationTitleGenerator),
+            usage_service: None,
+            context_summarizer: Arc::new(LlmContextSummarizer),
+            checkpoint_store: None,
+            todo_service: None,
+            audit_service: None,
         }
     }
 
@@ -126,6 +149,43 @@ impl ChatServiceImpl {
         self
     }
 
+    /// Replace the model used for context summaries.
+    ///
+    /// Lets tests script the summarizer instead of reaching an LLM provider.
+    #[must_use]
+    pub fn with_context_summarizer(mut self, summarizer: Arc<dyn ContextSummarizer>) -> Self {
+        self.context_summarizer = summarizer;
+        self
+    }
+
+    /// Record the token usage of every completed turn in `usage_service`.
+    #[must_use]
+    pub fn with_usage_service(mut self, usage_service: Arc<dyn UsageService>) -> Self {
+        self.usage_service = Some(usage_service);
+        self
+    }
+
+    /// Checkpoint files in `store` before each native write or edit.
+    #[must_use]
+    pub fn with_checkpoint_store(mut self, store: Arc<CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
+    /// Keep each conversation's `TodoWrite` task list in `todo_service`.
+    #[must_use]
+    pub fn with_todo_service(mut self, todo_service: Arc<dyn TodoService>) -> Self {
+        self.todo_service = Some(todo_service);
+        self
+    }
+
+    /// Record every tool call of every turn in `audit_service`.
+    #[must_use]
+    pub fn with_audit_service(mut self, audit_service: Arc<dyn ToolAuditService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
     /// Build a fully wired service using settings-backed approval policy state.
     pub async fn new_with_settings(
         conversation_service: Arc<dyn ConversationService>,
@@ -187,28 +247,6 @@ impl ChatServiceImpl {
         .with_title_generator(Arc::new(DisabledConversationTitleGenerator))
     }
 
-    async fn refresh_tool_approval_policy_from_settings(&self) {
-        match ToolApprovalPolicy::load_from_settings(self.app_settings_service.as_ref()).await {
-            Ok(mut loaded_policy) => {
-                let mut policy = self.policy.lock().await;
-                let should_clear_session_allowlist = policy.yolo_mode && !loaded_policy.yolo_mode;
-
-                if should_clear_session_allowlist {
-                    loaded_policy.clear_session_allowlist();
-                } else {
-                    loaded_policy
-                        .session_allowlist
-                        .clone_from(&policy.session_allowlist);
-                }
-
-                *policy = loaded_policy;
-            }
-            Err(error) => {
-                tracing::warn!("Failed to refresh tool approval policy before send: {error}");
-            }
-        }
-    }
-
     /// Reserve a slot for a new stream for a specific conversation.
     ///
     /// Returns `(stream_id, cancel_token)` on success, or
@@ -247,6 +285,7 @@ impl ChatServiceImpl {
                     task: None,
                     cancel: cancel.clone(),
                     state: StreamLifecycle::Starting,
+                    policy: None,
                 },
             );
         }
@@ -269,31 +308,6 @@ impl ChatServiceImpl {
         }
     }
 
-    async fn load_compression_config(&self) -> CompressionConfig {
-        match self
-            .app_settings_service
-            .get_setting(COMPRESSION_SETTINGS_KEY)
-            .await
-        {
-            Ok(Some(raw_config)) => serde_json::from_str::<CompressionConfig>(&raw_config)
-                .unwrap_or_else(|error| {
-                    tracing::warn!(
-                        error = %error,
-                        "Failed to parse persisted compression config; using defaults"
-                    );
-                    CompressionConfig::default()
-                }),
-            Ok(None) => CompressionConfig::default(),
-            Err(error) => {
-                tracing::warn!(
-                    error = %error,
-                    "Failed to load persisted compression config; using defaults"
-                );
-                CompressionConfig::default()
-            }
-        }
-    }
-
     /// Cancel the active stream for a specific conversation.
     ///
     /// Always resolves any pending approvals for the target conversation and emits
@@ -317,6 +331,8 @@ impl ChatServiceImpl {
                 handle.abort();
             }
         }
+        crate::agent::processes::BackgroundProcesses::global().kill_conversation(conversation_id);
+        crate::agent::shell_sessions::ShellSessions::global().close(conversation_id);
 
         // @plan PLAN-20260416-ISSUE173.P07
         // @requirement REQ-173-003.3
@@ -357,6 +373,17 @@ impl ChatServiceImpl {
             .add_message(conversation_id, Message::user(content))
             .await?;
 
+        self.prepare_turn_context(conversation_id).await
+    }
+
+    /// Build the LLM request for the conversation's current history.
+    ///
+    /// Shared by fresh sends (after the prompt is stored) and regenerations
+    /// (which reuse the prompt already in the history).
+    async fn prepare_turn_context(
+        &self,
+        conversation_id: Uuid,
+    ) -> ServiceResult<(PreparedMessageContext, Option<TitleGenerationRequest>)> {
         let conversation = self
             .conversation_service
             .load(conversation_id)
@@ -384,17 +411,46 @@ impl ChatServiceImpl {
             .map_err(|e| ServiceError::Internal(format!("Failed to create LLM client: {e}")))?;
         let mut messages = Self::build_llm_messages(&conversation, &profile);
         strip_thinking_from_previous_turns(&mut messages);
-        let compression_config = self.load_compression_config().await;
-        let compression_result = CompressionPipeline::new().compress(
-            messages,
-            profile.context_window_size,
-            &compression_config,
-        );
+        let compression_result = self
+            .compress_turn_messages(conversation_id, messages, &profile)
+            .await;
         // The emoji filter drives both the system prompt and tool-output filtering, so
         // it is read once here rather than separately by each consumer.
         let filter_emoji = filter_emoji_setting(&self.app_settings_service).await;
-        let system_prompt =
+        let mut system_prompt =
             build_system_prompt(&self.skills_service, &conversation, &profile, filter_emoji).await;
+        append_todo_list(
+            &mut system_prompt,
+            self.todo_service.as_ref(),
+            conversation_id,
+        )
+        .await;
+        let calibration = self.calibration_sample(&profile, &system_prompt, &compression_result);
+        let workspace_roots =
+            WorkspaceSettings::load_from_settings(self.app_settings_service.as_ref())
+                .await
+                .map_or_else(
+                    |error| {
+                        tracing::warn!(error = %error, "Failed to load workspace roots");
+                        WorkspaceRoots::default()
+                    },
+                    |settings| settings.roots_for(profile.id),
+                );
+        // The reply is stored after the current history, at this `seq`.
+        let checkpoints = self.checkpoint_store.as_ref().map(|store| {
+            TurnCheckpoints::new(store.clone(), conversation_id, conversation.messages.len())
+        });
+        let tool_budget = ToolBudgetLimits::load_from_settings(self.app_settings_service.as_ref())
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!(error = %error, "Failed to load tool budget limits");
+                ToolBudgetLimits::default()
+            });
+        // Profiles the `Delegate` tool may run sub-agents on.
+        let profiles = self.profile_service.list().await.unwrap_or_else(|error| {
+            tracing::warn!(error = %error, "Failed to list profiles for delegation");
+            Vec::new()
+        });
 
         Ok((
             PreparedMessageContext {
@@ -405,6 +461,14 @@ impl ChatServiceImpl {
                 skills_service: self.skills_service.clone(),
                 compression_result,
                 filter_emoji,
+                usage_service: self.usage_service.clone(),
+                calibration,
+                workspace_roots,
+                checkpoints,
+                profiles,
+                todo_service: self.todo_service.clone(),
+                audit_service: self.audit_service.clone(),
+                tool_budget,
             },
             title_request,
         ))
@@ -575,7 +639,8 @@ impl ChatServiceImpl {
         let conversation_service = self.conversation_service.clone();
         let view_tx = self.view_tx.clone();
         let approval_gate = self.approval_gate.clone();
-        let policy = self.policy.clone();
+        let policy = self.turn_policy(prepared.profile.id, conversation_id).await;
+        let turn_policy = policy.clone();
 
         let handle = tokio::spawn(async move {
             run_stream_task(
@@ -599,6 +664,7 @@ impl ChatServiceImpl {
             Some(entry) if entry.stream_id == stream_id => {
                 entry.task = Some(handle);
                 entry.state = StreamLifecycle::Running;
+                entry.policy = Some(turn_policy);
             }
             // Entry was superseded by a newer reservation, or was removed
             // (e.g. by a concurrent cancel). Abort the spawned task so it
@@ -608,74 +674,6 @@ impl ChatServiceImpl {
             }
         }
     }
-
-    /// Resolve an in-flight tool approval request from UI input.
-    ///
-    /// # Errors
-    ///
-    /// Returns `ServiceError::NotFound` when `request_id` is unknown or already consumed,
-    /// or persistence-related errors for `ProceedAlways` decisions.
-    pub async fn resolve_tool_approval(
-        &self,
-        request_id: String,
-        decision: ToolApprovalResponseAction,
-    ) -> ServiceResult<()> {
-        let approved = !matches!(decision, ToolApprovalResponseAction::Denied);
-        let (conversation_id, tool_identifiers) = self
-            .approval_gate
-            .resolve_and_take_identifiers(&request_id, approved)
-            .ok_or_else(|| {
-                ServiceError::NotFound(format!("Tool approval request {request_id} not found"))
-            })?;
-
-        let mut emit_policy_snapshot = false;
-        match decision {
-            ToolApprovalResponseAction::ProceedSession => {
-                let mut policy = self.policy.lock().await;
-                for tool_identifier in tool_identifiers {
-                    policy.allow_for_session(tool_identifier);
-                }
-            }
-            ToolApprovalResponseAction::ProceedAlways => {
-                let mut updated_policy = self.policy.lock().await.clone();
-                updated_policy
-                    .allow_persistently_batch(tool_identifiers, self.app_settings_service.as_ref())
-                    .await?;
-
-                self.policy.lock().await.persistent_allowlist = updated_policy.persistent_allowlist;
-                emit_policy_snapshot = true;
-            }
-            ToolApprovalResponseAction::ProceedOnce => {}
-            ToolApprovalResponseAction::Denied => {
-                self.cancel_active_stream(conversation_id);
-            }
-        }
-
-        let _ = self.view_tx.try_send(ViewCommand::ToolApprovalResolved {
-            conversation_id,
-            request_id,
-            approved,
-        });
-
-        if emit_policy_snapshot {
-            let policy = self.policy.lock().await.clone();
-            let _ = self
-                .view_tx
-                .try_send(ViewCommand::ToolApprovalPolicyUpdated {
-                    yolo_mode: policy.yolo_mode,
-                    auto_approve_reads: policy.auto_approve_reads,
-                    skills_auto_approve: policy.skills_auto_approve,
-                    mcp_approval_mode: policy.mcp_approval_mode,
-                    persistent_allowlist: policy.persistent_allowlist,
-                    persistent_denylist: policy.persistent_denylist,
-                });
-            let _ = self.view_tx.try_send(ViewCommand::YoloModeChanged {
-                active: policy.yolo_mode,
-            });
-        }
-
-        Ok(())
-    }
 }
 
 struct PreparedMessageContext {
@@ -686,25 +684,14 @@ struct PreparedMessageContext {
     skills_service: Arc<dyn SkillsService>,
     compression_result: CompressionResult,
     filter_emoji: bool,
-}
-
-#[allow(clippy::missing_const_for_fn)]
-fn build_stream_context(
-    conversation_id: Uuid,
-    view_tx: tokio::sync::mpsc::Sender<ViewCommand>,
-    approval_gate: Arc<ApprovalGate>,
-    policy: Arc<AsyncMutex<ToolApprovalPolicy>>,
-    skills_service: Arc<dyn SkillsService>,
-    filter_emoji: bool,
-) -> crate::llm::client_agent::McpToolContext {
-    crate::llm::client_agent::McpToolContext {
-        conversation_id,
-        view_tx,
-        approval_gate,
-        policy,
-        skills_service,
-        filter_emoji,
-    }
+    usage_service: Option<Arc<dyn UsageService>>,
+    calibration: compression::CalibrationSample,
+    workspace_roots: WorkspaceRoots,
+    checkpoints: Option<TurnCheckpoints>,
+    profiles: Vec<crate::models::ModelProfile>,
+    todo_service: Option<Arc<dyn TodoService>>,
+    audit_service: Option<Arc<dyn ToolAuditService>>,
+    tool_budget: ToolBudgetLimits,
 }
 
 /// Create a stream agent for a conversation.
@@ -765,42 +752,37 @@ impl ChatService for ChatServiceImpl {
         // @plan PLAN-20260416-ISSUE173.P14-CR3
         // @requirement REQ-173-001.2
         let (stream_id, cancel) = self.begin_stream(conversation_id)?;
+        self.start_turn(conversation_id, stream_id, cancel, Some(content))
+            .await
+    }
 
-        self.refresh_tool_approval_policy_from_settings().await;
-
-        let (prepared, title_request) =
-            match self.prepare_message_context(conversation_id, content).await {
-                Ok(prepared) => prepared,
-                Err(error) => {
-                    self.clear_reservation(conversation_id, stream_id);
-                    return Err(error);
-                }
-            };
-        Self::emit_stream_started(conversation_id, prepared.profile.model_id.clone());
-
-        let mcp_tools = self.load_mcp_tools().await;
-
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<ChatStreamEvent>();
-        self.spawn_stream_task(
-            conversation_id,
-            stream_id,
-            cancel.clone(),
-            prepared,
-            mcp_tools,
-            tx,
-        )
-        .await;
-
-        if let Some(request) = title_request {
-            self.spawn_title_generation(conversation_id, request, cancel);
+    async fn regenerate(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<Box<dyn futures::Stream<Item = ChatStreamEvent> + Send + Unpin>> {
+        let (stream_id, cancel) = self.begin_stream(conversation_id)?;
+        if let Err(error) = self.archive_for_regenerate(conversation_id, seq).await {
+            self.clear_reservation(conversation_id, stream_id);
+            return Err(error);
         }
+        self.start_turn(conversation_id, stream_id, cancel, None)
+            .await
+    }
 
-        let message_stream: Pin<Box<dyn Stream<Item = ChatStreamEvent> + Send>> =
-            Box::pin(stream::unfold(rx, move |mut rx| async move {
-                rx.recv().await.map(|event| (event, rx))
-            }));
-
-        Ok(Box::new(message_stream))
+    async fn edit_and_resend(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+        content: String,
+    ) -> ServiceResult<Box<dyn futures::Stream<Item = ChatStreamEvent> + Send + Unpin>> {
+        let (stream_id, cancel) = self.begin_stream(conversation_id)?;
+        if let Err(error) = self.archive_for_edit(conversation_id, seq).await {
+            self.clear_reservation(conversation_id, stream_id);
+            return Err(error);
+        }
+        self.start_turn(conversation_id, stream_id, cancel, Some(content))
+            .await
     }
 
     /// Cancel the streaming operation for a specific conversation.
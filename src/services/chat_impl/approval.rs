@@ -0,0 +1,188 @@
+//! Tool approval state for turns.
+//!
+//! The service keeps the global policy, refreshed from settings before each
+//! turn. A turn runs under its own copy with the profile and conversation
+//! overrides applied, so approvals granted while it runs are recorded on
+//! both. "Always" approvals persist into the layer whose allowlist is in
+//! force for the conversation.
+
+use std::sync::Arc;
+
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+use super::ChatServiceImpl;
+use crate::agent::policy_overrides::PolicyOverrides;
+use crate::agent::tool_approval_policy::ToolApprovalPolicy;
+use crate::events::types::ToolApprovalResponseAction;
+use crate::presentation::view_command::ViewCommand;
+use crate::services::{ServiceError, ServiceResult};
+
+impl ChatServiceImpl {
+    pub(super) async fn refresh_tool_approval_policy_from_settings(&self) {
+        match ToolApprovalPolicy::load_from_settings(self.app_settings_service.as_ref()).await {
+            Ok(mut loaded_policy) => {
+                let mut policy = self.policy.lock().await;
+                let should_clear_session_allowlist = policy.yolo_mode && !loaded_policy.yolo_mode;
+
+                if should_clear_session_allowlist {
+                    loaded_policy.clear_session_allowlist();
+                } else {
+                    loaded_policy
+                        .session_allowlist
+                        .clone_from(&policy.session_allowlist);
+                }
+
+                *policy = loaded_policy;
+            }
+            Err(error) => {
+                tracing::warn!("Failed to refresh tool approval policy before send: {error}");
+            }
+        }
+    }
+
+    /// The policy a turn on `profile_id` runs under: the global policy with
+    /// the stored overrides applied. Unreadable overrides fall back to the
+    /// global policy alone.
+    pub(super) async fn turn_policy(
+        &self,
+        profile_id: Uuid,
+        conversation_id: Uuid,
+    ) -> Arc<AsyncMutex<ToolApprovalPolicy>> {
+        let overrides = PolicyOverrides::load_from_settings(self.app_settings_service.as_ref())
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!("Failed to load approval policy overrides: {error}");
+                PolicyOverrides::default()
+            });
+        let global = self.policy.lock().await.clone();
+        Arc::new(AsyncMutex::new(overrides.effective(
+            &global,
+            profile_id,
+            conversation_id,
+        )))
+    }
+
+    /// Allow `tool_identifiers` for the rest of the turn running in
+    /// `conversation_id`, if one is.
+    async fn allow_for_running_turn(&self, conversation_id: Uuid, tool_identifiers: &[String]) {
+        let turn_policy = {
+            let map = self.active_streams.lock().expect("active_streams poisoned");
+            map.get(&conversation_id)
+                .and_then(|active| active.policy.clone())
+        };
+        if let Some(turn_policy) = turn_policy {
+            let mut policy = turn_policy.lock().await;
+            for tool_identifier in tool_identifiers {
+                policy.allow_for_session(tool_identifier.clone());
+            }
+        }
+    }
+
+    /// Persist "always allow" into the override that replaces the global
+    /// allowlist for `conversation_id`, if one does; adding to the global
+    /// list would not reach that conversation's later turns.
+    async fn allow_in_overriding_layer(
+        &self,
+        conversation_id: Uuid,
+        tool_identifiers: &[String],
+    ) -> ServiceResult<bool> {
+        let settings = self.app_settings_service.as_ref();
+        let mut overrides = PolicyOverrides::load_from_settings(settings).await?;
+        let profile_id = self
+            .conversation_service
+            .load(conversation_id)
+            .await
+            .ok()
+            .map(|conversation| conversation.profile_id);
+        if !overrides.allow_in_overriding_layer(profile_id, conversation_id, tool_identifiers) {
+            return Ok(false);
+        }
+        overrides.save_to_settings(settings).await?;
+        let _ = self
+            .view_tx
+            .try_send(ViewCommand::ApprovalPolicyOverridesUpdated { overrides });
+        Ok(true)
+    }
+
+    /// Resolve an in-flight tool approval request from UI input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceError::NotFound` when `request_id` is unknown or already consumed,
+    /// or persistence-related errors for `ProceedAlways` decisions.
+    pub async fn resolve_tool_approval(
+        &self,
+        request_id: String,
+        decision: ToolApprovalResponseAction,
+    ) -> ServiceResult<()> {
+        let approved = !matches!(decision, ToolApprovalResponseAction::Denied);
+        let (conversation_id, tool_identifiers) = self
+            .approval_gate
+            .resolve_and_take_identifiers(&request_id, approved)
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Tool approval request {request_id} not found"))
+            })?;
+
+        let mut emit_policy_snapshot = false;
+        match decision {
+            ToolApprovalResponseAction::ProceedSession => {
+                self.allow_for_running_turn(conversation_id, &tool_identifiers)
+                    .await;
+                let mut policy = self.policy.lock().await;
+                for tool_identifier in tool_identifiers {
+                    policy.allow_for_session(tool_identifier);
+                }
+            }
+            ToolApprovalResponseAction::ProceedAlways => {
+                if !self
+                    .allow_in_overriding_layer(conversation_id, &tool_identifiers)
+                    .await?
+                {
+                    let mut updated_policy = self.policy.lock().await.clone();
+                    updated_policy
+                        .allow_persistently_batch(
+                            tool_identifiers.iter().cloned(),
+                            self.app_settings_service.as_ref(),
+                        )
+                        .await?;
+                    self.policy.lock().await.persistent_allowlist =
+                        updated_policy.persistent_allowlist;
+                    emit_policy_snapshot = true;
+                }
+                self.allow_for_running_turn(conversation_id, &tool_identifiers)
+                    .await;
+            }
+            ToolApprovalResponseAction::ProceedOnce => {}
+            ToolApprovalResponseAction::Denied => {
+                self.cancel_active_stream(conversation_id);
+            }
+        }
+
+        let _ = self.view_tx.try_send(ViewCommand::ToolApprovalResolved {
+            conversation_id,
+            request_id,
+            approved,
+        });
+
+        if emit_policy_snapshot {
+            let policy = self.policy.lock().await.clone();
+            let _ = self
+                .view_tx
+                .try_send(ViewCommand::ToolApprovalPolicyUpdated {
+                    yolo_mode: policy.yolo_mode,
+                    auto_approve_reads: policy.auto_approve_reads,
+                    skills_auto_approve: policy.skills_auto_approve,
+                    mcp_approval_mode: policy.mcp_approval_mode,
+                    persistent_allowlist: policy.persistent_allowlist,
+                    persistent_denylist: policy.persistent_denylist,
+                    rules: policy.rules,
+                });
+            let _ = self.view_tx.try_send(ViewCommand::YoloModeChanged {
+                active: policy.yolo_mode,
+            });
+        }
+
+        Ok(())
+    }
+}
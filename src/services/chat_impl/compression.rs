@@ -0,0 +1,191 @@
+//! Compressing a turn's history before it is sent.
+//!
+//! When the compression settings name a summarizer profile, the middle of a long
+//! history is summarized by that model, extending the summary persisted for the
+//! conversation on earlier turns. Any problem reaching the summarizer degrades
+//! to the heuristic pipeline rather than failing the send.
+//!
+//! Token estimates use the chat model's tokenizer family, scaled by a
+//! per-profile factor learned from the prompt sizes providers report.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use super::streaming::StreamTranscript;
+use super::ChatServiceImpl;
+use crate::compression::calibration::TokenCalibrations;
+use crate::compression::phases::llm_summary::LlmSummaryPhase;
+use crate::compression::pipeline::{CompressionPipeline, CompressionResult};
+use crate::compression::token_estimation::TokenEstimator;
+use crate::config::CompressionConfig;
+use crate::llm::Message as LlmMessage;
+use crate::models::ModelProfile;
+use crate::services::AppSettingsService;
+
+const COMPRESSION_SETTINGS_KEY: &str = "compression";
+const TOKEN_CALIBRATION_SETTINGS_KEY: &str = "token_calibration";
+
+async fn load_token_calibrations(app_settings: &dyn AppSettingsService) -> TokenCalibrations {
+    match app_settings
+        .get_setting(TOKEN_CALIBRATION_SETTINGS_KEY)
+        .await
+    {
+        Ok(Some(raw)) => serde_json::from_str(&raw).unwrap_or_else(|error| {
+            tracing::warn!(error = %error, "Failed to parse token calibration; starting over");
+            TokenCalibrations::default()
+        }),
+        Ok(None) => TokenCalibrations::default(),
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to load token calibration");
+            TokenCalibrations::default()
+        }
+    }
+}
+
+/// The local estimate of a turn's prompt, kept until the provider reports
+/// the real size.
+pub(super) struct CalibrationSample {
+    app_settings_service: Arc<dyn AppSettingsService>,
+    profile_id: Uuid,
+    estimated_prompt_tokens: usize,
+}
+
+impl CalibrationSample {
+    /// Fold the turn's reported prompt size into the profile's factor.
+    ///
+    /// The size is `TokenUsage::prompt_tokens`, which counts cached tokens
+    /// once whether or not the provider's own prompt count included them.
+    ///
+    /// Turns that ran tools are skipped: their usage adds up several requests,
+    /// each carrying the growing transcript, so it says little about one prompt.
+    pub(super) async fn record(self, transcript: &StreamTranscript) {
+        let Some(usage) = transcript.usage else {
+            return;
+        };
+        if !transcript.tool_calls.is_empty() {
+            return;
+        }
+
+        let mut calibrations = load_token_calibrations(self.app_settings_service.as_ref()).await;
+        if !calibrations.record(
+            self.profile_id,
+            self.estimated_prompt_tokens,
+            usage.prompt_tokens(),
+        ) {
+            return;
+        }
+        let result = match serde_json::to_string(&calibrations) {
+            Ok(json) => {
+                self.app_settings_service
+                    .set_setting(TOKEN_CALIBRATION_SETTINGS_KEY, json)
+                    .await
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, "Failed to serialize token calibration");
+                return;
+            }
+        };
+        if let Err(error) = result {
+            tracing::warn!(error = %error, "Failed to persist token calibration");
+        }
+    }
+}
+
+impl ChatServiceImpl {
+    async fn load_compression_config(&self) -> CompressionConfig {
+        match self
+            .app_settings_service
+            .get_setting(COMPRESSION_SETTINGS_KEY)
+            .await
+        {
+            Ok(Some(raw_config)) => serde_json::from_str::<CompressionConfig>(&raw_config)
+                .unwrap_or_else(|error| {
+                    tracing::warn!(
+                        error = %error,
+                        "Failed to parse persisted compression config; using defaults"
+                    );
+                    CompressionConfig::default()
+                }),
+            Ok(None) => CompressionConfig::default(),
+            Err(error) => {
+                tracing::warn!(
+                    error = %error,
+                    "Failed to load persisted compression config; using defaults"
+                );
+                CompressionConfig::default()
+            }
+        }
+    }
+
+    /// Token estimator for `profile`'s model with its learned correction.
+    async fn calibrated_estimator(&self, profile: &ModelProfile) -> TokenEstimator {
+        let calibrations = load_token_calibrations(self.app_settings_service.as_ref()).await;
+        TokenEstimator::for_model(&profile.model_id)
+            .with_correction(calibrations.factor_for(profile.id))
+    }
+
+    /// Run the compression pipeline over `messages` for `profile`'s model.
+    pub(super) async fn compress_turn_messages(
+        &self,
+        conversation_id: Uuid,
+        messages: Vec<LlmMessage>,
+        profile: &ModelProfile,
+    ) -> CompressionResult {
+        let config = self.load_compression_config().await;
+        let pipeline =
+            CompressionPipeline::with_estimator(self.calibrated_estimator(profile).await);
+        let context_window = profile.context_window_size;
+
+        let Some(summarizer_profile_id) = config.summarizer_profile_id else {
+            return pipeline.compress(messages, context_window, &config);
+        };
+        let summarizer_profile = match self.profile_service.get(summarizer_profile_id).await {
+            Ok(profile) => profile,
+            Err(error) => {
+                tracing::warn!(
+                    %summarizer_profile_id,
+                    error = %error,
+                    "Summarizer profile unavailable; using heuristic summary"
+                );
+                return pipeline.compress(messages, context_window, &config);
+            }
+        };
+
+        let prior_state = self
+            .conversation_service
+            .get_context_state(conversation_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let phase = LlmSummaryPhase {
+            summarizer: self.context_summarizer.as_ref(),
+            profile: &summarizer_profile,
+            prior: prior_state
+                .summary
+                .as_deref()
+                .zip(prior_state.summary_range),
+        };
+
+        pipeline
+            .compress_with_llm_summary(messages, context_window, &config, &phase)
+            .await
+    }
+
+    /// Remember the raw estimate of the prompt about to be sent.
+    pub(super) fn calibration_sample(
+        &self,
+        profile: &ModelProfile,
+        system_prompt: &str,
+        compression_result: &CompressionResult,
+    ) -> CalibrationSample {
+        let system_tokens =
+            TokenEstimator::for_model(&profile.model_id).estimate_text_tokens(system_prompt);
+        CalibrationSample {
+            app_settings_service: self.app_settings_service.clone(),
+            profile_id: profile.id,
+            estimated_prompt_tokens: compression_result.estimated_tokens + system_tokens,
+        }
+    }
+}
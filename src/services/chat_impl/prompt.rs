@@ -2,13 +2,16 @@
 //!
 //! The prompt the model sees is the conversation's own system message (falling back to
 //! the profile's), with template variables expanded, followed by the enabled-skills
-//! block and the emoji instruction.
+//! block and the emoji instruction. The conversation's `TodoWrite` task list follows,
+//! which is how the list outlives compression of the history it was written in.
 
 use std::sync::Arc;
 
-use crate::models::{Conversation, MessageRole, ModelProfile};
+use uuid::Uuid;
+
+use crate::models::{format_todo_list, Conversation, MessageRole, ModelProfile};
 use crate::services::template::{build_skills_prompt_block, expand_system_prompt, TemplateContext};
-use crate::services::{AppSettingsService, SkillsService};
+use crate::services::{AppSettingsService, SkillsService, TodoService};
 
 /// Build the system prompt for a send.
 pub(super) async fn build_system_prompt(
@@ -81,6 +84,33 @@ pub(super) async fn filter_emoji_setting(
     }
 }
 
+/// Append the conversation's task list, when it has one.
+pub(super) async fn append_todo_list(
+    system_prompt: &mut String,
+    todo_service: Option<&Arc<dyn TodoService>>,
+    conversation_id: Uuid,
+) {
+    let Some(todo_service) = todo_service else {
+        return;
+    };
+    match todo_service.list(conversation_id).await {
+        Ok(todos) if !todos.is_empty() => append_prompt_section(
+            system_prompt,
+            &format!(
+                "Your task list for this conversation (keep it current with TodoWrite):\n{}",
+                format_todo_list(&todos)
+            ),
+        ),
+        Ok(_) => {}
+        Err(error) => {
+            tracing::warn!(
+                error = %error,
+                "Failed to load the task list; continuing without it"
+            );
+        }
+    }
+}
+
 /// Append `section` to `system_prompt`, separated by a blank line when both are present.
 fn append_prompt_section(system_prompt: &mut String, section: &str) {
     if section.is_empty() {
@@ -2,14 +2,17 @@
 
 use super::{
     ActiveStream, AgentClientExt, ApprovalGate, AsyncMutex, ChatEvent, ChatStreamEvent,
-    CompressionResult, LlmMessage, PreparedMessageContext, ServiceError, StdMutex,
-    ToolApprovalPolicy, ViewCommand,
+    CompressionResult, DelegationContext, LlmMessage, PreparedMessageContext, ServiceError,
+    StdMutex, ToolApprovalPolicy, ViewCommand,
 };
+use crate::agent::checkpoints::TurnCheckpoints;
+use crate::agent::tool_budget::TurnBudget;
+use crate::agent::workspace::WorkspaceRoots;
 use crate::events::{emit, AppEvent};
 use crate::llm::error::debug_error_message;
 use crate::llm::{LlmClient, StreamEvent as LlmStreamEvent};
-use crate::models::{ContextState, Message};
-use crate::services::ConversationService;
+use crate::models::{ContextState, Message, TokenUsage};
+use crate::services::{ConversationService, SkillsService, TodoService, ToolAuditService};
 use crate::ui_gpui::error_log::{
     base_url_host, sanitize_text, ErrorLogDiagnosticContext, ErrorLogRunStatus,
     ErrorLogStreamLifecycle, ErrorLogToolContext,
@@ -20,7 +23,8 @@ use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-use super::{build_stream_context, create_stream_agent};
+use super::create_stream_agent;
+use super::usage::record_turn_usage;
 
 pub(super) const STREAM_ERROR_MESSAGE: &str = "An error interrupted the chat stream.";
 
@@ -32,9 +36,42 @@ pub(super) struct StreamTranscript {
     pub(super) tool_results: Vec<crate::llm::tools::ToolResult>,
     pub(super) input_tokens: Option<u32>,
     pub(super) output_tokens: Option<u32>,
+    pub(super) usage: Option<TokenUsage>,
     pub(super) completed: bool,
 }
 
+/// The tool context for one turn's agent run.
+#[allow(clippy::missing_const_for_fn, clippy::too_many_arguments)]
+fn build_stream_context(
+    conversation_id: Uuid,
+    view_tx: tokio::sync::mpsc::Sender<ViewCommand>,
+    approval_gate: Arc<ApprovalGate>,
+    policy: Arc<AsyncMutex<ToolApprovalPolicy>>,
+    skills_service: Arc<dyn SkillsService>,
+    filter_emoji: bool,
+    workspace_roots: WorkspaceRoots,
+    checkpoints: Option<TurnCheckpoints>,
+    delegation: Arc<DelegationContext>,
+    todo_service: Option<Arc<dyn TodoService>>,
+    audit_service: Option<Arc<dyn ToolAuditService>>,
+    budget: Arc<TurnBudget>,
+) -> crate::llm::client_agent::McpToolContext {
+    crate::llm::client_agent::McpToolContext {
+        conversation_id,
+        view_tx,
+        approval_gate,
+        policy,
+        skills_service,
+        filter_emoji,
+        workspace_roots,
+        checkpoints,
+        delegation: Some(delegation),
+        todo_service,
+        audit_service,
+        budget: Some(budget),
+    }
+}
+
 #[derive(Clone, Default)]
 pub(super) struct StreamDiagnosticContext {
     pub(super) profile_id: Uuid,
@@ -70,6 +107,10 @@ pub(super) async fn stream_agent_response(
 
     if let Err(error) = client
         .run_agent_stream(agent, messages, context, |event| {
+            if let LlmStreamEvent::Usage(usage) = event {
+                transcript.usage = Some(usage);
+                return;
+            }
             handle_llm_stream_event(
                 diagnostics_context,
                 event,
@@ -182,9 +223,23 @@ pub(super) async fn run_stream_task(
         skills_service,
         compression_result,
         filter_emoji,
+        usage_service,
+        calibration,
+        workspace_roots,
+        checkpoints,
+        profiles,
+        todo_service,
+        audit_service,
+        tool_budget,
     } = prepared;
 
     let diagnostics_context = StreamDiagnosticContext::from_profile(&profile);
+    let delegation = Arc::new(DelegationContext {
+        profile: profile.clone(),
+        profiles,
+        mcp_tools: mcp_tools.clone(),
+        usage_service: usage_service.clone(),
+    });
 
     let Some(agent) = create_stream_agent(
         &client,
@@ -209,6 +264,12 @@ pub(super) async fn run_stream_task(
         policy.clone(),
         skills_service,
         filter_emoji,
+        workspace_roots,
+        checkpoints,
+        delegation,
+        todo_service,
+        audit_service,
+        Arc::new(TurnBudget::new(tool_budget)),
     );
 
     let transcript = stream_agent_response(
@@ -227,6 +288,17 @@ pub(super) async fn run_stream_task(
         return;
     }
 
+    // Recorded before `StreamCompleted` goes out so listeners that refresh
+    // totals on completion already see this turn.
+    record_turn_usage(
+        usage_service.as_ref(),
+        conversation_id,
+        &profile,
+        transcript.usage,
+    )
+    .await;
+    calibration.record(&transcript).await;
+
     finalize_stream_task(
         &conversation_service,
         conversation_id,
@@ -305,7 +377,7 @@ pub(super) fn handle_llm_stream_event(
             handle_stream_error_event(conversation_id, tx, diagnostics_context, &snapshot, &err);
         }
 
-        LlmStreamEvent::ToolUse(_tool_use) => {}
+        LlmStreamEvent::ToolUse(_tool_use) | LlmStreamEvent::Usage(_) => {}
     }
 }
 
@@ -407,6 +479,7 @@ fn handle_stream_error_event(
         tool_results: snapshot.tool_results.to_vec(),
         input_tokens: snapshot.input_tokens,
         output_tokens: snapshot.output_tokens,
+        usage: None,
         completed: false,
     };
     let mut diagnostics = build_stream_error_diagnostics(
@@ -603,6 +676,7 @@ pub(super) async fn persist_context_state(
     state.compression_phase = Some(compression_result.phase);
     state.masked_tool_seqs = compression_result.masked_tool_seqs;
     state.summary_range = compression_result.summary_range;
+    state.summary = compression_result.summary;
     state.compressed_at = Some(chrono::Utc::now());
     state.preserved_facts = compression_result.preserved_facts;
     state.last_input_tokens = input_tokens;
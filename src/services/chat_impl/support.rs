@@ -193,6 +193,9 @@ pub(super) fn make_approval_test_chat_service(
         persistent_allowlist: Vec::new(),
         persistent_denylist: Vec::new(),
         session_allowlist: std::collections::HashSet::new(),
+        web_fetch_allowed_domains: Vec::new(),
+        web_fetch_denied_domains: Vec::new(),
+        rules: Vec::new(),
     }));
 
     let skills_service = Arc::new(
@@ -269,6 +272,7 @@ impl super::super::ConversationService for MockConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &crate::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<crate::models::ConversationMetadata>, crate::services::ServiceError> {
@@ -287,6 +291,7 @@ impl super::super::ConversationService for MockConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &crate::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<crate::models::SearchResult>, crate::services::ServiceError> {
@@ -353,6 +358,46 @@ impl super::super::ConversationService for MockConversationService {
     ) -> Result<crate::models::Conversation, crate::services::ServiceError> {
         Err(crate::services::ServiceError::NotFound("test".to_string()))
     }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), crate::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, crate::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 pub(super) struct MockProfileService {
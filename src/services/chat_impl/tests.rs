@@ -1,6 +1,6 @@
 use super::*;
 use crate::agent::McpApprovalMode;
-use crate::models::{AuthConfig, ContextState, Message, Skill};
+use crate::models::{AuthConfig, ContextState, ConversationFilter, Message, Skill};
 use crate::services::{AppSettingsService, ProfileService, SkillsService};
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -12,6 +12,7 @@ mod approval_persistence;
 mod chat_test_support;
 mod compression_persistence;
 mod concurrent_streams;
+mod policy_overrides;
 mod three_stream_concurrency;
 mod title_generation;
 
@@ -303,6 +304,7 @@ async fn resolve_tool_approval_proceed_always_persists_all_identifiers() {
             mcp_approval_mode: McpApprovalMode::PerTool,
             persistent_allowlist: vec!["git status".to_string(), "pwd".to_string()],
             persistent_denylist: Vec::new(),
+            rules: Vec::new(),
         }
     );
 
@@ -366,6 +368,9 @@ async fn send_message_clears_session_allowlist_when_yolo_turns_off_in_settings()
         persistent_allowlist: Vec::new(),
         persistent_denylist: Vec::new(),
         session_allowlist: std::collections::HashSet::new(),
+        web_fetch_allowed_domains: Vec::new(),
+        web_fetch_denied_domains: Vec::new(),
+        rules: Vec::new(),
     };
     persisted_policy
         .save_to_settings(app_settings.as_ref())
@@ -384,6 +389,9 @@ async fn send_message_clears_session_allowlist_when_yolo_turns_off_in_settings()
         persistent_allowlist: Vec::new(),
         persistent_denylist: Vec::new(),
         session_allowlist,
+        web_fetch_allowed_domains: Vec::new(),
+        web_fetch_denied_domains: Vec::new(),
+        rules: Vec::new(),
     };
 
     let skills_service = Arc::new(
@@ -613,7 +621,7 @@ async fn support_mock_conversation_service_covers_crud_and_lookup_paths() {
         .expect("conversation create should succeed");
     assert_eq!(created.profile_id, profile_id);
     assert!(conversation_service
-        .list_metadata(None, None)
+        .list_metadata(&ConversationFilter::default(), None, None)
         .await
         .unwrap()
         .is_empty());
@@ -631,7 +639,7 @@ async fn support_mock_conversation_service_covers_crud_and_lookup_paths() {
     assert_eq!(loaded.messages.len(), 1);
     assert_eq!(
         conversation_service
-            .search("hello", None, None)
+            .search("hello", &ConversationFilter::default(), None, None)
             .await
             .unwrap()
             .len(),
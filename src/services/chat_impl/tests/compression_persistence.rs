@@ -135,6 +135,125 @@ async fn prepare_message_context_uses_persisted_compression_config() {
     let _ = crate::services::secure_store::api_keys::delete("_test_compression_config");
 }
 
+/// Local stand-in for the summarizer model: answers with a fixed summary and
+/// records the prompt it was sent.
+#[derive(Default)]
+struct RecordingSummarizer {
+    prompts: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl crate::compression::phases::llm_summary::ContextSummarizer for RecordingSummarizer {
+    async fn summarize(
+        &self,
+        _profile: &crate::models::ModelProfile,
+        request: &[crate::llm::Message],
+    ) -> anyhow::Result<String> {
+        self.prompts
+            .lock()
+            .expect("prompts lock")
+            .push(request[1].content.clone());
+        Ok("- earlier turns\n- later turns".to_string())
+    }
+}
+
+#[tokio::test]
+async fn prepare_message_context_extends_persisted_summary_with_summarizer_profile() {
+    crate::services::secure_store::use_mock_backend();
+    crate::services::secure_store::api_keys::store(
+        "_test_llm_summary",
+        "fake-key-for-llm-summary-test",
+    )
+    .expect("store test key");
+
+    let mut profile = crate::models::ModelProfile::new(
+        "Summary Test".to_string(),
+        "openai".to_string(),
+        "gpt-4o".to_string(),
+        "https://api.openai.com/v1".to_string(),
+        AuthConfig::Keychain {
+            label: "_test_llm_summary".to_string(),
+        },
+    );
+    profile.system_prompt = String::new();
+    let conversation_service_impl = Arc::new(MockConversationService::new(profile.id));
+    for index in 0..9 {
+        conversation_service_impl
+            .messages
+            .write()
+            .await
+            .push(Message::user(format!("history {index}")));
+    }
+    *conversation_service_impl.context_state.write().await = Some(ContextState {
+        summary: Some("- earlier turns".to_string()),
+        summary_range: Some((1, 6)),
+        ..ContextState::default()
+    });
+    let mock_profile_service = Arc::new(MockProfileService::new());
+    mock_profile_service.add_profile(profile.clone()).await;
+
+    let app_settings_impl = Arc::new(InMemoryAppSettingsService::new());
+    app_settings_impl
+        .set_setting(
+            "compression",
+            serde_json::to_string(&crate::config::CompressionConfig {
+                observation_mask_threshold: 2.0,
+                sandwich_summary_threshold: 0.0,
+                truncation_threshold: 3.0,
+                preserve_top_fraction: 0.1,
+                preserve_bottom_fraction: 0.2,
+                min_middle_messages: 2,
+                summarizer_profile_id: Some(profile.id),
+                ..crate::config::CompressionConfig::default()
+            })
+            .expect("compression config should serialize"),
+        )
+        .await
+        .expect("compression config should persist");
+
+    let app_settings = app_settings_impl as Arc<dyn AppSettingsService>;
+
+    let (view_tx, _view_rx) = tokio::sync::mpsc::channel(8);
+    let skills_service = Arc::new(
+        crate::services::SkillsServiceImpl::new(app_settings.clone())
+            .expect("skills service should initialize"),
+    ) as Arc<dyn crate::services::SkillsService>;
+    let summarizer = Arc::new(RecordingSummarizer::default());
+    let service = ChatServiceImpl::new(
+        conversation_service_impl.clone() as Arc<dyn super::super::ConversationService>,
+        mock_profile_service as Arc<dyn ProfileService>,
+        app_settings,
+        skills_service,
+        view_tx,
+        Arc::new(ApprovalGate::new()),
+        Arc::new(AsyncMutex::new(ToolApprovalPolicy::default())),
+    )
+    .with_title_generator(Arc::new(DisabledConversationTitleGenerator))
+    .with_context_summarizer(summarizer.clone());
+
+    let (prepared, _title_request) = service
+        .prepare_message_context(Uuid::new_v4(), "next question".to_string())
+        .await
+        .expect("prepare_message_context should succeed");
+
+    let result = &prepared.compression_result;
+    assert_eq!(result.phase, crate::models::CompressionPhase::Summarized);
+    assert_eq!(result.summary_range, Some((1, 8)));
+    assert_eq!(
+        result.summary.as_deref(),
+        Some("- earlier turns\n- later turns")
+    );
+
+    let prompts = summarizer.prompts.lock().expect("prompts lock").clone();
+    assert_eq!(prompts.len(), 1, "only the new middle messages are sent");
+    assert!(prompts[0].contains("<previous_summary>\n- earlier turns\n</previous_summary>"));
+    assert!(prompts[0].contains("history 6"));
+    assert!(prompts[0].contains("history 7"));
+    assert!(!prompts[0].contains("history 5"));
+
+    let _ = crate::services::secure_store::api_keys::delete("_test_llm_summary");
+}
+
 #[tokio::test]
 async fn persist_context_state_preserves_existing_fields() {
     let conversation_service_impl = Arc::new(MockConversationService::new(Uuid::new_v4()));
@@ -158,6 +277,7 @@ async fn persist_context_state_preserves_existing_fields() {
             masked_tool_seqs: Some(vec![1]),
             summary_range: Some((1, 2)),
             preserved_facts: Some(vec!["fact".to_string()]),
+            summary: Some("refreshed summary".to_string()),
             estimated_tokens: 42,
         },
         Some(10),
@@ -176,7 +296,7 @@ async fn persist_context_state_preserves_existing_fields() {
         stored_state.strategy.as_deref(),
         Some("keep-existing-strategy")
     );
-    assert_eq!(stored_state.summary.as_deref(), Some("existing summary"));
+    assert_eq!(stored_state.summary.as_deref(), Some("refreshed summary"));
     assert_eq!(stored_state.visible_range, Some((3, 9)));
     assert_eq!(
         stored_state.compression_phase,
@@ -327,6 +447,7 @@ fn build_stream_error_diagnostics_sanitizes_and_summarizes_context() {
         )],
         input_tokens: Some(13),
         output_tokens: Some(17),
+        usage: None,
         completed: false,
     };
 
@@ -425,6 +546,7 @@ async fn persist_context_state_covers_existing_new_and_error_paths() {
         masked_tool_seqs: Some(vec![2, 4]),
         summary_range: Some((1, 3)),
         preserved_facts: Some(vec!["fact".to_string()]),
+        summary: Some("- model summary".to_string()),
         estimated_tokens: 42,
     };
 
@@ -450,6 +572,7 @@ async fn persist_context_state_covers_existing_new_and_error_paths() {
     assert_eq!(state.masked_tool_seqs, Some(vec![2, 4]));
     assert_eq!(state.summary_range, Some((1, 3)));
     assert_eq!(state.preserved_facts, Some(vec!["fact".to_string()]));
+    assert_eq!(state.summary.as_deref(), Some("- model summary"));
 
     assert_eq!(state.last_input_tokens, Some(10));
     assert_eq!(state.last_output_tokens, Some(20));
@@ -463,6 +586,7 @@ async fn persist_context_state_covers_existing_new_and_error_paths() {
             masked_tool_seqs: None,
             summary_range: None,
             preserved_facts: None,
+            summary: None,
             estimated_tokens: 0,
         },
         None,
@@ -476,3 +600,115 @@ async fn persist_context_state_covers_existing_new_and_error_paths() {
         .await
         .is_some());
 }
+
+#[tokio::test]
+async fn calibration_sample_records_reported_prompt_size_for_single_request_turns() {
+    let profile = crate::models::ModelProfile::default();
+    let service = ChatServiceImpl::new_for_tests(
+        Arc::new(MockConversationService::new(profile.id)),
+        Arc::new(MockProfileService::new()),
+    );
+    let compression = CompressionResult {
+        messages: vec![crate::llm::Message::user("hello")],
+        phase: crate::models::CompressionPhase::None,
+        masked_tool_seqs: None,
+        summary_range: None,
+        preserved_facts: None,
+        summary: None,
+        estimated_tokens: 1_000,
+    };
+    let usage = crate::models::TokenUsage {
+        input_tokens: 900,
+        cache_read_tokens: 300,
+        ..crate::models::TokenUsage::default()
+    };
+
+    let tool_turn = streaming::StreamTranscript {
+        tool_calls: vec![crate::llm::ToolUse::new(
+            "call-1",
+            "search",
+            serde_json::json!({}),
+        )],
+        usage: Some(usage),
+        ..streaming::StreamTranscript::default()
+    };
+    service
+        .calibration_sample(&profile, "", &compression)
+        .record(&tool_turn)
+        .await;
+    assert_eq!(
+        service
+            .app_settings_service
+            .get_setting("token_calibration")
+            .await
+            .expect("settings should load"),
+        None,
+        "turns that ran tools are not used for calibration"
+    );
+
+    let plain_turn = streaming::StreamTranscript {
+        usage: Some(usage),
+        ..streaming::StreamTranscript::default()
+    };
+    service
+        .calibration_sample(&profile, "", &compression)
+        .record(&plain_turn)
+        .await;
+    let stored: crate::compression::calibration::TokenCalibrations = serde_json::from_str(
+        &service
+            .app_settings_service
+            .get_setting("token_calibration")
+            .await
+            .expect("settings should load")
+            .expect("calibration should be stored"),
+    )
+    .expect("calibration should parse");
+    assert!((stored.factor_for(profile.id) - 1.2).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn calibration_sample_counts_cached_prompt_tokens_once() {
+    let profile = crate::models::ModelProfile::default();
+    let service = ChatServiceImpl::new_for_tests(
+        Arc::new(MockConversationService::new(profile.id)),
+        Arc::new(MockProfileService::new()),
+    );
+    let compression = CompressionResult {
+        messages: vec![crate::llm::Message::user("hello")],
+        phase: crate::models::CompressionPhase::None,
+        masked_tool_seqs: None,
+        summary_range: None,
+        preserved_facts: None,
+        summary: None,
+        estimated_tokens: 1_000,
+    };
+    // OpenAI-compatible providers count cache reads inside `prompt_tokens`.
+    let usage = crate::llm::client_agent::usage_from_json(&serde_json::json!({
+        "prompt_tokens": 1_100,
+        "completion_tokens": 20,
+        "prompt_tokens_details": { "cached_tokens": 1_000 },
+    }));
+    let cached_turn = streaming::StreamTranscript {
+        usage: Some(usage),
+        ..streaming::StreamTranscript::default()
+    };
+
+    service
+        .calibration_sample(&profile, "", &compression)
+        .record(&cached_turn)
+        .await;
+    let stored: crate::compression::calibration::TokenCalibrations = serde_json::from_str(
+        &service
+            .app_settings_service
+            .get_setting("token_calibration")
+            .await
+            .expect("settings should load")
+            .expect("calibration should be stored"),
+    )
+    .expect("calibration should parse");
+    assert!(
+        (stored.factor_for(profile.id) - 1.1).abs() < 1e-9,
+        "factor {}",
+        stored.factor_for(profile.id)
+    );
+}
@@ -0,0 +1,130 @@
+use super::*;
+use crate::agent::policy_overrides::{OverrideLayer, PolicyOverride, PolicyOverrides};
+use crate::agent::tool_approval_policy::ToolApprovalDecision;
+
+#[tokio::test]
+async fn turn_policy_applies_profile_then_conversation_overrides() {
+    let app_settings = Arc::new(InMemoryAppSettingsService::new()) as Arc<dyn AppSettingsService>;
+    let (service, _view_rx, _approval_gate) = make_approval_test_chat_service(app_settings.clone());
+    let profile_id = Uuid::new_v4();
+    let conversation_id = Uuid::new_v4();
+    let mut overrides = PolicyOverrides::default();
+    overrides.set(
+        OverrideLayer::Profile,
+        profile_id,
+        PolicyOverride::parse("yolo=on allow=git").expect("override"),
+    );
+    overrides.set(
+        OverrideLayer::Conversation,
+        conversation_id,
+        PolicyOverride::parse("yolo=off").expect("override"),
+    );
+    overrides
+        .save_to_settings(app_settings.as_ref())
+        .await
+        .expect("save overrides");
+
+    let turn_policy = service.turn_policy(profile_id, conversation_id).await;
+    let turn_policy = turn_policy.lock().await.clone();
+    assert!(!turn_policy.yolo_mode);
+    assert_eq!(turn_policy.persistent_allowlist, vec!["git".to_string()]);
+
+    let other_turn = service.turn_policy(profile_id, Uuid::new_v4()).await;
+    assert!(other_turn.lock().await.yolo_mode);
+    assert!(
+        service.policy.lock().await.persistent_allowlist.is_empty(),
+        "overrides must not leak into the global policy"
+    );
+}
+
+#[tokio::test]
+async fn proceed_session_reaches_the_running_turn_policy() {
+    let app_settings = Arc::new(InMemoryAppSettingsService::new()) as Arc<dyn AppSettingsService>;
+    let (service, _view_rx, approval_gate) = make_approval_test_chat_service(app_settings);
+    let conversation_id = Uuid::new_v4();
+    service
+        .begin_stream_for_test(conversation_id)
+        .expect("stream should start");
+    let turn_policy = service.turn_policy(Uuid::new_v4(), conversation_id).await;
+    service
+        .active_streams
+        .lock()
+        .expect("active_streams poisoned")
+        .get_mut(&conversation_id)
+        .expect("active stream")
+        .policy = Some(turn_policy.clone());
+
+    let request_id = Uuid::new_v4().to_string();
+    let waiter = approval_gate.wait_for_approval(
+        request_id.clone(),
+        "cargo test".to_string(),
+        conversation_id,
+    );
+    service
+        .resolve_tool_approval(request_id, ToolApprovalResponseAction::ProceedSession)
+        .await
+        .expect("session resolution should succeed");
+    assert!(waiter.wait().await.expect("decision"));
+
+    assert_eq!(
+        turn_policy.lock().await.evaluate("cargo test --workspace"),
+        ToolApprovalDecision::Allow
+    );
+    service.clear_all_streams_for_test();
+}
+
+#[tokio::test]
+async fn proceed_always_reaches_later_turns_under_an_allowlist_override() {
+    let app_settings = Arc::new(InMemoryAppSettingsService::new()) as Arc<dyn AppSettingsService>;
+    let (service, _view_rx, approval_gate) = make_approval_test_chat_service(app_settings.clone());
+    let profile_id = Uuid::new_v4();
+    let conversation_id = Uuid::new_v4();
+    let mut overrides = PolicyOverrides::default();
+    overrides.set(
+        OverrideLayer::Conversation,
+        conversation_id,
+        PolicyOverride::parse("allow=git").expect("override"),
+    );
+    overrides
+        .save_to_settings(app_settings.as_ref())
+        .await
+        .expect("save overrides");
+
+    service
+        .begin_stream_for_test(conversation_id)
+        .expect("stream should start");
+    let first_turn = service.turn_policy(profile_id, conversation_id).await;
+    service
+        .active_streams
+        .lock()
+        .expect("active_streams poisoned")
+        .get_mut(&conversation_id)
+        .expect("active stream")
+        .policy = Some(first_turn.clone());
+    let request_id = Uuid::new_v4().to_string();
+    let waiter = approval_gate.wait_for_approval(
+        request_id.clone(),
+        "cargo test".to_string(),
+        conversation_id,
+    );
+    service
+        .resolve_tool_approval(request_id, ToolApprovalResponseAction::ProceedAlways)
+        .await
+        .expect("always resolution should succeed");
+    assert!(waiter.wait().await.expect("decision"));
+    assert_eq!(
+        first_turn.lock().await.evaluate("cargo test --workspace"),
+        ToolApprovalDecision::Allow
+    );
+    service.clear_all_streams_for_test();
+
+    let second_turn = service.turn_policy(profile_id, conversation_id).await;
+    assert_eq!(
+        second_turn.lock().await.evaluate("cargo test --workspace"),
+        ToolApprovalDecision::Allow
+    );
+    assert!(
+        service.policy.lock().await.persistent_allowlist.is_empty(),
+        "the approval belongs to the override, not the global list"
+    );
+}
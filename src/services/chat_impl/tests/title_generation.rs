@@ -616,6 +616,8 @@ async fn the_generated_title_reaches_the_title_bar_and_the_conversation_list() {
             updated_at: chrono::Utc::now(),
             message_count: 1,
             preview: None,
+            parent_id: None,
+            organization: crate::models::ConversationOrganization::default(),
         }],
         selected_conversation: None,
     });
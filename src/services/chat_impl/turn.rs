@@ -0,0 +1,143 @@
+//! Turn orchestration shared by sending, regenerating, and editing.
+//!
+//! Every turn reserves the conversation's stream slot first (see
+//! `ChatServiceImpl::begin_stream`). Regeneration and edit-and-resend then
+//! archive the history from the target message onward as an alternate
+//! version before the new turn is streamed in its place.
+
+use std::pin::Pin;
+
+use futures::{stream, Stream};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::ChatServiceImpl;
+use crate::models::MessageRole;
+use crate::services::{ChatStreamEvent, ServiceError, ServiceResult};
+
+impl ChatServiceImpl {
+    /// Run one assistant turn on a conversation whose stream slot is reserved.
+    ///
+    /// With `content`, the prompt is stored first; without it, the model
+    /// answers the history as it stands (used by regeneration). Clears the
+    /// reservation if preparation fails.
+    pub(super) async fn start_turn(
+        &self,
+        conversation_id: Uuid,
+        stream_id: Uuid,
+        cancel: CancellationToken,
+        content: Option<String>,
+    ) -> ServiceResult<Box<dyn futures::Stream<Item = ChatStreamEvent> + Send + Unpin>> {
+        self.refresh_tool_approval_policy_from_settings().await;
+
+        let prepared = match content {
+            Some(content) => self.prepare_message_context(conversation_id, content).await,
+            None => self.prepare_turn_context(conversation_id).await,
+        };
+        let (prepared, title_request) = match prepared {
+            Ok(prepared) => prepared,
+            Err(error) => {
+                self.clear_reservation(conversation_id, stream_id);
+                return Err(error);
+            }
+        };
+        Self::emit_stream_started(conversation_id, prepared.profile.model_id.clone());
+        self.spawn_checkpoint_pruning();
+
+        let mcp_tools = self.load_mcp_tools().await;
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<ChatStreamEvent>();
+        self.spawn_stream_task(
+            conversation_id,
+            stream_id,
+            cancel.clone(),
+            prepared,
+            mcp_tools,
+            tx,
+        )
+        .await;
+
+        if let Some(request) = title_request {
+            self.spawn_title_generation(conversation_id, request, cancel);
+        }
+
+        let message_stream: Pin<Box<dyn Stream<Item = ChatStreamEvent> + Send>> =
+            Box::pin(stream::unfold(rx, move |mut rx| async move {
+                rx.recv().await.map(|event| (event, rx))
+            }));
+
+        Ok(Box::new(message_stream))
+    }
+
+    /// Prune stale file checkpoints without holding up the turn.
+    fn spawn_checkpoint_pruning(&self) {
+        let Some(store) = self.checkpoint_store.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            if let Err(error) = store.prune().await {
+                tracing::warn!(error = %error, "Failed to prune file checkpoints");
+            }
+        });
+    }
+
+    /// Archive the assistant response at `seq` (and everything after it).
+    ///
+    /// A `seq` one past the last message is accepted without archiving when
+    /// the last message is a prompt still waiting for its reply.
+    pub(super) async fn archive_for_regenerate(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<()> {
+        match self.message_role(conversation_id, seq).await? {
+            Some(MessageRole::Assistant) => {
+                self.conversation_service
+                    .begin_message_version(conversation_id, seq)
+                    .await
+            }
+            None if self.is_pending_prompt(conversation_id, seq).await? => Ok(()),
+            _ => Err(ServiceError::Validation(format!(
+                "message {seq} is not an assistant response"
+            ))),
+        }
+    }
+
+    /// Archive the user prompt at `seq` (and everything after it).
+    pub(super) async fn archive_for_edit(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<()> {
+        match self.message_role(conversation_id, seq).await? {
+            Some(MessageRole::User) => {
+                self.conversation_service
+                    .begin_message_version(conversation_id, seq)
+                    .await
+            }
+            _ => Err(ServiceError::Validation(format!(
+                "message {seq} is not a user prompt"
+            ))),
+        }
+    }
+
+    async fn message_role(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<Option<MessageRole>> {
+        let message = self
+            .conversation_service
+            .get_message(conversation_id, seq)
+            .await?;
+        Ok(message.map(|message| message.role))
+    }
+
+    /// Whether the message before `seq` is a prompt still waiting for its reply.
+    async fn is_pending_prompt(&self, conversation_id: Uuid, seq: usize) -> ServiceResult<bool> {
+        let Some(previous) = seq.checked_sub(1) else {
+            return Ok(false);
+        };
+        Ok(self.message_role(conversation_id, previous).await? == Some(MessageRole::User))
+    }
+}
@@ -0,0 +1,67 @@
+//! Recording a completed turn in the usage ledger.
+//!
+//! Like titling, this is bookkeeping next to the answer: a missing price or a
+//! failed write is logged and never surfaces as a chat error.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::models::{ModelProfile, TokenUsage, UsageRecord};
+use crate::registry::{Cost, RegistryCache};
+use crate::services::UsageService;
+
+/// Prices change rarely, so a registry cache well past the usual 24-hour
+/// refresh is still good enough to price a turn.
+const PRICING_CACHE_MAX_AGE_HOURS: i64 = 24 * 30;
+
+/// models.dev pricing for the profile's model, read from the local registry
+/// cache. `None` when the cache is missing or does not list the model.
+fn registry_pricing(provider_id: &str, model_id: &str) -> Option<Cost> {
+    let cache = RegistryCache::new(
+        RegistryCache::default_path().ok()?,
+        PRICING_CACHE_MAX_AGE_HOURS,
+    );
+    let registry = cache.load().ok()??;
+    registry.get_model(provider_id, model_id)?.cost.clone()
+}
+
+/// Append the turn's usage to the ledger. Turns without reported usage are
+/// skipped rather than recorded as zero.
+pub(crate) async fn record_turn_usage(
+    usage_service: Option<&Arc<dyn UsageService>>,
+    conversation_id: Uuid,
+    profile: &ModelProfile,
+    usage: Option<TokenUsage>,
+) {
+    let (Some(usage_service), Some(usage)) = (usage_service, usage) else {
+        return;
+    };
+    if usage.is_empty() {
+        return;
+    }
+
+    let provider_id = profile.provider_id.clone();
+    let model_id = profile.model_id.clone();
+    let pricing = tokio::task::spawn_blocking(move || registry_pricing(&provider_id, &model_id))
+        .await
+        .ok()
+        .flatten();
+
+    let record = UsageRecord {
+        conversation_id: Some(conversation_id),
+        profile_id: profile.id,
+        provider_id: profile.provider_id.clone(),
+        model_id: profile.model_id.clone(),
+        usage,
+        cost_usd: pricing.map(|pricing| usage.cost_usd(&pricing)),
+        created_at: chrono::Utc::now(),
+    };
+    if let Err(error) = usage_service.record(record).await {
+        tracing::warn!(
+            conversation_id = %conversation_id,
+            error = %error,
+            "Failed to record turn usage"
+        );
+    }
+}
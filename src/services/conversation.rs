@@ -6,7 +6,10 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::models::{ContextState, Conversation, ConversationMetadata, Message, SearchResult};
+use crate::models::{
+    ContextState, Conversation, ConversationBranch, ConversationFilter, ConversationMetadata,
+    Message, MessageVersionInfo, SearchResult,
+};
 use crate::services::ServiceResult;
 
 /// Conversation service trait
@@ -26,12 +29,13 @@ pub trait ConversationService: Send + Sync {
     /// Load a conversation by ID
     async fn load(&self, id: Uuid) -> ServiceResult<Conversation>;
 
-    /// List conversation metadata, ordered by `updated_at` DESC.
+    /// List conversation metadata matching `filter`, ordered by `updated_at` DESC.
     ///
     /// Returns lightweight metadata without loading message content.
     /// `limit` defaults to 100 (max 1000). `offset` defaults to 0.
     async fn list_metadata(
         &self,
+        filter: &ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> ServiceResult<Vec<ConversationMetadata>>;
@@ -43,13 +47,18 @@ pub trait ConversationService: Send + Sync {
     /// etc.) and passes it here. The returned `Message` is the persisted form.
     async fn add_message(&self, conversation_id: Uuid, message: Message) -> ServiceResult<Message>;
 
-    /// Full-text search across conversation titles and message content.
+    /// Full-text search across conversation titles and message content,
+    /// restricted to conversations matching `filter`.
+    ///
+    /// `query` may contain phrases, prefixes, and `profile:`/`model:`/`role:`/
+    /// `before:`/`after:`/`tag:` operators (see `SearchQuery`).
     ///
     /// Returns results ranked by relevance (title matches rank higher than content).
     /// `limit` defaults to 100 (max 1000). `offset` defaults to 0.
     async fn search(
         &self,
         query: &str,
+        filter: &ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> ServiceResult<Vec<SearchResult>>;
@@ -82,6 +91,13 @@ pub trait ConversationService: Send + Sync {
     /// Get message history for a conversation
     async fn get_messages(&self, conversation_id: Uuid) -> ServiceResult<Vec<Message>>;
 
+    /// Get the message stored at `seq`, or `None` when there is none
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<Option<Message>>;
+
     /// Update conversation metadata
     async fn update(
         &self,
@@ -89,4 +105,60 @@ pub trait ConversationService: Send + Sync {
         title: Option<String>,
         model_profile_id: Option<Uuid>,
     ) -> ServiceResult<Conversation>;
+
+    /// Fork a conversation at the message with the given `seq`.
+    ///
+    /// Creates a new conversation linked to `conversation_id` that contains
+    /// copies of messages `0..=seq`. The original conversation is untouched.
+    /// Returns `NotFound` if the conversation or the message does not exist.
+    async fn fork_at(&self, conversation_id: Uuid, seq: usize) -> ServiceResult<Conversation>;
+
+    /// List every conversation in the same branch tree as `conversation_id`.
+    ///
+    /// The root conversation comes first, followed by its descendants in
+    /// creation order. A conversation that was never forked returns a
+    /// single-element list containing itself.
+    async fn list_branches(&self, conversation_id: Uuid) -> ServiceResult<Vec<ConversationBranch>>;
+
+    /// Archive messages `seq..` as an alternate version and remove them from
+    /// the visible history.
+    ///
+    /// The next message added at `seq` starts a new version. Returns
+    /// `NotFound` if there is no message at `seq`.
+    async fn begin_message_version(&self, conversation_id: Uuid, seq: usize) -> ServiceResult<()>;
+
+    /// Show `version` of the messages starting at `seq`, archiving the
+    /// currently visible ones in its place.
+    async fn switch_message_version(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+        version: u32,
+    ) -> ServiceResult<()>;
+
+    /// List the message positions that have alternate versions, by `seq`.
+    async fn list_message_versions(
+        &self,
+        conversation_id: Uuid,
+    ) -> ServiceResult<Vec<MessageVersionInfo>>;
+
+    /// Pin or unpin a conversation.
+    async fn set_pinned(&self, id: Uuid, pinned: bool) -> ServiceResult<()>;
+
+    /// Archive or restore a conversation. Archived conversations are hidden
+    /// by the default `ConversationFilter`.
+    async fn set_archived(&self, id: Uuid, archived: bool) -> ServiceResult<()>;
+
+    /// Move a conversation into `folder`, or out of any folder with `None`.
+    async fn set_folder(&self, id: Uuid, folder: Option<String>) -> ServiceResult<()>;
+
+    /// Replace a conversation's tags. Tags are normalized with
+    /// `models::normalize_tag`; blanks and duplicates are dropped.
+    async fn set_tags(&self, id: Uuid, tags: Vec<String>) -> ServiceResult<()>;
+
+    /// Every tag in use, sorted.
+    async fn list_tags(&self) -> ServiceResult<Vec<String>>;
+
+    /// Every folder in use, sorted.
+    async fn list_folders(&self) -> ServiceResult<Vec<String>>;
 }
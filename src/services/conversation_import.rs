@@ -0,0 +1,87 @@
+//! Conversation import service trait
+//!
+//! Brings conversation history over from other chat apps' data exports and
+//! from this app's own JSON export.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::ServiceResult;
+
+/// Which app produced an export file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportSource {
+    /// `conversations.json` from a ChatGPT data export.
+    ChatGpt,
+    /// `conversations.json` from a Claude data export.
+    Claude,
+    /// `ConversationExportFormat::Json` output of this app.
+    PersonalAgent,
+}
+
+impl ImportSource {
+    /// Stable key stored with each imported conversation.
+    #[must_use]
+    pub const fn as_key(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "chatgpt",
+            Self::Claude => "claude",
+            Self::PersonalAgent => "personal-agent",
+        }
+    }
+
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "ChatGPT",
+            Self::Claude => "Claude",
+            Self::PersonalAgent => "Personal Agent",
+        }
+    }
+}
+
+/// Outcome of importing one export file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub source: ImportSource,
+    /// Conversations created by this import.
+    pub imported: usize,
+    /// Conversations already imported earlier, or with no messages.
+    pub skipped: usize,
+    /// Conversations that could not be read or stored.
+    pub failed: usize,
+}
+
+impl ImportSummary {
+    #[must_use]
+    pub const fn new(source: ImportSource) -> Self {
+        Self {
+            source,
+            imported: 0,
+            skipped: 0,
+            failed: 0,
+        }
+    }
+}
+
+/// Conversation import service interface
+#[async_trait]
+pub trait ConversationImportService: Send + Sync {
+    /// Import every conversation in the export file at `path`.
+    ///
+    /// The format is detected from the file's contents. Conversations are
+    /// created under `profile_id`; ones already imported from the same
+    /// source are skipped, so importing a file twice is harmless.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a recognised
+    /// export. Individual conversations that fail are counted in
+    /// `ImportSummary::failed` instead.
+    async fn import_file(&self, path: &Path, profile_id: Uuid) -> ServiceResult<ImportSummary>;
+
+    /// Import from export contents already in memory. See `import_file`.
+    async fn import_json(&self, json: &str, profile_id: Uuid) -> ServiceResult<ImportSummary>;
+}
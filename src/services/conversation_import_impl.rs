@@ -0,0 +1,292 @@
+//! `ConversationImportService` implementation.
+//!
+//! Export files are parsed into `ImportedConversation`s by a per-source
+//! module, then written through `ConversationService` so search indexing and
+//! every other invariant of the normal write path still hold. The only
+//! direct database access is the `conversation_imports` key table used for
+//! deduplication, and restoring the source's timestamps afterwards.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::db::worker::DbHandle;
+use crate::models::Message;
+use crate::services::conversation_import::{
+    ConversationImportService, ImportSource, ImportSummary,
+};
+use crate::services::{ConversationService, ServiceError, ServiceResult};
+
+mod chatgpt;
+mod claude;
+mod native;
+
+/// One conversation read from an export, before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportedConversation {
+    /// The source app's id for the conversation, used for deduplication.
+    external_id: String,
+    title: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    messages: Vec<Message>,
+}
+
+/// Conversations parsed from one export; each entry fails independently.
+type ParsedExport = (ImportSource, Vec<Result<ImportedConversation, String>>);
+
+/// Detect the export format and parse every conversation in it.
+///
+/// Exports are either a single conversation object or an array of them; the
+/// first conversation's shape decides the source.
+fn parse_export(json: &str) -> ServiceResult<ParsedExport> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| ServiceError::Serialization(format!("export is not valid JSON: {e}")))?;
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        object @ serde_json::Value::Object(_) => vec![object],
+        _ => {
+            return Err(ServiceError::Validation(
+                "export must be a conversation object or an array of them".to_string(),
+            ))
+        }
+    };
+
+    let Some(first) = items.first() else {
+        return Err(ServiceError::Validation(
+            "export contains no conversations".to_string(),
+        ));
+    };
+    let source = if first.get("mapping").is_some() {
+        ImportSource::ChatGpt
+    } else if first.get("chat_messages").is_some() {
+        ImportSource::Claude
+    } else if first.get("messages").is_some() && first.get("profile_id").is_some() {
+        ImportSource::PersonalAgent
+    } else {
+        return Err(ServiceError::Validation(
+            "unrecognised export: expected a ChatGPT, Claude, or Personal Agent JSON export"
+                .to_string(),
+        ));
+    };
+
+    let parse = match source {
+        ImportSource::ChatGpt => chatgpt::parse_conversation,
+        ImportSource::Claude => claude::parse_conversation,
+        ImportSource::PersonalAgent => native::parse_conversation,
+    };
+    Ok((source, items.into_iter().map(parse).collect()))
+}
+
+/// Convert a Unix timestamp in (fractional) seconds, as ChatGPT stores them.
+#[allow(clippy::cast_possible_truncation)] // millisecond precision is plenty
+fn from_unix_seconds(seconds: f64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_millis((seconds * 1000.0).round() as i64)
+}
+
+fn to_ts(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+pub struct ConversationImportServiceImpl {
+    db: DbHandle,
+    conversation_service: Arc<dyn ConversationService>,
+}
+
+impl ConversationImportServiceImpl {
+    #[must_use]
+    pub fn new(db: DbHandle, conversation_service: Arc<dyn ConversationService>) -> Self {
+        Self {
+            db,
+            conversation_service,
+        }
+    }
+
+    /// Whether `external_id` from `source` is already in the database.
+    ///
+    /// Our own exports keep their conversation ids, so one whose id already
+    /// exists (e.g. exported from and re-imported into the same database) is
+    /// also a duplicate.
+    async fn already_imported(
+        &self,
+        source: ImportSource,
+        external_id: &str,
+    ) -> ServiceResult<bool> {
+        let source_key = source.as_key();
+        let external_id = external_id.to_string();
+        let check_conversations = source == ImportSource::PersonalAgent;
+        self.db
+            .execute(move |conn| {
+                let keyed: bool = conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM conversation_imports
+                                   WHERE source = ?1 AND external_id = ?2)",
+                    rusqlite::params![source_key, external_id],
+                    |row| row.get(0),
+                )?;
+                if keyed || !check_conversations {
+                    return Ok(keyed);
+                }
+                conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM conversations WHERE id = ?1)",
+                    [&external_id],
+                    |row| row.get(0),
+                )
+            })
+            .await
+    }
+
+    /// Record the import key and restore the source's timestamps, which
+    /// `create` and `add_message` set to the time of the import.
+    async fn finish_import(
+        &self,
+        source: ImportSource,
+        conversation: &ImportedConversation,
+        conversation_id: Uuid,
+    ) -> ServiceResult<()> {
+        let source_key = source.as_key();
+        let external_id = conversation.external_id.clone();
+        let id = conversation_id.to_string();
+        let created_at = to_ts(conversation.created_at);
+        let updated_at = to_ts(conversation.updated_at);
+        let imported_at = to_ts(Utc::now());
+        self.db
+            .execute(move |conn| {
+                let tx = conn.unchecked_transaction()?;
+                tx.execute(
+                    "INSERT INTO conversation_imports
+                         (source, external_id, conversation_id, imported_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![source_key, external_id, id, imported_at],
+                )?;
+                tx.execute(
+                    "UPDATE conversations SET created_at = ?1, updated_at = ?2 WHERE id = ?3",
+                    rusqlite::params![created_at, updated_at, id],
+                )?;
+                tx.commit()
+            })
+            .await
+    }
+
+    /// Store one conversation. Returns `false` if it was skipped.
+    async fn import_one(
+        &self,
+        source: ImportSource,
+        conversation: ImportedConversation,
+        profile_id: Uuid,
+    ) -> ServiceResult<bool> {
+        if conversation.messages.is_empty()
+            || self
+                .already_imported(source, &conversation.external_id)
+                .await?
+        {
+            return Ok(false);
+        }
+
+        let created = self
+            .conversation_service
+            .create(conversation.title.clone(), profile_id)
+            .await?;
+        let stored = async {
+            for message in &conversation.messages {
+                self.conversation_service
+                    .add_message(created.id, message.clone())
+                    .await?;
+            }
+            self.finish_import(source, &conversation, created.id).await
+        }
+        .await;
+
+        if let Err(e) = stored {
+            // Leave nothing half-imported behind; the next import retries it.
+            let _ = self.conversation_service.delete(created.id).await;
+            return Err(e);
+        }
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl ConversationImportService for ConversationImportServiceImpl {
+    async fn import_file(&self, path: &Path, profile_id: Uuid) -> ServiceResult<ImportSummary> {
+        let json = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| ServiceError::Io(format!("failed to read {}: {e}", path.display())))?;
+        self.import_json(&json, profile_id).await
+    }
+
+    async fn import_json(&self, json: &str, profile_id: Uuid) -> ServiceResult<ImportSummary> {
+        let (source, conversations) = parse_export(json)?;
+        let mut summary = ImportSummary::new(source);
+
+        for parsed in conversations {
+            let outcome = match parsed {
+                Ok(conversation) => self.import_one(source, conversation, profile_id).await,
+                Err(e) => Err(ServiceError::Serialization(e)),
+            };
+            match outcome {
+                Ok(true) => summary.imported += 1,
+                Ok(false) => summary.skipped += 1,
+                Err(e) => {
+                    tracing::warn!(
+                        source = source.as_key(),
+                        "Failed to import conversation: {e}"
+                    );
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        tracing::info!(
+            source = source.as_key(),
+            imported = summary.imported,
+            skipped = summary.skipped,
+            failed = summary.failed,
+            "Conversation import finished"
+        );
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_each_export_format() {
+        let chatgpt = r#"[{"id": "c1", "title": "t", "mapping": {}}]"#;
+        let claude = r#"[{"uuid": "c1", "name": "t", "chat_messages": []}]"#;
+        let native = serde_json::to_string(&crate::models::Conversation::new(Uuid::nil())).unwrap();
+
+        assert_eq!(parse_export(chatgpt).unwrap().0, ImportSource::ChatGpt);
+        assert_eq!(parse_export(claude).unwrap().0, ImportSource::Claude);
+        assert_eq!(
+            parse_export(&native).unwrap().0,
+            ImportSource::PersonalAgent
+        );
+    }
+
+    #[test]
+    fn rejects_unrecognised_exports() {
+        assert!(matches!(
+            parse_export(r#"[{"foo": 1}]"#),
+            Err(ServiceError::Validation(_))
+        ));
+        assert!(matches!(
+            parse_export("[]"),
+            Err(ServiceError::Validation(_))
+        ));
+        assert!(matches!(
+            parse_export("not json"),
+            Err(ServiceError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn unix_seconds_keep_milliseconds() {
+        let ts = from_unix_seconds(1_700_000_000.123_4).unwrap();
+        assert_eq!(to_ts(ts), "2023-11-14T22:13:20.123Z");
+    }
+}
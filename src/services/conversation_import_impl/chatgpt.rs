@@ -0,0 +1,420 @@
+//! ChatGPT `conversations.json` parsing.
+//!
+//! A ChatGPT conversation is a tree of message nodes (edits and regenerations
+//! branch it); only the branch ending at `current_node`, the one the user
+//! last saw, is imported. Consecutive assistant-side nodes — reasoning, tool
+//! invocations, tool output, and the reply — fold into one assistant
+//! `Message`, the way this app stores a turn.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{from_unix_seconds, ImportedConversation};
+use crate::llm::tools::{ToolResult, ToolUse};
+use crate::models::Message;
+
+#[derive(Debug, Deserialize)]
+struct ChatGptConversation {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    conversation_id: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    create_time: Option<f64>,
+    #[serde(default)]
+    update_time: Option<f64>,
+    #[serde(default)]
+    mapping: HashMap<String, ChatGptNode>,
+    #[serde(default)]
+    current_node: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptNode {
+    #[serde(default)]
+    message: Option<ChatGptMessage>,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    children: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptMessage {
+    #[serde(default)]
+    id: String,
+    author: ChatGptAuthor,
+    #[serde(default)]
+    create_time: Option<f64>,
+    #[serde(default)]
+    content: Value,
+    #[serde(default)]
+    recipient: Option<String>,
+    #[serde(default)]
+    metadata: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptAuthor {
+    role: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+impl ChatGptMessage {
+    fn is_hidden(&self) -> bool {
+        self.metadata
+            .get("is_visually_hidden_from_conversation")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    fn content_type(&self) -> &str {
+        self.content
+            .get("content_type")
+            .and_then(Value::as_str)
+            .unwrap_or("text")
+    }
+
+    /// Text of the message: string `parts` (images and other attachments are
+    /// dropped), or `text` for code and execution output.
+    fn text(&self) -> String {
+        if let Some(parts) = self.content.get("parts").and_then(Value::as_array) {
+            return parts
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        self.content
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Reasoning from a `thoughts` message.
+    fn thoughts(&self) -> String {
+        self.content
+            .get("thoughts")
+            .and_then(Value::as_array)
+            .map(|thoughts| {
+                thoughts
+                    .iter()
+                    .filter_map(|thought| thought.get("content").and_then(Value::as_str))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            })
+            .unwrap_or_default()
+    }
+
+    fn model_slug(&self) -> Option<String> {
+        self.metadata
+            .get("model_slug")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    }
+}
+
+/// The branch the user last saw, root first.
+fn active_branch(conversation: &ChatGptConversation) -> Vec<&ChatGptMessage> {
+    let mapping = &conversation.mapping;
+    let leaf = conversation
+        .current_node
+        .clone()
+        .filter(|id| mapping.contains_key(id))
+        .or_else(|| {
+            // Without `current_node`, follow the newest child from the root.
+            let mut id = mapping
+                .iter()
+                .find(|(_, node)| node.parent.is_none())
+                .map(|(id, _)| id.clone())?;
+            while let Some(child) = mapping.get(&id).and_then(|node| node.children.last()) {
+                id.clone_from(child);
+            }
+            Some(id)
+        });
+
+    let mut branch = Vec::new();
+    let mut next = leaf;
+    while let Some(node) = next.as_ref().and_then(|id| mapping.get(id)) {
+        if let Some(message) = &node.message {
+            branch.push(message);
+        }
+        if branch.len() > mapping.len() {
+            break; // malformed parent cycle
+        }
+        next.clone_from(&node.parent);
+    }
+    branch.reverse();
+    branch
+}
+
+/// Assistant-side output accumulated until the next user message.
+#[derive(Default)]
+struct PendingTurn {
+    content: Vec<String>,
+    thinking: Vec<String>,
+    tool_calls: Vec<ToolUse>,
+    tool_results: Vec<ToolResult>,
+    model_id: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl PendingTurn {
+    fn flush(&mut self, messages: &mut Vec<Message>) {
+        let turn = std::mem::take(self);
+        if turn.content.is_empty() && turn.thinking.is_empty() && turn.tool_calls.is_empty() {
+            return;
+        }
+        let mut message = Message::assistant(turn.content.join("\n\n"));
+        if !turn.thinking.is_empty() {
+            message.thinking_content = Some(turn.thinking.join("\n\n"));
+        }
+        if !turn.tool_calls.is_empty() {
+            message.tool_calls = serde_json::to_string(&turn.tool_calls).ok();
+        }
+        if !turn.tool_results.is_empty() {
+            message.tool_results = serde_json::to_string(&turn.tool_results).ok();
+        }
+        message.model_id = turn.model_id;
+        if let Some(timestamp) = turn.timestamp {
+            message.timestamp = timestamp;
+        }
+        messages.push(message);
+    }
+
+    fn add_assistant(&mut self, message: &ChatGptMessage, timestamp: DateTime<Utc>) {
+        self.timestamp.get_or_insert(timestamp);
+        if let Some(model) = message.model_slug() {
+            self.model_id = Some(model);
+        }
+        match (message.content_type(), message.recipient.as_deref()) {
+            ("thoughts", _) => self.thinking.push(message.thoughts()),
+            ("reasoning_recap", _) => {}
+            (_, Some(recipient)) if recipient != "all" => {
+                self.tool_calls.push(ToolUse::new(
+                    message.id.clone(),
+                    recipient,
+                    serde_json::json!({ "input": message.text() }),
+                ));
+            }
+            _ => {
+                let text = message.text();
+                if !text.is_empty() {
+                    self.content.push(text);
+                }
+            }
+        }
+    }
+
+    fn add_tool_output(&mut self, message: &ChatGptMessage) {
+        let tool_use_id = self.tool_calls.last().map_or_else(
+            || message.author.name.clone().unwrap_or_default(),
+            |call| call.id.clone(),
+        );
+        self.tool_results
+            .push(ToolResult::success(tool_use_id, message.text()));
+    }
+}
+
+pub(super) fn parse_conversation(value: Value) -> Result<ImportedConversation, String> {
+    let conversation: ChatGptConversation =
+        serde_json::from_value(value).map_err(|e| format!("invalid ChatGPT conversation: {e}"))?;
+    let external_id = conversation
+        .conversation_id
+        .clone()
+        .or_else(|| conversation.id.clone())
+        .ok_or("ChatGPT conversation has no id")?;
+    let created_at = conversation
+        .create_time
+        .and_then(from_unix_seconds)
+        .unwrap_or_else(Utc::now);
+    let updated_at = conversation
+        .update_time
+        .and_then(from_unix_seconds)
+        .unwrap_or(created_at);
+
+    let mut messages = Vec::new();
+    let mut pending = PendingTurn::default();
+    for message in active_branch(&conversation) {
+        if message.is_hidden() {
+            continue;
+        }
+        let timestamp = message
+            .create_time
+            .and_then(from_unix_seconds)
+            .unwrap_or(created_at);
+        match message.author.role.as_str() {
+            "user" => {
+                pending.flush(&mut messages);
+                let mut user = Message::user(message.text());
+                user.timestamp = timestamp;
+                messages.push(user);
+            }
+            "assistant" => pending.add_assistant(message, timestamp),
+            "tool" => pending.add_tool_output(message),
+            "system" => {
+                let text = message.text();
+                if !text.trim().is_empty() {
+                    pending.flush(&mut messages);
+                    let mut system = Message::system(text);
+                    system.timestamp = timestamp;
+                    messages.push(system);
+                }
+            }
+            _ => {}
+        }
+    }
+    pending.flush(&mut messages);
+
+    Ok(ImportedConversation {
+        external_id,
+        title: conversation.title.filter(|title| !title.trim().is_empty()),
+        created_at,
+        updated_at,
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use serde_json::json;
+
+    fn node(id: &str, parent: Option<&str>, children: &[&str], message: Value) -> (String, Value) {
+        (
+            id.to_string(),
+            json!({ "id": id, "parent": parent, "children": children, "message": message }),
+        )
+    }
+
+    fn message(id: &str, role: &str, content: Value) -> Value {
+        json!({
+            "id": id,
+            "author": { "role": role, "name": null },
+            "create_time": 1_700_000_000.0,
+            "content": content,
+            "recipient": "all",
+            "metadata": { "model_slug": "gpt-4o" },
+        })
+    }
+
+    fn text(parts: &[&str]) -> Value {
+        json!({ "content_type": "text", "parts": parts })
+    }
+
+    #[test]
+    fn follows_the_current_branch_and_folds_tool_use_into_the_reply() {
+        let mut call = message(
+            "call",
+            "assistant",
+            json!({ "content_type": "code", "text": "2+2" }),
+        );
+        call["recipient"] = json!("python");
+        let mut tool = message(
+            "out",
+            "tool",
+            json!({ "content_type": "execution_output", "text": "4" }),
+        );
+        tool["author"]["name"] = json!("python");
+
+        let mapping: serde_json::Map<String, Value> = [
+            node("root", None, &["u1"], Value::Null),
+            node(
+                "u1",
+                Some("root"),
+                &["old", "think"],
+                message("u1", "user", text(&["What is 2+2?"])),
+            ),
+            node(
+                "old",
+                Some("u1"),
+                &[],
+                message("old", "assistant", text(&["Discarded"])),
+            ),
+            node(
+                "think",
+                Some("u1"),
+                &["call"],
+                message(
+                    "think",
+                    "assistant",
+                    json!({
+                        "content_type": "thoughts",
+                        "thoughts": [{ "summary": "s", "content": "Add them." }],
+                    }),
+                ),
+            ),
+            node("call", Some("think"), &["out"], call),
+            node("out", Some("call"), &["a1"], tool),
+            node(
+                "a1",
+                Some("out"),
+                &[],
+                message("a1", "assistant", text(&["It is 4."])),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let conversation = parse_conversation(json!({
+            "id": "conv-1",
+            "title": "Math",
+            "create_time": 1_700_000_000.0,
+            "update_time": 1_700_000_100.0,
+            "mapping": mapping,
+            "current_node": "a1",
+        }))
+        .unwrap();
+
+        assert_eq!(conversation.external_id, "conv-1");
+        assert_eq!(conversation.title.as_deref(), Some("Math"));
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[0].role, MessageRole::User);
+        assert_eq!(conversation.messages[0].content, "What is 2+2?");
+
+        let reply = &conversation.messages[1];
+        assert_eq!(reply.content, "It is 4.");
+        assert_eq!(reply.thinking_content.as_deref(), Some("Add them."));
+        assert_eq!(reply.model_id.as_deref(), Some("gpt-4o"));
+        let calls: Vec<ToolUse> =
+            serde_json::from_str(reply.tool_calls.as_deref().unwrap()).unwrap();
+        assert_eq!(calls[0].name, "python");
+        let results: Vec<ToolResult> =
+            serde_json::from_str(reply.tool_results.as_deref().unwrap()).unwrap();
+        assert_eq!(results[0].tool_use_id, "call");
+        assert_eq!(results[0].content, "4");
+    }
+
+    #[test]
+    fn hidden_and_empty_system_messages_are_dropped() {
+        let mut hidden = message("h", "user", text(&["context"]));
+        hidden["metadata"] = json!({ "is_visually_hidden_from_conversation": true });
+        let mapping: serde_json::Map<String, Value> = [
+            node("s", None, &["h"], message("s", "system", text(&[""]))),
+            node("h", Some("s"), &["u"], hidden),
+            node("u", Some("h"), &[], message("u", "user", text(&["Hi"]))),
+        ]
+        .into_iter()
+        .collect();
+
+        let conversation = parse_conversation(json!({
+            "conversation_id": "conv-2",
+            "title": null,
+            "mapping": mapping,
+        }))
+        .unwrap();
+
+        assert_eq!(conversation.title, None);
+        assert_eq!(conversation.messages.len(), 1);
+        assert_eq!(conversation.messages[0].content, "Hi");
+    }
+}
@@ -0,0 +1,236 @@
+//! Claude data export (`conversations.json`) parsing.
+//!
+//! Each message carries typed `content` blocks — text, thinking, tool use and
+//! tool result — with a flattened `text` for older exports that lack them.
+//! Text pasted as attachments is kept, appended to the user's message.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::ImportedConversation;
+use crate::llm::tools::{ToolResult, ToolUse};
+use crate::models::Message;
+
+#[derive(Debug, Deserialize)]
+struct ClaudeConversation {
+    uuid: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    chat_messages: Vec<ClaudeMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeMessage {
+    #[serde(default)]
+    uuid: String,
+    #[serde(default)]
+    sender: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    content: Vec<Value>,
+    #[serde(default)]
+    attachments: Vec<ClaudeAttachment>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeAttachment {
+    #[serde(default)]
+    file_name: String,
+    #[serde(default)]
+    extracted_content: String,
+}
+
+/// Text of a tool result, which is either a string or a list of text blocks.
+fn tool_result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|block| block.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn str_field<'a>(block: &'a Value, key: &str) -> Option<&'a str> {
+    block.get(key).and_then(Value::as_str)
+}
+
+/// Build a `Message` from the content blocks, or from `text` if there are none.
+fn convert_message(message: &ClaudeMessage, fallback_ts: DateTime<Utc>) -> Option<Message> {
+    let mut text = Vec::new();
+    let mut thinking = Vec::new();
+    let mut tool_calls: Vec<ToolUse> = Vec::new();
+    let mut tool_results = Vec::new();
+
+    for (index, block) in message.content.iter().enumerate() {
+        match str_field(block, "type") {
+            Some("text") => text.extend(str_field(block, "text").map(str::to_string)),
+            Some("thinking") => thinking.extend(str_field(block, "thinking").map(str::to_string)),
+            Some("tool_use") => tool_calls.push(ToolUse::new(
+                str_field(block, "id")
+                    .map_or_else(|| format!("{}-{index}", message.uuid), str::to_string),
+                str_field(block, "name").unwrap_or("tool"),
+                block.get("input").cloned().unwrap_or(Value::Null),
+            )),
+            Some("tool_result") => {
+                let tool_use_id = str_field(block, "tool_use_id")
+                    .map(str::to_string)
+                    .or_else(|| tool_calls.last().map(|call| call.id.clone()))
+                    .unwrap_or_default();
+                tool_results.push(ToolResult {
+                    tool_use_id,
+                    content: tool_result_text(block.get("content")),
+                    is_error: block
+                        .get("is_error")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                });
+            }
+            _ => {}
+        }
+    }
+    if message.content.is_empty() && !message.text.is_empty() {
+        text.push(message.text.clone());
+    }
+    for attachment in &message.attachments {
+        if !attachment.extracted_content.is_empty() {
+            text.push(format!(
+                "[{}]\n{}",
+                attachment.file_name, attachment.extracted_content
+            ));
+        }
+    }
+
+    let content = text.join("\n\n");
+    let mut converted = match message.sender.as_str() {
+        "human" => Message::user(content),
+        "assistant" => {
+            if content.is_empty() && thinking.is_empty() && tool_calls.is_empty() {
+                return None;
+            }
+            let mut reply = Message::assistant(content);
+            if !thinking.is_empty() {
+                reply.thinking_content = Some(thinking.join("\n\n"));
+            }
+            if !tool_calls.is_empty() {
+                reply.tool_calls = serde_json::to_string(&tool_calls).ok();
+            }
+            if !tool_results.is_empty() {
+                reply.tool_results = serde_json::to_string(&tool_results).ok();
+            }
+            reply
+        }
+        _ => return None,
+    };
+    converted.timestamp = message.created_at.unwrap_or(fallback_ts);
+    Some(converted)
+}
+
+pub(super) fn parse_conversation(value: Value) -> Result<ImportedConversation, String> {
+    let conversation: ClaudeConversation =
+        serde_json::from_value(value).map_err(|e| format!("invalid Claude conversation: {e}"))?;
+    let created_at = conversation.created_at.unwrap_or_else(Utc::now);
+    let updated_at = conversation.updated_at.unwrap_or(created_at);
+    let messages = conversation
+        .chat_messages
+        .iter()
+        .filter_map(|message| convert_message(message, created_at))
+        .collect();
+
+    Ok(ImportedConversation {
+        external_id: conversation.uuid,
+        title: conversation.name.filter(|name| !name.trim().is_empty()),
+        created_at,
+        updated_at,
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use serde_json::json;
+
+    #[test]
+    fn maps_content_blocks_to_thinking_and_tools() {
+        let conversation = parse_conversation(json!({
+            "uuid": "conv-1",
+            "name": "Weather",
+            "created_at": "2024-05-01T10:00:00.000000Z",
+            "updated_at": "2024-05-01T10:05:00.000000Z",
+            "chat_messages": [
+                {
+                    "uuid": "m1",
+                    "sender": "human",
+                    "text": "Weather in Paris?",
+                    "content": [{ "type": "text", "text": "Weather in Paris?" }],
+                    "attachments": [{ "file_name": "notes.txt", "extracted_content": "umbrella" }],
+                    "created_at": "2024-05-01T10:00:01Z",
+                },
+                {
+                    "uuid": "m2",
+                    "sender": "assistant",
+                    "text": "",
+                    "content": [
+                        { "type": "thinking", "thinking": "Look it up." },
+                        { "type": "tool_use", "name": "web_search", "input": { "query": "paris weather" } },
+                        { "type": "tool_result", "name": "web_search",
+                          "content": [{ "type": "text", "text": "Sunny" }], "is_error": false },
+                        { "type": "text", "text": "It is sunny." },
+                    ],
+                    "created_at": "2024-05-01T10:00:05Z",
+                },
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(conversation.external_id, "conv-1");
+        assert_eq!(conversation.title.as_deref(), Some("Weather"));
+        assert_eq!(conversation.messages.len(), 2);
+
+        let prompt = &conversation.messages[0];
+        assert_eq!(prompt.role, MessageRole::User);
+        assert_eq!(prompt.content, "Weather in Paris?\n\n[notes.txt]\numbrella");
+
+        let reply = &conversation.messages[1];
+        assert_eq!(reply.content, "It is sunny.");
+        assert_eq!(reply.thinking_content.as_deref(), Some("Look it up."));
+        let calls: Vec<ToolUse> =
+            serde_json::from_str(reply.tool_calls.as_deref().unwrap()).unwrap();
+        assert_eq!(calls[0].id, "m2-1");
+        assert_eq!(calls[0].input["query"], "paris weather");
+        let results: Vec<ToolResult> =
+            serde_json::from_str(reply.tool_results.as_deref().unwrap()).unwrap();
+        assert_eq!(results[0].tool_use_id, "m2-1");
+        assert_eq!(results[0].content, "Sunny");
+    }
+
+    #[test]
+    fn falls_back_to_flat_text_for_older_exports() {
+        let conversation = parse_conversation(json!({
+            "uuid": "conv-2",
+            "name": "",
+            "chat_messages": [
+                { "sender": "human", "text": "Hello" },
+                { "sender": "assistant", "text": "" },
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(conversation.title, None);
+        assert_eq!(conversation.messages.len(), 1);
+        assert_eq!(conversation.messages[0].content, "Hello");
+    }
+}
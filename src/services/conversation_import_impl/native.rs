@@ -0,0 +1,18 @@
+//! This app's own JSON export: a serialized `Conversation`.
+
+use serde_json::Value;
+
+use super::ImportedConversation;
+use crate::models::Conversation;
+
+pub(super) fn parse_conversation(value: Value) -> Result<ImportedConversation, String> {
+    let conversation: Conversation =
+        serde_json::from_value(value).map_err(|e| format!("invalid conversation export: {e}"))?;
+    Ok(ImportedConversation {
+        external_id: conversation.id.to_string(),
+        title: conversation.title,
+        created_at: conversation.created_at,
+        updated_at: conversation.updated_at,
+        messages: conversation.messages,
+    })
+}
@@ -9,18 +9,22 @@
 
 use async_trait::async_trait;
 use chrono::SecondsFormat;
-use std::collections::HashMap;
 use std::sync::Mutex;
 use uuid::Uuid;
 
 use crate::db::worker::DbHandle;
 use crate::models::{
-    ContextState, Conversation, ConversationMetadata, Message, MessageRole, SearchMatchType,
-    SearchResult,
+    ContextState, Conversation, ConversationBranch, ConversationFilter, ConversationMetadata,
+    Message, MessageRole, MessageVersionInfo, SearchResult,
 };
 use crate::services::conversation::ConversationService;
 use crate::services::{ServiceError, ServiceResult};
 
+mod branches;
+mod organization;
+mod search;
+mod versions;
+
 // ---------------------------------------------------------------------------
 // Struct
 // ---------------------------------------------------------------------------
@@ -154,77 +158,46 @@ fn row_to_message(
 }
 
 // ---------------------------------------------------------------------------
-// FTS5 query sanitization (spec §4.2)
+// Helper – query the messages of a conversation (used by load, get_messages
+// and get_message)
 // ---------------------------------------------------------------------------
 
-/// Sanitize a raw user query into a safe FTS5 MATCH expression.
-///
-/// Returns `None` if the query is empty after sanitization (caller should
-/// return `vec![]` immediately without querying the DB).
-fn sanitize_fts_query(raw: &str) -> Option<String> {
-    // Step 1: trim leading/trailing whitespace.
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-
-    // Step 2: replace FTS5 structural operators with spaces so adjacent tokens
-    // separated by operators become distinct terms (e.g., `foo(bar)` → `foo bar`).
-    // Characters: * ( ) { } ^ ~ :
-    // Note: + - " # . are preserved here because they are treated as literal
-    // characters inside double-quoted FTS5 terms (spec §4.2 step 4).
-    let replaced: String = trimmed
-        .chars()
-        .map(|c| {
-            if matches!(c, '*' | '(' | ')' | '{' | '}' | '^' | '~' | ':') {
-                ' '
-            } else {
-                c
-            }
-        })
-        .collect();
-
-    // Step 3: tokenize on whitespace.
-    let terms: Vec<&str> = replaced.split_whitespace().collect();
-    if terms.is_empty() {
-        return None;
-    }
-
-    // Step 4 & 5: quote each term (escaping embedded double-quotes as ""),
-    // join with implicit AND, append prefix wildcard to last term.
-    let last_idx = terms.len() - 1;
-    let quoted: Vec<String> = terms
-        .iter()
-        .enumerate()
-        .map(|(i, term)| {
-            let escaped = term.replace('"', "\"\"");
-            if i == last_idx {
-                format!("\"{escaped}\"*")
-            } else {
-                format!("\"{escaped}\"")
-            }
-        })
-        .collect();
-
-    Some(quoted.join(" "))
+fn select_messages(
+    conn: &rusqlite::Connection,
+    conversation_id: &str,
+) -> Result<Vec<Message>, rusqlite::Error> {
+    query_messages(
+        conn,
+        "WHERE conversation_id = ?1 ORDER BY seq ASC",
+        rusqlite::params![conversation_id],
+    )
 }
 
-// ---------------------------------------------------------------------------
-// Helper – query all messages for a conversation (used by load + get_messages)
-// ---------------------------------------------------------------------------
-
-fn select_messages(
+fn select_message(
     conn: &rusqlite::Connection,
     conversation_id: &str,
+    seq: i64,
+) -> Result<Option<Message>, rusqlite::Error> {
+    let messages = query_messages(
+        conn,
+        "WHERE conversation_id = ?1 AND seq = ?2",
+        rusqlite::params![conversation_id, seq],
+    )?;
+    Ok(messages.into_iter().next())
+}
+
+fn query_messages(
+    conn: &rusqlite::Connection,
+    filter: &str,
+    params: impl rusqlite::Params,
 ) -> Result<Vec<Message>, rusqlite::Error> {
-    let mut stmt = conn.prepare_cached(
+    let mut stmt = conn.prepare_cached(&format!(
         "SELECT role, content, thinking_content, model_id, tool_calls, tool_results, created_at
          FROM messages
-         WHERE conversation_id = ?1
-         ORDER BY seq ASC",
-    )?;
+         {filter}"
+    ))?;
 
-    let rows = stmt.query_map([conversation_id], |row| {
+    let rows = stmt.query_map(params, |row| {
         Ok((
             row.get::<_, String>(0)?,
             row.get::<_, String>(1)?,
@@ -265,82 +238,6 @@ fn select_messages(
     Ok(messages)
 }
 
-// ---------------------------------------------------------------------------
-// Per-conversation aggregation state used by search
-// ---------------------------------------------------------------------------
-
-struct ConvAgg {
-    id_str: String,
-    title_opt: Option<String>,
-    updated_at_str: String,
-    message_count: i64,
-    best_score: f64, // minimum (most negative) = best
-    best_ctx: String,
-}
-
-/// Aggregate per-FTS-hit rows into one `ConvAgg` per conversation,
-/// keeping the best (most-negative) BM25 score and its snippet.
-fn aggregate_hits(
-    per_hit_rows: Vec<(String, Option<String>, String, i64, f64, String)>,
-) -> Vec<ConvAgg> {
-    let mut agg: HashMap<String, ConvAgg> = HashMap::new();
-    for (id_str, title_opt, updated_at_str, message_count, score, ctx) in per_hit_rows {
-        let entry = agg.entry(id_str.clone()).or_insert_with(|| ConvAgg {
-            id_str: id_str.clone(),
-            title_opt: title_opt.clone(),
-            updated_at_str: updated_at_str.clone(),
-            message_count,
-            best_score: score,
-            best_ctx: ctx.clone(),
-        });
-        // BM25 scores are negative; lower (more negative) is a better match.
-        if score < entry.best_score {
-            entry.best_score = score;
-            entry.best_ctx = ctx;
-        }
-    }
-
-    // Sort by best score ascending (most negative first), then updated_at DESC.
-    let mut agg_vec: Vec<ConvAgg> = agg.into_values().collect();
-    agg_vec.sort_by(|a, b| {
-        a.best_score
-            .partial_cmp(&b.best_score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| b.updated_at_str.cmp(&a.updated_at_str))
-    });
-    agg_vec
-}
-
-/// Convert a `ConvAgg` into a `SearchResult`, classifying the match type by
-/// checking whether any raw query term appears in the title (case-insensitive).
-fn conv_agg_to_search_result(
-    agg: ConvAgg,
-    raw_terms: &[String],
-) -> Result<SearchResult, ServiceError> {
-    let conversation_id = Uuid::parse_str(&agg.id_str)
-        .map_err(|e| ServiceError::Storage(format!("invalid uuid: {e}")))?;
-    let title = agg.title_opt.unwrap_or_else(|| "Untitled".to_string());
-    let updated_at = parse_ts(&agg.updated_at_str)?;
-    let title_lower = title.to_lowercase();
-    let match_type = if raw_terms
-        .iter()
-        .any(|term| title_lower.contains(term.as_str()))
-    {
-        SearchMatchType::Title
-    } else {
-        SearchMatchType::Content
-    };
-    Ok(SearchResult {
-        conversation_id,
-        title,
-        match_type,
-        match_context: agg.best_ctx,
-        score: agg.best_score,
-        updated_at,
-        message_count: usize::try_from(agg.message_count).unwrap_or(0),
-    })
-}
-
 // ---------------------------------------------------------------------------
 // Trait implementation
 // ---------------------------------------------------------------------------
@@ -445,6 +342,7 @@ impl ConversationService for SqliteConversationService {
 
     async fn list_metadata(
         &self,
+        filter: &ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> ServiceResult<Vec<ConversationMetadata>> {
@@ -452,58 +350,7 @@ impl ConversationService for SqliteConversationService {
         let limit = i64::try_from(limit.unwrap_or(100).min(1000)).unwrap_or(i64::MAX);
         let offset = i64::try_from(offset.unwrap_or(0)).unwrap_or(i64::MAX);
 
-        self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare_cached(
-                    "SELECT
-                         c.id,
-                         c.title,
-                         c.profile_id,
-                         c.created_at,
-                         c.updated_at,
-                         (SELECT COUNT(*) FROM messages m WHERE m.conversation_id = c.id)
-                             AS message_count,
-                         (SELECT SUBSTR(m2.content, 1, 100)
-                          FROM messages m2
-                          WHERE m2.conversation_id = c.id
-                          ORDER BY m2.seq DESC
-                          LIMIT 1) AS last_message_preview
-                     FROM conversations c
-                     ORDER BY c.updated_at DESC
-                     LIMIT ?1 OFFSET ?2",
-                )?;
-
-                let rows = stmt.query_map(rusqlite::params![limit, offset], |row| {
-                    Ok((
-                        row.get::<_, String>(0)?,
-                        row.get::<_, Option<String>>(1)?,
-                        row.get::<_, Option<String>>(2)?,
-                        row.get::<_, String>(3)?,
-                        row.get::<_, String>(4)?,
-                        row.get::<_, i64>(5)?,
-                        row.get::<_, Option<String>>(6)?,
-                    ))
-                })?;
-
-                let mut results = Vec::new();
-                for row in rows {
-                    let (id_str, title, profile_id_str, ca_str, ua_str, mc, preview) = row?;
-                    results.push(ConversationMetadata {
-                        id: parse_uuid_sql(&id_str, 0)?,
-                        title,
-                        profile_id: profile_id_str
-                            .as_deref()
-                            .and_then(|s| Uuid::parse_str(s).ok()),
-                        created_at: parse_ts_sql(&ca_str, 3)?,
-                        updated_at: parse_ts_sql(&ua_str, 4)?,
-                        message_count: usize::try_from(mc).unwrap_or(0),
-                        last_message_preview: preview,
-                    });
-                }
-
-                Ok(results)
-            })
-            .await
+        organization::list_metadata(&self.db, filter, limit, offset).await
     }
 
     // -----------------------------------------------------------------------
@@ -568,77 +415,11 @@ impl ConversationService for SqliteConversationService {
     async fn search(
         &self,
         query: &str,
+        filter: &ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> ServiceResult<Vec<SearchResult>> {
-        // Sanitize the query before any DB interaction.
-        let Some(fts_query) = sanitize_fts_query(query) else {
-            return Ok(Vec::new());
-        };
-
-        // Keep the raw terms for post-query SearchMatchType classification.
-        let raw_terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
-
-        let limit = i64::try_from(limit.unwrap_or(100).min(1000)).unwrap_or(i64::MAX);
-        let offset = i64::try_from(offset.unwrap_or(0)).unwrap_or(i64::MAX);
-
-        // SQLite FTS5 auxiliary functions (bm25, snippet) cannot be used in
-        // aggregate context (e.g., MIN(bm25(...)) GROUP BY) — they require the
-        // FTS virtual table to be the driving query with no aggregation layer
-        // between the FTS scan and the auxiliary function call.
-        //
-        // Strategy: collect one row per FTS hit (no GROUP BY in SQL), then
-        // aggregate per conversation_id in Rust. This preserves the per-hit
-        // BM25 score and snippet while allowing us to pick the best score and
-        // context for each conversation.
-        let per_hit_rows: Vec<(String, Option<String>, String, i64, f64, String)> = self
-            .db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare_cached(
-                    "SELECT
-                         c.id, c.title, c.updated_at,
-                         (SELECT COUNT(*) FROM messages m WHERE m.conversation_id = c.id)
-                             AS message_count,
-                         bm25(search_index, 10.0, 1.0) AS score,
-                         snippet(search_index, 1, '[', ']', '...', 24) AS ctx
-                     FROM search_index
-                     JOIN conversations c ON c.id = search_index.conversation_id
-                     WHERE search_index MATCH ?1",
-                )?;
-
-                let rows = stmt.query_map(rusqlite::params![fts_query], |row| {
-                    Ok((
-                        row.get::<_, String>(0)?,
-                        row.get::<_, Option<String>>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, i64>(3)?,
-                        row.get::<_, f64>(4)?,
-                        row.get::<_, String>(5)?,
-                    ))
-                })?;
-
-                let mut results = Vec::new();
-                for row in rows {
-                    results.push(row?);
-                }
-                Ok(results)
-            })
-            .await?;
-
-        // Aggregate per-hit rows into per-conversation best scores in Rust.
-        // BM25 scores are negative (more negative = better match).
-        let agg_vec = aggregate_hits(per_hit_rows);
-
-        // Apply limit/offset after in-Rust aggregation.
-        let skip = usize::try_from(offset).unwrap_or(0);
-        let take = usize::try_from(limit).unwrap_or(usize::MAX);
-
-        agg_vec
-            .into_iter()
-            .skip(skip)
-            .take(take)
-            .map(|agg| conv_agg_to_search_result(agg, &raw_terms))
-            .collect()
+        search::search(&self.db, query, filter, limit, offset).await
     }
 
     // -----------------------------------------------------------------------
@@ -783,6 +564,19 @@ impl ConversationService for SqliteConversationService {
             .await
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<Option<Message>> {
+        let id_str = conversation_id.to_string();
+        let seq = i64::try_from(seq)
+            .map_err(|_| ServiceError::Validation(format!("message seq out of range: {seq}")))?;
+        self.db
+            .execute(move |conn| select_message(conn, &id_str, seq))
+            .await
+    }
+
     // -----------------------------------------------------------------------
     // update
     // -----------------------------------------------------------------------
@@ -898,4 +692,65 @@ impl ConversationService for SqliteConversationService {
             }
         })
     }
+
+    // -----------------------------------------------------------------------
+    // fork_at / list_branches  (see branches.rs)
+    // -----------------------------------------------------------------------
+
+    async fn fork_at(&self, conversation_id: Uuid, seq: usize) -> ServiceResult<Conversation> {
+        let branch_id = branches::fork(&self.db, conversation_id, seq).await?;
+        self.load(branch_id).await
+    }
+
+    async fn list_branches(&self, conversation_id: Uuid) -> ServiceResult<Vec<ConversationBranch>> {
+        branches::list(&self.db, conversation_id).await
+    }
+
+    async fn begin_message_version(&self, conversation_id: Uuid, seq: usize) -> ServiceResult<()> {
+        versions::begin(&self.db, conversation_id, seq).await
+    }
+
+    async fn switch_message_version(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+        version: u32,
+    ) -> ServiceResult<()> {
+        versions::switch(&self.db, conversation_id, seq, version).await
+    }
+
+    async fn list_message_versions(
+        &self,
+        conversation_id: Uuid,
+    ) -> ServiceResult<Vec<MessageVersionInfo>> {
+        versions::list(&self.db, conversation_id).await
+    }
+
+    // -----------------------------------------------------------------------
+    // pinning / archive / folders / tags  (see organization.rs)
+    // -----------------------------------------------------------------------
+
+    async fn set_pinned(&self, id: Uuid, pinned: bool) -> ServiceResult<()> {
+        organization::set_flag(&self.db, id, "pinned", pinned).await
+    }
+
+    async fn set_archived(&self, id: Uuid, archived: bool) -> ServiceResult<()> {
+        organization::set_flag(&self.db, id, "archived", archived).await
+    }
+
+    async fn set_folder(&self, id: Uuid, folder: Option<String>) -> ServiceResult<()> {
+        organization::set_folder(&self.db, id, folder).await
+    }
+
+    async fn set_tags(&self, id: Uuid, tags: Vec<String>) -> ServiceResult<()> {
+        organization::set_tags(&self.db, id, &tags).await
+    }
+
+    async fn list_tags(&self) -> ServiceResult<Vec<String>> {
+        organization::list_tags(&self.db).await
+    }
+
+    async fn list_folders(&self) -> ServiceResult<Vec<String>> {
+        organization::list_folders(&self.db).await
+    }
 }
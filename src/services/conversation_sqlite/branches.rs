@@ -0,0 +1,246 @@
+//! Conversation branching for `SqliteConversationService`.
+//!
+//! A branch is an ordinary conversation row whose `parent_id`/`parent_seq`
+//! columns point back at the conversation and message it was forked from.
+//! Forking copies the parent's messages up to and including `parent_seq`, so
+//! each branch owns its full history and can be loaded, searched, exported,
+//! and deleted independently. Deleting a parent leaves its branches in place
+//! (`ON DELETE SET NULL`), promoting them to roots of their own trees.
+
+use uuid::Uuid;
+
+use super::{now_ts, parse_ts_sql, parse_uuid_sql};
+use crate::db::worker::DbHandle;
+use crate::models::ConversationBranch;
+use crate::services::{ServiceError, ServiceResult};
+
+/// Suffix appended to the parent title when naming a new branch.
+const BRANCH_TITLE_SUFFIX: &str = " (branch)";
+
+/// Build the default title for a branch of a conversation titled `parent`.
+fn branch_title(parent: Option<&str>) -> String {
+    let base = parent
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("Untitled Conversation");
+    format!("{base}{BRANCH_TITLE_SUFFIX}")
+}
+
+/// Create a branch of `conversation_id` containing messages `0..=seq`.
+///
+/// Runs in a single transaction: the branch row is inserted before its
+/// messages so the FTS insert trigger indexes them under the branch title.
+/// Context state is intentionally not copied — its summary ranges describe
+/// the parent's full history, not the truncated branch. Alternate versions
+/// anchored at or before `seq` are copied, along with the nested versions
+/// they own, so the branch keeps the switcher for its prefix. The branch
+/// inherits the parent's folder and tags but starts unpinned and unarchived.
+pub(super) async fn fork(db: &DbHandle, conversation_id: Uuid, seq: usize) -> ServiceResult<Uuid> {
+    let branch_id = Uuid::new_v4();
+    let source_str = conversation_id.to_string();
+    let branch_str = branch_id.to_string();
+    let seq_i64 = i64::try_from(seq)
+        .map_err(|_| ServiceError::Validation(format!("message seq out of range: {seq}")))?;
+    let now = now_ts();
+
+    db.execute(move |conn| {
+        let tx = conn.unchecked_transaction()?;
+
+        let (title, profile_id): (Option<String>, String) = tx.query_row(
+            "SELECT title, profile_id FROM conversations WHERE id = ?1",
+            [&source_str],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let message_exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM messages WHERE conversation_id = ?1 AND seq = ?2)",
+            rusqlite::params![source_str, seq_i64],
+            |row| row.get(0),
+        )?;
+        if !message_exists {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+
+        tx.execute(
+            "INSERT INTO conversations
+                 (id, title, profile_id, created_at, updated_at, parent_id, parent_seq, folder)
+             VALUES (?1, ?2, ?3, ?4, ?4, ?5, ?6,
+                     (SELECT folder FROM conversations WHERE id = ?5))",
+            rusqlite::params![
+                branch_str,
+                branch_title(title.as_deref()),
+                profile_id,
+                now,
+                source_str,
+                seq_i64,
+            ],
+        )?;
+
+        tx.execute(
+            "INSERT INTO messages
+                 (conversation_id, role, content, thinking_content,
+                  model_id, tool_calls, tool_results, created_at, seq)
+             SELECT ?1, role, content, thinking_content,
+                    model_id, tool_calls, tool_results, created_at, seq
+             FROM messages
+             WHERE conversation_id = ?2 AND seq <= ?3
+             ORDER BY seq ASC",
+            rusqlite::params![branch_str, source_str, seq_i64],
+        )?;
+
+        tx.execute(
+            "WITH RECURSIVE kept(anchor_seq, version) AS (
+                 SELECT anchor_seq, version FROM message_versions
+                 WHERE conversation_id = ?2 AND anchor_seq <= ?3
+                 UNION
+                 SELECT v.anchor_seq, v.version
+                 FROM message_versions v
+                 JOIN kept k
+                   ON v.owner_anchor_seq = k.anchor_seq AND v.owner_version = k.version
+                 WHERE v.conversation_id = ?2
+             )
+             INSERT INTO message_versions
+                 (conversation_id, anchor_seq, version, owner_anchor_seq, owner_version,
+                  seq, role, content, thinking_content,
+                  model_id, tool_calls, tool_results, created_at)
+             SELECT ?1, v.anchor_seq, v.version, v.owner_anchor_seq, v.owner_version,
+                    v.seq, v.role, v.content, v.thinking_content,
+                    v.model_id, v.tool_calls, v.tool_results, v.created_at
+             FROM message_versions v
+             JOIN kept k ON v.anchor_seq = k.anchor_seq AND v.version = k.version
+             WHERE v.conversation_id = ?2
+             ORDER BY v.id ASC",
+            rusqlite::params![branch_str, source_str, seq_i64],
+        )?;
+
+        tx.execute(
+            "INSERT INTO conversation_tags (conversation_id, tag)
+             SELECT ?1, tag FROM conversation_tags WHERE conversation_id = ?2",
+            rusqlite::params![branch_str, source_str],
+        )?;
+
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| {
+        if matches!(e, ServiceError::NotFound(_)) {
+            ServiceError::NotFound(format!(
+                "no message with seq {seq} in conversation {conversation_id}"
+            ))
+        } else {
+            e
+        }
+    })?;
+
+    Ok(branch_id)
+}
+
+/// Walks `parent_id` up from `?1`. `path` holds every id visited so far, so
+/// a corrupted link back onto the path ends the walk instead of looping.
+const ANCESTORS_CTE: &str = "ancestors(id, parent_id, path) AS (
+         SELECT id, parent_id, '/' || id || '/' FROM conversations WHERE id = ?1
+         UNION ALL
+         SELECT c.id, c.parent_id, a.path || c.id || '/'
+         FROM conversations c JOIN ancestors a ON c.id = a.parent_id
+         WHERE instr(a.path, '/' || c.id || '/') = 0
+     )";
+
+/// List the branch tree containing `conversation_id`, root first.
+///
+/// Walks `parent_id` up to the root, then collects every descendant of the
+/// root. If the parent links were ever corrupted into a cycle there is no
+/// root to start from; that is reported as a storage error rather than as
+/// a missing conversation.
+pub(super) async fn list(
+    db: &DbHandle,
+    conversation_id: Uuid,
+) -> ServiceResult<Vec<ConversationBranch>> {
+    let id_str = conversation_id.to_string();
+
+    let (branches, in_cycle) = db
+        .execute(move |conn| {
+            let mut stmt = conn.prepare_cached(&format!(
+                "WITH RECURSIVE
+                     {ANCESTORS_CTE},
+                     tree(id) AS (
+                         SELECT id FROM ancestors WHERE parent_id IS NULL
+                         UNION
+                         SELECT c.id FROM conversations c JOIN tree t ON c.parent_id = t.id
+                     )
+                 SELECT c.id, c.title, c.parent_id, c.parent_seq, c.created_at,
+                        (SELECT COUNT(*) FROM messages m WHERE m.conversation_id = c.id)
+                 FROM conversations c JOIN tree t ON t.id = c.id
+                 ORDER BY c.parent_id IS NOT NULL, c.created_at ASC"
+            ))?;
+
+            let rows = stmt.query_map([&id_str], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<i64>>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, i64>(5)?,
+                ))
+            })?;
+
+            let mut branches = Vec::new();
+            for row in rows {
+                let (id, title, parent_id, parent_seq, created_at, message_count) = row?;
+                branches.push(ConversationBranch {
+                    id: parse_uuid_sql(&id, 0)?,
+                    title,
+                    parent_id: parent_id.as_deref().and_then(|s| Uuid::parse_str(s).ok()),
+                    parent_seq: parent_seq.and_then(|s| usize::try_from(s).ok()),
+                    created_at: parse_ts_sql(&created_at, 4)?,
+                    message_count: usize::try_from(message_count).unwrap_or(0),
+                });
+            }
+            if !branches.is_empty() {
+                return Ok((branches, false));
+            }
+
+            let in_cycle: bool = conn.query_row(
+                &format!(
+                    "WITH RECURSIVE {ANCESTORS_CTE}
+                     SELECT EXISTS(
+                         SELECT 1 FROM ancestors
+                         WHERE instr(path, '/' || parent_id || '/') > 0
+                     )"
+                ),
+                [&id_str],
+                |row| row.get(0),
+            )?;
+            Ok((branches, in_cycle))
+        })
+        .await?;
+
+    if in_cycle {
+        return Err(ServiceError::Storage(format!(
+            "branch parent links of conversation {conversation_id} form a cycle"
+        )));
+    }
+    if branches.is_empty() {
+        return Err(ServiceError::NotFound(format!(
+            "conversation not found: {conversation_id}"
+        )));
+    }
+    Ok(branches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::branch_title;
+
+    #[test]
+    fn branch_title_appends_suffix_to_parent_title() {
+        assert_eq!(branch_title(Some("Trip plan")), "Trip plan (branch)");
+    }
+
+    #[test]
+    fn branch_title_falls_back_for_blank_parent_title() {
+        assert_eq!(branch_title(None), "Untitled Conversation (branch)");
+        assert_eq!(branch_title(Some("   ")), "Untitled Conversation (branch)");
+    }
+}
@@ -0,0 +1,316 @@
+//! Pinning, archive, folders, and tags for `SqliteConversationService`.
+//!
+//! Pinned/archived flags and the folder live on `conversations`; tags live in
+//! `conversation_tags`. `filter_clause` turns a `ConversationFilter` into SQL
+//! conditions on the `conversations` alias `c`, shared by `list_metadata` and
+//! `search` so both apply a filter identically.
+
+use std::fmt::Write as _;
+
+use rusqlite::types::Value;
+use uuid::Uuid;
+
+use super::{parse_ts_sql, parse_uuid_sql};
+use crate::db::worker::DbHandle;
+use crate::models::{
+    normalize_folder, normalize_tag, ArchiveFilter, ConversationFilter, ConversationMetadata,
+    ConversationOrganization,
+};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Separator used to pack tags into one column; tags never contain whitespace.
+const TAG_SEPARATOR: char = '\n';
+
+/// SQL conditions (each prefixed with ` AND `) and their positional
+/// parameters, numbered from `first_param`.
+pub(super) fn filter_clause(
+    filter: &ConversationFilter,
+    first_param: usize,
+) -> (String, Vec<Value>) {
+    let mut sql = String::new();
+    let mut params = Vec::new();
+
+    match filter.archived {
+        ArchiveFilter::Exclude => sql.push_str(" AND c.archived = 0"),
+        ArchiveFilter::Include => {}
+        ArchiveFilter::Only => sql.push_str(" AND c.archived = 1"),
+    }
+    if filter.pinned_only {
+        sql.push_str(" AND c.pinned = 1");
+    }
+    if let Some(folder) = filter.folder.as_deref().and_then(normalize_folder) {
+        params.push(Value::Text(folder));
+        let _ = write!(sql, " AND c.folder = ?{}", first_param + params.len() - 1);
+    }
+    for tag in filter.tags.iter().filter_map(|tag| normalize_tag(tag)) {
+        params.push(Value::Text(tag));
+        let _ = write!(
+            sql,
+            " AND EXISTS (SELECT 1 FROM conversation_tags t \
+             WHERE t.conversation_id = c.id AND t.tag = ?{})",
+            first_param + params.len() - 1
+        );
+    }
+
+    (sql, params)
+}
+
+/// Normalize, dedupe, and sort user-entered tags.
+fn normalized_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags.iter().filter_map(|tag| normalize_tag(tag)).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn unpack_tags(packed: Option<&str>) -> Vec<String> {
+    let mut tags: Vec<String> = packed
+        .unwrap_or_default()
+        .split(TAG_SEPARATOR)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect();
+    tags.sort();
+    tags
+}
+
+fn not_found(id: Uuid) -> impl FnOnce(ServiceError) -> ServiceError {
+    move |e| match e {
+        ServiceError::NotFound(_) => {
+            ServiceError::NotFound(format!("conversation not found: {id}"))
+        }
+        other => other,
+    }
+}
+
+/// List conversation metadata matching `filter`, newest first.
+pub(super) async fn list_metadata(
+    db: &DbHandle,
+    filter: &ConversationFilter,
+    limit: i64,
+    offset: i64,
+) -> ServiceResult<Vec<ConversationMetadata>> {
+    let (conditions, mut params) = filter_clause(filter, 3);
+    params.splice(0..0, [Value::Integer(limit), Value::Integer(offset)]);
+
+    db.execute(move |conn| {
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT
+                 c.id,
+                 c.title,
+                 c.profile_id,
+                 c.created_at,
+                 c.updated_at,
+                 (SELECT COUNT(*) FROM messages m WHERE m.conversation_id = c.id)
+                     AS message_count,
+                 (SELECT SUBSTR(m2.content, 1, 100)
+                  FROM messages m2
+                  WHERE m2.conversation_id = c.id
+                  ORDER BY m2.seq DESC
+                  LIMIT 1) AS last_message_preview,
+                 c.parent_id,
+                 c.pinned,
+                 c.archived,
+                 c.folder,
+                 (SELECT GROUP_CONCAT(t.tag, char(10))
+                  FROM conversation_tags t
+                  WHERE t.conversation_id = c.id) AS tags
+             FROM conversations c
+             WHERE 1 = 1{conditions}
+             ORDER BY c.updated_at DESC
+             LIMIT ?1 OFFSET ?2"
+        ))?;
+
+        let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            Ok((
+                (
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                ),
+                ConversationOrganization {
+                    pinned: row.get(8)?,
+                    archived: row.get(9)?,
+                    folder: row.get(10)?,
+                    tags: unpack_tags(row.get::<_, Option<String>>(11)?.as_deref()),
+                },
+            ))
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let (
+                (id_str, title, profile_id_str, ca_str, ua_str, mc, preview, parent),
+                organization,
+            ) = row?;
+            results.push(ConversationMetadata {
+                id: parse_uuid_sql(&id_str, 0)?,
+                title,
+                profile_id: profile_id_str
+                    .as_deref()
+                    .and_then(|s| Uuid::parse_str(s).ok()),
+                created_at: parse_ts_sql(&ca_str, 3)?,
+                updated_at: parse_ts_sql(&ua_str, 4)?,
+                message_count: usize::try_from(mc).unwrap_or(0),
+                last_message_preview: preview,
+                parent_id: parent.as_deref().and_then(|s| Uuid::parse_str(s).ok()),
+                organization,
+            });
+        }
+
+        Ok(results)
+    })
+    .await
+}
+
+/// Set the `pinned` or `archived` flag.
+pub(super) async fn set_flag(
+    db: &DbHandle,
+    id: Uuid,
+    column: &'static str,
+    value: bool,
+) -> ServiceResult<()> {
+    let id_str = id.to_string();
+
+    db.execute(move |conn| {
+        let changed = conn.execute(
+            &format!("UPDATE conversations SET {column} = ?1 WHERE id = ?2"),
+            rusqlite::params![value, id_str],
+        )?;
+        if changed == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+        Ok(())
+    })
+    .await
+    .map_err(not_found(id))
+}
+
+pub(super) async fn set_folder(
+    db: &DbHandle,
+    id: Uuid,
+    folder: Option<String>,
+) -> ServiceResult<()> {
+    let id_str = id.to_string();
+    let folder = folder.as_deref().and_then(normalize_folder);
+
+    db.execute(move |conn| {
+        let changed = conn.execute(
+            "UPDATE conversations SET folder = ?1 WHERE id = ?2",
+            rusqlite::params![folder, id_str],
+        )?;
+        if changed == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+        Ok(())
+    })
+    .await
+    .map_err(not_found(id))
+}
+
+pub(super) async fn set_tags(db: &DbHandle, id: Uuid, tags: &[String]) -> ServiceResult<()> {
+    let id_str = id.to_string();
+    let tags = normalized_tags(tags);
+
+    db.execute(move |conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.query_row(
+            "SELECT 1 FROM conversations WHERE id = ?1",
+            [&id_str],
+            |_| Ok(()),
+        )?;
+        tx.execute(
+            "DELETE FROM conversation_tags WHERE conversation_id = ?1",
+            [&id_str],
+        )?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO conversation_tags (conversation_id, tag) VALUES (?1, ?2)",
+            )?;
+            for tag in &tags {
+                insert.execute(rusqlite::params![id_str, tag])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+    .map_err(not_found(id))
+}
+
+pub(super) async fn list_tags(db: &DbHandle) -> ServiceResult<Vec<String>> {
+    list_distinct(
+        db,
+        "SELECT DISTINCT tag FROM conversation_tags ORDER BY tag",
+    )
+    .await
+}
+
+pub(super) async fn list_folders(db: &DbHandle) -> ServiceResult<Vec<String>> {
+    list_distinct(
+        db,
+        "SELECT DISTINCT folder FROM conversations WHERE folder IS NOT NULL ORDER BY folder",
+    )
+    .await
+}
+
+async fn list_distinct(db: &DbHandle, sql: &'static str) -> ServiceResult<Vec<String>> {
+    db.execute(move |conn| {
+        let mut stmt = conn.prepare_cached(sql)?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_filter_only_hides_archived() {
+        let (sql, params) = filter_clause(&ConversationFilter::default(), 1);
+        assert_eq!(sql, " AND c.archived = 0");
+        assert!(params.is_empty());
+
+        let (sql, _) = filter_clause(&ConversationFilter::all(), 1);
+        assert!(sql.is_empty());
+    }
+
+    #[test]
+    fn folder_and_tag_parameters_are_numbered_from_first_param() {
+        let filter = ConversationFilter {
+            folder: Some(" Work ".to_string()),
+            tags: vec!["#Rust".to_string(), "  ".to_string(), "q3".to_string()],
+            ..ConversationFilter::default()
+        };
+        let (sql, params) = filter_clause(&filter, 3);
+        assert!(sql.contains("c.folder = ?3"), "{sql}");
+        assert!(sql.contains("t.tag = ?4"), "{sql}");
+        assert!(sql.contains("t.tag = ?5"), "{sql}");
+        assert_eq!(
+            params,
+            [
+                Value::Text("Work".to_string()),
+                Value::Text("rust".to_string()),
+                Value::Text("q3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tags_are_normalized_deduped_and_sorted() {
+        let tags = normalized_tags(&[
+            "Work".to_string(),
+            "#work".to_string(),
+            "side project".to_string(),
+            String::new(),
+        ]);
+        assert_eq!(tags, ["side-project", "work"]);
+    }
+}
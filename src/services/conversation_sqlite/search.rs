@@ -0,0 +1,327 @@
+//! Structured full-text search for `SqliteConversationService`.
+//!
+//! The raw query is parsed into a `SearchQuery`; free text becomes an FTS5
+//! `MATCH` expression and operators become SQL conditions on the matching
+//! message (role, model, date) or its conversation (profile, tags). Hits are
+//! read from `search_index`, whose `message_rowid` is a `messages.id` when
+//! positive and a negated `message_versions.id` when negative, so inactive
+//! versions are searchable too. Operator-only queries scan `messages`
+//! directly and score every hit equally. Each conversation's best hit is
+//! picked, ordered and paged in SQL, so only one page of rows is read.
+//!
+//! Excerpts come from FTS5 `snippet()`/`highlight()` with control-character
+//! markers, which are stripped into byte ranges for the view to style.
+
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use rusqlite::types::Value;
+use uuid::Uuid;
+
+use super::{organization, parse_ts, role_to_str};
+use crate::db::worker::DbHandle;
+use crate::models::{ConversationFilter, SearchMatchType, SearchQuery, SearchResult};
+use crate::services::{ServiceError, ServiceResult};
+
+const MARK_START: char = '\u{2}';
+const MARK_END: char = '\u{3}';
+
+/// Context length, in characters, for operator-only hits (no FTS snippet).
+const PLAIN_CONTEXT_CHARS: usize = 120;
+
+/// Most results returned for one page, whatever limit is requested.
+const MAX_RESULTS: usize = 1000;
+
+/// Visible index of hit message `m`: non-system messages before it.
+const MESSAGE_INDEX_SQL: &str = "CASE WHEN m.id IS NULL OR m.role = 'system' THEN NULL ELSE
+         (SELECT COUNT(*) FROM messages p
+          WHERE p.conversation_id = m.conversation_id
+            AND p.seq < m.seq AND p.role != 'system') END";
+
+const MESSAGE_COUNT_SQL: &str =
+    "(SELECT COUNT(*) FROM messages mc WHERE mc.conversation_id = c.id)";
+
+/// The best-matching message of one conversation.
+struct Hit {
+    conversation_id: String,
+    title: Option<String>,
+    marked_title: Option<String>,
+    updated_at: String,
+    message_count: i64,
+    score: f64,
+    context: String,
+    message_index: Option<i64>,
+}
+
+impl Hit {
+    fn from_row(row: &rusqlite::Row<'_>) -> Result<Self, rusqlite::Error> {
+        Ok(Self {
+            conversation_id: row.get(0)?,
+            title: row.get(1)?,
+            marked_title: row.get(2)?,
+            updated_at: row.get(3)?,
+            message_count: row.get(4)?,
+            score: row.get(5)?,
+            context: row.get(6)?,
+            message_index: row.get(7)?,
+        })
+    }
+}
+
+/// Strip highlight markers, returning the plain text and the byte ranges
+/// that were marked.
+fn split_highlights(marked: &str) -> (String, Vec<Range<usize>>) {
+    let mut text = String::with_capacity(marked.len());
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for c in marked.chars() {
+        match c {
+            MARK_START => start = Some(text.len()),
+            MARK_END => {
+                if let Some(start) = start.take().filter(|&s| s < text.len()) {
+                    ranges.push(start..text.len());
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+    (text, ranges)
+}
+
+/// Shorten plain message content to a one-line excerpt.
+fn plain_context(content: &str) -> String {
+    let line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= PLAIN_CONTEXT_CHARS {
+        return line;
+    }
+    let mut excerpt: String = line.chars().take(PLAIN_CONTEXT_CHARS).collect();
+    excerpt.push_str("...");
+    excerpt
+}
+
+/// Escape `LIKE` wildcards so `model:` matches the value literally.
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// SQL conditions for the query's operators, numbering parameters from
+/// `first_param`. `column` maps a message column name to the expression that
+/// reads it for the current hit.
+fn operator_clause(
+    query: &SearchQuery,
+    column: impl Fn(&str) -> String,
+    first_param: usize,
+) -> (String, Vec<Value>) {
+    let mut conditions: Vec<(String, Value)> = Vec::new();
+    if let Some(profile) = &query.profile {
+        conditions.push(("c.profile_id = ?".to_string(), Value::Text(profile.clone())));
+    }
+    if let Some(model) = &query.model {
+        conditions.push((
+            format!("{} LIKE ? ESCAPE '\\'", column("model_id")),
+            Value::Text(like_pattern(model)),
+        ));
+    }
+    if let Some(role) = &query.role {
+        conditions.push((
+            format!("{} = ?", column("role")),
+            Value::Text(role_to_str(role).to_string()),
+        ));
+    }
+    if let Some(before) = query.before {
+        conditions.push((
+            format!("{} < ?", column("created_at")),
+            Value::Text(before.to_string()),
+        ));
+    }
+    if let Some(after) = query.after {
+        conditions.push((
+            format!("{} >= ?", column("created_at")),
+            Value::Text(after.to_string()),
+        ));
+    }
+
+    let mut sql = String::new();
+    let mut params = Vec::with_capacity(conditions.len());
+    for (i, (condition, value)) in conditions.into_iter().enumerate() {
+        let placeholder = format!("?{}", first_param + i);
+        let _ = write!(sql, " AND {}", condition.replacen('?', &placeholder, 1));
+        params.push(value);
+    }
+    (sql, params)
+}
+
+/// Run `query` against the index, returning the best-scoring hit of each
+/// matching conversation (BM25: lower is better), ordered by score and
+/// recency (then id, so pages are stable), for the requested page.
+async fn fetch_hits(
+    db: &DbHandle,
+    query: &SearchQuery,
+    filter: &ConversationFilter,
+    limit: usize,
+    offset: usize,
+) -> ServiceResult<Vec<Hit>> {
+    let fts = query.fts_expression();
+    let plain = fts.is_none();
+    let first_param = usize::from(fts.is_some()) + 1;
+    let (filter_sql, mut params) = organization::filter_clause(filter, first_param);
+
+    let hits_sql = if let Some(fts) = fts {
+        let (operator_sql, operator_params) = operator_clause(
+            query,
+            |col| format!("COALESCE(m.{col}, v.{col})"),
+            first_param + params.len(),
+        );
+        params.insert(0, Value::Text(fts));
+        params.extend(operator_params);
+        // FTS5 auxiliary functions cannot run under GROUP BY, so hits are
+        // scored here and narrowed to one per conversation by a window below.
+        format!(
+            "SELECT c.id AS conversation_id, c.title,
+                 highlight(search_index, 0, char(2), char(3)) AS marked_title,
+                 c.updated_at, {MESSAGE_COUNT_SQL} AS message_count,
+                 bm25(search_index, 10.0, 1.0) AS score,
+                 snippet(search_index, 1, char(2), char(3), '...', 24) AS context,
+                 {MESSAGE_INDEX_SQL} AS message_index,
+                 COALESCE(m.seq, v.seq) AS seq
+             FROM search_index
+             JOIN conversations c ON c.id = search_index.conversation_id
+             LEFT JOIN messages m ON m.id = search_index.message_rowid
+             LEFT JOIN message_versions v ON v.id = -search_index.message_rowid
+             WHERE search_index MATCH ?1{filter_sql}{operator_sql}"
+        )
+    } else {
+        let (operator_sql, operator_params) =
+            operator_clause(query, |col| format!("m.{col}"), first_param + params.len());
+        params.extend(operator_params);
+        format!(
+            "SELECT c.id AS conversation_id, c.title, NULL AS marked_title,
+                 c.updated_at, {MESSAGE_COUNT_SQL} AS message_count,
+                 0.0 AS score, m.content AS context,
+                 {MESSAGE_INDEX_SQL} AS message_index, m.seq
+             FROM messages m
+             JOIN conversations c ON c.id = m.conversation_id
+             WHERE 1 = 1{filter_sql}{operator_sql}"
+        )
+    };
+
+    // Ties within a conversation go to the newest message, so operator-only
+    // queries (which score every hit equally) excerpt the latest match.
+    let page_param = params.len() + 1;
+    let sql = format!(
+        "SELECT conversation_id, title, marked_title, updated_at, message_count,
+             score, context, message_index
+         FROM (
+             SELECT *, ROW_NUMBER() OVER (
+                 PARTITION BY conversation_id ORDER BY score, seq DESC
+             ) AS position
+             FROM ({hits_sql})
+         )
+         WHERE position = 1
+         ORDER BY score, updated_at DESC, conversation_id
+         LIMIT ?{page_param} OFFSET ?{}",
+        page_param + 1
+    );
+    params.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+    params.push(Value::Integer(i64::try_from(offset).unwrap_or(i64::MAX)));
+
+    let mut hits = db
+        .execute(move |conn| {
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(params), Hit::from_row)?;
+            rows.collect::<Result<Vec<_>, _>>()
+        })
+        .await?;
+    if plain {
+        for hit in &mut hits {
+            hit.context = plain_context(&hit.context);
+        }
+    }
+    Ok(hits)
+}
+
+fn hit_to_search_result(hit: Hit) -> ServiceResult<SearchResult> {
+    let conversation_id = Uuid::parse_str(&hit.conversation_id)
+        .map_err(|e| ServiceError::Storage(format!("invalid uuid: {e}")))?;
+    let title = hit.title.unwrap_or_else(|| "Untitled".to_string());
+    // The index copy of the title is kept in sync by triggers; fall back to no
+    // highlights rather than misplacing them if it ever differs.
+    let title_highlights = hit
+        .marked_title
+        .map(|marked| split_highlights(&marked))
+        .filter(|(plain, _)| *plain == title)
+        .map(|(_, ranges)| ranges)
+        .unwrap_or_default();
+    let (match_context, context_highlights) = split_highlights(&hit.context);
+
+    Ok(SearchResult {
+        conversation_id,
+        match_type: if title_highlights.is_empty() {
+            SearchMatchType::Content
+        } else {
+            SearchMatchType::Title
+        },
+        title,
+        match_context,
+        score: hit.score,
+        updated_at: parse_ts(&hit.updated_at)?,
+        message_count: usize::try_from(hit.message_count).unwrap_or(0),
+        title_highlights,
+        context_highlights,
+        message_index: hit.message_index.and_then(|i| usize::try_from(i).ok()),
+    })
+}
+
+/// Search conversations with a structured query (see `SearchQuery`).
+///
+/// `tag:` operators narrow `filter`; an empty query returns no results.
+pub(super) async fn search(
+    db: &DbHandle,
+    raw_query: &str,
+    filter: &ConversationFilter,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> ServiceResult<Vec<SearchResult>> {
+    let query = SearchQuery::parse(raw_query);
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut filter = filter.clone();
+    filter.tags.extend(query.tags.iter().cloned());
+
+    let limit = limit.unwrap_or(100).min(MAX_RESULTS);
+    fetch_hits(db, &query, &filter, limit, offset.unwrap_or(0))
+        .await?
+        .into_iter()
+        .map(hit_to_search_result)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markers_become_byte_ranges() {
+        let (text, ranges) = split_highlights("...the \u{2}borrow\u{3} checker \u{2}é\u{3}");
+        assert_eq!(text, "...the borrow checker é");
+        assert_eq!(ranges, [7..13, 22..24]);
+        assert_eq!(&text[ranges[1].clone()], "é");
+    }
+
+    #[test]
+    fn unbalanced_markers_are_dropped() {
+        let (text, ranges) = split_highlights("a\u{3}b\u{2}\u{3}c\u{2}d");
+        assert_eq!(text, "abcd");
+        assert!(ranges.is_empty());
+    }
+
+    #[test]
+    fn model_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("gpt_4%"), "%gpt\\_4\\%%");
+    }
+}
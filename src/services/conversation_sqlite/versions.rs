@@ -0,0 +1,258 @@
+//! Alternate message versions for `SqliteConversationService`.
+//!
+//! Regenerating a response or editing a prompt replaces the conversation
+//! from some `seq` onward. The replaced messages are moved into
+//! `message_versions` as version *n* of the tail anchored at that `seq`, and
+//! the next message written at `seq` becomes the new live version.
+//!
+//! Only the live version of an anchor lives in `messages`; every other
+//! version sits in `message_versions`. The live version number is therefore
+//! the smallest number not used by an archived version at that anchor.
+//! Archiving a tail also archives the alternates of any anchors inside it:
+//! those rows are tagged with the owning `(anchor_seq, version)` and are
+//! untagged again when that version is restored.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rusqlite::Transaction;
+use uuid::Uuid;
+
+use super::now_ts;
+use crate::db::worker::DbHandle;
+use crate::models::MessageVersionInfo;
+use crate::services::{ServiceError, ServiceResult};
+
+const MESSAGE_COLUMNS: &str =
+    "role, content, thinking_content, model_id, tool_calls, tool_results, created_at";
+
+/// Smallest version number not taken by a free archived version at `anchor`.
+fn live_version(tx: &Transaction<'_>, conversation_id: &str, anchor: i64) -> rusqlite::Result<u32> {
+    let mut stmt = tx.prepare_cached(
+        "SELECT DISTINCT version FROM message_versions
+         WHERE conversation_id = ?1 AND anchor_seq = ?2 AND owner_anchor_seq IS NULL",
+    )?;
+    let taken = stmt
+        .query_map(rusqlite::params![conversation_id, anchor], |row| {
+            row.get::<_, u32>(0)
+        })?
+        .collect::<rusqlite::Result<BTreeSet<u32>>>()?;
+    Ok(first_free(&taken))
+}
+
+fn first_free(taken: &BTreeSet<u32>) -> u32 {
+    (0..).find(|n| !taken.contains(n)).unwrap_or(0)
+}
+
+/// Move live messages `anchor..` into `message_versions` as `version`.
+///
+/// Returns `false` (and changes nothing) when there are no live messages at
+/// or after `anchor`, so an empty tail never claims a version number.
+fn archive_tail(
+    tx: &Transaction<'_>,
+    conversation_id: &str,
+    anchor: i64,
+    version: u32,
+) -> rusqlite::Result<bool> {
+    let live: i64 = tx.query_row(
+        "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1 AND seq >= ?2",
+        rusqlite::params![conversation_id, anchor],
+        |row| row.get(0),
+    )?;
+    if live == 0 {
+        return Ok(false);
+    }
+
+    tx.execute(
+        "UPDATE message_versions SET owner_anchor_seq = ?2, owner_version = ?3
+         WHERE conversation_id = ?1 AND anchor_seq > ?2 AND owner_anchor_seq IS NULL",
+        rusqlite::params![conversation_id, anchor, version],
+    )?;
+    tx.execute(
+        &format!(
+            "INSERT INTO message_versions
+                 (conversation_id, anchor_seq, version, seq, {MESSAGE_COLUMNS})
+             SELECT conversation_id, ?2, ?3, seq, {MESSAGE_COLUMNS}
+             FROM messages
+             WHERE conversation_id = ?1 AND seq >= ?2
+             ORDER BY seq ASC"
+        ),
+        rusqlite::params![conversation_id, anchor, version],
+    )?;
+    tx.execute(
+        "DELETE FROM messages WHERE conversation_id = ?1 AND seq >= ?2",
+        rusqlite::params![conversation_id, anchor],
+    )?;
+    Ok(true)
+}
+
+/// Move archived `version` at `anchor` back into `messages`.
+fn restore_tail(
+    tx: &Transaction<'_>,
+    conversation_id: &str,
+    anchor: i64,
+    version: u32,
+) -> rusqlite::Result<()> {
+    tx.execute(
+        &format!(
+            "INSERT INTO messages (conversation_id, seq, {MESSAGE_COLUMNS})
+             SELECT conversation_id, seq, {MESSAGE_COLUMNS}
+             FROM message_versions
+             WHERE conversation_id = ?1 AND anchor_seq = ?2 AND version = ?3
+               AND owner_anchor_seq IS NULL
+             ORDER BY seq ASC"
+        ),
+        rusqlite::params![conversation_id, anchor, version],
+    )?;
+    tx.execute(
+        "DELETE FROM message_versions
+         WHERE conversation_id = ?1 AND anchor_seq = ?2 AND version = ?3
+           AND owner_anchor_seq IS NULL",
+        rusqlite::params![conversation_id, anchor, version],
+    )?;
+    tx.execute(
+        "UPDATE message_versions SET owner_anchor_seq = NULL, owner_version = NULL
+         WHERE conversation_id = ?1 AND owner_anchor_seq = ?2 AND owner_version = ?3",
+        rusqlite::params![conversation_id, anchor, version],
+    )?;
+    Ok(())
+}
+
+fn touch(tx: &Transaction<'_>, conversation_id: &str) -> rusqlite::Result<()> {
+    tx.execute(
+        "UPDATE conversations SET updated_at = ?2 WHERE id = ?1",
+        rusqlite::params![conversation_id, now_ts()],
+    )?;
+    Ok(())
+}
+
+fn seq_param(seq: usize) -> ServiceResult<i64> {
+    i64::try_from(seq)
+        .map_err(|_| ServiceError::Validation(format!("message seq out of range: {seq}")))
+}
+
+/// Archive messages `seq..` so a new version can be written at `seq`.
+pub(super) async fn begin(db: &DbHandle, conversation_id: Uuid, seq: usize) -> ServiceResult<()> {
+    let id_str = conversation_id.to_string();
+    let anchor = seq_param(seq)?;
+
+    db.execute(move |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let version = live_version(&tx, &id_str, anchor)?;
+        if !archive_tail(&tx, &id_str, anchor, version)? {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+        touch(&tx, &id_str)?;
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| match e {
+        ServiceError::NotFound(_) => ServiceError::NotFound(format!(
+            "no message with seq {seq} in conversation {conversation_id}"
+        )),
+        other => other,
+    })
+}
+
+/// Make archived `version` at `seq` the live one.
+pub(super) async fn switch(
+    db: &DbHandle,
+    conversation_id: Uuid,
+    seq: usize,
+    version: u32,
+) -> ServiceResult<()> {
+    let id_str = conversation_id.to_string();
+    let anchor = seq_param(seq)?;
+
+    db.execute(move |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let current = live_version(&tx, &id_str, anchor)?;
+        if current == version {
+            return Ok(());
+        }
+
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM message_versions
+             WHERE conversation_id = ?1 AND anchor_seq = ?2 AND version = ?3
+               AND owner_anchor_seq IS NULL)",
+            rusqlite::params![id_str, anchor, version],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+
+        archive_tail(&tx, &id_str, anchor, current)?;
+        restore_tail(&tx, &id_str, anchor, version)?;
+        touch(&tx, &id_str)?;
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| match e {
+        ServiceError::NotFound(_) => ServiceError::NotFound(format!(
+            "no version {version} at seq {seq} in conversation {conversation_id}"
+        )),
+        other => other,
+    })
+}
+
+/// Report every anchor in the live history that has archived versions.
+pub(super) async fn list(
+    db: &DbHandle,
+    conversation_id: Uuid,
+) -> ServiceResult<Vec<MessageVersionInfo>> {
+    let id_str = conversation_id.to_string();
+
+    let (archived, live_len) = db
+        .execute(move |conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT DISTINCT anchor_seq, version FROM message_versions
+                 WHERE conversation_id = ?1 AND owner_anchor_seq IS NULL",
+            )?;
+            let rows = stmt
+                .query_map([&id_str], |row| {
+                    Ok((row.get::<_, i64>(0)?, row.get::<_, u32>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let live_len: i64 = conn.query_row(
+                "SELECT COALESCE(MAX(seq) + 1, 0) FROM messages WHERE conversation_id = ?1",
+                [&id_str],
+                |row| row.get(0),
+            )?;
+            Ok((rows, live_len))
+        })
+        .await?;
+
+    let mut by_anchor: BTreeMap<i64, BTreeSet<u32>> = BTreeMap::new();
+    for (anchor, version) in archived {
+        by_anchor.entry(anchor).or_default().insert(version);
+    }
+
+    Ok(by_anchor
+        .into_iter()
+        .filter_map(|(anchor, taken)| {
+            let seq = usize::try_from(anchor).ok()?;
+            let has_live = anchor < live_len;
+            let archived = u32::try_from(taken.len()).unwrap_or(u32::MAX);
+            Some(MessageVersionInfo {
+                seq,
+                selected: has_live.then(|| first_free(&taken)),
+                count: archived + u32::from(has_live),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::first_free;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn first_free_fills_gaps_before_extending() {
+        assert_eq!(first_free(&BTreeSet::new()), 0);
+        assert_eq!(first_free(&BTreeSet::from([0, 1])), 2);
+        assert_eq!(first_free(&BTreeSet::from([0, 2])), 1);
+    }
+}
@@ -24,6 +24,8 @@ pub mod skills;
 pub mod skills_impl;
 
 pub mod conversation;
+pub mod conversation_import;
+pub mod conversation_import_impl;
 pub mod conversation_sqlite;
 pub mod conversation_title;
 pub mod login_item;
@@ -40,6 +42,12 @@ pub mod secrets;
 pub mod secrets_impl;
 pub mod secure_store;
 pub mod template;
+pub mod todo;
+pub mod todo_sqlite;
+pub mod tool_audit;
+pub mod tool_audit_sqlite;
+pub mod usage;
+pub mod usage_sqlite;
 
 use thiserror::Error;
 
@@ -85,6 +93,7 @@ pub use app_settings::AppSettingsService;
 pub use backup::BackupService;
 pub use chat::ChatService;
 pub use conversation::ConversationService;
+pub use conversation_import::{ConversationImportService, ImportSource, ImportSummary};
 pub use conversation_title::{ConversationTitleGenerator, LlmConversationTitleGenerator};
 pub use mcp::McpService;
 pub use mcp_registry::McpRegistryService;
@@ -92,13 +101,20 @@ pub use models_registry::ModelsRegistryService;
 pub use profile::ProfileService;
 pub use secrets::SecretsService;
 pub use template::{expand_system_prompt, TemplateContext};
+pub use todo::TodoService;
+pub use tool_audit::ToolAuditService;
+pub use usage::UsageService;
 
 // Re-export service implementations
 pub use app_settings_impl::AppSettingsServiceImpl;
 pub use backup_impl::BackupServiceImpl;
 pub use chat_impl::ChatServiceImpl;
+pub use conversation_import_impl::ConversationImportServiceImpl;
 pub use conversation_sqlite::SqliteConversationService;
 pub use skills::SkillsService;
+pub use todo_sqlite::SqliteTodoService;
+pub use tool_audit_sqlite::SqliteToolAuditService;
+pub use usage_sqlite::SqliteUsageService;
 
 pub use mcp_impl::McpServiceImpl;
 pub use mcp_registry_impl::McpRegistryServiceImpl;
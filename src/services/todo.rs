@@ -0,0 +1,21 @@
+//! Todo service trait
+//!
+//! Stores the task list an agent keeps for each conversation with the
+//! `TodoWrite` tool.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::models::TodoItem;
+use crate::services::ServiceResult;
+
+/// Per-conversation task list storage
+#[async_trait]
+pub trait TodoService: Send + Sync {
+    /// The conversation's task list, in order. A conversation without a
+    /// list returns an empty one rather than `NotFound`.
+    async fn list(&self, conversation_id: Uuid) -> ServiceResult<Vec<TodoItem>>;
+
+    /// Replace the conversation's task list; an empty list clears it.
+    async fn replace(&self, conversation_id: Uuid, items: Vec<TodoItem>) -> ServiceResult<()>;
+}
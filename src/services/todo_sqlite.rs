@@ -0,0 +1,81 @@
+//! SQLite-backed `TodoService` implementation.
+//!
+//! Items live in the `conversation_todos` table, ordered by `position`.
+//! Updates rewrite the whole list in one transaction.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::worker::DbHandle;
+use crate::models::{TodoItem, TodoStatus};
+use crate::services::todo::TodoService;
+use crate::services::{ServiceError, ServiceResult};
+
+pub struct SqliteTodoService {
+    db: DbHandle,
+}
+
+impl SqliteTodoService {
+    #[must_use]
+    pub const fn new(db: DbHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TodoService for SqliteTodoService {
+    async fn list(&self, conversation_id: Uuid) -> ServiceResult<Vec<TodoItem>> {
+        let id = conversation_id.to_string();
+        let rows = self
+            .db
+            .execute(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT content, status FROM conversation_todos
+                     WHERE conversation_id = ?1
+                     ORDER BY position",
+                )?;
+                let rows = stmt.query_map([id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?;
+                rows.collect::<Result<Vec<_>, _>>()
+            })
+            .await?;
+
+        rows.into_iter()
+            .map(|(content, status)| {
+                let status = TodoStatus::parse(&status).ok_or_else(|| {
+                    ServiceError::Storage(format!("unknown todo status: {status}"))
+                })?;
+                Ok(TodoItem { content, status })
+            })
+            .collect()
+    }
+
+    async fn replace(&self, conversation_id: Uuid, items: Vec<TodoItem>) -> ServiceResult<()> {
+        let id = conversation_id.to_string();
+        self.db
+            .execute(move |conn| {
+                let tx = conn.unchecked_transaction()?;
+                tx.execute(
+                    "DELETE FROM conversation_todos WHERE conversation_id = ?1",
+                    [&id],
+                )?;
+                {
+                    let mut insert = tx.prepare(
+                        "INSERT INTO conversation_todos (conversation_id, position, content, status)
+                         VALUES (?1, ?2, ?3, ?4)",
+                    )?;
+                    for (position, item) in items.iter().enumerate() {
+                        insert.execute(rusqlite::params![
+                            id,
+                            i64::try_from(position).unwrap_or(i64::MAX),
+                            item.content,
+                            item.status.as_str(),
+                        ])?;
+                    }
+                }
+                tx.commit()
+            })
+            .await
+    }
+}
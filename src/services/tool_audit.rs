@@ -0,0 +1,19 @@
+//! Tool audit service trait
+//!
+//! Keeps a durable record of every tool call agents make, for the settings
+//! audit log and its exports.
+
+use async_trait::async_trait;
+
+use crate::models::{ToolAuditEntry, ToolAuditFilter};
+use crate::services::ServiceResult;
+
+/// Tool call audit log
+#[async_trait]
+pub trait ToolAuditService: Send + Sync {
+    /// Append one finished tool call to the log.
+    async fn record(&self, entry: ToolAuditEntry) -> ServiceResult<()>;
+
+    /// Records matching `filter`, newest first.
+    async fn list(&self, filter: ToolAuditFilter) -> ServiceResult<Vec<ToolAuditEntry>>;
+}
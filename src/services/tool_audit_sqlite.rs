@@ -0,0 +1,156 @@
+//! SQLite-backed `ToolAuditService` implementation.
+//!
+//! Records live in the `tool_audit` table. A record naming a conversation
+//! that no longer exists is kept without its conversation id.
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::db::worker::DbHandle;
+use crate::models::{ApprovalSource, AuditOutcome, ToolAuditEntry, ToolAuditFilter};
+use crate::services::tool_audit::ToolAuditService;
+use crate::services::{ServiceError, ServiceResult};
+
+/// Columns read by `AuditRow::from_row`, in order.
+const AUDIT_COLUMNS: &str = "conversation_id, tool_name, arguments, approval_source,
+     approval_detail, outcome, exit_code, output, started_at, duration_ms";
+
+pub struct SqliteToolAuditService {
+    db: DbHandle,
+}
+
+impl SqliteToolAuditService {
+    #[must_use]
+    pub const fn new(db: DbHandle) -> Self {
+        Self { db }
+    }
+}
+
+/// A `tool_audit` row before its text columns are parsed.
+struct AuditRow {
+    conversation_id: Option<String>,
+    tool_name: String,
+    arguments: String,
+    approval_source: String,
+    approval_detail: Option<String>,
+    outcome: String,
+    exit_code: Option<i32>,
+    output: String,
+    started_at: String,
+    duration_ms: i64,
+}
+
+impl AuditRow {
+    fn from_row(row: &rusqlite::Row<'_>) -> Result<Self, rusqlite::Error> {
+        Ok(Self {
+            conversation_id: row.get(0)?,
+            tool_name: row.get(1)?,
+            arguments: row.get(2)?,
+            approval_source: row.get(3)?,
+            approval_detail: row.get(4)?,
+            outcome: row.get(5)?,
+            exit_code: row.get(6)?,
+            output: row.get(7)?,
+            started_at: row.get(8)?,
+            duration_ms: row.get(9)?,
+        })
+    }
+
+    fn into_entry(self) -> ServiceResult<ToolAuditEntry> {
+        let conversation_id = self
+            .conversation_id
+            .map(|id| {
+                Uuid::parse_str(&id)
+                    .map_err(|e| ServiceError::Storage(format!("invalid uuid: {e}")))
+            })
+            .transpose()?;
+        let approval_source = ApprovalSource::parse(&self.approval_source).ok_or_else(|| {
+            ServiceError::Storage(format!("unknown approval source: {}", self.approval_source))
+        })?;
+        let outcome = AuditOutcome::parse(&self.outcome).ok_or_else(|| {
+            ServiceError::Storage(format!("unknown audit outcome: {}", self.outcome))
+        })?;
+        let started_at = DateTime::parse_from_rfc3339(&self.started_at)
+            .map_err(|e| {
+                ServiceError::Storage(format!("invalid audit time '{}': {e}", self.started_at))
+            })?
+            .with_timezone(&Utc);
+
+        Ok(ToolAuditEntry {
+            conversation_id,
+            tool_name: self.tool_name,
+            arguments: self.arguments,
+            approval_source,
+            approval_detail: self.approval_detail,
+            outcome,
+            exit_code: self.exit_code,
+            output: self.output,
+            started_at,
+            duration_ms: u64::try_from(self.duration_ms).unwrap_or(0),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolAuditService for SqliteToolAuditService {
+    async fn record(&self, entry: ToolAuditEntry) -> ServiceResult<()> {
+        let started_at = entry
+            .started_at
+            .to_rfc3339_opts(SecondsFormat::Millis, true);
+        self.db
+            .execute(move |conn| {
+                conn.execute(
+                    "INSERT INTO tool_audit (conversation_id, tool_name, arguments,
+                         approval_source, approval_detail, outcome, exit_code, output,
+                         started_at, duration_ms)
+                     VALUES ((SELECT id FROM conversations WHERE id = ?1),
+                         ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    rusqlite::params![
+                        entry.conversation_id.map(|id| id.to_string()),
+                        entry.tool_name,
+                        entry.arguments,
+                        entry.approval_source.as_str(),
+                        entry.approval_detail,
+                        entry.outcome.as_str(),
+                        entry.exit_code,
+                        entry.output,
+                        started_at,
+                        i64::try_from(entry.duration_ms).unwrap_or(i64::MAX),
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn list(&self, filter: ToolAuditFilter) -> ServiceResult<Vec<ToolAuditEntry>> {
+        let search = filter
+            .search
+            .map(|search| search.trim().to_lowercase())
+            .filter(|search| !search.is_empty());
+        let outcome = filter.outcome.map(AuditOutcome::as_str);
+        let limit = filter.limit.map_or(-1, i64::from);
+        let rows = self
+            .db
+            .execute(move |conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {AUDIT_COLUMNS} FROM tool_audit
+                     WHERE (?1 IS NULL OR instr(lower(tool_name || ' ' || arguments || ' '
+                                || output || ' ' || COALESCE(approval_detail, '') || ' '
+                                || COALESCE(conversation_id, '')), ?1) > 0)
+                       AND (?2 IS NULL OR outcome = ?2)
+                     ORDER BY started_at DESC, id DESC
+                     LIMIT ?3"
+                ))?;
+                let rows = stmt.query_map(
+                    rusqlite::params![search, outcome, limit],
+                    AuditRow::from_row,
+                )?;
+                rows.collect::<Result<Vec<_>, _>>()
+            })
+            .await?;
+
+        rows.into_iter().map(AuditRow::into_entry).collect()
+    }
+}
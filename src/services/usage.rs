@@ -0,0 +1,29 @@
+//! Usage service trait
+//!
+//! Records the token usage and cost of every assistant turn and reports
+//! totals per conversation, per profile, and per day.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::{DailyUsage, ProfileUsage, UsageRecord, UsageTotals};
+use crate::services::ServiceResult;
+
+/// Usage ledger interface
+#[async_trait]
+pub trait UsageService: Send + Sync {
+    /// Append one turn to the ledger.
+    async fn record(&self, record: UsageRecord) -> ServiceResult<()>;
+
+    /// Totals for a conversation. A conversation without recorded turns
+    /// returns zeroed totals rather than `NotFound`.
+    async fn conversation_totals(&self, conversation_id: Uuid) -> ServiceResult<UsageTotals>;
+
+    /// Totals per profile, highest spend first.
+    async fn profile_totals(&self) -> ServiceResult<Vec<ProfileUsage>>;
+
+    /// Totals per UTC day on or after `since`, newest day first. Days
+    /// without usage are omitted.
+    async fn daily_totals(&self, since: NaiveDate) -> ServiceResult<Vec<DailyUsage>>;
+}
@@ -0,0 +1,155 @@
+//! SQLite-backed `UsageService` implementation.
+//!
+//! Ledger rows live in the `usage` table next to conversations; all sums are
+//! computed by `SQLite` so callers never load individual rows.
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, SecondsFormat};
+use uuid::Uuid;
+
+use crate::db::worker::DbHandle;
+use crate::models::{DailyUsage, ProfileUsage, UsageRecord, UsageTotals};
+use crate::services::usage::UsageService;
+use crate::services::{ServiceError, ServiceResult};
+
+/// Aggregate columns read by `totals_from_row`, in order.
+const TOTALS_COLUMNS: &str = "COALESCE(SUM(input_tokens), 0),
+     COALESCE(SUM(output_tokens), 0),
+     COALESCE(SUM(cache_read_tokens), 0),
+     COALESCE(SUM(cache_write_tokens), 0),
+     COALESCE(SUM(cost_usd), 0.0),
+     COUNT(*),
+     COUNT(*) - COUNT(cost_usd)";
+
+pub struct SqliteUsageService {
+    db: DbHandle,
+}
+
+impl SqliteUsageService {
+    #[must_use]
+    pub const fn new(db: DbHandle) -> Self {
+        Self { db }
+    }
+}
+
+/// Read `TOTALS_COLUMNS` starting at column `first`.
+fn totals_from_row(row: &rusqlite::Row<'_>, first: usize) -> Result<UsageTotals, rusqlite::Error> {
+    let count = |index: usize| -> Result<u64, rusqlite::Error> {
+        let value: i64 = row.get(first + index)?;
+        Ok(u64::try_from(value).unwrap_or(0))
+    };
+    Ok(UsageTotals {
+        input_tokens: count(0)?,
+        output_tokens: count(1)?,
+        cache_read_tokens: count(2)?,
+        cache_write_tokens: count(3)?,
+        cost_usd: row.get(first + 4)?,
+        turns: count(5)?,
+        unpriced_turns: count(6)?,
+    })
+}
+
+fn parse_uuid(value: &str) -> ServiceResult<Uuid> {
+    Uuid::parse_str(value).map_err(|e| ServiceError::Storage(format!("invalid uuid: {e}")))
+}
+
+#[async_trait]
+impl UsageService for SqliteUsageService {
+    async fn record(&self, record: UsageRecord) -> ServiceResult<()> {
+        let created_at = record
+            .created_at
+            .to_rfc3339_opts(SecondsFormat::Millis, true);
+        self.db
+            .execute(move |conn| {
+                conn.execute(
+                    "INSERT INTO usage (conversation_id, profile_id, provider_id, model_id,
+                         input_tokens, output_tokens, cache_read_tokens, cache_write_tokens,
+                         cost_usd, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    rusqlite::params![
+                        record.conversation_id.map(|id| id.to_string()),
+                        record.profile_id.to_string(),
+                        record.provider_id,
+                        record.model_id,
+                        record.usage.input_tokens,
+                        record.usage.output_tokens,
+                        record.usage.cache_read_tokens,
+                        record.usage.cache_write_tokens,
+                        record.cost_usd,
+                        created_at,
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn conversation_totals(&self, conversation_id: Uuid) -> ServiceResult<UsageTotals> {
+        let id = conversation_id.to_string();
+        self.db
+            .execute(move |conn| {
+                conn.query_row(
+                    &format!("SELECT {TOTALS_COLUMNS} FROM usage WHERE conversation_id = ?1"),
+                    [id],
+                    |row| totals_from_row(row, 0),
+                )
+            })
+            .await
+    }
+
+    async fn profile_totals(&self) -> ServiceResult<Vec<ProfileUsage>> {
+        let rows = self
+            .db
+            .execute(|conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT profile_id, {TOTALS_COLUMNS} FROM usage
+                     GROUP BY profile_id
+                     ORDER BY SUM(cost_usd) DESC, SUM(input_tokens + output_tokens) DESC"
+                ))?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, totals_from_row(row, 1)?))
+                })?;
+                rows.collect::<Result<Vec<_>, _>>()
+            })
+            .await?;
+
+        rows.into_iter()
+            .map(|(profile_id, totals)| {
+                Ok(ProfileUsage {
+                    profile_id: parse_uuid(&profile_id)?,
+                    totals,
+                })
+            })
+            .collect()
+    }
+
+    async fn daily_totals(&self, since: NaiveDate) -> ServiceResult<Vec<DailyUsage>> {
+        // Timestamps are stored as UTC RFC 3339, so the first ten characters
+        // are the UTC date and compare correctly as text.
+        let since = since.to_string();
+        let rows = self
+            .db
+            .execute(move |conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT substr(created_at, 1, 10) AS day, {TOTALS_COLUMNS} FROM usage
+                     WHERE created_at >= ?1
+                     GROUP BY day
+                     ORDER BY day DESC"
+                ))?;
+                let rows = stmt.query_map([since], |row| {
+                    Ok((row.get::<_, String>(0)?, totals_from_row(row, 1)?))
+                })?;
+                rows.collect::<Result<Vec<_>, _>>()
+            })
+            .await?;
+
+        rows.into_iter()
+            .map(|(day, totals)| {
+                let date = NaiveDate::parse_from_str(&day, "%Y-%m-%d").map_err(|e| {
+                    ServiceError::Storage(format!("invalid usage date '{day}': {e}"))
+                })?;
+                Ok(DailyUsage { date, totals })
+            })
+            .collect()
+    }
+}
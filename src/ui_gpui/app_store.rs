@@ -35,6 +35,7 @@ use selection_helpers::{
     load_state_targets_different_conversation, maybe_sync_selected_title,
     maybe_upgrade_selected_title_from_history, mutate_history_and_selected_selection_if_targeted,
     mutate_history_and_selected_title_if_targeted, mutate_profiles_snapshot,
+    replace_transcript_if_selected,
 };
 
 mod selection_helpers;
@@ -254,6 +255,16 @@ impl GpuiAppStore {
         prune_disconnected_subscribers_locked(&mut inner);
     }
 
+    /// Publish the current snapshot again, unchanged, so views re-apply it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the store mutex is poisoned.
+    pub fn republish(&self) {
+        let mut inner = self.inner.lock().expect("gpui app store mutex poisoned");
+        publish_snapshot_to_subscribers(&mut inner);
+    }
+
     /// Begin a generation-tracked conversation selection.
     ///
     /// @plan PLAN-20260304-GPUIREMEDIATE.P05
@@ -369,6 +380,7 @@ pub const fn is_store_managed(cmd: &ViewCommand) -> bool {
             | ViewCommand::ConversationTitleUpdated { .. }
             | ViewCommand::ConversationMessagesLoaded { .. }
             | ViewCommand::ConversationLoadFailed { .. }
+            | ViewCommand::ConversationTranscriptReplaced { .. }
             | ViewCommand::MessageAppended { .. }
             | ViewCommand::ShowThinking { .. }
             | ViewCommand::HideThinking { .. }
@@ -525,6 +537,10 @@ fn reduce_view_command_without_publish(inner: &mut AppStoreInner, command: ViewC
             selection_generation,
             message,
         } => reduce_conversation_load_failed(inner, conversation_id, selection_generation, message),
+        ViewCommand::ConversationTranscriptReplaced {
+            conversation_id,
+            messages,
+        } => replace_transcript_if_selected(inner, conversation_id, messages),
         ViewCommand::MessageAppended {
             conversation_id,
             role,
@@ -862,6 +878,8 @@ fn reduce_conversation_created(inner: &mut AppStoreInner, id: Uuid) -> bool {
             updated_at: chrono::Utc::now(),
             message_count: 0,
             preview: None,
+            parent_id: None,
+            organization: crate::models::ConversationOrganization::default(),
         };
         inner
             .snapshot
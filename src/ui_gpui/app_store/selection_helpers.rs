@@ -118,6 +118,27 @@ pub(super) fn append_persisted_message_if_target_matches_selected(
     true
 }
 
+/// Swap in a rewritten transcript for the selected conversation.
+///
+/// Unlike `ConversationMessagesLoaded` this is not tied to a selection
+/// generation: it follows a regenerate, edit, or version switch on the
+/// conversation the user is already looking at.
+pub(super) fn replace_transcript_if_selected(
+    inner: &mut AppStoreInner,
+    conversation_id: Uuid,
+    messages: Vec<ConversationMessagePayload>,
+) -> bool {
+    if inner.snapshot.chat.selected_conversation_id != Some(conversation_id) {
+        return false;
+    }
+    inner.finalized_stream_guards.remove(&conversation_id);
+    if inner.snapshot.chat.transcript == messages {
+        return false;
+    }
+    inner.snapshot.chat.transcript = messages;
+    true
+}
+
 pub(super) fn mutate_profiles_snapshot(
     inner: &mut AppStoreInner,
     profiles: Vec<ProfileSummary>,
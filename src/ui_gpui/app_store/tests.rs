@@ -25,6 +25,8 @@ fn conversation_summary(id: Uuid, title: &str, message_count: usize) -> Conversa
         updated_at: Utc::now(),
         message_count,
         preview: None,
+        parent_id: None,
+        organization: crate::models::ConversationOrganization::default(),
     }
 }
 
@@ -357,3 +359,49 @@ fn deleting_selected_last_conversation_leaves_empty_idle_state() {
     assert_eq!(after.chat.load_state, ConversationLoadState::Idle);
     assert_eq!(after.chat.selected_conversation_title, "New Conversation");
 }
+
+#[test]
+fn transcript_replaced_only_applies_to_selected_conversation() {
+    let conversation_a = Uuid::new_v4();
+    let conversation_b = Uuid::new_v4();
+    let store = GpuiAppStore::from_startup_inputs(startup_inputs(
+        conversation_a,
+        conversation_b,
+        Uuid::new_v4(),
+    ));
+    let generation = match store.begin_selection(conversation_a, BeginSelectionMode::BatchNoPublish)
+    {
+        BeginSelectionResult::NoOpSameSelection => 1,
+        BeginSelectionResult::BeganSelection { generation } => generation,
+    };
+    store.reduce_batch(vec![ViewCommand::ConversationMessagesLoaded {
+        conversation_id: conversation_a,
+        selection_generation: generation,
+        messages: vec![user_message("first"), user_message("second")],
+    }]);
+
+    let changed = store.reduce_batch(vec![ViewCommand::ConversationTranscriptReplaced {
+        conversation_id: conversation_b,
+        messages: vec![user_message("other")],
+    }]);
+    assert!(
+        !changed,
+        "replacement for an unselected conversation is ignored"
+    );
+
+    let changed = store.reduce_batch(vec![ViewCommand::ConversationTranscriptReplaced {
+        conversation_id: conversation_a,
+        messages: vec![user_message("first")],
+    }]);
+    assert!(changed);
+    let snapshot = store.current_snapshot();
+    assert_eq!(snapshot.chat.transcript, vec![user_message("first")]);
+    assert_eq!(
+        snapshot.chat.load_state,
+        ConversationLoadState::Ready {
+            conversation_id: conversation_a,
+            generation,
+        },
+        "replacing the transcript does not restart selection loading"
+    );
+}
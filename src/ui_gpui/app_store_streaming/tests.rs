@@ -24,6 +24,8 @@ fn conversation_summary(id: Uuid, title: &str) -> ConversationSummary {
         updated_at: chrono::Utc::now(),
         message_count: 0,
         preview: None,
+        parent_id: None,
+        organization: crate::models::ConversationOrganization::default(),
     }
 }
 
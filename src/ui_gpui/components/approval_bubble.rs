@@ -10,14 +10,46 @@
 //! ```
 //!
 //! When expanded, shows the list of grouped operations.
+//!
+//! File edits and writes carry a unified diff, shown with added and removed
+//! lines colored. Diffs longer than `DIFF_PREVIEW_LINES` start collapsed.
+//!
+//! A turn paused at its tool budget is shown the same way with only
+//! [Continue] [Stop], which answer as Yes and No.
 
 use gpui::{div, prelude::*, px, IntoElement, MouseButton, SharedString};
 use std::sync::Arc;
 
-use crate::presentation::view_command::ToolApprovalContext;
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory};
 use crate::ui_gpui::theme::Theme;
 use crate::ui_gpui::views::chat_view::{ApprovalBubbleState, GroupedOperation};
 
+/// Diff lines shown before the "Show full diff" toggle.
+pub const DIFF_PREVIEW_LINES: usize = 12;
+
+type ToggleHandler = Box<dyn Fn(&gpui::MouseDownEvent, &mut gpui::Window, &mut gpui::App)>;
+
+/// How a diff line is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLineKind {
+    Added,
+    Removed,
+    Hunk,
+    Context,
+}
+
+fn diff_line_kind(line: &str) -> DiffLineKind {
+    if line.starts_with("@@") || line.starts_with("--- ") || line.starts_with("+++ ") {
+        DiffLineKind::Hunk
+    } else if line.starts_with('+') {
+        DiffLineKind::Added
+    } else if line.starts_with('-') {
+        DiffLineKind::Removed
+    } else {
+        DiffLineKind::Context
+    }
+}
+
 /// Inline approval bubble for tool calls, with support for grouped operations.
 pub struct ApprovalBubble {
     request_id: String,
@@ -26,6 +58,8 @@ pub struct ApprovalBubble {
     operation_count: usize,
     expanded: bool,
     grouped_operations: Vec<GroupedOperation>,
+    diff_expanded: bool,
+    on_toggle_diff: Option<ToggleHandler>,
     on_yes: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
     on_session: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
     on_always: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
@@ -45,6 +79,8 @@ impl ApprovalBubble {
             operation_count: 1,
             expanded: false,
             grouped_operations: Vec::new(),
+            diff_expanded: false,
+            on_toggle_diff: None,
             on_yes: None,
             on_session: None,
             on_always: None,
@@ -70,6 +106,21 @@ impl ApprovalBubble {
         self
     }
 
+    #[must_use]
+    pub const fn diff_expanded(mut self, expanded: bool) -> Self {
+        self.diff_expanded = expanded;
+        self
+    }
+
+    #[must_use]
+    pub fn on_toggle_diff(
+        mut self,
+        f: impl Fn(&gpui::MouseDownEvent, &mut gpui::Window, &mut gpui::App) + 'static,
+    ) -> Self {
+        self.on_toggle_diff = Some(Box::new(f));
+        self
+    }
+
     #[must_use]
     pub fn on_yes(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
         self.on_yes = Some(Arc::new(f));
@@ -94,17 +145,17 @@ impl ApprovalBubble {
         self
     }
 
-    const fn icon_for_category(
-        category: crate::presentation::view_command::ToolCategory,
-    ) -> &'static str {
-        use crate::presentation::view_command::ToolCategory;
+    const fn icon_for_category(category: ToolCategory) -> &'static str {
         match category {
-            ToolCategory::FileEdit => "\u{270F}",   // Pencil
-            ToolCategory::FileWrite => "\u{1F4DD}", // Memo
-            ToolCategory::FileRead => "\u{1F4C4}",  // Page
-            ToolCategory::Search => "\u{1F50D}",    // Magnifying glass
-            ToolCategory::Shell => "\u{1F527}",     // Wrench
-            ToolCategory::Mcp => "\u{1F9F0}",       // Toolbox
+            ToolCategory::FileEdit => "\u{270F}",         // Pencil
+            ToolCategory::FileWrite => "\u{1F4DD}",       // Memo
+            ToolCategory::FileRead => "\u{1F4C4}",        // Page
+            ToolCategory::Search => "\u{1F50D}",          // Magnifying glass
+            ToolCategory::Shell => "\u{1F527}",           // Wrench
+            ToolCategory::Mcp => "\u{1F9F0}",             // Toolbox
+            ToolCategory::OutsideWorkspace => "\u{26A0}", // Warning sign
+            ToolCategory::Web => "\u{1F310}",             // Globe
+            ToolCategory::Budget => "\u{23F8}",           // Pause
         }
     }
 
@@ -184,6 +235,66 @@ impl ApprovalBubble {
         )
     }
 
+    fn render_diff(&mut self) -> Option<impl IntoElement> {
+        let diff = self.context.diff.as_ref()?;
+        let total = diff.lines().count();
+        let collapsible = total > DIFF_PREVIEW_LINES;
+        let shown = if collapsible && !self.diff_expanded {
+            DIFF_PREVIEW_LINES
+        } else {
+            total
+        };
+
+        let lines = diff.lines().take(shown).map(|line| {
+            let row = div()
+                .w_full()
+                .px(px(Theme::SPACING_SM))
+                .child(line.to_string());
+            match diff_line_kind(line) {
+                DiffLineKind::Added => row.text_color(Theme::success()),
+                DiffLineKind::Removed => row.text_color(Theme::error()),
+                DiffLineKind::Hunk => row.text_color(Theme::accent()),
+                DiffLineKind::Context => row.text_color(Theme::text_secondary()),
+            }
+        });
+        let mut container = div().w_full().flex().flex_col().child(
+            div()
+                .w_full()
+                .py(px(4.0))
+                .rounded(px(Theme::RADIUS_SM))
+                .bg(Theme::bg_darker())
+                .overflow_hidden()
+                .font_family(Theme::mono_font_family())
+                .text_size(px(Theme::font_size_small()))
+                .children(lines),
+        );
+
+        if collapsible {
+            let label = if self.diff_expanded {
+                "\u{25BC} Hide full diff".to_string()
+            } else {
+                format!("\u{25B6} Show full diff ({total} lines)")
+            };
+            let mut toggle = div()
+                .id(SharedString::from(format!(
+                    "approval-diff-toggle-{}",
+                    self.request_id
+                )))
+                .pt(px(2.0))
+                .text_size(px(Theme::font_size_small()))
+                .text_color(Theme::text_muted())
+                .cursor_pointer()
+                .hover(|s| s.text_color(Theme::accent()))
+                .child(label);
+            if let Some(handler) = self.on_toggle_diff.take() {
+                toggle = toggle.on_mouse_down(MouseButton::Left, handler);
+            }
+            container = container.child(toggle);
+        }
+
+        Some(container)
+    }
+
     fn render_grouped_ops(&self) -> Option<impl IntoElement> {
         if self.operation_count <= 1 {
             return None;
@@ -254,12 +365,34 @@ impl ApprovalBubble {
         Some(container)
     }
 
+    fn is_budget_prompt(&self) -> bool {
+        self.context.category == ToolCategory::Budget
+    }
+
     fn render_action_buttons(&self) -> impl IntoElement {
         let yes_id = format!("approval-yes-{}", self.request_id);
         let session_id = format!("approval-session-{}", self.request_id);
         let always_id = format!("approval-always-{}", self.request_id);
         let no_id = format!("approval-no-{}", self.request_id);
 
+        if self.is_budget_prompt() {
+            return div()
+                .flex()
+                .gap(px(Theme::SPACING_SM))
+                .child(Self::render_action_button(
+                    &yes_id,
+                    "Continue",
+                    Theme::button_primary(div()),
+                    self.on_yes.clone(),
+                ))
+                .child(Self::render_action_button(
+                    &no_id,
+                    "Stop",
+                    Theme::button_danger(div()),
+                    self.on_no.clone(),
+                ));
+        }
+
         div()
             .flex()
             .gap(px(Theme::SPACING_SM))
@@ -289,16 +422,21 @@ impl ApprovalBubble {
             ))
     }
 
-    fn render_state_indicator(state: &ApprovalBubbleState) -> impl IntoElement {
+    fn render_state_indicator(state: &ApprovalBubbleState, budget: bool) -> impl IntoElement {
+        let (approved, denied) = if budget {
+            ("\u{2713} Continued", "\u{2717} Stopped")
+        } else {
+            ("\u{2713} Approved", "\u{2717} Denied")
+        };
         match state {
             ApprovalBubbleState::Approved => div()
                 .text_size(px(Theme::font_size_ui()))
                 .text_color(Theme::success())
-                .child("\u{2713} Approved"),
+                .child(approved),
             ApprovalBubbleState::Denied => div()
                 .text_size(px(Theme::font_size_ui()))
                 .text_color(Theme::error())
-                .child("\u{2717} Denied"),
+                .child(denied),
             ApprovalBubbleState::Pending => div(),
         }
     }
@@ -307,7 +445,7 @@ impl ApprovalBubble {
 impl IntoElement for ApprovalBubble {
     type Element = gpui::Stateful<gpui::Div>;
 
-    fn into_element(self) -> Self::Element {
+    fn into_element(mut self) -> Self::Element {
         let bubble_id = SharedString::from(format!("approval-bubble-{}", self.request_id));
         let icon = Self::icon_for_category(self.context.category);
 
@@ -331,6 +469,10 @@ impl IntoElement for ApprovalBubble {
             container = container.child(details);
         }
 
+        if let Some(diff) = self.render_diff() {
+            container = container.child(diff);
+        }
+
         if let Some(grouped) = self.render_grouped_ops() {
             container = container.child(grouped);
         }
@@ -340,7 +482,8 @@ impl IntoElement for ApprovalBubble {
                 container = container.child(self.render_action_buttons());
             }
             state => {
-                container = container.child(Self::render_state_indicator(state));
+                let budget = self.is_budget_prompt();
+                container = container.child(Self::render_state_indicator(state, budget));
             }
         }
 
@@ -442,6 +585,34 @@ mod tests {
         assert_eq!(bubble.grouped_operations.len(), 2);
     }
 
+    #[test]
+    fn approval_bubble_carries_diff_and_toggle() {
+        let context = ToolApprovalContext::new("EditFile", ToolCategory::FileEdit, "/tmp/f.txt")
+            .with_diff("@@ -1 +1 @@\n-old\n+new");
+        let bubble = ApprovalBubble::new("req-10", context, ApprovalBubbleState::Pending)
+            .diff_expanded(true)
+            .on_toggle_diff(|_, _, _| {});
+        assert!(bubble.diff_expanded);
+        assert!(bubble.on_toggle_diff.is_some());
+        assert_eq!(
+            bubble.context.diff.as_deref(),
+            Some("@@ -1 +1 @@\n-old\n+new")
+        );
+    }
+
+    #[test]
+    fn diff_lines_are_classified_by_prefix() {
+        assert_eq!(diff_line_kind("@@ -1,2 +1,2 @@"), DiffLineKind::Hunk);
+        assert_eq!(diff_line_kind("+++ /work/src/lib.rs"), DiffLineKind::Hunk);
+        assert_eq!(diff_line_kind("+added"), DiffLineKind::Added);
+        assert_eq!(diff_line_kind("-removed"), DiffLineKind::Removed);
+        assert_eq!(diff_line_kind(" context"), DiffLineKind::Context);
+        assert_eq!(
+            diff_line_kind("... 3 more diff lines not shown"),
+            DiffLineKind::Context
+        );
+    }
+
     #[test]
     fn approval_bubble_icon_for_category() {
         assert!(!ApprovalBubble::icon_for_category(ToolCategory::FileEdit).is_empty());
@@ -450,5 +621,8 @@ mod tests {
         assert!(!ApprovalBubble::icon_for_category(ToolCategory::Search).is_empty());
         assert!(!ApprovalBubble::icon_for_category(ToolCategory::Shell).is_empty());
         assert!(!ApprovalBubble::icon_for_category(ToolCategory::Mcp).is_empty());
+        assert!(!ApprovalBubble::icon_for_category(ToolCategory::OutsideWorkspace).is_empty());
+        assert!(!ApprovalBubble::icon_for_category(ToolCategory::Web).is_empty());
+        assert!(!ApprovalBubble::icon_for_category(ToolCategory::Budget).is_empty());
     }
 }
@@ -38,6 +38,7 @@ pub mod secure_text_field;
 pub mod text_field;
 pub mod toggle;
 pub mod top_bar;
+pub mod usage_format;
 
 // Exports - Phase 02
 pub use divider::Divider;
@@ -0,0 +1,79 @@
+//! Compact text for token counts and spend, shared by the chat top bar and
+//! the settings usage panel.
+
+use crate::models::UsageTotals;
+
+/// `950`, `12.3k`, `4.1M`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // display rounding only
+pub fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+/// Spend in USD, with more precision for small amounts. A trailing `+`
+/// marks a lower bound (some turns had no pricing); `None` means no turn
+/// could be priced.
+#[must_use]
+pub fn format_cost(totals: &UsageTotals) -> Option<String> {
+    if totals.turns == 0 || totals.unpriced_turns == totals.turns {
+        return None;
+    }
+    let amount = if totals.cost_usd < 0.01 {
+        format!("${:.4}", totals.cost_usd)
+    } else {
+        format!("${:.2}", totals.cost_usd)
+    };
+    let bound = if totals.unpriced_turns > 0 { "+" } else { "" };
+    Some(format!("{amount}{bound}"))
+}
+
+/// `12.3k tokens · $0.42`, or just the tokens when nothing was priced.
+#[must_use]
+pub fn usage_summary(totals: &UsageTotals) -> String {
+    let tokens = format!("{} tokens", format_tokens(totals.total_tokens()));
+    match format_cost(totals) {
+        Some(cost) => format!("{tokens} · {cost}"),
+        None => tokens,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_abbreviated() {
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(12_345), "12.3k");
+        assert_eq!(format_tokens(4_100_000), "4.1M");
+    }
+
+    #[test]
+    fn cost_marks_partial_and_missing_pricing() {
+        let priced = UsageTotals {
+            input_tokens: 12_000,
+            output_tokens: 345,
+            cost_usd: 0.4213,
+            turns: 2,
+            ..UsageTotals::default()
+        };
+        assert_eq!(usage_summary(&priced), "12.3k tokens · $0.42");
+
+        let partial = UsageTotals {
+            cost_usd: 0.0042,
+            unpriced_turns: 1,
+            ..priced
+        };
+        assert_eq!(format_cost(&partial).as_deref(), Some("$0.0042+"));
+
+        let unpriced = UsageTotals {
+            unpriced_turns: 2,
+            ..priced
+        };
+        assert_eq!(usage_summary(&unpriced), "12.3k tokens");
+    }
+}
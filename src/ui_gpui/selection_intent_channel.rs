@@ -15,6 +15,8 @@ use uuid::Uuid;
 
 pub struct SelectionIntentChannel {
     pending: Mutex<VecDeque<Uuid>>,
+    /// Conversation and visible message index to scroll to once loaded.
+    message_jump: Mutex<Option<(Uuid, usize)>>,
 }
 
 impl SelectionIntentChannel {
@@ -22,6 +24,7 @@ impl SelectionIntentChannel {
     pub const fn new() -> Self {
         Self {
             pending: Mutex::new(VecDeque::new()),
+            message_jump: Mutex::new(None),
         }
     }
 
@@ -35,12 +38,68 @@ impl SelectionIntentChannel {
     ///
     /// Panics if the selection intent mutex is poisoned.
     pub fn request_select(&self, conversation_id: Uuid) {
+        self.message_jump
+            .lock()
+            .expect("selection intent channel mutex poisoned")
+            .take();
         self.pending
             .lock()
             .expect("selection intent channel mutex poisoned")
             .push_back(conversation_id);
     }
 
+    /// Queue a selection that also scrolls to the visible message at
+    /// `message_index` once the conversation is loaded. Replaces any earlier
+    /// jump request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selection intent mutex is poisoned.
+    pub fn request_select_message(&self, conversation_id: Uuid, message_index: usize) {
+        self.request_select(conversation_id);
+        *self
+            .message_jump
+            .lock()
+            .expect("selection intent channel mutex poisoned") =
+            Some((conversation_id, message_index));
+    }
+
+    /// Whether a message jump is waiting for `conversation_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selection intent mutex is poisoned.
+    pub fn has_message_jump(&self, conversation_id: Uuid) -> bool {
+        self.message_jump
+            .lock()
+            .expect("selection intent channel mutex poisoned")
+            .is_some_and(|(id, _)| id == conversation_id)
+    }
+
+    /// Take the pending message jump for `conversation_id`, provided its
+    /// target is among the `loaded_messages` currently shown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selection intent mutex is poisoned.
+    pub fn take_message_jump(
+        &self,
+        conversation_id: Uuid,
+        loaded_messages: usize,
+    ) -> Option<usize> {
+        let mut jump = self
+            .message_jump
+            .lock()
+            .expect("selection intent channel mutex poisoned");
+        match *jump {
+            Some((id, index)) if id == conversation_id && index < loaded_messages => {
+                *jump = None;
+                Some(index)
+            }
+            _ => None,
+        }
+    }
+
     /// Dequeue the next pending selection request.
     ///
     /// @plan PLAN-20260304-GPUIREMEDIATE.P05
@@ -71,3 +130,31 @@ static SELECTION_INTENT_CHANNEL: once_cell::sync::Lazy<SelectionIntentChannel> =
 pub fn selection_intent_channel() -> &'static SelectionIntentChannel {
     &SELECTION_INTENT_CHANNEL
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_jump_waits_for_its_conversation_to_load() {
+        let channel = SelectionIntentChannel::new();
+        let id = Uuid::new_v4();
+        channel.request_select_message(id, 3);
+
+        assert_eq!(channel.take_pending(), Some(id));
+        assert!(channel.has_message_jump(id));
+        assert_eq!(channel.take_message_jump(Uuid::new_v4(), 10), None);
+        assert_eq!(channel.take_message_jump(id, 3), None);
+        assert_eq!(channel.take_message_jump(id, 4), Some(3));
+        assert!(!channel.has_message_jump(id));
+    }
+
+    #[test]
+    fn plain_selection_cancels_a_pending_jump() {
+        let channel = SelectionIntentChannel::new();
+        let id = Uuid::new_v4();
+        channel.request_select_message(id, 1);
+        channel.request_select(id);
+        assert_eq!(channel.take_message_jump(id, 10), None);
+    }
+}
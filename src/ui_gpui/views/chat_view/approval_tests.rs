@@ -4,6 +4,7 @@
 #![allow(unused_imports)]
 #![allow(deprecated)]
 
+use crate::agent::policy_overrides::{OverrideLayer, PolicyOverride, PolicyOverrides};
 use crate::events::types::{ToolApprovalResponseAction, UserEvent};
 use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
 use crate::ui_gpui::bridge::GpuiBridge;
@@ -215,6 +216,107 @@ async fn yolo_mode_auto_approves_tool_requests_without_rendering_bubble(cx: &mut
     );
 }
 
+#[gpui::test]
+async fn conversation_override_decides_yolo_auto_approval(cx: &mut TestAppContext) {
+    let (bridge, user_rx) = make_bridge();
+    let view = cx.new(|cx| {
+        let mut view = ChatView::new(ChatState::default(), cx);
+        view.set_bridge(bridge);
+        view
+    });
+    let mut visual_cx = cx.add_empty_window().clone();
+    let conversation_id = Uuid::new_v4();
+    let mut overrides = PolicyOverrides::default();
+    overrides.set(
+        OverrideLayer::Conversation,
+        conversation_id,
+        PolicyOverride::parse("yolo=off").expect("override"),
+    );
+
+    visual_cx.update(|_window, app| {
+        view.update(app, |view: &mut ChatView, cx| {
+            view.handle_command(ViewCommand::YoloModeChanged { active: true }, cx);
+            view.handle_command(
+                ViewCommand::ApprovalPolicyOverridesUpdated { overrides },
+                cx,
+            );
+            view.handle_command(
+                ViewCommand::ToolApprovalRequest {
+                    conversation_id,
+                    request_id: "req-override".into(),
+                    context: make_shell_context("cargo publish"),
+                },
+                cx,
+            );
+            assert_eq!(view.state.approval_bubbles[&conversation_id].len(), 1);
+            assert!(user_rx.try_recv().is_err());
+
+            view.handle_command(
+                ViewCommand::ApprovalPolicyOverridesUpdated {
+                    overrides: PolicyOverrides::default(),
+                },
+                cx,
+            );
+            assert!(view.state.approval_bubbles.is_empty());
+        });
+    });
+
+    assert_eq!(
+        user_rx
+            .try_recv()
+            .expect("cleared override should auto-approve"),
+        UserEvent::ToolApprovalResponse {
+            request_id: "req-override".into(),
+            decision: ToolApprovalResponseAction::ProceedOnce,
+        }
+    );
+}
+
+#[gpui::test]
+async fn yolo_mode_leaves_tool_budget_prompts_to_the_user(cx: &mut TestAppContext) {
+    let (bridge, user_rx) = make_bridge();
+    let view = cx.new(|cx| {
+        let mut view = ChatView::new(ChatState::default(), cx);
+        view.set_bridge(bridge);
+        view
+    });
+    let mut visual_cx = cx.add_empty_window().clone();
+    let conversation_id = Uuid::new_v4();
+
+    visual_cx.update(|_window, app| {
+        view.update(app, |view: &mut ChatView, cx| {
+            view.handle_command(
+                ViewCommand::ToolApprovalRequest {
+                    conversation_id,
+                    request_id: "req-budget".into(),
+                    context: ToolApprovalContext::new(
+                        "Tool budget reached",
+                        ToolCategory::Budget,
+                        "101 tool calls this turn",
+                    ),
+                },
+                cx,
+            );
+            view.handle_command(ViewCommand::YoloModeChanged { active: true }, cx);
+            view.handle_command(
+                ViewCommand::ToolApprovalRequest {
+                    conversation_id,
+                    request_id: "req-budget-time".into(),
+                    context: ToolApprovalContext::new(
+                        "Tool budget reached",
+                        ToolCategory::Budget,
+                        "Turn running for 30m 0s",
+                    ),
+                },
+                cx,
+            );
+            assert_eq!(view.state.approval_bubbles[&conversation_id].len(), 2);
+        });
+    });
+
+    assert!(user_rx.try_recv().is_err());
+}
+
 #[gpui::test]
 async fn conversation_cleared_also_clears_approval_bubbles(cx: &mut TestAppContext) {
     let view = cx.new(|cx| ChatView::new(ChatState::default(), cx));
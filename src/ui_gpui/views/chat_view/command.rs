@@ -9,13 +9,20 @@
 //!   `RefreshHistory` side-effect.
 //! - `ToggleThinkingVisibility` — view-local toggle.
 //! - Export feedback commands — view-local display state.
+//! - Branch commands — switch to a new branch and hold the branch strip.
+//! - `TurnCheckpointsLoaded` — turns whose file changes can be reverted.
+//! - `ConversationUsageUpdated` — token and spend totals for the top bar.
+//! - `BackgroundProcessesUpdated` — the conversation's background processes.
+//! - `SubAgentUpdated` — progress of a running `Delegate` sub-agent.
+//! - `TodoListUpdated` — the conversation's `TodoWrite` task list.
+//! - MCP picker commands — the catalog and text for the composer.
 //!
 //! @plan PLAN-20250130-GPUIREDUX.P04
 
 use super::state::{ApprovalBubbleState, ToolApprovalBubble};
 use super::ChatView;
 use crate::events::types::{ToolApprovalResponseAction, UserEvent};
-use crate::presentation::view_command::{ToolApprovalContext, ViewCommand};
+use crate::presentation::view_command::{ToolApprovalContext, ToolCategory, ViewCommand};
 
 impl ChatView {
     fn is_export_notification(message: &str) -> bool {
@@ -33,7 +40,8 @@ impl ChatView {
         context: ToolApprovalContext,
         cx: &mut gpui::Context<Self>,
     ) {
-        if self.state.yolo_mode {
+        // YOLO approves tool calls, not going past the turn's budget.
+        if context.category != ToolCategory::Budget && self.yolo_for(conversation_id) {
             self.emit(UserEvent::ToolApprovalResponse {
                 request_id,
                 decision: ToolApprovalResponseAction::ProceedOnce,
@@ -55,6 +63,7 @@ impl ChatView {
             // Group with existing bubble
             let details = context.details.clone();
             existing.add_operation(request_id, details);
+            existing.append_diff(context.diff);
             if is_visible_conversation {
                 cx.notify();
             }
@@ -69,36 +78,62 @@ impl ChatView {
         }
     }
 
-    /// Handle YOLO mode activation - auto-approve any pending tool approval bubbles.
-    fn handle_yolo_mode_changed(&mut self, active: bool, cx: &mut gpui::Context<Self>) {
-        self.state.yolo_mode = active;
-        if active {
-            // Retroactively auto-approve any bubbles that arrived before YOLO was confirmed
-            // Use flat_map to emit for all request_ids in grouped bubbles
-            let pending_ids: Vec<String> = self
-                .state
-                .approval_bubbles
-                .values()
-                .flat_map(|bubbles| bubbles.iter())
-                .filter(|b| b.state == ApprovalBubbleState::Pending)
-                .flat_map(|b| b.request_ids.clone())
-                .collect();
-
-            for request_id in pending_ids {
-                self.emit(UserEvent::ToolApprovalResponse {
-                    request_id,
-                    decision: ToolApprovalResponseAction::ProceedOnce,
-                });
-            }
-
-            // Drop all pending bubbles — they've been auto-approved
-            for bubbles in self.state.approval_bubbles.values_mut() {
-                bubbles.retain(|b| b.state != ApprovalBubbleState::Pending);
-            }
-            self.state
-                .approval_bubbles
-                .retain(|_, bubbles| !bubbles.is_empty());
+    /// Whether YOLO mode is on for `conversation_id` with the approval
+    /// overrides applied. Only the conversation on screen has a known
+    /// profile, so other conversations skip the profile layer.
+    pub(super) fn yolo_for(&self, conversation_id: uuid::Uuid) -> bool {
+        let profile_id = self
+            .state
+            .selected_profile_id
+            .filter(|_| self.state.active_conversation_id == Some(conversation_id));
+        self.state.approval_overrides.yolo_mode(
+            self.state.yolo_mode,
+            profile_id,
+            Some(conversation_id),
+        )
+    }
+
+    fn yolo_approves(bubble: &ToolApprovalBubble) -> bool {
+        bubble.state == ApprovalBubbleState::Pending
+            && bubble.context.category != ToolCategory::Budget
+    }
+
+    /// Handle YOLO mode or override changes - auto-approve the pending tool
+    /// approval bubbles of every conversation now running under YOLO.
+    fn auto_approve_pending_under_yolo(&mut self, cx: &mut gpui::Context<Self>) {
+        let yolo_conversations: Vec<uuid::Uuid> = self
+            .state
+            .approval_bubbles
+            .keys()
+            .copied()
+            .filter(|conversation_id| self.yolo_for(*conversation_id))
+            .collect();
+        // Retroactively auto-approve any bubbles that arrived before YOLO was confirmed
+        // Use flat_map to emit for all request_ids in grouped bubbles
+        let pending_ids: Vec<String> = yolo_conversations
+            .iter()
+            .filter_map(|conversation_id| self.state.approval_bubbles.get(conversation_id))
+            .flat_map(|bubbles| bubbles.iter())
+            .filter(|b| Self::yolo_approves(b))
+            .flat_map(|b| b.request_ids.clone())
+            .collect();
+
+        for request_id in pending_ids {
+            self.emit(UserEvent::ToolApprovalResponse {
+                request_id,
+                decision: ToolApprovalResponseAction::ProceedOnce,
+            });
+        }
+
+        // Drop those pending bubbles — they've been auto-approved
+        for conversation_id in yolo_conversations {
+            if let Some(bubbles) = self.state.approval_bubbles.get_mut(&conversation_id) {
+                bubbles.retain(|b| !Self::yolo_approves(b));
+            }
         }
+        self.state
+            .approval_bubbles
+            .retain(|_, bubbles| !bubbles.is_empty());
         cx.notify();
     }
 
@@ -135,6 +170,76 @@ impl ChatView {
         cx.notify();
     }
 
+    /// Store per-conversation details shown around the transcript: branches,
+    /// message versions, checkpoints, usage and background processes.
+    fn handle_conversation_details(&mut self, cmd: ViewCommand, cx: &mut gpui::Context<Self>) {
+        match cmd {
+            ViewCommand::ConversationBranchesLoaded {
+                conversation_id,
+                branches,
+            } => {
+                self.state.branches_conversation_id = Some(conversation_id);
+                self.state.conversation_branches = branches;
+                cx.notify();
+            }
+            ViewCommand::MessageVersionsLoaded {
+                conversation_id,
+                versions,
+            } => {
+                self.state.message_versions_conversation_id = Some(conversation_id);
+                self.state.message_versions = versions;
+                cx.notify();
+            }
+            ViewCommand::TurnCheckpointsLoaded {
+                conversation_id,
+                checkpoints,
+            } => {
+                self.state.turn_checkpoints_conversation_id = Some(conversation_id);
+                self.state.turn_checkpoints = checkpoints;
+                cx.notify();
+            }
+            ViewCommand::ConversationUsageUpdated {
+                conversation_id,
+                totals,
+            } => {
+                self.state.conversation_usage = Some((conversation_id, totals));
+                cx.notify();
+            }
+            ViewCommand::BackgroundProcessesUpdated {
+                conversation_id,
+                processes,
+            } => {
+                if processes.is_empty() {
+                    self.state.background_processes.remove(&conversation_id);
+                } else {
+                    self.state
+                        .background_processes
+                        .insert(conversation_id, processes);
+                }
+                cx.notify();
+            }
+            ViewCommand::SubAgentUpdated {
+                conversation_id,
+                agent,
+            } => {
+                self.state.update_sub_agent(conversation_id, agent);
+                cx.notify();
+            }
+            ViewCommand::TodoListUpdated {
+                conversation_id,
+                todos,
+            } => {
+                if todos.is_empty() {
+                    self.state.todo_lists.remove(&conversation_id);
+                } else {
+                    self.state.todo_lists.insert(conversation_id, todos);
+                }
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+
     /// Handle incoming `ViewCommands` that are NOT store-managed.
     ///
     /// All shared state commands arrive exclusively through
@@ -217,11 +322,33 @@ impl ChatView {
                 cx.notify();
             }
             ViewCommand::YoloModeChanged { active } => {
-                self.handle_yolo_mode_changed(active, cx);
+                self.state.yolo_mode = active;
+                self.auto_approve_pending_under_yolo(cx);
+            }
+            ViewCommand::ApprovalPolicyOverridesUpdated { overrides } => {
+                self.state.approval_overrides = overrides;
+                self.auto_approve_pending_under_yolo(cx);
             }
             ViewCommand::ConversationSearchResults { results } => {
                 self.handle_conversation_search_results(results, cx);
             }
+            ViewCommand::ConversationBranched { branch_id, .. } => {
+                self.open_branch(branch_id, cx);
+            }
+            ViewCommand::ConversationBranchesLoaded { .. }
+            | ViewCommand::MessageVersionsLoaded { .. }
+            | ViewCommand::TurnCheckpointsLoaded { .. }
+            | ViewCommand::ConversationUsageUpdated { .. }
+            | ViewCommand::BackgroundProcessesUpdated { .. }
+            | ViewCommand::SubAgentUpdated { .. }
+            | ViewCommand::TodoListUpdated { .. } => {
+                self.handle_conversation_details(cmd, cx);
+            }
+            ViewCommand::McpCatalogLoaded { .. }
+            | ViewCommand::McpComposerTextReady { .. }
+            | ViewCommand::McpComposerTextFailed { .. } => {
+                self.handle_mcp_picker_command(cmd, cx);
+            }
             _ => {}
         }
     }
@@ -238,7 +365,10 @@ impl ChatView {
         if query.trim().is_empty() {
             self.state.sidebar_search_results = None;
         } else {
-            self.emit(crate::events::types::UserEvent::SearchConversations { query });
+            self.emit(crate::events::types::UserEvent::SearchConversations {
+                query,
+                filter: crate::models::ConversationFilter::default(),
+            });
         }
         cx.notify();
     }
@@ -25,6 +25,20 @@ mod snapshot_tests;
 
 mod render_bars_export;
 
+mod render_branches;
+
+mod render_checkpoints;
+
+mod render_conversation_dropdown;
+
+mod render_mcp_picker;
+
+mod render_processes;
+mod render_sub_agents;
+mod render_todos;
+
+mod render_versions;
+
 mod render_sidebar;
 mod snapshot;
 mod state;
@@ -90,6 +104,8 @@ pub struct ChatView {
     /// @plan PLAN-20260420-ISSUE180.P03
     /// @requirement REQ-180-001
     pub(super) conversation_list: Entity<ConversationListView>,
+    /// The composer's picker of MCP resources and prompts.
+    pub(super) mcp_picker: render_mcp_picker::McpPicker,
     #[cfg(test)]
     pub(super) maybe_scroll_chat_to_bottom_invocations: Cell<usize>,
 }
@@ -106,6 +122,7 @@ impl ChatView {
             selection_generation: 0,
             chat_scroll_handle: ScrollHandle::new(),
             conversation_list,
+            mcp_picker: render_mcp_picker::McpPicker::default(),
             #[cfg(test)]
             maybe_scroll_chat_to_bottom_invocations: Cell::new(0),
         }
@@ -244,6 +261,13 @@ impl ChatView {
         });
     }
 
+    /// Show the outcome of an archive export in the embedded sidebar list.
+    pub fn apply_archive_status(&self, status: String, cx: &mut gpui::Context<Self>) {
+        self.conversation_list.update(cx, |list, list_cx| {
+            list.apply_archive_status(status, list_cx);
+        });
+    }
+
     /// Read-only accessor for the sidebar search-focus flag, owned by the
     /// embedded `ConversationListView`.
     /// @plan PLAN-20260420-ISSUE180.P03
@@ -322,16 +346,17 @@ impl ChatView {
         index: usize,
         cx: &mut gpui::Context<Self>,
     ) {
-        if self.state.conversations.is_empty() {
+        let order = self.state.dropdown_conversation_ids();
+        if order.is_empty() {
             return;
         }
 
-        let bounded = index.min(self.state.conversations.len() - 1);
-        let conversation_id = self.state.conversations[bounded].id;
+        let bounded = index.min(order.len() - 1);
+        let conversation_id = order[bounded];
         tracing::info!(
             conversation_id = %conversation_id,
             index = bounded,
-            total = self.state.conversations.len(),
+            total = order.len(),
             "ChatView: selecting conversation from dropdown"
         );
         let switching_conversation = self.state.active_conversation_id != Some(conversation_id);
@@ -372,11 +397,11 @@ impl ChatView {
         delta: isize,
         cx: &mut gpui::Context<Self>,
     ) {
-        if !self.state.conversation_dropdown_open || self.state.conversations.is_empty() {
+        let len = self.state.dropdown_conversation_ids().len().cast_signed();
+        if !self.state.conversation_dropdown_open || len == 0 {
             return;
         }
 
-        let len = self.state.conversations.len().cast_signed();
         let current = self.state.conversation_dropdown_index.cast_signed();
         let next = (current + delta).clamp(0, len - 1).cast_unsigned();
         if next != self.state.conversation_dropdown_index {
@@ -399,9 +424,9 @@ impl ChatView {
     ) {
         if let Some(index) = self
             .state
-            .conversations
+            .dropdown_conversation_ids()
             .iter()
-            .position(|conversation| conversation.id == conversation_id)
+            .position(|id| *id == conversation_id)
         {
             self.select_conversation_at_index(index, cx);
         }
@@ -515,6 +540,20 @@ impl ChatView {
         cx.notify();
     }
 
+    /// Show or collapse the full diff preview of an approval bubble.
+    pub(super) fn toggle_approval_diff(&mut self, request_id: &str, cx: &mut gpui::Context<Self>) {
+        let bubble = self
+            .state
+            .approval_bubbles
+            .values_mut()
+            .flatten()
+            .find(|bubble| bubble.request_id == request_id);
+        if let Some(bubble) = bubble {
+            bubble.diff_expanded = !bubble.diff_expanded;
+            cx.notify();
+        }
+    }
+
     #[must_use]
     pub const fn profile_dropdown_open(&self) -> bool {
         self.state.profile_dropdown_open
@@ -623,7 +662,7 @@ impl ChatView {
         }
     }
 
-    fn insert_composer_text(&mut self, text: &str, cx: &mut gpui::Context<Self>) {
+    pub(super) fn insert_composer_text(&mut self, text: &str, cx: &mut gpui::Context<Self>) {
         if self.state.conversation_dropdown_open || self.state.profile_dropdown_open {
             return;
         }
@@ -784,12 +823,32 @@ impl ChatView {
         text: String,
         cx: &mut gpui::Context<Self>,
     ) {
-        self.emit(UserEvent::SendMessage {
-            text,
-            conversation_id: self.conversation_id,
-        });
+        if self.take_mcp_prompt_command(&text, cx) {
+            return;
+        }
+        match (
+            self.state.editing_message_index.take(),
+            self.state.active_conversation_id,
+        ) {
+            (Some(message_index), Some(conversation_id)) => {
+                self.emit(UserEvent::EditAndResend {
+                    conversation_id,
+                    message_index,
+                    content: text,
+                });
+            }
+            _ => self.emit(UserEvent::SendMessage {
+                text,
+                conversation_id: self.conversation_id,
+            }),
+        }
         self.state.input_text.clear();
         self.state.cursor_position = 0;
+        self.start_local_streaming(cx);
+    }
+
+    /// Put the view into streaming mode for a turn the user just started.
+    pub(super) fn start_local_streaming(&mut self, cx: &mut gpui::Context<Self>) {
         self.state.chat_autoscroll_enabled = true;
         self.state.conversation_dropdown_open = false;
         self.state.profile_dropdown_open = false;
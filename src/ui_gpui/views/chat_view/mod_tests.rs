@@ -130,6 +130,7 @@ async fn sidebar_helpers_toggle_visibility_and_emit_search_events(cx: &mut TestA
                 user_rx.try_recv().ok(),
                 Some(UserEvent::SearchConversations {
                     query: "  skills  ".to_string(),
+                    filter: crate::models::ConversationFilter::default(),
                 })
             );
 
@@ -248,6 +249,8 @@ fn conversation_ready_scroll_fixture() -> (
         updated_at: Utc::now(),
         message_count: 2,
         preview: Some("latest reply".to_string()),
+        parent_id: None,
+        organization: crate::models::ConversationOrganization::default(),
     };
     let loaded_messages = || {
         vec![
@@ -420,6 +423,8 @@ async fn apply_store_snapshot_streaming_calls_maybe_scroll_when_autoscroll_enabl
                     updated_at: Utc::now(),
                     message_count: 0,
                     preview: None,
+                    parent_id: None,
+                    organization: crate::models::ConversationOrganization::default(),
                 }],
             };
 
@@ -469,6 +474,8 @@ async fn apply_store_snapshot_streaming_skips_maybe_scroll_when_autoscroll_disab
                     updated_at: Utc::now(),
                     message_count: 0,
                     preview: None,
+                    parent_id: None,
+                    organization: crate::models::ConversationOrganization::default(),
                 }],
             };
 
@@ -521,6 +528,8 @@ async fn apply_store_snapshot_stream_finalize_calls_maybe_scroll_when_autoscroll
                     updated_at: Utc::now(),
                     message_count: 1,
                     preview: None,
+                    parent_id: None,
+                    organization: crate::models::ConversationOrganization::default(),
                 }],
             };
 
@@ -575,6 +584,8 @@ async fn apply_store_snapshot_stream_finalize_skips_maybe_scroll_when_autoscroll
                     updated_at: Utc::now(),
                     message_count: 1,
                     preview: None,
+                    parent_id: None,
+                    organization: crate::models::ConversationOrganization::default(),
                 }],
             };
 
@@ -626,6 +637,8 @@ async fn apply_store_snapshot_thinking_only_calls_maybe_scroll_when_autoscroll_e
                     updated_at: Utc::now(),
                     message_count: 0,
                     preview: None,
+                    parent_id: None,
+                    organization: crate::models::ConversationOrganization::default(),
                 }],
             };
 
@@ -678,6 +691,8 @@ async fn apply_store_snapshot_thinking_only_skips_maybe_scroll_when_autoscroll_d
                     updated_at: Utc::now(),
                     message_count: 0,
                     preview: None,
+                    parent_id: None,
+                    organization: crate::models::ConversationOrganization::default(),
                 }],
             };
 
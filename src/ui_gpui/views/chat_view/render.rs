@@ -149,6 +149,8 @@ impl ChatView {
                     // Refocus so keyboard input works after stopping.
                     self.focus_composer(cx);
                     cx.notify();
+                } else if self.state.editing_message_index.is_some() {
+                    self.cancel_message_edit(cx);
                 }
             }
             _ => {}
@@ -260,6 +262,13 @@ impl ChatView {
         let streaming = self.state.streaming.clone();
         let show_thinking = self.state.show_thinking;
         let filter_emoji = self.state.filter_emoji;
+        let can_branch = self.state.active_conversation_id.is_some()
+            && !matches!(streaming, StreamingState::Streaming { .. });
+        let message_count = messages.len();
+        let last_is_user = messages
+            .last()
+            .is_some_and(|msg| msg.role == MessageRole::User);
+        let jump_index = self.state.search_jump_index;
         div()
             .id("chat-area")
             .flex_1()
@@ -302,14 +311,27 @@ impl ChatView {
             .when(!messages.is_empty(), |d| {
                 d.children(messages.into_iter().enumerate().map(|(i, msg)| {
                     let id = SharedString::from(format!("msg-{i}"));
+                    let is_user = msg.role == MessageRole::User;
                     div()
                         .id(id)
                         .w_full()
                         .flex()
+                        .flex_col()
                         .justify_start()
+                        .when(jump_index == Some(i), |d| {
+                            d.border_l_2().border_color(Theme::accent()).pl(px(6.0))
+                        })
                         .child(Self::render_message(&msg, show_thinking, filter_emoji))
+                        .when(can_branch, |d| {
+                            d.child(self.render_message_actions(i, is_user, cx))
+                        })
+                        .children(self.render_turn_checkpoints(i, cx))
                 }))
             })
+            // Retry a trailing prompt that never got a reply
+            .when(can_branch && last_is_user, |d| {
+                d.child(self.render_retry_row(message_count, cx))
+            })
             // Approval bubbles (inline in message stream) - queue: only first pending
             .children(
                 self.state
@@ -484,7 +506,15 @@ impl ChatView {
         let mut approval = ApprovalBubble::new(&bubble.request_id, bubble.context.clone(), state)
             .operation_count(operation_count)
             .expanded(expanded)
-            .grouped_operations(grouped_ops);
+            .grouped_operations(grouped_ops)
+            .diff_expanded(bubble.diff_expanded);
+
+        if bubble.context.diff.is_some() {
+            let request_id = bubble.request_id.clone();
+            approval = approval.on_toggle_diff(cx.listener(move |this, _, _window, cx| {
+                this.toggle_approval_diff(&request_id, cx);
+            }));
+        }
 
         if matches!(bubble.state, ApprovalBubbleState::Pending) {
             let bridge = self.bridge.clone();
@@ -559,8 +589,6 @@ impl ChatView {
             });
         }
 
-        // Use cx to mark the closure as capturing the context lifetime
-        let _ = cx;
         approval
     }
 
@@ -653,6 +681,7 @@ impl ChatView {
                     }
                 }),
             )
+            .child(self.render_mcp_button(cx))
             .child(Self::render_composer_field(
                 focus_handle,
                 input_box_height,
@@ -826,8 +855,32 @@ impl ChatView {
             .when(self.state.export_feedback_message.is_some(), |d| {
                 d.child(self.render_export_feedback_bar())
             })
+            // Branch strip (only when the conversation has branches)
+            .when(!self.state.active_branches().is_empty(), |d| {
+                d.child(self.render_branch_strip(cx))
+            })
             // Chat area (flex)
             .child(self.render_chat_area(cx))
+            // Edit banner (only while editing a sent prompt)
+            .when(self.state.editing_message_index.is_some(), |d| {
+                d.child(self.render_edit_banner(cx))
+            })
+            // Background processes (only while the conversation has any)
+            .when(!self.state.active_background_processes().is_empty(), |d| {
+                d.child(self.render_process_strip(cx))
+            })
+            // Task list (only while the agent's plan has unfinished items)
+            .when(self.state.active_todo_list().is_some(), |d| {
+                d.child(self.render_todo_panel())
+            })
+            // Running sub-agents (only while a `Delegate` call is in progress)
+            .when(!self.state.active_sub_agents().is_empty(), |d| {
+                d.child(self.render_sub_agent_strip())
+            })
+            // MCP picker (while open or fetching for the composer)
+            .when(self.mcp_picker.is_visible(), |d| {
+                d.child(self.render_mcp_picker(cx))
+            })
             // Input bar (50px)
             .child(self.render_input_bar(cx))
         // Note: Dropdown overlays are now rendered at root level in render()
@@ -1,20 +1,23 @@
 //! Chat view bar and dropdown render subtrees above the chat area.
-//! Contains `render_top_bar`, `render_title_bar`, `render_conversation_dropdown`, and `render_profile_dropdown`.
+//! Contains `render_top_bar`, `render_title_bar`, and `render_profile_dropdown`; the
+//! conversation dropdown lives in `render_conversation_dropdown.rs`.
 //! @plan PLAN-20260325-ISSUE11B.P02
 use super::state::StreamingState;
 use super::ChatView;
 use crate::events::types::UserEvent;
-use crate::presentation::view_command::{AppMode, ConversationSummary, ProfileSummary};
+use crate::models::UsageTotals;
+use crate::presentation::view_command::{AppMode, ProfileSummary};
 use crate::ui_gpui::components::copy_icons::copy_icon;
+use crate::ui_gpui::components::usage_format::usage_summary;
 use crate::ui_gpui::theme::Theme;
 use crate::ui_gpui::views::main_panel::MainPanelAppState;
 use gpui::{div, prelude::*, px, FontWeight, MouseButton, SharedString};
 /// Height of the top bar.
-const TOP_BAR_HEIGHT: f32 = 44.0;
+pub(super) const TOP_BAR_HEIGHT: f32 = 44.0;
 /// Height of the title bar where selectors live.
-const TITLE_BAR_HEIGHT: f32 = 32.0;
+pub(super) const TITLE_BAR_HEIGHT: f32 = 32.0;
 /// Gap below bars before dropdown appears (negative = move up).
-const DROPDOWN_GAP: f32 = -1.0;
+pub(super) const DROPDOWN_GAP: f32 = -1.0;
 macro_rules! icon_btn {
     ($id:expr, $label:expr, $active:expr, $handler:expr) => {
         div()
@@ -104,7 +107,12 @@ impl ChatView {
 
     /// @plan PLAN-20250130-GPUIREDUX.P04
     pub(super) fn render_top_bar(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
-        let yolo_active = self.state.yolo_mode;
+        let conversation_id = self.displayed_conversation_id();
+        let yolo_active = conversation_id.map_or(self.state.yolo_mode, |id| self.yolo_for(id));
+        let override_labels = self
+            .state
+            .approval_overrides
+            .active_labels(self.state.selected_profile_id, conversation_id);
         let is_popout = cx
             .try_global::<MainPanelAppState>()
             .is_some_and(|s| s.app_mode == AppMode::Popout);
@@ -136,7 +144,13 @@ impl ChatView {
                             .text_color(Theme::text_primary())
                             .child("PersonalAgent"),
                     )
-                    .when(yolo_active, |d| d.child(Self::render_yolo_badge())),
+                    .when(yolo_active, |d| d.child(Self::render_yolo_badge()))
+                    .when(!override_labels.is_empty(), |d| {
+                        d.child(Self::render_overrides_badge(&override_labels))
+                    })
+                    .when_some(self.state.active_conversation_usage(), |d, totals| {
+                        d.child(Self::render_usage_label(totals))
+                    }),
             )
             .child(self.render_toolbar_buttons(cx))
     }
@@ -155,6 +169,29 @@ impl ChatView {
             .child("YOLO")
     }
 
+    /// Approval overrides in force for the displayed conversation.
+    fn render_overrides_badge(labels: &[String]) -> impl IntoElement {
+        div()
+            .id("approval-overrides-badge")
+            .px(px(6.0))
+            .py(px(2.0))
+            .rounded(px(Theme::RADIUS_SM))
+            .border_1()
+            .border_color(Theme::warning())
+            .text_size(px(Theme::font_size_ui()))
+            .text_color(Theme::warning())
+            .child(labels.join(" · "))
+    }
+
+    /// Tokens and spend recorded for the active conversation.
+    fn render_usage_label(totals: &UsageTotals) -> impl IntoElement {
+        div()
+            .id("conversation-usage")
+            .text_size(px(Theme::font_size_ui()))
+            .text_color(Theme::text_muted())
+            .child(usage_summary(totals))
+    }
+
     /// Emoji filter toggle button with smiley icon.
     fn render_emoji_filter_button(
         filter_emoji: bool,
@@ -719,129 +756,6 @@ impl ChatView {
             )
     }
 
-    pub(super) fn render_conversation_dropdown(
-        &self,
-        cx: &mut gpui::Context<Self>,
-    ) -> impl IntoElement {
-        let active_id = self.state.active_conversation_id;
-        let highlighted = self.state.conversation_dropdown_index;
-        let sidebar_toggle_offset = Self::sidebar_toggle_offset(cx);
-
-        div()
-            .id("chat-conversation-dropdown-overlay")
-            .absolute()
-            .top(px(0.0))
-            .left(px(0.0))
-            .right(px(0.0))
-            .bottom(px(0.0))
-            .on_mouse_down(
-                MouseButton::Left,
-                cx.listener(|this, _, _window, cx| {
-                    if this.state.conversation_dropdown_open {
-                        this.state.conversation_dropdown_open = false;
-                        cx.notify();
-                    }
-                }),
-            )
-            .child(
-                div()
-                    .id("chat-conversation-dropdown-menu")
-                    .absolute()
-                    .top(px(
-                        (TOP_BAR_HEIGHT + TITLE_BAR_HEIGHT + DROPDOWN_GAP) * Theme::ui_scale()
-                    ))
-                    .left(px(12.0 + sidebar_toggle_offset))
-                    .min_w(px(220.0))
-                    .max_w(px(520.0))
-                    .max_h(px(220.0))
-                    .overflow_y_scroll()
-                    .bg(Theme::bg_dark())
-                    .border_1()
-                    .border_color(Theme::border())
-                    .rounded(px(4.0))
-                    .shadow_lg()
-                    .on_mouse_down(MouseButton::Left, cx.listener(|_, _, _, _| {}))
-                    .children(
-                        self.state
-                            .conversations
-                            .iter()
-                            .enumerate()
-                            .map(|(index, conv)| {
-                                Self::render_conversation_item(
-                                    index,
-                                    conv,
-                                    active_id,
-                                    highlighted,
-                                    cx,
-                                )
-                            }),
-                    ),
-            )
-    }
-
-    /// Single row inside the conversation dropdown.
-    fn render_conversation_item(
-        index: usize,
-        conversation: &ConversationSummary,
-        active_id: Option<uuid::Uuid>,
-        highlighted_index: usize,
-        cx: &mut gpui::Context<Self>,
-    ) -> impl IntoElement {
-        let conversation_id = conversation.id;
-        let selected = active_id == Some(conversation_id);
-        let highlighted = highlighted_index == index;
-        let title = if conversation.title.trim().is_empty() {
-            "Untitled Conversation".to_string()
-        } else {
-            conversation.title.clone()
-        };
-        let count_label = if conversation.message_count == 1 {
-            "1 message".to_string()
-        } else {
-            format!("{} messages", conversation.message_count)
-        };
-
-        div()
-            .id(SharedString::from(format!(
-                "chat-conversation-item-{conversation_id}"
-            )))
-            .w_full()
-            .px(px(Theme::spacing_sm_scaled()))
-            .py(px(Theme::spacing_md_scaled() * 0.5))
-            .cursor_pointer()
-            .when(selected, |row| {
-                row.bg(Theme::accent()).text_color(Theme::selection_fg())
-            })
-            .when(!selected && highlighted, |row| {
-                row.bg(Theme::accent_hover())
-                    .text_color(Theme::selection_fg())
-            })
-            .when(!selected && !highlighted, |row| {
-                row.hover(|s| s.bg(Theme::bg_darker()))
-                    .text_color(Theme::text_primary())
-            })
-            .child(
-                div()
-                    .flex()
-                    .items_center()
-                    .justify_between()
-                    .child(div().text_size(px(Theme::font_size_ui())).child(title))
-                    .child(
-                        div()
-                            .text_size(px(Theme::font_size_ui()))
-                            .text_color(Theme::text_secondary())
-                            .child(count_label),
-                    ),
-            )
-            .on_mouse_down(
-                MouseButton::Left,
-                cx.listener(move |this, _, _window, cx| {
-                    this.select_conversation_at_index(index, cx);
-                    cx.stop_propagation();
-                }),
-            )
-    }
-
     /// Render profile dropdown overlay at root level.
     pub(super) fn render_profile_dropdown(
         &self,
@@ -962,7 +876,7 @@ impl ChatView {
     }
 
     /// Extra left offset when the sidebar toggle button is present in popout mode.
-    fn sidebar_toggle_offset(cx: &gpui::Context<Self>) -> f32 {
+    pub(super) fn sidebar_toggle_offset(cx: &gpui::Context<Self>) -> f32 {
         let is_popout = cx
             .try_global::<MainPanelAppState>()
             .is_some_and(|s| s.app_mode == AppMode::Popout);
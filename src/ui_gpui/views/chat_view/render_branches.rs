@@ -0,0 +1,128 @@
+//! Conversation branch UI: the branch strip above the chat area and the
+//! per-message "Branch" link.
+//!
+//! Branch state arrives through `ConversationBranchesLoaded` (see
+//! `command.rs`); switching branches goes through the selection intent
+//! channel exactly like picking a conversation from the dropdown.
+
+use super::ChatView;
+use crate::events::types::UserEvent;
+use crate::presentation::view_command::ConversationBranchSummary;
+use crate::ui_gpui::selection_intent_channel;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+use uuid::Uuid;
+
+impl ChatView {
+    /// Switch the view to `branch_id`.
+    ///
+    /// Goes straight to the selection intent channel rather than
+    /// `select_conversation_by_id`, because a freshly created branch may not
+    /// be in the conversation list snapshot yet.
+    pub(super) fn open_branch(&mut self, branch_id: Uuid, cx: &mut gpui::Context<Self>) {
+        if self.state.active_conversation_id == Some(branch_id) {
+            return;
+        }
+        self.state.conversation_dropdown_open = false;
+        self.state.conversation_title_editing = false;
+        self.state.chat_autoscroll_enabled = true;
+        self.chat_scroll_handle.scroll_to_bottom();
+        selection_intent_channel().request_select(branch_id);
+        cx.notify();
+    }
+
+    /// Fork the active conversation at the visible message `index`.
+    pub(super) fn branch_at_message(&mut self, index: usize, cx: &mut gpui::Context<Self>) {
+        let Some(conversation_id) = self.state.active_conversation_id else {
+            return;
+        };
+        if matches!(
+            self.state.streaming,
+            super::state::StreamingState::Streaming { .. }
+        ) {
+            return;
+        }
+        self.emit(UserEvent::BranchConversation {
+            conversation_id,
+            message_index: index,
+        });
+        cx.notify();
+    }
+
+    /// Render the row of sibling branches for the active conversation.
+    pub(super) fn render_branch_strip(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let active_id = self.state.active_conversation_id;
+        let branches = self.state.active_branches().to_vec();
+
+        div()
+            .id("chat-branch-strip")
+            .flex_shrink_0()
+            .h(px(24.0))
+            .w_full()
+            .bg(Theme::bg_darker())
+            .px(px(12.0))
+            .flex()
+            .items_center()
+            .gap(px(6.0))
+            .overflow_x_scroll()
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_size(px(Theme::font_size_small()))
+                    .text_color(Theme::text_muted())
+                    .child("Branches:"),
+            )
+            .children(branches.into_iter().enumerate().map(|(i, branch)| {
+                let is_active = active_id == Some(branch.id);
+                let branch_id = branch.id;
+                div()
+                    .id(SharedString::from(format!("branch-{i}")))
+                    .flex_shrink_0()
+                    .px(px(6.0))
+                    .rounded(px(4.0))
+                    .text_size(px(Theme::font_size_small()))
+                    .when(is_active, |d| {
+                        d.bg(Theme::bg_dark()).text_color(Theme::text_primary())
+                    })
+                    .when(!is_active, |d| {
+                        d.text_color(Theme::accent())
+                            .cursor_pointer()
+                            .hover(|s| s.text_color(Theme::accent_hover()))
+                    })
+                    .child(Self::branch_label(&branch))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _window, cx| {
+                            this.open_branch(branch_id, cx);
+                        }),
+                    )
+            }))
+    }
+
+    /// Small "Branch" link rendered in a finalized message's action row.
+    pub(super) fn render_branch_link(
+        index: usize,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(SharedString::from(format!("branch-from-{index}")))
+            .text_size(px(Theme::font_size_small()))
+            .text_color(Theme::text_muted())
+            .cursor_pointer()
+            .hover(|s| s.text_color(Theme::accent()))
+            .child("Branch from here")
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _window, cx| {
+                    this.branch_at_message(index, cx);
+                }),
+            )
+    }
+
+    fn branch_label(branch: &ConversationBranchSummary) -> String {
+        match branch.forked_at_index {
+            Some(index) => format!("{} (@{})", branch.title, index + 1),
+            None => branch.title.clone(),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! File checkpoint UI: the "Revert turn" / per-file "Revert" row under a
+//! message whose turn wrote files.
+//!
+//! Checkpoint state arrives through `TurnCheckpointsLoaded` (see
+//! `command.rs`). Reverting emits `RevertTurnFiles`; the presenter answers
+//! with a fresh `TurnCheckpointsLoaded` once the files are restored.
+
+use super::state::StreamingState;
+use super::ChatView;
+use crate::events::types::UserEvent;
+use crate::presentation::view_command::TurnCheckpointSummary;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+use uuid::Uuid;
+
+/// Short label for a changed file: its name, or the full path if it has none.
+fn file_label(path: &str) -> String {
+    std::path::Path::new(path).file_name().map_or_else(
+        || path.to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+impl ChatView {
+    fn revert_turn_files(
+        &mut self,
+        turn_id: Uuid,
+        path: Option<String>,
+        cx: &mut gpui::Context<Self>,
+    ) {
+        if matches!(self.state.streaming, StreamingState::Streaming { .. }) {
+            return;
+        }
+        let Some(conversation_id) = self.state.active_conversation_id else {
+            return;
+        };
+        self.emit(UserEvent::RevertTurnFiles {
+            conversation_id,
+            turn_id,
+            path,
+        });
+        cx.notify();
+    }
+
+    /// Rows for the revertible turns anchored at visible `index`.
+    pub(super) fn render_turn_checkpoints(
+        &self,
+        index: usize,
+        cx: &mut gpui::Context<Self>,
+    ) -> Vec<gpui::AnyElement> {
+        self.state
+            .turn_checkpoints_at(index)
+            .into_iter()
+            .map(|checkpoint| Self::render_turn_checkpoint(checkpoint, cx).into_any_element())
+            .collect()
+    }
+
+    fn render_turn_checkpoint(
+        checkpoint: TurnCheckpointSummary,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let turn_id = checkpoint.turn_id;
+        let count = checkpoint.files.len();
+        let summary = if count == 1 {
+            "1 file changed".to_string()
+        } else {
+            format!("{count} files changed")
+        };
+
+        div()
+            .w_full()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap(px(8.0))
+            .text_size(px(Theme::font_size_small()))
+            .text_color(Theme::text_muted())
+            .child(summary)
+            .child(
+                Self::action_link(
+                    SharedString::from(format!("revert-turn-{turn_id}")),
+                    "Revert turn",
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| {
+                        this.revert_turn_files(turn_id, None, cx);
+                    }),
+                ),
+            )
+            .children(
+                checkpoint
+                    .files
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, path)| {
+                        div()
+                            .id(SharedString::from(format!(
+                                "revert-file-{turn_id}-{position}"
+                            )))
+                            .px(px(4.0))
+                            .rounded(px(3.0))
+                            .bg(Theme::bg_darker())
+                            .cursor_pointer()
+                            .hover(|s| s.text_color(Theme::accent()))
+                            .child(format!("\u{21A9} {}", file_label(&path)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, _, _window, cx| {
+                                    this.revert_turn_files(turn_id, Some(path.clone()), cx);
+                                }),
+                            )
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_label_uses_file_name() {
+        assert_eq!(file_label("/work/app/src/main.rs"), "main.rs");
+        assert_eq!(file_label("/"), "/");
+    }
+}
@@ -0,0 +1,156 @@
+//! Conversation dropdown under the title bar.
+//!
+//! Lists conversations in the same order as the conversation list: pinned
+//! first, archived ones hidden unless active (see
+//! `conversation_list::groups`). `conversation_dropdown_index` indexes this
+//! display order, so keyboard navigation steps through rows as rendered.
+
+use super::render_bars::{DROPDOWN_GAP, TITLE_BAR_HEIGHT, TOP_BAR_HEIGHT};
+use super::ChatView;
+use crate::models::ConversationFilter;
+use crate::presentation::view_command::ConversationSummary;
+use crate::ui_gpui::theme::Theme;
+use crate::ui_gpui::views::conversation_list::groups::group_conversations;
+use gpui::{div, prelude::*, px, AnyElement, MouseButton, SharedString};
+
+impl ChatView {
+    pub(super) fn render_conversation_dropdown(
+        &self,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let active_id = self.state.active_conversation_id;
+        let highlighted = self.state.conversation_dropdown_index;
+        let sidebar_toggle_offset = Self::sidebar_toggle_offset(cx);
+
+        let groups = group_conversations(
+            &self.state.conversations,
+            &ConversationFilter::default(),
+            false,
+            active_id,
+        );
+        let mut rows: Vec<AnyElement> = Vec::new();
+        let mut index = 0;
+        for group in groups {
+            if let Some(label) = group.label {
+                rows.push(Self::render_dropdown_group_label(label));
+            }
+            for conv in group.conversations {
+                rows.push(
+                    Self::render_conversation_item(index, conv, active_id, highlighted, cx)
+                        .into_any_element(),
+                );
+                index += 1;
+            }
+        }
+
+        div()
+            .id("chat-conversation-dropdown-overlay")
+            .absolute()
+            .top(px(0.0))
+            .left(px(0.0))
+            .right(px(0.0))
+            .bottom(px(0.0))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _window, cx| {
+                    if this.state.conversation_dropdown_open {
+                        this.state.conversation_dropdown_open = false;
+                        cx.notify();
+                    }
+                }),
+            )
+            .child(
+                div()
+                    .id("chat-conversation-dropdown-menu")
+                    .absolute()
+                    .top(px(
+                        (TOP_BAR_HEIGHT + TITLE_BAR_HEIGHT + DROPDOWN_GAP) * Theme::ui_scale()
+                    ))
+                    .left(px(12.0 + sidebar_toggle_offset))
+                    .min_w(px(220.0))
+                    .max_w(px(520.0))
+                    .max_h(px(220.0))
+                    .overflow_y_scroll()
+                    .bg(Theme::bg_dark())
+                    .border_1()
+                    .border_color(Theme::border())
+                    .rounded(px(4.0))
+                    .shadow_lg()
+                    .on_mouse_down(MouseButton::Left, cx.listener(|_, _, _, _| {}))
+                    .children(rows),
+            )
+    }
+
+    fn render_dropdown_group_label(label: String) -> AnyElement {
+        div()
+            .px(px(Theme::spacing_sm_scaled()))
+            .pt(px(4.0))
+            .text_size(px(9.0))
+            .text_color(Theme::text_secondary())
+            .child(SharedString::from(label))
+            .into_any_element()
+    }
+
+    /// Single row inside the conversation dropdown.
+    fn render_conversation_item(
+        index: usize,
+        conversation: &ConversationSummary,
+        active_id: Option<uuid::Uuid>,
+        highlighted_index: usize,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let conversation_id = conversation.id;
+        let selected = active_id == Some(conversation_id);
+        let highlighted = highlighted_index == index;
+        let title = if conversation.title.trim().is_empty() {
+            "Untitled Conversation".to_string()
+        } else {
+            conversation.title.clone()
+        };
+        let count_label = if conversation.message_count == 1 {
+            "1 message".to_string()
+        } else {
+            format!("{} messages", conversation.message_count)
+        };
+
+        div()
+            .id(SharedString::from(format!(
+                "chat-conversation-item-{conversation_id}"
+            )))
+            .w_full()
+            .px(px(Theme::spacing_sm_scaled()))
+            .py(px(Theme::spacing_md_scaled() * 0.5))
+            .cursor_pointer()
+            .when(selected, |row| {
+                row.bg(Theme::accent()).text_color(Theme::selection_fg())
+            })
+            .when(!selected && highlighted, |row| {
+                row.bg(Theme::accent_hover())
+                    .text_color(Theme::selection_fg())
+            })
+            .when(!selected && !highlighted, |row| {
+                row.hover(|s| s.bg(Theme::bg_darker()))
+                    .text_color(Theme::text_primary())
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(div().text_size(px(Theme::font_size_ui())).child(title))
+                    .child(
+                        div()
+                            .text_size(px(Theme::font_size_ui()))
+                            .text_color(Theme::text_secondary())
+                            .child(count_label),
+                    ),
+            )
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _window, cx| {
+                    this.select_conversation_at_index(index, cx);
+                    cx.stop_propagation();
+                }),
+            )
+    }
+}
@@ -0,0 +1,426 @@
+//! MCP picker: the panel above the input bar listing the resources and
+//! prompts of the running MCP servers, opened from the "MCP" button.
+//!
+//! Opening it emits `RefreshMcpCatalog` and the catalog arrives through
+//! `McpCatalogLoaded` (see `command.rs`). Picking a resource emits
+//! `AttachMcpResource`; a prompt without arguments emits `InsertMcpPrompt`.
+//! Either way the text lands in the composer once `McpComposerTextReady`
+//! arrives. A prompt with arguments instead puts a `/prompt` command on a
+//! new last line of the composer; sending it renders the prompt in place of
+//! that line rather than sending a message.
+
+use super::ChatView;
+use crate::events::types::UserEvent;
+use crate::mcp::{McpPrompt, McpPromptInvocation, McpResource};
+use crate::presentation::view_command::ViewCommand;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+
+/// Tallest the picker grows before it scrolls.
+const MAX_PICKER_HEIGHT: f32 = 160.0;
+
+/// What the composer's MCP picker shows.
+#[derive(Debug, Default)]
+pub(super) struct McpPicker {
+    pub(super) open: bool,
+    pub(super) loading: bool,
+    pub(super) resources: Vec<McpResource>,
+    pub(super) prompts: Vec<McpPrompt>,
+    /// The resource or prompt being fetched for the composer.
+    pub(super) pending: Option<String>,
+    pub(super) error: Option<String>,
+}
+
+impl McpPicker {
+    pub(super) const fn is_visible(&self) -> bool {
+        self.open || self.pending.is_some() || self.error.is_some()
+    }
+}
+
+fn prompt_label(prompt: &McpPrompt) -> String {
+    if prompt.arguments.is_empty() {
+        return prompt.name.clone();
+    }
+    let arguments = prompt
+        .arguments
+        .iter()
+        .map(|argument| {
+            if argument.required {
+                argument.name.clone()
+            } else {
+                format!("{}?", argument.name)
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("{}({})", prompt.name, arguments.join(", "))
+}
+
+fn render_section_title(title: &'static str) -> impl IntoElement {
+    div()
+        .pt(px(4.0))
+        .text_size(px(Theme::font_size_small()))
+        .text_color(Theme::text_muted())
+        .child(title)
+}
+
+fn render_picker_row(
+    id: SharedString,
+    server: &str,
+    label: String,
+    detail: Option<String>,
+) -> gpui::Stateful<gpui::Div> {
+    div()
+        .id(id)
+        .flex()
+        .items_center()
+        .gap(px(6.0))
+        .px(px(4.0))
+        .rounded(px(Theme::RADIUS_MD))
+        .text_size(px(Theme::font_size_small()))
+        .cursor_pointer()
+        .hover(|s| s.bg(Theme::bg_dark()))
+        .child(
+            div()
+                .flex_shrink_0()
+                .text_color(Theme::text_muted())
+                .child(format!("[{server}]")),
+        )
+        .child(
+            div()
+                .flex_shrink_0()
+                .text_color(Theme::text_primary())
+                .child(label),
+        )
+        .when_some(detail, |d, detail| {
+            d.child(
+                div()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_color(Theme::text_secondary())
+                    .child(detail),
+            )
+        })
+}
+
+impl ChatView {
+    pub(super) fn toggle_mcp_picker(&mut self, cx: &mut gpui::Context<Self>) {
+        let picker = &mut self.mcp_picker;
+        picker.open = !picker.open;
+        picker.error = None;
+        if picker.open {
+            picker.loading = true;
+            self.emit(UserEvent::RefreshMcpCatalog);
+        }
+        cx.notify();
+    }
+
+    fn attach_mcp_resource(&mut self, resource: &McpResource, cx: &mut gpui::Context<Self>) {
+        self.mcp_picker.pending = Some(format!("Reading {}\u{2026}", resource.name));
+        self.mcp_picker.error = None;
+        self.emit(UserEvent::AttachMcpResource {
+            server: resource.server.clone(),
+            uri: resource.uri.clone(),
+        });
+        cx.notify();
+    }
+
+    fn pick_mcp_prompt(&mut self, prompt: &McpPrompt, cx: &mut gpui::Context<Self>) {
+        self.mcp_picker.error = None;
+        if prompt.arguments.is_empty() {
+            self.request_mcp_prompt(McpPromptInvocation {
+                server: prompt.server.clone(),
+                name: prompt.name.clone(),
+                ..McpPromptInvocation::default()
+            });
+        } else {
+            let input = &mut self.state.input_text;
+            if !input.is_empty() && !input.ends_with('\n') {
+                input.push('\n');
+            }
+            input.push_str(&McpPromptInvocation::template(prompt));
+            self.state.cursor_position = input.len();
+            self.mcp_picker.open = false;
+        }
+        cx.notify();
+    }
+
+    fn request_mcp_prompt(&mut self, invocation: McpPromptInvocation) {
+        self.mcp_picker.pending = Some(format!("Rendering {}\u{2026}", invocation.name));
+        self.emit(UserEvent::InsertMcpPrompt { invocation });
+    }
+
+    /// Run the last line of `text` as a `/prompt` command instead of
+    /// sending it, keeping the lines above it in the composer. Returns
+    /// `false` when it is not one.
+    pub(super) fn take_mcp_prompt_command(
+        &mut self,
+        text: &str,
+        cx: &mut gpui::Context<Self>,
+    ) -> bool {
+        let text = text.trim_end();
+        let (draft, command) = text.rsplit_once('\n').unwrap_or(("", text));
+        match McpPromptInvocation::parse(command) {
+            None => return false,
+            Some(Ok(invocation)) => {
+                self.state.input_text = if draft.trim().is_empty() {
+                    String::new()
+                } else {
+                    format!("{draft}\n")
+                };
+                self.state.cursor_position = self.state.input_text.len();
+                self.mcp_picker.error = None;
+                self.request_mcp_prompt(invocation);
+            }
+            Some(Err(error)) => self.mcp_picker.error = Some(error),
+        }
+        cx.notify();
+        true
+    }
+
+    /// Apply the MCP picker's commands.
+    pub(super) fn handle_mcp_picker_command(
+        &mut self,
+        cmd: ViewCommand,
+        cx: &mut gpui::Context<Self>,
+    ) {
+        let picker = &mut self.mcp_picker;
+        match cmd {
+            ViewCommand::McpCatalogLoaded { resources, prompts } => {
+                picker.loading = false;
+                picker.resources = resources;
+                picker.prompts = prompts;
+            }
+            ViewCommand::McpComposerTextReady { text } => {
+                picker.pending = None;
+                picker.open = false;
+                self.insert_composer_text(&text, cx);
+            }
+            ViewCommand::McpComposerTextFailed { error } => {
+                picker.pending = None;
+                picker.error = Some(error);
+            }
+            _ => return,
+        }
+        cx.notify();
+    }
+
+    /// Button beside the composer that opens the MCP picker.
+    pub(super) fn render_mcp_button(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let open = self.mcp_picker.open;
+        div()
+            .id("mcp-picker-btn")
+            .debug_selector(|| "chat-mcp-button".to_string())
+            .flex_shrink_0()
+            .min_h(px(36.0))
+            .px(px(Theme::SPACING_SM))
+            .py(px(Theme::SPACING_SM))
+            .rounded(px(Theme::RADIUS_MD))
+            .cursor_pointer()
+            .bg(Theme::bg_dark())
+            .text_color(if open {
+                Theme::accent()
+            } else {
+                Theme::text_secondary()
+            })
+            .hover(|s| s.text_color(Theme::text_primary()))
+            .child("MCP")
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _window, cx| this.toggle_mcp_picker(cx)),
+            )
+    }
+
+    fn render_mcp_picker_header(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let picker = &self.mcp_picker;
+        let status = picker
+            .pending
+            .clone()
+            .or_else(|| picker.loading.then(|| "Loading\u{2026}".to_string()));
+
+        div()
+            .flex()
+            .items_center()
+            .gap(px(10.0))
+            .text_size(px(Theme::font_size_small()))
+            .child(
+                div()
+                    .text_color(Theme::text_muted())
+                    .child("MCP resources and prompts"),
+            )
+            .when_some(status, |d, status| {
+                d.child(div().text_color(Theme::text_secondary()).child(status))
+            })
+            .child(
+                div()
+                    .id("mcp-picker-close")
+                    .ml_auto()
+                    .text_color(Theme::accent())
+                    .cursor_pointer()
+                    .hover(|s| s.text_color(Theme::accent_hover()))
+                    .child("Close")
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _window, cx| {
+                            this.mcp_picker.open = false;
+                            this.mcp_picker.error = None;
+                            cx.notify();
+                        }),
+                    ),
+            )
+    }
+
+    /// Render the picker listing the running servers' resources and prompts.
+    pub(super) fn render_mcp_picker(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let picker = &self.mcp_picker;
+        let is_empty = picker.resources.is_empty() && picker.prompts.is_empty();
+
+        let resource_rows = picker
+            .resources
+            .iter()
+            .enumerate()
+            .map(|(index, resource)| {
+                let picked = resource.clone();
+                render_picker_row(
+                    SharedString::from(format!("mcp-resource-{index}")),
+                    &resource.server,
+                    resource.name.clone(),
+                    Some(
+                        resource
+                            .description
+                            .clone()
+                            .unwrap_or_else(|| resource.uri.clone()),
+                    ),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| this.attach_mcp_resource(&picked, cx)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let prompt_rows = picker
+            .prompts
+            .iter()
+            .enumerate()
+            .map(|(index, prompt)| {
+                let picked = prompt.clone();
+                render_picker_row(
+                    SharedString::from(format!("mcp-prompt-{index}")),
+                    &prompt.server,
+                    prompt_label(prompt),
+                    prompt.description.clone(),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| this.pick_mcp_prompt(&picked, cx)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .id("chat-mcp-picker")
+            .flex_shrink_0()
+            .w_full()
+            .max_h(px(MAX_PICKER_HEIGHT))
+            .overflow_y_scroll()
+            .bg(Theme::bg_darker())
+            .px(px(12.0))
+            .py(px(6.0))
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .child(self.render_mcp_picker_header(cx))
+            .when_some(picker.error.clone(), |d, error| {
+                d.child(
+                    div()
+                        .text_size(px(Theme::font_size_small()))
+                        .text_color(Theme::error())
+                        .child(error),
+                )
+            })
+            .when(picker.open && !picker.loading && is_empty, |d| {
+                d.child(
+                    div()
+                        .text_size(px(Theme::font_size_small()))
+                        .text_color(Theme::text_secondary())
+                        .child("No running MCP server offers resources or prompts"),
+                )
+            })
+            .when(picker.open && !resource_rows.is_empty(), |d| {
+                d.child(render_section_title("Resources"))
+                    .children(resource_rows)
+            })
+            .when(picker.open && !prompt_rows.is_empty(), |d| {
+                d.child(render_section_title("Prompts"))
+                    .children(prompt_rows)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::future_not_send)]
+
+    use super::*;
+    use crate::mcp::McpPromptArgument;
+    use crate::ui_gpui::bridge::GpuiBridge;
+    use crate::ui_gpui::views::chat_view::{ChatState, StreamingState};
+    use gpui::{AppContext, TestAppContext};
+    use std::sync::Arc;
+
+    #[test]
+    fn prompt_labels_mark_optional_arguments() {
+        let argument = |name: &str, required| McpPromptArgument {
+            name: name.to_string(),
+            description: None,
+            required,
+        };
+        let prompt = McpPrompt {
+            server: "git".to_string(),
+            name: "review".to_string(),
+            description: None,
+            arguments: vec![argument("branch", true), argument("focus", false)],
+        };
+
+        assert_eq!(prompt_label(&prompt), "review(branch, focus?)");
+    }
+
+    #[gpui::test]
+    async fn a_prompt_command_line_is_rendered_into_the_composer(cx: &mut TestAppContext) {
+        let view = cx.new(|cx| ChatView::new(ChatState::default(), cx));
+        let mut visual_cx = cx.add_empty_window().clone();
+        let (user_tx, user_rx) = flume::bounded(8);
+        let (_view_tx, view_rx) = flume::bounded(8);
+        let bridge = Arc::new(GpuiBridge::new(user_tx, view_rx));
+
+        visual_cx.update(|_window, app| {
+            view.update(app, |view: &mut ChatView, cx| {
+                view.set_bridge(bridge.clone());
+                view.state.input_text = "Please check:\n/prompt git/review branch=main".to_string();
+                view.state.cursor_position = view.state.input_text.len();
+
+                view.handle_enter(cx);
+
+                let Ok(UserEvent::InsertMcpPrompt { invocation }) = user_rx.try_recv() else {
+                    panic!("expected an InsertMcpPrompt event");
+                };
+                assert_eq!(
+                    (invocation.server.as_str(), invocation.name.as_str()),
+                    ("git", "review")
+                );
+                assert_eq!(invocation.arguments["branch"], "main");
+                assert_eq!(view.state.input_text, "Please check:\n");
+                assert_eq!(view.state.streaming, StreamingState::Idle);
+
+                view.handle_command(
+                    ViewCommand::McpComposerTextReady {
+                        text: "Review main".to_string(),
+                    },
+                    cx,
+                );
+                assert_eq!(view.state.input_text, "Please check:\nReview main");
+                assert!(!view.mcp_picker.is_visible());
+            });
+        });
+    }
+}
@@ -0,0 +1,102 @@
+//! Background process UI: the strip above the input bar listing the
+//! processes `ShellExec` started in the background, with a "Stop" link on
+//! each running one.
+//!
+//! Process state arrives through `BackgroundProcessesUpdated` (see
+//! `command.rs`). Stopping emits `KillBackgroundProcess`; the registry then
+//! publishes the killed state itself.
+
+use super::ChatView;
+use crate::events::types::UserEvent;
+use crate::presentation::view_command::{BackgroundProcessState, BackgroundProcessSummary};
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+
+/// Longest command shown in the strip before it is cut off.
+const MAX_COMMAND_CHARS: usize = 40;
+
+fn process_label(process: &BackgroundProcessSummary) -> String {
+    let mut command: String = process.command.chars().take(MAX_COMMAND_CHARS).collect();
+    if command.len() < process.command.len() {
+        command.push('\u{2026}');
+    }
+    let state = match process.state {
+        BackgroundProcessState::Running => "running".to_string(),
+        BackgroundProcessState::Exited { code: Some(code) } => format!("exit {code}"),
+        BackgroundProcessState::Exited { code: None } => "exited".to_string(),
+        BackgroundProcessState::Killed => "stopped".to_string(),
+    };
+    format!("{}: {command} ({state})", process.id)
+}
+
+impl ChatView {
+    fn kill_background_process(&mut self, process_id: String, cx: &mut gpui::Context<Self>) {
+        let Some(conversation_id) = self.state.active_conversation_id else {
+            return;
+        };
+        self.emit(UserEvent::KillBackgroundProcess {
+            conversation_id,
+            process_id,
+        });
+        cx.notify();
+    }
+
+    /// Render the row of background processes for the active conversation.
+    pub(super) fn render_process_strip(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let processes = self.state.active_background_processes().to_vec();
+
+        div()
+            .id("chat-process-strip")
+            .flex_shrink_0()
+            .h(px(24.0))
+            .w_full()
+            .bg(Theme::bg_darker())
+            .px(px(12.0))
+            .flex()
+            .items_center()
+            .gap(px(10.0))
+            .overflow_x_scroll()
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_size(px(Theme::font_size_small()))
+                    .text_color(Theme::text_muted())
+                    .child("Processes:"),
+            )
+            .children(processes.into_iter().map(|process| {
+                let is_running = process.state == BackgroundProcessState::Running;
+                let process_id = process.id.clone();
+                div()
+                    .flex_shrink_0()
+                    .flex()
+                    .items_center()
+                    .gap(px(4.0))
+                    .text_size(px(Theme::font_size_small()))
+                    .child(
+                        div()
+                            .text_color(if is_running {
+                                Theme::text_primary()
+                            } else {
+                                Theme::text_muted()
+                            })
+                            .child(process_label(&process)),
+                    )
+                    .when(is_running, |d| {
+                        d.child(
+                            div()
+                                .id(SharedString::from(format!("stop-{}", process.id)))
+                                .text_color(Theme::accent())
+                                .cursor_pointer()
+                                .hover(|s| s.text_color(Theme::accent_hover()))
+                                .child("Stop")
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |this, _, _window, cx| {
+                                        this.kill_background_process(process_id.clone(), cx);
+                                    }),
+                                ),
+                        )
+                    })
+            }))
+    }
+}
@@ -0,0 +1,70 @@
+//! Sub-agent UI: the strip above the input bar showing each running
+//! `Delegate` sub-agent with its turn count and latest tool.
+//!
+//! Progress arrives through `SubAgentUpdated` (see `command.rs`); a
+//! sub-agent leaves the strip once it finishes, fails or is stopped.
+
+use super::ChatView;
+use crate::presentation::view_command::SubAgentSummary;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px};
+
+/// Longest description shown in the strip before it is cut off.
+const MAX_DESCRIPTION_CHARS: usize = 40;
+
+fn sub_agent_label(agent: &SubAgentSummary) -> String {
+    let mut description: String = agent
+        .description
+        .chars()
+        .take(MAX_DESCRIPTION_CHARS)
+        .collect();
+    if description.len() < agent.description.len() {
+        description.push('\u{2026}');
+    }
+    let tools = match agent.tool_calls {
+        1 => "1 tool".to_string(),
+        count => format!("{count} tools"),
+    };
+    let mut label = format!(
+        "{description} \u{b7} {} \u{b7} turn {}/{} \u{b7} {tools}",
+        agent.profile_name, agent.turns, agent.max_turns
+    );
+    if let Some(tool) = &agent.last_tool {
+        label.push_str(" \u{b7} ");
+        label.push_str(tool);
+    }
+    label
+}
+
+impl ChatView {
+    /// Render the row of running sub-agents for the active conversation.
+    pub(super) fn render_sub_agent_strip(&self) -> impl IntoElement {
+        let agents = self.state.active_sub_agents();
+
+        div()
+            .id("chat-sub-agent-strip")
+            .flex_shrink_0()
+            .h(px(24.0))
+            .w_full()
+            .bg(Theme::bg_darker())
+            .px(px(12.0))
+            .flex()
+            .items_center()
+            .gap(px(10.0))
+            .overflow_x_scroll()
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_size(px(Theme::font_size_small()))
+                    .text_color(Theme::text_muted())
+                    .child("Sub-agents:"),
+            )
+            .children(agents.iter().map(|agent| {
+                div()
+                    .flex_shrink_0()
+                    .text_size(px(Theme::font_size_small()))
+                    .text_color(Theme::text_primary())
+                    .child(sub_agent_label(agent))
+            }))
+    }
+}
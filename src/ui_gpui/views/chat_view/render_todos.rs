@@ -0,0 +1,75 @@
+//! Task list UI: the checklist above the input bar showing the agent's
+//! `TodoWrite` plan for the active conversation.
+//!
+//! Lists arrive through `TodoListUpdated` (see `command.rs`), both while the
+//! agent works and when a conversation is selected. The checklist is hidden
+//! once every item is completed.
+
+use super::ChatView;
+use crate::models::{TodoItem, TodoStatus};
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px};
+
+/// Tallest the checklist grows before it scrolls.
+const MAX_CHECKLIST_HEIGHT: f32 = 120.0;
+
+const fn status_mark(status: TodoStatus) -> &'static str {
+    match status {
+        TodoStatus::Pending => "\u{25CB}",
+        TodoStatus::InProgress => "\u{25D0}",
+        TodoStatus::Completed => "\u{2713}",
+    }
+}
+
+fn render_todo_row(todo: &TodoItem) -> impl IntoElement {
+    let (mark_color, text_color) = match todo.status {
+        TodoStatus::Pending => (Theme::text_muted(), Theme::text_primary()),
+        TodoStatus::InProgress => (Theme::accent(), Theme::text_primary()),
+        TodoStatus::Completed => (Theme::success(), Theme::text_muted()),
+    };
+
+    div()
+        .flex()
+        .items_center()
+        .gap(px(6.0))
+        .text_size(px(Theme::font_size_small()))
+        .child(
+            div()
+                .flex_shrink_0()
+                .w(px(12.0))
+                .text_color(mark_color)
+                .child(status_mark(todo.status)),
+        )
+        .child(div().text_color(text_color).child(todo.content.clone()))
+}
+
+impl ChatView {
+    /// Render the active conversation's task list.
+    pub(super) fn render_todo_panel(&self) -> impl IntoElement {
+        let todos = self.state.active_todo_list().unwrap_or_default();
+        let done = todos
+            .iter()
+            .filter(|todo| todo.status == TodoStatus::Completed)
+            .count();
+
+        div()
+            .id("chat-todo-panel")
+            .flex_shrink_0()
+            .w_full()
+            .max_h(px(MAX_CHECKLIST_HEIGHT))
+            .overflow_y_scroll()
+            .bg(Theme::bg_darker())
+            .px(px(12.0))
+            .py(px(6.0))
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .child(
+                div()
+                    .text_size(px(Theme::font_size_small()))
+                    .text_color(Theme::text_muted())
+                    .child(format!("Tasks ({done}/{} done)", todos.len())),
+            )
+            .children(todos.iter().map(render_todo_row))
+    }
+}
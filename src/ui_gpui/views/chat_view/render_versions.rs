@@ -0,0 +1,265 @@
+//! Message version UI: the per-message action row (edit, regenerate, branch),
+//! the "‹ n/m ›" version switcher, and the composer's edit banner.
+//!
+//! Version state arrives through `MessageVersionsLoaded` (see `command.rs`).
+//! Editing a prompt loads it into the composer; the next send goes out as
+//! `EditAndResend` instead of `SendMessage` (see `send_message_and_start_streaming`).
+
+use super::state::StreamingState;
+use super::ChatView;
+use crate::events::types::UserEvent;
+use crate::presentation::view_command::MessageVersionSummary;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+use uuid::Uuid;
+
+/// Version numbers reachable from the switcher's previous/next arrows.
+fn neighbour_versions(version: MessageVersionSummary) -> (Option<u32>, Option<u32>) {
+    match version.selected {
+        Some(selected) => (
+            selected.checked_sub(1),
+            Some(selected + 1).filter(|next| *next < version.count),
+        ),
+        None => (version.count.checked_sub(1), None),
+    }
+}
+
+impl ChatView {
+    /// Conversation a rewrite action may target: the active one, while idle.
+    fn rewritable_conversation(&self) -> Option<Uuid> {
+        if matches!(self.state.streaming, StreamingState::Streaming { .. }) {
+            return None;
+        }
+        self.state.active_conversation_id
+    }
+
+    /// Load the prompt at visible `index` into the composer for editing.
+    pub(super) fn start_message_edit(&mut self, index: usize, cx: &mut gpui::Context<Self>) {
+        if self.rewritable_conversation().is_none() {
+            return;
+        }
+        let Some(content) = self
+            .state
+            .messages
+            .get(index)
+            .map(|message| message.content.as_str().to_string())
+        else {
+            return;
+        };
+        self.state.input_text = content;
+        self.state.cursor_position = self.state.input_text.len();
+        self.state.editing_message_index = Some(index);
+        self.focus_composer(cx);
+        cx.notify();
+    }
+
+    /// Leave edit mode and clear the composer.
+    pub(super) fn cancel_message_edit(&mut self, cx: &mut gpui::Context<Self>) {
+        if self.state.editing_message_index.take().is_some() {
+            self.state.input_text.clear();
+            self.state.cursor_position = 0;
+            cx.notify();
+        }
+    }
+
+    /// Ask for a new response at visible `index`.
+    fn regenerate_at(&mut self, index: usize, cx: &mut gpui::Context<Self>) {
+        let Some(conversation_id) = self.rewritable_conversation() else {
+            return;
+        };
+        self.state.editing_message_index = None;
+        self.emit(UserEvent::RegenerateResponse {
+            conversation_id,
+            message_index: index,
+        });
+        self.start_local_streaming(cx);
+    }
+
+    fn switch_version_at(&mut self, index: usize, version: u32, cx: &mut gpui::Context<Self>) {
+        let Some(conversation_id) = self.rewritable_conversation() else {
+            return;
+        };
+        self.state.editing_message_index = None;
+        self.emit(UserEvent::SwitchMessageVersion {
+            conversation_id,
+            message_index: index,
+            version,
+        });
+        cx.notify();
+    }
+
+    /// Action row under a finalized message.
+    pub(super) fn render_message_actions(
+        &self,
+        index: usize,
+        is_user: bool,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let version = self.state.message_version(index);
+        let (label, prefix) = if is_user {
+            ("Edit", "edit")
+        } else {
+            ("Regenerate", "regenerate")
+        };
+
+        div()
+            .w_full()
+            .flex()
+            .gap(px(10.0))
+            .when(is_user, gpui::Styled::justify_end)
+            .when_some(version, |d, version| {
+                d.child(Self::render_version_switcher(index, version, cx))
+            })
+            .child(
+                Self::action_link(SharedString::from(format!("{prefix}-{index}")), label)
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _window, cx| {
+                            if is_user {
+                                this.start_message_edit(index, cx);
+                            } else {
+                                this.regenerate_at(index, cx);
+                            }
+                        }),
+                    ),
+            )
+            .child(Self::render_branch_link(index, cx))
+    }
+
+    /// Row after a trailing prompt that has no reply (e.g. the request
+    /// failed), offering to retry it.
+    pub(super) fn render_retry_row(
+        &self,
+        index: usize,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let version = self.state.message_version(index);
+
+        div()
+            .w_full()
+            .flex()
+            .gap(px(10.0))
+            .when_some(version, |d, version| {
+                d.child(Self::render_version_switcher(index, version, cx))
+            })
+            .child(
+                Self::action_link(SharedString::from("retry-response"), "Regenerate response")
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _window, cx| {
+                            this.regenerate_at(index, cx);
+                        }),
+                    ),
+            )
+    }
+
+    fn render_version_switcher(
+        index: usize,
+        version: MessageVersionSummary,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let (previous, next) = neighbour_versions(version);
+        let position = version.selected.map_or_else(
+            || "\u{2013}".to_string(),
+            |selected| (selected + 1).to_string(),
+        );
+
+        div()
+            .flex()
+            .items_center()
+            .gap(px(4.0))
+            .text_size(px(Theme::font_size_small()))
+            .text_color(Theme::text_muted())
+            .child(Self::version_arrow(index, "\u{2039}", "prev", previous, cx))
+            .child(format!("{position}/{}", version.count))
+            .child(Self::version_arrow(index, "\u{203a}", "next", next, cx))
+    }
+
+    fn version_arrow(
+        index: usize,
+        glyph: &'static str,
+        direction: &'static str,
+        target: Option<u32>,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(SharedString::from(format!("version-{direction}-{index}")))
+            .px(px(2.0))
+            .child(glyph)
+            .when_some(target, |d, version| {
+                d.text_color(Theme::text_primary())
+                    .cursor_pointer()
+                    .hover(|s| s.text_color(Theme::accent()))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _window, cx| {
+                            this.switch_version_at(index, version, cx);
+                        }),
+                    )
+            })
+    }
+
+    /// Banner above the composer while a prompt is being edited.
+    pub(super) fn render_edit_banner(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        div()
+            .id("chat-edit-banner")
+            .w_full()
+            .flex()
+            .justify_between()
+            .px(px(Theme::SPACING_MD))
+            .py(px(4.0))
+            .bg(Theme::bg_darker())
+            .text_size(px(Theme::font_size_small()))
+            .text_color(Theme::text_secondary())
+            .child("Editing message \u{2014} the original is kept as a version")
+            .child(
+                Self::action_link(SharedString::from("cancel-edit"), "Cancel").on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, _window, cx| {
+                        this.cancel_message_edit(cx);
+                    }),
+                ),
+            )
+    }
+
+    pub(super) fn action_link(id: SharedString, label: &'static str) -> gpui::Stateful<gpui::Div> {
+        div()
+            .id(id)
+            .text_size(px(Theme::font_size_small()))
+            .text_color(Theme::text_muted())
+            .cursor_pointer()
+            .hover(|s| s.text_color(Theme::accent()))
+            .child(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours_stay_within_version_range() {
+        let first = MessageVersionSummary {
+            message_index: 1,
+            selected: Some(0),
+            count: 3,
+        };
+        assert_eq!(neighbour_versions(first), (None, Some(1)));
+
+        let last = MessageVersionSummary {
+            selected: Some(2),
+            ..first
+        };
+        assert_eq!(neighbour_versions(last), (Some(1), None));
+    }
+
+    #[test]
+    fn empty_live_tail_steps_back_to_last_archived_version() {
+        let version = MessageVersionSummary {
+            message_index: 2,
+            selected: None,
+            count: 2,
+        };
+        assert_eq!(neighbour_versions(version), (Some(1), None));
+    }
+}
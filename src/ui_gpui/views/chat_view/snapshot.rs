@@ -9,6 +9,7 @@ use super::state::{ChatMessage, StreamingState};
 use super::{save_draft, take_draft, ChatView};
 use crate::presentation::view_command::ConversationMessagePayload;
 use crate::ui_gpui::app_store::{ChatStoreSnapshot, ConversationLoadState, StreamingStoreSnapshot};
+use crate::ui_gpui::selection_intent_channel;
 
 impl ChatView {
     const fn reset_autoscroll_if_needed(&mut self, should_reset_autoscroll: bool) {
@@ -134,6 +135,9 @@ impl ChatView {
         let previous_messages_empty = self.state.messages.is_empty();
 
         self.carry_draft_across_selection(previous_conversation_id, selected_conversation_id);
+        if previous_conversation_id != selected_conversation_id {
+            self.state.search_jump_index = None;
+        }
 
         self.state.conversations = conversations;
         self.state.active_conversation_id = selected_conversation_id;
@@ -187,12 +191,33 @@ impl ChatView {
         {
             self.maybe_scroll_chat_to_bottom(cx);
         }
+        self.apply_pending_message_jump(cx);
 
         self.sync_conversation_list_state(cx);
 
         cx.notify();
     }
 
+    /// Scroll to the message a search result pointed at, once it is loaded.
+    ///
+    /// Runs after any scroll-to-bottom above so its deferred scroll wins, and
+    /// pauses autoscroll so the jump target stays in view.
+    fn apply_pending_message_jump(&mut self, cx: &mut gpui::Context<Self>) {
+        let Some(conversation_id) = self.conversation_id else {
+            return;
+        };
+        let Some(index) = selection_intent_channel()
+            .take_message_jump(conversation_id, self.state.messages.len())
+        else {
+            return;
+        };
+        self.state.chat_autoscroll_enabled = false;
+        self.state.search_jump_index = Some(index);
+        self.chat_scroll_handle.scroll_to_item(index);
+        let scroll_handle = self.chat_scroll_handle.clone();
+        cx.defer(move |_| scroll_handle.scroll_to_item(index));
+    }
+
     /// Keep composer drafts attached to their conversation across a selection change.
     ///
     /// The outgoing conversation's text is stashed and the incoming one's is restored (or
@@ -212,6 +237,8 @@ impl ChatView {
             return;
         }
 
+        // An in-progress prompt edit belongs to the outgoing conversation.
+        self.state.editing_message_index = None;
         if let Some(prev_id) = previous_conversation_id {
             save_draft(prev_id, &self.state.input_text);
         }
@@ -27,6 +27,8 @@ fn ready_snapshot_for(conversation_id: Uuid, title: &str) -> ChatStoreSnapshot {
             updated_at: Utc::now(),
             message_count: 0,
             preview: None,
+            parent_id: None,
+            organization: crate::models::ConversationOrganization::default(),
         }],
     }
 }
@@ -5,12 +5,16 @@
 //!
 //! @plan PLAN-20260325-ISSUE11B.P02
 
-use crate::models::ConversationExportFormat;
+use crate::models::{
+    ConversationExportFormat, ConversationFilter, TodoItem, TodoStatus, UsageTotals,
+};
 use crate::presentation::view_command::{
-    ConversationSearchResult, ConversationSummary, ProfileSummary, ToolApprovalContext,
-    ToolCategory,
+    BackgroundProcessSummary, ConversationBranchSummary, ConversationSearchResult,
+    ConversationSummary, MessageVersionSummary, ProfileSummary, SubAgentState, SubAgentSummary,
+    ToolApprovalContext, ToolCategory, TurnCheckpointSummary,
 };
 use crate::ui_gpui::components::markdown_content::{parse_markdown_blocks, MarkdownBlock};
+use crate::ui_gpui::views::conversation_list::groups::{display_order, group_conversations};
 use std::cell::OnceCell;
 use std::collections::HashMap;
 use std::ops::Range;
@@ -160,6 +164,8 @@ pub struct ToolApprovalBubble {
     pub grouped_operations: Vec<GroupedOperation>,
     /// Whether the grouped operations list is expanded
     pub expanded: bool,
+    /// Whether a long diff preview is shown in full
+    pub diff_expanded: bool,
 }
 
 /// Represents a single operation within a grouped approval bubble.
@@ -183,6 +189,7 @@ impl ToolApprovalBubble {
             group_key,
             grouped_operations: Vec::new(),
             expanded: false,
+            diff_expanded: false,
         }
     }
 
@@ -204,6 +211,17 @@ impl ToolApprovalBubble {
         });
     }
 
+    /// Append a grouped operation's diff preview after the existing one.
+    pub fn append_diff(&mut self, diff: Option<String>) {
+        let Some(diff) = diff else {
+            return;
+        };
+        self.context.diff = Some(match self.context.diff.take() {
+            Some(existing) => format!("{existing}\n{diff}"),
+            None => diff,
+        });
+    }
+
     /// Get the total number of operations in this bubble.
     #[must_use]
     pub const fn operation_count(&self) -> usize {
@@ -247,6 +265,8 @@ pub struct ChatState {
     pub approval_bubbles: HashMap<Uuid, Vec<ToolApprovalBubble>>,
     /// Whether YOLO mode (auto-approve all) is currently active.
     pub yolo_mode: bool,
+    /// Profile and conversation approval overrides layered over the global policy.
+    pub approval_overrides: crate::agent::policy_overrides::PolicyOverrides,
     /// Whether the sidebar is visible (popout mode only).
     pub sidebar_visible: bool,
     /// Current search query typed in the sidebar search box.
@@ -268,6 +288,30 @@ pub struct ChatState {
     /// @plan PLAN-20260416-ISSUE173.P11
     /// @requirement REQ-173-004.3
     pub streaming_conversation_ids: std::collections::HashSet<Uuid>,
+    /// Conversation whose branch tree is held in `conversation_branches`.
+    pub branches_conversation_id: Option<Uuid>,
+    /// Branch tree containing `branches_conversation_id`, root first.
+    pub conversation_branches: Vec<ConversationBranchSummary>,
+    /// Conversation whose version switchers are held in `message_versions`.
+    pub message_versions_conversation_id: Option<Uuid>,
+    /// Messages with alternate versions, by visible index.
+    pub message_versions: Vec<MessageVersionSummary>,
+    /// Conversation whose revertible turns are held in `turn_checkpoints`.
+    pub turn_checkpoints_conversation_id: Option<Uuid>,
+    /// Turns with file changes that can be reverted, by visible index.
+    pub turn_checkpoints: Vec<TurnCheckpointSummary>,
+    /// Visible index of the prompt being edited in the composer, if any.
+    pub editing_message_index: Option<usize>,
+    /// Visible index of the message a search result jumped to, if any.
+    pub search_jump_index: Option<usize>,
+    /// Recorded token usage and spend, and the conversation it belongs to.
+    pub conversation_usage: Option<(Uuid, UsageTotals)>,
+    /// Background processes started by `ShellExec`, by conversation.
+    pub background_processes: HashMap<Uuid, Vec<BackgroundProcessSummary>>,
+    /// Running `Delegate` sub-agents, by conversation.
+    pub sub_agents: HashMap<Uuid, Vec<SubAgentSummary>>,
+    /// `TodoWrite` task lists, by conversation.
+    pub todo_lists: HashMap<Uuid, Vec<TodoItem>>,
 }
 
 impl Default for ChatState {
@@ -278,7 +322,6 @@ impl Default for ChatState {
             show_thinking: false,
             thinking_content: None,
             composer_focused: false,
-
             input_text: String::new(),
             cursor_position: 0,
             conversation_title: "New Conversation".to_string(),
@@ -293,6 +336,7 @@ impl Default for ChatState {
             marked_range: None,
             approval_bubbles: HashMap::new(),
             yolo_mode: false,
+            approval_overrides: crate::agent::policy_overrides::PolicyOverrides::default(),
             sidebar_visible: true,
             sidebar_search_query: String::new(),
             sidebar_search_results: None,
@@ -307,6 +351,18 @@ impl Default for ChatState {
             export_feedback_path: None,
             filter_emoji: false,
             streaming_conversation_ids: std::collections::HashSet::new(),
+            branches_conversation_id: None,
+            conversation_branches: Vec::new(),
+            message_versions_conversation_id: None,
+            message_versions: Vec::new(),
+            turn_checkpoints_conversation_id: None,
+            turn_checkpoints: Vec::new(),
+            editing_message_index: None,
+            search_jump_index: None,
+            conversation_usage: None,
+            background_processes: HashMap::new(),
+            sub_agents: HashMap::new(),
+            todo_lists: HashMap::new(),
         }
     }
 }
@@ -381,16 +437,116 @@ impl ChatState {
         );
     }
 
+    /// Conversation ids in the order the dropdown lists them: pinned first,
+    /// archived hidden unless active.
+    pub(super) fn dropdown_conversation_ids(&self) -> Vec<Uuid> {
+        display_order(&group_conversations(
+            &self.conversations,
+            &ConversationFilter::default(),
+            false,
+            self.active_conversation_id,
+        ))
+    }
+
     pub(super) fn sync_conversation_dropdown_index(&mut self) {
+        let order = self.dropdown_conversation_ids();
         self.conversation_dropdown_index = self
             .active_conversation_id
-            .and_then(|id| {
-                self.conversations
+            .and_then(|id| order.iter().position(|candidate| *candidate == id))
+            .unwrap_or(0)
+            .min(order.len().saturating_sub(1));
+    }
+
+    /// Branch tree for the active conversation, empty when it has no branches.
+    pub(super) fn active_branches(&self) -> &[ConversationBranchSummary] {
+        if self.active_conversation_id.is_some()
+            && self.branches_conversation_id == self.active_conversation_id
+            && self.conversation_branches.len() > 1
+        {
+            &self.conversation_branches
+        } else {
+            &[]
+        }
+    }
+
+    /// Usage totals for the active conversation, once it has any turns.
+    pub(super) fn active_conversation_usage(&self) -> Option<&UsageTotals> {
+        self.conversation_usage
+            .as_ref()
+            .filter(|(id, totals)| Some(*id) == self.active_conversation_id && totals.turns > 0)
+            .map(|(_, totals)| totals)
+    }
+
+    /// Background processes of the active conversation, oldest first.
+    pub(super) fn active_background_processes(&self) -> &[BackgroundProcessSummary] {
+        self.active_conversation_id
+            .and_then(|id| self.background_processes.get(&id))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Record a sub-agent's progress; sub-agents that stopped running drop
+    /// out of the list.
+    pub(super) fn update_sub_agent(&mut self, conversation_id: Uuid, agent: SubAgentSummary) {
+        let agents = self.sub_agents.entry(conversation_id).or_default();
+        let existing = agents.iter().position(|known| known.id == agent.id);
+        match (existing, agent.state == SubAgentState::Running) {
+            (Some(index), true) => agents[index] = agent,
+            (Some(index), false) => {
+                agents.remove(index);
+            }
+            (None, true) => agents.push(agent),
+            (None, false) => {}
+        }
+        if agents.is_empty() {
+            self.sub_agents.remove(&conversation_id);
+        }
+    }
+
+    /// Running sub-agents of the active conversation, oldest first.
+    pub(super) fn active_sub_agents(&self) -> &[SubAgentSummary] {
+        self.active_conversation_id
+            .and_then(|id| self.sub_agents.get(&id))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Task list of the active conversation while any of it is unfinished.
+    pub(super) fn active_todo_list(&self) -> Option<&[TodoItem]> {
+        self.active_conversation_id
+            .and_then(|id| self.todo_lists.get(&id))
+            .filter(|todos| {
+                todos
                     .iter()
-                    .position(|conversation| conversation.id == id)
+                    .any(|todo| todo.status != TodoStatus::Completed)
             })
-            .unwrap_or(0)
-            .min(self.conversations.len().saturating_sub(1));
+            .map(Vec::as_slice)
+    }
+
+    /// Version switcher state for the visible message `index`, if it has
+    /// alternate versions in the active conversation.
+    pub(super) fn message_version(&self, index: usize) -> Option<MessageVersionSummary> {
+        if self.active_conversation_id.is_none()
+            || self.message_versions_conversation_id != self.active_conversation_id
+        {
+            return None;
+        }
+        self.message_versions
+            .iter()
+            .find(|version| version.message_index == index)
+            .copied()
+    }
+
+    /// Revertible turns anchored at visible `index`.
+    pub(super) fn turn_checkpoints_at(&self, index: usize) -> Vec<TurnCheckpointSummary> {
+        if self.active_conversation_id.is_none()
+            || self.turn_checkpoints_conversation_id != self.active_conversation_id
+        {
+            return Vec::new();
+        }
+        self.turn_checkpoints
+            .iter()
+            .filter(|checkpoint| checkpoint.message_index == index)
+            .cloned()
+            .collect()
     }
 
     pub(super) fn selected_profile(&self) -> Option<&ProfileSummary> {
@@ -458,6 +614,76 @@ mod tests {
         assert!(state.export_feedback_path.is_none());
     }
 
+    #[test]
+    fn active_background_processes_follow_the_active_conversation() {
+        let conversation_id = Uuid::new_v4();
+        let mut state = ChatState::default();
+        state.background_processes.insert(
+            conversation_id,
+            vec![BackgroundProcessSummary {
+                id: "proc-1".to_string(),
+                command: "npm run dev".to_string(),
+                state: crate::presentation::view_command::BackgroundProcessState::Running,
+            }],
+        );
+
+        assert!(state.active_background_processes().is_empty());
+        state.active_conversation_id = Some(conversation_id);
+        assert_eq!(state.active_background_processes().len(), 1);
+        state.active_conversation_id = Some(Uuid::new_v4());
+        assert!(state.active_background_processes().is_empty());
+    }
+
+    #[test]
+    fn sub_agents_are_listed_while_running() {
+        let conversation_id = Uuid::new_v4();
+        let mut state = ChatState {
+            active_conversation_id: Some(conversation_id),
+            ..ChatState::default()
+        };
+        let mut agent = SubAgentSummary {
+            id: Uuid::new_v4(),
+            description: "Find callers".to_string(),
+            profile_name: "Default".to_string(),
+            state: SubAgentState::Running,
+            turns: 1,
+            max_turns: 10,
+            tool_calls: 0,
+            last_tool: None,
+        };
+
+        state.update_sub_agent(conversation_id, agent.clone());
+        agent.tool_calls = 2;
+        state.update_sub_agent(conversation_id, agent.clone());
+        assert_eq!(state.active_sub_agents().len(), 1);
+        assert_eq!(state.active_sub_agents()[0].tool_calls, 2);
+
+        agent.state = SubAgentState::Finished;
+        state.update_sub_agent(conversation_id, agent);
+        assert!(state.active_sub_agents().is_empty());
+        assert!(state.sub_agents.is_empty());
+    }
+
+    #[test]
+    fn todo_list_is_shown_until_every_item_is_completed() {
+        let conversation_id = Uuid::new_v4();
+        let mut state = ChatState {
+            active_conversation_id: Some(conversation_id),
+            ..ChatState::default()
+        };
+        let mut todos = vec![
+            TodoItem::new("Read the parser", TodoStatus::Completed),
+            TodoItem::new("Fix the bug", TodoStatus::InProgress),
+        ];
+
+        state.todo_lists.insert(conversation_id, todos.clone());
+        assert_eq!(state.active_todo_list().map(<[TodoItem]>::len), Some(2));
+
+        todos[1].status = TodoStatus::Completed;
+        state.todo_lists.insert(conversation_id, todos);
+        assert!(state.active_todo_list().is_none());
+    }
+
     #[test]
     fn chat_state_builder_chains() {
         let state = ChatState::new()
@@ -746,6 +972,22 @@ mod tests {
         assert_eq!(bubble.grouped_operations.len(), 1);
     }
 
+    #[test]
+    fn tool_approval_bubble_append_diff_joins_grouped_diffs() {
+        let mut bubble = ToolApprovalBubble::new(
+            "req-1",
+            ToolApprovalContext::new("EditFile", ToolCategory::FileEdit, "/tmp/main.rs"),
+        );
+        bubble.append_diff(Some("@@ -1 +1 @@\n-a\n+b".to_string()));
+        bubble.append_diff(None);
+        bubble.append_diff(Some("@@ -9 +9 @@\n-c\n+d".to_string()));
+
+        assert_eq!(
+            bubble.context.diff.as_deref(),
+            Some("@@ -1 +1 @@\n-a\n+b\n@@ -9 +9 @@\n-c\n+d")
+        );
+    }
+
     #[test]
     fn grouped_operation_creation() {
         let op = GroupedOperation {
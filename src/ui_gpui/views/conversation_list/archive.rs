@@ -0,0 +1,163 @@
+//! "Export all / export shown" row of the conversation list.
+//!
+//! Either action writes one archive (zip or JSON Lines) with a manifest into
+//! the export directory; "shown" means the conversations currently visible
+//! after search and filters.
+
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+use uuid::Uuid;
+
+use super::groups::{display_order, group_conversations};
+use super::{ConversationListState, ConversationListView};
+use crate::events::types::UserEvent;
+use crate::ui_gpui::theme::Theme;
+
+/// Ids of the conversations the list currently shows, in display order.
+pub(super) fn shown_conversation_ids(state: &ConversationListState) -> Vec<Uuid> {
+    state.sidebar_search_results.as_ref().map_or_else(
+        || {
+            display_order(&group_conversations(
+                &state.conversations,
+                &state.filter,
+                state.group_by_folder,
+                state.active_conversation_id,
+            ))
+        },
+        |results| results.iter().map(|result| result.id).collect(),
+    )
+}
+
+impl ConversationListView {
+    /// Show the outcome of an archive export.
+    pub fn apply_archive_status(&mut self, status: String, cx: &mut gpui::Context<Self>) {
+        self.state.archive_in_progress = false;
+        self.state.archive_status = Some(status);
+        cx.notify();
+    }
+
+    fn export_archive(&mut self, ids: Option<Vec<Uuid>>, cx: &mut gpui::Context<Self>) {
+        if self.state.archive_in_progress {
+            return;
+        }
+        self.state.archive_in_progress = true;
+        self.state.archive_status = None;
+        self.emit(&UserEvent::ExportConversations {
+            ids,
+            archive: self.state.archive_format,
+        });
+        cx.notify();
+    }
+
+    pub(super) fn render_archive_row(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let shown = shown_conversation_ids(&self.state).len();
+        let status = if self.state.archive_in_progress {
+            Some("Exporting...".to_string())
+        } else {
+            self.state.archive_status.clone()
+        };
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap(px(8.0))
+            .text_size(px(10.0))
+            .child(div().text_color(Theme::text_secondary()).child("EXPORT"))
+            .child(
+                archive_link("conv-export-all", "All".to_string()).on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, _window, cx| this.export_archive(None, cx)),
+                ),
+            )
+            .when(shown > 0, |row| {
+                row.child(
+                    archive_link("conv-export-shown", format!("Shown ({shown})")).on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _window, cx| {
+                            let ids = shown_conversation_ids(&this.state);
+                            this.export_archive(Some(ids), cx);
+                        }),
+                    ),
+                )
+            })
+            .child(
+                archive_link(
+                    "conv-export-format",
+                    self.state.archive_format.display_label().to_string(),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, _window, cx| {
+                        this.state.archive_format = this.state.archive_format.next();
+                        cx.notify();
+                    }),
+                ),
+            )
+            .when_some(status, |row, status| {
+                row.child(
+                    div()
+                        .w_full()
+                        .text_color(Theme::text_muted())
+                        .child(SharedString::from(status)),
+                )
+            })
+    }
+}
+
+fn archive_link(id: &'static str, label: String) -> gpui::Stateful<gpui::Div> {
+    div()
+        .id(id)
+        .text_color(Theme::selection_fg())
+        .cursor_pointer()
+        .hover(|s| s.text_color(Theme::accent()))
+        .child(SharedString::from(label))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::{ArchiveFilter, ConversationOrganization};
+    use crate::presentation::view_command::{ConversationSearchResult, ConversationSummary};
+
+    fn summary(archived: bool) -> ConversationSummary {
+        ConversationSummary {
+            id: Uuid::new_v4(),
+            title: "t".to_string(),
+            updated_at: Utc::now(),
+            message_count: 1,
+            preview: None,
+            parent_id: None,
+            organization: ConversationOrganization {
+                archived,
+                ..ConversationOrganization::default()
+            },
+        }
+    }
+
+    #[test]
+    fn shown_ids_follow_filter_then_search_results() {
+        let visible = summary(false);
+        let archived = summary(true);
+        let mut state = ConversationListState::new()
+            .with_conversations(vec![visible.clone(), archived.clone()]);
+        assert_eq!(shown_conversation_ids(&state), vec![visible.id]);
+
+        state.filter.archived = ArchiveFilter::Only;
+        assert_eq!(shown_conversation_ids(&state), vec![archived.id]);
+
+        state.sidebar_search_results = Some(vec![ConversationSearchResult {
+            id: visible.id,
+            title: "t".to_string(),
+            is_title_match: true,
+            match_context: String::new(),
+            message_count: 1,
+            updated_at: Utc::now(),
+            title_highlights: Vec::new(),
+            context_highlights: Vec::new(),
+            message_index: None,
+        }]);
+        assert_eq!(shown_conversation_ids(&state), vec![visible.id]);
+    }
+}
@@ -0,0 +1,208 @@
+//! Grouping of the loaded conversation list for display.
+//!
+//! The store holds every conversation, archived or not; views narrow it with a
+//! `ConversationFilter` and render pinned conversations first, optionally
+//! followed by one group per folder. Shared by the conversation list and the
+//! chat view's title dropdown so both show the same order.
+
+use uuid::Uuid;
+
+use crate::models::ConversationFilter;
+use crate::presentation::view_command::ConversationSummary;
+
+/// Label of the pinned group.
+pub const PINNED_LABEL: &str = "PINNED";
+/// Label of the unpinned group when folders are not shown.
+pub const RECENT_LABEL: &str = "RECENT";
+/// Label of the group holding conversations without a folder.
+pub const UNFILED_LABEL: &str = "NO FOLDER";
+
+/// A run of conversations rendered under one label.
+#[derive(Debug, Clone)]
+pub struct ConversationGroup<'a> {
+    /// Header text; `None` when the list needs no header at all.
+    pub label: Option<String>,
+    pub conversations: Vec<&'a ConversationSummary>,
+}
+
+/// Split `conversations` into display groups.
+///
+/// Conversations the filter rejects are hidden, except `keep` (normally the
+/// active conversation) so the selection never disappears from view. Order
+/// within a group follows the input order (most recently updated first).
+#[must_use]
+pub fn group_conversations<'a>(
+    conversations: &'a [ConversationSummary],
+    filter: &ConversationFilter,
+    by_folder: bool,
+    keep: Option<Uuid>,
+) -> Vec<ConversationGroup<'a>> {
+    let (pinned, rest): (Vec<_>, Vec<_>) = conversations
+        .iter()
+        .filter(|c| Some(c.id) == keep || filter.admits(&c.organization))
+        .partition(|c| c.organization.pinned);
+
+    let mut groups = Vec::new();
+    let labelled = !pinned.is_empty() || by_folder;
+    if !pinned.is_empty() {
+        groups.push(ConversationGroup {
+            label: Some(PINNED_LABEL.to_string()),
+            conversations: pinned,
+        });
+    }
+
+    if by_folder {
+        let mut folders: Vec<&str> = rest
+            .iter()
+            .filter_map(|c| c.organization.folder.as_deref())
+            .collect();
+        folders.sort_unstable();
+        folders.dedup();
+        for folder in folders {
+            groups.push(ConversationGroup {
+                label: Some(folder.to_string()),
+                conversations: rest
+                    .iter()
+                    .copied()
+                    .filter(|c| c.organization.folder.as_deref() == Some(folder))
+                    .collect(),
+            });
+        }
+        let unfiled: Vec<_> = rest
+            .into_iter()
+            .filter(|c| c.organization.folder.is_none())
+            .collect();
+        if !unfiled.is_empty() {
+            groups.push(ConversationGroup {
+                label: Some(UNFILED_LABEL.to_string()),
+                conversations: unfiled,
+            });
+        }
+    } else if !rest.is_empty() {
+        groups.push(ConversationGroup {
+            label: labelled.then(|| RECENT_LABEL.to_string()),
+            conversations: rest,
+        });
+    }
+
+    groups
+}
+
+/// Conversation ids in display order, for keyboard navigation.
+#[must_use]
+pub fn display_order(groups: &[ConversationGroup<'_>]) -> Vec<Uuid> {
+    groups
+        .iter()
+        .flat_map(|group| group.conversations.iter().map(|c| c.id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::{ArchiveFilter, ConversationOrganization};
+
+    fn summary(title: &str, organization: ConversationOrganization) -> ConversationSummary {
+        ConversationSummary {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            updated_at: Utc::now(),
+            message_count: 0,
+            preview: None,
+            parent_id: None,
+            organization,
+        }
+    }
+
+    fn titles(group: &ConversationGroup<'_>) -> Vec<String> {
+        group
+            .conversations
+            .iter()
+            .map(|c| c.title.clone())
+            .collect()
+    }
+
+    fn sample() -> Vec<ConversationSummary> {
+        vec![
+            summary("plain", ConversationOrganization::default()),
+            summary(
+                "pinned",
+                ConversationOrganization {
+                    pinned: true,
+                    folder: Some("Work".to_string()),
+                    ..ConversationOrganization::default()
+                },
+            ),
+            summary(
+                "filed",
+                ConversationOrganization {
+                    folder: Some("Work".to_string()),
+                    tags: vec!["rust".to_string()],
+                    ..ConversationOrganization::default()
+                },
+            ),
+            summary(
+                "old",
+                ConversationOrganization {
+                    archived: true,
+                    ..ConversationOrganization::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn flat_list_without_pins_has_no_headers() {
+        let conversations = vec![summary("a", ConversationOrganization::default())];
+        let groups =
+            group_conversations(&conversations, &ConversationFilter::default(), false, None);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].label, None);
+    }
+
+    #[test]
+    fn pinned_first_and_archived_hidden_by_default() {
+        let conversations = sample();
+        let groups =
+            group_conversations(&conversations, &ConversationFilter::default(), false, None);
+
+        assert_eq!(groups[0].label.as_deref(), Some(PINNED_LABEL));
+        assert_eq!(titles(&groups[0]), ["pinned"]);
+        assert_eq!(groups[1].label.as_deref(), Some(RECENT_LABEL));
+        assert_eq!(titles(&groups[1]), ["plain", "filed"]);
+        assert_eq!(display_order(&groups).len(), 3);
+    }
+
+    #[test]
+    fn folder_grouping_puts_unfiled_last() {
+        let conversations = sample();
+        let groups =
+            group_conversations(&conversations, &ConversationFilter::default(), true, None);
+
+        let labels: Vec<_> = groups.iter().map(|g| g.label.clone().unwrap()).collect();
+        assert_eq!(labels, [PINNED_LABEL, "Work", UNFILED_LABEL]);
+        assert_eq!(titles(&groups[1]), ["filed"]);
+    }
+
+    #[test]
+    fn filter_narrows_but_keeps_active_conversation() {
+        let conversations = sample();
+        let archived_id = conversations[3].id;
+
+        let tagged = ConversationFilter {
+            tags: vec!["rust".to_string()],
+            ..ConversationFilter::default()
+        };
+        let groups = group_conversations(&conversations, &tagged, false, Some(archived_id));
+        assert_eq!(titles(&groups[0]), ["filed", "old"]);
+
+        let archive = ConversationFilter {
+            archived: ArchiveFilter::Only,
+            ..ConversationFilter::default()
+        };
+        let groups = group_conversations(&conversations, &archive, false, None);
+        assert_eq!(display_order(&groups), [archived_id]);
+    }
+}
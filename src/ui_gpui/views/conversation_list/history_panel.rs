@@ -64,6 +64,13 @@ impl HistoryPanelView {
         });
     }
 
+    /// Show the outcome of an archive export in the embedded list.
+    pub fn apply_archive_status(&self, status: String, cx: &mut gpui::Context<Self>) {
+        self.list.update(cx, |list, list_cx| {
+            list.apply_archive_status(status, list_cx);
+        });
+    }
+
     /// Read the current list of conversations from the embedded list view.
     /// Used by debug/test helpers that need to inspect the conversation list.
     pub fn conversation_summaries(
@@ -76,6 +76,19 @@ impl gpui::EntityInputHandler for ConversationListView {
         _window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
     ) {
+        if self.state.labels_editing {
+            let labels = &mut self.state.labels_input;
+            if let Some(r) = range {
+                let start = utf16_offset_to_utf8(labels, r.start);
+                let end = utf16_offset_to_utf8(labels, r.end);
+                labels.replace_range(start..end, text);
+            } else {
+                labels.push_str(text);
+            }
+            cx.notify();
+            return;
+        }
+
         if self.state.conversation_title_editing {
             if self.state.rename_replace_on_next_char {
                 self.state.conversation_title_input.clear();
@@ -11,8 +11,11 @@
 //! @plan PLAN-20260420-ISSUE180
 //! @requirement REQ-180-001
 
+mod archive;
+pub(crate) mod groups;
 mod history_panel;
 mod ime;
+mod organize;
 mod render;
 pub mod state;
 
@@ -111,6 +114,23 @@ impl ConversationListView {
         }
     }
 
+    /// Open a search hit, jumping to its matching message when known.
+    pub(super) fn handle_search_result_click(
+        &self,
+        conversation_id: Uuid,
+        message_index: Option<usize>,
+    ) {
+        if let Some(index) = message_index {
+            crate::ui_gpui::selection_intent_channel()
+                .request_select_message(conversation_id, index);
+            if matches!(self.mode, ConversationListMode::FullPanel) {
+                crate::ui_gpui::navigation_channel().request_navigate(ViewId::Chat);
+            }
+        } else {
+            self.handle_row_click(conversation_id);
+        }
+    }
+
     // ── Inline rename flow ───────────────────────────────────────────
 
     pub fn start_rename_conversation(&mut self, cx: &mut gpui::Context<Self>) {
@@ -124,6 +144,7 @@ impl ConversationListView {
             .find(|c| c.id == id)
             .map(|c| c.title.clone())
             .unwrap_or_default();
+        self.state.labels_editing = false;
         self.state.conversation_title_editing = true;
         self.state.conversation_title_input = title;
         self.state.rename_replace_on_next_char = true;
@@ -185,7 +206,10 @@ impl ConversationListView {
         if query.trim().is_empty() {
             self.state.sidebar_search_results = None;
         } else {
-            self.emit(&UserEvent::SearchConversations { query });
+            self.emit(&UserEvent::SearchConversations {
+                query,
+                filter: self.state.filter.clone(),
+            });
         }
         cx.notify();
     }
@@ -213,7 +237,9 @@ impl ConversationListView {
     /// Read the input text currently owned by the list's active editor.
     #[must_use]
     pub fn active_input_text(&self) -> &str {
-        if self.state.conversation_title_editing {
+        if self.state.labels_editing {
+            &self.state.labels_input
+        } else if self.state.conversation_title_editing {
             &self.state.conversation_title_input
         } else {
             &self.state.sidebar_search_query
@@ -251,7 +277,9 @@ impl ConversationListView {
                     if !text.is_empty() {
                         cx.write_to_clipboard(gpui::ClipboardItem::new_string(text));
                     }
-                    if self.state.conversation_title_editing {
+                    if self.state.labels_editing {
+                        self.state.labels_input.clear();
+                    } else if self.state.conversation_title_editing {
                         self.state.conversation_title_input.clear();
                         self.state.rename_replace_on_next_char = false;
                     } else {
@@ -265,6 +293,19 @@ impl ConversationListView {
             return;
         }
 
+        if self.state.labels_editing {
+            match key.as_str() {
+                "escape" => self.cancel_labels_edit(cx),
+                "backspace" => {
+                    self.state.labels_input.pop();
+                    cx.notify();
+                }
+                "enter" => self.submit_labels_edit(cx),
+                _ => {}
+            }
+            return;
+        }
+
         if self.state.conversation_title_editing {
             match key.as_str() {
                 "escape" => self.cancel_rename_conversation(cx),
@@ -296,7 +337,10 @@ impl ConversationListView {
 
     /// Paste text into the list's active editor.
     pub fn handle_paste(&mut self, text: &str, cx: &mut gpui::Context<Self>) {
-        if self.state.conversation_title_editing {
+        if self.state.labels_editing {
+            self.state.labels_input.push_str(text);
+            cx.notify();
+        } else if self.state.conversation_title_editing {
             if self.state.rename_replace_on_next_char {
                 self.state.conversation_title_input.clear();
                 self.state.rename_replace_on_next_char = false;
@@ -0,0 +1,406 @@
+//! Pinning, archive, folders, and tags in `ConversationListView`.
+//!
+//! The filter row under the search box narrows the list (pinned only, the
+//! archive, one folder, one tag) and toggles folder grouping. The selected
+//! row offers Pin / Archive / Labels actions; Labels opens an inline editor
+//! that takes a folder name followed by `#tags`, e.g. `Work #rust #q3`.
+//!
+//! Changes are applied to the local state immediately and persisted through
+//! `SetConversation*` events; the presenter answers with a refreshed list.
+
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+use uuid::Uuid;
+
+use super::ConversationListView;
+use crate::events::types::UserEvent;
+use crate::models::{normalize_folder, normalize_tag, ArchiveFilter, ConversationOrganization};
+use crate::presentation::view_command::ConversationSummary;
+use crate::ui_gpui::theme::Theme;
+
+/// Split labels-editor input into a folder and tags: `#words` are tags, the
+/// remaining words form the folder name.
+#[must_use]
+pub fn parse_labels(input: &str) -> (Option<String>, Vec<String>) {
+    let (tags, folder): (Vec<&str>, Vec<&str>) = input
+        .split_whitespace()
+        .partition(|word| word.starts_with('#'));
+    let mut unique: Vec<String> = Vec::new();
+    for tag in tags.into_iter().filter_map(normalize_tag) {
+        if !unique.contains(&tag) {
+            unique.push(tag);
+        }
+    }
+    (normalize_folder(&folder.join(" ")), unique)
+}
+
+/// Render an organization as labels-editor input, the inverse of `parse_labels`.
+#[must_use]
+pub fn format_labels(organization: &ConversationOrganization) -> String {
+    organization
+        .folder
+        .iter()
+        .cloned()
+        .chain(organization.tags.iter().map(|tag| format!("#{tag}")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl ConversationListView {
+    fn organization_mut(&mut self, id: Uuid) -> Option<&mut ConversationOrganization> {
+        self.state
+            .conversations
+            .iter_mut()
+            .find(|c| c.id == id)
+            .map(|c| &mut c.organization)
+    }
+
+    pub fn toggle_pinned(&mut self, id: Uuid, cx: &mut gpui::Context<Self>) {
+        let Some(organization) = self.organization_mut(id) else {
+            return;
+        };
+        organization.pinned = !organization.pinned;
+        let pinned = organization.pinned;
+        self.emit(&UserEvent::SetConversationPinned { id, pinned });
+        cx.notify();
+    }
+
+    pub fn toggle_archived(&mut self, id: Uuid, cx: &mut gpui::Context<Self>) {
+        let Some(organization) = self.organization_mut(id) else {
+            return;
+        };
+        organization.archived = !organization.archived;
+        let archived = organization.archived;
+        self.emit(&UserEvent::SetConversationArchived { id, archived });
+        cx.notify();
+    }
+
+    /// Open the labels editor for the active conversation.
+    pub fn start_labels_edit(&mut self, cx: &mut gpui::Context<Self>) {
+        let Some(id) = self.state.active_conversation_id else {
+            return;
+        };
+        let Some(organization) = self.organization_mut(id) else {
+            return;
+        };
+        let input = format_labels(organization);
+        self.state.conversation_title_editing = false;
+        self.state.labels_input = input;
+        self.state.labels_editing = true;
+        cx.notify();
+    }
+
+    pub fn submit_labels_edit(&mut self, cx: &mut gpui::Context<Self>) {
+        if !self.state.labels_editing {
+            return;
+        }
+        self.state.labels_editing = false;
+        let input = std::mem::take(&mut self.state.labels_input);
+        let Some(id) = self.state.active_conversation_id else {
+            cx.notify();
+            return;
+        };
+        let (folder, tags) = parse_labels(&input);
+        if let Some(organization) = self.organization_mut(id) {
+            organization.folder.clone_from(&folder);
+            organization.tags.clone_from(&tags);
+        }
+        self.emit(&UserEvent::SetConversationFolder { id, folder });
+        self.emit(&UserEvent::SetConversationTags { id, tags });
+        cx.notify();
+    }
+
+    pub fn cancel_labels_edit(&mut self, cx: &mut gpui::Context<Self>) {
+        if !self.state.labels_editing {
+            return;
+        }
+        self.state.labels_editing = false;
+        self.state.labels_input.clear();
+        cx.notify();
+    }
+
+    /// Re-run an active search after the filter changed.
+    fn filter_changed(&mut self, cx: &mut gpui::Context<Self>) {
+        if self.state.sidebar_search_results.is_some() {
+            self.trigger_sidebar_search(cx);
+        }
+        cx.notify();
+    }
+
+    fn toggle_folder_filter(&mut self, folder: Option<String>, cx: &mut gpui::Context<Self>) {
+        self.state.filter.folder = if self.state.filter.folder == folder {
+            None
+        } else {
+            folder
+        };
+        self.filter_changed(cx);
+    }
+
+    fn toggle_tag_filter(&mut self, tag: String, cx: &mut gpui::Context<Self>) {
+        let tags = &mut self.state.filter.tags;
+        if let Some(position) = tags.iter().position(|t| *t == tag) {
+            tags.remove(position);
+        } else {
+            tags.push(tag);
+        }
+        self.filter_changed(cx);
+    }
+
+    /// Chip row under the search box.
+    pub(super) fn render_filter_row(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let filter = &self.state.filter;
+        let archive_view = filter.archived == ArchiveFilter::Only;
+
+        div()
+            .flex()
+            .flex_wrap()
+            .gap(px(4.0))
+            .child(
+                filter_chip("conv-filter-pinned", "Pinned", filter.pinned_only).on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, _window, cx| {
+                        this.state.filter.pinned_only = !this.state.filter.pinned_only;
+                        this.filter_changed(cx);
+                    }),
+                ),
+            )
+            .child(
+                filter_chip("conv-filter-archived", "Archived", archive_view).on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| {
+                        this.state.filter.archived = if archive_view {
+                            ArchiveFilter::Exclude
+                        } else {
+                            ArchiveFilter::Only
+                        };
+                        this.filter_changed(cx);
+                    }),
+                ),
+            )
+            .child(
+                filter_chip("conv-filter-folders", "Folders", self.state.group_by_folder)
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _window, cx| {
+                            this.state.group_by_folder = !this.state.group_by_folder;
+                            cx.notify();
+                        }),
+                    ),
+            )
+            .children(filter.folder.clone().map(|folder| {
+                filter_chip("conv-filter-folder", &format!("{folder} \u{00d7}"), true)
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _window, cx| {
+                            this.toggle_folder_filter(None, cx);
+                        }),
+                    )
+            }))
+            .children(filter.tags.iter().map(|tag| {
+                let tag = tag.clone();
+                filter_chip(
+                    SharedString::from(format!("conv-filter-tag-{tag}")),
+                    &format!("#{tag} \u{00d7}"),
+                    true,
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| {
+                        this.toggle_tag_filter(tag.clone(), cx);
+                    }),
+                )
+            }))
+    }
+
+    /// Folder and tag chips for a row; clicking one filters by it. While the
+    /// labels editor is open on the row, shows the editor instead.
+    pub(super) fn render_labels_row(
+        &self,
+        conv: &ConversationSummary,
+        is_selected: bool,
+        cx: &mut gpui::Context<Self>,
+    ) -> Option<gpui::AnyElement> {
+        if is_selected && self.state.labels_editing {
+            let input = &self.state.labels_input;
+            let text = if input.is_empty() {
+                "Folder #tag #tag|".to_string()
+            } else {
+                format!("{input}|")
+            };
+            return Some(
+                div()
+                    .pl(px(super::render::SIDEBAR_TITLE_LEADING_INDENT))
+                    .text_size(px(10.0))
+                    .text_color(Theme::selection_fg())
+                    .border_b_1()
+                    .border_color(Theme::accent())
+                    .child(SharedString::from(text))
+                    .into_any_element(),
+            );
+        }
+
+        let organization = &conv.organization;
+        if organization.folder.is_none() && organization.tags.is_empty() {
+            return None;
+        }
+        let conv_id = conv.id;
+        Some(
+            div()
+                .pl(px(super::render::SIDEBAR_TITLE_LEADING_INDENT))
+                .flex()
+                .flex_wrap()
+                .gap(px(6.0))
+                .children(organization.folder.clone().map(|folder| {
+                    label_link(format!("folder-{conv_id}"), folder.clone()).on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _window, cx| {
+                            cx.stop_propagation();
+                            this.toggle_folder_filter(Some(folder.clone()), cx);
+                        }),
+                    )
+                }))
+                .children(organization.tags.iter().map(|tag| {
+                    let tag = tag.clone();
+                    label_link(format!("tag-{conv_id}-{tag}"), format!("#{tag}")).on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _window, cx| {
+                            cx.stop_propagation();
+                            this.toggle_tag_filter(tag.clone(), cx);
+                        }),
+                    )
+                }))
+                .into_any_element(),
+        )
+    }
+
+    /// Pin / Archive / Labels actions shown under the selected row.
+    pub(super) fn render_row_actions(
+        conv: &ConversationSummary,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let conv_id = conv.id;
+        let pin_label = if conv.organization.pinned {
+            "Unpin"
+        } else {
+            "Pin"
+        };
+        let archive_label = if conv.organization.archived {
+            "Unarchive"
+        } else {
+            "Archive"
+        };
+
+        div()
+            .pl(px(super::render::SIDEBAR_TITLE_LEADING_INDENT))
+            .flex()
+            .gap(px(10.0))
+            .child(
+                action_link(format!("pin-{conv_id}"), pin_label).on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| {
+                        cx.stop_propagation();
+                        this.toggle_pinned(conv_id, cx);
+                    }),
+                ),
+            )
+            .child(
+                action_link(format!("archive-{conv_id}"), archive_label).on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, _window, cx| {
+                        cx.stop_propagation();
+                        this.toggle_archived(conv_id, cx);
+                    }),
+                ),
+            )
+            .child(
+                action_link(format!("labels-{conv_id}"), "Labels").on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, window, cx| {
+                        cx.stop_propagation();
+                        window.focus(&this.focus_handle, cx);
+                        this.start_labels_edit(cx);
+                    }),
+                ),
+            )
+    }
+}
+
+fn filter_chip(
+    id: impl Into<SharedString>,
+    label: &str,
+    active: bool,
+) -> gpui::Stateful<gpui::Div> {
+    let id: SharedString = id.into();
+    div()
+        .id(id)
+        .px(px(6.0))
+        .py(px(1.0))
+        .rounded(px(Theme::RADIUS_MD))
+        .border_1()
+        .cursor_pointer()
+        .text_size(px(10.0))
+        .when(active, |d| {
+            d.border_color(Theme::accent()).text_color(Theme::accent())
+        })
+        .when(!active, |d| {
+            d.border_color(Theme::border())
+                .text_color(Theme::text_secondary())
+                .hover(|s| s.bg(Theme::bg_dark()))
+        })
+        .child(SharedString::from(label.to_string()))
+}
+
+fn label_link(id: String, label: String) -> gpui::Stateful<gpui::Div> {
+    div()
+        .id(SharedString::from(id))
+        .text_size(px(10.0))
+        .text_color(Theme::accent())
+        .cursor_pointer()
+        .hover(|s| s.text_color(Theme::text_primary()))
+        .child(SharedString::from(label))
+}
+
+fn action_link(id: String, label: &'static str) -> gpui::Stateful<gpui::Div> {
+    div()
+        .id(SharedString::from(id))
+        .text_size(px(10.0))
+        .text_color(Theme::selection_fg())
+        .cursor_pointer()
+        .hover(|s| s.text_color(Theme::accent()))
+        .child(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_split_into_folder_and_normalized_tags() {
+        assert_eq!(
+            parse_labels("  Side project #Rust  #q3 #rust"),
+            (
+                Some("Side project".to_string()),
+                vec!["rust".to_string(), "q3".to_string()]
+            )
+        );
+        assert_eq!(
+            parse_labels("#only-tags"),
+            (None, vec!["only-tags".to_string()])
+        );
+        assert_eq!(parse_labels("   "), (None, Vec::new()));
+    }
+
+    #[test]
+    fn format_round_trips_through_parse() {
+        let organization = ConversationOrganization {
+            folder: Some("Work".to_string()),
+            tags: vec!["q3".to_string(), "rust".to_string()],
+            ..ConversationOrganization::default()
+        };
+        let text = format_labels(&organization);
+        assert_eq!(text, "Work #q3 #rust");
+        assert_eq!(
+            parse_labels(&text),
+            (organization.folder.clone(), organization.tags.clone())
+        );
+    }
+}
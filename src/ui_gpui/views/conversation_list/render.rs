@@ -10,22 +10,25 @@
 //! @requirement REQ-180-001
 
 use std::collections::HashSet;
+use std::ops::Range;
 
 use gpui::{
     canvas, div, prelude::*, px, AnyElement, Bounds, ElementInputHandler, FontWeight, MouseButton,
-    Pixels, SharedString,
+    Pixels, SharedString, StyledText, TextRun, UnderlineStyle,
 };
 use uuid::Uuid;
 
+use super::groups::group_conversations;
 use super::{ConversationListMode, ConversationListView};
 use crate::events::types::UserEvent;
+use crate::models::ConversationFilter;
 use crate::presentation::view_command::ConversationSummary;
 use crate::ui_gpui::theme::Theme;
 
 /// Leading indent (px) for conversation meta and detail rows so they line up
 /// beneath the title text, past the delete icon and streaming indicator
 /// rendered in the title row.
-const SIDEBAR_TITLE_LEADING_INDENT: f32 = 34.0;
+pub(super) const SIDEBAR_TITLE_LEADING_INDENT: f32 = 34.0;
 
 /// Returns true if this conversation should display a streaming indicator.
 #[must_use]
@@ -114,6 +117,8 @@ impl ConversationListView {
                             ),
                     ),
             )
+            .child(self.render_filter_row(cx))
+            .child(self.render_archive_row(cx))
     }
 
     fn header_label(&self) -> SharedString {
@@ -164,8 +169,22 @@ impl ConversationListView {
                 }
             }
         } else {
-            for conv in &self.state.conversations {
-                list = list.child(self.render_conversation_item(conv, cx));
+            let groups = group_conversations(
+                &self.state.conversations,
+                &self.state.filter,
+                self.state.group_by_folder,
+                self.state.active_conversation_id,
+            );
+            if groups.is_empty() && self.state.filter != ConversationFilter::default() {
+                list = list.child(Self::render_group_label("NO MATCHING CONVERSATIONS"));
+            }
+            for group in groups {
+                if let Some(label) = group.label {
+                    list = list.child(Self::render_group_label(&label));
+                }
+                for conv in group.conversations {
+                    list = list.child(self.render_conversation_item(conv, cx));
+                }
             }
         }
 
@@ -206,6 +225,12 @@ impl ConversationListView {
         } else {
             conv.title.clone()
         };
+        // Mark branches so they read as offshoots of their parent thread.
+        let title = if conv.parent_id.is_some() && !is_renaming {
+            format!("\u{21b3} {title}")
+        } else {
+            title
+        };
         let (title_color, meta_color) = selection_colors(is_selected);
         let updated = format_relative_time(conv.updated_at);
         let msg_count = conv.message_count;
@@ -217,18 +242,25 @@ impl ConversationListView {
         Self::render_item_body(conv_id, is_selected)
             .child(render_title_row(
                 self.render_delete_x(conv_id, cx),
-                title,
+                StyledText::new(title),
                 title_color,
                 is_renaming,
                 has_streaming_indicator,
             ))
             .child(render_meta_row(&updated, msg_count, meta_color))
             .when(!preview.is_empty(), |d| {
-                d.child(render_detail_row(preview, meta_color))
+                d.child(render_detail_row(StyledText::new(preview), meta_color))
+            })
+            .children(self.render_labels_row(conv, is_selected, cx))
+            .when(is_selected && !is_renaming, |d| {
+                d.child(Self::render_row_actions(conv, cx))
             })
             .on_mouse_down(MouseButton::Left, {
                 cx.listener(move |this, _, _window, cx| {
                     this.state.delete_confirming_id = None;
+                    if !is_selected {
+                        this.state.labels_editing = false;
+                    }
                     this.state.sidebar_search_focused = false;
                     this.handle_row_click(conv_id);
                     cx.notify();
@@ -251,6 +283,8 @@ impl ConversationListView {
                 updated_at: result.updated_at,
                 message_count: result.message_count,
                 preview: None,
+                parent_id: None,
+                organization: crate::models::ConversationOrganization::default(),
             };
             return self
                 .render_delete_confirmation(&summary, cx)
@@ -265,13 +299,20 @@ impl ConversationListView {
         };
         let updated = format_relative_time(result.updated_at);
         let msg_count = result.message_count;
-        let context = result.match_context.clone();
         let (title_color, meta_color) = selection_colors(is_selected);
         let context_color = if is_selected {
             Theme::selection_fg()
         } else {
             Theme::accent()
         };
+        let has_context = !result.match_context.is_empty();
+        let context = highlighted_text(
+            result.match_context.clone(),
+            &result.context_highlights,
+            context_color,
+        );
+        let title = highlighted_text(title, &result.title_highlights, title_color);
+        let message_index = result.message_index;
 
         let has_streaming_indicator =
             conversation_has_streaming_indicator(conv_id, &self.state.streaming_conversation_ids);
@@ -285,14 +326,14 @@ impl ConversationListView {
                 has_streaming_indicator,
             ))
             .child(render_meta_row(&updated, msg_count, meta_color))
-            .when(!context.is_empty(), |d| {
+            .when(has_context, |d| {
                 d.child(render_detail_row(context, context_color))
             })
             .on_mouse_down(MouseButton::Left, {
                 cx.listener(move |this, _, _window, cx| {
                     this.state.delete_confirming_id = None;
                     this.state.sidebar_search_focused = false;
-                    this.handle_row_click(conv_id);
+                    this.handle_search_result_click(conv_id, message_index);
                     cx.notify();
                 })
             })
@@ -436,7 +477,7 @@ fn render_streaming_indicator(show: bool) -> impl IntoElement {
 
 fn render_title_row(
     delete_x: impl IntoElement,
-    title: String,
+    title: StyledText,
     title_color: gpui::Hsla,
     is_renaming: bool,
     has_streaming_indicator: bool,
@@ -457,7 +498,7 @@ fn render_title_row(
                 .text_size(px(12.0))
                 .font_weight(FontWeight::SEMIBOLD)
                 .text_color(title_color)
-                .child(SharedString::from(title))
+                .child(title)
                 .when(is_renaming, |d| {
                     d.border_b_1().border_color(Theme::accent())
                 }),
@@ -474,7 +515,7 @@ fn render_meta_row(updated: &str, msg_count: usize, color: gpui::Hsla) -> impl I
         )))
 }
 
-fn render_detail_row(text: String, color: gpui::Hsla) -> impl IntoElement {
+fn render_detail_row(text: StyledText, color: gpui::Hsla) -> impl IntoElement {
     div()
         .pl(px(SIDEBAR_TITLE_LEADING_INDENT))
         .overflow_hidden()
@@ -482,7 +523,51 @@ fn render_detail_row(text: String, color: gpui::Hsla) -> impl IntoElement {
         .text_ellipsis()
         .text_size(px(10.0))
         .text_color(color)
-        .child(SharedString::from(text))
+        .child(text)
+}
+
+/// Text runs that embolden and underline `ranges` (sorted byte ranges) of
+/// `text`. Ranges that overlap, overrun, or split a character are skipped.
+fn highlight_runs(text: &str, ranges: &[Range<usize>], color: gpui::Hsla) -> Vec<TextRun> {
+    let plain = |len| TextRun {
+        len,
+        color,
+        ..Default::default()
+    };
+    let mut runs = Vec::new();
+    let mut pos = 0;
+    for range in ranges {
+        let (start, end) = (range.start, range.end.min(text.len()));
+        if start < pos
+            || start >= end
+            || !text.is_char_boundary(start)
+            || !text.is_char_boundary(end)
+        {
+            continue;
+        }
+        if start > pos {
+            runs.push(plain(start - pos));
+        }
+        let mut run = plain(end - start);
+        run.font.weight = FontWeight::BOLD;
+        run.underline = Some(UnderlineStyle {
+            thickness: px(1.0),
+            color: None,
+            wavy: false,
+        });
+        runs.push(run);
+        pos = end;
+    }
+    if pos < text.len() {
+        runs.push(plain(text.len() - pos));
+    }
+    runs
+}
+
+/// Search-result text with its matched ranges highlighted.
+fn highlighted_text(text: String, ranges: &[Range<usize>], color: gpui::Hsla) -> StyledText {
+    let runs = highlight_runs(&text, ranges, color);
+    StyledText::new(text).with_runs(runs)
 }
 
 fn format_relative_time(dt: chrono::DateTime<chrono::Utc>) -> String {
@@ -555,7 +640,9 @@ mod tests {
     use std::collections::HashSet;
     use uuid::Uuid;
 
-    use super::conversation_has_streaming_indicator;
+    use super::{conversation_has_streaming_indicator, highlight_runs};
+    use crate::ui_gpui::theme::Theme;
+    use gpui::{FontWeight, TextRun};
 
     #[test]
     fn streaming_indicator_true_when_in_set() {
@@ -580,4 +667,24 @@ mod tests {
         let ids = HashSet::new();
         assert!(!conversation_has_streaming_indicator(id, &ids));
     }
+
+    fn lengths_and_bold(runs: &[TextRun]) -> Vec<(usize, bool)> {
+        runs.iter()
+            .map(|run| (run.len, run.font.weight == FontWeight::BOLD))
+            .collect()
+    }
+
+    #[test]
+    fn highlight_runs_cover_the_whole_text() {
+        let runs = highlight_runs("the borrow checker", &[4..10], Theme::accent());
+        assert_eq!(lengths_and_bold(&runs), [(4, false), (6, true), (8, false)]);
+    }
+
+    #[test]
+    fn invalid_ranges_are_skipped() {
+        let text = "é borrow";
+        let runs = highlight_runs(text, &[1..2, 3..9, 0..2, 1..3], Theme::accent());
+        assert_eq!(lengths_and_bold(&runs), [(3, false), (6, true)]);
+        assert_eq!(runs.iter().map(|run| run.len).sum::<usize>(), text.len());
+    }
 }
@@ -2,8 +2,8 @@
 //!
 //! Single source of truth for everything visible in the popout sidebar
 //! and the popin History panel: conversations list, current selection,
-//! per-row streaming indicators, search query/results, inline rename
-//! editor and inline delete confirmation.
+//! per-row streaming indicators, search query/results, organization
+//! filter, inline rename and labels editors and inline delete confirmation.
 //!
 //! @plan PLAN-20260420-ISSUE180.P01
 //! @requirement REQ-180-001
@@ -12,6 +12,7 @@ use std::collections::HashSet;
 
 use uuid::Uuid;
 
+use crate::models::{ConversationArchiveFormat, ConversationFilter};
 use crate::presentation::view_command::{ConversationSearchResult, ConversationSummary};
 use crate::ui_gpui::app_store::HistoryStoreSnapshot;
 
@@ -19,6 +20,7 @@ use crate::ui_gpui::app_store::HistoryStoreSnapshot;
 ///
 /// All fields are pure UI state — there is no rendering, no bridge,
 /// and no event emission here.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Default)]
 pub struct ConversationListState {
     /// Conversations sourced from the history store snapshot.
@@ -41,6 +43,20 @@ pub struct ConversationListState {
     pub conversation_title_input: String,
     /// When true, the next character in the rename editor replaces the buffer.
     pub rename_replace_on_next_char: bool,
+    /// Organization filter narrowing the list and search results.
+    pub filter: ConversationFilter,
+    /// Whether unpinned conversations are grouped by folder.
+    pub group_by_folder: bool,
+    /// Whether the inline folder/tags editor is open for the active row.
+    pub labels_editing: bool,
+    /// Working buffer for the labels editor, e.g. `Work #rust #q3`.
+    pub labels_input: String,
+    /// Container used by the "export all / shown" actions.
+    pub archive_format: ConversationArchiveFormat,
+    /// Whether an archive export is in flight.
+    pub archive_in_progress: bool,
+    /// Outcome of the last archive export.
+    pub archive_status: Option<String>,
 }
 
 impl ConversationListState {
@@ -91,6 +107,9 @@ impl ConversationListState {
             self.conversation_title_input.clear();
             self.rename_replace_on_next_char = false;
         }
+        if !self.labels_editing {
+            self.labels_input.clear();
+        }
     }
 
     /// Returns true if the given conversation id is currently streaming.
@@ -141,6 +160,8 @@ mod tests {
             updated_at,
             message_count,
             preview: None,
+            parent_id: None,
+            organization: crate::models::ConversationOrganization::default(),
         }
     }
 
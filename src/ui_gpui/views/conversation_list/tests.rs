@@ -13,6 +13,7 @@ use uuid::Uuid;
 
 use super::{ConversationListMode, ConversationListView};
 use crate::events::types::UserEvent;
+use crate::models::{ArchiveFilter, ConversationOrganization};
 use crate::presentation::view_command::ConversationSummary;
 use crate::ui_gpui::app_store::HistoryStoreSnapshot;
 use crate::ui_gpui::bridge::GpuiBridge;
@@ -30,6 +31,8 @@ fn summary(id: Uuid, title: &str) -> ConversationSummary {
         updated_at: Utc::now() - Duration::minutes(2),
         message_count: 0,
         preview: None,
+        parent_id: None,
+        organization: ConversationOrganization::default(),
     }
 }
 
@@ -72,7 +75,7 @@ async fn trigger_sidebar_search_emits_event_when_query_non_empty(cx: &mut TestAp
 
     let event = user_rx.recv().expect("expected SearchConversations event");
     assert!(
-        matches!(event, UserEvent::SearchConversations { ref query } if query == "hello"),
+        matches!(event, UserEvent::SearchConversations { ref query, .. } if query == "hello"),
         "got {event:?}"
     );
 }
@@ -220,7 +223,7 @@ async fn list_input_handler_types_into_focused_search(cx: &mut TestAppContext) {
 
     let event = user_rx.recv().expect("expected SearchConversations event");
     assert!(
-        matches!(event, UserEvent::SearchConversations { ref query } if query == "hist"),
+        matches!(event, UserEvent::SearchConversations { ref query, .. } if query == "hist"),
         "got {event:?}"
     );
 }
@@ -242,11 +245,87 @@ async fn list_input_handler_supports_backspace_and_escape_for_search(cx: &mut Te
 
     let event = user_rx.recv().expect("expected SearchConversations event");
     assert!(
-        matches!(event, UserEvent::SearchConversations { ref query } if query == "histor"),
+        matches!(event, UserEvent::SearchConversations { ref query, .. } if query == "histor"),
         "got {event:?}"
     );
 }
 
+#[gpui::test]
+async fn toggle_pinned_updates_row_and_emits_event(cx: &mut TestAppContext) {
+    let (bridge, user_rx) = make_bridge();
+    let id = Uuid::new_v4();
+    let view = cx.new(|cx| ConversationListView::new(ConversationListMode::Inline, cx));
+
+    view.update(cx, |view, cx| {
+        view.set_bridge(Arc::clone(&bridge));
+        view.state.conversations = vec![summary(id, "Plans")];
+        view.toggle_pinned(id, cx);
+        assert!(view.state.conversations[0].organization.pinned);
+    });
+
+    assert_eq!(
+        user_rx.recv().ok(),
+        Some(UserEvent::SetConversationPinned { id, pinned: true })
+    );
+}
+
+#[gpui::test]
+async fn labels_editor_submits_folder_and_tags(cx: &mut TestAppContext) {
+    let (bridge, user_rx) = make_bridge();
+    let id = Uuid::new_v4();
+    let view = cx.new(|cx| ConversationListView::new(ConversationListMode::Inline, cx));
+
+    view.update(cx, |view, cx| {
+        view.set_bridge(Arc::clone(&bridge));
+        view.state.conversations = vec![summary(id, "Plans")];
+        view.state.active_conversation_id = Some(id);
+        view.start_labels_edit(cx);
+        assert!(view.state.labels_editing);
+        view.handle_paste("Travel #Summer", cx);
+        view.handle_key_down(&key_event("enter"), cx);
+        assert!(!view.state.labels_editing);
+        assert_eq!(
+            view.state.conversations[0].organization.folder.as_deref(),
+            Some("Travel")
+        );
+    });
+
+    assert_eq!(
+        user_rx.recv().ok(),
+        Some(UserEvent::SetConversationFolder {
+            id,
+            folder: Some("Travel".to_string()),
+        })
+    );
+    assert_eq!(
+        user_rx.recv().ok(),
+        Some(UserEvent::SetConversationTags {
+            id,
+            tags: vec!["summer".to_string()],
+        })
+    );
+}
+
+#[gpui::test]
+async fn search_carries_the_active_filter(cx: &mut TestAppContext) {
+    let (bridge, user_rx) = make_bridge();
+    let view = cx.new(|cx| ConversationListView::new(ConversationListMode::Inline, cx));
+
+    view.update(cx, |view, cx| {
+        view.set_bridge(Arc::clone(&bridge));
+        view.state.filter.archived = ArchiveFilter::Only;
+        view.state.sidebar_search_query = "lisbon".to_string();
+        view.trigger_sidebar_search(cx);
+    });
+
+    match user_rx.recv().expect("expected SearchConversations event") {
+        UserEvent::SearchConversations { filter, .. } => {
+            assert_eq!(filter.archived, ArchiveFilter::Only);
+        }
+        other => panic!("unexpected event: {other:?}"),
+    }
+}
+
 fn key_event(key: &str) -> gpui::KeyDownEvent {
     gpui::KeyDownEvent {
         keystroke: gpui::Keystroke::parse(key).unwrap_or_else(|_| panic!("{key} keystroke")),
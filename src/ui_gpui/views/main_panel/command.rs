@@ -62,17 +62,42 @@ impl MainPanel {
             | ShowConversationExportFormat { .. }
             | ExportCompleted { .. }
             | ToolApprovalRequest { .. }
-            | ToolApprovalResolved { .. } => self.forward_to_chat(cmd, cx),
+            | ToolApprovalResolved { .. }
+            | ConversationBranched { .. }
+            | ConversationBranchesLoaded { .. }
+            | MessageVersionsLoaded { .. }
+            | TurnCheckpointsLoaded { .. }
+            | BackgroundProcessesUpdated { .. }
+            | SubAgentUpdated { .. }
+            | TodoListUpdated { .. }
+            | McpCatalogLoaded { .. }
+            | McpComposerTextReady { .. }
+            | McpComposerTextFailed { .. }
+            | ConversationUsageUpdated { .. } => self.forward_to_chat(cmd, cx),
 
             ConversationSearchResults { results } => {
                 self.forward_conversation_search_results(results, cx);
             }
             ErrorLogExportCompleted { .. } => self.forward_to_error_log(cmd, cx),
+            ConversationArchiveExported { path, count } => {
+                let noun = if count == 1 {
+                    "conversation"
+                } else {
+                    "conversations"
+                };
+                self.forward_archive_status(format!("Exported {count} {noun} to {path}"), cx);
+            }
+            ConversationArchiveFailed { error } => {
+                self.forward_archive_status(format!("Export failed: {error}"), cx);
+            }
 
             // ── settings-only forwarding ─────────────────────────────
             ExportDirectoryLoaded { .. }
             | SkillsLoaded { .. }
-            | ToolApprovalPolicyUpdated { .. } => self.forward_to_settings(cmd, cx),
+            | ToolApprovalPolicyUpdated { .. }
+            | UsageSummaryLoaded { .. }
+            | ToolAuditLoaded { .. }
+            | WorkspaceRootsUpdated { .. } => self.forward_to_settings(cmd, cx),
 
             // ── model selector + profile editor ─────────────────────────
             ModelSearchResults { .. }
@@ -82,8 +107,8 @@ impl MainPanel {
                 self.handle_model_profile_command(cmd, cx);
             }
 
-            YoloModeChanged { .. } => {
-                self.forward_yolo_to_settings_and_chat(&cmd, cx);
+            YoloModeChanged { .. } | ApprovalPolicyOverridesUpdated { .. } => {
+                self.forward_to_settings_and_chat(&cmd, cx);
             }
 
             // ── settings + profiles (non-store) ────────────────────────
@@ -108,11 +133,13 @@ impl MainPanel {
             | ApiKeyStored { .. }
             | ApiKeyDeleted { .. } => self.handle_notification_api_command(cmd, cx),
 
-            // ── backup commands (forward to settings view) ──────────────
+            // ── backup + import commands (forward to settings view) ─────
             BackupSettingsLoaded { .. }
             | BackupCompleted { .. }
             | BackupListRefreshed { .. }
-            | RestoreCompleted { .. } => {
+            | RestoreCompleted { .. }
+            | ConversationImportFinished { .. }
+            | ConversationImportFailed { .. } => {
                 tracing::info!(
                     "MainPanel: forwarding backup command {:?}",
                     std::mem::discriminant(&cmd)
@@ -171,6 +198,19 @@ impl MainPanel {
         }
     }
 
+    fn forward_archive_status(&self, status: String, cx: &mut gpui::Context<Self>) {
+        if let Some(ref chat) = self.chat_view {
+            chat.update(cx, |view, cx| {
+                view.apply_archive_status(status.clone(), cx);
+            });
+        }
+        if let Some(ref history_panel) = self.history_panel {
+            history_panel.update(cx, |view, cx| {
+                view.apply_archive_status(status, cx);
+            });
+        }
+    }
+
     fn forward_to_settings(&self, cmd: ViewCommand, cx: &mut gpui::Context<Self>) {
         if let Some(ref settings) = self.settings_view {
             settings.update(cx, |view, cx| {
@@ -187,19 +227,16 @@ impl MainPanel {
         }
     }
 
-    fn forward_yolo_to_settings_and_chat(&self, cmd: &ViewCommand, cx: &mut gpui::Context<Self>) {
-        if let ViewCommand::YoloModeChanged { active } = cmd {
-            let active = *active;
-            if let Some(ref settings) = self.settings_view {
-                settings.update(cx, |view, cx| {
-                    view.handle_command(ViewCommand::YoloModeChanged { active }, cx);
-                });
-            }
-            if let Some(ref chat) = self.chat_view {
-                chat.update(cx, |view, cx| {
-                    view.handle_command(ViewCommand::YoloModeChanged { active }, cx);
-                });
-            }
+    fn forward_to_settings_and_chat(&self, cmd: &ViewCommand, cx: &mut gpui::Context<Self>) {
+        if let Some(ref settings) = self.settings_view {
+            settings.update(cx, |view, cx| {
+                view.handle_command(cmd.clone(), cx);
+            });
+        }
+        if let Some(ref chat) = self.chat_view {
+            chat.update(cx, |view, cx| {
+                view.handle_command(cmd.clone(), cx);
+            });
         }
     }
 
@@ -122,8 +122,13 @@ impl MainPanel {
 
         if let Some(ref settings_view) = self.settings_view {
             let settings = snapshot.settings;
+            let chat = snapshot.chat;
             settings_view.update(cx, |view, _cx| {
                 view.apply_profile_summaries(settings.profiles, settings.selected_profile_id);
+                view.set_active_conversation(
+                    chat.selected_conversation_id,
+                    chat.selected_conversation_title,
+                );
             });
         }
 
@@ -188,6 +188,8 @@ pub(super) fn conversation_summary(
         updated_at: Utc::now(),
         message_count,
         preview: None,
+        parent_id: None,
+        organization: crate::models::ConversationOrganization::default(),
     }
 }
 
@@ -217,6 +217,9 @@ async fn conversation_search_results_update_history_panel(cx: &mut TestAppContex
                         match_context: "Startup".to_string(),
                         message_count: 2,
                         updated_at: chrono::Utc::now(),
+                        title_highlights: vec![0..5],
+                        context_highlights: Vec::new(),
+                        message_index: None,
                     },
                 ],
             },
@@ -700,6 +703,7 @@ async fn handle_command_forwards_tool_approval_policy_to_settings_view(cx: &mut
                 mcp_approval_mode: crate::agent::McpApprovalMode::PerServer,
                 persistent_allowlist: vec!["git".to_string(), "ls".to_string()],
                 persistent_denylist: vec!["rm".to_string()],
+                rules: Vec::new(),
             },
             cx,
         );
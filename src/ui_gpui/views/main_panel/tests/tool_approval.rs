@@ -19,6 +19,7 @@ async fn route_tool_approval_policy_updated_increments_counter(cx: &mut TestAppC
             mcp_approval_mode: crate::agent::McpApprovalMode::PerTool,
             persistent_allowlist: vec!["git".to_string()],
             persistent_denylist: vec!["rm".to_string()],
+            rules: Vec::new(),
         },
         1,
         |targets| targets.tool_approval_policy_count,
@@ -52,6 +53,7 @@ async fn handle_command_forwards_tool_approval_policy_to_settings_view(cx: &mut
                 mcp_approval_mode: crate::agent::McpApprovalMode::PerServer,
                 persistent_allowlist: vec!["git".to_string(), "ls".to_string()],
                 persistent_denylist: vec!["rm".to_string()],
+                rules: Vec::new(),
             },
             cx,
         );
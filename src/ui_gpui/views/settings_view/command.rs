@@ -99,6 +99,7 @@ impl SettingsView {
                 mcp_approval_mode,
                 persistent_allowlist,
                 persistent_denylist,
+                rules,
             } => {
                 self.state.yolo_mode = *yolo_mode;
                 self.state.auto_approve_reads = *auto_approve_reads;
@@ -110,8 +111,21 @@ impl SettingsView {
                 self.state
                     .persistent_denylist
                     .clone_from(persistent_denylist);
+                self.state.approval_rules.clone_from(rules);
                 self.state.allowlist_input.clear();
                 self.state.denylist_input.clear();
+                self.state.rule_input.clear();
+                true
+            }
+            ViewCommand::WorkspaceRootsUpdated { roots } => {
+                self.state.workspace_roots = roots.iter().cloned().collect();
+                self.state.workspace_root_input.clear();
+                true
+            }
+            ViewCommand::ApprovalPolicyOverridesUpdated { overrides } => {
+                self.state.policy_overrides.clone_from(overrides);
+                self.state.profile_override_input.clear();
+                self.state.conversation_override_input.clear();
                 true
             }
             ViewCommand::SkillsLoaded {
@@ -159,6 +173,15 @@ impl SettingsView {
                 self.state.filter_emoji = enabled;
                 true
             }
+            ViewCommand::UsageSummaryLoaded { by_profile, by_day } => {
+                self.state.usage_by_profile = by_profile;
+                self.state.usage_by_day = by_day;
+                true
+            }
+            ViewCommand::ToolAuditLoaded { entries } => {
+                self.state.audit_entries = entries;
+                true
+            }
             ViewCommand::SetLaunchAtLoginState { enabled, error } => {
                 self.state.launch_at_login = enabled;
                 self.state.launch_at_login_error = error;
@@ -353,6 +376,17 @@ impl SettingsView {
                 self.state.selected_backup_id = None;
                 true
             }
+            ViewCommand::ConversationImportFinished { summary } => {
+                self.state.import_in_progress = false;
+                self.state.import_status =
+                    Some(super::render_import::import_summary_message(summary));
+                true
+            }
+            ViewCommand::ConversationImportFailed { error } => {
+                self.state.import_in_progress = false;
+                self.state.import_status = Some(format!("Import failed: {error}"));
+                true
+            }
             _ => false,
         }
     }
@@ -9,17 +9,24 @@ mod command;
 mod input_handler;
 mod render;
 mod render_appearance;
+mod render_approval_rules;
+mod render_audit;
 mod render_backup_panel;
+mod render_import;
+mod render_policy_overrides;
 mod render_skills;
 mod render_tool_approval;
+mod render_usage;
 mod types;
 
 use gpui::FocusHandle;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::agent::McpApprovalMode;
+use crate::agent::policy_overrides::{OverrideLayer, PolicyOverrides};
+use crate::agent::{ApprovalRule, McpApprovalMode};
 use crate::events::types::UserEvent;
 use crate::presentation::view_command::{ProfileSummary, ThemeSummary};
 use crate::ui_gpui::bridge::GpuiBridge;
@@ -34,8 +41,14 @@ pub use types::{
 pub(super) enum ActiveField {
     AllowlistInput,
     DenylistInput,
+    RuleInput,
+    RuleTestInput,
     ExportDirInput,
     InstallSkillUrlInput,
+    WorkspaceRootInput,
+    ProfileOverrideInput,
+    ConversationOverrideInput,
+    AuditSearchInput,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -60,6 +73,20 @@ pub struct SettingsState {
     pub persistent_denylist: Vec<String>,
     pub allowlist_input: String,
     pub denylist_input: String,
+    /// Structured approval rules, as reported by the presenter.
+    pub approval_rules: Vec<ApprovalRule>,
+    pub rule_input: String,
+    /// Sample call typed into the rule tester.
+    pub rule_test_input: String,
+    /// Workspace roots per profile, as reported by the presenter.
+    pub workspace_roots: HashMap<Uuid, Vec<String>>,
+    pub workspace_root_input: String,
+    /// Profile and conversation approval overrides, as reported by the presenter.
+    pub policy_overrides: PolicyOverrides,
+    /// Id and title of the conversation open in the chat view.
+    pub active_conversation: Option<(Uuid, String)>,
+    pub profile_override_input: String,
+    pub conversation_override_input: String,
     pub export_dir_input: String,
     pub install_skill_url_input: String,
     pub watched_skill_directories: Vec<String>,
@@ -86,6 +113,10 @@ pub struct SettingsState {
     pub backup_in_progress: bool,
     /// Selected backup ID for restore
     pub selected_backup_id: Option<usize>,
+    /// Whether a conversation import is running
+    pub import_in_progress: bool,
+    /// Outcome of the last conversation import
+    pub import_status: Option<String>,
     /// When true, emojis are stripped from assistant message display
     pub filter_emoji: bool,
     /// Launch-at-login toggle (Issue #177; macOS only). Reflects the
@@ -97,6 +128,17 @@ pub struct SettingsState {
     /// "requires approval", "not in .app bundle"). `None` when the toggle
     /// is healthy.
     pub launch_at_login_error: Option<String>,
+    // Usage panel
+    /// Token and spend totals per profile, highest spend first
+    pub usage_by_profile: Vec<crate::presentation::view_command::ProfileUsageSummary>,
+    /// Totals per day for the recent window, newest first
+    pub usage_by_day: Vec<crate::models::DailyUsage>,
+    // Audit log panel
+    /// Audit records matching the filter, newest first
+    pub audit_entries: Vec<crate::models::ToolAuditEntry>,
+    pub audit_search_input: String,
+    /// Outcome the audit records are filtered to; `None` shows all
+    pub audit_outcome: Option<crate::models::AuditOutcome>,
 }
 
 impl SettingsState {
@@ -127,6 +169,15 @@ impl Default for SettingsState {
             persistent_denylist: Vec::new(),
             allowlist_input: String::new(),
             denylist_input: String::new(),
+            approval_rules: Vec::new(),
+            rule_input: String::new(),
+            rule_test_input: String::new(),
+            workspace_roots: HashMap::new(),
+            workspace_root_input: String::new(),
+            policy_overrides: PolicyOverrides::default(),
+            active_conversation: None,
+            profile_override_input: String::new(),
+            conversation_override_input: String::new(),
             export_dir_input: String::new(),
             install_skill_url_input: String::new(),
             watched_skill_directories: Vec::new(),
@@ -146,9 +197,16 @@ impl Default for SettingsState {
             backup_status: None,
             backup_in_progress: false,
             selected_backup_id: None,
+            import_in_progress: false,
+            import_status: None,
             filter_emoji: false,
             launch_at_login: false,
             launch_at_login_error: None,
+            usage_by_profile: Vec::new(),
+            usage_by_day: Vec::new(),
+            audit_entries: Vec::new(),
+            audit_search_input: String::new(),
+            audit_outcome: None,
         }
     }
 }
@@ -346,71 +404,40 @@ impl SettingsView {
     }
 
     fn append_to_active_field(&mut self, text: &str) {
-        if text.is_empty() {
-            return;
-        }
-
-        match self.state.active_field {
-            Some(ActiveField::AllowlistInput) => self.state.allowlist_input.push_str(text),
-            Some(ActiveField::DenylistInput) => self.state.denylist_input.push_str(text),
-            Some(ActiveField::ExportDirInput) => self.state.export_dir_input.push_str(text),
-            Some(ActiveField::InstallSkillUrlInput) => {
-                self.state.install_skill_url_input.push_str(text);
-            }
-
-            None => {}
+        if let Some(field) = self.active_field_text_mut() {
+            field.push_str(text);
         }
     }
 
     fn backspace_active_field(&mut self) {
-        match self.state.active_field {
-            Some(ActiveField::AllowlistInput) => {
-                self.state.allowlist_input.pop();
-            }
-            Some(ActiveField::DenylistInput) => {
-                self.state.denylist_input.pop();
-            }
-            Some(ActiveField::ExportDirInput) => {
-                self.state.export_dir_input.pop();
-            }
-            Some(ActiveField::InstallSkillUrlInput) => {
-                self.state.install_skill_url_input.pop();
-            }
-            None => {}
+        if let Some(field) = self.active_field_text_mut() {
+            field.pop();
         }
     }
 
     fn remove_trailing_bytes_from_active_field(&mut self, byte_count: usize) {
-        if byte_count == 0 {
-            return;
+        if let Some(field) = self.active_field_text_mut() {
+            field.truncate(field.len().saturating_sub(byte_count));
         }
+    }
 
+    const fn active_field_text_mut(&mut self) -> Option<&mut String> {
         match self.state.active_field {
-            Some(ActiveField::AllowlistInput) => {
-                let len = self.state.allowlist_input.len();
-                self.state
-                    .allowlist_input
-                    .truncate(len.saturating_sub(byte_count));
-            }
-            Some(ActiveField::DenylistInput) => {
-                let len = self.state.denylist_input.len();
-                self.state
-                    .denylist_input
-                    .truncate(len.saturating_sub(byte_count));
-            }
-            Some(ActiveField::ExportDirInput) => {
-                let len = self.state.export_dir_input.len();
-                self.state
-                    .export_dir_input
-                    .truncate(len.saturating_sub(byte_count));
-            }
+            Some(ActiveField::AllowlistInput) => Some(&mut self.state.allowlist_input),
+            Some(ActiveField::DenylistInput) => Some(&mut self.state.denylist_input),
+            Some(ActiveField::RuleInput) => Some(&mut self.state.rule_input),
+            Some(ActiveField::RuleTestInput) => Some(&mut self.state.rule_test_input),
+            Some(ActiveField::ExportDirInput) => Some(&mut self.state.export_dir_input),
             Some(ActiveField::InstallSkillUrlInput) => {
-                let len = self.state.install_skill_url_input.len();
-                self.state
-                    .install_skill_url_input
-                    .truncate(len.saturating_sub(byte_count));
+                Some(&mut self.state.install_skill_url_input)
             }
-            None => {}
+            Some(ActiveField::WorkspaceRootInput) => Some(&mut self.state.workspace_root_input),
+            Some(ActiveField::ProfileOverrideInput) => Some(&mut self.state.profile_override_input),
+            Some(ActiveField::ConversationOverrideInput) => {
+                Some(&mut self.state.conversation_override_input)
+            }
+            Some(ActiveField::AuditSearchInput) => Some(&mut self.state.audit_search_input),
+            None => None,
         }
     }
 
@@ -418,8 +445,14 @@ impl SettingsView {
         match self.state.active_field {
             Some(ActiveField::AllowlistInput) => &self.state.allowlist_input,
             Some(ActiveField::DenylistInput) => &self.state.denylist_input,
+            Some(ActiveField::RuleInput) => &self.state.rule_input,
+            Some(ActiveField::RuleTestInput) => &self.state.rule_test_input,
             Some(ActiveField::ExportDirInput) => &self.state.export_dir_input,
             Some(ActiveField::InstallSkillUrlInput) => &self.state.install_skill_url_input,
+            Some(ActiveField::WorkspaceRootInput) => &self.state.workspace_root_input,
+            Some(ActiveField::ProfileOverrideInput) => &self.state.profile_override_input,
+            Some(ActiveField::ConversationOverrideInput) => &self.state.conversation_override_input,
+            Some(ActiveField::AuditSearchInput) => &self.state.audit_search_input,
             None => "",
         }
     }
@@ -434,7 +467,16 @@ impl SettingsView {
             Some(ActiveField::ExportDirInput) => ActiveField::AllowlistInput,
             Some(ActiveField::AllowlistInput) => ActiveField::DenylistInput,
             Some(ActiveField::DenylistInput) => ActiveField::InstallSkillUrlInput,
-            Some(ActiveField::InstallSkillUrlInput) | None => ActiveField::ExportDirInput,
+            Some(
+                ActiveField::InstallSkillUrlInput
+                | ActiveField::WorkspaceRootInput
+                | ActiveField::ProfileOverrideInput
+                | ActiveField::ConversationOverrideInput
+                | ActiveField::RuleInput
+                | ActiveField::RuleTestInput
+                | ActiveField::AuditSearchInput,
+            )
+            | None => ActiveField::ExportDirInput,
         };
         self.set_active_field(Some(next));
     }
@@ -477,6 +519,24 @@ impl SettingsView {
         self.emit(&UserEvent::RemoveToolApprovalDenylistPrefix { prefix });
     }
 
+    fn add_workspace_root(&mut self) {
+        let path = self.state.workspace_root_input.trim().to_string();
+        let Some(profile_id) = self.state.selected_profile_id else {
+            return;
+        };
+        if path.is_empty() {
+            return;
+        }
+
+        self.emit(&UserEvent::AddWorkspaceRoot { profile_id, path });
+    }
+
+    fn remove_workspace_root(&self, path: String) {
+        if let Some(profile_id) = self.state.selected_profile_id {
+            self.emit(&UserEvent::RemoveWorkspaceRoot { profile_id, path });
+        }
+    }
+
     fn select_profile_by_index(&mut self, index: usize, emit_event: bool) {
         if let Some(profile) = self.state.profiles.get(index) {
             self.state.selected_profile_id = Some(profile.id);
@@ -690,6 +750,32 @@ impl SettingsView {
                 cx.notify();
                 return;
             }
+            Some(ActiveField::RuleInput) => {
+                self.add_approval_rule();
+                cx.notify();
+                return;
+            }
+            Some(ActiveField::RuleTestInput) => return,
+            Some(ActiveField::AuditSearchInput) => {
+                self.apply_audit_filter();
+                cx.notify();
+                return;
+            }
+            Some(ActiveField::WorkspaceRootInput) => {
+                self.add_workspace_root();
+                cx.notify();
+                return;
+            }
+            Some(ActiveField::ProfileOverrideInput) => {
+                self.set_policy_override(OverrideLayer::Profile);
+                cx.notify();
+                return;
+            }
+            Some(ActiveField::ConversationOverrideInput) => {
+                self.set_policy_override(OverrideLayer::Conversation);
+                cx.notify();
+                return;
+            }
             Some(ActiveField::ExportDirInput) => {
                 self.save_export_directory();
                 cx.notify();
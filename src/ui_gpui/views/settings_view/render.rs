@@ -695,6 +695,8 @@ impl SettingsView {
             SettingsCategory::Security => self.render_security_panel(cx).into_any_element(),
             SettingsCategory::McpTools => self.render_mcp_tools_panel(cx).into_any_element(),
             SettingsCategory::Backup => self.render_backup_panel(cx).into_any_element(),
+            SettingsCategory::Usage => self.render_usage_panel().into_any_element(),
+            SettingsCategory::Audit => self.render_audit_panel(cx).into_any_element(),
         };
 
         div()
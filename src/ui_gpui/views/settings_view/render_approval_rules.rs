@@ -0,0 +1,227 @@
+//! Approval rule editor for `SettingsView`: the saved rules, a field for a
+//! new rule in its one-line text form, and a tester showing how a sample
+//! call would be decided by the rule being typed and by the saved rules.
+
+use super::{ActiveField, SettingsView};
+use crate::agent::approval_rules::{deciding_rule, SampleCall};
+use crate::agent::ApprovalRule;
+use crate::events::types::UserEvent;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton};
+
+const RULE_PLACEHOLDER: &str = "e.g. deny tool=ShellExec command=\"^git push .*--force\"";
+const SAMPLE_PLACEHOLDER: &str = "Test this rule, e.g. ShellExec git push --force";
+
+/// Tester lines for `sample` under the `draft` rule and the saved `rules`.
+fn rule_test_report(draft: &str, sample: &str, rules: &[ApprovalRule]) -> Vec<String> {
+    if sample.trim().is_empty() {
+        return Vec::new();
+    }
+    let sample = match SampleCall::parse(sample) {
+        Ok(sample) => sample,
+        Err(error) => return vec![error],
+    };
+    let call = sample.call();
+
+    let mut report = Vec::new();
+    if !draft.trim().is_empty() {
+        report.push(match ApprovalRule::parse(draft) {
+            Ok(rule) if rule.matches(&call) => format!("This rule: {}", rule.effect.as_str()),
+            Ok(_) => "This rule: no match".to_string(),
+            Err(error) => format!("This rule: {error}"),
+        });
+    }
+    report.push(deciding_rule(rules, &call).map_or_else(
+        || "Saved rules: no match, so the lists and toggles decide".to_string(),
+        |rule| format!("Saved rules: {} by {rule}", rule.effect.as_str()),
+    ));
+    report
+}
+
+impl SettingsView {
+    pub(super) fn add_approval_rule(&mut self) {
+        let text = self.state.rule_input.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        match ApprovalRule::parse(text) {
+            Ok(rule) => self.emit(&UserEvent::AddToolApprovalRule { rule }),
+            Err(error) => {
+                self.state.status_message = Some(format!("Invalid rule: {error}"));
+                self.state.status_is_error = true;
+            }
+        }
+    }
+
+    fn remove_approval_rule(&self, text: &str) {
+        if let Some(rule) = self
+            .state
+            .approval_rules
+            .iter()
+            .find(|rule| rule.to_string() == text)
+        {
+            self.emit(&UserEvent::RemoveToolApprovalRule { rule: rule.clone() });
+        }
+    }
+
+    fn render_convert_button(cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        div()
+            .id("approval-rules-convert")
+            .h(px(20.0))
+            .px(px(8.0))
+            .bg(Theme::bg_dark())
+            .border_1()
+            .border_color(Theme::border())
+            .rounded(px(4.0))
+            .flex()
+            .items_center()
+            .cursor_pointer()
+            .hover(|s| s.bg(Theme::accent()).text_color(Theme::accent_fg()))
+            .text_size(px(Theme::font_size_ui()))
+            .text_color(Theme::text_primary())
+            .child("Convert lists to rules")
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _window, _cx| {
+                    this.emit(&UserEvent::ConvertToolApprovalPrefixesToRules);
+                }),
+            )
+    }
+
+    fn render_rule_tester(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let is_active = self.state.active_field == Some(ActiveField::RuleTestInput);
+        let sample = &self.state.rule_test_input;
+        let report = rule_test_report(&self.state.rule_input, sample, &self.state.approval_rules);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .child(
+                div()
+                    .id("approval-rule-test-field")
+                    .w_full()
+                    .h(px(24.0))
+                    .px(px(8.0))
+                    .bg(Theme::bg_dark())
+                    .border_1()
+                    .border_color(if is_active {
+                        Theme::accent()
+                    } else {
+                        Theme::border()
+                    })
+                    .rounded(px(4.0))
+                    .flex()
+                    .items_center()
+                    .text_size(px(Theme::font_size_mono()))
+                    .overflow_hidden()
+                    .cursor_text()
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, window, cx| {
+                            window.focus(&this.focus_handle, cx);
+                            this.set_active_field(Some(ActiveField::RuleTestInput));
+                            cx.notify();
+                        }),
+                    )
+                    .child(if sample.is_empty() {
+                        div()
+                            .text_color(Theme::text_muted())
+                            .child(SAMPLE_PLACEHOLDER)
+                    } else {
+                        div()
+                            .text_color(Theme::text_primary())
+                            .child(sample.clone())
+                    }),
+            )
+            .children(report.into_iter().map(|line| {
+                div()
+                    .px(px(8.0))
+                    .text_size(px(Theme::font_size_ui()))
+                    .text_color(Theme::text_muted())
+                    .child(line)
+            }))
+    }
+
+    /// Rules are checked before the lists below them; the highest priority
+    /// match wins, and at equal priority deny beats ask beats allow.
+    pub(super) fn render_approval_rules_section(
+        &self,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let entries: Vec<String> = self
+            .state
+            .approval_rules
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let has_prefixes = !self.state.persistent_allowlist.is_empty()
+            || !self.state.persistent_denylist.is_empty();
+        let is_active = self.state.active_field == Some(ActiveField::RuleInput);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(4.0))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_size(px(Theme::font_size_ui()))
+                            .text_color(Theme::text_muted())
+                            .child("RULES (checked first; deny > ask > allow at equal priority)"),
+                    )
+                    .when(has_prefixes, |d| d.child(Self::render_convert_button(cx))),
+            )
+            .child(self.render_prefix_list(
+                "approval-rule",
+                &entries,
+                |this, text| this.remove_approval_rule(&text),
+                cx,
+            ))
+            .child(Self::render_input_row(
+                "approval-rule",
+                &self.state.rule_input,
+                RULE_PLACEHOLDER,
+                is_active,
+                |this, cx| {
+                    this.set_active_field(Some(ActiveField::RuleInput));
+                    cx.notify();
+                },
+                |this, cx| {
+                    this.add_approval_rule();
+                    cx.notify();
+                },
+                cx,
+            ))
+            .child(self.render_rule_tester(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_test_report_covers_the_draft_and_the_saved_rules() {
+        let saved = [ApprovalRule::parse("allow tool=ShellExec command=^git").expect("rule")];
+
+        assert!(rule_test_report("deny command=--force", " ", &saved).is_empty());
+        assert_eq!(
+            rule_test_report("deny command=--force", "ShellExec git push --force", &saved),
+            vec![
+                "This rule: deny".to_string(),
+                "Saved rules: allow by allow tool=ShellExec command=^git".to_string(),
+            ]
+        );
+        assert_eq!(
+            rule_test_report("", "ShellExec ls", &saved),
+            vec!["Saved rules: no match, so the lists and toggles decide".to_string()]
+        );
+        assert!(rule_test_report("permit", "ShellExec ls", &saved)[0].contains("Unknown effect"));
+    }
+}
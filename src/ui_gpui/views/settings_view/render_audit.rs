@@ -0,0 +1,325 @@
+//! Audit log panel for `SettingsView`: a search field and outcome filter over
+//! the recorded tool calls, export buttons, and one row per call.
+
+use super::{ActiveField, SettingsView};
+use crate::events::types::UserEvent;
+use crate::models::{ApprovalSource, AuditExportFormat, AuditOutcome, ToolAuditEntry};
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton, SharedString};
+
+const SEARCH_PLACEHOLDER: &str = "Filter by tool, arguments, output or conversation; Enter applies";
+
+/// Characters of arguments or output shown in a row.
+const PREVIEW_CHARS: usize = 160;
+
+/// Who or what decided a call's approval, with the deciding rule if any.
+fn approval_label(entry: &ToolAuditEntry) -> String {
+    match (entry.approval_source, entry.approval_detail.as_deref()) {
+        (ApprovalSource::Rule, Some(rule)) => format!("rule: {rule}"),
+        (ApprovalSource::None, _) => "no approval".to_string(),
+        (source, _) => source.as_str().to_string(),
+    }
+}
+
+/// `text` on one line, cut to `PREVIEW_CHARS` characters.
+fn preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line,
+    }
+}
+
+/// The row's heading: tool, approval, outcome, exit code and duration.
+fn audit_heading(entry: &ToolAuditEntry) -> String {
+    let exit = entry
+        .exit_code
+        .map(|code| format!(" · exit {code}"))
+        .unwrap_or_default();
+    format!(
+        "{} · {} · {}{exit} · {} ms",
+        entry.tool_name,
+        approval_label(entry),
+        entry.outcome.as_str(),
+        entry.duration_ms
+    )
+}
+
+fn audit_row(index: usize, entry: &ToolAuditEntry) -> impl IntoElement {
+    let outcome_color = match entry.outcome {
+        AuditOutcome::Succeeded => Theme::success(),
+        AuditOutcome::Failed => Theme::error(),
+        AuditOutcome::Denied => Theme::warning(),
+    };
+    let started = entry.started_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+
+    div()
+        .id(SharedString::from(format!("audit-row-{index}")))
+        .flex()
+        .flex_col()
+        .gap(px(2.0))
+        .px(px(8.0))
+        .py(px(4.0))
+        .rounded(px(4.0))
+        .bg(Theme::bg_dark())
+        .border_l_2()
+        .border_color(outcome_color)
+        .child(
+            div()
+                .flex()
+                .justify_between()
+                .gap(px(8.0))
+                .text_size(px(Theme::font_size_ui()))
+                .child(
+                    div()
+                        .text_color(Theme::text_primary())
+                        .child(audit_heading(entry)),
+                )
+                .child(
+                    div()
+                        .flex_shrink_0()
+                        .text_color(Theme::text_muted())
+                        .child(started),
+                ),
+        )
+        .child(
+            div()
+                .overflow_hidden()
+                .text_size(px(Theme::font_size_mono()))
+                .text_color(Theme::text_muted())
+                .child(format!("args: {}", preview(&entry.arguments))),
+        )
+        .when(!entry.output.is_empty(), |d| {
+            d.child(
+                div()
+                    .overflow_hidden()
+                    .text_size(px(Theme::font_size_mono()))
+                    .text_color(Theme::text_muted())
+                    .child(format!("out: {}", preview(&entry.output))),
+            )
+        })
+}
+
+impl SettingsView {
+    pub(super) fn apply_audit_filter(&self) {
+        self.emit(&UserEvent::FilterToolAudit {
+            search: self.state.audit_search_input.clone(),
+            outcome: self.state.audit_outcome,
+        });
+    }
+
+    fn export_audit(&self, format: AuditExportFormat) {
+        self.emit(&UserEvent::ExportToolAudit {
+            search: self.state.audit_search_input.clone(),
+            outcome: self.state.audit_outcome,
+            format,
+        });
+    }
+
+    fn render_outcome_chip(
+        &self,
+        label: &'static str,
+        outcome: Option<AuditOutcome>,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let is_selected = self.state.audit_outcome == outcome;
+        div()
+            .id(SharedString::from(format!("audit-outcome-{label}")))
+            .px(px(8.0))
+            .py(px(2.0))
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .border_1()
+            .border_color(if is_selected {
+                Theme::accent()
+            } else {
+                Theme::border()
+            })
+            .when(is_selected, |d| {
+                d.bg(Theme::selection_bg())
+                    .text_color(Theme::selection_fg())
+            })
+            .when(!is_selected, |d| {
+                d.bg(Theme::bg_dark())
+                    .text_color(Theme::text_primary())
+                    .hover(|s| s.bg(Theme::bg_darker()))
+            })
+            .text_size(px(Theme::font_size_ui()))
+            .child(label)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _window, cx| {
+                    this.state.audit_outcome = outcome;
+                    this.apply_audit_filter();
+                    cx.notify();
+                }),
+            )
+    }
+
+    fn render_export_button(
+        label: &'static str,
+        format: AuditExportFormat,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(SharedString::from(format!(
+                "audit-export-{}",
+                format.extension()
+            )))
+            .h(px(20.0))
+            .px(px(8.0))
+            .bg(Theme::bg_dark())
+            .border_1()
+            .border_color(Theme::border())
+            .rounded(px(4.0))
+            .flex()
+            .items_center()
+            .cursor_pointer()
+            .hover(|s| s.bg(Theme::accent()).text_color(Theme::accent_fg()))
+            .text_size(px(Theme::font_size_ui()))
+            .text_color(Theme::text_primary())
+            .child(label)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _window, _cx| this.export_audit(format)),
+            )
+    }
+
+    /// Audit log panel: recorded tool calls, newest first. Exports cover
+    /// every record matching the filter, not just the ones shown.
+    pub(super) fn render_audit_panel(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let entries = &self.state.audit_entries;
+        let search = &self.state.audit_search_input;
+        let is_active = self.state.active_field == Some(ActiveField::AuditSearchInput);
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .gap(px(8.0))
+            .child(
+                div()
+                    .id("audit-search-field")
+                    .w_full()
+                    .h(px(24.0))
+                    .px(px(8.0))
+                    .bg(Theme::bg_dark())
+                    .border_1()
+                    .border_color(if is_active {
+                        Theme::accent()
+                    } else {
+                        Theme::border()
+                    })
+                    .rounded(px(4.0))
+                    .flex()
+                    .items_center()
+                    .text_size(px(Theme::font_size_mono()))
+                    .overflow_hidden()
+                    .cursor_text()
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, window, cx| {
+                            window.focus(&this.focus_handle, cx);
+                            this.set_active_field(Some(ActiveField::AuditSearchInput));
+                            cx.notify();
+                        }),
+                    )
+                    .child(if search.is_empty() {
+                        div()
+                            .text_color(Theme::text_muted())
+                            .child(SEARCH_PLACEHOLDER)
+                    } else {
+                        div()
+                            .text_color(Theme::text_primary())
+                            .child(search.clone())
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0))
+                    .child(self.render_outcome_chip("All", None, cx))
+                    .child(self.render_outcome_chip("Succeeded", Some(AuditOutcome::Succeeded), cx))
+                    .child(self.render_outcome_chip("Failed", Some(AuditOutcome::Failed), cx))
+                    .child(self.render_outcome_chip("Denied", Some(AuditOutcome::Denied), cx))
+                    .child(div().flex_1())
+                    .child(Self::render_export_button(
+                        "Export CSV",
+                        AuditExportFormat::Csv,
+                        cx,
+                    ))
+                    .child(Self::render_export_button(
+                        "Export JSON",
+                        AuditExportFormat::Json,
+                        cx,
+                    )),
+            )
+            .child(
+                div()
+                    .id("audit-panel-scroll")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap(px(6.0))
+                    .overflow_y_scroll()
+                    .when(entries.is_empty(), |d| {
+                        d.child(
+                            div()
+                                .text_size(px(Theme::font_size_ui()))
+                                .text_color(Theme::text_muted())
+                                .child("No tool calls match"),
+                        )
+                    })
+                    .children(
+                        entries
+                            .iter()
+                            .enumerate()
+                            .map(|(index, entry)| audit_row(index, entry)),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn entry(source: ApprovalSource, detail: Option<&str>) -> ToolAuditEntry {
+        ToolAuditEntry {
+            conversation_id: None,
+            tool_name: "ShellExec".to_string(),
+            arguments: "{}".to_string(),
+            approval_source: source,
+            approval_detail: detail.map(str::to_string),
+            outcome: AuditOutcome::Failed,
+            exit_code: Some(1),
+            output: String::new(),
+            started_at: Utc::now(),
+            duration_ms: 15,
+        }
+    }
+
+    #[test]
+    fn headings_name_the_deciding_rule() {
+        assert_eq!(
+            audit_heading(&entry(ApprovalSource::Rule, Some("allow tool=ShellExec"))),
+            "ShellExec · rule: allow tool=ShellExec · failed · exit 1 · 15 ms"
+        );
+        assert_eq!(
+            approval_label(&entry(ApprovalSource::None, None)),
+            "no approval"
+        );
+        assert_eq!(approval_label(&entry(ApprovalSource::Yolo, None)), "yolo");
+    }
+
+    #[test]
+    fn previews_are_single_line_and_cut() {
+        assert_eq!(preview("line one\n  line two"), "line one line two");
+
+        let long = "x".repeat(PREVIEW_CHARS + 10);
+        assert_eq!(preview(&long).chars().count(), PREVIEW_CHARS + 1);
+        assert!(preview(&long).ends_with('…'));
+    }
+}
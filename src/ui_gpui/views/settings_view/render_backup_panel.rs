@@ -6,7 +6,8 @@ use gpui::{div, prelude::*, px, MouseButton, SharedString};
 
 #[allow(clippy::unused_self)]
 impl SettingsView {
-    /// Backup panel: automatic backup settings, manual backup controls, and restore.
+    /// Backup panel: automatic backup settings, manual backup controls, restore,
+    /// and conversation import.
     pub(super) fn render_backup_panel(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
         let settings = self.state.backup_settings.clone().unwrap_or_default();
         let backups = &self.state.backups;
@@ -26,6 +27,7 @@ impl SettingsView {
             .child(self.render_backup_status_section(last_backup, backups.len(), status, cx))
             .child(self.render_backup_actions_section(in_progress, cx))
             .child(self.render_restore_section(backups, selected_backup_id, cx))
+            .child(self.render_import_section(cx))
     }
 
     /// Backup settings section: enable toggle, interval, max copies, directory.
@@ -0,0 +1,154 @@
+//! Conversation import section of the backup panel.
+
+use super::SettingsView;
+use crate::events::types::UserEvent;
+use crate::services::ImportSummary;
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, MouseButton};
+
+/// Status line shown after an import, e.g.
+/// "ChatGPT: imported 12, skipped 3".
+pub(super) fn import_summary_message(summary: &ImportSummary) -> String {
+    let mut message = format!(
+        "{}: imported {}",
+        summary.source.display_name(),
+        summary.imported
+    );
+    if summary.skipped > 0 {
+        message.push_str(&format!(", skipped {}", summary.skipped));
+    }
+    if summary.failed > 0 {
+        message.push_str(&format!(", {} failed", summary.failed));
+    }
+    message
+}
+
+#[allow(clippy::unused_self)]
+impl SettingsView {
+    /// Import section: pick a ChatGPT, Claude, or Personal Agent export.
+    pub(super) fn render_import_section(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let in_progress = self.state.import_in_progress;
+        let status = self.state.import_status.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(8.0))
+            .child(
+                div()
+                    .text_size(px(Theme::font_size_ui()))
+                    .text_color(Theme::text_primary())
+                    .child("IMPORT CONVERSATIONS"),
+            )
+            .child(
+                div()
+                    .text_size(px(Theme::font_size_ui()))
+                    .text_color(Theme::text_muted())
+                    .child(
+                        "ChatGPT or Claude conversations.json, or a JSON export from this app. \
+                         Conversations already imported are skipped.",
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(8.0))
+                    .child(self.render_import_button(in_progress, cx))
+                    .when_some(status, |d, status| {
+                        d.child(
+                            div()
+                                .text_size(px(Theme::font_size_ui()))
+                                .text_color(Theme::text_muted())
+                                .child(status),
+                        )
+                    }),
+            )
+    }
+
+    fn render_import_button(
+        &self,
+        in_progress: bool,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id("btn-import-conversations")
+            .px(px(16.0))
+            .py(px(8.0))
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .when(!in_progress, |d| {
+                d.hover(|s| s.bg(Theme::accent()).text_color(Theme::accent_fg()))
+            })
+            .bg(if in_progress {
+                Theme::bg_dark()
+            } else {
+                Theme::selection_bg()
+            })
+            .text_size(px(Theme::font_size_ui()))
+            .text_color(if in_progress {
+                Theme::text_muted()
+            } else {
+                Theme::selection_fg()
+            })
+            .child(if in_progress {
+                "Importing..."
+            } else {
+                "Import..."
+            })
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _window, cx| {
+                    if !in_progress {
+                        this.browse_import_file(cx);
+                    }
+                }),
+            )
+    }
+
+    fn browse_import_file(&mut self, cx: &mut gpui::Context<Self>) {
+        let receiver = cx.prompt_for_paths(gpui::PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Import Conversations".into()),
+        });
+        cx.spawn(async move |this, cx| {
+            if let Ok(Ok(Some(paths))) = receiver.await {
+                if let Some(path) = paths.first() {
+                    let path = path.to_string_lossy().to_string();
+                    cx.update(|cx| {
+                        this.update(cx, |view, cx| {
+                            view.state.import_in_progress = true;
+                            view.state.import_status = None;
+                            view.emit(&UserEvent::ImportConversations { path });
+                            cx.notify();
+                        })
+                    })
+                    .ok();
+                }
+            }
+        })
+        .detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ImportSource;
+
+    #[test]
+    fn summary_message_mentions_only_nonzero_counts() {
+        let mut summary = ImportSummary::new(ImportSource::ChatGpt);
+        summary.imported = 12;
+        assert_eq!(import_summary_message(&summary), "ChatGPT: imported 12");
+
+        summary.skipped = 3;
+        summary.failed = 1;
+        assert_eq!(
+            import_summary_message(&summary),
+            "ChatGPT: imported 12, skipped 3, 1 failed"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Approval override editors for `SettingsView`: one-line overrides of the
+//! tool approval policy for the selected profile and for the conversation
+//! open in the chat view.
+
+use uuid::Uuid;
+
+use super::{ActiveField, SettingsView};
+use crate::agent::policy_overrides::{OverrideLayer, PolicyOverride};
+use crate::events::types::UserEvent;
+use gpui::{div, prelude::*, px};
+
+const OVERRIDE_PLACEHOLDER: &str = "e.g. yolo=on reads=off mcp=per_server allow=\"git,cargo test\"";
+
+impl SettingsView {
+    /// Track the conversation open in the chat view, whose override the
+    /// conversation editor changes.
+    pub fn set_active_conversation(&mut self, id: Option<Uuid>, title: String) {
+        let current_id = self
+            .state
+            .active_conversation
+            .as_ref()
+            .map(|(current, _)| *current);
+        if current_id != id {
+            self.state.conversation_override_input.clear();
+        }
+        self.state.active_conversation = id.map(|id| (id, title));
+    }
+
+    /// The id and display name of what `layer` is edited for, if anything.
+    fn override_target(&self, layer: OverrideLayer) -> Option<(Uuid, String)> {
+        match layer {
+            OverrideLayer::Profile => self.state.selected_profile_id.and_then(|id| {
+                self.state
+                    .profiles
+                    .iter()
+                    .find(|profile| profile.id == id)
+                    .map(|profile| (profile.id, profile.name.clone()))
+            }),
+            OverrideLayer::Conversation => self.state.active_conversation.clone(),
+        }
+    }
+
+    const fn override_input(&self, layer: OverrideLayer) -> &String {
+        match layer {
+            OverrideLayer::Profile => &self.state.profile_override_input,
+            OverrideLayer::Conversation => &self.state.conversation_override_input,
+        }
+    }
+
+    /// Replace `layer`'s override with the one typed into its field.
+    pub(super) fn set_policy_override(&mut self, layer: OverrideLayer) {
+        let text = self.override_input(layer).trim();
+        let Some((id, _)) = self.override_target(layer) else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        match PolicyOverride::parse(text) {
+            Ok(policy_override) => self.emit(&UserEvent::SetApprovalPolicyOverride {
+                layer,
+                id,
+                policy_override,
+            }),
+            Err(error) => {
+                self.state.status_message = Some(format!("Invalid override: {error}"));
+                self.state.status_is_error = true;
+            }
+        }
+    }
+
+    fn clear_policy_override(&self, layer: OverrideLayer) {
+        if let Some((id, _)) = self.override_target(layer) {
+            self.emit(&UserEvent::SetApprovalPolicyOverride {
+                layer,
+                id,
+                policy_override: PolicyOverride::default(),
+            });
+        }
+    }
+
+    fn render_override_editor(
+        &self,
+        layer: OverrideLayer,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        let (id_prefix, field, empty_label) = match layer {
+            OverrideLayer::Profile => (
+                "profile-override",
+                ActiveField::ProfileOverrideInput,
+                "select a profile",
+            ),
+            OverrideLayer::Conversation => (
+                "conversation-override",
+                ActiveField::ConversationOverrideInput,
+                "open a conversation",
+            ),
+        };
+        let target = self.override_target(layer);
+        let label = format!(
+            "{} OVERRIDE ({})",
+            layer.label().to_uppercase(),
+            target
+                .as_ref()
+                .map_or(empty_label, |(_, name)| name.as_str())
+        );
+        let current: Vec<String> = target
+            .and_then(|(id, _)| self.state.policy_overrides.get(layer, id))
+            .map(ToString::to_string)
+            .into_iter()
+            .collect();
+
+        self.render_editable_list_section(
+            &label,
+            id_prefix,
+            &current,
+            self.override_input(layer),
+            OVERRIDE_PLACEHOLDER,
+            field,
+            move |this, _| this.clear_policy_override(layer),
+            move |this, cx| {
+                this.set_policy_override(layer);
+                cx.notify();
+            },
+            cx,
+        )
+    }
+
+    /// Overrides replace the global settings above for one profile or one
+    /// conversation; the conversation's override wins over its profile's.
+    pub(super) fn render_policy_overrides_section(
+        &self,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .child(self.render_override_editor(OverrideLayer::Profile, cx))
+            .child(self.render_override_editor(OverrideLayer::Conversation, cx))
+    }
+}
@@ -54,7 +54,7 @@ impl SettingsView {
     }
 
     #[allow(clippy::unused_self)]
-    fn render_prefix_list(
+    pub(super) fn render_prefix_list(
         &self,
         id_prefix: &str,
         entries: &[String],
@@ -128,7 +128,7 @@ impl SettingsView {
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn render_input_row(
+    pub(super) fn render_input_row(
         id: &str,
         value: &str,
         placeholder: &str,
@@ -266,7 +266,7 @@ impl SettingsView {
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn render_editable_list_section(
+    pub(super) fn render_editable_list_section(
         &self,
         label: &str,
         id_prefix: &str,
@@ -304,6 +304,36 @@ impl SettingsView {
             ))
     }
 
+    /// Roots confining the selected profile's file and shell tools.
+    fn render_workspace_roots_section(&self, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let selected = self
+            .state
+            .selected_profile_id
+            .and_then(|id| self.state.profiles.iter().find(|profile| profile.id == id));
+        let label = selected.map_or_else(
+            || "WORKSPACE ROOTS (select a profile)".to_string(),
+            |profile| format!("WORKSPACE ROOTS ({})", profile.name),
+        );
+        let roots = selected
+            .and_then(|profile| self.state.workspace_roots.get(&profile.id))
+            .map_or(&[][..], Vec::as_slice);
+
+        self.render_editable_list_section(
+            &label,
+            "workspace-root",
+            roots,
+            &self.state.workspace_root_input,
+            "e.g. /Users/me/projects/app",
+            ActiveField::WorkspaceRootInput,
+            |this, path| this.remove_workspace_root(path),
+            |this, cx| {
+                this.add_workspace_root();
+                cx.notify();
+            },
+            cx,
+        )
+    }
+
     pub(super) fn render_tool_approval_section(
         &self,
         cx: &mut gpui::Context<Self>,
@@ -339,7 +369,9 @@ impl SettingsView {
                                 .px(px(22.0))
                                 .text_size(px(Theme::font_size_ui()))
                                 .text_color(Theme::warning())
-                                .child("Auto-approves all tool calls except denylisted commands."),
+                                .child(
+                                    "Auto-approves all tool calls except denylisted commands, ask and deny rules, and access outside workspace roots.",
+                                ),
                         )
                     }),
             )
@@ -382,6 +414,9 @@ impl SettingsView {
                 },
                 cx,
             ))
+            .child(self.render_approval_rules_section(cx))
+            .child(self.render_workspace_roots_section(cx))
+            .child(self.render_policy_overrides_section(cx))
             .when_some(self.state.status_message.clone(), |d, msg| {
                 d.child(
                     div()
@@ -0,0 +1,109 @@
+//! Usage panel rendering for `SettingsView`.
+
+use super::SettingsView;
+use crate::models::UsageTotals;
+use crate::ui_gpui::components::usage_format::{format_cost, format_tokens};
+use crate::ui_gpui::theme::Theme;
+use gpui::{div, prelude::*, px, SharedString};
+
+/// One row: a label on the left, tokens and spend on the right.
+fn usage_row(id: SharedString, label: String, totals: &UsageTotals) -> impl IntoElement {
+    let cost = format_cost(totals).unwrap_or_else(|| "unpriced".to_string());
+    div()
+        .id(id)
+        .flex()
+        .items_center()
+        .justify_between()
+        .gap(px(8.0))
+        .px(px(8.0))
+        .py(px(4.0))
+        .rounded(px(4.0))
+        .bg(Theme::bg_dark())
+        .child(
+            div()
+                .flex_1()
+                .overflow_hidden()
+                .text_size(px(Theme::font_size_mono()))
+                .text_color(Theme::text_primary())
+                .child(label),
+        )
+        .child(
+            div()
+                .flex_shrink_0()
+                .text_size(px(Theme::font_size_ui()))
+                .text_color(Theme::text_muted())
+                .child(format!(
+                    "{} in · {} out · {} cached · {cost}",
+                    format_tokens(totals.input_tokens),
+                    format_tokens(totals.output_tokens),
+                    format_tokens(totals.cache_read_tokens + totals.cache_write_tokens),
+                )),
+        )
+}
+
+fn section_heading(title: &'static str) -> impl IntoElement {
+    div()
+        .text_size(px(Theme::font_size_ui()))
+        .text_color(Theme::text_primary())
+        .child(title)
+}
+
+fn empty_note(text: &'static str) -> impl IntoElement {
+    div()
+        .text_size(px(Theme::font_size_ui()))
+        .text_color(Theme::text_muted())
+        .child(text)
+}
+
+impl SettingsView {
+    /// Usage panel: recorded tokens and spend per profile and per day.
+    pub(super) fn render_usage_panel(&self) -> impl IntoElement {
+        let by_profile = &self.state.usage_by_profile;
+        let by_day = &self.state.usage_by_day;
+
+        div()
+            .id("usage-panel-scroll")
+            .flex()
+            .flex_col()
+            .flex_1()
+            .gap(px(16.0))
+            .overflow_y_scroll()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(px(6.0))
+                    .child(section_heading("BY PROFILE"))
+                    .when(by_profile.is_empty(), |d| {
+                        d.child(empty_note("No usage recorded yet"))
+                    })
+                    .children(by_profile.iter().map(|entry| {
+                        usage_row(
+                            SharedString::from(format!("usage-profile-{}", entry.profile_id)),
+                            entry.profile_name.clone(),
+                            &entry.totals,
+                        )
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(px(6.0))
+                    .child(section_heading("LAST 30 DAYS"))
+                    .when(by_day.is_empty(), |d| {
+                        d.child(empty_note("No usage in the last 30 days"))
+                    })
+                    .children(by_day.iter().map(|entry| {
+                        usage_row(
+                            SharedString::from(format!("usage-day-{}", entry.date)),
+                            entry.date.format("%Y-%m-%d").to_string(),
+                            &entry.totals,
+                        )
+                    })),
+            )
+            .child(empty_note(
+                "Prices come from the models.dev registry; unpriced turns count tokens only.",
+            ))
+    }
+}
@@ -487,6 +487,7 @@ async fn tool_approval_policy_updated_applies_all_fields(cx: &mut TestAppContext
                 mcp_approval_mode: crate::agent::McpApprovalMode::PerServer,
                 persistent_allowlist: vec!["git".to_string(), "ls".to_string()],
                 persistent_denylist: vec!["rm".to_string()],
+                rules: Vec::new(),
             },
             cx,
         );
@@ -523,6 +524,7 @@ async fn tool_approval_policy_updated_clears_input_buffers(cx: &mut TestAppConte
                 mcp_approval_mode: crate::agent::McpApprovalMode::PerTool,
                 persistent_allowlist: vec![],
                 persistent_denylist: vec![],
+                rules: Vec::new(),
             },
             cx,
         );
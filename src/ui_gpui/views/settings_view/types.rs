@@ -163,10 +163,12 @@ pub enum SettingsCategory {
     Security,
     McpTools,
     Backup,
+    Usage,
+    Audit,
 }
 
 impl SettingsCategory {
-    pub const ALL: [Self; 7] = [
+    pub const ALL: [Self; 9] = [
         Self::General,
         Self::Appearance,
         Self::Models,
@@ -174,6 +176,8 @@ impl SettingsCategory {
         Self::Security,
         Self::McpTools,
         Self::Backup,
+        Self::Usage,
+        Self::Audit,
     ];
 
     #[must_use]
@@ -186,6 +190,8 @@ impl SettingsCategory {
             Self::Security => "Security",
             Self::McpTools => "MCP Tools",
             Self::Backup => "Backup",
+            Self::Usage => "Usage",
+            Self::Audit => "Audit Log",
         }
     }
 }
@@ -18,6 +18,8 @@ fn make_summary(id: Uuid, title: &str, message_count: usize) -> ConversationSumm
         updated_at: Utc::now(),
         message_count,
         preview: None,
+        parent_id: None,
+        organization: personal_agent::models::ConversationOrganization::default(),
     }
 }
 
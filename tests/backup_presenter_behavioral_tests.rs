@@ -84,6 +84,16 @@ impl BackupService for MockBackupService {
     async fn should_backup(&self) -> ServiceResult<bool> {
         Ok(true)
     }
+
+    async fn create_pre_migration_backup(
+        &self,
+        _from_version: u32,
+        _to_version: u32,
+    ) -> personal_agent::services::ServiceResult<personal_agent::backup::BackupResult> {
+        Ok(personal_agent::backup::BackupResult::Skipped {
+            reason: "pre-migration backups not supported by mock".to_string(),
+        })
+    }
 }
 
 /// Test: `TriggerBackupNow` emits `BackupCompleted` and refreshes list
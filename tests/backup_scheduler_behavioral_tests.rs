@@ -83,6 +83,16 @@ impl BackupService for MockBackupService {
     async fn should_backup(&self) -> ServiceResult<bool> {
         Ok(self.should_backup_returns.load(Ordering::SeqCst))
     }
+
+    async fn create_pre_migration_backup(
+        &self,
+        _from_version: u32,
+        _to_version: u32,
+    ) -> personal_agent::services::ServiceResult<personal_agent::backup::BackupResult> {
+        Ok(personal_agent::backup::BackupResult::Skipped {
+            reason: "pre-migration backups not supported by mock".to_string(),
+        })
+    }
 }
 
 /// Test: Scheduler exits immediately when backups are disabled
@@ -14,6 +14,7 @@ use chrono::Utc;
 use flate2::read::GzDecoder;
 use personal_agent::backup::{BackupResult, DatabaseBackupSettings, RestoreResult};
 use personal_agent::db::spawn_db_thread;
+use personal_agent::models::ConversationFilter;
 use personal_agent::services::app_settings_impl::AppSettingsServiceImpl;
 use personal_agent::services::{
     AppSettingsService, BackupService, BackupServiceImpl, ConversationService,
@@ -405,7 +406,7 @@ async fn restore_backup_recovers_data() {
 
     // Verify we have multiple conversations now
     let all_conv_before = conv_service
-        .list_metadata(None, None)
+        .list_metadata(&ConversationFilter::default(), None, None)
         .await
         .expect("list conversations");
     assert!(
@@ -106,6 +106,7 @@ impl ConversationService for MockConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &personal_agent::models::ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> Result<Vec<ConversationMetadata>, ServiceError> {
@@ -129,6 +130,8 @@ impl ConversationService for MockConversationService {
                     .messages
                     .last()
                     .map(|m| m.content.chars().take(100).collect()),
+                parent_id: None,
+                organization: personal_agent::models::ConversationOrganization::default(),
             })
             .collect())
     }
@@ -151,6 +154,7 @@ impl ConversationService for MockConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<SearchResult>, ServiceError> {
@@ -254,6 +258,18 @@ impl ConversationService for MockConversationService {
         Ok(messages)
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> Result<Option<Message>, ServiceError> {
+        Ok(self
+            .get_messages(conversation_id)
+            .await?
+            .into_iter()
+            .nth(seq))
+    }
+
     #[allow(clippy::significant_drop_tightening)]
     async fn update(
         &self,
@@ -274,6 +290,97 @@ impl ConversationService for MockConversationService {
         }
         Ok(conversation.clone())
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<personal_agent::models::Conversation, personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::ConversationBranch>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::MessageVersionInfo>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 struct MockChatService {
@@ -328,6 +435,33 @@ impl ChatService for MockChatService {
     ) -> Result<(), ServiceError> {
         Ok(())
     }
+
+    async fn regenerate(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = personal_agent::services::ChatStreamEvent> + Send + Unpin>,
+        personal_agent::services::ServiceError,
+    > {
+        Err(personal_agent::services::ServiceError::Internal(
+            "regenerate not supported by mock".to_string(),
+        ))
+    }
+
+    async fn edit_and_resend(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _content: String,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = personal_agent::services::ChatStreamEvent> + Send + Unpin>,
+        personal_agent::services::ServiceError,
+    > {
+        Err(personal_agent::services::ServiceError::Internal(
+            "edit and resend not supported by mock".to_string(),
+        ))
+    }
 }
 
 struct MockProfileService {
@@ -1195,6 +1329,15 @@ async fn select_conversation_replays_messages_and_filters_system_messages() {
         loaded_messages[1].thinking_content.as_deref(),
         Some("reasoning")
     );
+
+    // No versions still reaches the view, so an earlier switcher is cleared.
+    assert!(commands.iter().any(|command| matches!(
+        command,
+        ViewCommand::MessageVersionsLoaded {
+            conversation_id: seen_id,
+            versions,
+        } if *seen_id == conversation_id && versions.is_empty()
+    )));
 }
 
 #[tokio::test]
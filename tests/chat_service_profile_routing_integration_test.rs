@@ -75,6 +75,7 @@ impl ConversationService for StubConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<personal_agent::models::ConversationMetadata>, ServiceError> {
@@ -92,6 +93,7 @@ impl ConversationService for StubConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<personal_agent::models::SearchResult>, ServiceError> {
@@ -140,6 +142,18 @@ impl ConversationService for StubConversationService {
         Ok(vec![])
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> Result<Option<personal_agent::models::Message>, ServiceError> {
+        Ok(self
+            .get_messages(conversation_id)
+            .await?
+            .into_iter()
+            .nth(seq))
+    }
+
     async fn update(
         &self,
         _id: Uuid,
@@ -148,6 +162,97 @@ impl ConversationService for StubConversationService {
     ) -> Result<personal_agent::models::Conversation, ServiceError> {
         Err(ServiceError::NotFound("stub".to_string()))
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<personal_agent::models::Conversation, personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::ConversationBranch>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::MessageVersionInfo>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Profile service with explicit profiles-by-id lookup and a configurable default.
@@ -23,6 +23,8 @@ fn conversation(id: Uuid, title: &str, message_count: usize) -> ConversationSumm
         updated_at: Utc::now(),
         message_count,
         preview: None,
+        parent_id: None,
+        organization: personal_agent::models::ConversationOrganization::default(),
     }
 }
 
@@ -1,3 +1,4 @@
+use personal_agent::models::ConversationFilter;
 use personal_agent::{Config, Message, Result};
 use tempfile::TempDir;
 use uuid::Uuid;
@@ -62,7 +63,7 @@ async fn sqlite_conversation_round_trip_and_list_order() {
         .expect("add assistant message");
 
     let list = service
-        .list_metadata(None, None)
+        .list_metadata(&ConversationFilter::default(), None, None)
         .await
         .expect("list metadata");
     assert_eq!(list.len(), 2);
@@ -86,6 +86,7 @@ impl ConversationService for CoverageConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &personal_agent::models::ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> Result<Vec<ConversationMetadata>, ServiceError> {
@@ -109,6 +110,8 @@ impl ConversationService for CoverageConversationService {
                     .messages
                     .last()
                     .map(|m| m.content.chars().take(100).collect()),
+                parent_id: None,
+                organization: personal_agent::models::ConversationOrganization::default(),
             })
             .collect())
     }
@@ -130,6 +133,7 @@ impl ConversationService for CoverageConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<SearchResult>, ServiceError> {
@@ -177,6 +181,18 @@ impl ConversationService for CoverageConversationService {
         Ok(self.load(conversation_id).await?.messages)
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> Result<Option<Message>, ServiceError> {
+        Ok(self
+            .get_messages(conversation_id)
+            .await?
+            .into_iter()
+            .nth(seq))
+    }
+
     async fn update(
         &self,
         _id: Uuid,
@@ -185,6 +201,97 @@ impl ConversationService for CoverageConversationService {
     ) -> Result<Conversation, ServiceError> {
         Err(ServiceError::NotFound("not implemented".to_string()))
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<personal_agent::models::Conversation, personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::ConversationBranch>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::MessageVersionInfo>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 struct CoverageProfileService {
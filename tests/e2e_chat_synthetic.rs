@@ -51,6 +51,12 @@ fn summarize_events(events: &[personal_agent::StreamEvent]) -> (String, Vec<Stri
                     tool_results.len()
                 )
             }
+            personal_agent::StreamEvent::Usage(usage) => {
+                format!(
+                    "Usage(input={}, output={})",
+                    usage.input_tokens, usage.output_tokens
+                )
+            }
             personal_agent::StreamEvent::Complete {
                 input_tokens,
                 output_tokens,
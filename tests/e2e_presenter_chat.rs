@@ -381,6 +381,41 @@ async fn test_chat_presenter_error_handling() {
         ) -> Result<(), personal_agent::services::ServiceError> {
             Ok(())
         }
+
+        async fn regenerate(
+            &self,
+            _conversation_id: Uuid,
+            _seq: usize,
+        ) -> Result<
+            Box<
+                dyn futures::Stream<Item = personal_agent::services::ChatStreamEvent>
+                    + Send
+                    + Unpin,
+            >,
+            personal_agent::services::ServiceError,
+        > {
+            Err(personal_agent::services::ServiceError::Internal(
+                "regenerate not supported by mock".to_string(),
+            ))
+        }
+
+        async fn edit_and_resend(
+            &self,
+            _conversation_id: Uuid,
+            _seq: usize,
+            _content: String,
+        ) -> Result<
+            Box<
+                dyn futures::Stream<Item = personal_agent::services::ChatStreamEvent>
+                    + Send
+                    + Unpin,
+            >,
+            personal_agent::services::ServiceError,
+        > {
+            Err(personal_agent::services::ServiceError::Internal(
+                "edit and resend not supported by mock".to_string(),
+            ))
+        }
     }
 
     let chat_service: Arc<dyn ChatService> = Arc::new(FailingChatService);
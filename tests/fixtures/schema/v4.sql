@@ -0,0 +1,140 @@
+-- Conversation database as written by schema version 4 (pinning, archive,
+-- folders and tags, before the usage ledger). Used by
+-- tests/sqlite_schema_migration_tests.rs; never edit this file to match newer
+-- schemas.
+
+CREATE TABLE conversations (
+    id              TEXT PRIMARY KEY,
+    title           TEXT,
+    profile_id      TEXT,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    context_state   TEXT,
+    parent_id       TEXT REFERENCES conversations(id) ON DELETE SET NULL,
+    parent_seq      INTEGER,
+    pinned          INTEGER NOT NULL DEFAULT 0,
+    archived        INTEGER NOT NULL DEFAULT 0,
+    folder          TEXT
+);
+
+CREATE TABLE messages (
+    id                  INTEGER PRIMARY KEY,
+    conversation_id     TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    role                TEXT NOT NULL CHECK(role IN ('user', 'assistant', 'system')),
+    content             TEXT NOT NULL,
+    thinking_content    TEXT,
+    model_id            TEXT,
+    tool_calls          TEXT,
+    tool_results        TEXT,
+    created_at          TEXT NOT NULL,
+    seq                 INTEGER NOT NULL
+);
+
+CREATE VIRTUAL TABLE search_index USING fts5(
+    title,
+    content,
+    conversation_id UNINDEXED,
+    message_rowid UNINDEXED
+);
+
+CREATE TRIGGER messages_ai AFTER INSERT ON messages BEGIN
+    INSERT INTO search_index(title, content, conversation_id, message_rowid)
+    SELECT c.title, NEW.content, NEW.conversation_id, NEW.id
+    FROM conversations c WHERE c.id = NEW.conversation_id;
+END;
+
+CREATE TRIGGER messages_au AFTER UPDATE OF content ON messages BEGIN
+    DELETE FROM search_index WHERE message_rowid = OLD.id;
+    INSERT INTO search_index(title, content, conversation_id, message_rowid)
+    SELECT c.title, NEW.content, NEW.conversation_id, NEW.id
+    FROM conversations c WHERE c.id = NEW.conversation_id;
+END;
+
+CREATE TRIGGER messages_ad AFTER DELETE ON messages BEGIN
+    DELETE FROM search_index WHERE message_rowid = OLD.id;
+END;
+
+CREATE TRIGGER conversations_title_au AFTER UPDATE OF title ON conversations BEGIN
+    UPDATE search_index SET title = NEW.title WHERE conversation_id = NEW.id;
+END;
+
+CREATE TRIGGER conversations_ad AFTER DELETE ON conversations BEGIN
+    DELETE FROM search_index WHERE conversation_id = OLD.id;
+END;
+
+CREATE INDEX idx_conversations_updated ON conversations(updated_at DESC);
+CREATE INDEX idx_conversations_profile ON conversations(profile_id);
+CREATE UNIQUE INDEX idx_messages_ordering ON messages(conversation_id, seq);
+CREATE INDEX idx_messages_conversation_ts ON messages(conversation_id, created_at);
+CREATE INDEX idx_conversations_parent ON conversations(parent_id);
+
+CREATE TABLE message_versions (
+    id                  INTEGER PRIMARY KEY,
+    conversation_id     TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    anchor_seq          INTEGER NOT NULL,
+    version             INTEGER NOT NULL,
+    owner_anchor_seq    INTEGER,
+    owner_version       INTEGER,
+    seq                 INTEGER NOT NULL,
+    role                TEXT NOT NULL CHECK(role IN ('user', 'assistant', 'system')),
+    content             TEXT NOT NULL,
+    thinking_content    TEXT,
+    model_id            TEXT,
+    tool_calls          TEXT,
+    tool_results        TEXT,
+    created_at          TEXT NOT NULL
+);
+
+CREATE INDEX idx_message_versions_anchor ON message_versions(conversation_id, anchor_seq, version);
+
+CREATE TRIGGER message_versions_ai AFTER INSERT ON message_versions BEGIN
+    INSERT INTO search_index(title, content, conversation_id, message_rowid)
+    SELECT c.title, NEW.content, NEW.conversation_id, -NEW.id
+    FROM conversations c WHERE c.id = NEW.conversation_id;
+END;
+
+CREATE TRIGGER message_versions_ad AFTER DELETE ON message_versions BEGIN
+    DELETE FROM search_index WHERE message_rowid = -OLD.id;
+END;
+
+CREATE INDEX idx_conversations_folder ON conversations(folder);
+
+CREATE TABLE conversation_tags (
+    conversation_id     TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    tag                 TEXT NOT NULL,
+    PRIMARY KEY (conversation_id, tag)
+);
+
+CREATE INDEX idx_conversation_tags_tag ON conversation_tags(tag);
+
+INSERT INTO conversations (id, title, profile_id, created_at, updated_at)
+VALUES ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 'Trip plan',
+        '00000000-0000-0000-0000-000000000001',
+        '2026-01-05T10:00:00+00:00', '2026-01-05T10:05:00+00:00');
+
+INSERT INTO messages (conversation_id, role, content, created_at, seq) VALUES
+    ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 'user', 'where to?', '2026-01-05T10:00:00+00:00', 0),
+    ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 'assistant', 'Lisbon', '2026-01-05T10:01:00+00:00', 1),
+    ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 'user', 'when?', '2026-01-05T10:02:00+00:00', 2),
+    ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 'assistant', 'May', '2026-01-05T10:03:00+00:00', 3);
+
+INSERT INTO conversations (id, title, profile_id, created_at, updated_at, parent_id, parent_seq)
+VALUES ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a02', 'Trip plan (branch)',
+        '00000000-0000-0000-0000-000000000001',
+        '2026-01-06T09:00:00+00:00', '2026-01-06T09:00:00+00:00',
+        '6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 1);
+
+INSERT INTO messages (conversation_id, role, content, created_at, seq) VALUES
+    ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a02', 'user', 'where to?', '2026-01-05T10:00:00+00:00', 0),
+    ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a02', 'assistant', 'Lisbon', '2026-01-05T10:01:00+00:00', 1);
+
+INSERT INTO message_versions (conversation_id, anchor_seq, version, seq, role, content, created_at)
+VALUES ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a02', 1, 0, 1, 'assistant', 'Porto', '2026-01-06T09:01:00+00:00');
+
+UPDATE conversations SET pinned = 1, folder = 'Travel'
+WHERE id = '6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01';
+
+INSERT INTO conversation_tags (conversation_id, tag)
+VALUES ('6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01', 'lisbon');
+
+PRAGMA user_version = 4;
@@ -7,6 +7,7 @@ use tokio::time::{sleep, timeout, Duration};
 use uuid::Uuid;
 
 use chrono::{DateTime, Utc};
+use personal_agent::agent::policy_overrides::{OverrideLayer, PolicyOverride, PolicyOverrides};
 use personal_agent::backup::{BackupInfo, BackupResult, DatabaseBackupSettings, RestoreResult};
 use personal_agent::events::{
     bus::EventBus,
@@ -70,6 +71,7 @@ impl ConversationService for MockConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<ConversationMetadata>, ServiceError> {
@@ -87,6 +89,7 @@ impl ConversationService for MockConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<SearchResult>, ServiceError> {
@@ -131,6 +134,18 @@ impl ConversationService for MockConversationService {
         Ok(vec![])
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> Result<Option<Message>, ServiceError> {
+        Ok(self
+            .get_messages(conversation_id)
+            .await?
+            .into_iter()
+            .nth(seq))
+    }
+
     async fn update(
         &self,
         _id: Uuid,
@@ -139,6 +154,97 @@ impl ConversationService for MockConversationService {
     ) -> Result<Conversation, ServiceError> {
         Err(ServiceError::NotFound("not implemented".to_string()))
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<personal_agent::models::Conversation, personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::ConversationBranch>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::MessageVersionInfo>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 struct MockBackupService;
@@ -178,6 +284,16 @@ impl BackupService for MockBackupService {
     async fn should_backup(&self) -> personal_agent::services::ServiceResult<bool> {
         Ok(false)
     }
+
+    async fn create_pre_migration_backup(
+        &self,
+        _from_version: u32,
+        _to_version: u32,
+    ) -> personal_agent::services::ServiceResult<personal_agent::backup::BackupResult> {
+        Ok(personal_agent::backup::BackupResult::Skipped {
+            reason: "pre-migration backups not supported by mock".to_string(),
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -656,6 +772,48 @@ mod history_presenter_tests {
         assert_mpsc_no_command(&mut view_rx).await;
     }
 
+    #[tokio::test]
+    async fn delete_conversation_removes_its_policy_override() {
+        let event_bus = Arc::new(EventBus::new(16));
+        let conversation_service = Arc::new(MockConversationService::new(vec![Ok(())]));
+        let app_settings = Arc::new(MockAppSettingsService::new(None));
+        let id = Uuid::new_v4();
+        let kept = Uuid::new_v4();
+        let mut overrides = PolicyOverrides::default();
+        for conversation_id in [id, kept] {
+            overrides.set(
+                OverrideLayer::Conversation,
+                conversation_id,
+                PolicyOverride::parse("yolo=on").unwrap(),
+            );
+        }
+        overrides
+            .save_to_settings(app_settings.as_ref())
+            .await
+            .unwrap();
+
+        let (view_tx, mut view_rx) = mpsc::channel(16);
+        let mut presenter = HistoryPresenter::new(event_bus.clone(), conversation_service, view_tx)
+            .with_app_settings_service(app_settings.clone());
+        presenter.start().await.expect("start should succeed");
+
+        publish_history_event(
+            &event_bus,
+            AppEvent::User(UserEvent::DeleteConversation { id }),
+        )
+        .await;
+        assert_eq!(
+            recv_mpsc_command(&mut view_rx).await,
+            ViewCommand::ConversationDeleted { id }
+        );
+
+        let overrides = PolicyOverrides::load_from_settings(app_settings.as_ref())
+            .await
+            .unwrap();
+        assert!(overrides.get(OverrideLayer::Conversation, id).is_none());
+        assert!(overrides.get(OverrideLayer::Conversation, kept).is_some());
+    }
+
     #[tokio::test]
     async fn delete_conversation_failure_logs_only_and_emits_no_command() {
         let event_bus = Arc::new(EventBus::new(16));
@@ -768,6 +926,7 @@ mod history_presenter_tests {
 
 mod settings_presenter_tests {
     use super::*;
+    use personal_agent::agent::{ApprovalRule, RuleEffect};
     use personal_agent::ui_gpui::theme::{active_theme_slug, set_active_theme_slug};
 
     static THEME_RUNTIME_TEST_LOCK: std::sync::LazyLock<Mutex<()>> =
@@ -2467,4 +2626,52 @@ mod settings_presenter_tests {
             other => panic!("expected ToolApprovalPolicyUpdated, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn converting_prefixes_to_rules_replaces_the_list_entries() {
+        let profile = make_profile(Uuid::new_v4(), "default", "openai", "gpt-4");
+        let profile_service = MockProfileService::new(vec![profile.clone()], Some(profile.id));
+        let app_settings_service = MockAppSettingsService::new(Some(profile.id));
+        let (mut presenter, event_tx, mut view_rx, _ps, _as) =
+            setup_settings_presenter(profile_service, app_settings_service);
+
+        presenter.start().await.expect("start");
+        drain_startup(&mut view_rx).await;
+
+        let _ = event_tx.send(AppEvent::User(UserEvent::AddToolApprovalAllowlistPrefix {
+            prefix: "git".to_string(),
+        }));
+        sleep(PROCESSING_DELAY).await;
+        let _ = recv_broadcast_command(&mut view_rx).await; // ToolApprovalPolicyUpdated
+        let _ = recv_broadcast_command(&mut view_rx).await; // YoloModeChanged
+
+        let _ = event_tx.send(AppEvent::User(
+            UserEvent::ConvertToolApprovalPrefixesToRules,
+        ));
+        sleep(PROCESSING_DELAY).await;
+
+        let expected = ApprovalRule::from_prefix(RuleEffect::Allow, "git");
+        match recv_broadcast_command(&mut view_rx).await {
+            ViewCommand::ToolApprovalPolicyUpdated {
+                persistent_allowlist,
+                rules,
+                ..
+            } => {
+                assert!(persistent_allowlist.is_empty());
+                assert_eq!(rules, vec![expected.clone()]);
+            }
+            other => panic!("expected ToolApprovalPolicyUpdated, got {other:?}"),
+        }
+        let _ = recv_broadcast_command(&mut view_rx).await; // YoloModeChanged
+
+        let _ = event_tx.send(AppEvent::User(UserEvent::RemoveToolApprovalRule {
+            rule: expected,
+        }));
+        sleep(PROCESSING_DELAY).await;
+
+        match recv_broadcast_command(&mut view_rx).await {
+            ViewCommand::ToolApprovalPolicyUpdated { rules, .. } => assert!(rules.is_empty()),
+            other => panic!("expected ToolApprovalPolicyUpdated, got {other:?}"),
+        }
+    }
 }
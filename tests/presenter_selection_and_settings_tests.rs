@@ -54,6 +54,7 @@ impl ConversationService for SelectConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<ConversationMetadata>, ServiceError> {
@@ -71,6 +72,7 @@ impl ConversationService for SelectConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> Result<Vec<SearchResult>, ServiceError> {
@@ -121,6 +123,18 @@ impl ConversationService for SelectConversationService {
         }
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> Result<Option<Message>, ServiceError> {
+        Ok(self
+            .get_messages(conversation_id)
+            .await?
+            .into_iter()
+            .nth(seq))
+    }
+
     async fn update(
         &self,
         _id: Uuid,
@@ -129,6 +143,97 @@ impl ConversationService for SelectConversationService {
     ) -> Result<Conversation, ServiceError> {
         Err(ServiceError::NotFound("not implemented".to_string()))
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<personal_agent::models::Conversation, personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::ConversationBranch>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::MessageVersionInfo>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 struct MockChatService;
@@ -165,6 +270,33 @@ impl ChatService for MockChatService {
     ) -> Result<(), ServiceError> {
         Ok(())
     }
+
+    async fn regenerate(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = personal_agent::services::ChatStreamEvent> + Send + Unpin>,
+        personal_agent::services::ServiceError,
+    > {
+        Err(personal_agent::services::ServiceError::Internal(
+            "regenerate not supported by mock".to_string(),
+        ))
+    }
+
+    async fn edit_and_resend(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _content: String,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = personal_agent::services::ChatStreamEvent> + Send + Unpin>,
+        personal_agent::services::ServiceError,
+    > {
+        Err(personal_agent::services::ServiceError::Internal(
+            "edit and resend not supported by mock".to_string(),
+        ))
+    }
 }
 
 struct EmptyProfileService;
@@ -392,6 +524,16 @@ impl BackupService for MockBackupService {
     async fn should_backup(&self) -> personal_agent::services::ServiceResult<bool> {
         Ok(false)
     }
+
+    async fn create_pre_migration_backup(
+        &self,
+        _from_version: u32,
+        _to_version: u32,
+    ) -> personal_agent::services::ServiceResult<personal_agent::backup::BackupResult> {
+        Ok(personal_agent::backup::BackupResult::Skipped {
+            reason: "pre-migration backups not supported by mock".to_string(),
+        })
+    }
 }
 
 #[tokio::test]
@@ -532,6 +674,8 @@ async fn startup_and_manual_selection_converge_on_one_authoritative_delivery_pat
             updated_at: Utc::now(),
             message_count: 2,
             preview: None,
+            parent_id: None,
+            organization: personal_agent::models::ConversationOrganization::default(),
         }],
         selected_conversation: Some(StartupSelectedConversation {
             conversation_id: conv_id,
@@ -16,6 +16,8 @@ fn conversation_summary(id: Uuid, title: &str, message_count: usize) -> Conversa
         updated_at: Utc::now(),
         message_count,
         preview: None,
+        parent_id: None,
+        organization: personal_agent::models::ConversationOrganization::default(),
     }
 }
 
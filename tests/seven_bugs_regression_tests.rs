@@ -63,6 +63,7 @@ impl ConversationService for InMemoryConversationService {
 
     async fn list_metadata(
         &self,
+        _filter: &personal_agent::models::ConversationFilter,
         limit: Option<usize>,
         offset: Option<usize>,
     ) -> ServiceResult<Vec<ConversationMetadata>> {
@@ -86,6 +87,8 @@ impl ConversationService for InMemoryConversationService {
                     .messages
                     .last()
                     .map(|m| m.content.chars().take(100).collect()),
+                parent_id: None,
+                organization: personal_agent::models::ConversationOrganization::default(),
             })
             .collect())
     }
@@ -103,6 +106,7 @@ impl ConversationService for InMemoryConversationService {
     async fn search(
         &self,
         _query: &str,
+        _filter: &personal_agent::models::ConversationFilter,
         _limit: Option<usize>,
         _offset: Option<usize>,
     ) -> ServiceResult<Vec<SearchResult>> {
@@ -164,6 +168,18 @@ impl ConversationService for InMemoryConversationService {
         Ok(conv.messages.clone())
     }
 
+    async fn get_message(
+        &self,
+        conversation_id: Uuid,
+        seq: usize,
+    ) -> ServiceResult<Option<Message>> {
+        Ok(self
+            .get_messages(conversation_id)
+            .await?
+            .into_iter()
+            .nth(seq))
+    }
+
     async fn update(
         &self,
         id: Uuid,
@@ -183,6 +199,97 @@ impl ConversationService for InMemoryConversationService {
         }
         Ok(conv.clone())
     }
+
+    async fn fork_at(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<personal_agent::models::Conversation, personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "branching not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_branches(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::ConversationBranch>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn begin_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn switch_message_version(
+        &self,
+        _conversation_id: Uuid,
+        _seq: usize,
+        _version: u32,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Err(personal_agent::services::ServiceError::NotFound(
+            "message versions not supported by mock".to_string(),
+        ))
+    }
+
+    async fn list_message_versions(
+        &self,
+        _conversation_id: Uuid,
+    ) -> Result<
+        Vec<personal_agent::models::MessageVersionInfo>,
+        personal_agent::services::ServiceError,
+    > {
+        Ok(Vec::new())
+    }
+
+    async fn set_pinned(
+        &self,
+        _id: Uuid,
+        _pinned: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_archived(
+        &self,
+        _id: Uuid,
+        _archived: bool,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_folder(
+        &self,
+        _id: Uuid,
+        _folder: Option<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn set_tags(
+        &self,
+        _id: Uuid,
+        _tags: Vec<String>,
+    ) -> Result<(), personal_agent::services::ServiceError> {
+        Ok(())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_folders(&self) -> Result<Vec<String>, personal_agent::services::ServiceError> {
+        Ok(Vec::new())
+    }
 }
 
 // ======================================================================
@@ -203,6 +310,8 @@ fn bug1_rename_enters_edit_mode_on_active_conversation() {
         updated_at: chrono::Utc::now(),
         message_count: 3,
         preview: None,
+        parent_id: None,
+        organization: personal_agent::models::ConversationOrganization::default(),
     }];
 
     // Simulate start_rename_conversation logic
@@ -235,6 +344,8 @@ fn bug1_rename_submit_updates_title_everywhere() {
         updated_at: chrono::Utc::now(),
         message_count: 2,
         preview: None,
+        parent_id: None,
+        organization: personal_agent::models::ConversationOrganization::default(),
     }];
 
     let new_title = state.conversation_title_input.trim().to_string();
@@ -0,0 +1,228 @@
+//! Integration tests for conversation branching (`fork_at` / `list_branches`)
+//! on `SqliteConversationService` against a real `SQLite` database.
+
+use std::sync::Arc;
+
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::spawn_db_thread;
+use personal_agent::models::{ConversationFilter, Message, MessageVersionInfo};
+use personal_agent::services::{ConversationService, ServiceError, SqliteConversationService};
+
+async fn make_service(dir: &TempDir) -> Arc<SqliteConversationService> {
+    let db_path = dir.path().join("test.db");
+    let handle = tokio::task::spawn_blocking(move || {
+        spawn_db_thread(&db_path).expect("spawn_db_thread failed")
+    })
+    .await
+    .expect("spawn_blocking failed");
+    Arc::new(SqliteConversationService::new(handle))
+}
+
+/// Create a conversation with four alternating user/assistant messages.
+async fn seeded_conversation(svc: &SqliteConversationService) -> Uuid {
+    let conv = svc
+        .create(Some("Trip plan".to_string()), Uuid::new_v4())
+        .await
+        .unwrap();
+    for (i, text) in ["where to?", "Lisbon", "when?", "May"].iter().enumerate() {
+        let message = if i % 2 == 0 {
+            Message::user((*text).to_string())
+        } else {
+            Message::assistant((*text).to_string())
+        };
+        svc.add_message(conv.id, message).await.unwrap();
+    }
+    conv.id
+}
+
+#[tokio::test]
+async fn fork_at_copies_prefix_and_links_parent() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = seeded_conversation(&svc).await;
+
+    let branch = svc.fork_at(parent, 1).await.expect("fork_at failed");
+
+    assert_ne!(branch.id, parent);
+    assert_eq!(branch.title.as_deref(), Some("Trip plan (branch)"));
+    let contents: Vec<_> = branch.messages.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, ["where to?", "Lisbon"]);
+
+    let original = svc.get_messages(parent).await.unwrap();
+    assert_eq!(original.len(), 4, "parent must be left untouched");
+
+    let metadata = svc
+        .list_metadata(&ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    let branch_meta = metadata.iter().find(|m| m.id == branch.id).unwrap();
+    assert_eq!(branch_meta.parent_id, Some(parent));
+    let parent_meta = metadata.iter().find(|m| m.id == parent).unwrap();
+    assert_eq!(parent_meta.parent_id, None);
+
+    // Versions anchored inside the copied prefix come along; later ones stay.
+    svc.begin_message_version(parent, 1).await.unwrap();
+    for message in [
+        Message::assistant("Porto".to_string()),
+        Message::user("when?".to_string()),
+        Message::assistant("May".to_string()),
+    ] {
+        svc.add_message(parent, message).await.unwrap();
+    }
+    svc.begin_message_version(parent, 3).await.unwrap();
+    svc.add_message(parent, Message::assistant("June".to_string()))
+        .await
+        .unwrap();
+
+    let branch = svc.fork_at(parent, 1).await.expect("fork_at failed");
+    assert_eq!(
+        svc.list_message_versions(branch.id).await.unwrap(),
+        [MessageVersionInfo {
+            seq: 1,
+            selected: Some(1),
+            count: 2,
+        }]
+    );
+    svc.switch_message_version(branch.id, 1, 0).await.unwrap();
+    let contents: Vec<_> = svc
+        .get_messages(branch.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(contents, ["where to?", "Lisbon", "when?", "May"]);
+    assert_eq!(svc.list_message_versions(parent).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn branch_accepts_new_messages_independently() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = seeded_conversation(&svc).await;
+
+    let branch = svc.fork_at(parent, 1).await.unwrap();
+    svc.add_message(branch.id, Message::user("actually, Porto".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(svc.get_messages(branch.id).await.unwrap().len(), 3);
+    assert_eq!(svc.get_messages(parent).await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn fork_at_missing_seq_is_not_found() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = seeded_conversation(&svc).await;
+
+    let err = svc.fork_at(parent, 10).await.unwrap_err();
+    assert!(matches!(err, ServiceError::NotFound(_)), "got {err:?}");
+
+    let err = svc.fork_at(Uuid::new_v4(), 0).await.unwrap_err();
+    assert!(matches!(err, ServiceError::NotFound(_)), "got {err:?}");
+
+    let metadata = svc
+        .list_metadata(&ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(metadata.len(), 1, "failed forks must not leave rows behind");
+}
+
+#[tokio::test]
+async fn list_branches_returns_whole_tree_root_first() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let root = seeded_conversation(&svc).await;
+
+    let first = svc.fork_at(root, 1).await.unwrap();
+    let nested = svc.fork_at(first.id, 0).await.unwrap();
+    let second = svc.fork_at(root, 3).await.unwrap();
+
+    // The same tree is reported no matter which member is asked.
+    for member in [root, first.id, nested.id, second.id] {
+        let branches = svc.list_branches(member).await.unwrap();
+        let ids: Vec<_> = branches.iter().map(|b| b.id).collect();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(ids[0], root);
+        assert!(ids.contains(&first.id));
+        assert!(ids.contains(&nested.id));
+        assert!(ids.contains(&second.id));
+    }
+
+    let branches = svc.list_branches(root).await.unwrap();
+    let nested_entry = branches.iter().find(|b| b.id == nested.id).unwrap();
+    assert_eq!(nested_entry.parent_id, Some(first.id));
+    assert_eq!(nested_entry.parent_seq, Some(0));
+    assert_eq!(nested_entry.message_count, 1);
+}
+
+#[tokio::test]
+async fn list_branches_for_unbranched_conversation_is_just_itself() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    let branches = svc.list_branches(conv).await.unwrap();
+    assert_eq!(branches.len(), 1);
+    assert_eq!(branches[0].id, conv);
+    assert_eq!(branches[0].parent_id, None);
+
+    let err = svc.list_branches(Uuid::new_v4()).await.unwrap_err();
+    assert!(matches!(err, ServiceError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn list_branches_reports_a_parent_cycle() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = seeded_conversation(&svc).await;
+    let branch = svc.fork_at(parent, 1).await.unwrap();
+
+    let conn = rusqlite::Connection::open(dir.path().join("test.db")).unwrap();
+    conn.execute(
+        "UPDATE conversations SET parent_id = ?1 WHERE id = ?2",
+        [branch.id.to_string(), parent.to_string()],
+    )
+    .unwrap();
+
+    let err = svc.list_branches(branch.id).await.unwrap_err();
+    assert!(
+        matches!(&err, ServiceError::Storage(message) if message.contains("cycle")),
+        "got {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn deleting_parent_keeps_branch_as_new_root() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = seeded_conversation(&svc).await;
+    let branch = svc.fork_at(parent, 2).await.unwrap();
+
+    svc.delete(parent).await.unwrap();
+
+    let loaded = svc.load(branch.id).await.expect("branch should survive");
+    assert_eq!(loaded.messages.len(), 3);
+    let branches = svc.list_branches(branch.id).await.unwrap();
+    assert_eq!(branches.len(), 1);
+    assert_eq!(branches[0].parent_id, None);
+}
+
+#[tokio::test]
+async fn branch_messages_are_searchable() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = seeded_conversation(&svc).await;
+    let branch = svc.fork_at(parent, 1).await.unwrap();
+
+    let results = svc
+        .search("Lisbon", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    let ids: Vec<_> = results.iter().map(|r| r.conversation_id).collect();
+    assert!(ids.contains(&parent));
+    assert!(ids.contains(&branch.id));
+}
@@ -0,0 +1,188 @@
+//! Integration tests for `ConversationImportServiceImpl`: importing exports
+//! through `SqliteConversationService`, deduplicating re-imports, and keeping
+//! imported messages searchable.
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::{spawn_db_thread, DbHandle};
+use personal_agent::models::{ConversationFilter, Message};
+use personal_agent::services::{
+    ConversationImportService, ConversationImportServiceImpl, ConversationService, ImportSource,
+    SqliteConversationService,
+};
+
+async fn open_db(dir: &TempDir) -> DbHandle {
+    let db_path = dir.path().join("test.db");
+    tokio::task::spawn_blocking(move || spawn_db_thread(&db_path).expect("spawn_db_thread failed"))
+        .await
+        .expect("spawn_blocking failed")
+}
+
+fn services(db: DbHandle) -> (Arc<dyn ConversationService>, ConversationImportServiceImpl) {
+    let conversations: Arc<dyn ConversationService> =
+        Arc::new(SqliteConversationService::new(db.clone()));
+    let importer = ConversationImportServiceImpl::new(db, conversations.clone());
+    (conversations, importer)
+}
+
+const CLAUDE_EXPORT: &str = r#"[
+  {
+    "uuid": "claude-1",
+    "name": "Sourdough",
+    "created_at": "2024-02-01T09:00:00Z",
+    "updated_at": "2024-02-01T09:10:00Z",
+    "chat_messages": [
+      { "uuid": "m1", "sender": "human", "text": "How long to proof sourdough?",
+        "content": [{ "type": "text", "text": "How long to proof sourdough?" }],
+        "created_at": "2024-02-01T09:00:00Z" },
+      { "uuid": "m2", "sender": "assistant", "text": "About twelve hours.",
+        "content": [{ "type": "text", "text": "About twelve hours." }],
+        "created_at": "2024-02-01T09:00:05Z" }
+    ]
+  },
+  {
+    "uuid": "claude-2",
+    "name": "Empty",
+    "chat_messages": []
+  }
+]"#;
+
+#[tokio::test]
+async fn claude_export_imports_once_and_is_searchable() {
+    let dir = TempDir::new().unwrap();
+    let (conversations, importer) = services(open_db(&dir).await);
+    let profile = Uuid::new_v4();
+
+    let summary = importer.import_json(CLAUDE_EXPORT, profile).await.unwrap();
+    assert_eq!(summary.source, ImportSource::Claude);
+    assert_eq!(summary.imported, 1);
+    assert_eq!(summary.skipped, 1);
+    assert_eq!(summary.failed, 0);
+
+    let listed = conversations
+        .list_metadata(&ConversationFilter::all(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].title.as_deref(), Some("Sourdough"));
+    assert_eq!(
+        listed[0].created_at,
+        Utc.with_ymd_and_hms(2024, 2, 1, 9, 0, 0).unwrap()
+    );
+    assert_eq!(
+        listed[0].updated_at,
+        Utc.with_ymd_and_hms(2024, 2, 1, 9, 10, 0).unwrap()
+    );
+
+    let loaded = conversations.load(listed[0].id).await.unwrap();
+    assert_eq!(loaded.profile_id, profile);
+    assert_eq!(loaded.messages.len(), 2);
+    assert_eq!(loaded.messages[1].content, "About twelve hours.");
+
+    let hits = conversations
+        .search("proof", &ConversationFilter::all(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+
+    let again = importer.import_json(CLAUDE_EXPORT, profile).await.unwrap();
+    assert_eq!(again.imported, 0);
+    assert_eq!(again.skipped, 2);
+    let listed = conversations
+        .list_metadata(&ConversationFilter::all(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+}
+
+#[tokio::test]
+async fn deleted_imports_can_be_imported_again() {
+    let dir = TempDir::new().unwrap();
+    let (conversations, importer) = services(open_db(&dir).await);
+    let profile = Uuid::new_v4();
+
+    importer.import_json(CLAUDE_EXPORT, profile).await.unwrap();
+    let listed = conversations
+        .list_metadata(&ConversationFilter::all(), None, None)
+        .await
+        .unwrap();
+    conversations.delete(listed[0].id).await.unwrap();
+
+    let summary = importer.import_json(CLAUDE_EXPORT, profile).await.unwrap();
+    assert_eq!(summary.imported, 1);
+}
+
+#[tokio::test]
+async fn own_json_export_round_trips_into_another_database() {
+    let source_dir = TempDir::new().unwrap();
+    let (source, same_db) = services(open_db(&source_dir).await);
+    let profile = Uuid::new_v4();
+    let original = source
+        .create(Some("Trip plan".to_string()), profile)
+        .await
+        .unwrap();
+    let mut reply = Message::assistant_with_thinking("Go in May.".into(), "Weather".into());
+    reply.tool_calls = Some(r#"[{"id":"t1","name":"search","input":{}}]"#.to_string());
+    source
+        .add_message(original.id, Message::user("When to visit Kyoto?".into()))
+        .await
+        .unwrap();
+    source.add_message(original.id, reply).await.unwrap();
+    let exported = serde_json::to_string_pretty(&source.load(original.id).await.unwrap()).unwrap();
+
+    // Re-importing into the database it came from is a no-op.
+    let summary = same_db.import_json(&exported, profile).await.unwrap();
+    assert_eq!(summary.source, ImportSource::PersonalAgent);
+    assert_eq!(summary.skipped, 1);
+
+    let target_dir = TempDir::new().unwrap();
+    let (target, importer) = services(open_db(&target_dir).await);
+    let summary = importer
+        .import_json(&exported, Uuid::new_v4())
+        .await
+        .unwrap();
+    assert_eq!(summary.imported, 1);
+
+    let listed = target
+        .list_metadata(&ConversationFilter::all(), None, None)
+        .await
+        .unwrap();
+    let imported = target.load(listed[0].id).await.unwrap();
+    assert_eq!(imported.title.as_deref(), Some("Trip plan"));
+    assert_eq!(imported.messages.len(), 2);
+    assert_eq!(
+        imported.messages[1].thinking_content.as_deref(),
+        Some("Weather")
+    );
+    assert!(imported.messages[1].tool_calls.is_some());
+
+    let again = importer
+        .import_json(&exported, Uuid::new_v4())
+        .await
+        .unwrap();
+    assert_eq!(again.skipped, 1);
+}
+
+#[tokio::test]
+async fn unreadable_conversations_are_counted_as_failed() {
+    let dir = TempDir::new().unwrap();
+    let (_, importer) = services(open_db(&dir).await);
+
+    let export = r#"[
+      { "id": "gpt-1", "title": "ok", "mapping": {
+          "root": { "parent": null, "children": ["u"], "message": null },
+          "u": { "parent": "root", "children": [], "message": {
+              "id": "u", "author": { "role": "user" },
+              "content": { "content_type": "text", "parts": ["hello"] } } } },
+        "current_node": "u" },
+      { "title": "no id", "mapping": {} }
+    ]"#;
+    let summary = importer.import_json(export, Uuid::new_v4()).await.unwrap();
+    assert_eq!(summary.source, ImportSource::ChatGpt);
+    assert_eq!(summary.imported, 1);
+    assert_eq!(summary.failed, 1);
+}
@@ -0,0 +1,263 @@
+//! Integration tests for conversation pinning, archive, folders, and tags on
+//! `SqliteConversationService` against a real `SQLite` database.
+
+use std::sync::Arc;
+
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::spawn_db_thread;
+use personal_agent::models::{ArchiveFilter, ConversationFilter, Message};
+use personal_agent::services::{ConversationService, ServiceError, SqliteConversationService};
+
+async fn make_service(dir: &TempDir) -> Arc<SqliteConversationService> {
+    let db_path = dir.path().join("test.db");
+    let handle = tokio::task::spawn_blocking(move || {
+        spawn_db_thread(&db_path).expect("spawn_db_thread failed")
+    })
+    .await
+    .expect("spawn_blocking failed");
+    Arc::new(SqliteConversationService::new(handle))
+}
+
+async fn conversation_with(svc: &SqliteConversationService, title: &str, text: &str) -> Uuid {
+    let conv = svc
+        .create(Some(title.to_string()), Uuid::new_v4())
+        .await
+        .unwrap();
+    svc.add_message(conv.id, Message::user(text.to_string()))
+        .await
+        .unwrap();
+    conv.id
+}
+
+async fn listed_ids(svc: &SqliteConversationService, filter: &ConversationFilter) -> Vec<Uuid> {
+    svc.list_metadata(filter, None, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn organization_round_trips_through_metadata() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let id = conversation_with(&svc, "Trip plan", "Lisbon in May").await;
+
+    svc.set_pinned(id, true).await.unwrap();
+    svc.set_folder(id, Some("  Travel ".to_string()))
+        .await
+        .unwrap();
+    svc.set_tags(id, vec!["#Summer".to_string(), "family trip".to_string()])
+        .await
+        .unwrap();
+
+    let metadata = svc
+        .list_metadata(&ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    let organization = &metadata[0].organization;
+    assert!(organization.pinned);
+    assert!(!organization.archived);
+    assert_eq!(organization.folder.as_deref(), Some("Travel"));
+    assert_eq!(organization.tags, ["family-trip", "summer"]);
+
+    svc.set_folder(id, Some("   ".to_string())).await.unwrap();
+    svc.set_tags(id, Vec::new()).await.unwrap();
+    let metadata = svc
+        .list_metadata(&ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(metadata[0].organization.folder, None);
+    assert!(metadata[0].organization.tags.is_empty());
+}
+
+#[tokio::test]
+async fn archived_conversations_are_hidden_by_default() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let kept = conversation_with(&svc, "Kept", "Lisbon").await;
+    let archived = conversation_with(&svc, "Old", "Lisbon").await;
+    svc.set_archived(archived, true).await.unwrap();
+
+    assert_eq!(
+        listed_ids(&svc, &ConversationFilter::default()).await,
+        [kept]
+    );
+    assert_eq!(listed_ids(&svc, &ConversationFilter::all()).await.len(), 2);
+    let only = ConversationFilter {
+        archived: ArchiveFilter::Only,
+        ..ConversationFilter::default()
+    };
+    assert_eq!(listed_ids(&svc, &only).await, [archived]);
+
+    let hits = svc
+        .search("Lisbon", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    assert!(
+        hits.iter().all(|hit| hit.conversation_id == kept),
+        "{hits:?}"
+    );
+    let hits = svc.search("Lisbon", &only, None, None).await.unwrap();
+    assert!(
+        hits.iter().all(|hit| hit.conversation_id == archived),
+        "{hits:?}"
+    );
+    assert!(!hits.is_empty());
+}
+
+#[tokio::test]
+async fn filters_combine_pinned_folder_and_tags() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let work = conversation_with(&svc, "Release", "ship notes").await;
+    let side = conversation_with(&svc, "Parser", "ship parser").await;
+    let home = conversation_with(&svc, "Garden", "ship seeds").await;
+
+    svc.set_folder(work, Some("Work".to_string()))
+        .await
+        .unwrap();
+    svc.set_folder(side, Some("Work".to_string()))
+        .await
+        .unwrap();
+    svc.set_tags(work, vec!["rust".to_string(), "q3".to_string()])
+        .await
+        .unwrap();
+    svc.set_tags(side, vec!["rust".to_string()]).await.unwrap();
+    svc.set_pinned(home, true).await.unwrap();
+
+    let pinned = ConversationFilter {
+        pinned_only: true,
+        ..ConversationFilter::default()
+    };
+    assert_eq!(listed_ids(&svc, &pinned).await, [home]);
+
+    let folder = ConversationFilter {
+        folder: Some("Work".to_string()),
+        ..ConversationFilter::default()
+    };
+    let mut in_folder = listed_ids(&svc, &folder).await;
+    in_folder.sort();
+    let mut expected = vec![work, side];
+    expected.sort();
+    assert_eq!(in_folder, expected);
+
+    let tagged = ConversationFilter {
+        tags: vec!["RUST".to_string(), "#q3".to_string()],
+        ..ConversationFilter::default()
+    };
+    assert_eq!(listed_ids(&svc, &tagged).await, [work]);
+
+    let hits = svc.search("ship", &tagged, None, None).await.unwrap();
+    assert!(!hits.is_empty());
+    assert!(
+        hits.iter().all(|hit| hit.conversation_id == work),
+        "{hits:?}"
+    );
+}
+
+#[tokio::test]
+async fn admits_normalizes_like_the_sql_filter() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let work = conversation_with(&svc, "Release", "ship notes").await;
+    let side = conversation_with(&svc, "Parser", "ship parser").await;
+    svc.set_folder(work, Some("Work".to_string()))
+        .await
+        .unwrap();
+    svc.set_tags(work, vec!["Rust".to_string()]).await.unwrap();
+    svc.set_tags(side, vec!["rust".to_string()]).await.unwrap();
+
+    let filter = ConversationFilter {
+        folder: Some("  Work ".to_string()),
+        tags: vec![" #RUST  ".to_string()],
+        ..ConversationFilter::default()
+    };
+    let admitted: Vec<Uuid> = svc
+        .list_metadata(&ConversationFilter::all(), None, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|m| filter.admits(&m.organization))
+        .map(|m| m.id)
+        .collect();
+    assert_eq!(admitted, [work]);
+    assert_eq!(listed_ids(&svc, &filter).await, admitted);
+}
+
+#[tokio::test]
+async fn distinct_tags_and_folders_are_listed_sorted() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let a = conversation_with(&svc, "A", "one").await;
+    let b = conversation_with(&svc, "B", "two").await;
+
+    svc.set_folder(a, Some("Work".to_string())).await.unwrap();
+    svc.set_folder(b, Some("Home".to_string())).await.unwrap();
+    svc.set_tags(a, vec!["rust".to_string(), "ideas".to_string()])
+        .await
+        .unwrap();
+    svc.set_tags(b, vec!["rust".to_string()]).await.unwrap();
+
+    assert_eq!(svc.list_folders().await.unwrap(), ["Home", "Work"]);
+    assert_eq!(svc.list_tags().await.unwrap(), ["ideas", "rust"]);
+
+    svc.delete(a).await.unwrap();
+    assert_eq!(svc.list_folders().await.unwrap(), ["Home"]);
+    assert_eq!(svc.list_tags().await.unwrap(), ["rust"]);
+}
+
+#[tokio::test]
+async fn fork_inherits_folder_and_tags_but_not_pin_or_archive() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let parent = conversation_with(&svc, "Trip plan", "where to?").await;
+    svc.add_message(parent, Message::assistant("Lisbon".to_string()))
+        .await
+        .unwrap();
+    svc.set_folder(parent, Some("Travel".to_string()))
+        .await
+        .unwrap();
+    svc.set_tags(parent, vec!["summer".to_string()])
+        .await
+        .unwrap();
+    svc.set_pinned(parent, true).await.unwrap();
+
+    let branch = svc.fork_at(parent, 1).await.unwrap();
+
+    let metadata = svc
+        .list_metadata(&ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    let organization = &metadata
+        .iter()
+        .find(|m| m.id == branch.id)
+        .unwrap()
+        .organization;
+    assert_eq!(organization.folder.as_deref(), Some("Travel"));
+    assert_eq!(organization.tags, ["summer"]);
+    assert!(!organization.pinned);
+}
+
+#[tokio::test]
+async fn unknown_conversation_is_not_found() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let missing = Uuid::new_v4();
+
+    for err in [
+        svc.set_pinned(missing, true).await.unwrap_err(),
+        svc.set_archived(missing, true).await.unwrap_err(),
+        svc.set_folder(missing, Some("Work".to_string()))
+            .await
+            .unwrap_err(),
+        svc.set_tags(missing, vec!["rust".to_string()])
+            .await
+            .unwrap_err(),
+    ] {
+        assert!(matches!(err, ServiceError::NotFound(_)), "got {err:?}");
+    }
+}
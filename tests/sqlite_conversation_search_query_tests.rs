@@ -0,0 +1,219 @@
+//! Integration tests for structured search on `SqliteConversationService`:
+//! operators, phrases, highlighted excerpts, and message positions.
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::spawn_db_thread;
+use personal_agent::models::{ConversationFilter, Message, SearchMatchType, SearchResult};
+use personal_agent::services::{ConversationService, SqliteConversationService};
+
+async fn make_service(dir: &TempDir) -> Arc<SqliteConversationService> {
+    let db_path = dir.path().join("test.db");
+    let handle = tokio::task::spawn_blocking(move || {
+        spawn_db_thread(&db_path).expect("spawn_db_thread failed")
+    })
+    .await
+    .expect("spawn_blocking failed");
+    Arc::new(SqliteConversationService::new(handle))
+}
+
+fn at(mut message: Message, year: i32) -> Message {
+    message.timestamp = Utc.with_ymd_and_hms(year, 3, 1, 12, 0, 0).unwrap();
+    message
+}
+
+fn prompt(text: &str, year: i32) -> Message {
+    at(Message::user(text.to_string()), year)
+}
+
+fn reply(text: &str, model: &str, year: i32) -> Message {
+    let mut message = Message::assistant(text.to_string());
+    message.model_id = Some(model.to_string());
+    at(message, year)
+}
+
+async fn search(svc: &SqliteConversationService, query: &str) -> Vec<SearchResult> {
+    svc.search(query, &ConversationFilter::default(), None, None)
+        .await
+        .unwrap()
+}
+
+async fn ids(svc: &SqliteConversationService, query: &str) -> Vec<Uuid> {
+    let mut ids: Vec<Uuid> = search(svc, query)
+        .await
+        .into_iter()
+        .map(|hit| hit.conversation_id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+/// Two conversations under different profiles: an older one answered by a
+/// Sonnet model and a newer one answered by GPT.
+async fn seed(svc: &SqliteConversationService) -> (Uuid, Uuid, Uuid) {
+    let work_profile = Uuid::new_v4();
+    let old = svc
+        .create(Some("Lifetimes".to_string()), work_profile)
+        .await
+        .unwrap();
+    svc.add_message(old.id, prompt("explain borrowing", 2023))
+        .await
+        .unwrap();
+    svc.add_message(
+        old.id,
+        reply("the borrow checker tracks loans", "claude-sonnet-4", 2023),
+    )
+    .await
+    .unwrap();
+
+    let new = svc
+        .create(Some("Parsers".to_string()), Uuid::new_v4())
+        .await
+        .unwrap();
+    svc.add_message(new.id, prompt("checker for borrow rules?", 2025))
+        .await
+        .unwrap();
+    svc.add_message(new.id, reply("use a borrow graph", "gpt-4o", 2025))
+        .await
+        .unwrap();
+
+    (old.id, new.id, work_profile)
+}
+
+#[tokio::test]
+async fn operators_narrow_matching_messages() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let (old, new, work_profile) = seed(&svc).await;
+    let mut both = vec![old, new];
+    both.sort();
+
+    assert_eq!(ids(&svc, "borrow").await, both);
+    assert_eq!(ids(&svc, "borrow model:SONNET").await, [old]);
+    assert_eq!(
+        ids(&svc, &format!("borrow profile:{work_profile}")).await,
+        [old]
+    );
+    assert_eq!(ids(&svc, "borrow before:2024-01-01").await, [old]);
+    assert_eq!(ids(&svc, "borrow after:2025-03-01").await, [new]);
+    assert_eq!(ids(&svc, "checker role:user").await, [new]);
+    assert_eq!(ids(&svc, "checker role:assistant").await, [old]);
+    assert!(ids(&svc, "borrow profile:Unresolved").await.is_empty());
+
+    svc.set_tags(new, vec!["rust".to_string()]).await.unwrap();
+    assert_eq!(ids(&svc, "borrow tag:#Rust").await, [new]);
+}
+
+#[tokio::test]
+async fn operator_only_queries_list_matching_conversations() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let (old, new, _) = seed(&svc).await;
+
+    let hits = search(&svc, "model:gpt").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].conversation_id, new);
+    assert_eq!(hits[0].match_context, "use a borrow graph");
+    assert_eq!(hits[0].message_index, Some(1));
+
+    assert_eq!(ids(&svc, "before:2024-01-01").await, [old]);
+    assert!(search(&svc, "   ").await.is_empty());
+}
+
+#[tokio::test]
+async fn phrases_match_adjacent_words_only() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let (old, new, _) = seed(&svc).await;
+    let mut both = vec![old, new];
+    both.sort();
+
+    assert_eq!(ids(&svc, "checker borrow").await, both);
+    assert_eq!(ids(&svc, "\"borrow checker\"").await, [old]);
+    assert_eq!(ids(&svc, "lo*").await, [old]);
+}
+
+#[tokio::test]
+async fn hits_carry_highlights_and_message_position() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let (old, _, _) = seed(&svc).await;
+
+    let hits = search(&svc, "loans").await;
+    assert_eq!(hits.len(), 1);
+    let hit = &hits[0];
+    assert_eq!(hit.conversation_id, old);
+    assert_eq!(hit.match_type, SearchMatchType::Content);
+    assert!(hit.title_highlights.is_empty());
+    assert!(!hit.match_context.contains('\u{2}'));
+    let highlighted: Vec<&str> = hit
+        .context_highlights
+        .iter()
+        .map(|range| &hit.match_context[range.clone()])
+        .collect();
+    assert_eq!(highlighted, ["loans"]);
+    assert_eq!(hit.message_index, Some(1));
+
+    let hits = search(&svc, "lifetimes").await;
+    assert_eq!(hits[0].match_type, SearchMatchType::Title);
+    assert_eq!(hits[0].title_highlights, [0..9]);
+}
+
+#[tokio::test]
+async fn archived_versions_match_without_a_position() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let (old, _, _) = seed(&svc).await;
+
+    svc.begin_message_version(old, 1).await.unwrap();
+    svc.add_message(old, Message::assistant("loans are tracked".to_string()))
+        .await
+        .unwrap();
+
+    let hits = search(&svc, "\"borrow checker\"").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].message_index, None);
+
+    let hits = search(&svc, "tracked").await;
+    assert_eq!(hits[0].message_index, Some(1));
+}
+
+#[tokio::test]
+async fn results_are_one_per_conversation_and_paged() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    for year in 2020..2025 {
+        let conversation = svc.create(None, Uuid::new_v4()).await.unwrap();
+        for _ in 0..3 {
+            svc.add_message(conversation.id, prompt("borrow again", year))
+                .await
+                .unwrap();
+        }
+    }
+
+    let all = search(&svc, "borrow").await;
+    assert_eq!(all.len(), 5);
+
+    let page = svc
+        .search("borrow", &ConversationFilter::default(), Some(2), Some(1))
+        .await
+        .unwrap();
+    let page_ids: Vec<Uuid> = page.iter().map(|hit| hit.conversation_id).collect();
+    let expected: Vec<Uuid> = all[1..3].iter().map(|hit| hit.conversation_id).collect();
+    assert_eq!(page_ids, expected);
+
+    let last = svc
+        .search(
+            "role:user",
+            &ConversationFilter::default(),
+            Some(10),
+            Some(4),
+        )
+        .await
+        .unwrap();
+    assert_eq!(last.len(), 1);
+}
@@ -21,7 +21,9 @@ use tempfile::TempDir;
 use uuid::Uuid;
 
 use personal_agent::db::spawn_db_thread;
-use personal_agent::models::{ContextState, Message, MessageRole, SearchMatchType};
+use personal_agent::models::{
+    ContextState, ConversationFilter, Message, MessageRole, SearchMatchType,
+};
 use personal_agent::services::{ConversationService, ServiceError, SqliteConversationService};
 
 // ---------------------------------------------------------------------------
@@ -191,7 +193,7 @@ async fn sqlite_conversation_list_metadata_ordering_and_pagination() {
 
     // list_metadata(None, None) — all 3, most-recently-updated first
     let all = svc
-        .list_metadata(None, None)
+        .list_metadata(&ConversationFilter::default(), None, None)
         .await
         .expect("list_metadata failed");
     assert_eq!(all.len(), 3);
@@ -232,13 +234,19 @@ async fn sqlite_conversation_list_metadata_ordering_and_pagination() {
     );
 
     // Pagination: limit=2, offset=0 → first 2
-    let page1 = svc.list_metadata(Some(2), None).await.unwrap();
+    let page1 = svc
+        .list_metadata(&ConversationFilter::default(), Some(2), None)
+        .await
+        .unwrap();
     assert_eq!(page1.len(), 2);
     assert_eq!(page1[0].id, c3.id);
     assert_eq!(page1[1].id, c2.id);
 
     // Pagination: limit=2, offset=1 → 2nd and 3rd
-    let page2 = svc.list_metadata(Some(2), Some(1)).await.unwrap();
+    let page2 = svc
+        .list_metadata(&ConversationFilter::default(), Some(2), Some(1))
+        .await
+        .unwrap();
     assert_eq!(page2.len(), 2);
     assert_eq!(page2[0].id, c2.id);
     assert_eq!(page2[1].id, c1.id);
@@ -278,14 +286,17 @@ async fn sqlite_conversation_fts5_search() {
     .unwrap();
 
     // search("Rust") → returns Rust conversation with Title match
-    let results = svc.search("Rust", None, None).await.expect("search failed");
+    let results = svc
+        .search("Rust", &ConversationFilter::default(), None, None)
+        .await
+        .expect("search failed");
     assert_eq!(results.len(), 1, "search('Rust') should return 1 result");
     assert_eq!(results[0].conversation_id, rust_conv.id);
     assert_eq!(results[0].match_type, SearchMatchType::Title);
 
     // search("generators") → returns Python conversation with Content match
     let results = svc
-        .search("generators", None, None)
+        .search("generators", &ConversationFilter::default(), None, None)
         .await
         .expect("search failed");
     assert_eq!(
@@ -298,7 +309,12 @@ async fn sqlite_conversation_fts5_search() {
 
     // search("nonexistent_term_xyz") → empty
     let results = svc
-        .search("nonexistent_term_xyz", None, None)
+        .search(
+            "nonexistent_term_xyz",
+            &ConversationFilter::default(),
+            None,
+            None,
+        )
         .await
         .unwrap();
     assert!(
@@ -307,7 +323,10 @@ async fn sqlite_conversation_fts5_search() {
     );
 
     // search("") → empty (empty query optimization)
-    let results = svc.search("", None, None).await.unwrap();
+    let results = svc
+        .search("", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
     assert!(results.is_empty(), "empty search should return empty");
 }
 
@@ -507,7 +526,12 @@ async fn sqlite_conversation_fts_trigger_consistency() {
 
     // search("specific_content_marker") should return the conversation
     let results = svc
-        .search("specific_content_marker", None, None)
+        .search(
+            "specific_content_marker",
+            &ConversationFilter::default(),
+            None,
+            None,
+        )
         .await
         .unwrap();
     assert_eq!(
@@ -521,7 +545,10 @@ async fn sqlite_conversation_fts_trigger_consistency() {
     svc.rename(conv.id, "New Title".to_string()).await.unwrap();
 
     // search("New Title") should return the conversation (title FTS updated by trigger)
-    let results = svc.search("New Title", None, None).await.unwrap();
+    let results = svc
+        .search("New Title", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
     assert_eq!(
         results.len(),
         1,
@@ -535,7 +562,10 @@ async fn sqlite_conversation_fts_trigger_consistency() {
     // but the title itself is now "New Title". We verify the conversation is found
     // by new title and not by old title specifically.
     // Since no message content has "Original Title", searching for both words should be empty.
-    let results_old = svc.search("Original Title", None, None).await.unwrap();
+    let results_old = svc
+        .search("Original Title", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
     // Either 0 results (title updated correctly) or 1 result (if partial match on content)
     // The spec says trigger updates title in FTS, so exact title-based search should change.
     // If any results come back, they should NOT be a Title match for "Original Title".
@@ -556,7 +586,12 @@ async fn sqlite_conversation_fts_trigger_consistency() {
 
     // search("specific_content_marker") should return empty (FTS cleaned up by trigger)
     let results = svc
-        .search("specific_content_marker", None, None)
+        .search(
+            "specific_content_marker",
+            &ConversationFilter::default(),
+            None,
+            None,
+        )
         .await
         .unwrap();
     assert!(
@@ -565,7 +600,10 @@ async fn sqlite_conversation_fts_trigger_consistency() {
     );
 
     // search("New Title") should also be empty after delete
-    let results = svc.search("New Title", None, None).await.unwrap();
+    let results = svc
+        .search("New Title", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
     assert!(
         results.is_empty(),
         "search by title should be empty after conversation deleted"
@@ -661,7 +699,10 @@ async fn sqlite_conversation_startup_restore_via_metadata_ordering() {
         .unwrap();
 
     // list_metadata(Some(1), Some(0)) → single most recently updated
-    let recent = svc.list_metadata(Some(1), Some(0)).await.unwrap();
+    let recent = svc
+        .list_metadata(&ConversationFilter::default(), Some(1), Some(0))
+        .await
+        .unwrap();
     assert_eq!(recent.len(), 1);
     assert_eq!(recent[0].id, c3.id, "most recently updated should be c3");
 }
@@ -699,7 +740,10 @@ async fn sqlite_conversation_search_ranking_title_beats_content() {
     .await
     .unwrap();
 
-    let results = svc.search("ownership", None, None).await.unwrap();
+    let results = svc
+        .search("ownership", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
     assert_eq!(
         results.len(),
         2,
@@ -733,7 +777,12 @@ async fn sqlite_conversation_search_requires_messages() {
 
     // search should NOT return it (no FTS rows = no messages)
     let results = svc
-        .search("searchable_unique_term", None, None)
+        .search(
+            "searchable_unique_term",
+            &ConversationFilter::default(),
+            None,
+            None,
+        )
         .await
         .unwrap();
     assert!(
@@ -747,7 +796,12 @@ async fn sqlite_conversation_search_requires_messages() {
         .unwrap();
 
     let results = svc
-        .search("searchable_unique_term", None, None)
+        .search(
+            "searchable_unique_term",
+            &ConversationFilter::default(),
+            None,
+            None,
+        )
         .await
         .unwrap();
     assert_eq!(
@@ -803,7 +857,10 @@ async fn sqlite_conversation_zero_message_metadata() {
         .await
         .unwrap();
 
-    let all = svc.list_metadata(None, None).await.unwrap();
+    let all = svc
+        .list_metadata(&ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
     let meta = all.iter().find(|m| m.id == conv.id).expect("conv in list");
 
     assert_eq!(meta.message_count, 0);
@@ -0,0 +1,248 @@
+//! Integration tests for alternate message versions (`begin_message_version`,
+//! `switch_message_version`, `list_message_versions`) on
+//! `SqliteConversationService` against a real `SQLite` database.
+
+use std::sync::Arc;
+
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::spawn_db_thread;
+use personal_agent::models::{ConversationFilter, Message, MessageVersionInfo};
+use personal_agent::services::{ConversationService, ServiceError, SqliteConversationService};
+
+async fn make_service(dir: &TempDir) -> Arc<SqliteConversationService> {
+    let db_path = dir.path().join("test.db");
+    let handle = tokio::task::spawn_blocking(move || {
+        spawn_db_thread(&db_path).expect("spawn_db_thread failed")
+    })
+    .await
+    .expect("spawn_blocking failed");
+    Arc::new(SqliteConversationService::new(handle))
+}
+
+/// Create a conversation with four alternating user/assistant messages.
+async fn seeded_conversation(svc: &SqliteConversationService) -> Uuid {
+    let conv = svc
+        .create(Some("Trip plan".to_string()), Uuid::new_v4())
+        .await
+        .unwrap();
+    for (i, text) in ["where to?", "Lisbon", "when?", "May"].iter().enumerate() {
+        let message = if i % 2 == 0 {
+            Message::user((*text).to_string())
+        } else {
+            Message::assistant((*text).to_string())
+        };
+        svc.add_message(conv.id, message).await.unwrap();
+    }
+    conv.id
+}
+
+async fn contents(svc: &SqliteConversationService, id: Uuid) -> Vec<String> {
+    svc.get_messages(id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.content)
+        .collect()
+}
+
+#[tokio::test]
+async fn begin_archives_tail_and_next_message_becomes_new_version() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    svc.begin_message_version(conv, 3).await.unwrap();
+    assert_eq!(contents(&svc, conv).await, ["where to?", "Lisbon", "when?"]);
+
+    let versions = svc.list_message_versions(conv).await.unwrap();
+    assert_eq!(
+        versions,
+        [MessageVersionInfo {
+            seq: 3,
+            selected: None,
+            count: 1,
+        }]
+    );
+
+    svc.add_message(conv, Message::assistant("June".to_string()))
+        .await
+        .unwrap();
+    let versions = svc.list_message_versions(conv).await.unwrap();
+    assert_eq!(
+        versions,
+        [MessageVersionInfo {
+            seq: 3,
+            selected: Some(1),
+            count: 2,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn switch_round_trips_between_versions() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    svc.begin_message_version(conv, 3).await.unwrap();
+    svc.add_message(conv, Message::assistant("June".to_string()))
+        .await
+        .unwrap();
+
+    svc.switch_message_version(conv, 3, 0).await.unwrap();
+    assert_eq!(contents(&svc, conv).await.last().unwrap(), "May");
+    assert_eq!(
+        svc.list_message_versions(conv).await.unwrap()[0].selected,
+        Some(0)
+    );
+
+    svc.switch_message_version(conv, 3, 1).await.unwrap();
+    assert_eq!(contents(&svc, conv).await.last().unwrap(), "June");
+
+    // Switching to the live version is a no-op.
+    svc.switch_message_version(conv, 3, 1).await.unwrap();
+    assert_eq!(contents(&svc, conv).await.len(), 4);
+}
+
+#[tokio::test]
+async fn edited_prompt_keeps_original_tail_as_version() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    svc.begin_message_version(conv, 2).await.unwrap();
+    svc.add_message(conv, Message::user("how long?".to_string()))
+        .await
+        .unwrap();
+    svc.add_message(conv, Message::assistant("A week".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(
+        contents(&svc, conv).await,
+        ["where to?", "Lisbon", "how long?", "A week"]
+    );
+
+    svc.switch_message_version(conv, 2, 0).await.unwrap();
+    assert_eq!(
+        contents(&svc, conv).await,
+        ["where to?", "Lisbon", "when?", "May"]
+    );
+}
+
+#[tokio::test]
+async fn nested_versions_survive_outer_switch() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    // Inner alternate at seq 3, then an outer edit at seq 2.
+    svc.begin_message_version(conv, 3).await.unwrap();
+    svc.add_message(conv, Message::assistant("June".to_string()))
+        .await
+        .unwrap();
+    svc.begin_message_version(conv, 2).await.unwrap();
+    svc.add_message(conv, Message::user("budget?".to_string()))
+        .await
+        .unwrap();
+
+    // The inner alternate belongs to the archived outer version.
+    let versions = svc.list_message_versions(conv).await.unwrap();
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].seq, 2);
+
+    svc.switch_message_version(conv, 2, 0).await.unwrap();
+    assert_eq!(contents(&svc, conv).await.last().unwrap(), "June");
+    let versions = svc.list_message_versions(conv).await.unwrap();
+    assert_eq!(
+        versions,
+        [
+            MessageVersionInfo {
+                seq: 2,
+                selected: Some(0),
+                count: 2,
+            },
+            MessageVersionInfo {
+                seq: 3,
+                selected: Some(1),
+                count: 2,
+            },
+        ]
+    );
+
+    svc.switch_message_version(conv, 3, 0).await.unwrap();
+    assert_eq!(contents(&svc, conv).await.last().unwrap(), "May");
+}
+
+#[tokio::test]
+async fn missing_seq_or_version_is_not_found() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    let err = svc.begin_message_version(conv, 10).await.unwrap_err();
+    assert!(matches!(err, ServiceError::NotFound(_)), "got {err:?}");
+
+    let err = svc.switch_message_version(conv, 3, 5).await.unwrap_err();
+    assert!(matches!(err, ServiceError::NotFound(_)), "got {err:?}");
+
+    assert_eq!(contents(&svc, conv).await.len(), 4);
+    assert!(svc.list_message_versions(conv).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn get_message_reads_the_live_row_at_seq() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    svc.begin_message_version(conv, 1).await.unwrap();
+    svc.add_message(conv, Message::assistant("Porto".to_string()))
+        .await
+        .unwrap();
+
+    let message = svc.get_message(conv, 1).await.unwrap().unwrap();
+    assert_eq!(message.content, "Porto");
+    assert!(svc.get_message(conv, 2).await.unwrap().is_none());
+    assert!(svc.get_message(Uuid::new_v4(), 0).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn archived_versions_are_searchable() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    svc.begin_message_version(conv, 1).await.unwrap();
+    svc.add_message(conv, Message::assistant("Porto".to_string()))
+        .await
+        .unwrap();
+
+    for term in ["Lisbon", "Porto"] {
+        let results = svc
+            .search(term, &ConversationFilter::default(), None, None)
+            .await
+            .unwrap();
+        assert!(
+            results.iter().any(|r| r.conversation_id == conv),
+            "expected {term} to match"
+        );
+    }
+}
+
+#[tokio::test]
+async fn deleting_conversation_removes_versions() {
+    let dir = TempDir::new().unwrap();
+    let svc = make_service(&dir).await;
+    let conv = seeded_conversation(&svc).await;
+
+    svc.begin_message_version(conv, 1).await.unwrap();
+    svc.delete(conv).await.unwrap();
+
+    let results = svc
+        .search("Lisbon", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    assert!(results.is_empty(), "archived rows must leave the index");
+}
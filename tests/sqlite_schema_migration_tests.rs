@@ -0,0 +1,353 @@
+//! Schema migration tests: databases written by older releases (checked-in
+//! fixtures under `tests/fixtures/schema/`) are upgraded to the latest schema
+//! on open with their data intact, newer databases are refused, and
+//! `migrate_with_backup` snapshots the old file before touching it.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::backup::{
+    migrate_with_backup, BackupInfo, BackupResult, DatabaseBackupSettings, RestoreResult,
+};
+use personal_agent::db::migrations::LATEST_VERSION;
+use personal_agent::db::{spawn_db_thread, spawn_db_thread_unmigrated, DbHandle};
+use personal_agent::models::{ConversationFilter, Message, TodoItem, TodoStatus, ToolAuditFilter};
+use personal_agent::services::app_settings_impl::AppSettingsServiceImpl;
+use personal_agent::services::{
+    AppSettingsService, BackupService, BackupServiceImpl, ConversationService, ServiceResult,
+    SqliteConversationService, SqliteTodoService, SqliteToolAuditService, SqliteUsageService,
+    TodoService, ToolAuditService, UsageService,
+};
+
+const TRIP_PLAN: &str = "6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a01";
+const TRIP_BRANCH: &str = "6f1c2a52-5a0e-4f57-9a5e-0d6f2b7c1a02";
+
+fn fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/schema")
+        .join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {e}", path.display()))
+}
+
+/// Write `sql` into a fresh database file under `dir`.
+fn database_from_sql(dir: &TempDir, sql: &str) -> PathBuf {
+    let db_path = dir.path().join("test.db");
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    conn.execute_batch(sql).unwrap();
+    db_path
+}
+
+fn schema_version_of(db_path: &Path) -> u32 {
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap()
+}
+
+async fn open(db_path: PathBuf, migrate: bool) -> Result<DbHandle, String> {
+    tokio::task::spawn_blocking(move || {
+        if migrate {
+            spawn_db_thread(&db_path)
+        } else {
+            spawn_db_thread_unmigrated(&db_path)
+        }
+    })
+    .await
+    .expect("spawn_blocking failed")
+    .map_err(|e| e.to_string())
+}
+
+async fn contents(svc: &SqliteConversationService, id: Uuid) -> Vec<String> {
+    svc.get_messages(id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.content)
+        .collect()
+}
+
+async fn assert_trip_plan_usable(svc: &SqliteConversationService) {
+    let id = Uuid::parse_str(TRIP_PLAN).unwrap();
+    assert_eq!(
+        contents(svc, id).await,
+        ["where to?", "Lisbon", "when?", "May"]
+    );
+
+    let results = svc
+        .search("Lisbon", &ConversationFilter::default(), None, None)
+        .await
+        .unwrap();
+    assert!(results.iter().any(|r| r.conversation_id == id));
+
+    // Features added by later migrations work on upgraded data.
+    let branch = svc.fork_at(id, 1).await.unwrap();
+    assert_eq!(contents(svc, branch.id).await, ["where to?", "Lisbon"]);
+    svc.begin_message_version(id, 3).await.unwrap();
+    svc.add_message(id, Message::assistant("June".to_string()))
+        .await
+        .unwrap();
+    svc.switch_message_version(id, 3, 0).await.unwrap();
+    assert_eq!(contents(svc, id).await.last().unwrap(), "May");
+}
+
+#[tokio::test]
+async fn fresh_database_is_created_at_latest_version() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("test.db");
+    let handle = open(db_path.clone(), true).await.unwrap();
+    drop(handle);
+
+    assert_eq!(schema_version_of(&db_path), LATEST_VERSION);
+}
+
+#[tokio::test]
+async fn v1_database_is_upgraded_with_data_intact() {
+    let dir = TempDir::new().unwrap();
+    let db_path = database_from_sql(&dir, &fixture("v1.sql"));
+
+    let svc = SqliteConversationService::new(open(db_path.clone(), true).await.unwrap());
+    assert_eq!(
+        svc.list_metadata(&ConversationFilter::default(), None, None)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_trip_plan_usable(&svc).await;
+
+    assert_eq!(schema_version_of(&db_path), LATEST_VERSION);
+}
+
+#[tokio::test]
+async fn v2_database_keeps_branch_lineage() {
+    let dir = TempDir::new().unwrap();
+    let db_path = database_from_sql(&dir, &fixture("v2.sql"));
+
+    let svc = SqliteConversationService::new(open(db_path.clone(), true).await.unwrap());
+    let branches = svc
+        .list_branches(Uuid::parse_str(TRIP_PLAN).unwrap())
+        .await
+        .unwrap();
+    let branch = branches
+        .iter()
+        .find(|b| b.id == Uuid::parse_str(TRIP_BRANCH).unwrap())
+        .expect("fixture branch listed");
+    assert_eq!(branch.parent_id, Some(Uuid::parse_str(TRIP_PLAN).unwrap()));
+    assert_eq!(branch.parent_seq, Some(1));
+    assert_trip_plan_usable(&svc).await;
+
+    assert_eq!(schema_version_of(&db_path), LATEST_VERSION);
+}
+
+/// Check the rows each fixture adds for the features of its version.
+async fn assert_version_data_intact(db: &DbHandle, version: u32) {
+    let trip_plan = Uuid::parse_str(TRIP_PLAN).unwrap();
+    let svc = SqliteConversationService::new(db.clone());
+    if version >= 3 {
+        let versions = svc
+            .list_message_versions(Uuid::parse_str(TRIP_BRANCH).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!((versions[0].seq, versions[0].count), (1, 2));
+    }
+    if version >= 4 {
+        assert_eq!(svc.list_tags().await.unwrap(), ["lisbon"]);
+        assert_eq!(svc.list_folders().await.unwrap(), ["Travel"]);
+    }
+    if version >= 5 {
+        let totals = SqliteUsageService::new(db.clone())
+            .conversation_totals(trip_plan)
+            .await
+            .unwrap();
+        assert_eq!((totals.input_tokens, totals.output_tokens), (1200, 300));
+    }
+    if version >= 6 {
+        let imported: String = db
+            .execute(|conn| {
+                conn.query_row(
+                    "SELECT conversation_id FROM conversation_imports \
+                     WHERE source = 'chatgpt' AND external_id = 'trip-plan'",
+                    [],
+                    |row| row.get(0),
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(imported, TRIP_PLAN);
+    }
+    if version >= 7 {
+        assert_eq!(
+            SqliteTodoService::new(db.clone())
+                .list(trip_plan)
+                .await
+                .unwrap(),
+            [
+                TodoItem::new("Book flights", TodoStatus::Completed),
+                TodoItem::new("Find a hotel", TodoStatus::InProgress),
+            ]
+        );
+    }
+    if version >= 8 {
+        let entries = SqliteToolAuditService::new(db.clone())
+            .list(ToolAuditFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tool_name, "WebFetch");
+        assert_eq!(entries[0].conversation_id, Some(trip_plan));
+    }
+}
+
+#[tokio::test]
+async fn every_schema_version_has_a_fixture_that_upgrades() {
+    for version in 1..=LATEST_VERSION {
+        let dir = TempDir::new().unwrap();
+        let db_path = database_from_sql(&dir, &fixture(&format!("v{version}.sql")));
+        assert_eq!(schema_version_of(&db_path), version, "v{version}.sql");
+
+        let db = open(db_path.clone(), true).await.unwrap();
+        assert_version_data_intact(&db, version).await;
+        assert_trip_plan_usable(&SqliteConversationService::new(db)).await;
+
+        assert_eq!(schema_version_of(&db_path), LATEST_VERSION);
+    }
+}
+
+#[tokio::test]
+async fn database_from_newer_build_is_refused() {
+    let dir = TempDir::new().unwrap();
+    let db_path = database_from_sql(
+        &dir,
+        &format!("PRAGMA user_version = {};", LATEST_VERSION + 1),
+    );
+
+    for migrate in [true, false] {
+        let err = open(db_path.clone(), migrate).await.unwrap_err();
+        assert!(err.contains("newer than this build"), "{err}");
+    }
+    assert_eq!(schema_version_of(&db_path), LATEST_VERSION + 1);
+}
+
+// ---------------------------------------------------------------------------
+// migrate_with_backup
+// ---------------------------------------------------------------------------
+
+async fn real_backup_service(
+    dir: &TempDir,
+    db: DbHandle,
+    db_path: PathBuf,
+) -> (Arc<dyn BackupService>, PathBuf) {
+    let backup_dir = dir.path().join("backups");
+    std::fs::create_dir_all(&backup_dir).unwrap();
+    let app_settings = Arc::new(
+        AppSettingsServiceImpl::new(dir.path().join("settings.json"))
+            .expect("create app settings service"),
+    );
+    let settings = DatabaseBackupSettings {
+        backup_directory: Some(backup_dir.clone()),
+        // Pre-migration backups must not depend on scheduled backups.
+        enabled: false,
+        ..Default::default()
+    };
+    app_settings
+        .set_setting("backup_settings", serde_json::to_string(&settings).unwrap())
+        .await
+        .unwrap();
+    let service = Arc::new(BackupServiceImpl::new(db, app_settings, db_path));
+    (service, backup_dir)
+}
+
+fn backup_files(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(".db.gz"))
+        .collect()
+}
+
+#[tokio::test]
+async fn upgrade_takes_a_backup_first() {
+    let dir = TempDir::new().unwrap();
+    let db_path = database_from_sql(&dir, &fixture("v1.sql"));
+    let db = open(db_path.clone(), false).await.unwrap();
+    let (backup_service, backup_dir) = real_backup_service(&dir, db.clone(), db_path).await;
+
+    let version = migrate_with_backup(&db, backup_service.as_ref())
+        .await
+        .unwrap();
+
+    assert_eq!(version, LATEST_VERSION);
+    assert_eq!(backup_files(&backup_dir).len(), 1);
+    assert_trip_plan_usable(&SqliteConversationService::new(db)).await;
+}
+
+#[tokio::test]
+async fn fresh_database_needs_no_backup() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("test.db");
+    let db = open(db_path.clone(), false).await.unwrap();
+    let (backup_service, backup_dir) = real_backup_service(&dir, db.clone(), db_path).await;
+
+    let version = migrate_with_backup(&db, backup_service.as_ref())
+        .await
+        .unwrap();
+
+    assert_eq!(version, LATEST_VERSION);
+    assert!(backup_files(&backup_dir).is_empty());
+}
+
+/// Backup service whose pre-migration backup never completes.
+struct SkippingBackupService;
+
+#[async_trait]
+impl BackupService for SkippingBackupService {
+    async fn create_backup(&self) -> ServiceResult<BackupResult> {
+        unimplemented!()
+    }
+    async fn create_pre_migration_backup(
+        &self,
+        _from_version: u32,
+        _to_version: u32,
+    ) -> ServiceResult<BackupResult> {
+        Ok(BackupResult::Skipped {
+            reason: "disk full".to_string(),
+        })
+    }
+    async fn list_backups(&self) -> ServiceResult<Vec<BackupInfo>> {
+        unimplemented!()
+    }
+    async fn restore_backup(&self, _backup_path: &Path) -> ServiceResult<RestoreResult> {
+        unimplemented!()
+    }
+    async fn get_settings(&self) -> ServiceResult<DatabaseBackupSettings> {
+        unimplemented!()
+    }
+    async fn update_settings(&self, _settings: DatabaseBackupSettings) -> ServiceResult<()> {
+        unimplemented!()
+    }
+    async fn get_last_backup_time(&self) -> ServiceResult<Option<chrono::DateTime<chrono::Utc>>> {
+        unimplemented!()
+    }
+    async fn should_backup(&self) -> ServiceResult<bool> {
+        unimplemented!()
+    }
+}
+
+#[tokio::test]
+async fn failed_backup_leaves_database_unmigrated() {
+    let dir = TempDir::new().unwrap();
+    let db_path = database_from_sql(&dir, &fixture("v1.sql"));
+    let db = open(db_path.clone(), false).await.unwrap();
+
+    let err = migrate_with_backup(&db, &SkippingBackupService)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("refusing to migrate"), "{err}");
+
+    drop(db);
+    assert_eq!(schema_version_of(&db_path), 1);
+}
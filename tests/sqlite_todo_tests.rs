@@ -0,0 +1,78 @@
+//! Integration tests for `SqliteTodoService`: replacing and reading a
+//! conversation's task list.
+
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::{spawn_db_thread, DbHandle};
+use personal_agent::models::{TodoItem, TodoStatus};
+use personal_agent::services::{
+    ConversationService, SqliteConversationService, SqliteTodoService, TodoService,
+};
+
+async fn open_db(dir: &TempDir) -> DbHandle {
+    let db_path = dir.path().join("test.db");
+    tokio::task::spawn_blocking(move || spawn_db_thread(&db_path).expect("spawn_db_thread failed"))
+        .await
+        .expect("spawn_blocking failed")
+}
+
+#[tokio::test]
+async fn replace_rewrites_the_list_in_order() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let todos = SqliteTodoService::new(db);
+    let conversation = conversations.create(None, Uuid::new_v4()).await.unwrap();
+
+    assert!(todos.list(conversation.id).await.unwrap().is_empty());
+
+    todos
+        .replace(
+            conversation.id,
+            vec![
+                TodoItem::new("Read the parser", TodoStatus::InProgress),
+                TodoItem::new("Fix the bug", TodoStatus::Pending),
+                TodoItem::new("Add a test", TodoStatus::Pending),
+            ],
+        )
+        .await
+        .unwrap();
+    let updated = vec![
+        TodoItem::new("Read the parser", TodoStatus::Completed),
+        TodoItem::new("Fix the bug", TodoStatus::InProgress),
+    ];
+    todos
+        .replace(conversation.id, updated.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(todos.list(conversation.id).await.unwrap(), updated);
+
+    todos.replace(conversation.id, Vec::new()).await.unwrap();
+    assert!(todos.list(conversation.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn deleting_a_conversation_removes_its_list() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let todos = SqliteTodoService::new(db);
+    let kept = conversations.create(None, Uuid::new_v4()).await.unwrap();
+    let deleted = conversations.create(None, Uuid::new_v4()).await.unwrap();
+
+    for conversation_id in [kept.id, deleted.id] {
+        todos
+            .replace(
+                conversation_id,
+                vec![TodoItem::new("Plan the change", TodoStatus::Pending)],
+            )
+            .await
+            .unwrap();
+    }
+    conversations.delete(deleted.id).await.unwrap();
+
+    assert!(todos.list(deleted.id).await.unwrap().is_empty());
+    assert_eq!(todos.list(kept.id).await.unwrap().len(), 1);
+}
@@ -0,0 +1,145 @@
+//! Integration tests for `SqliteToolAuditService`: recording tool calls and
+//! listing them through the audit filter.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::{spawn_db_thread, DbHandle};
+use personal_agent::models::{ApprovalSource, AuditOutcome, ToolAuditEntry, ToolAuditFilter};
+use personal_agent::services::{
+    ConversationService, SqliteConversationService, SqliteToolAuditService, ToolAuditService,
+};
+
+async fn open_db(dir: &TempDir) -> DbHandle {
+    let db_path = dir.path().join("test.db");
+    tokio::task::spawn_blocking(move || spawn_db_thread(&db_path).expect("spawn_db_thread failed"))
+        .await
+        .expect("spawn_blocking failed")
+}
+
+fn base_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 4, 2, 8, 0, 0).unwrap()
+}
+
+fn entry(
+    conversation_id: Option<Uuid>,
+    tool_name: &str,
+    arguments: &str,
+    outcome: AuditOutcome,
+    minutes: i64,
+) -> ToolAuditEntry {
+    ToolAuditEntry {
+        conversation_id,
+        tool_name: tool_name.to_string(),
+        arguments: arguments.to_string(),
+        approval_source: ApprovalSource::Policy,
+        approval_detail: None,
+        outcome,
+        exit_code: None,
+        output: "done".to_string(),
+        started_at: base_time() + Duration::minutes(minutes),
+        duration_ms: 12,
+    }
+}
+
+#[tokio::test]
+async fn records_are_listed_newest_first_and_filtered() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let audit = SqliteToolAuditService::new(db);
+    let conversation = conversations.create(None, Uuid::new_v4()).await.unwrap();
+
+    let shell = ToolAuditEntry {
+        approval_source: ApprovalSource::Rule,
+        approval_detail: Some("allow tool=ShellExec command=^git".to_string()),
+        exit_code: Some(1),
+        ..entry(
+            Some(conversation.id),
+            "ShellExec",
+            r#"{"command":"git status"}"#,
+            AuditOutcome::Failed,
+            0,
+        )
+    };
+    let read = entry(
+        Some(conversation.id),
+        "ReadFile",
+        r#"{"path":"README.md"}"#,
+        AuditOutcome::Succeeded,
+        1,
+    );
+    let denied = entry(
+        None,
+        "WriteFile",
+        r#"{"path":"/etc/hosts"}"#,
+        AuditOutcome::Denied,
+        2,
+    );
+    for record in [&shell, &read, &denied] {
+        audit.record(record.clone()).await.unwrap();
+    }
+
+    let all = audit.list(ToolAuditFilter::default()).await.unwrap();
+    assert_eq!(all, vec![denied.clone(), read.clone(), shell.clone()]);
+
+    let by_text = ToolAuditFilter {
+        search: Some("GIT".to_string()),
+        ..ToolAuditFilter::default()
+    };
+    assert_eq!(audit.list(by_text).await.unwrap(), vec![shell.clone()]);
+
+    let by_conversation = ToolAuditFilter {
+        search: Some(conversation.id.to_string()),
+        ..ToolAuditFilter::default()
+    };
+    assert_eq!(audit.list(by_conversation).await.unwrap().len(), 2);
+
+    let by_outcome = ToolAuditFilter {
+        outcome: Some(AuditOutcome::Denied),
+        ..ToolAuditFilter::default()
+    };
+    assert_eq!(audit.list(by_outcome).await.unwrap(), vec![denied]);
+
+    let limited = ToolAuditFilter {
+        limit: Some(1),
+        ..ToolAuditFilter::default()
+    };
+    assert_eq!(audit.list(limited).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn records_outlive_their_conversation() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let audit = SqliteToolAuditService::new(db);
+    let conversation = conversations.create(None, Uuid::new_v4()).await.unwrap();
+
+    audit
+        .record(entry(
+            Some(conversation.id),
+            "ShellExec",
+            "{}",
+            AuditOutcome::Succeeded,
+            0,
+        ))
+        .await
+        .unwrap();
+    audit
+        .record(entry(
+            Some(Uuid::new_v4()),
+            "ReadFile",
+            "{}",
+            AuditOutcome::Succeeded,
+            1,
+        ))
+        .await
+        .unwrap();
+    conversations.delete(conversation.id).await.unwrap();
+
+    let listed = audit.list(ToolAuditFilter::default()).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    assert!(listed.iter().all(|record| record.conversation_id.is_none()));
+}
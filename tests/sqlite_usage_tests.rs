@@ -0,0 +1,138 @@
+//! Integration tests for `SqliteUsageService`: recording turns and summing
+//! them per conversation, profile, and day.
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use personal_agent::db::{spawn_db_thread, DbHandle};
+use personal_agent::models::{TokenUsage, UsageRecord};
+use personal_agent::services::{
+    ConversationService, SqliteConversationService, SqliteUsageService, UsageService,
+};
+
+async fn open_db(dir: &TempDir) -> DbHandle {
+    let db_path = dir.path().join("test.db");
+    tokio::task::spawn_blocking(move || spawn_db_thread(&db_path).expect("spawn_db_thread failed"))
+        .await
+        .expect("spawn_blocking failed")
+}
+
+fn turn(
+    conversation_id: Uuid,
+    profile_id: Uuid,
+    day: u32,
+    input_tokens: u32,
+    cost_usd: Option<f64>,
+) -> UsageRecord {
+    UsageRecord {
+        conversation_id: Some(conversation_id),
+        profile_id,
+        provider_id: "anthropic".to_string(),
+        model_id: "claude-sonnet-4".to_string(),
+        usage: TokenUsage {
+            input_tokens,
+            output_tokens: 10,
+            cache_read_tokens: 5,
+            cache_write_tokens: 0,
+        },
+        cost_usd,
+        created_at: Utc.with_ymd_and_hms(2025, 6, day, 23, 30, 0).unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn conversation_totals_sum_recorded_turns() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let usage = SqliteUsageService::new(db);
+    let profile = Uuid::new_v4();
+    let conversation = conversations.create(None, profile).await.unwrap();
+
+    let empty = usage.conversation_totals(conversation.id).await.unwrap();
+    assert_eq!(empty.turns, 0);
+    assert_eq!(empty.total_tokens(), 0);
+
+    usage
+        .record(turn(conversation.id, profile, 1, 100, Some(0.25)))
+        .await
+        .unwrap();
+    usage
+        .record(turn(conversation.id, profile, 2, 50, None))
+        .await
+        .unwrap();
+
+    let totals = usage.conversation_totals(conversation.id).await.unwrap();
+    assert_eq!(totals.input_tokens, 150);
+    assert_eq!(totals.output_tokens, 20);
+    assert_eq!(totals.cache_read_tokens, 10);
+    assert_eq!(totals.turns, 2);
+    assert_eq!(totals.unpriced_turns, 1);
+    assert!((totals.cost_usd - 0.25).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn profile_and_daily_totals_group_turns() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let usage = SqliteUsageService::new(db);
+    let cheap = Uuid::new_v4();
+    let pricey = Uuid::new_v4();
+    let first = conversations.create(None, cheap).await.unwrap();
+    let second = conversations.create(None, pricey).await.unwrap();
+
+    for record in [
+        turn(first.id, cheap, 1, 100, Some(0.01)),
+        turn(first.id, cheap, 3, 100, Some(0.01)),
+        turn(second.id, pricey, 3, 100, Some(1.0)),
+    ] {
+        usage.record(record).await.unwrap();
+    }
+
+    let by_profile = usage.profile_totals().await.unwrap();
+    let profiles: Vec<Uuid> = by_profile.iter().map(|entry| entry.profile_id).collect();
+    assert_eq!(profiles, [pricey, cheap]);
+    assert_eq!(by_profile[1].totals.turns, 2);
+
+    let since = NaiveDate::from_ymd_opt(2025, 6, 2).unwrap();
+    let by_day = usage.daily_totals(since).await.unwrap();
+    assert_eq!(by_day.len(), 1);
+    assert_eq!(by_day[0].date, NaiveDate::from_ymd_opt(2025, 6, 3).unwrap());
+    assert_eq!(by_day[0].totals.turns, 2);
+    assert!((by_day[0].totals.cost_usd - 1.01).abs() < 1e-9);
+
+    let all_days = usage
+        .daily_totals(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .await
+        .unwrap();
+    let days: Vec<u32> = all_days
+        .iter()
+        .map(|entry| chrono::Datelike::day(&entry.date))
+        .collect();
+    assert_eq!(days, [3, 1]);
+}
+
+#[tokio::test]
+async fn spend_survives_conversation_deletion() {
+    let dir = TempDir::new().unwrap();
+    let db = open_db(&dir).await;
+    let conversations = SqliteConversationService::new(db.clone());
+    let usage = SqliteUsageService::new(db);
+    let profile = Uuid::new_v4();
+    let conversation = conversations.create(None, profile).await.unwrap();
+    usage
+        .record(turn(conversation.id, profile, 1, 100, Some(0.5)))
+        .await
+        .unwrap();
+
+    conversations.delete(conversation.id).await.unwrap();
+
+    let totals = usage.conversation_totals(conversation.id).await.unwrap();
+    assert_eq!(totals.turns, 0);
+    let by_profile = usage.profile_totals().await.unwrap();
+    assert_eq!(by_profile.len(), 1);
+    assert_eq!(by_profile[0].profile_id, profile);
+    assert!((by_profile[0].totals.cost_usd - 0.5).abs() < 1e-9);
+}